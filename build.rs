@@ -24,8 +24,12 @@ fn main() -> anyhow::Result<()> {
         writeln!(out, "#[cfg(test)]")?;
         writeln!(out, "#[allow(non_snake_case)]")?;
         if *strategy == "Winch" {
-            // We only test Winch on x86_64, for now.
-            writeln!(out, "{}", "#[cfg(all(target_arch = \"x86_64\"))]")?;
+            // We only test Winch on x86_64 and aarch64, for now.
+            writeln!(
+                out,
+                "{}",
+                "#[cfg(any(target_arch = \"x86_64\", target_arch = \"aarch64\"))]"
+            )?;
         }
         writeln!(out, "mod {} {{", strategy)?;
 
@@ -342,6 +342,9 @@ fn run_compilation(compilation: &IsleCompilation) -> Result<(), Errors> {
         // include!()s it. (See
         // https://github.com/rust-lang/rust/issues/47995.)
         options.exclude_global_allow_pragmas = true;
+        // Lowering records which rules produced each machine instruction
+        // when the `machine_code_isle_rule_info` setting is enabled.
+        options.rule_tracing = cfg!(feature = "isle-rule-info");
 
         isle::compile::from_files(file_paths, &options)?
     };
@@ -462,6 +462,110 @@ fn gen_instruction_data_impl(formats: &[Rc<InstructionFormat>], fmt: &mut Format
             fmt.line("}");
         });
         fmt.line("}");
+
+        fmt.empty_line();
+
+        fmt.doc_comment(r#"
+            Copy this `InstructionData` into another function, rewriting
+            every value, block and entity reference through `mapper`.
+
+            Any lists referenced by this instruction live in `src_pool`; the
+            lists of the returned instruction are allocated in `dst_pool`.
+        "#);
+        fmt.line("pub fn map<M: ir::instructions::InstructionMapper>(&self, src_pool: &ir::ValueListPool, dst_pool: &mut ir::ValueListPool, mapper: &mut M) -> Self {");
+        fmt.indent(|fmt| {
+            fmt.line("match *self {");
+            fmt.indent(|fmt| {
+                for format in formats {
+                    let name = format!("Self::{}", format.name);
+                    let mut members = vec!["opcode"];
+
+                    if format.has_value_list {
+                        members.push("ref args");
+                    } else if format.num_value_operands == 1 {
+                        members.push("arg");
+                    } else if format.num_value_operands > 0 {
+                        members.push("args");
+                    }
+
+                    match format.num_block_operands {
+                        0 => {}
+                        1 => {
+                            members.push("destination");
+                        }
+                        _ => {
+                            members.push("blocks");
+                        }
+                    };
+
+                    for field in &format.imm_fields {
+                        members.push(field.member);
+                    }
+                    let members = members.join(", ");
+
+                    fmtln!(fmt, "{}{{{}}} => {{", name, members ); // beware the moustaches
+                    fmt.indent(|fmt| {
+                        fmtln!(fmt, "Self::{} {{", format.name);
+                        fmt.indent(|fmt| {
+                            fmtln!(fmt, "opcode,");
+
+                            if format.has_value_list {
+                                fmtln!(fmt, "args: {");
+                                fmt.indent(|fmt| {
+                                    fmtln!(fmt, "let mut list = ir::ValueList::default();");
+                                    fmtln!(fmt, "for arg in args.as_slice(src_pool) {");
+                                    fmt.indent(|fmt| {
+                                        fmtln!(fmt, "let arg = mapper.map_value(*arg);");
+                                        fmtln!(fmt, "list.push(arg, dst_pool);");
+                                    });
+                                    fmtln!(fmt, "}");
+                                    fmtln!(fmt, "list");
+                                });
+                                fmtln!(fmt, "},");
+                            } else if format.num_value_operands == 1 {
+                                fmtln!(fmt, "arg: mapper.map_value(arg),");
+                            } else if format.num_value_operands > 0 {
+                                fmtln!(fmt, "args: args.map(|arg| mapper.map_value(arg)),");
+                            }
+
+                            match format.num_block_operands {
+                                0 => {}
+                                1 => {
+                                    fmtln!(fmt, "destination: mapper.map_block_call(destination, src_pool, dst_pool),");
+                                }
+                                2 => {
+                                    fmtln!(fmt, "blocks: [mapper.map_block_call(blocks[0], src_pool, dst_pool), mapper.map_block_call(blocks[1], src_pool, dst_pool)],");
+                                }
+                                _ => panic!("Too many block targets in instruction"),
+                            }
+
+                            for field in &format.imm_fields {
+                                let method = match field.kind.rust_type {
+                                    "ir::FuncRef" => Some("map_func_ref"),
+                                    "ir::SigRef" => Some("map_sig_ref"),
+                                    "ir::GlobalValue" => Some("map_global_value"),
+                                    "ir::JumpTable" => Some("map_jump_table"),
+                                    "ir::StackSlot" => Some("map_stack_slot"),
+                                    "ir::DynamicStackSlot" => Some("map_dynamic_stack_slot"),
+                                    "ir::Table" => Some("map_table"),
+                                    "ir::Constant" => Some("map_constant"),
+                                    "ir::Immediate" => Some("map_immediate"),
+                                    _ => None,
+                                };
+                                match method {
+                                    Some(method) => fmtln!(fmt, "{}: mapper.{}({}),", field.member, method, field.member),
+                                    None => fmtln!(fmt, "{},", field.member),
+                                }
+                            }
+                        });
+                        fmtln!(fmt, "}");
+                    });
+                    fmtln!(fmt, "}");
+                }
+            });
+            fmt.line("}");
+        });
+        fmt.line("}");
     });
     fmt.line("}");
 }
@@ -77,6 +77,20 @@ pub(crate) fn define() -> SettingGroup {
         false,
     );
 
+    settings.add_bool(
+        "enable_bounds_check_elimination",
+        "Remove heap bounds checks that facts prove redundant.",
+        r#"
+            This uses the proof-carrying-code facts attached to bounds-check comparisons,
+            together with a simple range analysis of their operands, to remove `trapnz`
+            heap-out-of-bounds checks that can never fire or that are implied by a dominating
+            check, and Spectre guards that can never select their out-of-bounds arm. Spectre
+            guards are kept when `enable_pcc` is set so that the memory accesses behind them
+            can still be verified.
+        "#,
+        false,
+    );
+
     // Note that Cranelift doesn't currently need an is_pie flag, because PIE is
     // just PIC where symbols can't be pre-empted, which can be expressed with the
     // `colocated` flag on external functions and global values.
@@ -235,6 +249,21 @@ pub(crate) fn define() -> SettingGroup {
         false,
     );
 
+    settings.add_bool(
+        "machine_code_isle_rule_info",
+        "Generate metadata naming the ISLE rules that produced machine code.",
+        r#"
+            This increases compile time, but allows for the embedder to
+            attribute ranges of the generated machine code to the ISLE
+            lowering rules that produced them. This is useful for, e.g.,
+            compiler explorers and diagnosing instruction selection.
+
+            No info is generated unless Cranelift is built with the
+            `isle-rule-info` cargo feature.
+        "#,
+        false,
+    );
+
     // Stack probing options.
 
     settings.add_bool(
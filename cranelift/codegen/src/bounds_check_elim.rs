@@ -0,0 +1,522 @@
+//! Bounds-check elimination.
+//!
+//! Frontends guard memory accesses with comparisons that either feed a
+//! `trapnz` or a `select_spectre_guard`. This pass removes the ones it can
+//! prove never fire:
+//!
+//! - a check whose comparison is statically false, given what we know about
+//!   the ranges of its operands (from `Range` facts, global-value facts, and a
+//!   small structural analysis of constants, zero-extensions and masks), and
+//! - a `trapnz` whose comparison is implied by a dominating `trapnz` that
+//!   compares the same symbolic bases; once the dominating check has not
+//!   trapped, the later one cannot trap either.
+//!
+//! Comparisons are understood through the `Compare` facts that frontends
+//! attach to them, or failing that by looking at the `icmp` itself. Global
+//! values are treated as function-invariant symbols, exactly as in
+//! proof-carrying code; heap bounds may grow during a call but never shrink,
+//! so knowledge of the form `x <= bound` stays valid.
+//!
+//! Spectre guards are only removed when their condition is statically false:
+//! a dominating check does not stop speculative execution from running past
+//! it. When `enable_pcc` is set they are kept altogether, so that the facts
+//! on the guarded addresses can still be verified after lowering.
+
+use crate::dominator_tree::{DominatorTree, DominatorTreePreorder};
+use crate::fx::FxHashMap;
+use crate::ir::condcodes::IntCC;
+use crate::ir::pcc::{BaseExpr, Expr, Fact};
+use crate::ir::{Function, InstructionData, Opcode, Value};
+use crate::timing;
+use crate::trace;
+use alloc::vec::Vec;
+
+/// How deep to look through value definitions when computing ranges.
+const MAX_RANGE_DEPTH: usize = 4;
+
+/// A normalized bounds check: it fails when `lhs > rhs`, with both sides
+/// evaluated as unbounded unsigned integers.
+#[derive(Clone, Debug)]
+struct Check {
+    lhs: Expr,
+    rhs: Expr,
+}
+
+/// Statistics about the eliminated checks.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// `trapnz` checks removed because they can never fire.
+    pub static_traps: u64,
+    /// `trapnz` checks removed because a dominating check implies them.
+    pub dominated_traps: u64,
+    /// `select_spectre_guard`s removed because they can never select the
+    /// out-of-bounds arm.
+    pub static_guards: u64,
+}
+
+/// Remove redundant bounds checks from `func`.
+///
+/// Spectre guards are left in place when `keep_guards` is set.
+pub fn do_bounds_check_elimination(
+    func: &mut Function,
+    domtree: &DominatorTree,
+    keep_guards: bool,
+) -> Stats {
+    let _tt = timing::bounds_check_elim();
+    debug_assert!(domtree.is_valid());
+
+    let mut stats = Stats::default();
+    let entry = match func.layout.entry_block() {
+        Some(entry) => entry,
+        None => return stats,
+    };
+    let mut preorder = DominatorTreePreorder::new();
+    preorder.compute(domtree, &func.layout);
+
+    // Known facts of the form `lhs_base + d <= rhs_base`, keyed by the pair
+    // of bases and holding the largest `d` established so far on the path
+    // from the entry. Changes are undone when leaving a domtree subtree.
+    let mut known: FxHashMap<(BaseExpr, BaseExpr), i128> = FxHashMap::default();
+    let mut undo: Vec<((BaseExpr, BaseExpr), Option<i128>)> = Vec::new();
+
+    enum Step {
+        Enter(crate::ir::Block),
+        Leave(usize),
+    }
+    let mut stack = alloc::vec![Step::Enter(entry)];
+
+    while let Some(step) = stack.pop() {
+        let block = match step {
+            Step::Enter(block) => block,
+            Step::Leave(depth) => {
+                while undo.len() > depth {
+                    let (key, old) = undo.pop().unwrap();
+                    match old {
+                        Some(d) => known.insert(key, d),
+                        None => known.remove(&key),
+                    };
+                }
+                continue;
+            }
+        };
+        stack.push(Step::Leave(undo.len()));
+
+        let mut next = func.layout.first_inst(block);
+        while let Some(inst) = next {
+            next = func.layout.next_inst(inst);
+            match func.dfg.insts[inst] {
+                InstructionData::CondTrap {
+                    opcode: Opcode::Trapnz,
+                    arg,
+                    ..
+                } => {
+                    let check = match check_of(func, arg) {
+                        CheckOf::NeverFails => {
+                            trace!("bounds-check elim: removing {inst}: never traps");
+                            func.layout.remove_inst(inst);
+                            stats.static_traps += 1;
+                            continue;
+                        }
+                        CheckOf::Check(check) => check,
+                        CheckOf::Unknown => continue,
+                    };
+                    if never_fails(func, &check) {
+                        trace!("bounds-check elim: removing {inst}: never traps");
+                        func.layout.remove_inst(inst);
+                        stats.static_traps += 1;
+                        continue;
+                    }
+                    let key = (check.lhs.base.clone(), check.rhs.base.clone());
+                    let d = i128::from(check.lhs.offset) - i128::from(check.rhs.offset);
+                    let old = known.get(&key).copied();
+                    if old.map_or(false, |known| known >= d) {
+                        trace!("bounds-check elim: removing {inst}: implied by a dominating check");
+                        func.layout.remove_inst(inst);
+                        stats.dominated_traps += 1;
+                        continue;
+                    }
+                    if is_invariant_symbol(&check.lhs.base) {
+                        known.insert(key.clone(), d);
+                        undo.push((key, old));
+                    }
+                }
+
+                InstructionData::Ternary {
+                    opcode: Opcode::SelectSpectreGuard,
+                    args: [cond, _, if_in_bounds],
+                } if !keep_guards => {
+                    let proven = match check_of(func, cond) {
+                        CheckOf::NeverFails => true,
+                        CheckOf::Check(check) => never_fails(func, &check),
+                        CheckOf::Unknown => false,
+                    };
+                    if proven {
+                        trace!("bounds-check elim: removing {inst}: never out of bounds");
+                        let result = func.dfg.first_result(inst);
+                        func.layout.remove_inst(inst);
+                        func.dfg.detach_results(inst);
+                        func.dfg.change_to_alias(result, if_in_bounds);
+                        stats.static_guards += 1;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        for child in preorder.children(block) {
+            stack.push(Step::Enter(child));
+        }
+    }
+
+    stats
+}
+
+/// Whether knowledge about a base stays valid for the rest of the function.
+///
+/// SSA values and constants never change. Global values are symbols for
+/// things like heap bounds that may grow, so they are only trusted on the
+/// right-hand side of a check.
+fn is_invariant_symbol(base: &BaseExpr) -> bool {
+    matches!(base, BaseExpr::None | BaseExpr::Value(_))
+}
+
+enum CheckOf {
+    /// The condition is the constant zero.
+    NeverFails,
+    /// The condition is a comparison we understand.
+    Check(Check),
+    /// Anything else.
+    Unknown,
+}
+
+/// Understand the condition `cond` as a normalized check.
+fn check_of(func: &Function, cond: Value) -> CheckOf {
+    let cond = func.dfg.resolve_aliases(cond);
+    let inst = match func.dfg.value_def(cond).inst() {
+        Some(inst) => inst,
+        None => return CheckOf::Unknown,
+    };
+
+    let (kind, lhs, rhs) = match (&func.dfg.facts[cond], &func.dfg.insts[inst]) {
+        (
+            _,
+            InstructionData::UnaryImm {
+                opcode: Opcode::Iconst,
+                imm,
+            },
+        ) if imm.bits() == 0 => {
+            return CheckOf::NeverFails;
+        }
+        (Some(Fact::Compare { kind, lhs, rhs }), _) => (*kind, lhs.clone(), rhs.clone()),
+        (
+            _,
+            InstructionData::IntCompare {
+                opcode: Opcode::Icmp,
+                cond,
+                args: [a, b],
+            },
+        ) => (*cond, expr_of(func, *a), expr_of(func, *b)),
+        (
+            _,
+            InstructionData::IntCompareImm {
+                opcode: Opcode::IcmpImm,
+                cond,
+                arg,
+                imm,
+            },
+        ) => {
+            let bits = func.dfg.value_type(*arg).bits();
+            match i64::try_from(mask(imm.bits() as u64, bits)) {
+                Ok(k) => (*cond, expr_of(func, *arg), Expr::constant(k)),
+                Err(_) => return CheckOf::Unknown,
+            }
+        }
+        _ => return CheckOf::Unknown,
+    };
+
+    let check = match kind {
+        IntCC::UnsignedGreaterThan => Check { lhs, rhs },
+        IntCC::UnsignedLessThan => Check { lhs: rhs, rhs: lhs },
+        IntCC::UnsignedGreaterThanOrEqual => match Expr::offset(&lhs, 1) {
+            Some(lhs) => Check { lhs, rhs },
+            None => return CheckOf::Unknown,
+        },
+        IntCC::UnsignedLessThanOrEqual => match Expr::offset(&rhs, 1) {
+            Some(rhs_plus_one) => Check {
+                lhs: rhs_plus_one,
+                rhs: lhs,
+            },
+            None => return CheckOf::Unknown,
+        },
+        _ => return CheckOf::Unknown,
+    };
+    CheckOf::Check(check)
+}
+
+/// The symbolic expression for `value`: the symbol the frontend attached to
+/// it, its constant value, or the value itself.
+fn expr_of(func: &Function, value: Value) -> Expr {
+    let value = func.dfg.resolve_aliases(value);
+    if let Some(expr) = func.dfg.facts[value].as_ref().and_then(|f| f.as_symbol()) {
+        return expr.clone();
+    }
+    if let Some(inst) = func.dfg.value_def(value).inst() {
+        if let InstructionData::UnaryImm {
+            opcode: Opcode::Iconst,
+            imm,
+        } = func.dfg.insts[inst]
+        {
+            let bits = func.dfg.value_type(value).bits();
+            if let Ok(k) = i64::try_from(mask(imm.bits() as u64, bits)) {
+                return Expr::constant(k);
+            }
+        }
+    }
+    Expr::value(value)
+}
+
+/// Can the check `lhs > rhs` never succeed?
+fn never_fails(func: &Function, check: &Check) -> bool {
+    match (expr_max(func, &check.lhs), expr_min(func, &check.rhs)) {
+        (Some(max), Some(min)) => max <= min,
+        _ => false,
+    }
+}
+
+/// An upper bound for the value of `expr`.
+fn expr_max(func: &Function, expr: &Expr) -> Option<i128> {
+    let base = match &expr.base {
+        BaseExpr::None => 0,
+        BaseExpr::Value(v) => i128::from(value_range(func, *v, 0)?.1),
+        BaseExpr::GlobalValue(gv) => match func.global_value_facts[*gv] {
+            Some(Fact::Range { max, .. }) => i128::from(max),
+            _ => return None,
+        },
+        BaseExpr::Max => return None,
+    };
+    Some(base + i128::from(expr.offset))
+}
+
+/// A lower bound for the value of `expr`.
+fn expr_min(func: &Function, expr: &Expr) -> Option<i128> {
+    let base = match &expr.base {
+        BaseExpr::None => 0,
+        BaseExpr::Value(v) => value_range(func, *v, 0).map_or(0, |r| i128::from(r.0)),
+        BaseExpr::GlobalValue(gv) => match func.global_value_facts[*gv] {
+            Some(Fact::Range { min, .. }) => i128::from(min),
+            _ => 0,
+        },
+        BaseExpr::Max => return Some(i128::from(u64::MAX)),
+    };
+    Some(base + i128::from(expr.offset))
+}
+
+/// The inclusive unsigned range of `value`, if it is an integer of at most
+/// 64 bits.
+fn value_range(func: &Function, value: Value, depth: usize) -> Option<(u64, u64)> {
+    let value = func.dfg.resolve_aliases(value);
+    let ty = func.dfg.value_type(value);
+    if !ty.is_int() || ty.bits() > 64 {
+        return None;
+    }
+    let bits = ty.bits();
+    let full = (0, mask(u64::MAX, bits));
+
+    let (min, max) = match &func.dfg.facts[value] {
+        Some(Fact::Range {
+            bit_width,
+            min,
+            max,
+        }) if u32::from(*bit_width) >= bits => (*min, (*max).min(full.1)),
+        _ => full,
+    };
+    if depth >= MAX_RANGE_DEPTH {
+        return Some((min, max));
+    }
+    let operand = |v: Value| value_range(func, v, depth + 1).unwrap_or(full);
+
+    let inst = match func.dfg.value_def(value).inst() {
+        Some(inst) => inst,
+        None => return Some((min, max)),
+    };
+    let (def_min, def_max) = match func.dfg.insts[inst] {
+        InstructionData::UnaryImm {
+            opcode: Opcode::Iconst,
+            imm,
+        } => {
+            let k = mask(imm.bits() as u64, bits);
+            (k, k)
+        }
+        InstructionData::Unary {
+            opcode: Opcode::Uextend,
+            arg,
+        } => operand(arg),
+        InstructionData::Binary {
+            opcode: Opcode::Band,
+            args: [a, b],
+        } => (0, operand(a).1.min(operand(b).1)),
+        InstructionData::BinaryImm64 {
+            opcode: Opcode::BandImm,
+            arg,
+            imm,
+        } => (0, operand(arg).1.min(mask(imm.bits() as u64, bits))),
+        InstructionData::Binary {
+            opcode: Opcode::Ushr,
+            args: [a, b],
+        } => match operand(b) {
+            (shift, shift_max) if shift == shift_max && shift < u64::from(bits) => {
+                let (a_min, a_max) = operand(a);
+                (a_min >> shift, a_max >> shift)
+            }
+            _ => full,
+        },
+        _ => full,
+    };
+    let (min, max) = (min.max(def_min), max.min(def_max));
+    if min > max {
+        // Contradictory information; only possible in unreachable code.
+        return Some(full);
+    }
+    Some((min, max))
+}
+
+/// Truncate `value` to `bits` bits.
+fn mask(value: u64, bits: u32) -> u64 {
+    if bits >= 64 {
+        value
+    } else {
+        value & ((1 << bits) - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::flowgraph::ControlFlowGraph;
+    use crate::ir::types::{I32, I64, I8};
+    use crate::ir::{AbiParam, InstBuilder, Signature, TrapCode};
+    use crate::isa::CallConv;
+
+    fn run(func: &mut Function, keep_guards: bool) -> Stats {
+        let cfg = ControlFlowGraph::with_function(func);
+        let domtree = DominatorTree::with_function(func, &cfg);
+        do_bounds_check_elimination(func, &domtree, keep_guards)
+    }
+
+    fn count(func: &Function, opcode: Opcode) -> usize {
+        func.layout
+            .blocks()
+            .flat_map(|b| func.layout.block_insts(b))
+            .filter(|i| func.dfg.insts[*i].opcode() == opcode)
+            .count()
+    }
+
+    /// `fn(index: i32, bound: i64)`, with the index zero-extended to 64 bits.
+    fn function() -> (Function, Value, Value) {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I32));
+        sig.params.push(AbiParam::new(I64));
+        let mut func = Function::with_name_signature(Default::default(), sig);
+        let block = func.dfg.make_block();
+        let index = func.dfg.append_block_param(block, I32);
+        let bound = func.dfg.append_block_param(block, I64);
+        func.layout.append_block(block);
+        let mut pos = FuncCursor::new(&mut func).at_bottom(block);
+        let index = pos.ins().uextend(I64, index);
+        (func, index, bound)
+    }
+
+    #[test]
+    fn masked_index_below_bound() {
+        let (mut func, index, _) = function();
+        let block = func.layout.entry_block().unwrap();
+        let mut pos = FuncCursor::new(&mut func).at_bottom(block);
+        let masked = pos.ins().band_imm(index, 0xff);
+        let limit = pos.ins().iconst(I64, 0x1_0000 - 4);
+        let oob = pos.ins().icmp(IntCC::UnsignedGreaterThan, masked, limit);
+        pos.ins().trapnz(oob, TrapCode::HeapOutOfBounds);
+        let null = pos.ins().iconst(I64, 0);
+        let guarded = pos.ins().select_spectre_guard(oob, null, masked);
+        pos.ins().return_(&[guarded]);
+
+        let stats = run(&mut func, false);
+        assert_eq!(stats.static_traps, 1);
+        assert_eq!(stats.static_guards, 1);
+        assert_eq!(count(&func, Opcode::Trapnz), 0);
+        assert_eq!(count(&func, Opcode::SelectSpectreGuard), 0);
+    }
+
+    #[test]
+    fn dominated_check() {
+        let (mut func, index, bound) = function();
+        let block = func.layout.entry_block().unwrap();
+        let mut pos = FuncCursor::new(&mut func).at_bottom(block);
+
+        // `index + 8 > bound` implies `index + 4 > bound`, but not the other
+        // way around.
+        let bound_minus_8 = pos.ins().iadd_imm(bound, -8);
+        let oob = pos
+            .ins()
+            .icmp(IntCC::UnsignedGreaterThan, index, bound_minus_8);
+        pos.func.dfg.facts[oob] = Some(Fact::Compare {
+            kind: IntCC::UnsignedGreaterThan,
+            lhs: Expr {
+                base: BaseExpr::Value(index),
+                offset: 8,
+            },
+            rhs: Expr::value(bound),
+        });
+        pos.ins().trapnz(oob, TrapCode::HeapOutOfBounds);
+
+        let bound_minus_4 = pos.ins().iadd_imm(bound, -4);
+        let oob = pos
+            .ins()
+            .icmp(IntCC::UnsignedGreaterThan, index, bound_minus_4);
+        pos.func.dfg.facts[oob] = Some(Fact::Compare {
+            kind: IntCC::UnsignedGreaterThan,
+            lhs: Expr {
+                base: BaseExpr::Value(index),
+                offset: 4,
+            },
+            rhs: Expr::value(bound),
+        });
+        pos.ins().trapnz(oob, TrapCode::HeapOutOfBounds);
+
+        let bound_minus_16 = pos.ins().iadd_imm(bound, -16);
+        let oob = pos
+            .ins()
+            .icmp(IntCC::UnsignedGreaterThan, index, bound_minus_16);
+        pos.func.dfg.facts[oob] = Some(Fact::Compare {
+            kind: IntCC::UnsignedGreaterThan,
+            lhs: Expr {
+                base: BaseExpr::Value(index),
+                offset: 16,
+            },
+            rhs: Expr::value(bound),
+        });
+        pos.ins().trapnz(oob, TrapCode::HeapOutOfBounds);
+        pos.ins().return_(&[]);
+
+        let stats = run(&mut func, false);
+        assert_eq!(stats.dominated_traps, 1);
+        assert_eq!(count(&func, Opcode::Trapnz), 2);
+    }
+
+    #[test]
+    fn guards_kept_when_requested() {
+        let (mut func, index, _) = function();
+        let block = func.layout.entry_block().unwrap();
+        let mut pos = FuncCursor::new(&mut func).at_bottom(block);
+        let small = pos.ins().iconst(I8, 0);
+        let oob = pos.ins().icmp_imm(IntCC::UnsignedGreaterThan, small, 1);
+        let null = pos.ins().iconst(I64, 0);
+        let guarded = pos.ins().select_spectre_guard(oob, null, index);
+        pos.ins().return_(&[guarded]);
+
+        let stats = run(&mut func, true);
+        assert_eq!(stats.static_guards, 0);
+        assert_eq!(count(&func, Opcode::SelectSpectreGuard), 1);
+    }
+}
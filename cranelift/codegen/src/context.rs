@@ -10,10 +10,12 @@
 //! single ISA instance.
 
 use crate::alias_analysis::AliasAnalysis;
+use crate::bounds_check_elim::do_bounds_check_elimination;
 use crate::dce::do_dce;
 use crate::dominator_tree::DominatorTree;
 use crate::egraph::EgraphPass;
 use crate::flowgraph::ControlFlowGraph;
+use crate::inline::{do_inlining, Inline};
 use crate::ir::Function;
 use crate::isa::TargetIsa;
 use crate::legalizer::simple_legalize;
@@ -173,6 +175,13 @@ impl Context {
             self.canonicalize_nans(isa)?;
         }
 
+        // Bounds checks are `trapnz`s until legalization expands them into
+        // branches to trap blocks, so eliminate redundant ones first.
+        if isa.flags().enable_bounds_check_elimination() {
+            self.compute_domtree();
+            self.bounds_check_elimination(isa)?;
+        }
+
         self.legalize(isa)?;
 
         self.compute_domtree();
@@ -276,6 +285,23 @@ impl Context {
         Ok(())
     }
 
+    /// Inline the calls of the function selected by `inliner`.
+    ///
+    /// This is meant to be run before [`Context::compile`], so that the
+    /// inlined bodies go through the optimizer along with the rest of the
+    /// function. Returns whether any call was inlined.
+    pub fn inline<'a, FOI: Into<FlagsOrIsa<'a>>>(
+        &mut self,
+        inliner: impl Inline,
+        fisa: FOI,
+    ) -> CodegenResult<bool> {
+        let inlined = do_inlining(&mut self.func, inliner)?;
+        if inlined {
+            self.verify_if(fisa)?;
+        }
+        Ok(inlined)
+    }
+
     /// Perform NaN canonicalizing rewrites on the function.
     pub fn canonicalize_nans(&mut self, isa: &dyn TargetIsa) -> CodegenResult<()> {
         do_nan_canonicalization(&mut self.func);
@@ -346,6 +372,19 @@ impl Context {
         Ok(())
     }
 
+    /// Remove bounds checks that are statically known to never fail or that
+    /// are implied by a dominating check.
+    pub fn bounds_check_elimination<'a, FOI>(&mut self, fisa: FOI) -> CodegenResult<()>
+    where
+        FOI: Into<FlagsOrIsa<'a>>,
+    {
+        let fisa = fisa.into();
+        let stats =
+            do_bounds_check_elimination(&mut self.func, &self.domtree, fisa.flags.enable_pcc());
+        log::debug!("bounds-check elimination stats: {:?}", stats);
+        self.verify_if(fisa)
+    }
+
     /// Run optimizations via the egraph infrastructure.
     pub fn egraph_pass<'a, FOI>(&mut self, fisa: FOI) -> CodegenResult<()>
     where
@@ -0,0 +1,603 @@
+//! Function inlining.
+//!
+//! This pass replaces direct `call` instructions with a copy of the callee's
+//! body. Cranelift compiles one function at a time and has no notion of the
+//! other functions in a module, so the decision of which calls to inline, and
+//! the bodies of the callees, are supplied by the embedder through the
+//! [`Inline`] trait.
+//!
+//! Inlining is performed before the mid-end optimizations run, so that the
+//! inlined body is optimized together with the caller: arguments which are
+//! constant at the call site get propagated into the callee's body by the
+//! e-graph pass.
+//!
+//! Only the calls which are present in the caller when the pass starts are
+//! considered, which means that calls within an inlined body are never
+//! inlined themselves. This bounds the amount of code growth and rules out
+//! unbounded recursive inlining.
+
+use crate::dominator_tree::DominatorTree;
+use crate::flowgraph::ControlFlowGraph;
+use crate::ir::instructions::InstructionMapper;
+use crate::ir::{
+    self, ArgumentPurpose, Block, DataFlowGraph, ExtFuncData, ExternalName, FuncRef, Function,
+    GlobalValue, GlobalValueData, Inst, InstBuilder, InstructionData, JumpTable, JumpTableData,
+    Opcode, SigRef, StackSlot, Value,
+};
+use crate::packed_option::PackedOption;
+use crate::result::CodegenResult;
+use crate::{timing, trace};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use cranelift_entity::SecondaryMap;
+use smallvec::SmallVec;
+
+/// What to do with a particular call site, as decided by an [`Inline`]
+/// implementation.
+pub enum InlineCommand<'a> {
+    /// Leave the call as it is.
+    KeepCall,
+    /// Replace the call with a copy of the given callee body.
+    Inline(Cow<'a, Function>),
+}
+
+/// A policy for the inlining pass.
+///
+/// This is queried once for every direct `call` in the function being
+/// compiled, and decides whether that call should be inlined. When it should,
+/// the implementation also provides the CLIF body of the callee.
+pub trait Inline {
+    /// Decide what to do with the call instruction `call_inst` in `caller`,
+    /// which calls `callee` with the arguments `args`.
+    fn inline(
+        &mut self,
+        caller: &Function,
+        call_inst: Inst,
+        callee: FuncRef,
+        args: &[Value],
+    ) -> CodegenResult<InlineCommand<'_>>;
+}
+
+/// Perform inlining on `func`, as directed by `inliner`.
+///
+/// Returns whether any call was inlined.
+pub fn do_inlining(func: &mut Function, mut inliner: impl Inline) -> CodegenResult<bool> {
+    let _tt = timing::inline();
+
+    let mut calls = Vec::new();
+    for block in func.layout.blocks() {
+        for inst in func.layout.block_insts(block) {
+            if func.dfg.insts[inst].opcode() == Opcode::Call {
+                calls.push(inst);
+            }
+        }
+    }
+
+    let mut inlined_any = false;
+    for call in calls {
+        let (func_ref, args) = match func.dfg.insts[call] {
+            InstructionData::Call {
+                func_ref, ref args, ..
+            } => {
+                let args: SmallVec<[Value; 8]> = args
+                    .as_slice(&func.dfg.value_lists)
+                    .iter()
+                    .map(|arg| func.dfg.resolve_aliases(*arg))
+                    .collect();
+                (func_ref, args)
+            }
+            _ => unreachable!(),
+        };
+
+        let callee = match inliner.inline(func, call, func_ref, &args)? {
+            InlineCommand::KeepCall => continue,
+            InlineCommand::Inline(callee) => callee,
+        };
+
+        if let Err(reason) = check_can_inline(func, func_ref, &args, &callee) {
+            trace!(
+                "not inlining {} into {}: {}",
+                callee.name,
+                func.name,
+                reason
+            );
+            continue;
+        }
+
+        trace!("inlining {} into {} at {}", callee.name, func.name, call);
+        inline_call(func, call, &args, &callee);
+        inlined_any = true;
+    }
+
+    Ok(inlined_any)
+}
+
+/// Check that `callee` can be spliced into `caller` at a call to `func_ref`
+/// with the arguments `args`.
+fn check_can_inline(
+    caller: &Function,
+    func_ref: FuncRef,
+    args: &[Value],
+    callee: &Function,
+) -> Result<(), &'static str> {
+    let entry = match callee.layout.entry_block() {
+        Some(entry) => entry,
+        None => return Err("the callee has no body"),
+    };
+
+    let sig = &caller.dfg.signatures[caller.dfg.ext_funcs[func_ref].signature];
+    if sig.params.len() != callee.signature.params.len()
+        || sig.returns.len() != callee.signature.returns.len()
+        || sig
+            .params
+            .iter()
+            .zip(&callee.signature.params)
+            .any(|(a, b)| a.value_type != b.value_type)
+        || sig
+            .returns
+            .iter()
+            .zip(&callee.signature.returns)
+            .any(|(a, b)| a.value_type != b.value_type)
+    {
+        return Err("the callee's signature does not match the call");
+    }
+
+    // Dynamic vector types are tied to the target and the function they are
+    // declared in, and memory types only exist to support proof-carrying
+    // code; neither is worth the complexity of being carried over.
+    if !callee.dynamic_stack_slots.is_empty()
+        || !callee.dfg.dynamic_types.is_empty()
+        || !callee.memory_types.is_empty()
+    {
+        return Err("the callee uses dynamic types or memory types");
+    }
+
+    // `global_value vmctx` in the callee is resolved against the function's
+    // `vmctx` parameter during legalization. Once inlined that will be the
+    // caller's `vmctx`, so both have to be the same.
+    let uses_vmctx = callee
+        .global_values
+        .values()
+        .any(|gv| matches!(gv, GlobalValueData::VMContext));
+    if uses_vmctx {
+        let index = callee
+            .signature
+            .special_param_index(ArgumentPurpose::VMContext)
+            .ok_or("the callee uses a vmctx it does not receive")?;
+        if caller.special_param(ArgumentPurpose::VMContext) != Some(args[index]) {
+            return Err("the callee's vmctx is not the caller's vmctx");
+        }
+    }
+
+    for block in callee.layout.blocks() {
+        for inst in callee.layout.block_insts(block) {
+            let data = &callee.dfg.insts[inst];
+            match data.opcode() {
+                // A tail call from the callee would return from the caller.
+                Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+                    return Err("the callee contains tail calls");
+                }
+                // These observe the callee's own frame, which disappears.
+                Opcode::GetFramePointer | Opcode::GetStackPointer | Opcode::GetReturnAddress => {
+                    return Err("the callee inspects its own frame");
+                }
+                _ => {}
+            }
+            for dest in data.branch_destination(&callee.dfg.jump_tables) {
+                if dest.block(&callee.dfg.value_lists) == entry {
+                    return Err("the callee branches to its entry block");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Mapping of the entities of the callee to their copies in the caller.
+struct EntityMap<'a> {
+    callee: &'a DataFlowGraph,
+    values: SecondaryMap<Value, PackedOption<Value>>,
+    blocks: SecondaryMap<Block, PackedOption<Block>>,
+    func_refs: SecondaryMap<FuncRef, PackedOption<FuncRef>>,
+    sig_refs: SecondaryMap<SigRef, PackedOption<SigRef>>,
+    global_values: SecondaryMap<GlobalValue, PackedOption<GlobalValue>>,
+    jump_tables: SecondaryMap<JumpTable, PackedOption<JumpTable>>,
+    stack_slots: SecondaryMap<StackSlot, PackedOption<StackSlot>>,
+    tables: SecondaryMap<ir::Table, PackedOption<ir::Table>>,
+    constants: SecondaryMap<ir::Constant, PackedOption<ir::Constant>>,
+    immediates: SecondaryMap<ir::Immediate, PackedOption<ir::Immediate>>,
+}
+
+impl InstructionMapper for EntityMap<'_> {
+    fn map_value(&mut self, value: Value) -> Value {
+        let value = self.callee.resolve_aliases(value);
+        self.values[value].expect("values are defined before they are used in reverse post-order")
+    }
+
+    fn map_block(&mut self, block: Block) -> Block {
+        self.blocks[block].unwrap()
+    }
+
+    fn map_func_ref(&mut self, func_ref: FuncRef) -> FuncRef {
+        self.func_refs[func_ref].unwrap()
+    }
+
+    fn map_sig_ref(&mut self, sig_ref: SigRef) -> SigRef {
+        self.sig_refs[sig_ref].unwrap()
+    }
+
+    fn map_global_value(&mut self, global_value: GlobalValue) -> GlobalValue {
+        self.global_values[global_value].unwrap()
+    }
+
+    fn map_jump_table(&mut self, jump_table: JumpTable) -> JumpTable {
+        self.jump_tables[jump_table].unwrap()
+    }
+
+    fn map_stack_slot(&mut self, stack_slot: StackSlot) -> StackSlot {
+        self.stack_slots[stack_slot].unwrap()
+    }
+
+    fn map_dynamic_stack_slot(&mut self, _: ir::DynamicStackSlot) -> ir::DynamicStackSlot {
+        unreachable!("callees with dynamic stack slots are never inlined")
+    }
+
+    fn map_table(&mut self, table: ir::Table) -> ir::Table {
+        self.tables[table].unwrap()
+    }
+
+    fn map_constant(&mut self, constant: ir::Constant) -> ir::Constant {
+        self.constants[constant].unwrap()
+    }
+
+    fn map_immediate(&mut self, immediate: ir::Immediate) -> ir::Immediate {
+        self.immediates[immediate].unwrap()
+    }
+}
+
+/// Translate a reference to an external name of `callee` into `caller`.
+fn map_external_name(
+    caller: &mut Function,
+    callee: &Function,
+    name: &ExternalName,
+) -> ExternalName {
+    match name {
+        ExternalName::User(name) => {
+            let name = callee.params.user_named_funcs()[*name].clone();
+            ExternalName::User(caller.declare_imported_user_function(name))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Replace the call instruction `call` in `func` with the body of `callee`.
+fn inline_call(func: &mut Function, call: Inst, args: &[Value], callee: &Function) {
+    let srcloc = func.srcloc(call);
+    let call_block = func.layout.inst_block(call).unwrap();
+    let cold = func.layout.is_cold(call_block);
+
+    // Everything after the call moves to a new block, whose parameters take
+    // over the call's results. Each `return` in the callee becomes a jump to
+    // this block.
+    let return_block = func.dfg.make_block();
+    let next = func
+        .layout
+        .next_inst(call)
+        .expect("a call is never the last instruction of a block");
+    func.layout.split_block(return_block, next);
+    if cold {
+        func.layout.set_cold(return_block);
+    }
+    let results = func.dfg.detach_results(call);
+    let results: SmallVec<[Value; 4]> = results.as_slice(&func.dfg.value_lists).into();
+    for result in results {
+        func.dfg.attach_block_param(return_block, result);
+    }
+
+    let mut map = EntityMap {
+        callee: &callee.dfg,
+        values: SecondaryMap::new(),
+        blocks: SecondaryMap::new(),
+        func_refs: SecondaryMap::new(),
+        sig_refs: SecondaryMap::new(),
+        global_values: SecondaryMap::new(),
+        jump_tables: SecondaryMap::new(),
+        stack_slots: SecondaryMap::new(),
+        tables: SecondaryMap::new(),
+        constants: SecondaryMap::new(),
+        immediates: SecondaryMap::new(),
+    };
+
+    for (sig_ref, sig) in callee.dfg.signatures.iter() {
+        map.sig_refs[sig_ref] = func.import_signature(sig.clone()).into();
+    }
+
+    for (func_ref, data) in callee.dfg.ext_funcs.iter() {
+        let name = map_external_name(func, callee, &data.name);
+        map.func_refs[func_ref] = func
+            .import_function(ExtFuncData {
+                name,
+                signature: map.sig_refs[data.signature].unwrap(),
+                colocated: data.colocated,
+            })
+            .into();
+    }
+
+    // Global values may refer to each other in any order, so create them all
+    // first and then fix up their bases.
+    let caller_vmctx = func
+        .global_values
+        .iter()
+        .find(|(_, gv)| matches!(gv, GlobalValueData::VMContext))
+        .map(|(gv, _)| gv);
+    for (gv, data) in callee.global_values.iter() {
+        let new_gv = match (data, caller_vmctx) {
+            (GlobalValueData::VMContext, Some(vmctx)) => vmctx,
+            _ => func.create_global_value(data.clone()),
+        };
+        map.global_values[gv] = new_gv.into();
+    }
+    for (gv, data) in callee.global_values.iter() {
+        let mut data = data.clone();
+        match &mut data {
+            GlobalValueData::Load { base, .. } | GlobalValueData::IAddImm { base, .. } => {
+                *base = map.global_values[*base].unwrap();
+            }
+            GlobalValueData::Symbol { name, .. } => {
+                *name = map_external_name(func, callee, name);
+            }
+            GlobalValueData::VMContext | GlobalValueData::DynScaleTargetConst { .. } => continue,
+        }
+        func.global_values[map.global_values[gv].unwrap()] = data;
+    }
+
+    for (slot, data) in callee.sized_stack_slots.iter() {
+        map.stack_slots[slot] = func.create_sized_stack_slot(data.clone()).into();
+    }
+
+    for (table, data) in callee.tables.iter() {
+        let data = ir::TableData {
+            base_gv: map.global_values[data.base_gv].unwrap(),
+            bound_gv: map.global_values[data.bound_gv].unwrap(),
+            ..data.clone()
+        };
+        map.tables[table] = func.create_table(data).into();
+    }
+
+    for (constant, data) in callee.dfg.constants.iter() {
+        map.constants[*constant] = func.dfg.constants.insert(data.clone()).into();
+    }
+
+    for (immediate, data) in callee.dfg.immediates.iter() {
+        map.immediates[immediate] = func.dfg.immediates.push(data.clone()).into();
+    }
+
+    // Copy the blocks which are reachable from the callee's entry, keeping
+    // their relative order in the layout, between the call and the block
+    // which the callee returns to.
+    let cfg = ControlFlowGraph::with_function(callee);
+    let domtree = DominatorTree::with_function(callee, &cfg);
+    let entry = callee.layout.entry_block().unwrap();
+    for block in callee.layout.blocks() {
+        if !domtree.is_reachable(block) {
+            continue;
+        }
+        let new_block = func.dfg.make_block();
+        func.layout.insert_block(new_block, return_block);
+        if cold || callee.layout.is_cold(block) {
+            func.layout.set_cold(new_block);
+        }
+        map.blocks[block] = new_block.into();
+
+        // The parameters of the entry block are the call's arguments; those of
+        // any other block become parameters of its copy.
+        for (i, param) in callee.dfg.block_params(block).iter().enumerate() {
+            let new_param = if block == entry {
+                args[i]
+            } else {
+                let ty = callee.dfg.value_type(*param);
+                func.dfg.append_block_param(new_block, ty)
+            };
+            map.values[*param] = new_param.into();
+        }
+    }
+
+    // Copy instructions in reverse post-order, which guarantees that every
+    // value is defined before any of its uses.
+    for &block in domtree.cfg_postorder().iter().rev() {
+        let new_block = map.blocks[block].unwrap();
+        for inst in callee.layout.block_insts(block) {
+            let data = &callee.dfg.insts[inst];
+
+            let new_inst = if data.opcode() == Opcode::Return {
+                let rets: SmallVec<[Value; 4]> = data
+                    .arguments(&callee.dfg.value_lists)
+                    .iter()
+                    .map(|v| map.map_value(*v))
+                    .collect();
+                let destination = func.dfg.block_call(return_block, &rets);
+                func.dfg.make_inst(InstructionData::Jump {
+                    opcode: Opcode::Jump,
+                    destination,
+                })
+            } else {
+                if let InstructionData::BranchTable { table, .. } = *data {
+                    if map.jump_tables[table].is_none() {
+                        let src_pool = &callee.dfg.value_lists;
+                        let dst_pool = &mut func.dfg.value_lists;
+                        let table_data = &callee.dfg.jump_tables[table];
+                        let default = table_data.default_block();
+                        let default = map.map_block_call(default, src_pool, dst_pool);
+                        let entries: Vec<_> = table_data
+                            .as_slice()
+                            .iter()
+                            .map(|entry| map.map_block_call(*entry, src_pool, dst_pool))
+                            .collect();
+                        map.jump_tables[table] = func
+                            .create_jump_table(JumpTableData::new(default, &entries))
+                            .into();
+                    }
+                }
+                let new_data =
+                    data.map(&callee.dfg.value_lists, &mut func.dfg.value_lists, &mut map);
+                let new_inst = func.dfg.make_inst(new_data);
+                func.dfg
+                    .make_inst_results(new_inst, callee.dfg.ctrl_typevar(inst));
+                for (old, new) in callee
+                    .dfg
+                    .inst_results(inst)
+                    .iter()
+                    .zip(func.dfg.inst_results(new_inst))
+                {
+                    map.values[*old] = (*new).into();
+                }
+                new_inst
+            };
+
+            func.layout.append_inst(new_inst, new_block);
+            func.set_srcloc(new_inst, srcloc);
+        }
+    }
+
+    // Finally, the call itself becomes a jump into the copied body.
+    let new_entry = map.blocks[entry].unwrap();
+    func.dfg.replace(call).jump(new_entry, &[]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::ir::types::*;
+    use crate::ir::{AbiParam, Signature, UserExternalName, UserFuncName};
+    use crate::isa::CallConv;
+    use crate::verifier::verify_function;
+    use alloc::string::ToString;
+
+    struct InlineAll<'a>(&'a Function);
+
+    impl Inline for InlineAll<'_> {
+        fn inline(
+            &mut self,
+            _caller: &Function,
+            _call_inst: Inst,
+            _callee: FuncRef,
+            _args: &[Value],
+        ) -> CodegenResult<InlineCommand<'_>> {
+            Ok(InlineCommand::Inline(Cow::Borrowed(self.0)))
+        }
+    }
+
+    fn signature() -> Signature {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I32));
+        sig.params.push(AbiParam::new(I32));
+        sig.returns.push(AbiParam::new(I32));
+        sig
+    }
+
+    /// `fn callee(a, b) { if a { return a + b } else { return b } }`
+    fn callee() -> Function {
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 1), signature());
+        let block0 = func.dfg.make_block();
+        let a = func.dfg.append_block_param(block0, I32);
+        let b = func.dfg.append_block_param(block0, I32);
+        let block1 = func.dfg.make_block();
+        let block2 = func.dfg.make_block();
+
+        let mut cur = FuncCursor::new(&mut func);
+        cur.insert_block(block0);
+        cur.ins().brif(a, block1, &[], block2, &[]);
+
+        cur.insert_block(block1);
+        let sum = cur.ins().iadd(a, b);
+        cur.ins().return_(&[sum]);
+
+        cur.insert_block(block2);
+        cur.ins().return_(&[b]);
+        func
+    }
+
+    /// `fn caller(x) { return callee(x, 1) * 2 }`
+    fn caller() -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I32));
+        sig.returns.push(AbiParam::new(I32));
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 0), sig);
+        let callee_sig = func.import_signature(signature());
+        let name = func.declare_imported_user_function(UserExternalName::new(0, 1));
+        let callee = func.import_function(ExtFuncData {
+            name: ExternalName::User(name),
+            signature: callee_sig,
+            colocated: true,
+        });
+        let block0 = func.dfg.make_block();
+        let x = func.dfg.append_block_param(block0, I32);
+
+        let mut cur = FuncCursor::new(&mut func);
+        cur.insert_block(block0);
+        let one = cur.ins().iconst(I32, 1);
+        let call = cur.ins().call(callee, &[x, one]);
+        let result = cur.func.dfg.first_result(call);
+        let two = cur.ins().iconst(I32, 2);
+        let product = cur.ins().imul(result, two);
+        cur.ins().return_(&[product]);
+        func
+    }
+
+    #[test]
+    fn inline_simple_call() {
+        let callee = callee();
+        let mut func = caller();
+        assert!(do_inlining(&mut func, InlineAll(&callee)).unwrap());
+        verify_function(
+            &func,
+            &crate::settings::Flags::new(crate::settings::builder()),
+        )
+        .unwrap();
+
+        let insts = func
+            .layout
+            .blocks()
+            .flat_map(|block| func.layout.block_insts(block))
+            .map(|inst| func.dfg.insts[inst].opcode())
+            .collect::<Vec<_>>();
+        assert!(!insts.contains(&Opcode::Call));
+        assert_eq!(
+            insts.iter().filter(|op| **op == Opcode::Return).count(),
+            1,
+            "only the caller's return remains: {}",
+            func.display()
+        );
+    }
+
+    #[test]
+    fn keep_call() {
+        struct KeepAll;
+        impl Inline for KeepAll {
+            fn inline(
+                &mut self,
+                _caller: &Function,
+                _call_inst: Inst,
+                _callee: FuncRef,
+                _args: &[Value],
+            ) -> CodegenResult<InlineCommand<'_>> {
+                Ok(InlineCommand::KeepCall)
+            }
+        }
+
+        let mut func = caller();
+        let before = func.display().to_string();
+        assert!(!do_inlining(&mut func, KeepAll).unwrap());
+        assert_eq!(before, func.display().to_string());
+    }
+
+    #[test]
+    fn signature_mismatch_is_not_inlined() {
+        let mut callee = callee();
+        callee.signature.returns.clear();
+        let mut func = caller();
+        assert!(!do_inlining(&mut func, InlineAll(&callee)).unwrap());
+    }
+}
@@ -134,6 +134,65 @@ impl BlockCall {
     }
 }
 
+/// A set of entity mappings applied by [`InstructionData::map`].
+///
+/// This is used when an instruction is copied from one function into another,
+/// for example by the inliner, where every value, block and entity reference
+/// of the source function has to be translated into its counterpart in the
+/// destination function.
+pub trait InstructionMapper {
+    /// Map a value operand.
+    fn map_value(&mut self, value: Value) -> Value;
+
+    /// Map a block referenced by a branch.
+    fn map_block(&mut self, block: Block) -> Block;
+
+    /// Map a reference to an external function.
+    fn map_func_ref(&mut self, func_ref: FuncRef) -> FuncRef;
+
+    /// Map a reference to a function signature.
+    fn map_sig_ref(&mut self, sig_ref: SigRef) -> SigRef;
+
+    /// Map a reference to a global value.
+    fn map_global_value(&mut self, global_value: ir::GlobalValue) -> ir::GlobalValue;
+
+    /// Map a reference to a jump table.
+    fn map_jump_table(&mut self, jump_table: ir::JumpTable) -> ir::JumpTable;
+
+    /// Map a reference to a sized stack slot.
+    fn map_stack_slot(&mut self, stack_slot: StackSlot) -> StackSlot;
+
+    /// Map a reference to a dynamic stack slot.
+    fn map_dynamic_stack_slot(&mut self, stack_slot: ir::DynamicStackSlot) -> ir::DynamicStackSlot;
+
+    /// Map a reference to a table.
+    fn map_table(&mut self, table: ir::Table) -> ir::Table;
+
+    /// Map a reference to a constant in the constant pool.
+    fn map_constant(&mut self, constant: ir::Constant) -> ir::Constant;
+
+    /// Map a reference to an immediate stored out of line.
+    fn map_immediate(&mut self, immediate: ir::Immediate) -> ir::Immediate;
+
+    /// Map a block call, whose list lives in `src_pool`, into a new block call
+    /// allocated in `dst_pool`.
+    fn map_block_call(
+        &mut self,
+        block_call: BlockCall,
+        src_pool: &ValueListPool,
+        dst_pool: &mut ValueListPool,
+    ) -> BlockCall {
+        let block = self.map_block(block_call.block(src_pool));
+        let mut values = ValueList::default();
+        values.push(BlockCall::block_to_value(block), dst_pool);
+        for arg in block_call.args_slice(src_pool) {
+            let arg = self.map_value(*arg);
+            values.push(arg, dst_pool);
+        }
+        BlockCall { values }
+    }
+}
+
 /// Wrapper for the context needed to display a [BlockCall] value.
 pub struct DisplayBlockCall<'a> {
     block: BlockCall,
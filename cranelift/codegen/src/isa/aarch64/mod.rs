@@ -92,6 +92,7 @@ impl TargetIsa for AArch64Backend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            isle_rules: emit_result.isle_rules,
         })
     }
 
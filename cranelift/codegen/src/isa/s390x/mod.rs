@@ -92,6 +92,7 @@ impl TargetIsa for S390xBackend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            isle_rules: emit_result.isle_rules,
         })
     }
 
@@ -58,19 +58,21 @@ pub use crate::machinst::buffer::{
     MachTextSectionBuilder, MachTrap,
 };
 pub use crate::machinst::{
-    CompiledCode, Final, MachBuffer, MachBufferFinalized, MachInst, MachInstEmit,
+    CompiledCode, Final, IsleRuleRange, MachBuffer, MachBufferFinalized, MachInst, MachInstEmit,
     MachInstEmitState, MachLabel, Reg, TextSectionBuilder, VCodeConstantData, VCodeConstants,
     Writable,
 };
 
 mod alias_analysis;
 mod bitset;
+mod bounds_check_elim;
 mod constant_hash;
 mod context;
 mod ctxhash;
 mod dce;
 mod egraph;
 mod fx;
+mod inline;
 mod inst_predicates;
 mod isle_prelude;
 mod iterators;
@@ -87,6 +89,7 @@ mod value_label;
 #[cfg(feature = "souper-harvest")]
 mod souper_harvest;
 
+pub use crate::inline::{Inline, InlineCommand};
 pub use crate::result::{CodegenError, CodegenResult, CompileError};
 
 #[cfg(feature = "incremental-cache")]
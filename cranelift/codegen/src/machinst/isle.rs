@@ -47,6 +47,12 @@ macro_rules! isle_lower_prelude_methods {
     () => {
         isle_common_prelude_methods!();
 
+        #[cfg(feature = "isle-rule-info")]
+        #[inline]
+        fn rule_fired(&mut self, rule: &'static str) {
+            self.lower_ctx.rule_fired(rule);
+        }
+
         #[inline]
         fn value_type(&mut self, val: Value) -> Type {
             self.lower_ctx.dfg().value_type(val)
@@ -222,6 +222,11 @@ pub struct Lower<'func, I: VCodeInst> {
     /// Instructions collected for the CLIF inst in progress, in forward order.
     ir_insts: Vec<I>,
 
+    /// The ISLE rules that returned while lowering the current instruction,
+    /// each with the number of instructions in `ir_insts` at that point.
+    /// Only collected if the `machine_code_isle_rule_info` flag is set.
+    fired_rules: Vec<(usize, &'static str)>,
+
     /// The register to use for GetPinnedReg, if any, on this architecture.
     pinned_reg: Option<Reg>,
 
@@ -353,7 +358,7 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         flags: Flags,
     ) -> CodegenResult<Self> {
         let constants = VCodeConstants::with_capacity(f.dfg.constants.len());
-        let vcode = VCodeBuilder::new(
+        let mut vcode = VCodeBuilder::new(
             sigs,
             abi,
             emit_info,
@@ -361,6 +366,9 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
             constants,
             VCodeBuildDirection::Backward,
         );
+        if flags.machine_code_isle_rule_info() {
+            vcode.enable_isle_rule_info();
+        }
 
         let mut vregs = VRegAllocator::new();
 
@@ -464,6 +472,7 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
             cur_scan_entry_color: None,
             cur_inst: None,
             ir_insts: vec![],
+            fired_rules: vec![],
             pinned_reg: None,
             flags,
         })
@@ -924,12 +933,28 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
 
     fn finish_ir_inst(&mut self, loc: RelSourceLoc) {
         self.vcode.set_srcloc(loc);
+        // The rule that lowered the CLIF inst returns last, after any
+        // helper rules it used to construct its instructions. Each
+        // instruction is attributed to the first rule that returned after
+        // it was emitted, which is the innermost rule that produced it.
+        let lowering_rule = self.fired_rules.last().map(|&(_, rule)| rule);
         // The VCodeBuilder builds in reverse order (and reverses at
         // the end), but `ir_insts` is in forward order, so reverse
         // it.
-        for inst in self.ir_insts.drain(..).rev() {
+        for (i, inst) in self.ir_insts.drain(..).enumerate().rev() {
+            if let Some(lowering_rule) = lowering_rule {
+                let inner_rule = self
+                    .fired_rules
+                    .iter()
+                    .find(|&&(count, _)| count > i)
+                    .map_or(lowering_rule, |&(_, rule)| rule);
+                self.vcode.set_isle_rules(Some((lowering_rule, inner_rule)));
+            } else {
+                self.vcode.set_isle_rules(None);
+            }
             self.vcode.push(inst);
         }
+        self.fired_rules.clear();
     }
 
     fn finish_bb(&mut self) {
@@ -1370,6 +1395,14 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         self.ir_insts.push(mach_inst);
     }
 
+    /// Record that an ISLE rule, named by its source location, returned
+    /// while lowering the current instruction.
+    pub fn rule_fired(&mut self, rule: &'static str) {
+        if self.flags.machine_code_isle_rule_info() {
+            self.fired_rules.push((self.ir_insts.len(), rule));
+        }
+    }
+
     /// Indicate that the side-effect of an instruction has been sunk to the
     /// current scan location. This should only be done with the instruction's
     /// original results are not used (i.e., `put_input_in_regs` is not invoked
@@ -350,6 +350,28 @@ pub struct CompiledCodeBase<T: CompilePhase> {
     /// This info is generated only if the `machine_code_cfg_info`
     /// flag is set.
     pub bb_edges: Vec<(CodeOffset, CodeOffset)>,
+    /// Instruction-selection info: the ISLE rules that produced each range
+    /// of the machine code, in order of code offset.
+    ///
+    /// This info is generated only if the `machine_code_isle_rule_info`
+    /// flag is set and the `isle-rule-info` cargo feature is enabled.
+    pub isle_rules: Vec<IsleRuleRange>,
+}
+
+/// The ISLE rules that produced a range of machine code.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "enable-serde", derive(Serialize, Deserialize))]
+pub struct IsleRuleRange {
+    /// Code offset at which the range starts.
+    pub start: CodeOffset,
+    /// Code offset at which the range ends, exclusive.
+    pub end: CodeOffset,
+    /// The rule that lowered the CLIF instruction, e.g. `lower.isle line 12`.
+    pub lowering_rule: String,
+    /// The innermost rule that produced the machine instructions, typically
+    /// a helper constructing them, e.g. `inst.isle line 345`. This is the
+    /// same as `lowering_rule` if that rule produced them directly.
+    pub inner_rule: String,
 }
 
 impl CompiledCodeStencil {
@@ -364,6 +386,7 @@ impl CompiledCodeStencil {
             dynamic_stackslot_offsets: self.dynamic_stackslot_offsets,
             bb_starts: self.bb_starts,
             bb_edges: self.bb_edges,
+            isle_rules: self.isle_rules,
         }
     }
 }
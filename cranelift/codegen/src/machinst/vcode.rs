@@ -87,6 +87,11 @@ pub struct VCode<I: VCodeInst> {
     /// reasonable to keep one of these per instruction.)
     srclocs: Vec<RelSourceLoc>,
 
+    /// The lowering and innermost ISLE rules that produced each instruction,
+    /// if known. Only collected if the `machine_code_isle_rule_info` flag is
+    /// set; otherwise empty.
+    isle_rules: Vec<Option<(&'static str, &'static str)>>,
+
     /// Entry block.
     entry: BlockIndex,
 
@@ -213,6 +218,10 @@ pub struct EmitResult {
 
     /// Stack frame size.
     pub frame_size: u32,
+
+    /// The ISLE rules that produced each range of the machine code. Computed
+    /// only if the `machine_code_isle_rule_info` flag is set.
+    pub isle_rules: Vec<IsleRuleRange>,
 }
 
 /// A builder for a VCode function body.
@@ -255,6 +264,12 @@ pub struct VCodeBuilder<I: VCodeInst> {
     /// Current source location.
     cur_srcloc: RelSourceLoc,
 
+    /// Whether to record the ISLE rules that produced each instruction.
+    isle_rule_info: bool,
+
+    /// Current lowering and innermost ISLE rules.
+    cur_isle_rules: Option<(&'static str, &'static str)>,
+
     /// Debug-value label in-progress map, keyed by label. For each
     /// label, we keep disjoint ranges mapping to vregs. We'll flatten
     /// this into (vreg, range, label) tuples when done.
@@ -290,6 +305,8 @@ impl<I: VCodeInst> VCodeBuilder<I> {
             block_params_start: 0,
             branch_block_arg_succ_start: 0,
             cur_srcloc: Default::default(),
+            isle_rule_info: false,
+            cur_isle_rules: None,
             debug_info: FxHashMap::default(),
         }
     }
@@ -377,6 +394,9 @@ impl<I: VCodeInst> VCodeBuilder<I> {
     pub fn push(&mut self, insn: I) {
         self.vcode.insts.push(insn);
         self.vcode.srclocs.push(self.cur_srcloc);
+        if self.isle_rule_info {
+            self.vcode.isle_rules.push(self.cur_isle_rules);
+        }
     }
 
     /// Add a successor block with branch args.
@@ -390,6 +410,16 @@ impl<I: VCodeInst> VCodeBuilder<I> {
         self.cur_srcloc = srcloc;
     }
 
+    /// Record the ISLE rules that produced each instruction from now on.
+    pub fn enable_isle_rule_info(&mut self) {
+        self.isle_rule_info = true;
+    }
+
+    /// Set the current lowering and innermost ISLE rules.
+    pub fn set_isle_rules(&mut self, rules: Option<(&'static str, &'static str)>) {
+        self.cur_isle_rules = rules;
+    }
+
     /// Add a debug value label to a register.
     pub fn add_value_label(&mut self, reg: Reg, label: ValueLabel) {
         // We'll fix up labels in reverse(). Because we're generating
@@ -470,6 +500,7 @@ impl<I: VCodeInst> VCodeBuilder<I> {
         self.vcode.block_succ_range.reverse();
         self.vcode.insts.reverse();
         self.vcode.srclocs.reverse();
+        self.vcode.isle_rules.reverse();
         // Likewise, branch_block_arg_succ_range is indexed by block
         // so must be reversed.
         self.vcode.branch_block_arg_succ_range.reverse();
@@ -599,6 +630,43 @@ fn is_reftype(ty: Type) -> bool {
 
 const NO_INST_OFFSET: CodeOffset = u32::MAX;
 
+/// Record that the ISLE rules `lowering` and `inner` produced the code in
+/// `start..end`.
+fn record_isle_rules(
+    ranges: &mut Vec<IsleRuleRange>,
+    start: CodeOffset,
+    end: CodeOffset,
+    lowering: &str,
+    inner: &str,
+) {
+    // The `MachBuffer` may have removed branches at the end of the buffer
+    // since the previous ranges were recorded, so clip them to the code that
+    // remains.
+    while let Some(last) = ranges.last_mut() {
+        if last.start >= start {
+            ranges.pop();
+        } else {
+            last.end = last.end.min(start);
+            break;
+        }
+    }
+    if start == end {
+        return;
+    }
+    if let Some(last) = ranges.last_mut() {
+        if last.end == start && last.lowering_rule == lowering && last.inner_rule == inner {
+            last.end = end;
+            return;
+        }
+    }
+    ranges.push(IsleRuleRange {
+        start,
+        end,
+        lowering_rule: String::from(lowering),
+        inner_rule: String::from(inner),
+    });
+}
+
 impl<I: VCodeInst> VCode<I> {
     /// New empty VCode.
     fn new(
@@ -617,6 +685,7 @@ impl<I: VCodeInst> VCode<I> {
             operand_ranges: Vec::with_capacity(10 * n_blocks),
             clobbers: FxHashMap::default(),
             srclocs: Vec::with_capacity(10 * n_blocks),
+            isle_rules: vec![],
             entry: BlockIndex::new(0),
             block_ranges: Vec::with_capacity(n_blocks),
             block_succ_range: Vec::with_capacity(n_blocks),
@@ -786,6 +855,7 @@ impl<I: VCodeInst> VCode<I> {
         let mut state = I::State::new(&self.abi, std::mem::take(ctrl_plane));
 
         let mut disasm = String::new();
+        let mut isle_rules: Vec<IsleRuleRange> = vec![];
 
         if !self.debug_value_labels.is_empty() {
             inst_offsets.resize(self.insts.len(), NO_INST_OFFSET);
@@ -956,6 +1026,7 @@ impl<I: VCodeInst> VCode<I> {
                         // a return, place an epilogue at this point
                         // (and don't emit the return; the actual
                         // epilogue will contain it).
+                        let start = buffer.cur_offset();
                         if self.insts[iix.index()].is_term() == MachTerminator::Ret {
                             for inst in self.abi.gen_epilogue() {
                                 do_emit(&inst, &[], &mut disasm, &mut buffer, &mut state);
@@ -970,6 +1041,15 @@ impl<I: VCodeInst> VCode<I> {
                                 &mut state,
                             );
                         }
+                        if let Some(Some((lowering, inner))) = self.isle_rules.get(iix.index()) {
+                            record_isle_rules(
+                                &mut isle_rules,
+                                start,
+                                buffer.cur_offset(),
+                                lowering,
+                                inner,
+                            );
+                        }
                     }
 
                     InstOrEdit::Edit(Edit::Move { from, to }) => {
@@ -1060,6 +1140,10 @@ impl<I: VCodeInst> VCode<I> {
         *ctrl_plane = state.take_ctrl_plane();
 
         let func_body_len = buffer.cur_offset();
+        isle_rules.retain_mut(|range| {
+            range.end = range.end.min(func_body_len);
+            range.start < range.end
+        });
 
         // Create `bb_edges` and final (filtered) `bb_starts`.
         let mut bb_edges = vec![];
@@ -1097,6 +1181,7 @@ impl<I: VCodeInst> VCode<I> {
             dynamic_stackslot_offsets: self.abi.dynamic_stackslot_offsets().clone(),
             value_labels_ranges,
             frame_size,
+            isle_rules,
         }
     }
 
@@ -1793,6 +1878,7 @@ impl VCodeConstantData {
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloc::string::ToString;
     use std::mem::size_of;
 
     #[test]
@@ -1809,4 +1895,109 @@ mod test {
         // With certain versions of Rust, each `HashMap` in `VCodeConstants` occupied at
         // least 48 bytes, making an empty `VCodeConstants` cost 120 bytes.
     }
+
+    fn range(start: CodeOffset, end: CodeOffset, lowering: &str, inner: &str) -> IsleRuleRange {
+        IsleRuleRange {
+            start,
+            end,
+            lowering_rule: lowering.to_string(),
+            inner_rule: inner.to_string(),
+        }
+    }
+
+    #[test]
+    fn record_isle_rules_merges_adjacent_ranges() {
+        let mut ranges = vec![];
+        record_isle_rules(&mut ranges, 0, 4, "a", "b");
+        record_isle_rules(&mut ranges, 4, 8, "a", "b");
+        record_isle_rules(&mut ranges, 8, 8, "a", "c");
+        record_isle_rules(&mut ranges, 8, 12, "a", "c");
+        record_isle_rules(&mut ranges, 14, 16, "a", "c");
+        assert_eq!(
+            ranges,
+            [
+                range(0, 8, "a", "b"),
+                range(8, 12, "a", "c"),
+                range(14, 16, "a", "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_isle_rules_clips_removed_code() {
+        // The buffer removed the end of the previous range, e.g. a branch.
+        let mut ranges = vec![];
+        record_isle_rules(&mut ranges, 0, 4, "a", "a");
+        record_isle_rules(&mut ranges, 4, 10, "b", "b");
+        record_isle_rules(&mut ranges, 6, 8, "c", "c");
+        assert_eq!(
+            ranges,
+            [
+                range(0, 4, "a", "a"),
+                range(4, 6, "b", "b"),
+                range(6, 8, "c", "c"),
+            ]
+        );
+
+        // Whole ranges were removed.
+        record_isle_rules(&mut ranges, 4, 5, "d", "d");
+        assert_eq!(ranges, [range(0, 4, "a", "a"), range(4, 5, "d", "d")]);
+
+        // Empty code still clips the previous ranges.
+        record_isle_rules(&mut ranges, 2, 2, "e", "e");
+        assert_eq!(ranges, [range(0, 2, "a", "a")]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "isle-rule-info", feature = "x86"))]
+    fn isle_rule_ranges_cover_code() {
+        use crate::cursor::{Cursor, FuncCursor};
+        use crate::ir::{types, AbiParam, Function, InstBuilder, Signature};
+        use crate::isa::{lookup, CallConv};
+        use crate::settings::{self, Configurable};
+        use crate::Context;
+        use target_lexicon::triple;
+
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(Default::default(), sig);
+        let block0 = func.dfg.make_block();
+        let x = func.dfg.append_block_param(block0, types::I64);
+        let y = func.dfg.append_block_param(block0, types::I64);
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        let sum = pos.ins().iadd(x, y);
+        let product = pos.ins().imul(sum, x);
+        pos.ins().return_(&[product]);
+
+        let mut builder = settings::builder();
+        builder.enable("machine_code_isle_rule_info").unwrap();
+        let isa = lookup(triple!("x86_64"))
+            .unwrap()
+            .finish(settings::Flags::new(builder))
+            .unwrap();
+        let mut context = Context::for_function(func);
+        let code = context.compile(&*isa, &mut Default::default()).unwrap();
+
+        let ranges = &code.isle_rules;
+        assert!(!ranges.is_empty());
+        let len = code.buffer.data().len() as CodeOffset;
+        for pair in ranges.windows(2) {
+            assert!(pair[0].end <= pair[1].start, "{ranges:?}");
+        }
+        for range in ranges {
+            assert!(range.start < range.end && range.end <= len, "{ranges:?}");
+            assert!(range.lowering_rule.contains(".isle line "), "{range:?}");
+            assert!(range.inner_rule.contains(".isle line "), "{range:?}");
+        }
+        // `iadd` and `imul` are both lowered by rules of the x64 backend.
+        assert!(
+            ranges
+                .iter()
+                .any(|range| range.lowering_rule.starts_with("lower.isle")),
+            "{ranges:?}"
+        );
+    }
 }
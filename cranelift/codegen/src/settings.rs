@@ -531,6 +531,7 @@ regalloc_verbose_logs = false
 enable_alias_analysis = true
 enable_verifier = true
 enable_pcc = false
+enable_bounds_check_elimination = false
 is_pic = false
 use_colocated_libcalls = false
 enable_float = true
@@ -542,6 +543,7 @@ enable_llvm_abi_extensions = false
 unwind_info = true
 preserve_frame_pointers = false
 machine_code_cfg_info = false
+machine_code_isle_rule_info = false
 enable_probestack = false
 probestack_func_adjusts_sp = false
 enable_jump_tables = true
@@ -63,7 +63,9 @@ define_passes! {
     loop_analysis: "Loop analysis",
     preopt: "Pre-legalization rewriting",
     dce: "Dead code elimination",
+    inline: "Function inlining",
     egraph: "Egraph based optimizations",
+    bounds_check_elim: "Bounds-check elimination",
     gvn: "Global value numbering",
     licm: "Loop invariant code motion",
     unreachable_code: "Remove unreachable blocks",
@@ -0,0 +1,140 @@
+;;! target = "x86_64"
+;;!
+;;! optimize = true
+;;!
+;;! settings = [
+;;!   "enable_heap_access_spectre_mitigation=false",
+;;!   "enable_bounds_check_elimination=true",
+;;!   "opt_level=speed",
+;;! ]
+;;!
+;;! [globals.vmctx]
+;;! type = "i64"
+;;! vmctx = true
+;;!
+;;! [globals.heap_base]
+;;! type = "i64"
+;;! load = { base = "vmctx", offset = 0 }
+;;!
+;;! [globals.heap_bound]
+;;! type = "i64"
+;;! load = { base = "vmctx", offset = 8 }
+;;!
+;;! [[heaps]]
+;;! base = "heap_base"
+;;! min_size = 0x10000
+;;! offset_guard_size = 0
+;;! index_type = "i32"
+;;! style = { kind = "dynamic", bound = "heap_bound" }
+
+(module
+  (memory (export "memory") 1)
+
+  ;; Only the first, widest access needs a bounds check: it implies the
+  ;; checks of the following ones.
+  (func (export "loads") (param i32) (result i32)
+    local.get 0
+    i32.load8_u offset=3
+    local.get 0
+    i32.load8_u offset=1
+    i32.add
+    local.get 0
+    i32.load8_u offset=2
+    i32.add)
+
+  ;; A narrower access first still needs the second check.
+  (func (export "loads_widening") (param i32) (result i32)
+    local.get 0
+    i32.load8_u offset=1
+    local.get 0
+    i32.load8_u offset=3
+    i32.add)
+)
+;; function u0:0(i32, i64 vmctx) -> i32 fast {
+;;     gv0 = vmctx
+;;     gv1 ! range(64, 0x10000, 0xffffffffffffffff) = load.i64 notrap aligned gv0+8
+;;     gv2 = load.i64 notrap aligned gv0
+;;
+;;                                 block0(v0 ! def(v0): i32, v1: i64):
+;;                                     v35 -> v1
+;;                                     v36 -> v1
+;;                                     v37 -> v1
+;;                                     v38 -> v1
+;;                                     v39 -> v1
+;;                                     v40 -> v1
+;; @0046                               v4 ! dynamic_range(64, gv1, gv1) = load.i64 notrap aligned v1+8
+;; @0046                               v3 ! dynamic_range(64, v0, v0) = uextend.i64 v0
+;; @0046                               v5 ! range(64, 0x4, 0x4) = iconst.i64 4
+;; @0046                               v6 ! dynamic_range(64, gv1-0x4, gv1-0x4) = isub v4, v5  ; v5 = 4
+;; @0046                               v7 ! compare(ugt, v0+0x4, gv1) = icmp ugt v3, v6
+;; @0046                               brif v7, block2, block3
+;;
+;;                                 block2 cold:
+;; @0046                               trap heap_oob
+;;
+;;                                 block3:
+;; @0046                               v8 = load.i64 notrap aligned v1
+;; @0046                               v9 = iadd v8, v3
+;; @0046                               v10 = iconst.i64 3
+;; @0046                               v11 = iadd v9, v10  ; v10 = 3
+;; @0046                               v12 = uload8.i32 little heap v11
+;; @004b                               v20 = iconst.i64 1
+;; @004b                               v21 = iadd v9, v20  ; v20 = 1
+;; @004b                               v22 = uload8.i32 little heap v21
+;; @0051                               v31 = iconst.i64 2
+;; @0051                               v32 = iadd v9, v31  ; v31 = 2
+;; @0051                               v33 = uload8.i32 little heap v32
+;; @0055                               jump block1
+;;
+;;                                 block1:
+;; @004e                               v23 = iadd.i32 v12, v22
+;; @0054                               v34 = iadd v23, v33
+;;                                     v2 -> v34
+;; @0055                               return v34
+;; }
+;;
+;; function u0:1(i32, i64 vmctx) -> i32 fast {
+;;     gv0 = vmctx
+;;     gv1 ! range(64, 0x10000, 0xffffffffffffffff) = load.i64 notrap aligned gv0+8
+;;     gv2 = load.i64 notrap aligned gv0
+;;
+;;                                 block0(v0 ! def(v0): i32, v1: i64):
+;;                                     v24 -> v1
+;;                                     v25 -> v1
+;;                                     v26 -> v1
+;;                                     v27 -> v1
+;; @005a                               v4 ! dynamic_range(64, gv1, gv1) = load.i64 notrap aligned v1+8
+;; @005a                               v3 ! dynamic_range(64, v0, v0) = uextend.i64 v0
+;; @005a                               v5 ! range(64, 0x2, 0x2) = iconst.i64 2
+;; @005a                               v6 ! dynamic_range(64, gv1-0x2, gv1-0x2) = isub v4, v5  ; v5 = 2
+;; @005a                               v7 ! compare(ugt, v0+0x2, gv1) = icmp ugt v3, v6
+;; @005a                               brif v7, block2, block3
+;;
+;;                                 block2 cold:
+;; @005a                               trap heap_oob
+;;
+;;                                 block3:
+;; @005a                               v8 = load.i64 notrap aligned v1
+;; @005a                               v9 = iadd v8, v3
+;; @005a                               v10 = iconst.i64 1
+;; @005a                               v11 = iadd v9, v10  ; v10 = 1
+;; @005a                               v12 = uload8.i32 little heap v11
+;; @005f                               v15 ! range(64, 0x4, 0x4) = iconst.i64 4
+;; @005f                               v16 ! dynamic_range(64, gv1-0x4, gv1-0x4) = isub.i64 v4, v15  ; v15 = 4
+;; @005f                               v17 ! compare(ugt, v0+0x4, gv1) = icmp.i64 ugt v3, v16
+;; @005f                               brif v17, block4, block5
+;;
+;;                                 block4 cold:
+;; @005f                               trap heap_oob
+;;
+;;                                 block5:
+;; @005f                               v20 = iconst.i64 3
+;; @005f                               v21 = iadd.i64 v9, v20  ; v20 = 3
+;; @005f                               v22 = uload8.i32 little heap v21
+;; @0063                               jump block1
+;;
+;;                                 block1:
+;; @0062                               v23 = iadd.i32 v12, v22
+;;                                     v2 -> v23
+;; @0063                               return v23
+;; }
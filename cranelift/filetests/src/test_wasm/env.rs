@@ -21,6 +21,7 @@ pub struct ModuleEnv {
     pub config: TestConfig,
     pub heap_access_spectre_mitigation: bool,
     pub proof_carrying_code: bool,
+    pub bounds_check_elimination: bool,
 }
 
 impl ModuleEnv {
@@ -33,6 +34,7 @@ impl ModuleEnv {
                 .flags()
                 .enable_heap_access_spectre_mitigation(),
             proof_carrying_code: target_isa.flags().enable_pcc(),
+            bounds_check_elimination: target_isa.flags().enable_bounds_check_elimination(),
         }
     }
 }
@@ -54,6 +56,7 @@ impl<'data> ModuleEnvironment<'data> for ModuleEnv {
                 self.config.clone(),
                 self.heap_access_spectre_mitigation,
                 self.proof_carrying_code,
+                self.bounds_check_elimination,
             );
             let func_index = FuncIndex::new(
                 self.inner.get_num_func_imports() + self.inner.info.function_bodies.len(),
@@ -248,6 +251,7 @@ pub struct FuncEnv<'a> {
     pub next_heap: usize,
     pub heap_access_spectre_mitigation: bool,
     pub proof_carrying_code: bool,
+    pub bounds_check_elimination: bool,
 }
 
 impl<'a> FuncEnv<'a> {
@@ -257,6 +261,7 @@ impl<'a> FuncEnv<'a> {
         config: TestConfig,
         heap_access_spectre_mitigation: bool,
         proof_carrying_code: bool,
+        bounds_check_elimination: bool,
     ) -> Self {
         let inner = cranelift_wasm::DummyFuncEnvironment::new(mod_info, expected_reachability);
         Self {
@@ -266,6 +271,7 @@ impl<'a> FuncEnv<'a> {
             next_heap: 0,
             heap_access_spectre_mitigation,
             proof_carrying_code,
+            bounds_check_elimination,
         }
     }
 }
@@ -288,6 +294,10 @@ impl<'a> TargetEnvironment for FuncEnv<'a> {
     fn proof_carrying_code(&self) -> bool {
         self.proof_carrying_code
     }
+
+    fn bounds_check_elimination(&self) -> bool {
+        self.bounds_check_elimination
+    }
 }
 
 impl<'a> FuncEnvironment for FuncEnv<'a> {
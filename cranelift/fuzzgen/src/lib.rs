@@ -245,9 +245,11 @@ where
         builder.enable("enable_atomics")?;
         builder.enable("enable_float")?;
 
-        // `machine_code_cfg_info` generates additional metadata for the embedder but this doesn't feed back
-        // into compilation anywhere, we leave it on unconditionally to make sure the generation doesn't panic.
+        // `machine_code_cfg_info` and `machine_code_isle_rule_info` generate additional metadata for the
+        // embedder but this doesn't feed back into compilation anywhere, we leave them on unconditionally
+        // to make sure the generation doesn't panic.
         builder.enable("machine_code_cfg_info")?;
+        builder.enable("machine_code_isle_rule_info")?;
 
         Ok(Flags::new(builder))
     }
@@ -12,6 +12,11 @@ pub struct CodegenOptions {
     /// Do not include the `#![allow(...)]` pragmas in the generated
     /// source. Useful if it must be include!()'d elsewhere.
     pub exclude_global_allow_pragmas: bool,
+
+    /// Call `Context::rule_fired` with the source location of each rule
+    /// whenever its right-hand side is evaluated, so that contexts can
+    /// attribute their work to individual rules.
+    pub rule_tracing: bool,
 }
 
 /// Emit Rust source code for the given type and term environments.
@@ -34,16 +39,18 @@ struct Codegen<'a> {
 struct BodyContext<'a, W> {
     out: &'a mut W,
     ruleset: &'a RuleSet,
+    rule_tracing: bool,
     indent: String,
     is_ref: StableSet<BindingId>,
     is_bound: StableSet<BindingId>,
 }
 
 impl<'a, W: Write> BodyContext<'a, W> {
-    fn new(out: &'a mut W, ruleset: &'a RuleSet) -> Self {
+    fn new(out: &'a mut W, ruleset: &'a RuleSet, rule_tracing: bool) -> Self {
         Self {
             out,
             ruleset,
+            rule_tracing,
             indent: Default::default(),
             is_ref: Default::default(),
             is_bound: Default::default(),
@@ -97,9 +104,10 @@ impl<'a> Codegen<'a> {
         let mut code = String::new();
 
         self.generate_header(&mut code, options);
-        self.generate_ctx_trait(&mut code);
+        self.generate_ctx_trait(&mut code, options);
         self.generate_internal_types(&mut code);
-        self.generate_internal_term_constructors(&mut code).unwrap();
+        self.generate_internal_term_constructors(&mut code, options)
+            .unwrap();
 
         code
     }
@@ -190,7 +198,7 @@ impl<'a> Codegen<'a> {
         .unwrap();
     }
 
-    fn generate_ctx_trait(&self, code: &mut String) {
+    fn generate_ctx_trait(&self, code: &mut String, options: &CodegenOptions) {
         writeln!(code).unwrap();
         writeln!(
             code,
@@ -218,6 +226,19 @@ impl<'a> Codegen<'a> {
                 self.generate_trait_sig(code, "    ", &ext_sig);
             }
         }
+        if options.rule_tracing {
+            writeln!(
+                code,
+                "    /// Called with the location of a rule, such as `lower.isle line 12`,"
+            )
+            .unwrap();
+            writeln!(code, "    /// once its right-hand side has been evaluated.").unwrap();
+            writeln!(
+                code,
+                "    fn rule_fired(&mut self, _rule: &'static str) {{}}"
+            )
+            .unwrap();
+        }
         writeln!(code, "}}").unwrap();
         writeln!(
             code,
@@ -376,10 +397,14 @@ impl<L: Length, C> Length for ContextIterWrapper<L, C> {{
         }
     }
 
-    fn generate_internal_term_constructors(&self, code: &mut String) -> std::fmt::Result {
+    fn generate_internal_term_constructors(
+        &self,
+        code: &mut String,
+        options: &CodegenOptions,
+    ) -> std::fmt::Result {
         for &(termid, ref ruleset) in self.terms.iter() {
             let root = crate::serialize::serialize(ruleset);
-            let mut ctx = BodyContext::new(code, ruleset);
+            let mut ctx = BodyContext::new(code, ruleset, options.rule_tracing);
 
             let termdata = &self.termenv.terms[termid.index()];
             let term_name = &self.typeenv.syms[termdata.name.index()];
@@ -637,15 +662,38 @@ impl<L: Length, C> Length for ContextIterWrapper<L, C> {{
                         &ctx.indent,
                         pos.pretty_print_line(&self.typeenv.filenames)
                     )?;
+                    // The right-hand side may be emitted inline in the return
+                    // expression, so evaluate it before reporting the rule.
+                    if ctx.rule_tracing {
+                        write!(ctx.out, "{}let rule_result = ", &ctx.indent)?;
+                        self.emit_expr(ctx, result)?;
+                        if ctx.is_ref.contains(&result) {
+                            write!(ctx.out, ".clone()")?;
+                        }
+                        writeln!(ctx.out, ";")?;
+                        let filename = &self.typeenv.filenames[pos.file];
+                        let filename = std::path::Path::new(&**filename)
+                            .file_name()
+                            .map_or(filename.to_string(), |f| f.to_string_lossy().into_owned());
+                        writeln!(
+                            ctx.out,
+                            "{}ctx.rule_fired(\"{} line {}\");",
+                            &ctx.indent, filename, pos.line
+                        )?;
+                    }
                     write!(ctx.out, "{}", &ctx.indent)?;
                     match ret_kind {
                         ReturnKind::Plain => write!(ctx.out, "return ")?,
                         ReturnKind::Option => write!(ctx.out, "return Some(")?,
                         ReturnKind::Iterator => write!(ctx.out, "returns.extend(Some(")?,
                     }
-                    self.emit_expr(ctx, result)?;
-                    if ctx.is_ref.contains(&result) {
-                        write!(ctx.out, ".clone()")?;
+                    if ctx.rule_tracing {
+                        write!(ctx.out, "rule_result")?;
+                    } else {
+                        self.emit_expr(ctx, result)?;
+                        if ctx.is_ref.contains(&result) {
+                            write!(ctx.out, ".clone()")?;
+                        }
                     }
                     match ret_kind {
                         ReturnKind::Plain => writeln!(ctx.out, ";")?,
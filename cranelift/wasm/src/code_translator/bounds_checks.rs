@@ -61,6 +61,9 @@ where
     let offset_and_size = offset_plus_size(offset, access_size);
     let spectre_mitigations_enabled = env.heap_access_spectre_mitigation();
     let pcc = env.proof_carrying_code();
+    // Facts on the bounds checks are needed both to verify them and to let
+    // Cranelift remove the redundant ones.
+    let facts = pcc || env.bounds_check_elimination();
 
     let make_compare = |builder: &mut FunctionBuilder,
                         compare_kind: IntCC,
@@ -69,7 +72,7 @@ where
                         rhs: ir::Value,
                         rhs_off: Option<i64>| {
         let result = builder.ins().icmp(compare_kind, lhs, rhs);
-        if pcc {
+        if facts {
             // Name the original value as a def of the SSA value;
             // if the value was extended, name that as well with a
             // dynamic range, overwriting the basic full-range
@@ -87,15 +90,23 @@ where
                 lhs_off.unwrap(),
             ));
             // If the RHS is a symbolic value (v1 or gv1), we can
-            // emit a Compare fact.
+            // emit a Compare fact. The RHS may have been adjusted
+            // down by `rhs_off` to avoid an overflow on the LHS;
+            // move that adjustment back to the LHS so that the
+            // fact compares against the unadjusted symbol.
             if let Some(rhs) = builder.func.dfg.facts[rhs]
                 .as_ref()
                 .and_then(|f| f.as_symbol())
             {
+                let rhs_off = rhs_off.unwrap();
                 builder.func.dfg.facts[result] = Some(Fact::Compare {
                     kind: compare_kind,
-                    lhs: Expr::offset(&Expr::value(orig_index), lhs_off.unwrap()).unwrap(),
-                    rhs: Expr::offset(rhs, rhs_off.unwrap()).unwrap(),
+                    lhs: Expr::offset(
+                        &Expr::value(orig_index),
+                        lhs_off.unwrap().checked_add(rhs_off).unwrap(),
+                    )
+                    .unwrap(),
+                    rhs: Expr::offset(rhs, rhs_off).unwrap(),
                 });
             }
             // Likewise, if the RHS is a constant, we can emit a
@@ -141,7 +152,7 @@ where
         //            index + 1 > bound
         //        ==> index >= bound
         HeapStyle::Dynamic { bound_gv } if offset_and_size == 1 => {
-            let bound = get_dynamic_heap_bound(builder, env, heap);
+            let bound = get_dynamic_heap_bound(builder, env, heap, facts);
             let oob = make_compare(
                 builder,
                 IntCC::UnsignedGreaterThanOrEqual,
@@ -189,7 +200,7 @@ where
         //    multiple fields in the same struct that is in linear memory --
         //    will all emit the same `index > bound` check, which we can GVN.
         HeapStyle::Dynamic { bound_gv } if offset_and_size <= heap.offset_guard_size => {
-            let bound = get_dynamic_heap_bound(builder, env, heap);
+            let bound = get_dynamic_heap_bound(builder, env, heap, facts);
             let oob = make_compare(
                 builder,
                 IntCC::UnsignedGreaterThan,
@@ -219,15 +230,15 @@ where
         //            index + offset + access_size > bound
         //        ==> index > bound - (offset + access_size)
         HeapStyle::Dynamic { bound_gv } if offset_and_size <= heap.min_size.into() => {
-            let bound = get_dynamic_heap_bound(builder, env, heap);
+            let bound = get_dynamic_heap_bound(builder, env, heap, facts);
             let adjustment = offset_and_size as i64;
             let adjustment_value = builder.ins().iconst(env.pointer_type(), adjustment);
-            if pcc {
+            if facts {
                 builder.func.dfg.facts[adjustment_value] =
                     Some(Fact::constant(pointer_bit_width, offset_and_size));
             }
             let adjusted_bound = builder.ins().isub(bound, adjustment_value);
-            if pcc {
+            if facts {
                 builder.func.dfg.facts[adjusted_bound] = Some(Fact::global_value_offset(
                     pointer_bit_width,
                     bound_gv,
@@ -266,7 +277,7 @@ where
                 // Explicit cast from u64 to i64: we just want the raw
                 // bits, and iconst takes an `Imm64`.
                 .iconst(env.pointer_type(), offset_and_size as i64);
-            if pcc {
+            if facts {
                 builder.func.dfg.facts[access_size_val] =
                     Some(Fact::constant(pointer_bit_width, offset_and_size));
             }
@@ -275,14 +286,14 @@ where
                 access_size_val,
                 ir::TrapCode::HeapOutOfBounds,
             );
-            if pcc {
+            if facts {
                 builder.func.dfg.facts[adjusted_index] = Some(Fact::value_offset(
                     pointer_bit_width,
                     index,
                     i64::try_from(offset_and_size).unwrap(),
                 ));
             }
-            let bound = get_dynamic_heap_bound(builder, env, heap);
+            let bound = get_dynamic_heap_bound(builder, env, heap, facts);
             let oob = make_compare(
                 builder,
                 IntCC::UnsignedGreaterThan,
@@ -392,7 +403,7 @@ where
             let adjusted_bound_value = builder
                 .ins()
                 .iconst(env.pointer_type(), adjusted_bound as i64);
-            if pcc {
+            if facts {
                 builder.func.dfg.facts[adjusted_bound_value] =
                     Some(Fact::constant(pointer_bit_width, adjusted_bound));
             }
@@ -424,11 +435,13 @@ fn get_dynamic_heap_bound<Env>(
     builder: &mut FunctionBuilder,
     env: &mut Env,
     heap: &HeapData,
+    facts: bool,
 ) -> ir::Value
 where
     Env: FuncEnvironment + ?Sized,
 {
     let enable_pcc = heap.memory_type.is_some();
+    let pointer_bit_width = u16::try_from(env.pointer_type().bits()).unwrap();
 
     let (value, gv) = match (heap.max_size, &heap.style) {
         // The heap has a constant size, no need to actually load the
@@ -457,13 +470,25 @@ where
         (_, HeapStyle::Static { .. }) => unreachable!("not a dynamic heap"),
     };
 
-    // If proof-carrying code is enabled, apply a fact to the range to
-    // tie it to the GV.
-    if enable_pcc {
-        builder.func.dfg.facts[value] = Some(Fact::global_value(
-            u16::try_from(env.pointer_type().bits()).unwrap(),
-            gv,
-        ));
+    // If proof-carrying code or bounds-check elimination is enabled,
+    // apply a fact to the range to tie it to the GV.
+    if facts {
+        builder.func.dfg.facts[value] = Some(Fact::global_value(pointer_bit_width, gv));
+    }
+
+    // Without a memory type to verify against, record what we know
+    // about the bound's range on the GV itself, so that checks against
+    // indices known to be below the heap's minimum size can be removed.
+    if facts && !enable_pcc && builder.func.global_value_facts[gv].is_none() {
+        let max = match pointer_bit_width {
+            64 => u64::MAX,
+            bits => (1 << bits) - 1,
+        };
+        builder.func.global_value_facts[gv] = Some(Fact::Range {
+            bit_width: pointer_bit_width,
+            min: heap.min_size,
+            max: heap.max_size.unwrap_or(max).min(max),
+        });
     }
 
     value
@@ -265,6 +265,10 @@ impl<'dummy_environment> TargetEnvironment for DummyFuncEnvironment<'dummy_envir
     fn proof_carrying_code(&self) -> bool {
         false
     }
+
+    fn bounds_check_elimination(&self) -> bool {
+        false
+    }
 }
 
 impl<'dummy_environment> FuncEnvironment for DummyFuncEnvironment<'dummy_environment> {
@@ -721,6 +725,10 @@ impl TargetEnvironment for DummyEnvironment {
     fn proof_carrying_code(&self) -> bool {
         false
     }
+
+    fn bounds_check_elimination(&self) -> bool {
+        false
+    }
 }
 
 impl<'data> ModuleEnvironment<'data> for DummyEnvironment {
@@ -54,6 +54,10 @@ pub trait TargetEnvironment: TypeConvert {
     /// Whether to add proof-carrying-code facts to verify memory accesses.
     fn proof_carrying_code(&self) -> bool;
 
+    /// Whether to annotate bounds checks with facts so that Cranelift can
+    /// remove the redundant ones.
+    fn bounds_check_elimination(&self) -> bool;
+
     /// Get the Cranelift integer type to use for native pointers.
     ///
     /// This returns `I64` for 64-bit architectures and `I32` for 32-bit architectures.
@@ -312,7 +312,7 @@ impl FuncTranslationState {
     }
 
     /// Peek at the top of the stack without popping it.
-    pub(crate) fn peek1(&self) -> Value {
+    pub fn peek1(&self) -> Value {
         *self
             .stack
             .last()
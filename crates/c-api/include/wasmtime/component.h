@@ -0,0 +1,624 @@
+/**
+ * \file wasmtime/component.h
+ *
+ * Wasmtime APIs for compiling, linking and instantiating components from the
+ * WebAssembly component model.
+ *
+ * These APIs are only available when the C API is built with the
+ * `component-model` Cargo feature, which is on by default.
+ *
+ * Values passed between the host and components are represented with
+ * #wasmtime_component_val_t, a tagged union mirroring the
+ * `wasmtime::component::Val` type in Rust. Resources can be passed through
+ * host functions and returned from exports, but this API does not yet support
+ * defining host resource types.
+ */
+
+#ifndef WASMTIME_COMPONENT_H
+#define WASMTIME_COMPONENT_H
+
+#include <wasi.h>
+#include <wasm.h>
+#include <wasmtime/error.h>
+#include <wasmtime/module.h>
+#include <wasmtime/store.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * \typedef wasmtime_component_t
+ * \brief Convenience alias for #wasmtime_component
+ *
+ * \struct wasmtime_component
+ * \brief A compiled WebAssembly component.
+ *
+ * This type corresponds to `wasmtime::component::Component` in Rust. A
+ * component is safe to share across threads and is not connected to any
+ * particular store.
+ */
+typedef struct wasmtime_component wasmtime_component_t;
+
+/**
+ * \brief Compiles a WebAssembly component from the binary `buf` of length
+ * `len`.
+ *
+ * On success `NULL` is returned and `*ret` is filled in with a component which
+ * must be deleted with #wasmtime_component_delete. On failure an error is
+ * returned and `*ret` is not modified.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_new(const wasm_engine_t *engine, const uint8_t *buf,
+                       size_t len, wasmtime_component_t **ret);
+
+/**
+ * \brief Deletes a component.
+ */
+WASM_API_EXTERN void wasmtime_component_delete(wasmtime_component_t *component);
+
+/**
+ * \brief Creates a shallow clone of the specified component, increasing the
+ * internal reference count.
+ */
+WASM_API_EXTERN wasmtime_component_t *
+wasmtime_component_clone(const wasmtime_component_t *component);
+
+/**
+ * \brief Serializes a compiled component into a binary blob which can later be
+ * passed to #wasmtime_component_deserialize.
+ *
+ * On success `ret` is filled in with the serialized bytes which must be
+ * deallocated with #wasm_byte_vec_delete.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_serialize(const wasmtime_component_t *component,
+                             wasm_byte_vec_t *ret);
+
+/**
+ * \brief Deserializes a component previously produced by
+ * #wasmtime_component_serialize.
+ *
+ * This function is not safe to call with arbitrary input: the bytes must come
+ * from a trusted source, as with #wasmtime_module_deserialize.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_deserialize(const wasm_engine_t *engine, const uint8_t *buf,
+                               size_t len, wasmtime_component_t **ret);
+
+/**
+ * \brief Same as #wasmtime_component_deserialize, but reads the serialized
+ * component from the file at `path`.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_deserialize_file(const wasm_engine_t *engine,
+                                    const char *path,
+                                    wasmtime_component_t **ret);
+
+/**
+ * \typedef wasmtime_component_resource_any_t
+ * \brief Convenience alias for #wasmtime_component_resource_any
+ *
+ * \struct wasmtime_component_resource_any
+ * \brief An owned or borrowed handle to a resource, corresponding to
+ * `wasmtime::component::ResourceAny` in Rust.
+ */
+typedef struct wasmtime_component_resource_any
+    wasmtime_component_resource_any_t;
+
+/**
+ * \brief Drops the resource within the given store, running its destructor if
+ * it has one.
+ *
+ * This does not deallocate `resource` itself, which must still be deleted with
+ * #wasmtime_component_resource_any_delete.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_component_resource_any_drop(
+    wasmtime_context_t *context,
+    const wasmtime_component_resource_any_t *resource);
+
+/**
+ * \brief Creates a copy of the handle `resource`.
+ *
+ * Both handles refer to the same underlying resource.
+ */
+WASM_API_EXTERN wasmtime_component_resource_any_t *
+wasmtime_component_resource_any_clone(
+    const wasmtime_component_resource_any_t *resource);
+
+/**
+ * \brief Deallocates a resource handle.
+ */
+WASM_API_EXTERN void wasmtime_component_resource_any_delete(
+    wasmtime_component_resource_any_t *resource);
+
+/// \brief Discriminant used in #wasmtime_component_val_t::kind
+typedef uint8_t wasmtime_component_valkind_t;
+
+/// \brief Value of #wasmtime_component_valkind_t for a `bool`
+#define WASMTIME_COMPONENT_BOOL 0
+/// \brief Value of #wasmtime_component_valkind_t for an `s8`
+#define WASMTIME_COMPONENT_S8 1
+/// \brief Value of #wasmtime_component_valkind_t for a `u8`
+#define WASMTIME_COMPONENT_U8 2
+/// \brief Value of #wasmtime_component_valkind_t for an `s16`
+#define WASMTIME_COMPONENT_S16 3
+/// \brief Value of #wasmtime_component_valkind_t for a `u16`
+#define WASMTIME_COMPONENT_U16 4
+/// \brief Value of #wasmtime_component_valkind_t for an `s32`
+#define WASMTIME_COMPONENT_S32 5
+/// \brief Value of #wasmtime_component_valkind_t for a `u32`
+#define WASMTIME_COMPONENT_U32 6
+/// \brief Value of #wasmtime_component_valkind_t for an `s64`
+#define WASMTIME_COMPONENT_S64 7
+/// \brief Value of #wasmtime_component_valkind_t for a `u64`
+#define WASMTIME_COMPONENT_U64 8
+/// \brief Value of #wasmtime_component_valkind_t for a `float32`
+#define WASMTIME_COMPONENT_FLOAT32 9
+/// \brief Value of #wasmtime_component_valkind_t for a `float64`
+#define WASMTIME_COMPONENT_FLOAT64 10
+/// \brief Value of #wasmtime_component_valkind_t for a `char`
+#define WASMTIME_COMPONENT_CHAR 11
+/// \brief Value of #wasmtime_component_valkind_t for a `string`
+#define WASMTIME_COMPONENT_STRING 12
+/// \brief Value of #wasmtime_component_valkind_t for a `list`
+#define WASMTIME_COMPONENT_LIST 13
+/// \brief Value of #wasmtime_component_valkind_t for a `record`
+#define WASMTIME_COMPONENT_RECORD 14
+/// \brief Value of #wasmtime_component_valkind_t for a `tuple`
+#define WASMTIME_COMPONENT_TUPLE 15
+/// \brief Value of #wasmtime_component_valkind_t for a `variant`
+#define WASMTIME_COMPONENT_VARIANT 16
+/// \brief Value of #wasmtime_component_valkind_t for an `enum`
+#define WASMTIME_COMPONENT_ENUM 17
+/// \brief Value of #wasmtime_component_valkind_t for an `option`
+#define WASMTIME_COMPONENT_OPTION 18
+/// \brief Value of #wasmtime_component_valkind_t for a `result`
+#define WASMTIME_COMPONENT_RESULT 19
+/// \brief Value of #wasmtime_component_valkind_t for `flags`
+#define WASMTIME_COMPONENT_FLAGS 20
+/// \brief Value of #wasmtime_component_valkind_t for an `own` or `borrow`
+/// resource handle
+#define WASMTIME_COMPONENT_RESOURCE 21
+
+struct wasmtime_component_val;
+struct wasmtime_component_val_record_field;
+
+/// \brief A vector of component values.
+typedef struct wasmtime_component_val_vec {
+  /// Number of values in `data`.
+  size_t size;
+  /// Pointer to the values.
+  struct wasmtime_component_val *data;
+} wasmtime_component_val_vec_t;
+
+/// \brief The fields of a record value, in the order of the record's type.
+typedef struct wasmtime_component_val_record {
+  /// Number of fields in `data`.
+  size_t size;
+  /// Pointer to the fields.
+  struct wasmtime_component_val_record_field *data;
+} wasmtime_component_val_record_t;
+
+/// \brief The names of the flags which are set in a flags value.
+typedef struct wasmtime_component_val_flags {
+  /// Number of names in `data`.
+  size_t size;
+  /// Pointer to the names.
+  wasm_name_t *data;
+} wasmtime_component_val_flags_t;
+
+/// \brief Payload of a variant value.
+typedef struct wasmtime_component_val_variant {
+  /// Name of the case of the variant.
+  wasm_name_t discriminant;
+  /// Heap-allocated payload of the case, or `NULL` if the case has no payload.
+  struct wasmtime_component_val *val;
+} wasmtime_component_val_variant_t;
+
+/// \brief Payload of a result value.
+typedef struct wasmtime_component_val_result {
+  /// Whether this is the `ok` case or the `err` case.
+  bool is_ok;
+  /// Heap-allocated payload of the case, or `NULL` if the case has no payload.
+  struct wasmtime_component_val *val;
+} wasmtime_component_val_result_t;
+
+/**
+ * \typedef wasmtime_component_valunion_t
+ * \brief Convenience alias for #wasmtime_component_valunion
+ *
+ * \union wasmtime_component_valunion
+ * \brief Container for the payload of a #wasmtime_component_val_t.
+ */
+typedef union wasmtime_component_valunion {
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_BOOL
+  bool boolean;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_S8
+  int8_t s8;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_U8
+  uint8_t u8;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_S16
+  int16_t s16;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_U16
+  uint16_t u16;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_S32
+  int32_t s32;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_U32
+  uint32_t u32;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_S64
+  int64_t s64;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_U64
+  uint64_t u64;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_FLOAT32
+  float float32;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_FLOAT64
+  double float64;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_CHAR,
+  /// a Unicode scalar value.
+  uint32_t character;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_STRING, UTF-8 encoded.
+  wasm_name_t string;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_LIST
+  wasmtime_component_val_vec_t list;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_RECORD
+  wasmtime_component_val_record_t record;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_TUPLE
+  wasmtime_component_val_vec_t tuple;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_VARIANT
+  wasmtime_component_val_variant_t variant;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_ENUM,
+  /// the name of the case.
+  wasm_name_t enumeration;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_OPTION, a heap-allocated value or `NULL` for `none`.
+  struct wasmtime_component_val *option;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_RESULT
+  wasmtime_component_val_result_t result;
+  /// Field used if #wasmtime_component_val_t::kind is #WASMTIME_COMPONENT_FLAGS
+  wasmtime_component_val_flags_t flags;
+  /// Field used if #wasmtime_component_val_t::kind is
+  /// #WASMTIME_COMPONENT_RESOURCE, an owned non-`NULL` handle.
+  wasmtime_component_resource_any_t *resource;
+} wasmtime_component_valunion_t;
+
+/**
+ * \typedef wasmtime_component_val_t
+ * \brief Convenience alias for #wasmtime_component_val
+ *
+ * \struct wasmtime_component_val
+ * \brief A value passed to or returned from a component function.
+ *
+ * Values own all of their contents, including nested values which are
+ * allocated on the heap, and must be deallocated with
+ * #wasmtime_component_val_delete. Heap-allocated nested values must be
+ * created with #wasmtime_component_val_new.
+ */
+typedef struct wasmtime_component_val {
+  /// Discriminant of which field of `of` is valid.
+  wasmtime_component_valkind_t kind;
+  /// Payload of this value.
+  wasmtime_component_valunion_t of;
+} wasmtime_component_val_t;
+
+/// \brief A named field of a record value.
+typedef struct wasmtime_component_val_record_field {
+  /// Name of the field.
+  wasm_name_t name;
+  /// Value of the field.
+  wasmtime_component_val_t val;
+} wasmtime_component_val_record_field_t;
+
+/**
+ * \brief Allocates a new value on the heap, initialized to `false`, suitable
+ * for use as the payload of an option, variant or result.
+ *
+ * The returned value is freed with #wasmtime_component_val_free.
+ */
+WASM_API_EXTERN wasmtime_component_val_t *wasmtime_component_val_new(void);
+
+/**
+ * \brief Deallocates a heap-allocated value and everything it owns.
+ */
+WASM_API_EXTERN void wasmtime_component_val_free(wasmtime_component_val_t *val);
+
+/**
+ * \brief Deep-copies `val` into the uninitialized `out`.
+ */
+WASM_API_EXTERN void
+wasmtime_component_val_clone(const wasmtime_component_val_t *val,
+                             wasmtime_component_val_t *out);
+
+/**
+ * \brief Deallocates everything owned by `val`, resetting it to `false`.
+ */
+WASM_API_EXTERN void wasmtime_component_val_delete(wasmtime_component_val_t *val);
+
+/// \brief Allocates a vector of `size` values, copied from `ptr`.
+WASM_API_EXTERN void
+wasmtime_component_val_vec_new(wasmtime_component_val_vec_t *out, size_t size,
+                               const wasmtime_component_val_t *ptr);
+/// \brief Initializes an empty vector of values.
+WASM_API_EXTERN void
+wasmtime_component_val_vec_new_empty(wasmtime_component_val_vec_t *out);
+/// \brief Allocates a vector of `size` values, each initialized to `false`.
+WASM_API_EXTERN void
+wasmtime_component_val_vec_new_uninitialized(wasmtime_component_val_vec_t *out,
+                                             size_t size);
+/// \brief Deep-copies a vector of values.
+WASM_API_EXTERN void
+wasmtime_component_val_vec_copy(wasmtime_component_val_vec_t *out,
+                                const wasmtime_component_val_vec_t *src);
+/// \brief Deallocates a vector of values and everything it owns.
+WASM_API_EXTERN void
+wasmtime_component_val_vec_delete(wasmtime_component_val_vec_t *vec);
+
+/// \brief Allocates a list of `size` record fields, copied from `ptr`.
+WASM_API_EXTERN void wasmtime_component_val_record_new(
+    wasmtime_component_val_record_t *out, size_t size,
+    const wasmtime_component_val_record_field_t *ptr);
+/// \brief Initializes an empty list of record fields.
+WASM_API_EXTERN void
+wasmtime_component_val_record_new_empty(wasmtime_component_val_record_t *out);
+/// \brief Allocates a list of `size` empty record fields.
+WASM_API_EXTERN void wasmtime_component_val_record_new_uninitialized(
+    wasmtime_component_val_record_t *out, size_t size);
+/// \brief Deep-copies a list of record fields.
+WASM_API_EXTERN void
+wasmtime_component_val_record_copy(wasmtime_component_val_record_t *out,
+                                   const wasmtime_component_val_record_t *src);
+/// \brief Deallocates a list of record fields and everything it owns.
+WASM_API_EXTERN void
+wasmtime_component_val_record_delete(wasmtime_component_val_record_t *vec);
+
+/// \brief Allocates a list of `size` flag names, copied from `ptr`.
+WASM_API_EXTERN void
+wasmtime_component_val_flags_new(wasmtime_component_val_flags_t *out,
+                                 size_t size, const wasm_name_t *ptr);
+/// \brief Initializes an empty list of flag names.
+WASM_API_EXTERN void
+wasmtime_component_val_flags_new_empty(wasmtime_component_val_flags_t *out);
+/// \brief Allocates a list of `size` empty flag names.
+WASM_API_EXTERN void wasmtime_component_val_flags_new_uninitialized(
+    wasmtime_component_val_flags_t *out, size_t size);
+/// \brief Deep-copies a list of flag names.
+WASM_API_EXTERN void
+wasmtime_component_val_flags_copy(wasmtime_component_val_flags_t *out,
+                                  const wasmtime_component_val_flags_t *src);
+/// \brief Deallocates a list of flag names.
+WASM_API_EXTERN void
+wasmtime_component_val_flags_delete(wasmtime_component_val_flags_t *vec);
+
+/**
+ * \typedef wasmtime_component_func_t
+ * \brief Convenience alias for #wasmtime_component_func
+ *
+ * \struct wasmtime_component_func
+ * \brief A function exported from a component instance.
+ *
+ * Functions are owned by the store they were exported from and may only be
+ * used with that store.
+ */
+typedef struct wasmtime_component_func wasmtime_component_func_t;
+
+/**
+ * \brief Deletes a handle to a function.
+ */
+WASM_API_EXTERN void wasmtime_component_func_delete(wasmtime_component_func_t *func);
+
+/**
+ * \brief Returns the number of parameters the function takes.
+ */
+WASM_API_EXTERN size_t wasmtime_component_func_param_arity(
+    wasmtime_context_t *context, const wasmtime_component_func_t *func);
+
+/**
+ * \brief Returns the number of results the function returns.
+ */
+WASM_API_EXTERN size_t wasmtime_component_func_result_arity(
+    wasmtime_context_t *context, const wasmtime_component_func_t *func);
+
+/**
+ * \brief Calls a component function.
+ *
+ * The `args` are converted to the function's parameter types, which fails if
+ * their shapes don't match. `nresults` must be the function's result arity,
+ * and on success `results` is initialized with values that must be
+ * deallocated with #wasmtime_component_val_delete. The `args` are not taken
+ * ownership of.
+ *
+ * The component's `post-return` function, if any, is run automatically after
+ * the call completes.
+ *
+ * Returns an error if the call fails or traps, in which case `results` is not
+ * initialized.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_component_func_call(
+    wasmtime_context_t *context, const wasmtime_component_func_t *func,
+    const wasmtime_component_val_t *args, size_t nargs,
+    wasmtime_component_val_t *results, size_t nresults);
+
+/**
+ * \typedef wasmtime_component_instance_t
+ * \brief Convenience alias for #wasmtime_component_instance
+ *
+ * \struct wasmtime_component_instance
+ * \brief An instantiated component.
+ */
+typedef struct wasmtime_component_instance wasmtime_component_instance_t;
+
+/**
+ * \brief Deletes a handle to a component instance.
+ *
+ * The instance itself remains alive within its store.
+ */
+WASM_API_EXTERN void
+wasmtime_component_instance_delete(wasmtime_component_instance_t *instance);
+
+/**
+ * \brief Looks up an exported function.
+ *
+ * If `instance_name_len` is 0 the function `name` is looked up among the root
+ * exports of the component, otherwise it's looked up within the exported
+ * instance `instance_name`, for example `wasi:cli/run@0.2.0`.
+ *
+ * Returns `true` and fills in `*ret` with a function which must be deleted
+ * with #wasmtime_component_func_delete if the export was found, or `false`
+ * otherwise.
+ */
+WASM_API_EXTERN bool wasmtime_component_instance_get_func(
+    const wasmtime_component_instance_t *instance, wasmtime_context_t *context,
+    const char *instance_name, size_t instance_name_len, const char *name,
+    size_t name_len, wasmtime_component_func_t **ret);
+
+/**
+ * \typedef wasmtime_component_linker_t
+ * \brief Convenience alias for #wasmtime_component_linker
+ *
+ * \struct wasmtime_component_linker
+ * \brief Name-based linker used to instantiate components, corresponding to
+ * `wasmtime::component::Linker` in Rust.
+ */
+typedef struct wasmtime_component_linker wasmtime_component_linker_t;
+
+/**
+ * \typedef wasmtime_component_linker_instance_t
+ * \brief Convenience alias for #wasmtime_component_linker_instance
+ *
+ * \struct wasmtime_component_linker_instance
+ * \brief A view of one instance namespace within a
+ * #wasmtime_component_linker_t used to define items.
+ *
+ * This borrows the linker it was created from: the linker must not be used,
+ * and no other linker instance may be created from it, until this object has
+ * been deleted with #wasmtime_component_linker_instance_delete.
+ */
+typedef struct wasmtime_component_linker_instance
+    wasmtime_component_linker_instance_t;
+
+/**
+ * \brief Creates a new linker for components in the specified engine.
+ */
+WASM_API_EXTERN wasmtime_component_linker_t *
+wasmtime_component_linker_new(const wasm_engine_t *engine);
+
+/**
+ * \brief Deletes a component linker.
+ */
+WASM_API_EXTERN void
+wasmtime_component_linker_delete(wasmtime_component_linker_t *linker);
+
+/**
+ * \brief Configures whether later definitions may shadow previous definitions.
+ *
+ * By default this setting is `false`.
+ */
+WASM_API_EXTERN void
+wasmtime_component_linker_allow_shadowing(wasmtime_component_linker_t *linker,
+                                          bool allow_shadowing);
+
+/**
+ * \brief Returns the root instance of the linker, used to define top-level
+ * imports.
+ *
+ * The returned object must be deleted with
+ * #wasmtime_component_linker_instance_delete before the linker is used again.
+ */
+WASM_API_EXTERN wasmtime_component_linker_instance_t *
+wasmtime_component_linker_root(wasmtime_component_linker_t *linker);
+
+/**
+ * \brief Deletes a linker instance, releasing its borrow of the linker.
+ */
+WASM_API_EXTERN void wasmtime_component_linker_instance_delete(
+    wasmtime_component_linker_instance_t *linker_instance);
+
+/**
+ * \brief Defines, or looks up, the nested instance `name`.
+ *
+ * On success `*ret` is filled in with a new linker instance which borrows
+ * `linker_instance` and must be deleted before `linker_instance` is used again.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_linker_instance_add_instance(
+    wasmtime_component_linker_instance_t *linker_instance, const char *name,
+    size_t name_len, wasmtime_component_linker_instance_t **ret);
+
+/**
+ * \brief Defines the core wasm module `name` within this instance.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_component_linker_instance_add_module(
+    wasmtime_component_linker_instance_t *linker_instance, const char *name,
+    size_t name_len, const wasmtime_module_t *module);
+
+/**
+ * \brief Callback signature for host functions defined with
+ * #wasmtime_component_linker_instance_add_func.
+ *
+ * The `args` are owned by the caller and only valid for the duration of the
+ * call. The `results` are initialized to `false` and must be overwritten with
+ * values of the function's result types; ownership of them is taken once the
+ * callback returns. Returning an error traps the calling component.
+ */
+typedef wasmtime_error_t *(*wasmtime_component_func_callback_t)(
+    void *env, wasmtime_context_t *context, const wasmtime_component_val_t *args,
+    size_t nargs, wasmtime_component_val_t *results, size_t nresults);
+
+/**
+ * \brief Defines a host function `name` within this instance.
+ *
+ * The type of the function is taken from the import of the same name in
+ * `component`, which is used to convert results returned from `callback`.
+ * The `finalizer`, if not `NULL`, is called with `data` once the definition
+ * is dropped.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_component_linker_instance_add_func(
+    wasmtime_component_linker_instance_t *linker_instance,
+    const wasmtime_component_t *component, const char *name, size_t name_len,
+    wasmtime_component_func_callback_t callback, void *data,
+    void (*finalizer)(void *));
+
+/**
+ * \brief Adds the WASI preview2 command interfaces to the linker.
+ *
+ * Components instantiated with this linker use the WASI context of their
+ * store, see #wasmtime_context_set_wasip2.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_component_linker_add_wasip2(wasmtime_component_linker_t *linker);
+
+/**
+ * \brief Configures WASI preview2 for components instantiated within the
+ * given store.
+ *
+ * This takes ownership of the `wasi` argument. Preopened sockets are not
+ * supported and return an error.
+ *
+ * Stores which aren't configured with this function use an empty context,
+ * with no arguments, environment variables or preopened directories, a
+ * closed stdin, and stdout and stderr which discard their output.
+ */
+WASM_API_EXTERN wasmtime_error_t *
+wasmtime_context_set_wasip2(wasmtime_context_t *context, wasi_config_t *wasi);
+
+/**
+ * \brief Instantiates `component` within the given store.
+ *
+ * On success `*ret` is filled in with an instance which must be deleted with
+ * #wasmtime_component_instance_delete.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_component_linker_instantiate(
+    const wasmtime_component_linker_t *linker, wasmtime_context_t *context,
+    const wasmtime_component_t *component, wasmtime_component_instance_t **ret);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif
+
+#endif // WASMTIME_COMPONENT_H
@@ -213,6 +213,15 @@ WASMTIME_CONFIG_PROP(void, wasm_multi_memory, bool)
  */
 WASMTIME_CONFIG_PROP(void, wasm_memory64, bool)
 
+/**
+ * \brief Configures whether the WebAssembly component model proposal is
+ * enabled.
+ *
+ * This setting is `false` by default and is only available when the C API is
+ * built with the `component-model` feature.
+ */
+WASMTIME_CONFIG_PROP(void, wasm_component_model, bool)
+
 /**
  * \brief Configures how JIT code will be compiled.
  *
@@ -0,0 +1,80 @@
+use crate::{error_from_panic, wasmtime_component_val_t, wasmtime_error_t, CStoreContextMut};
+use anyhow::{bail, Result};
+use std::mem::MaybeUninit;
+use std::panic::{self, AssertUnwindSafe};
+use wasmtime::component::{Func, Val};
+
+/// An exported function of a component instance.
+#[derive(Clone)]
+pub struct wasmtime_component_func_t {
+    pub(crate) func: Func,
+}
+
+wasmtime_c_api_macros::declare_own!(wasmtime_component_func_t);
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_func_param_arity(
+    store: CStoreContextMut<'_>,
+    func: &wasmtime_component_func_t,
+) -> usize {
+    func.func.params(store).len()
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_func_result_arity(
+    store: CStoreContextMut<'_>,
+    func: &wasmtime_component_func_t,
+) -> usize {
+    func.func.results(store).len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_component_func_call(
+    mut store: CStoreContextMut<'_>,
+    func: &wasmtime_component_func_t,
+    args: *const wasmtime_component_val_t,
+    nargs: usize,
+    results: *mut MaybeUninit<wasmtime_component_val_t>,
+    nresults: usize,
+) -> Option<Box<wasmtime_error_t>> {
+    let args = crate::slice_from_raw_parts(args, nargs);
+    let results = crate::slice_from_raw_parts_mut(results, nresults);
+
+    // Like `wasmtime_func_call`, insulate the caller from Rust panics by
+    // turning them into errors.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        call(&mut store, &func.func, args, results)
+    }));
+    match result {
+        Ok(result) => crate::handle_result(result, |()| {}),
+        Err(panic) => Some(Box::new(wasmtime_error_t::from(error_from_panic(panic)))),
+    }
+}
+
+fn call(
+    store: &mut CStoreContextMut<'_>,
+    func: &Func,
+    args: &[wasmtime_component_val_t],
+    results: &mut [MaybeUninit<wasmtime_component_val_t>],
+) -> Result<()> {
+    let params = func.params(&*store);
+    if params.len() != args.len() {
+        bail!("expected {} arguments, got {}", params.len(), args.len());
+    }
+    let nresults = func.results(&*store).len();
+    if nresults != results.len() {
+        bail!("expected {} results, got {}", nresults, results.len());
+    }
+    let args = args
+        .iter()
+        .zip(params.iter())
+        .map(|(arg, ty)| arg.to_val(ty))
+        .collect::<Result<Vec<_>>>()?;
+    let mut vals = vec![Val::Bool(false); nresults];
+    func.call(&mut *store, &args, &mut vals)?;
+    func.post_return(&mut *store)?;
+    for (slot, val) in results.iter_mut().zip(vals.iter()) {
+        crate::initialize(slot, wasmtime_component_val_t::from_val(val));
+    }
+    Ok(())
+}
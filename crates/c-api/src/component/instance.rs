@@ -0,0 +1,50 @@
+use crate::{wasmtime_component_func_t, CStoreContextMut};
+use std::str;
+use wasmtime::component::Instance;
+
+/// An instantiated component.
+#[derive(Clone)]
+pub struct wasmtime_component_instance_t {
+    pub(crate) instance: Instance,
+}
+
+wasmtime_c_api_macros::declare_own!(wasmtime_component_instance_t);
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_component_instance_get_func(
+    instance: &wasmtime_component_instance_t,
+    mut store: CStoreContextMut<'_>,
+    instance_name: *const u8,
+    instance_name_len: usize,
+    name: *const u8,
+    name_len: usize,
+    out: &mut *mut wasmtime_component_func_t,
+) -> bool {
+    let name = match str::from_utf8(crate::slice_from_raw_parts(name, name_len)) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let mut exports = instance.instance.exports(&mut store);
+    let func = if instance_name_len == 0 {
+        exports.root().func(name)
+    } else {
+        let instance_name = match str::from_utf8(crate::slice_from_raw_parts(
+            instance_name,
+            instance_name_len,
+        )) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
+        match exports.instance(instance_name) {
+            Some(mut instance) => instance.func(name),
+            None => return false,
+        }
+    };
+    match func {
+        Some(func) => {
+            *out = Box::into_raw(Box::new(wasmtime_component_func_t { func }));
+            true
+        }
+        None => false,
+    }
+}
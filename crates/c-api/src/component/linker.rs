@@ -0,0 +1,166 @@
+use crate::linker::to_str;
+use crate::{
+    bad_utf8, handle_result, wasm_engine_t, wasmtime_component_instance_t, wasmtime_component_t,
+    wasmtime_component_val_t, wasmtime_error_t, wasmtime_module_t, CStoreContextMut, StoreData,
+};
+use anyhow::Result;
+use std::ffi::c_void;
+use std::mem;
+use std::str;
+use wasmtime::component::{Linker, LinkerInstance, Type, Val};
+
+pub struct wasmtime_component_linker_t {
+    pub(crate) linker: Linker<StoreData>,
+}
+
+wasmtime_c_api_macros::declare_own!(wasmtime_component_linker_t);
+
+/// A borrowed view of one instance within a `wasmtime_component_linker_t`.
+///
+/// The lifetime here is a lie: the linker this was created from must not be
+/// used or deleted while this value is alive, which is documented in the C
+/// header.
+pub struct wasmtime_component_linker_instance_t {
+    instance: LinkerInstance<'static, StoreData>,
+}
+
+wasmtime_c_api_macros::declare_own!(wasmtime_component_linker_instance_t);
+
+pub type wasmtime_component_func_callback_t = extern "C" fn(
+    *mut c_void,
+    CStoreContextMut<'_>,
+    *const wasmtime_component_val_t,
+    usize,
+    *mut wasmtime_component_val_t,
+    usize,
+) -> Option<Box<wasmtime_error_t>>;
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_linker_new(
+    engine: &wasm_engine_t,
+) -> Box<wasmtime_component_linker_t> {
+    Box::new(wasmtime_component_linker_t {
+        linker: Linker::new(&engine.engine),
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_linker_allow_shadowing(
+    linker: &mut wasmtime_component_linker_t,
+    allow_shadowing: bool,
+) {
+    linker.linker.allow_shadowing(allow_shadowing);
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_linker_root(
+    linker: &mut wasmtime_component_linker_t,
+) -> Box<wasmtime_component_linker_instance_t> {
+    let instance = linker.linker.root();
+    // SAFETY: see the documentation on `wasmtime_component_linker_instance_t`.
+    let instance = unsafe {
+        mem::transmute::<LinkerInstance<'_, StoreData>, LinkerInstance<'static, StoreData>>(
+            instance,
+        )
+    };
+    Box::new(wasmtime_component_linker_instance_t { instance })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_component_linker_instance_add_instance(
+    linker_instance: &mut wasmtime_component_linker_instance_t,
+    name: *const u8,
+    name_len: usize,
+    out: &mut *mut wasmtime_component_linker_instance_t,
+) -> Option<Box<wasmtime_error_t>> {
+    let name = to_str!(name, name_len);
+    handle_result(linker_instance.instance.instance(name), |instance| {
+        let instance = mem::transmute::<
+            LinkerInstance<'_, StoreData>,
+            LinkerInstance<'static, StoreData>,
+        >(instance);
+        *out = Box::into_raw(Box::new(wasmtime_component_linker_instance_t { instance }));
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_component_linker_instance_add_module(
+    linker_instance: &mut wasmtime_component_linker_instance_t,
+    name: *const u8,
+    name_len: usize,
+    module: &wasmtime_module_t,
+) -> Option<Box<wasmtime_error_t>> {
+    let name = to_str!(name, name_len);
+    handle_result(
+        linker_instance.instance.module(name, &module.module),
+        |()| {},
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_component_linker_instance_add_func(
+    linker_instance: &mut wasmtime_component_linker_instance_t,
+    component: &wasmtime_component_t,
+    name: *const u8,
+    name_len: usize,
+    callback: wasmtime_component_func_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+) -> Option<Box<wasmtime_error_t>> {
+    let name = to_str!(name, name_len);
+    let foreign = crate::ForeignData { data, finalizer };
+    let func = move |store: CStoreContextMut<'_>,
+                     params: &[Val],
+                     result_tys: &[Type],
+                     results: &mut [Val]|
+          -> Result<()> {
+        let _ = &foreign; // move entire foreign into this closure
+        let params = params
+            .iter()
+            .map(wasmtime_component_val_t::from_val)
+            .collect::<Vec<_>>();
+        let mut out = vec![wasmtime_component_val_t::default(); results.len()];
+        if let Some(err) = callback(
+            foreign.data,
+            store,
+            params.as_ptr(),
+            params.len(),
+            out.as_mut_ptr(),
+            out.len(),
+        ) {
+            return Err((*err).into());
+        }
+        for ((slot, val), ty) in results.iter_mut().zip(&out).zip(result_tys) {
+            *slot = val.to_val(ty)?;
+        }
+        Ok(())
+    };
+    handle_result(
+        linker_instance
+            .instance
+            .func_new_with_result_types(&component.component, name, func),
+        |()| {},
+    )
+}
+
+#[cfg(feature = "wasi")]
+#[no_mangle]
+pub extern "C" fn wasmtime_component_linker_add_wasip2(
+    linker: &mut wasmtime_component_linker_t,
+) -> Option<Box<wasmtime_error_t>> {
+    let result = wasmtime_wasi::preview2::command::sync::add_to_linker(&mut linker.linker);
+    handle_result(result, |()| {})
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_linker_instantiate(
+    linker: &wasmtime_component_linker_t,
+    store: CStoreContextMut<'_>,
+    component: &wasmtime_component_t,
+    out: &mut *mut wasmtime_component_instance_t,
+) -> Option<Box<wasmtime_error_t>> {
+    let result = linker.linker.instantiate(store, &component.component);
+    handle_result(result, |instance| {
+        *out = Box::into_raw(Box::new(wasmtime_component_instance_t { instance }));
+    })
+}
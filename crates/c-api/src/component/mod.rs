@@ -0,0 +1,14 @@
+//! Implementation of the component model portions of the C API, documented in
+//! `include/wasmtime/component.h`.
+
+mod component;
+mod func;
+mod instance;
+mod linker;
+mod val;
+
+pub use self::component::*;
+pub use self::func::*;
+pub use self::instance::*;
+pub use self::linker::*;
+pub use self::val::*;
@@ -0,0 +1,484 @@
+use crate::{
+    wasm_name_t, wasmtime_component_val_flags_t, wasmtime_component_val_record_t,
+    wasmtime_component_val_vec_t, wasmtime_error_t, CStoreContextMut,
+};
+use anyhow::{anyhow, bail, Result};
+use std::mem::MaybeUninit;
+use wasmtime::component::{ResourceAny, Type, Val};
+
+/// C representation of a component model value, mirroring
+/// `wasmtime::component::Val`.
+///
+/// Compound values own their contents which are recursively deallocated with
+/// `wasmtime_component_val_delete`.
+#[repr(C, u8)]
+#[derive(Clone)]
+pub enum wasmtime_component_val_t {
+    Bool(bool),
+    S8(i8),
+    U8(u8),
+    S16(i16),
+    U16(u16),
+    S32(i32),
+    U32(u32),
+    S64(i64),
+    U64(u64),
+    Float32(f32),
+    Float64(f64),
+    Char(u32),
+    String(wasm_name_t),
+    List(wasmtime_component_val_vec_t),
+    Record(wasmtime_component_val_record_t),
+    Tuple(wasmtime_component_val_vec_t),
+    Variant(wasmtime_component_val_variant_t),
+    Enum(wasm_name_t),
+    Option(Option<Box<wasmtime_component_val_t>>),
+    Result(wasmtime_component_val_result_t),
+    Flags(wasmtime_component_val_flags_t),
+    Resource(Box<wasmtime_component_resource_any_t>),
+}
+
+#[repr(C)]
+#[derive(Clone, Default)]
+pub struct wasmtime_component_val_record_field_t {
+    pub name: wasm_name_t,
+    pub val: wasmtime_component_val_t,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wasmtime_component_val_variant_t {
+    pub discriminant: wasm_name_t,
+    pub val: Option<Box<wasmtime_component_val_t>>,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wasmtime_component_val_result_t {
+    pub is_ok: bool,
+    pub val: Option<Box<wasmtime_component_val_t>>,
+}
+
+/// An opaque handle to a resource owned by, or borrowed from, a component
+/// instance.
+#[derive(Clone)]
+pub struct wasmtime_component_resource_any_t {
+    pub(crate) resource: ResourceAny,
+}
+
+wasmtime_c_api_macros::declare_own!(wasmtime_component_resource_any_t);
+
+impl Default for wasmtime_component_val_t {
+    fn default() -> Self {
+        wasmtime_component_val_t::Bool(false)
+    }
+}
+
+fn name(s: &str) -> wasm_name_t {
+    wasm_name_t::from_name(s.to_string())
+}
+
+fn str_of(name: &wasm_name_t) -> Result<&str> {
+    std::str::from_utf8(name.as_slice()).map_err(|_| anyhow!("input was not valid utf-8"))
+}
+
+fn boxed(val: Option<&Val>) -> Option<Box<wasmtime_component_val_t>> {
+    val.map(|v| Box::new(wasmtime_component_val_t::from_val(v)))
+}
+
+fn payload(
+    val: &Option<Box<wasmtime_component_val_t>>,
+    ty: Option<Type>,
+    what: &str,
+) -> Result<Option<Val>> {
+    match (val, ty) {
+        (Some(val), Some(ty)) => Ok(Some(val.to_val(&ty)?)),
+        (None, None) => Ok(None),
+        (Some(_), None) => bail!("{what} does not have a payload"),
+        (None, Some(_)) => bail!("{what} requires a payload"),
+    }
+}
+
+impl wasmtime_component_val_t {
+    pub(crate) fn from_val(val: &Val) -> wasmtime_component_val_t {
+        match val {
+            Val::Bool(b) => wasmtime_component_val_t::Bool(*b),
+            Val::S8(v) => wasmtime_component_val_t::S8(*v),
+            Val::U8(v) => wasmtime_component_val_t::U8(*v),
+            Val::S16(v) => wasmtime_component_val_t::S16(*v),
+            Val::U16(v) => wasmtime_component_val_t::U16(*v),
+            Val::S32(v) => wasmtime_component_val_t::S32(*v),
+            Val::U32(v) => wasmtime_component_val_t::U32(*v),
+            Val::S64(v) => wasmtime_component_val_t::S64(*v),
+            Val::U64(v) => wasmtime_component_val_t::U64(*v),
+            Val::Float32(v) => wasmtime_component_val_t::Float32(*v),
+            Val::Float64(v) => wasmtime_component_val_t::Float64(*v),
+            Val::Char(c) => wasmtime_component_val_t::Char(u32::from(*c)),
+            Val::String(s) => wasmtime_component_val_t::String(name(s)),
+            Val::List(list) => wasmtime_component_val_t::List(
+                list.iter()
+                    .map(wasmtime_component_val_t::from_val)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            Val::Record(record) => wasmtime_component_val_t::Record(
+                record
+                    .fields()
+                    .map(|(n, v)| wasmtime_component_val_record_field_t {
+                        name: name(n),
+                        val: wasmtime_component_val_t::from_val(v),
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            Val::Tuple(tuple) => wasmtime_component_val_t::Tuple(
+                tuple
+                    .values()
+                    .iter()
+                    .map(wasmtime_component_val_t::from_val)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            Val::Variant(variant) => {
+                wasmtime_component_val_t::Variant(wasmtime_component_val_variant_t {
+                    discriminant: name(variant.discriminant()),
+                    val: boxed(variant.payload()),
+                })
+            }
+            Val::Enum(e) => wasmtime_component_val_t::Enum(name(e.discriminant())),
+            Val::Option(o) => wasmtime_component_val_t::Option(boxed(o.value())),
+            Val::Result(r) => {
+                let (is_ok, val) = match r.value() {
+                    Ok(v) => (true, v),
+                    Err(v) => (false, v),
+                };
+                wasmtime_component_val_t::Result(wasmtime_component_val_result_t {
+                    is_ok,
+                    val: boxed(val),
+                })
+            }
+            Val::Flags(flags) => {
+                wasmtime_component_val_t::Flags(flags.flags().map(name).collect::<Vec<_>>().into())
+            }
+            Val::Resource(resource) => {
+                wasmtime_component_val_t::Resource(Box::new(wasmtime_component_resource_any_t {
+                    resource: *resource,
+                }))
+            }
+        }
+    }
+
+    /// Converts this C value into a `Val` of type `ty`, returning an error if
+    /// the shapes don't match.
+    pub(crate) fn to_val(&self, ty: &Type) -> Result<Val> {
+        Ok(match (self, ty) {
+            (wasmtime_component_val_t::Bool(b), Type::Bool) => Val::Bool(*b),
+            (wasmtime_component_val_t::S8(v), Type::S8) => Val::S8(*v),
+            (wasmtime_component_val_t::U8(v), Type::U8) => Val::U8(*v),
+            (wasmtime_component_val_t::S16(v), Type::S16) => Val::S16(*v),
+            (wasmtime_component_val_t::U16(v), Type::U16) => Val::U16(*v),
+            (wasmtime_component_val_t::S32(v), Type::S32) => Val::S32(*v),
+            (wasmtime_component_val_t::U32(v), Type::U32) => Val::U32(*v),
+            (wasmtime_component_val_t::S64(v), Type::S64) => Val::S64(*v),
+            (wasmtime_component_val_t::U64(v), Type::U64) => Val::U64(*v),
+            (wasmtime_component_val_t::Float32(v), Type::Float32) => Val::Float32(*v),
+            (wasmtime_component_val_t::Float64(v), Type::Float64) => Val::Float64(*v),
+            (wasmtime_component_val_t::Char(c), Type::Char) => Val::Char(
+                char::from_u32(*c).ok_or_else(|| anyhow!("invalid unicode scalar value {c:#x}"))?,
+            ),
+            (wasmtime_component_val_t::String(s), Type::String) => Val::String(str_of(s)?.into()),
+            (wasmtime_component_val_t::List(vals), Type::List(list)) => {
+                let elem = list.ty();
+                let vals = vals
+                    .as_slice()
+                    .iter()
+                    .map(|v| v.to_val(&elem))
+                    .collect::<Result<Vec<_>>>()?;
+                list.new_val(vals.into())?
+            }
+            (wasmtime_component_val_t::Record(fields), Type::Record(record)) => {
+                let fields = fields.as_slice();
+                let mut vals = Vec::with_capacity(fields.len());
+                for (field, ty) in fields.iter().zip(record.fields()) {
+                    vals.push((str_of(&field.name)?, field.val.to_val(&ty.ty)?));
+                }
+                record.new_val(vals)?
+            }
+            (wasmtime_component_val_t::Tuple(vals), Type::Tuple(tuple)) => {
+                let vals = vals
+                    .as_slice()
+                    .iter()
+                    .zip(tuple.types())
+                    .map(|(v, ty)| v.to_val(&ty))
+                    .collect::<Result<Vec<_>>>()?;
+                tuple.new_val(vals.into())?
+            }
+            (wasmtime_component_val_t::Variant(v), Type::Variant(variant)) => {
+                let discriminant = str_of(&v.discriminant)?;
+                let case = variant
+                    .cases()
+                    .find(|c| c.name == discriminant)
+                    .ok_or_else(|| anyhow!("unknown variant case `{discriminant}`"))?;
+                let what = format!("variant case `{discriminant}`");
+                variant.new_val(discriminant, payload(&v.val, case.ty, &what)?)?
+            }
+            (wasmtime_component_val_t::Enum(n), Type::Enum(e)) => e.new_val(str_of(n)?)?,
+            (wasmtime_component_val_t::Option(v), Type::Option(option)) => {
+                let val = match v {
+                    Some(v) => Some(v.to_val(&option.ty())?),
+                    None => None,
+                };
+                option.new_val(val)?
+            }
+            (wasmtime_component_val_t::Result(r), Type::Result(result)) => {
+                if r.is_ok {
+                    result.new_val(Ok(payload(&r.val, result.ok(), "`ok` result")?))?
+                } else {
+                    result.new_val(Err(payload(&r.val, result.err(), "`err` result")?))?
+                }
+            }
+            (wasmtime_component_val_t::Flags(names), Type::Flags(flags)) => {
+                let names = names
+                    .as_slice()
+                    .iter()
+                    .map(str_of)
+                    .collect::<Result<Vec<_>>>()?;
+                flags.new_val(&names)?
+            }
+            (wasmtime_component_val_t::Resource(r), Type::Own(_) | Type::Borrow(_)) => {
+                Val::Resource(r.resource)
+            }
+            (_, ty) => bail!("value does not match the expected type {ty:?}"),
+        })
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_val_new() -> Box<wasmtime_component_val_t> {
+    Box::new(wasmtime_component_val_t::default())
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_val_clone(
+    val: &wasmtime_component_val_t,
+    out: &mut MaybeUninit<wasmtime_component_val_t>,
+) {
+    crate::initialize(out, val.clone());
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_val_delete(val: &mut wasmtime_component_val_t) {
+    *val = wasmtime_component_val_t::default();
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_val_free(val: Option<Box<wasmtime_component_val_t>>) {
+    drop(val);
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_resource_any_drop(
+    store: CStoreContextMut<'_>,
+    resource: &wasmtime_component_resource_any_t,
+) -> Option<Box<wasmtime_error_t>> {
+    crate::handle_result(resource.resource.resource_drop(store), |()| {})
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_component_resource_any_clone(
+    resource: &wasmtime_component_resource_any_t,
+) -> Box<wasmtime_component_resource_any_t> {
+    Box::new(resource.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasmtime::component::{Component, Linker};
+    use wasmtime::{Engine, Store};
+
+    /// Component type definitions along with the core wasm parameters they
+    /// are flattened to.
+    const TYPES: &[(&str, &str)] = &[
+        ("bool", "i32"),
+        ("s8", "i32"),
+        ("u8", "i32"),
+        ("s16", "i32"),
+        ("u16", "i32"),
+        ("s32", "i32"),
+        ("u32", "i32"),
+        ("s64", "i64"),
+        ("u64", "i64"),
+        ("float32", "f32"),
+        ("float64", "f64"),
+        ("char", "i32"),
+        ("string", "i32 i32"),
+        ("(list u32)", "i32 i32"),
+        (
+            r#"(record (field "a" u32) (field "b" (option string)))"#,
+            "i32 i32 i32 i32",
+        ),
+        ("(tuple u8 s64)", "i32 i64"),
+        (
+            r#"(variant (case "a") (case "b" u32) (case "c" string))"#,
+            "i32 i32 i32",
+        ),
+        (r#"(enum "x" "y" "z")"#, "i32"),
+        ("(option (option u32))", "i32 i32 i32"),
+        ("(result u32 (error string))", "i32 i32 i32"),
+        ("(result)", "i32"),
+        (r#"(flags "p" "q" "r")"#, "i32"),
+    ];
+
+    /// Returns the type of each entry of `TYPES`, as the parameter of an
+    /// exported function.
+    fn types() -> Result<Vec<Type>> {
+        let mut core_funcs = String::new();
+        let mut funcs = String::new();
+        for (i, (ty, params)) in TYPES.iter().enumerate() {
+            // Compound types used by exported functions must be exported too.
+            let ty = if ty.starts_with('(') {
+                funcs += &format!("(type $t{i}' {ty}) (export $t{i} \"t{i}\" (type $t{i}'))\n");
+                format!("$t{i}")
+            } else {
+                ty.to_string()
+            };
+            core_funcs += &format!("(func (export \"f{i}\") (param {params}))\n");
+            funcs += &format!(
+                "(func (export \"f{i}\") (param \"x\" {ty})
+                    (canon lift (core func $i \"f{i}\") (memory $i \"memory\")
+                        (realloc (func $i \"realloc\"))))\n"
+            );
+        }
+        let wat = format!(
+            r#"
+                (component
+                    (core module $m
+                        (memory (export "memory") 1)
+                        (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                            unreachable)
+                        {core_funcs}
+                    )
+                    (core instance $i (instantiate $m))
+                    {funcs}
+                )
+            "#
+        );
+        let engine = Engine::new(wasmtime::Config::new().wasm_component_model(true))?;
+        let component = Component::new(&engine, wat::parse_str(&wat)?)?;
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+        Ok((0..TYPES.len())
+            .map(|i| {
+                let func = instance.get_func(&mut store, &format!("f{i}")).unwrap();
+                func.params(&store)[0].clone()
+            })
+            .collect())
+    }
+
+    fn round_trip(val: &Val, ty: &Type) -> Result<Val> {
+        wasmtime_component_val_t::from_val(val).to_val(ty)
+    }
+
+    #[test]
+    fn round_trips() -> Result<()> {
+        let types = types()?;
+        let ty = |i: usize| &types[i];
+        let mut vals = vec![
+            Val::Bool(true),
+            Val::S8(-1),
+            Val::U8(u8::MAX),
+            Val::S16(i16::MIN),
+            Val::U16(u16::MAX),
+            Val::S32(-42),
+            Val::U32(42),
+            Val::S64(i64::MIN),
+            Val::U64(u64::MAX),
+            Val::Float32(1.5),
+            Val::Float64(-0.25),
+            Val::Char('🦀'),
+            Val::String("hello".into()),
+        ];
+        vals.push(
+            ty(13)
+                .unwrap_list()
+                .new_val(vec![Val::U32(1), Val::U32(2)].into())?,
+        );
+
+        let string = Val::String("b".into());
+        let some_string = ty(14).unwrap_record().fields().nth(1).unwrap().ty;
+        let some_string = some_string.unwrap_option().new_val(Some(string.clone()))?;
+        vals.push(
+            ty(14)
+                .unwrap_record()
+                .new_val([("a", Val::U32(1)), ("b", some_string)])?,
+        );
+        vals.push(
+            ty(15)
+                .unwrap_tuple()
+                .new_val(vec![Val::U8(1), Val::S64(-1)].into())?,
+        );
+        let variant = ty(16).unwrap_variant();
+        vals.push(variant.new_val("a", None)?);
+        vals.push(variant.new_val("b", Some(Val::U32(7)))?);
+        vals.push(variant.new_val("c", Some(string.clone()))?);
+        vals.push(ty(17).unwrap_enum().new_val("y")?);
+
+        let option = ty(18).unwrap_option();
+        let inner = option.ty();
+        vals.push(option.new_val(None)?);
+        vals.push(option.new_val(Some(inner.unwrap_option().new_val(None)?))?);
+        vals.push(option.new_val(Some(inner.unwrap_option().new_val(Some(Val::U32(3)))?))?);
+        let result = ty(19).unwrap_result();
+        vals.push(result.new_val(Ok(Some(Val::U32(1))))?);
+        vals.push(result.new_val(Err(Some(string.clone())))?);
+        let empty_result = ty(20).unwrap_result();
+        vals.push(empty_result.new_val(Ok(None))?);
+        vals.push(empty_result.new_val(Err(None))?);
+        let flags = ty(21).unwrap_flags();
+        vals.push(flags.new_val(&[])?);
+        vals.push(flags.new_val(&["p", "r"])?);
+
+        for val in vals {
+            let ty = val.ty();
+            assert_eq!(round_trip(&val, &ty)?, val);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_mismatched_values() -> Result<()> {
+        let types = types()?;
+        let c_val = |val: wasmtime_component_val_t, ty: usize| val.to_val(&types[ty]);
+
+        // Scalars must match exactly, and chars must be valid.
+        assert!(c_val(wasmtime_component_val_t::U32(1), 5).is_err());
+        assert!(c_val(wasmtime_component_val_t::Char(0xd800), 11).is_err());
+        assert!(c_val(wasmtime_component_val_t::String(name("a")), 13).is_err());
+
+        // Variant cases must exist and have a payload only if declared.
+        let variant = |case: &str, val: Option<wasmtime_component_val_t>| {
+            wasmtime_component_val_t::Variant(wasmtime_component_val_variant_t {
+                discriminant: name(case),
+                val: val.map(Box::new),
+            })
+        };
+        assert!(c_val(variant("d", None), 16).is_err());
+        assert!(c_val(variant("a", Some(wasmtime_component_val_t::U32(1))), 16).is_err());
+        assert!(c_val(variant("b", None), 16).is_err());
+
+        // Results follow the same rules for their payloads.
+        let result = wasmtime_component_val_t::Result(wasmtime_component_val_result_t {
+            is_ok: true,
+            val: None,
+        });
+        assert!(c_val(result.clone(), 19).is_err());
+        assert!(c_val(result, 20).is_ok());
+
+        // Unknown enum cases and flags are rejected.
+        assert!(c_val(wasmtime_component_val_t::Enum(name("w")), 17).is_err());
+        let flags = wasmtime_component_val_t::Flags(vec![name("s")].into());
+        assert!(c_val(flags, 21).is_err());
+        Ok(())
+    }
+}
@@ -72,6 +72,14 @@ pub struct StoreData {
     foreign: crate::ForeignData,
     #[cfg(feature = "wasi")]
     pub(crate) wasi: Option<wasmtime_wasi::WasiCtx>,
+    /// The WASI preview2 context, which is empty until
+    /// `wasmtime_context_set_wasip2` is called.
+    #[cfg(all(feature = "wasi", feature = "component-model"))]
+    pub(crate) wasip2: wasmtime_wasi::preview2::WasiCtx,
+
+    /// Table of resources handed out to WASI preview2.
+    #[cfg(all(feature = "wasi", feature = "component-model"))]
+    pub(crate) resource_table: wasmtime::component::ResourceTable,
 
     /// Temporary storage for usage during a wasm->host call to store values
     /// in a slice we pass to the C API.
@@ -98,6 +106,10 @@ pub extern "C" fn wasmtime_store_new(
                 foreign: ForeignData { data, finalizer },
                 #[cfg(feature = "wasi")]
                 wasi: None,
+                #[cfg(all(feature = "wasi", feature = "component-model"))]
+                wasip2: wasmtime_wasi::preview2::WasiCtxBuilder::new().build(),
+                #[cfg(all(feature = "wasi", feature = "component-model"))]
+                resource_table: wasmtime::component::ResourceTable::new(),
                 hostcall_val_storage: Vec::new(),
                 wasm_val_storage: Vec::new(),
                 store_limits: StoreLimits::default(),
@@ -202,6 +214,36 @@ pub extern "C" fn wasmtime_context_set_wasi(
     })
 }
 
+#[cfg(all(feature = "wasi", feature = "component-model"))]
+#[no_mangle]
+pub extern "C" fn wasmtime_context_set_wasip2(
+    mut context: CStoreContextMut<'_>,
+    wasi: Box<crate::wasi_config_t>,
+) -> Option<Box<wasmtime_error_t>> {
+    crate::handle_result(wasi.into_wasip2_ctx(), |wasi| {
+        context.data_mut().wasip2 = wasi;
+    })
+}
+
+#[cfg(all(feature = "wasi", feature = "component-model"))]
+impl wasmtime_wasi::preview2::WasiView for StoreData {
+    fn table(&self) -> &wasmtime::component::ResourceTable {
+        &self.resource_table
+    }
+
+    fn table_mut(&mut self) -> &mut wasmtime::component::ResourceTable {
+        &mut self.resource_table
+    }
+
+    fn ctx(&self) -> &wasmtime_wasi::preview2::WasiCtx {
+        &self.wasip2
+    }
+
+    fn ctx_mut(&mut self) -> &mut wasmtime_wasi::preview2::WasiCtx {
+        &mut self.wasip2
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn wasmtime_context_gc(mut context: CStoreContextMut<'_>) {
     context.gc();
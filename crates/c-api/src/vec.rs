@@ -79,6 +79,15 @@ macro_rules! declare_vecs {
             }
         }
 
+        impl$(<$lt>)? Default for $name $(<$lt>)? {
+            fn default() -> Self {
+                $name {
+                    size: 0,
+                    data: ptr::null_mut(),
+                }
+            }
+        }
+
         impl$(<$lt>)? Clone for $name $(<$lt>)? {
             fn clone(&self) -> Self {
                 self.as_slice().to_vec().into()
@@ -249,3 +258,34 @@ declare_vecs! {
         delete: wasm_extern_vec_delete,
     )
 }
+
+#[cfg(feature = "component-model")]
+declare_vecs! {
+    (
+        name: wasmtime_component_val_vec_t,
+        ty: crate::wasmtime_component_val_t,
+        new: wasmtime_component_val_vec_new,
+        empty: wasmtime_component_val_vec_new_empty,
+        uninit: wasmtime_component_val_vec_new_uninitialized,
+        copy: wasmtime_component_val_vec_copy,
+        delete: wasmtime_component_val_vec_delete,
+    )
+    (
+        name: wasmtime_component_val_record_t,
+        ty: crate::wasmtime_component_val_record_field_t,
+        new: wasmtime_component_val_record_new,
+        empty: wasmtime_component_val_record_new_empty,
+        uninit: wasmtime_component_val_record_new_uninitialized,
+        copy: wasmtime_component_val_record_copy,
+        delete: wasmtime_component_val_record_delete,
+    )
+    (
+        name: wasmtime_component_val_flags_t,
+        ty: wasm_name_t,
+        new: wasmtime_component_val_flags_new,
+        empty: wasmtime_component_val_flags_new_empty,
+        uninit: wasmtime_component_val_flags_new_uninitialized,
+        copy: wasmtime_component_val_flags_copy,
+        delete: wasmtime_component_val_flags_delete,
+    )
+}
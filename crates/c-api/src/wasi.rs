@@ -138,6 +138,104 @@ impl wasi_config_t {
         }
         Ok(builder.build())
     }
+
+    /// Same as [`wasi_config_t::into_wasi_ctx`], but creates a WASI preview2
+    /// context suitable for use with components.
+    #[cfg(feature = "component-model")]
+    pub fn into_wasip2_ctx(self) -> Result<wasmtime_wasi::preview2::WasiCtx> {
+        use wasmtime_wasi::preview2::{self, DirPerms, FilePerms};
+
+        let mut builder = preview2::WasiCtxBuilder::new();
+        if self.inherit_args {
+            builder.args(&std::env::args().collect::<Vec<_>>());
+        } else if !self.args.is_empty() {
+            let args = self
+                .args
+                .into_iter()
+                .map(|bytes| Ok(String::from_utf8(bytes)?))
+                .collect::<Result<Vec<String>>>()?;
+            builder.args(&args);
+        }
+        if self.inherit_env {
+            builder.envs(&std::env::vars().collect::<Vec<_>>());
+        } else if !self.env.is_empty() {
+            let env = self
+                .env
+                .into_iter()
+                .map(|(kbytes, vbytes)| {
+                    let k = String::from_utf8(kbytes)?;
+                    let v = String::from_utf8(vbytes)?;
+                    Ok((k, v))
+                })
+                .collect::<Result<Vec<(String, String)>>>()?;
+            builder.envs(&env);
+        }
+        match self.stdin {
+            WasiConfigReadPipe::None => {}
+            WasiConfigReadPipe::Inherit => {
+                builder.inherit_stdin();
+            }
+            WasiConfigReadPipe::File(mut file) => {
+                let mut contents = Vec::new();
+                std::io::Read::read_to_end(&mut file, &mut contents)?;
+                builder.stdin(preview2::pipe::MemoryInputPipe::new(contents.into()));
+            }
+            WasiConfigReadPipe::Bytes(binary) => {
+                builder.stdin(preview2::pipe::MemoryInputPipe::new(binary.into()));
+            }
+        };
+        match self.stdout {
+            WasiConfigWritePipe::None => {}
+            WasiConfigWritePipe::Inherit => {
+                builder.inherit_stdout();
+            }
+            WasiConfigWritePipe::File(file) => {
+                builder.stdout(FileOutputStream(file));
+            }
+        };
+        match self.stderr {
+            WasiConfigWritePipe::None => {}
+            WasiConfigWritePipe::Inherit => {
+                builder.inherit_stderr();
+            }
+            WasiConfigWritePipe::File(file) => {
+                builder.stderr(FileOutputStream(file));
+            }
+        };
+        for (dir, path) in self.preopen_dirs {
+            let path = path
+                .to_str()
+                .ok_or_else(|| anyhow::anyhow!("preopen path is not valid utf-8"))?;
+            builder.preopened_dir(dir, DirPerms::all(), FilePerms::all(), path);
+        }
+        if !self.preopen_sockets.is_empty() {
+            anyhow::bail!("preopened sockets are not supported with WASI preview2");
+        }
+        Ok(builder.build())
+    }
+}
+
+/// A preview2 stdout/stderr stream which appends to a host file.
+#[cfg(feature = "component-model")]
+struct FileOutputStream(File);
+
+#[cfg(feature = "component-model")]
+impl wasmtime_wasi::preview2::StdoutStream for FileOutputStream {
+    fn stream(&self) -> Box<dyn wasmtime_wasi::preview2::HostOutputStream> {
+        use wasmtime_wasi::preview2::pipe::{AsyncWriteStream, ClosedOutputStream};
+
+        match self.0.try_clone() {
+            Ok(file) => Box::new(AsyncWriteStream::new(
+                1 << 16,
+                tokio::fs::File::from_std(file),
+            )),
+            Err(_) => Box::new(ClosedOutputStream),
+        }
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
 }
 
 #[no_mangle]
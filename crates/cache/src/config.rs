@@ -1,5 +1,8 @@
 //! Module for configuring the cache system.
 
+use super::store::{
+    CacheStore, DirectoryStore, HttpStore, MemoryStore, DEFAULT_HTTP_MAX_ENTRY_SIZE,
+};
 use super::Worker;
 use anyhow::{anyhow, bail, Context, Result};
 use directories_next::ProjectDirs;
@@ -28,7 +31,37 @@ struct Config {
 #[serde(deny_unknown_fields)]
 pub struct CacheConfig {
     enabled: bool,
+    #[serde(rename = "store")]
+    store_kind: Option<CacheStoreKind>,
     directory: Option<PathBuf>,
+    #[serde(
+        default,
+        rename = "memory-size-limit",
+        deserialize_with = "deserialize_disk_space"
+    )]
+    memory_size_limit: Option<u64>,
+    #[serde(rename = "http-url")]
+    http_url: Option<String>,
+    #[serde(
+        default,
+        rename = "http-timeout",
+        deserialize_with = "deserialize_duration"
+    )]
+    http_timeout: Option<Duration>,
+    #[serde(
+        default,
+        rename = "http-max-entry-size",
+        deserialize_with = "deserialize_disk_space"
+    )]
+    http_max_entry_size: Option<u64>,
+    #[serde(
+        default,
+        rename = "http-size-limit",
+        deserialize_with = "deserialize_disk_space"
+    )]
+    http_size_limit: Option<u64>,
+    #[serde(rename = "http-integrity-key")]
+    http_integrity_key: Option<String>,
     #[serde(
         default,
         rename = "worker-event-queue-size",
@@ -88,12 +121,26 @@ pub struct CacheConfig {
     )]
     files_total_size_limit_percent_if_deleting: Option<u8>,
 
+    #[serde(skip)]
+    store: Option<Arc<dyn CacheStore>>,
     #[serde(skip)]
     worker: Option<Worker>,
     #[serde(skip)]
     state: Arc<CacheState>,
 }
 
+/// The kind of storage backend holding the cached artifacts.
+#[derive(serde_derive::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheStoreKind {
+    /// Files in the local cache directory, cleaned up by the cache worker.
+    Directory,
+    /// Memory of the current process, dropped when the configuration is.
+    Memory,
+    /// A remote key-value store accessed over HTTP.
+    Http,
+}
+
 #[derive(Default, Debug)]
 struct CacheState {
     hits: AtomicUsize,
@@ -184,6 +231,10 @@ const DEFAULT_FILES_TOTAL_SIZE_SOFT_LIMIT: u64 = 1024 * 1024 * 512;
 const DEFAULT_FILE_COUNT_LIMIT_PERCENT_IF_DELETING: u8 = 70;
 // if changed, update cli-cache.md
 const DEFAULT_FILES_TOTAL_SIZE_LIMIT_PERCENT_IF_DELETING: u8 = 70;
+// if changed, update cli-cache.md
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+// if changed, update cli-cache.md
+const DEFAULT_MEMORY_SIZE_LIMIT: u64 = 1024 * 1024 * 256;
 
 fn project_dirs() -> Option<ProjectDirs> {
     ProjectDirs::from("", "BytecodeAlliance", "wasmtime")
@@ -300,6 +351,10 @@ impl CacheConfig {
     generate_setting_getter!(files_total_size_soft_limit: u64);
     generate_setting_getter!(file_count_limit_percent_if_deleting: u8);
     generate_setting_getter!(files_total_size_limit_percent_if_deleting: u8);
+    generate_setting_getter!(store_kind: CacheStoreKind);
+    generate_setting_getter!(memory_size_limit: u64);
+    generate_setting_getter!(http_timeout: Duration);
+    generate_setting_getter!(http_max_entry_size: u64);
 
     /// Returns true if and only if the cache is enabled.
     pub fn enabled(&self) -> bool {
@@ -308,18 +363,52 @@ impl CacheConfig {
 
     /// Returns path to the cache directory.
     ///
-    /// Panics if the cache is disabled.
+    /// Panics if the cache is disabled or doesn't use the directory store.
     pub fn directory(&self) -> &PathBuf {
         self.directory
             .as_ref()
             .expect(CACHE_IMPROPER_CONFIG_ERROR_MSG)
     }
 
+    /// Returns the URL of the HTTP store.
+    ///
+    /// Panics if the cache is disabled or doesn't use the HTTP store.
+    pub fn http_url(&self) -> &str {
+        self.http_url
+            .as_deref()
+            .expect(CACHE_IMPROPER_CONFIG_ERROR_MSG)
+    }
+
+    /// Returns the limit on the total size of entries written by the HTTP
+    /// store, if any.
+    pub fn http_size_limit(&self) -> Option<u64> {
+        self.http_size_limit
+    }
+
+    /// Returns the key authenticating entries of the HTTP store, if any.
+    pub fn http_integrity_key(&self) -> Option<&str> {
+        self.http_integrity_key.as_deref()
+    }
+
+    /// Returns the store holding the cached artifacts.
+    ///
+    /// Panics if the cache is disabled.
+    pub fn store(&self) -> &dyn CacheStore {
+        &**self.store.as_ref().expect(CACHE_IMPROPER_CONFIG_ERROR_MSG)
+    }
+
     /// Creates a new set of configuration which represents a disabled cache
     pub fn new_cache_disabled() -> Self {
         Self {
             enabled: false,
+            store_kind: None,
             directory: None,
+            memory_size_limit: None,
+            http_url: None,
+            http_timeout: None,
+            http_max_entry_size: None,
+            http_size_limit: None,
+            http_integrity_key: None,
             worker_event_queue_size: None,
             baseline_compression_level: None,
             optimized_compression_level: None,
@@ -331,6 +420,7 @@ impl CacheConfig {
             files_total_size_soft_limit: None,
             file_count_limit_percent_if_deleting: None,
             files_total_size_limit_percent_if_deleting: None,
+            store: None,
             worker: None,
             state: Arc::new(CacheState::default()),
         }
@@ -347,7 +437,10 @@ impl CacheConfig {
         let mut config = Self::load_and_parse_file(config_file)?;
 
         // validate values and fill in defaults
-        config.validate_directory_or_default()?;
+        config.validate_store_kind_or_default()?;
+        if config.store_kind() == CacheStoreKind::Directory {
+            config.validate_directory_or_default()?;
+        }
         config.validate_worker_event_queue_size_or_default();
         config.validate_baseline_compression_level_or_default()?;
         config.validate_optimized_compression_level_or_default()?;
@@ -359,17 +452,48 @@ impl CacheConfig {
         config.validate_files_total_size_soft_limit_or_default();
         config.validate_file_count_limit_percent_if_deleting_or_default()?;
         config.validate_files_total_size_limit_percent_if_deleting_or_default()?;
+        config.create_store()?;
         config.spawn_worker();
 
         Ok(config)
     }
 
+    fn create_store(&mut self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let store: Arc<dyn CacheStore> = match self.store_kind() {
+            CacheStoreKind::Directory => Arc::new(DirectoryStore::new(self.modules_directory())),
+            CacheStoreKind::Memory => Arc::new(MemoryStore::new(self.memory_size_limit())),
+            CacheStoreKind::Http => {
+                let mut store = HttpStore::new(self.http_url(), self.http_timeout())?
+                    .max_entry_size(self.http_max_entry_size());
+                if let Some(size) = self.http_size_limit() {
+                    store = store.size_limit(size);
+                }
+                if let Some(key) = self.http_integrity_key() {
+                    store = store.integrity_key(key.as_bytes());
+                }
+                Arc::new(store)
+            }
+        };
+        self.store = Some(store);
+        Ok(())
+    }
+
+    // The worker only manages files of the cache directory.
     fn spawn_worker(&mut self) {
-        if self.enabled {
+        if self.enabled && self.store_kind() == CacheStoreKind::Directory {
             self.worker = Some(Worker::start_new(self));
         }
     }
 
+    /// Returns the directory holding the files of the directory store.
+    fn modules_directory(&self) -> PathBuf {
+        self.directory().join("modules")
+    }
+
+    #[cfg(test)]
     pub(super) fn worker(&self) -> &Worker {
         assert!(self.enabled);
         self.worker.as_ref().unwrap()
@@ -385,14 +509,18 @@ impl CacheConfig {
         self.state.misses.load(SeqCst)
     }
 
-    pub(crate) fn on_cache_get_async(&self, path: impl AsRef<Path>) {
+    pub(crate) fn on_cache_get_async(&self, key: &str) {
         self.state.hits.fetch_add(1, SeqCst);
-        self.worker().on_cache_get_async(path)
+        if let Some(worker) = &self.worker {
+            worker.on_cache_get_async(self.modules_directory().join(key))
+        }
     }
 
-    pub(crate) fn on_cache_update_async(&self, path: impl AsRef<Path>) {
+    pub(crate) fn on_cache_update_async(&self, key: &str) {
         self.state.misses.fetch_add(1, SeqCst);
-        self.worker().on_cache_update_async(path)
+        if let Some(worker) = &self.worker {
+            worker.on_cache_update_async(self.modules_directory().join(key))
+        }
     }
 
     fn load_and_parse_file(config_file: Option<&Path>) -> Result<Self> {
@@ -420,6 +548,56 @@ impl CacheConfig {
         }
     }
 
+    fn validate_store_kind_or_default(&mut self) -> Result<()> {
+        if self.store_kind.is_none() {
+            self.store_kind = Some(CacheStoreKind::Directory);
+        }
+
+        let kind = self.store_kind.unwrap();
+        if kind == CacheStoreKind::Memory {
+            if self.memory_size_limit.is_none() {
+                self.memory_size_limit = Some(DEFAULT_MEMORY_SIZE_LIMIT);
+            }
+        } else if self.memory_size_limit.is_some() {
+            bail!(
+                "Setting `memory-size-limit` requires the `memory` cache store, found: {:?}",
+                kind
+            );
+        }
+
+        if kind == CacheStoreKind::Http {
+            if self.http_url.is_none() {
+                bail!("Cache store `http` requires the `http-url` setting");
+            }
+            if self.http_timeout.is_none() {
+                self.http_timeout = Some(DEFAULT_HTTP_TIMEOUT);
+            }
+            if self.http_max_entry_size.is_none() {
+                self.http_max_entry_size = Some(DEFAULT_HTTP_MAX_ENTRY_SIZE);
+            }
+            if self
+                .http_integrity_key
+                .as_ref()
+                .is_some_and(|k| k.is_empty())
+            {
+                bail!("Setting `http-integrity-key` must not be empty");
+            }
+        } else if self.http_url.is_some()
+            || self.http_timeout.is_some()
+            || self.http_max_entry_size.is_some()
+            || self.http_size_limit.is_some()
+            || self.http_integrity_key.is_some()
+        {
+            bail!(
+                "Settings `http-url`, `http-timeout`, `http-max-entry-size`, \
+                 `http-size-limit` and `http-integrity-key` require the `http` \
+                 cache store, found: {:?}",
+                kind
+            );
+        }
+        Ok(())
+    }
+
     fn validate_directory_or_default(&mut self) -> Result<()> {
         if self.directory.is_none() {
             match project_dirs() {
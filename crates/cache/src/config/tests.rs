@@ -1,4 +1,4 @@
-use super::CacheConfig;
+use super::{CacheConfig, CacheStoreKind};
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -81,6 +81,7 @@ fn test_all_settings() {
         cp,
         "[cache]\n\
          enabled = true\n\
+         store = 'directory'\n\
          directory = {cache_dir}\n\
          worker-event-queue-size = '16'\n\
          baseline-compression-level = 3\n\
@@ -120,6 +121,7 @@ fn test_all_settings() {
 
     fn check_conf(conf: &CacheConfig, cd: &PathBuf) {
         assert!(conf.enabled());
+        assert_eq!(conf.store_kind(), CacheStoreKind::Directory);
         assert_eq!(
             conf.directory(),
             &fs::canonicalize(cd).expect("canonicalize failed")
@@ -144,6 +146,158 @@ fn test_all_settings() {
     }
 }
 
+#[test]
+fn test_store_settings() {
+    let (_td, cd, cp) = test_prolog();
+    let conf = load_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'memory'\n\
+         directory = {cache_dir}",
+        cd
+    );
+    assert!(conf.enabled());
+    assert_eq!(conf.store_kind(), CacheStoreKind::Memory);
+    assert_eq!(conf.memory_size_limit(), 256 * 1024 * 1024);
+    // only the directory store uses the cache directory
+    assert!(!cd.exists());
+
+    let conf = load_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'memory'\n\
+         directory = {cache_dir}\n\
+         memory-size-limit = '64Mi'",
+        cd
+    );
+    assert_eq!(conf.memory_size_limit(), 64 * 1024 * 1024);
+
+    let conf = load_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}\n\
+         http-url = 'http://127.0.0.1:8080/wasmtime-cache'",
+        cd
+    );
+    assert_eq!(conf.store_kind(), CacheStoreKind::Http);
+    assert_eq!(conf.http_url(), "http://127.0.0.1:8080/wasmtime-cache");
+    assert_eq!(conf.http_timeout(), Duration::from_secs(30));
+    assert_eq!(conf.http_max_entry_size(), 256 * 1024 * 1024);
+    assert_eq!(conf.http_size_limit(), None);
+    assert_eq!(conf.http_integrity_key(), None);
+    assert!(!cd.exists());
+
+    let conf = load_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}\n\
+         http-url = 'http://127.0.0.1:8080'\n\
+         http-timeout = '2m'\n\
+         http-max-entry-size = '32M'\n\
+         http-size-limit = '2Gi'\n\
+         http-integrity-key = 'secret'",
+        cd
+    );
+    assert_eq!(conf.http_timeout(), Duration::from_secs(2 * 60));
+    assert_eq!(conf.http_max_entry_size(), 32_000_000);
+    assert_eq!(conf.http_size_limit(), Some(2 * 1024 * 1024 * 1024));
+    assert_eq!(conf.http_integrity_key(), Some("secret"));
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}\n\
+         http-url = 'http://127.0.0.1:8080'\n\
+         http-integrity-key = ''",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}\n\
+         http-url = 'http://127.0.0.1:8080'\n\
+         memory-size-limit = '1M'",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'memory'\n\
+         directory = {cache_dir}\n\
+         http-integrity-key = 'secret'",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'redis'\n\
+         directory = {cache_dir}",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         directory = {cache_dir}\n\
+         http-url = 'https://127.0.0.1:8080'",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         directory = {cache_dir}\n\
+         http-url = 'http://127.0.0.1:8080'",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'memory'\n\
+         directory = {cache_dir}\n\
+         http-timeout = '1m'",
+        cd
+    );
+
+    bad_config!(
+        cp,
+        "[cache]\n\
+         enabled = true\n\
+         directory = {cache_dir}\n\
+         http-size-limit = '1Gi'",
+        cd
+    );
+}
+
 #[test]
 fn test_compression_level_settings() {
     let (_td, cd, cp) = test_prolog();
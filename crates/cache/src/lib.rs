@@ -1,25 +1,27 @@
 use base64::Engine;
-use log::{debug, trace, warn};
+use log::{trace, warn};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::{fs, io};
 
 #[macro_use] // for tests
 mod config;
+mod store;
 mod worker;
 
-pub use config::{create_new_config, CacheConfig};
+pub use config::{create_new_config, CacheConfig, CacheStoreKind};
+pub use store::{CacheEntryMetadata, CacheStore, DirectoryStore, HttpStore, MemoryStore};
 use worker::Worker;
 
 /// Module level cache entry.
 pub struct ModuleCacheEntry<'config>(Option<ModuleCacheEntryInner<'config>>);
 
 struct ModuleCacheEntryInner<'config> {
-    root_path: PathBuf,
+    key_prefix: String,
     cache_config: &'config CacheConfig,
 }
 
@@ -89,18 +91,18 @@ impl<'config> ModuleCacheEntry<'config> {
         // standard encoding uses '/' which can't be used for filename
         let hash = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&hash);
 
-        if let Some(cached_val) = inner.get_data(&hash) {
+        let key = inner.key(&hash);
+
+        if let Some(cached_val) = inner.get_data(&key) {
             if let Some(val) = deserialize(state, cached_val) {
-                let mod_cache_path = inner.root_path.join(&hash);
-                inner.cache_config.on_cache_get_async(&mod_cache_path); // call on success
+                inner.cache_config.on_cache_get_async(&key); // call on success
                 return Ok(val);
             }
         }
         let val_to_cache = compute(state)?;
         if let Some(bytes) = serialize(state, &val_to_cache) {
-            if inner.update_data(&hash, &bytes).is_some() {
-                let mod_cache_path = inner.root_path.join(&hash);
-                inner.cache_config.on_cache_update_async(&mod_cache_path); // call on success
+            if inner.update_data(&key, &bytes).is_some() {
+                inner.cache_config.on_cache_update_async(&key); // call on success
             }
         }
         Ok(val_to_cache)
@@ -142,27 +144,32 @@ impl<'config> ModuleCacheEntryInner<'config> {
                 comp_ver = env!("GIT_REV"),
             )
         };
-        let root_path = cache_config.directory().join("modules").join(compiler_dir);
-
         Self {
-            root_path,
+            key_prefix: compiler_dir,
             cache_config,
         }
     }
 
-    fn get_data(&self, hash: &str) -> Option<Vec<u8>> {
-        let mod_cache_path = self.root_path.join(hash);
-        trace!("get_data() for path: {}", mod_cache_path.display());
-        let compressed_cache_bytes = fs::read(&mod_cache_path).ok()?;
+    fn key(&self, hash: &str) -> String {
+        format!("{}/{}", self.key_prefix, hash)
+    }
+
+    fn get_data(&self, key: &str) -> Option<Vec<u8>> {
+        trace!("get_data() for key: {}", key);
+        let compressed_cache_bytes = self
+            .cache_config
+            .store()
+            .get(key)
+            .map_err(|err| warn!("Failed to read cached code: {:?}", err))
+            .ok()??;
         let cache_bytes = zstd::decode_all(&compressed_cache_bytes[..])
             .map_err(|err| warn!("Failed to decompress cached code: {}", err))
             .ok()?;
         Some(cache_bytes)
     }
 
-    fn update_data(&self, hash: &str, serialized_data: &[u8]) -> Option<()> {
-        let mod_cache_path = self.root_path.join(hash);
-        trace!("update_data() for path: {}", mod_cache_path.display());
+    fn update_data(&self, key: &str, serialized_data: &[u8]) -> Option<()> {
+        trace!("update_data() for key: {}", key);
         let compressed_data = zstd::encode_all(
             &serialized_data[..],
             self.cache_config.baseline_compression_level(),
@@ -170,40 +177,11 @@ impl<'config> ModuleCacheEntryInner<'config> {
         .map_err(|err| warn!("Failed to compress cached code: {}", err))
         .ok()?;
 
-        // Optimize syscalls: first, try writing to disk. It should succeed in most cases.
-        // Otherwise, try creating the cache directory and retry writing to the file.
-        if fs_write_atomic(&mod_cache_path, "mod", &compressed_data).is_ok() {
-            return Some(());
-        }
-
-        debug!(
-            "Attempting to create the cache directory, because \
-             failed to write cached code to disk, path: {}",
-            mod_cache_path.display(),
-        );
-
-        let cache_dir = mod_cache_path.parent().unwrap();
-        fs::create_dir_all(cache_dir)
-            .map_err(|err| {
-                warn!(
-                    "Failed to create cache directory, path: {}, message: {}",
-                    cache_dir.display(),
-                    err
-                )
-            })
-            .ok()?;
-
-        match fs_write_atomic(&mod_cache_path, "mod", &compressed_data) {
-            Ok(_) => Some(()),
-            Err(err) => {
-                warn!(
-                    "Failed to write file with rename, target path: {}, err: {}",
-                    mod_cache_path.display(),
-                    err
-                );
-                None
-            }
-        }
+        self.cache_config
+            .store()
+            .insert(key, &compressed_data)
+            .map_err(|err| warn!("Failed to write cached code: {:?}", err))
+            .ok()
     }
 }
 
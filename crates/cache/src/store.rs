@@ -0,0 +1,589 @@
+//! Storage backends of the module cache.
+//!
+//! The cache system computes a key for every compiled artifact and hands the
+//! (compressed) artifact to a [`CacheStore`]. Which store is used is selected
+//! with the `store` setting of the cache configuration file.
+
+use super::fs_write_atomic;
+use anyhow::{anyhow, bail, Context, Result};
+use hmac::{Hmac, Mac};
+use log::{debug, trace};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// A key-value store holding cached artifacts.
+///
+/// Keys are made of URL-safe characters and `/`, which separates the
+/// compiler-specific prefix of the key from the hash of the cached state.
+///
+/// Stores must be safe to share between threads; failures are reported as
+/// errors, which the cache system logs and then treats like a cache miss.
+pub trait CacheStore: Send + Sync + fmt::Debug {
+    /// Returns the data stored under `key`, or `None` if there's no such entry.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Returns the metadata of the entry stored under `key`, or `None` if
+    /// there's no such entry.
+    fn metadata(&self, key: &str) -> Result<Option<CacheEntryMetadata>>;
+
+    /// Stores `data` under `key`, replacing any previous entry.
+    ///
+    /// Stores with limited capacity may evict other entries to make room for
+    /// `data`, or may not keep `data` at all.
+    fn insert(&self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// Removes the entry stored under `key`.
+    ///
+    /// Returns whether there was such an entry.
+    fn remove(&self, key: &str) -> Result<bool>;
+}
+
+/// Metadata of a single entry of a [`CacheStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntryMetadata {
+    /// Size of the stored data in bytes.
+    pub size: u64,
+    /// Time of the last modification of the entry, if known by the store.
+    pub modified: Option<SystemTime>,
+}
+
+/// Stores entries as files in a local directory.
+///
+/// This is the default store. The [cache worker] recompresses and cleans up
+/// files of this store in the background.
+///
+/// [cache worker]: https://bytecodealliance.github.io/wasmtime/cli-cache.html#cache-worker
+#[derive(Debug)]
+pub struct DirectoryStore {
+    root: PathBuf,
+}
+
+impl DirectoryStore {
+    /// Creates a store keeping its entries below the `root` directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the path of the file holding the entry for `key`.
+    pub fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+impl CacheStore for DirectoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(key);
+        trace!("get() for path: {}", path.display());
+        match fs::read(&path) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).context(format!("failed to read {}", path.display())),
+        }
+    }
+
+    fn metadata(&self, key: &str) -> Result<Option<CacheEntryMetadata>> {
+        let path = self.path(key);
+        match fs::metadata(&path) {
+            Ok(metadata) => Ok(Some(CacheEntryMetadata {
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+            })),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).context(format!("failed to stat {}", path.display())),
+        }
+    }
+
+    fn insert(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        trace!("insert() for path: {}", path.display());
+
+        // Optimize syscalls: first, try writing to disk. It should succeed in most cases.
+        // Otherwise, try creating the cache directory and retry writing to the file.
+        if fs_write_atomic(&path, "mod", data).is_ok() {
+            return Ok(());
+        }
+
+        debug!(
+            "Attempting to create the cache directory, because \
+             failed to write cached code to disk, path: {}",
+            path.display(),
+        );
+
+        let cache_dir = path.parent().unwrap();
+        fs::create_dir_all(cache_dir).context(format!(
+            "failed to create cache directory: {}",
+            cache_dir.display()
+        ))?;
+
+        fs_write_atomic(&path, "mod", data).context(format!(
+            "failed to write file with rename, target path: {}",
+            path.display()
+        ))
+    }
+
+    fn remove(&self, key: &str) -> Result<bool> {
+        let path = self.path(key);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err).context(format!("failed to remove {}", path.display())),
+        }
+    }
+}
+
+/// Keeps entries in memory, for the lifetime of the cache configuration.
+///
+/// The total size of the entries is bounded: once it would exceed the limit,
+/// the least recently used entries are evicted.
+#[derive(Debug)]
+pub struct MemoryStore {
+    size_limit: u64,
+    entries: Mutex<LruEntries<(Vec<u8>, SystemTime)>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store, which keeps at most `size_limit` bytes.
+    pub fn new(size_limit: u64) -> Self {
+        Self {
+            size_limit,
+            entries: Mutex::default(),
+        }
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut entries = self.entries.lock().unwrap();
+        Ok(entries.get(key).map(|(data, _)| data.clone()))
+    }
+
+    fn metadata(&self, key: &str) -> Result<Option<CacheEntryMetadata>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .peek(key)
+            .map(|(data, modified)| CacheEntryMetadata {
+                size: data.len() as u64,
+                modified: Some(*modified),
+            }))
+    }
+
+    fn insert(&self, key: &str, data: &[u8]) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let len = data.len() as u64;
+        if len > self.size_limit {
+            entries.remove(key);
+            debug!(
+                "Not keeping cache entry `{key}` of {len} bytes in memory, it exceeds the limit"
+            );
+            return Ok(());
+        }
+        let entry = (data.to_vec(), SystemTime::now());
+        for (evicted, _) in entries.insert(key, entry, len, self.size_limit) {
+            trace!("Evicted cache entry `{evicted}` from memory");
+        }
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<bool> {
+        Ok(self.entries.lock().unwrap().remove(key).is_some())
+    }
+}
+
+/// Entries of a store along with their sizes, evicted in least recently used
+/// order to bound their total size.
+#[derive(Debug)]
+struct LruEntries<T> {
+    /// The value of each entry, its size and the `clock` value of its last
+    /// use.
+    map: HashMap<String, (T, u64, u64)>,
+    /// Total size of all entries.
+    size: u64,
+    /// Incremented on every use of an entry.
+    clock: u64,
+}
+
+impl<T> Default for LruEntries<T> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            size: 0,
+            clock: 0,
+        }
+    }
+}
+
+impl<T> LruEntries<T> {
+    /// Returns the value of the entry for `key`, marking it as used.
+    fn get(&mut self, key: &str) -> Option<&T> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.map.get_mut(key).map(|(value, _, last_used)| {
+            *last_used = clock;
+            &*value
+        })
+    }
+
+    /// Returns the value of the entry for `key`, without marking it as used.
+    fn peek(&self, key: &str) -> Option<&T> {
+        self.map.get(key).map(|(value, _, _)| value)
+    }
+
+    /// Inserts an entry of `size` bytes for `key`, replacing any previous
+    /// one, and then evicts the least recently used other entries until the
+    /// total size is at most `size_limit`. Returns the evicted entries.
+    fn insert(&mut self, key: &str, value: T, size: u64, size_limit: u64) -> Vec<(String, T)> {
+        self.remove(key);
+        let mut evicted = Vec::new();
+        while !self.map.is_empty() && self.size.saturating_add(size) > size_limit {
+            let lru = self
+                .map
+                .iter()
+                .min_by_key(|(_, (_, _, last_used))| *last_used)
+                .map(|(key, _)| key.clone())
+                .unwrap();
+            let value = self.remove(&lru).unwrap();
+            evicted.push((lru, value));
+        }
+        self.clock += 1;
+        self.size += size;
+        self.map.insert(key.to_string(), (value, size, self.clock));
+        evicted
+    }
+
+    /// Removes the entry for `key`, returning its value.
+    fn remove(&mut self, key: &str) -> Option<T> {
+        let (value, size, _) = self.map.remove(key)?;
+        self.size -= size;
+        Some(value)
+    }
+}
+
+/// Keeps entries in a remote key-value store speaking plain HTTP/1.1.
+///
+/// The entry for `key` lives at `{url}/{key}`: it is read with `GET`,
+/// written with `PUT`, removed with `DELETE` and its metadata is queried with
+/// `HEAD`. A `404 Not Found` response means there's no such entry, and
+/// redirects aren't followed.
+///
+/// Only `http://` URLs are supported, so neither the connection nor the server
+/// are authenticated and cached artifacts are sent in the clear.
+///
+/// # Trust model
+///
+/// Cached artifacts contain native code which Wasmtime runs without further
+/// validation. Anybody who can write to the server, or tamper with the
+/// traffic to it, can therefore run arbitrary code in every process using
+/// the store. Unless an [integrity key](HttpStore::integrity_key) is
+/// configured, this store must only be used with a trusted server on a
+/// trusted network.
+///
+/// With an integrity key, every entry is prefixed with an HMAC-SHA256 tag of
+/// its key and data, and entries with a missing or wrong tag are treated as
+/// errors, which the cache system handles like a cache miss. All processes
+/// sharing the store need the same key, and it must be kept secret from
+/// anybody who can write to the server.
+///
+/// # Eviction
+///
+/// With a [size limit](HttpStore::size_limit), the store keeps track of the
+/// entries it wrote and removes the least recently used ones with `DELETE`
+/// once their total size would exceed the limit. This only bounds the storage
+/// used by a single process: entries written by other processes, or by
+/// earlier runs, are never removed, so the server remains responsible for
+/// bounding the storage used overall, for example by expiring entries which
+/// haven't been read for a while.
+#[derive(Debug)]
+pub struct HttpStore {
+    host: String,
+    port: u16,
+    path: String,
+    agent: ureq::Agent,
+    max_entry_size: u64,
+    integrity_key: Option<IntegrityKey>,
+    size_limit: Option<u64>,
+    /// The entries written by this store, when it has a size limit.
+    written: Mutex<LruEntries<()>>,
+}
+
+/// Length in bytes of the tag prefixed to entries by [`HttpStore`] when an
+/// integrity key is configured.
+const TAG_LEN: usize = 32;
+
+/// The key of the HMAC tags of an [`HttpStore`].
+struct IntegrityKey(Vec<u8>);
+
+impl fmt::Debug for IntegrityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IntegrityKey(..)")
+    }
+}
+
+impl IntegrityKey {
+    /// Computes the tag of the entry holding `data` under `key`.
+    fn tag(&self, key: &str, data: &[u8]) -> [u8; TAG_LEN] {
+        // The length prefix keeps the boundary between key and data unambiguous.
+        hmac_sha256(
+            &self.0,
+            &[&(key.len() as u64).to_le_bytes(), key.as_bytes(), data],
+        )
+    }
+
+    /// Checks the tag of the entry holding `data` under `key`, in constant
+    /// time.
+    fn verify(&self, key: &str, data: &[u8], tag: &[u8]) -> bool {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).unwrap();
+        mac.update(&(key.len() as u64).to_le_bytes());
+        mac.update(key.as_bytes());
+        mac.update(data);
+        mac.verify_slice(tag).is_ok()
+    }
+}
+
+/// Computes the HMAC-SHA256 of the concatenation of `parts`.
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; TAG_LEN] {
+    // HMAC accepts keys of any length.
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// The default of [`HttpStore::max_entry_size`].
+pub(crate) const DEFAULT_HTTP_MAX_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+impl HttpStore {
+    /// Creates a store keeping its entries below `url`.
+    ///
+    /// Connecting to the server, as well as every read and write of a request,
+    /// fails if it takes longer than `timeout`.
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow!("unsupported cache store URL, expected `http://`: {url}"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (
+                host,
+                port.parse()
+                    .with_context(|| format!("invalid port in cache store URL: {url}"))?,
+            ),
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            bail!("missing host in cache store URL: {url}");
+        }
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(timeout)
+            .timeout_read(timeout)
+            .timeout_write(timeout)
+            .redirects(0)
+            .build();
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+            agent,
+            max_entry_size: DEFAULT_HTTP_MAX_ENTRY_SIZE,
+            integrity_key: None,
+            size_limit: None,
+            written: Mutex::default(),
+        })
+    }
+
+    /// Limits the size of the entries written to and read from the server.
+    ///
+    /// Larger entries aren't written, and responses with larger bodies are
+    /// rejected without reading more than the limit.
+    pub fn max_entry_size(mut self, size: u64) -> Self {
+        self.max_entry_size = size;
+        self
+    }
+
+    /// Bounds the total size of the entries written by this store, removing
+    /// the least recently used ones from the server once it would be
+    /// exceeded.
+    ///
+    /// See the [eviction](HttpStore#eviction) of this store.
+    pub fn size_limit(mut self, size: u64) -> Self {
+        self.size_limit = Some(size);
+        self
+    }
+
+    /// Authenticates entries with an HMAC-SHA256 tag keyed with `key`.
+    ///
+    /// See the [trust model](HttpStore#trust-model) of this store.
+    pub fn integrity_key(mut self, key: &[u8]) -> Self {
+        self.integrity_key = Some(IntegrityKey(key.to_vec()));
+        self
+    }
+
+    /// Returns the maximal size of a response body, including the tag.
+    fn max_body_size(&self) -> u64 {
+        match self.integrity_key {
+            Some(_) => self.max_entry_size.saturating_add(TAG_LEN as u64),
+            None => self.max_entry_size,
+        }
+    }
+
+    /// Sends a request for the entry of `key`, returning `None` for a
+    /// `404 Not Found` response.
+    fn request(
+        &self,
+        method: &str,
+        key: &str,
+        body: Option<&[u8]>,
+    ) -> Result<Option<ureq::Response>> {
+        let url = format!("http://{}:{}{}/{}", self.host, self.port, self.path, key);
+        trace!("{method} {url}");
+        let request = self.agent.request(method, &url);
+        let result = match body {
+            Some(body) => request
+                .set("Content-Type", "application/octet-stream")
+                .send_bytes(body),
+            None => request.call(),
+        };
+        match result {
+            Ok(response) => Ok(Some(response)),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(err) => {
+                Err(err).with_context(|| format!("{method} request for cache entry `{key}` failed"))
+            }
+        }
+    }
+
+    /// Reads the body of `response`, failing once it exceeds the size limit.
+    fn read_body(&self, response: ureq::Response) -> Result<Vec<u8>> {
+        let limit = self.max_body_size();
+        if let Some(len) = content_length(&response)? {
+            check_body_size(len, limit)?;
+        }
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut body)?;
+        check_body_size(body.len() as u64, limit)?;
+        Ok(body)
+    }
+}
+
+fn content_length(response: &ureq::Response) -> Result<Option<u64>> {
+    match response.header("Content-Length") {
+        Some(len) => Ok(Some(len.parse().context("invalid Content-Length")?)),
+        None => Ok(None),
+    }
+}
+
+fn check_body_size(size: u64, limit: u64) -> Result<()> {
+    if size > limit {
+        bail!("HTTP response body of {size} bytes exceeds the limit of {limit} bytes");
+    }
+    Ok(())
+}
+
+fn unexpected(response: &ureq::Response) -> anyhow::Error {
+    anyhow!("unexpected HTTP status: {}", response.status())
+}
+
+impl CacheStore for HttpStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let response = match self.request("GET", key, None)? {
+            Some(response) if response.status() == 200 => response,
+            Some(response) => return Err(unexpected(&response)),
+            None => return Ok(None),
+        };
+        let mut body = self
+            .read_body(response)
+            .with_context(|| format!("failed to read cache entry `{key}`"))?;
+        if let Some(integrity_key) = &self.integrity_key {
+            if body.len() < TAG_LEN
+                || !integrity_key.verify(key, &body[TAG_LEN..], &body[..TAG_LEN])
+            {
+                bail!("integrity check of cache entry `{key}` failed");
+            }
+            body.drain(..TAG_LEN);
+        }
+        self.written.lock().unwrap().get(key);
+        Ok(Some(body))
+    }
+
+    fn metadata(&self, key: &str) -> Result<Option<CacheEntryMetadata>> {
+        let response = match self.request("HEAD", key, None)? {
+            Some(response) if response.status() == 200 => response,
+            Some(response) => return Err(unexpected(&response)),
+            None => return Ok(None),
+        };
+        let size = content_length(&response)?.ok_or_else(|| anyhow!("missing Content-Length"))?;
+        let size = match self.integrity_key {
+            Some(_) => size.saturating_sub(TAG_LEN as u64),
+            None => size,
+        };
+        Ok(Some(CacheEntryMetadata {
+            size,
+            modified: None,
+        }))
+    }
+
+    fn insert(&self, key: &str, data: &[u8]) -> Result<()> {
+        if data.len() as u64 > self.max_entry_size {
+            bail!(
+                "cache entry `{key}` of {} bytes exceeds the limit of {} bytes",
+                data.len(),
+                self.max_entry_size
+            );
+        }
+        let tagged;
+        let body = match &self.integrity_key {
+            Some(integrity_key) => {
+                tagged = [&integrity_key.tag(key, data)[..], data].concat();
+                &tagged[..]
+            }
+            None => data,
+        };
+        let size = body.len() as u64;
+        if let Some(limit) = self.size_limit.filter(|limit| size > *limit) {
+            debug!(
+                "Not writing cache entry `{key}` of {size} bytes, \
+                 it exceeds the size limit of {limit} bytes"
+            );
+            return Ok(());
+        }
+        match self.request("PUT", key, Some(body))? {
+            Some(response) if (200..=299).contains(&response.status()) => {}
+            Some(response) => return Err(unexpected(&response)),
+            None => bail!("PUT request for cache entry `{key}` failed: 404 Not Found"),
+        }
+        if let Some(limit) = self.size_limit {
+            let evicted = self.written.lock().unwrap().insert(key, (), size, limit);
+            for (key, ()) in evicted {
+                trace!("Evicting cache entry `{key}` from the server");
+                if let Err(err) = self.remove(&key) {
+                    debug!("Failed to evict cache entry `{key}`: {err:?}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<bool> {
+        self.written.lock().unwrap().remove(key);
+        match self.request("DELETE", key, None)? {
+            Some(response) if (200..=299).contains(&response.status()) => Ok(true),
+            Some(response) => Err(unexpected(&response)),
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,372 @@
+use super::*;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+/// Spawns a minimal HTTP key-value server on a random local port and returns
+/// its base URL.
+///
+/// The server keeps serving requests until the test process exits.
+pub fn spawn_http_server() -> String {
+    spawn_http_server_with_entries(Default::default())
+}
+
+/// Same as `spawn_http_server`, but keeps its entries in `entries`, which
+/// allows tests to inspect and modify them.
+fn spawn_http_server_with_entries(entries: Arc<Mutex<HashMap<String, Vec<u8>>>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind test server");
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let entries = entries.clone();
+            thread::spawn(move || serve(stream, &entries));
+        }
+    });
+    format!("http://{}/kv", addr)
+}
+
+fn serve(stream: TcpStream, entries: &Mutex<HashMap<String, Vec<u8>>>) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap().to_string();
+    let target = parts.next().unwrap().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().unwrap();
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    let key = target.strip_prefix("/kv/").unwrap_or(&target).to_string();
+    let mut entries = entries.lock().unwrap();
+    let (status, content) = match method.as_str() {
+        "GET" | "HEAD" => match entries.get(&key) {
+            Some(data) => ("200 OK", Some(data.clone())),
+            None => ("404 Not Found", None),
+        },
+        "PUT" => {
+            entries.insert(key, body);
+            ("201 Created", None)
+        }
+        "DELETE" => match entries.remove(&key) {
+            Some(_) => ("204 No Content", None),
+            None => ("404 Not Found", None),
+        },
+        _ => ("405 Method Not Allowed", None),
+    };
+    drop(entries);
+
+    let mut stream = reader.into_inner();
+    let content = content.unwrap_or_default();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        content.len()
+    )
+    .unwrap();
+    if method != "HEAD" {
+        stream.write_all(&content).unwrap();
+    }
+}
+
+fn test_store(store: &dyn CacheStore) {
+    assert_eq!(store.get("compiler/a").unwrap(), None);
+
+    store.insert("compiler/a", b"first").unwrap();
+    store.insert("compiler/b", b"").unwrap();
+    assert_eq!(
+        store.get("compiler/a").unwrap().as_deref(),
+        Some(&b"first"[..])
+    );
+    assert_eq!(store.get("compiler/b").unwrap().as_deref(), Some(&b""[..]));
+
+    store.insert("compiler/a", b"second").unwrap();
+    assert_eq!(
+        store.get("compiler/a").unwrap().as_deref(),
+        Some(&b"second"[..])
+    );
+    assert_eq!(store.get("compiler/b").unwrap().as_deref(), Some(&b""[..]));
+    assert_eq!(store.get("other/a").unwrap(), None);
+
+    let metadata = store.metadata("compiler/a").unwrap().unwrap();
+    assert_eq!(metadata.size, b"second".len() as u64);
+    assert_eq!(store.metadata("compiler/b").unwrap().unwrap().size, 0);
+    assert_eq!(store.metadata("other/a").unwrap(), None);
+
+    store.insert("compiler/c", b"third").unwrap();
+    assert!(store.remove("compiler/c").unwrap());
+    assert!(!store.remove("compiler/c").unwrap());
+    assert_eq!(store.get("compiler/c").unwrap(), None);
+    assert_eq!(store.metadata("compiler/c").unwrap(), None);
+    assert!(store.get("compiler/a").unwrap().is_some());
+}
+
+#[test]
+fn test_directory_store() {
+    let dir = tempfile::tempdir().expect("Can't create temporary directory");
+    let store = DirectoryStore::new(dir.path().join("modules"));
+    test_store(&store);
+    assert!(store.path("compiler/b").is_file());
+}
+
+#[test]
+fn test_memory_store() {
+    test_store(&MemoryStore::new(1024));
+}
+
+#[test]
+fn test_memory_store_eviction() {
+    let store = MemoryStore::new(10);
+    store.insert("a", b"1234").unwrap();
+    store.insert("b", b"1234").unwrap();
+    // `a` is now used more recently than `b`
+    assert!(store.get("a").unwrap().is_some());
+
+    store.insert("c", b"1234").unwrap();
+    assert!(store.get("a").unwrap().is_some());
+    assert_eq!(store.get("b").unwrap(), None);
+    assert!(store.get("c").unwrap().is_some());
+
+    // replacing an entry frees its old data first
+    store.insert("c", b"123456").unwrap();
+    assert!(store.get("a").unwrap().is_some());
+    assert_eq!(store.get("c").unwrap().as_deref(), Some(&b"123456"[..]));
+
+    // entries larger than the limit aren't kept, and don't evict others
+    store.insert("d", b"12345678901").unwrap();
+    assert_eq!(store.get("d").unwrap(), None);
+    assert!(store.get("a").unwrap().is_some());
+
+    store.insert("e", b"1234567890").unwrap();
+    assert_eq!(store.get("a").unwrap(), None);
+    assert_eq!(store.get("c").unwrap(), None);
+    assert!(store.get("e").unwrap().is_some());
+}
+
+#[test]
+fn test_http_store() {
+    let url = spawn_http_server();
+    test_store(&HttpStore::new(&url, Duration::from_secs(10)).unwrap());
+    // a trailing slash is ignored
+    let store = HttpStore::new(&format!("{url}/"), Duration::from_secs(10)).unwrap();
+    assert_eq!(store.get("compiler/b").unwrap().as_deref(), Some(&b""[..]));
+}
+
+#[test]
+fn test_http_store_eviction() {
+    let entries = Arc::new(Mutex::new(HashMap::new()));
+    let url = spawn_http_server_with_entries(entries.clone());
+    let store = HttpStore::new(&url, Duration::from_secs(10))
+        .unwrap()
+        .size_limit(10);
+    let keys = || {
+        let mut keys = entries.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    };
+    store.insert("a", b"1234").unwrap();
+    store.insert("b", b"1234").unwrap();
+    // `a` is now used more recently than `b`
+    assert!(store.get("a").unwrap().is_some());
+
+    store.insert("c", b"1234").unwrap();
+    assert_eq!(keys(), ["a", "c"]);
+
+    // entries larger than the limit aren't written, and don't evict others
+    store.insert("d", b"12345678901").unwrap();
+    assert_eq!(keys(), ["a", "c"]);
+
+    // entries removed by somebody else are skipped
+    entries.lock().unwrap().remove("a");
+    store.insert("e", b"1234567890").unwrap();
+    assert_eq!(keys(), ["e"]);
+}
+
+#[test]
+fn test_http_store_integrity() {
+    let entries = Arc::new(Mutex::new(HashMap::new()));
+    let url = spawn_http_server_with_entries(entries.clone());
+    let store = |key: &[u8]| {
+        HttpStore::new(&url, Duration::from_secs(10))
+            .unwrap()
+            .integrity_key(key)
+    };
+    test_store(&store(b"secret"));
+
+    // entries are prefixed with their tag
+    let stored = entries.lock().unwrap()["compiler/a"].clone();
+    assert_eq!(stored.len(), TAG_LEN + b"second".len());
+    assert_eq!(&stored[TAG_LEN..], b"second");
+
+    // another key doesn't accept the entry
+    assert!(store(b"other").get("compiler/a").is_err());
+
+    // neither does the key of another entry
+    entries
+        .lock()
+        .unwrap()
+        .insert("compiler/c".to_string(), stored.clone());
+    assert!(store(b"secret").get("compiler/c").is_err());
+
+    // nor modified data
+    let mut modified = stored.clone();
+    *modified.last_mut().unwrap() ^= 1;
+    entries
+        .lock()
+        .unwrap()
+        .insert("compiler/a".to_string(), modified);
+    assert!(store(b"secret").get("compiler/a").is_err());
+
+    // nor entries without a tag
+    entries
+        .lock()
+        .unwrap()
+        .insert("compiler/a".to_string(), b"short".to_vec());
+    assert!(store(b"secret").get("compiler/a").is_err());
+}
+
+#[test]
+fn test_hmac_sha256() {
+    // test case 2 of RFC 4231
+    assert_eq!(
+        hmac_sha256(b"Jefe", &[b"what do ya want ", b"for nothing?"]),
+        [
+            0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+            0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+            0x64, 0xec, 0x38, 0x43,
+        ]
+    );
+    // test case 6 of RFC 4231, with a key longer than the block size
+    assert_eq!(
+        hmac_sha256(
+            &[0xaa; 131],
+            &[b"Test Using Larger Than Block-Size Key - Hash Key First"]
+        ),
+        [
+            0x60, 0xe4, 0x31, 0x59, 0x1e, 0xe0, 0xb6, 0x7f, 0x0d, 0x8a, 0x26, 0xaa, 0xcb, 0xf5,
+            0xb7, 0x7f, 0x8e, 0x0b, 0xc6, 0x21, 0x37, 0x28, 0xc5, 0x14, 0x05, 0x46, 0x04, 0x0f,
+            0x0e, 0xe3, 0x7f, 0x54,
+        ]
+    );
+}
+
+/// Spawns a server answering every request with `response`, and returns its
+/// URL.
+fn spawn_raw_http_server(response: impl Into<String>) -> String {
+    let response = response.into();
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind test server");
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                line.clear();
+            }
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn test_http_store_max_entry_size() {
+    let url = spawn_http_server();
+    let store = HttpStore::new(&url, Duration::from_secs(10))
+        .unwrap()
+        .max_entry_size(4);
+    store.insert("compiler/a", b"1234").unwrap();
+    assert!(store.insert("compiler/b", b"12345").is_err());
+    assert_eq!(
+        store.get("compiler/a").unwrap().as_deref(),
+        Some(&b"1234"[..])
+    );
+    assert_eq!(store.get("compiler/b").unwrap(), None);
+
+    // Responses larger than the limit are rejected without reading more than
+    // the limit, no matter how they're framed.
+    for response in [
+        "HTTP/1.1 200 OK\r\nContent-Length: 18446744073709551615\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n123\r\nffffffffffff\r\n4567890",
+        "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n12345",
+    ] {
+        let store = HttpStore::new(&spawn_raw_http_server(response), Duration::from_secs(10))
+            .unwrap()
+            .max_entry_size(4);
+        let err = store.get("compiler/a").unwrap_err();
+        assert!(
+            format!("{err:?}").contains("exceeds the limit of 4 bytes"),
+            "{err:?}"
+        );
+    }
+    let store = HttpStore::new(
+        &spawn_raw_http_server(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\n12\r\n2\r\n34\r\n0\r\n\r\n",
+        ),
+        Duration::from_secs(10),
+    )
+    .unwrap()
+    .max_entry_size(4);
+    assert_eq!(
+        store.get("compiler/a").unwrap().as_deref(),
+        Some(&b"1234"[..])
+    );
+}
+
+#[test]
+fn test_http_store_long_header() {
+    // Header lines are bounded, so a server can't make the store buffer an
+    // arbitrary amount of data.
+    let response = format!("HTTP/1.1 200 OK\r\nX-Long: {}\r\n\r\n", "a".repeat(1 << 20));
+    let store = HttpStore::new(&spawn_raw_http_server(response), Duration::from_secs(10)).unwrap();
+    assert!(store.get("compiler/a").is_err());
+}
+
+#[test]
+fn test_http_store_unreachable() {
+    // grab a free port and close it again so nothing listens on it
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let store = HttpStore::new(&format!("http://{addr}"), Duration::from_secs(10)).unwrap();
+    assert!(store.get("compiler/a").is_err());
+    assert!(store.insert("compiler/a", b"data").is_err());
+}
+
+#[test]
+fn test_http_store_url() {
+    let store = HttpStore::new("http://cache.example:8080/a/b/", Duration::from_secs(1)).unwrap();
+    assert_eq!(store.host, "cache.example");
+    assert_eq!(store.port, 8080);
+    assert_eq!(store.path, "/a/b");
+
+    let store = HttpStore::new("http://cache.example", Duration::from_secs(1)).unwrap();
+    assert_eq!(store.port, 80);
+    assert_eq!(store.path, "");
+
+    let store = HttpStore::new("http://[::1]:8080", Duration::from_secs(1)).unwrap();
+    assert_eq!(store.host, "[::1]");
+    assert_eq!(store.port, 8080);
+
+    assert!(HttpStore::new("https://cache.example", Duration::from_secs(1)).is_err());
+    assert!(HttpStore::new("http://cache.example:port", Duration::from_secs(1)).is_err());
+    assert!(HttpStore::new("http://:8080/", Duration::from_secs(1)).is_err());
+}
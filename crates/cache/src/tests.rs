@@ -90,3 +90,97 @@ fn test_write_read_cache() {
     entry1.get_data::<_, i32, i32>(4, |_| panic!()).unwrap();
     entry2.get_data::<_, i32, i32>(1, |_| panic!()).unwrap();
 }
+
+#[test]
+fn test_write_read_cache_memory_store() {
+    let (_tempdir, cache_dir, config_path) = test_prolog();
+    let cache_config = load_config!(
+        config_path,
+        "[cache]\n\
+         enabled = true\n\
+         store = 'memory'\n\
+         directory = {cache_dir}\n",
+        cache_dir
+    );
+    assert_eq!(cache_config.store_kind(), CacheStoreKind::Memory);
+
+    let entry = ModuleCacheEntry::from_inner(ModuleCacheEntryInner::new("test", &cache_config));
+    entry.get_data::<_, i32, i32>(1, |_| Ok(100)).unwrap();
+    assert_eq!(entry.get_data::<_, i32, i32>(1, |_| panic!()).unwrap(), 100);
+    assert_eq!(cache_config.cache_hits(), 1);
+    assert_eq!(cache_config.cache_misses(), 1);
+
+    // clones of the configuration share the store
+    let cache_config2 = cache_config.clone();
+    let entry2 = ModuleCacheEntry::from_inner(ModuleCacheEntryInner::new("test", &cache_config2));
+    assert_eq!(
+        entry2.get_data::<_, i32, i32>(1, |_| panic!()).unwrap(),
+        100
+    );
+
+    // nothing was written to the disk
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn test_write_read_cache_http_store() {
+    let (_tempdir, cache_dir, config_path) = test_prolog();
+    let url = crate::store::tests::spawn_http_server();
+    let config_content = format!(
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         http-url = {}\n",
+        toml::to_string_pretty(&url).unwrap(),
+    );
+    fs::write(&config_path, config_content).expect("Failed to write test config file");
+
+    // two independent configurations, e.g. of two build machines
+    let cache_config1 = CacheConfig::from_file(Some(&config_path)).unwrap();
+    let cache_config2 = CacheConfig::from_file(Some(&config_path)).unwrap();
+    assert_eq!(cache_config1.store_kind(), CacheStoreKind::Http);
+
+    let entry1 = ModuleCacheEntry::from_inner(ModuleCacheEntryInner::new("test", &cache_config1));
+    let entry2 = ModuleCacheEntry::from_inner(ModuleCacheEntryInner::new("test", &cache_config2));
+
+    entry1.get_data::<_, i32, i32>(1, |_| Ok(100)).unwrap();
+    assert_eq!(
+        entry2.get_data::<_, i32, i32>(1, |_| panic!()).unwrap(),
+        100
+    );
+    entry2.get_data::<_, i32, i32>(2, |_| Ok(200)).unwrap();
+    assert_eq!(
+        entry1.get_data::<_, i32, i32>(2, |_| panic!()).unwrap(),
+        200
+    );
+    assert_eq!(cache_config1.cache_hits(), 1);
+    assert_eq!(cache_config1.cache_misses(), 1);
+
+    // nothing was written to the disk
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn test_http_store_unreachable_is_a_miss() {
+    let (_tempdir, _cache_dir, config_path) = test_prolog();
+    // grab a free port and close it again so nothing listens on it
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let config_content = format!(
+        "[cache]\n\
+         enabled = true\n\
+         store = 'http'\n\
+         http-url = 'http://{}'\n\
+         http-timeout = '5s'\n",
+        addr
+    );
+    fs::write(&config_path, config_content).expect("Failed to write test config file");
+    let cache_config = CacheConfig::from_file(Some(&config_path)).unwrap();
+
+    let entry = ModuleCacheEntry::from_inner(ModuleCacheEntryInner::new("test", &cache_config));
+    assert_eq!(entry.get_data::<_, i32, i32>(1, |_| Ok(100)).unwrap(), 100);
+    assert_eq!(entry.get_data::<_, i32, i32>(1, |_| Ok(101)).unwrap(), 101);
+    assert_eq!(cache_config.cache_hits(), 0);
+}
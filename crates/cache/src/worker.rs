@@ -5,7 +5,9 @@
 //! but we guarantee eventual consistency and fault tolerancy.
 //! Background tasks can be CPU intensive, but the worker thread has low priority.
 
+use super::store::{CacheEntryMetadata, CacheStore, DirectoryStore};
 use super::{fs_write_atomic, CacheConfig};
+use anyhow::anyhow;
 use log::{debug, info, trace, warn};
 use serde_derive::{Deserialize, Serialize};
 use std::cmp;
@@ -34,6 +36,9 @@ pub(super) struct Worker {
 struct WorkerThread {
     receiver: Receiver<CacheEvent>,
     cache_config: CacheConfig,
+    /// The cache directory, whose module files are cleaned up through the
+    /// store.
+    store: DirectoryStore,
     #[cfg(test)]
     stats: Arc<(Mutex<WorkerStats>, Condvar)>,
 }
@@ -66,6 +71,7 @@ impl Worker {
         let worker_thread = WorkerThread {
             receiver: rx,
             cache_config: cache_config.clone(),
+            store: DirectoryStore::new(cache_config.directory()),
             #[cfg(test)]
             stats: stats.clone(),
         };
@@ -167,6 +173,7 @@ impl ModuleCacheStatistics {
 enum CacheEntry {
     Recognized {
         path: PathBuf,
+        key: String,
         mtime: SystemTime,
         size: u64,
     },
@@ -501,14 +508,18 @@ impl WorkerThread {
         if let Some(idx) = start_delete_idx {
             for item in &cache_index[idx..] {
                 let (result, path, entity) = match item {
-                    CacheEntry::Recognized { path, .. }
-                    | CacheEntry::Unrecognized {
+                    CacheEntry::Recognized { path, key, .. } => {
+                        (self.store.remove(key).map(drop), path, "file")
+                    }
+                    CacheEntry::Unrecognized {
                         path,
                         is_dir: false,
-                    } => (fs::remove_file(path), path, "file"),
-                    CacheEntry::Unrecognized { path, is_dir: true } => {
-                        (fs::remove_dir_all(path), path, "directory")
-                    }
+                    } => (fs::remove_file(path).map_err(Into::into), path, "file"),
+                    CacheEntry::Unrecognized { path, is_dir: true } => (
+                        fs::remove_dir_all(path).map_err(Into::into),
+                        path,
+                        "directory",
+                    ),
                 };
                 if let Err(err) = result {
                     warn!(
@@ -531,6 +542,7 @@ impl WorkerThread {
             dir_path: &Path,
             level: u8,
             cache_config: &CacheConfig,
+            store: &DirectoryStore,
         ) {
             macro_rules! add_unrecognized {
                 (file: $path:expr) => {
@@ -589,7 +601,7 @@ impl WorkerThread {
                 );
                 let path = entry.path();
                 match (level, path.is_dir()) {
-                    (0..=1, true) => enter_dir(vec, &path, level + 1, cache_config),
+                    (0..=1, true) => enter_dir(vec, &path, level + 1, cache_config, store),
                     (0..=1, false) => {
                         if level == 0
                             && path.file_stem() == Some(OsStr::new(".cleanup"))
@@ -658,9 +670,15 @@ impl WorkerThread {
 
                 // construct a cache entry
                 match (mod_, stats_, is_mod) {
-                    (Some((mod_path, mod_entry)), Some((stats_path, stats_entry)), true) => {
+                    (Some((mod_path, _)), Some((stats_path, stats_entry)), true) => {
+                        let key = unwrap_or!(
+                            store_key(cache_config.directory(), mod_path),
+                            add_unrecognized_and!([file: stats_path, file: mod_path], continue),
+                            "Failed to get the cache key, deleting BOTH module cache and stats files",
+                            mod_path
+                        );
                         let mod_metadata = unwrap_or!(
-                            mod_entry.metadata(),
+                            entry_metadata(store, &key),
                             add_unrecognized_and!([file: stats_path, file: mod_path], continue),
                             "Failed to get metadata, deleting BOTH module cache and stats files",
                             mod_path
@@ -670,7 +688,7 @@ impl WorkerThread {
                             add_unrecognized_and!(
                                 [file: stats_path],
                                 unwrap_or!(
-                                    mod_metadata.modified(),
+                                    entry_mtime(&mod_metadata),
                                     add_unrecognized_and!(
                                         [file: stats_path, file: mod_path],
                                         continue
@@ -686,16 +704,22 @@ impl WorkerThread {
                         // .into() called for the SystemTimeStub if cfg(test)
                         vec.push(CacheEntry::Recognized {
                             path: mod_path.to_path_buf(),
+                            key,
                             mtime: stats_mtime.into(),
-                            size: mod_metadata.len(),
+                            size: mod_metadata.size,
                         })
                     }
                     (Some(_), Some(_), false) => (), // was or will be handled by previous branch
-                    (Some((mod_path, mod_entry)), None, _) => {
+                    (Some((mod_path, _)), None, _) => {
+                        let key = unwrap_or!(
+                            store_key(cache_config.directory(), mod_path),
+                            add_unrecognized_and!([file: mod_path], continue),
+                            "Failed to get the cache key, deleting the file",
+                            mod_path
+                        );
                         let (mod_metadata, mod_mtime) = unwrap_or!(
-                            mod_entry
-                                .metadata()
-                                .and_then(|md| md.modified().map(|mt| (md, mt))),
+                            entry_metadata(store, &key)
+                                .and_then(|md| entry_mtime(&md).map(|mt| (md, mt))),
                             add_unrecognized_and!([file: mod_path], continue),
                             "Failed to get metadata/mtime, deleting the file",
                             mod_path
@@ -703,8 +727,9 @@ impl WorkerThread {
                         // .into() called for the SystemTimeStub if cfg(test)
                         vec.push(CacheEntry::Recognized {
                             path: mod_path.to_path_buf(),
+                            key,
                             mtime: mod_mtime.into(),
-                            size: mod_metadata.len(),
+                            size: mod_metadata.size,
                         })
                     }
                     (None, Some((stats_path, _stats_entry)), _) => {
@@ -722,11 +747,34 @@ impl WorkerThread {
             self.cache_config.directory(),
             0,
             &self.cache_config,
+            &self.store,
         );
         vec
     }
 }
 
+/// Returns the key of the module cache file at `path` below `cache_dir`.
+fn store_key(cache_dir: &Path, path: &Path) -> anyhow::Result<String> {
+    let relative = path.strip_prefix(cache_dir)?;
+    let components = relative
+        .iter()
+        .map(|c| c.to_str().ok_or_else(|| anyhow!("non UTF-8 file name")))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(components.join("/"))
+}
+
+fn entry_metadata(store: &DirectoryStore, key: &str) -> anyhow::Result<CacheEntryMetadata> {
+    store
+        .metadata(key)?
+        .ok_or_else(|| anyhow!("cache entry was removed"))
+}
+
+fn entry_mtime(metadata: &CacheEntryMetadata) -> anyhow::Result<std::time::SystemTime> {
+    metadata
+        .modified
+        .ok_or_else(|| anyhow!("modification time isn't available"))
+}
+
 fn read_stats_file(path: &Path) -> Option<ModuleCacheStatistics> {
     fs::read(path)
         .map_err(|err| {
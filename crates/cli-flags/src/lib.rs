@@ -73,6 +73,13 @@ wasmtime_option_group! {
         /// Configure attempting to initialize linear memory via a
         /// copy-on-write mapping (default: yes)
         pub memory_init_cow: Option<bool>,
+
+        /// Inline small functions into their direct callers (default: no)
+        pub inlining: Option<bool>,
+
+        /// Remove redundant bounds checks on dynamic and 64-bit memories
+        /// (default: no)
+        pub bounds_check_elimination: Option<bool>,
     }
 
     enum Optimize {
@@ -98,6 +105,9 @@ wasmtime_option_group! {
         pub parallel_compilation: Option<bool>,
         /// Whether to enable proof-carrying code (PCC)-based validation.
         pub pcc: Option<bool>,
+        /// The maximum size, in bytes of its wasm body, of a function that
+        /// `-O inlining` inlines into its callers (default: 64)
+        pub inlining_max_callee_size: Option<u32>,
 
         #[prefixed = "cranelift"]
         /// Set a cranelift-specific option. Use `wasmtime settings` to see
@@ -123,6 +133,9 @@ wasmtime_option_group! {
         pub log_to_files: Option<bool>,
         /// Enable coredump generation to this file after a WebAssembly trap.
         pub coredump: Option<String>,
+        /// Keep copies of wasm locals in stack frames so that coredumps
+        /// include their values.
+        pub coredump_locals: Option<bool>,
     }
 
     enum Debug {
@@ -151,11 +164,13 @@ wasmtime_option_group! {
         pub max_wasm_stack: Option<usize>,
         /// Allow unknown exports when running commands.
         pub unknown_exports_allow: Option<bool>,
-        /// Allow the main module to import unknown functions, using an
-        /// implementation that immediately traps, when running commands.
+        /// Allow the main module or component to import unknown functions,
+        /// using an implementation that immediately traps, when running
+        /// commands.
         pub unknown_imports_trap: Option<bool>,
-        /// Allow the main module to import unknown functions, using an
-        /// implementation that returns default values, when running commands.
+        /// Allow the main module or component to import unknown functions,
+        /// using an implementation that returns default values, when running
+        /// commands.
         pub unknown_imports_default: Option<bool>,
         /// Enables memory error checking. (see wmemcheck.md for more info)
         pub wmemcheck: Option<bool>,
@@ -172,6 +187,9 @@ wasmtime_option_group! {
         pub max_tables: Option<usize>,
         /// Maximum number of WebAssembly linear memories allowed to be created.
         pub max_memories: Option<usize>,
+        /// Maximum number of live resources, such as WASI files and streams,
+        /// in the resource table of a component.
+        pub max_resource_table_entries: Option<usize>,
         /// Force a trap to be raised on `memory.grow` and `table.grow` failure
         /// instead of returning -1 from these instructions.
         ///
@@ -229,7 +247,14 @@ wasmtime_option_group! {
         /// Enable suport for WASI neural network API (experimental)
         pub nn: Option<bool>,
         /// Enable suport for WASI threading API (experimental)
+        ///
+        /// The threads of a component share its memory but not its WASI
+        /// state: each thread has its own, so e.g. files opened by one thread
+        /// can't be used by another.
         pub threads: Option<bool>,
+        /// Maximum number of threads of a component using the WASI threading
+        /// API that may run at the same time, per request with `serve`.
+        pub max_threads: Option<usize>,
         /// Enable suport for WASI HTTP API (experimental)
         pub http: Option<bool>,
         /// Inherit environment variables and file descriptors following the
@@ -413,6 +438,14 @@ impl CommonOptions {
             #[cfg(not(feature = "coredump"))]
             anyhow::bail!("support for coredumps disabled at compile time");
         }
+        if let Some(enable) = self.debug.coredump_locals {
+            #[cfg(feature = "coredump")]
+            config.coredump_locals(enable);
+            #[cfg(not(feature = "coredump"))]
+            if enable {
+                anyhow::bail!("support for coredumps disabled at compile time");
+            }
+        }
         match_feature! {
             ["cranelift" : self.opts.opt_level]
             level => config.cranelift_opt_level(level),
@@ -428,6 +461,21 @@ impl CommonOptions {
             enable => config.cranelift_pcc(enable),
             true => err,
         }
+        match_feature! {
+            ["cranelift" : self.opts.inlining]
+            enable => config.cranelift_inlining(enable),
+            true => err,
+        }
+        match_feature! {
+            ["cranelift" : self.codegen.inlining_max_callee_size]
+            size => config.inlining_max_callee_size(size),
+            _ => err,
+        }
+        match_feature! {
+            ["cranelift" : self.opts.bounds_check_elimination]
+            enable => config.cranelift_bounds_check_elimination(enable),
+            true => err,
+        }
 
         self.enable_wasm_features(&mut config)?;
 
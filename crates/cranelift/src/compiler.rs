@@ -16,8 +16,8 @@ use cranelift_codegen::{CompiledCode, MachStackMap};
 use cranelift_entity::{EntityRef, PrimaryMap};
 use cranelift_frontend::FunctionBuilder;
 use cranelift_wasm::{
-    DefinedFuncIndex, FuncIndex, FuncTranslator, MemoryIndex, OwnedMemoryIndex, WasmFuncType,
-    WasmType,
+    DefinedFuncIndex, FuncIndex, FuncTranslator, MemoryIndex, OwnedMemoryIndex, WasmError,
+    WasmFuncType, WasmType,
 };
 use object::write::{Object, StandardSegment, SymbolId};
 use object::{RelocationEncoding, RelocationKind, SectionKind};
@@ -31,13 +31,14 @@ use std::sync::{Arc, Mutex};
 use wasmparser::{FuncValidatorAllocations, FunctionBody};
 use wasmtime_cranelift_shared::{CompiledFunction, ModuleTextBuilder};
 use wasmtime_environ::{
-    AddressMapSection, CacheStore, CompileError, FlagValue, FunctionBodyData, FunctionLoc,
-    ModuleTranslation, ModuleTypesBuilder, PtrSize, StackMapInformation, TrapEncodingBuilder,
-    Tunables, VMOffsets, WasmFunctionInfo,
+    AddressMapSection, CacheStore, CompileError, FlagValue, FrameLocals, FunctionBodyData,
+    FunctionLoc, ModuleTranslation, ModuleTypesBuilder, PtrSize, StackMapInformation,
+    TrapEncodingBuilder, Tunables, TypeConvert, VMOffsets, WasmFunctionInfo,
 };
 
 #[cfg(feature = "component-model")]
 mod component;
+mod inlining;
 
 struct IncrementalCacheContext {
     #[cfg(feature = "incremental-cache")]
@@ -155,6 +156,17 @@ impl wasmtime_environ::Compiler for Compiler {
         let mut func_env =
             FuncEnvironment::new(isa, translation, types, &self.tunables, self.wmemcheck);
 
+        if self.tunables.coredump_locals || self.tunables.debug_breakpoints {
+            let mut local_types = wasm_func_ty.params().to_vec();
+            let mut locals = input.body.get_locals_reader().map_err(WasmError::from)?;
+            for _ in 0..locals.get_count() {
+                let (count, ty) = locals.read().map_err(WasmError::from)?;
+                let ty = func_env.convert_valtype(ty);
+                local_types.extend((0..count).map(|_| ty));
+            }
+            func_env.record_coredump_locals(local_types);
+        }
+
         // The `stack_limit` global value below is the implementation of stack
         // overflow checks in Wasmtime.
         //
@@ -216,19 +228,50 @@ impl wasmtime_environ::Compiler for Compiler {
             &mut context.func,
             &mut func_env,
         )?;
+        let coverage = func_env.take_coverage_regions();
+        let coredump_locals = func_env.take_coredump_locals();
+
+        // Inlined functions lose their frames, which debug information,
+        // memory checking, coredumps and debuggers rely on, and proof-carrying
+        // code facts aren't carried over into callers.
+        if !translation.inlining_candidates.is_empty()
+            && !self.tunables.generate_native_debuginfo
+            && !self.wmemcheck
+            && !self.tunables.coredump_locals
+            && !self.tunables.debug_breakpoints
+            && !isa.flags().enable_pcc()
+        {
+            let inliner = inlining::WasmInliner {
+                compiler: self,
+                translation,
+                types,
+                resources: validator.resources(),
+                caller: func_index,
+                callees: HashMap::new(),
+            };
+            context
+                .inline(inliner, isa)
+                .map_err(|error| CompileError::Codegen(pretty_error(&context.func, error)))?;
+        }
 
-        if let Some(path) = &self.clif_dir {
+        let clif_path = self.clif_dir.as_ref().map(|dir| {
             use std::io::Write;
 
-            let mut path = path.to_path_buf();
+            let mut path = dir.to_path_buf();
             path.push(format!("wasm_func_{}", func_index.as_u32()));
             path.set_extension("clif");
 
-            let mut output = std::fs::File::create(path).unwrap();
+            let mut output = std::fs::File::create(&path).unwrap();
             write!(output, "{}", context.func.display()).unwrap();
-        }
+            path
+        });
 
-        let (info, func) = compiler.finish_with_info(Some((&body, &self.tunables)))?;
+        let (mut info, func) = compiler.finish_with_info(
+            Some((&body, &self.tunables)),
+            coredump_locals,
+            clif_path.as_deref(),
+        )?;
+        info.coverage = coverage.into();
 
         let timing = cranelift_codegen::timing::take_current();
         log::debug!("{:?} translated in {:?}", func_index, timing.total());
@@ -1012,7 +1055,7 @@ impl FunctionCompiler<'_> {
     }
 
     fn finish(self) -> Result<CompiledFunction<CompiledFuncEnv>, CompileError> {
-        let (info, func) = self.finish_with_info(None)?;
+        let (info, func) = self.finish_with_info(None, None, None)?;
         assert!(info.stack_maps.is_empty());
         Ok(func)
     }
@@ -1020,6 +1063,8 @@ impl FunctionCompiler<'_> {
     fn finish_with_info(
         mut self,
         body_and_tunables: Option<(&FunctionBody<'_>, &Tunables)>,
+        coredump_locals: Option<(ir::StackSlot, Vec<WasmType>)>,
+        clif_path: Option<&path::Path>,
     ) -> Result<(WasmFunctionInfo, CompiledFunction<CompiledFuncEnv>), CompileError> {
         let context = &mut self.cx.codegen_context;
         let isa = &*self.compiler.isa;
@@ -1027,6 +1072,29 @@ impl FunctionCompiler<'_> {
             compile_maybe_cached(context, isa, self.cx.incremental_cache_ctx.as_mut())?;
         let compiled_code = context.compiled_code().unwrap();
 
+        // Alongside the CLIF the function was translated to, write out the
+        // optimized CLIF that was lowered and, if requested with the
+        // `machine_code_isle_rule_info` setting, which ISLE rules produced
+        // each range of its machine code.
+        if let Some(path) = clif_path {
+            use std::io::Write;
+
+            let mut output = std::fs::File::create(path.with_extension("opt.clif")).unwrap();
+            write!(output, "{}", context.func.display()).unwrap();
+
+            if !compiled_code.isle_rules.is_empty() {
+                let mut output = std::fs::File::create(path.with_extension("isle")).unwrap();
+                for range in &compiled_code.isle_rules {
+                    writeln!(
+                        output,
+                        "{} {} {}|{}",
+                        range.start, range.end, range.lowering_rule, range.inner_rule
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
         // Give wasm functions, user defined code, a "preferred" alignment
         // instead of the minimum alignment as this can help perf in niche
         // situations.
@@ -1085,6 +1153,14 @@ impl FunctionCompiler<'_> {
             }
         }
 
+        let frame_locals = match coredump_locals {
+            Some((slot, types)) => Some(FrameLocals {
+                fp_offset: frame_locals_fp_offset(isa, compiled_code, slot)?,
+                types: types.into(),
+            }),
+            None => None,
+        };
+
         let stack_maps = mach_stack_maps_to_stack_maps(compiled_code.buffer.stack_maps());
         compiled_function
             .set_sized_stack_slots(std::mem::take(&mut context.func.sized_stack_slots));
@@ -1094,12 +1170,41 @@ impl FunctionCompiler<'_> {
             WasmFunctionInfo {
                 start_srcloc: compiled_function.metadata().address_map.start_srcloc,
                 stack_maps: stack_maps.into(),
+                coverage: Default::default(),
+                frame_locals,
             },
             compiled_function,
         ))
     }
 }
 
+/// Returns the offset from the frame pointer of the stack slot `slot`.
+///
+/// Stack slot offsets are relative to the nominal stack pointer. On these
+/// targets the frame pointer sits right above the clobbered callee-saves, and
+/// the nominal stack pointer sits `frame_size` bytes below it since outgoing
+/// arguments aren't allocated in the prologue. On s390x there's no frame
+/// pointer, and the stack pointer that stands in for it sits below the
+/// outgoing argument area, whose size isn't known here.
+fn frame_locals_fp_offset(
+    isa: &dyn TargetIsa,
+    compiled_code: &CompiledCode,
+    slot: ir::StackSlot,
+) -> Result<i32, CompileError> {
+    match isa.triple().architecture {
+        target_lexicon::Architecture::X86_64
+        | target_lexicon::Architecture::Aarch64(_)
+        | target_lexicon::Architecture::Riscv64(_) => Ok(i32::try_from(
+            compiled_code.sized_stackslot_offsets[slot],
+        )
+        .unwrap()
+            - i32::try_from(compiled_code.frame_size).unwrap()),
+        arch => Err(CompileError::Codegen(format!(
+            "keeping wasm locals in stack frames isn't supported on {arch}"
+        ))),
+    }
+}
+
 fn mach_stack_maps_to_stack_maps(mach_stack_maps: &[MachStackMap]) -> Vec<StackMapInformation> {
     // This is converting from Cranelift's representation of a stack map to
     // Wasmtime's representation. They happen to align today but that may
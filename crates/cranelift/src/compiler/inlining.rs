@@ -0,0 +1,120 @@
+//! Inlining of small wasm functions into their direct callers.
+
+use crate::compiler::Compiler;
+use crate::func_environ::FuncEnvironment;
+use crate::wasm_call_signature;
+use cranelift_codegen::ir::{self, ExternalName, UserExternalName, UserFuncName};
+use cranelift_codegen::{CodegenResult, Inline, InlineCommand};
+use cranelift_wasm::{FuncIndex, FuncTranslator};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use wasmparser::{FuncToValidate, ValidatorResources};
+use wasmtime_environ::{ModuleTranslation, ModuleTypesBuilder};
+
+/// The inlining policy used when compiling wasm functions.
+///
+/// Direct calls to functions defined in the same module are inlined when the
+/// callee's body was small enough to be recorded in
+/// `ModuleTranslation::inlining_candidates`. Callees are translated to CLIF
+/// on demand, once per caller.
+pub(super) struct WasmInliner<'a> {
+    pub compiler: &'a Compiler,
+    pub translation: &'a ModuleTranslation<'a>,
+    pub types: &'a ModuleTypesBuilder,
+    pub resources: &'a ValidatorResources,
+    pub caller: FuncIndex,
+    pub callees: HashMap<FuncIndex, Option<ir::Function>>,
+}
+
+impl WasmInliner<'_> {
+    /// Returns the index of the wasm function called through `func_ref`, if
+    /// it's a function defined in this module.
+    fn callee_index(&self, caller: &ir::Function, func_ref: ir::FuncRef) -> Option<FuncIndex> {
+        let name = match caller.dfg.ext_funcs[func_ref].name {
+            ExternalName::User(name) => &caller.params.user_named_funcs()[name],
+            _ => return None,
+        };
+        if name.namespace != 0 {
+            return None;
+        }
+        let index = FuncIndex::from_u32(name.index);
+        if self.translation.module.is_imported_function(index) {
+            return None;
+        }
+        Some(index)
+    }
+
+    /// Translate the body of the wasm function `index` to CLIF.
+    fn translate(&self, index: FuncIndex) -> Option<ir::Function> {
+        let body = self.translation.inlining_candidates.get(&index)?;
+        let compiler = self.compiler;
+        let isa = &*compiler.isa;
+
+        let sig = self.translation.module.functions[index].signature;
+        let mut func = ir::Function::with_name_signature(
+            UserFuncName::User(UserExternalName {
+                namespace: 0,
+                index: index.as_u32(),
+            }),
+            wasm_call_signature(isa, &self.types[sig], &compiler.tunables),
+        );
+
+        // The validator only uses the type index to find the callee's
+        // parameters and results, so any type with its signature will do.
+        let (ty, _) = self
+            .translation
+            .module
+            .types
+            .iter()
+            .find(|(_, ty)| ty.unwrap_function() == sig)?;
+        let mut validator = FuncToValidate::new(
+            index.as_u32(),
+            ty.as_u32(),
+            self.resources,
+            &self.translation.wasm_features,
+        )
+        .into_validator(Default::default());
+        let mut func_env = FuncEnvironment::new(
+            isa,
+            self.translation,
+            self.types,
+            &compiler.tunables,
+            compiler.wmemcheck,
+        );
+        match FuncTranslator::new().translate_body(
+            &mut validator,
+            body.clone(),
+            &mut func,
+            &mut func_env,
+        ) {
+            Ok(()) => Some(func),
+            Err(e) => {
+                log::debug!("failed to translate {index:?} for inlining: {e}");
+                None
+            }
+        }
+    }
+}
+
+impl Inline for WasmInliner<'_> {
+    fn inline(
+        &mut self,
+        caller: &ir::Function,
+        _call_inst: ir::Inst,
+        callee: ir::FuncRef,
+        _args: &[ir::Value],
+    ) -> CodegenResult<InlineCommand<'_>> {
+        let index = match self.callee_index(caller, callee) {
+            Some(index) if index != self.caller => index,
+            _ => return Ok(InlineCommand::KeepCall),
+        };
+        if !self.callees.contains_key(&index) {
+            let func = self.translate(index);
+            self.callees.insert(index, func);
+        }
+        Ok(match &self.callees[&index] {
+            Some(func) => InlineCommand::Inline(Cow::Borrowed(func)),
+            None => InlineCommand::KeepCall,
+        })
+    }
+}
@@ -21,8 +21,9 @@ use std::convert::TryFrom;
 use std::mem;
 use wasmparser::Operator;
 use wasmtime_environ::{
-    BuiltinFunctionIndex, MemoryPlan, MemoryStyle, Module, ModuleTranslation, ModuleTypesBuilder,
-    PtrSize, TableStyle, Tunables, TypeConvert, VMOffsets, WASM_PAGE_SIZE,
+    BuiltinFunctionIndex, CoverageRegion, FilePos, FrameLocals, MemoryPlan, MemoryStyle, Module,
+    ModuleTranslation, ModuleTypesBuilder, PtrSize, TableStyle, Tunables, TypeConvert, VMOffsets,
+    WASM_PAGE_SIZE,
 };
 use wasmtime_environ::{FUNCREF_INIT_BIT, FUNCREF_MASK};
 
@@ -161,6 +162,29 @@ pub struct FuncEnvironment<'module_environment> {
 
     fuel_consumed: i64,
 
+    /// A function-local variable holding the pointer to this function's
+    /// coverage counters, loaded once in the prologue.
+    coverage_counters_var: cranelift_frontend::Variable,
+
+    /// The wasm code regions counted by this function's coverage counters,
+    /// in counter order.
+    coverage_regions: Vec<CoverageRegion>,
+
+    /// Whether the last region in `coverage_regions` still grows with each
+    /// translated operator.
+    coverage_region_open: bool,
+
+    /// Whether the next reachable operator starts a new coverage region,
+    /// because control may arrive there from elsewhere.
+    coverage_region_start: bool,
+
+    /// The types of this function's locals, if copies of them are kept in
+    /// its frame for coredumps.
+    coredump_locals: Vec<WasmType>,
+
+    /// The stack slot holding the copies of `coredump_locals`.
+    coredump_locals_slot: Option<ir::StackSlot>,
+
     #[cfg(feature = "wmemcheck")]
     wmemcheck: bool,
 }
@@ -206,11 +230,39 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             // Start with at least one fuel being consumed because even empty
             // functions should consume at least some fuel.
             fuel_consumed: 1,
+
+            coverage_counters_var: Variable::new(0),
+            coverage_regions: Vec::new(),
+            coverage_region_open: false,
+            coverage_region_start: true,
+
+            coredump_locals: Vec::new(),
+            coredump_locals_slot: None,
+
             #[cfg(feature = "wmemcheck")]
             wmemcheck,
         }
     }
 
+    /// Configures the function about to be translated to keep a copy of its
+    /// locals, of the given types, in its frame for coredumps.
+    pub fn record_coredump_locals(&mut self, types: Vec<WasmType>) {
+        self.coredump_locals = types;
+    }
+
+    /// Takes the stack slot and types of the locals recorded for coredumps
+    /// by the function that was just translated.
+    pub fn take_coredump_locals(&mut self) -> Option<(ir::StackSlot, Vec<WasmType>)> {
+        let slot = self.coredump_locals_slot.take()?;
+        Some((slot, mem::take(&mut self.coredump_locals)))
+    }
+
+    /// Takes the regions counted by the coverage counters of the function
+    /// that was just translated.
+    pub fn take_coverage_regions(&mut self) -> Vec<CoverageRegion> {
+        mem::take(&mut self.coverage_regions)
+    }
+
     fn pointer_type(&self) -> ir::Type {
         self.isa.pointer_type()
     }
@@ -398,6 +450,182 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         builder.def_var(self.vmruntime_limits_ptr, interrupt_ptr);
     }
 
+    fn coverage_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
+        // Each function's counters live in their own array so that functions
+        // can be compiled independently. The vmctx points to a table, indexed
+        // by defined function, of pointers to those arrays; look up this
+        // function's array once on entry.
+        let func_index = match &builder.func.name {
+            UserFuncName::User(user) => FuncIndex::from_u32(user.index),
+            _ => panic!("function name not a UserFuncName::User as expected"),
+        };
+        let def_index = self
+            .module
+            .defined_func_index(func_index)
+            .expect("only defined functions are instrumented");
+
+        let pointer_type = self.pointer_type();
+        builder.declare_var(self.coverage_counters_var, pointer_type);
+        let vmctx = self.vmctx(builder.func);
+        let base = builder.ins().global_value(pointer_type, vmctx);
+        let flags = ir::MemFlags::trusted().with_readonly();
+        let offset = i32::try_from(self.offsets.vmctx_coverage_counters()).unwrap();
+        let table = builder.ins().load(pointer_type, flags, base, offset);
+        let offset =
+            i32::try_from(def_index.as_u32() * u32::from(self.offsets.ptr.size())).unwrap();
+        let counters = builder.ins().load(pointer_type, flags, table, offset);
+        builder.def_var(self.coverage_counters_var, counters);
+    }
+
+    fn coverage_before_op(&mut self, builder: &mut FunctionBuilder<'_>, reachable: bool) {
+        let pos = builder.srcloc().bits();
+
+        // Everything up to this operator belongs to the open region.
+        if self.coverage_region_open {
+            self.coverage_regions.last_mut().unwrap().end = FilePos::new(pos);
+            self.coverage_region_open = !self.coverage_region_start;
+        }
+        if !reachable || !self.coverage_region_start {
+            return;
+        }
+        self.coverage_region_start = false;
+        self.coverage_region_open = true;
+
+        let index = self.coverage_regions.len();
+        self.coverage_regions.push(CoverageRegion {
+            start: FilePos::new(pos),
+            end: FilePos::new(pos),
+        });
+
+        let counters = builder.use_var(self.coverage_counters_var);
+        let offset = i32::try_from(index * mem::size_of::<u64>()).unwrap();
+        let count = builder
+            .ins()
+            .load(I64, ir::MemFlags::trusted(), counters, offset);
+        let count = builder.ins().iadd_imm(count, 1);
+        builder
+            .ins()
+            .store(ir::MemFlags::trusted(), count, counters, offset);
+    }
+
+    fn coverage_after_op(&mut self, op: &Operator<'_>) {
+        match op {
+            // Control may arrive right after these operators from somewhere
+            // other than the operator itself, or not at all, so the code that
+            // follows gets its own counter.
+            Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::Else
+            | Operator::End
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. }
+            | Operator::BrOnNull { .. }
+            | Operator::BrOnNonNull { .. }
+            | Operator::Return
+            | Operator::Unreachable
+
+            // Calls may not return, for example if the callee traps, so
+            // code after them is counted separately from code before them.
+            | Operator::Call { .. }
+            | Operator::CallIndirect { .. }
+            | Operator::CallRef { .. }
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. }
+            | Operator::ReturnCallRef { .. } => self.coverage_region_start = true,
+
+            _ => {}
+        }
+    }
+
+    fn coredump_locals_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
+        let size = FrameLocals::SLOT_SIZE * u32::try_from(self.coredump_locals.len()).unwrap();
+        let slot = builder.func.create_sized_stack_slot(ir::StackSlotData::new(
+            ir::StackSlotKind::ExplicitSlot,
+            size,
+        ));
+        self.coredump_locals_slot = Some(slot);
+
+        // Parameters and zero-initialized locals have already been defined,
+        // so record their initial values.
+        for index in 0..self.coredump_locals.len() {
+            self.coredump_record_local(builder, u32::try_from(index).unwrap());
+        }
+    }
+
+    fn coredump_record_local(&mut self, builder: &mut FunctionBuilder<'_>, index: u32) {
+        let (Some(slot), Some(ty)) = (
+            self.coredump_locals_slot,
+            self.coredump_locals.get(index as usize),
+        ) else {
+            return;
+        };
+        // References aren't recovered by coredumps and copies of them would be
+        // invisible to GC, so don't keep any.
+        if matches!(ty, WasmType::Ref(_)) {
+            return;
+        }
+        let val = builder.use_var(Variable::from_u32(index));
+        let offset = i32::try_from(index * FrameLocals::SLOT_SIZE).unwrap();
+        builder.ins().stack_store(val, slot, offset);
+    }
+
+    /// Whether this function keeps copies of its locals in its frame, for
+    /// coredumps or for debuggers to read.
+    fn records_frame_locals(&self) -> bool {
+        self.tunables.coredump_locals || self.tunables.debug_breakpoints
+    }
+
+    /// Calls into the host, before the current wasm instruction, if a
+    /// debugger has breakpoints set or is single-stepping.
+    fn debug_break_check(&mut self, builder: &mut FunctionBuilder<'_>) {
+        // The debugger may inspect or change the remaining fuel, so it's
+        // saved and reloaded around the call like the out-of-gas intrinsic
+        // does. Fuel for the preceding instructions has to be accounted for
+        // on both paths, so do that before branching.
+        if self.tunables.consume_fuel {
+            self.fuel_increment_var(builder);
+        }
+
+        let break_block = builder.create_block();
+        let continuation_block = builder.create_block();
+        builder.set_cold_block(break_block);
+
+        let limits = builder.use_var(self.vmruntime_limits_ptr);
+        let debug_break = builder.ins().load(
+            ir::types::I32,
+            ir::MemFlags::trusted(),
+            limits,
+            i32::from(self.offsets.ptr.vmruntime_limits_debug_break()),
+        );
+        builder
+            .ins()
+            .brif(debug_break, break_block, &[], continuation_block, &[]);
+        builder.seal_block(break_block);
+
+        builder.switch_to_block(break_block);
+        if self.tunables.consume_fuel {
+            self.fuel_save_from_var(builder);
+        }
+        let offset = builder.srcloc().bits();
+        let offset = builder.ins().iconst(ir::types::I32, i64::from(offset));
+        let debug_break_sig = self.builtin_function_signatures.debug_break(builder.func);
+        let (vmctx, debug_break) = self.translate_load_builtin_function_address(
+            &mut builder.cursor(),
+            BuiltinFunctionIndex::debug_break(),
+        );
+        builder
+            .ins()
+            .call_indirect(debug_break_sig, debug_break, &[vmctx, offset]);
+        if self.tunables.consume_fuel {
+            self.fuel_load_into_var(builder);
+        }
+        builder.ins().jump(continuation_block, &[]);
+        builder.seal_block(continuation_block);
+
+        builder.switch_to_block(continuation_block);
+    }
+
     fn fuel_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
         // On function entry we load the amount of fuel into a function-local
         // `self.fuel_var` to make fuel modifications fast locally. This cache
@@ -419,9 +647,9 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         &mut self,
         op: &Operator<'_>,
         builder: &mut FunctionBuilder<'_>,
-        reachable: bool,
+        state: &FuncTranslationState,
     ) {
-        if !reachable {
+        if !state.reachable() {
             // In unreachable code we shouldn't have any leftover fuel we
             // haven't accounted for since the reason for us to become
             // unreachable should have already added it to `self.fuel_var`.
@@ -429,23 +657,25 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             return;
         }
 
-        self.fuel_consumed += match op {
-            // Nop and drop generate no code, so don't consume fuel for them.
-            Operator::Nop | Operator::Drop => 0,
-
-            // Control flow may create branches, but is generally cheap and
-            // free, so don't consume fuel. Note the lack of `if` since some
-            // cost is incurred with the conditional check.
-            Operator::Block { .. }
-            | Operator::Loop { .. }
-            | Operator::Unreachable
-            | Operator::Return
-            | Operator::Else
-            | Operator::End => 0,
-
-            // everything else, just call it one operation.
-            _ => 1,
+        self.fuel_consumed += self.fuel_cost(op);
+
+        // Bulk memory and table operations may additionally be charged in
+        // proportion to the number of bytes or elements they process, which
+        // is always the topmost operand.
+        let model = &self.tunables.fuel_cost_model;
+        let units_per_fuel = match op {
+            Operator::MemoryCopy { .. }
+            | Operator::MemoryFill { .. }
+            | Operator::MemoryInit { .. } => model.bulk_memory_bytes_per_fuel,
+            Operator::TableCopy { .. }
+            | Operator::TableFill { .. }
+            | Operator::TableInit { .. }
+            | Operator::TableGrow { .. } => model.table_elements_per_fuel,
+            _ => 0,
         };
+        if units_per_fuel != 0 {
+            self.fuel_charge_length(builder, state.peek1(), units_per_fuel);
+        }
 
         match op {
             // Exiting a function (via a return or unreachable) or otherwise
@@ -516,6 +746,145 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         }
     }
 
+    /// Returns the fixed amount of fuel consumed by executing `op`, according
+    /// to the configured cost model.
+    fn fuel_cost(&self, op: &Operator<'_>) -> i64 {
+        let model = &self.tunables.fuel_cost_model;
+        let cost = match op {
+            // Nop and drop generate no code, so by default they don't consume
+            // fuel.
+            Operator::Nop | Operator::Drop => model.nop,
+
+            // Control flow may create branches, but is generally cheap and
+            // free by default. Note the lack of `if` since some cost is
+            // incurred with the conditional check.
+            Operator::Block { .. }
+            | Operator::Loop { .. }
+            | Operator::Unreachable
+            | Operator::Return
+            | Operator::Else
+            | Operator::End => model.control,
+
+            Operator::If { .. }
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. } => model.branch,
+
+            Operator::Call { .. }
+            | Operator::CallIndirect { .. }
+            | Operator::CallRef { .. }
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. }
+            | Operator::ReturnCallRef { .. } => model.call,
+
+            Operator::I32Load { .. }
+            | Operator::I64Load { .. }
+            | Operator::F32Load { .. }
+            | Operator::F64Load { .. }
+            | Operator::I32Load8S { .. }
+            | Operator::I32Load8U { .. }
+            | Operator::I32Load16S { .. }
+            | Operator::I32Load16U { .. }
+            | Operator::I64Load8S { .. }
+            | Operator::I64Load8U { .. }
+            | Operator::I64Load16S { .. }
+            | Operator::I64Load16U { .. }
+            | Operator::I64Load32S { .. }
+            | Operator::I64Load32U { .. }
+            | Operator::V128Load { .. }
+            | Operator::V128Load8x8S { .. }
+            | Operator::V128Load8x8U { .. }
+            | Operator::V128Load16x4S { .. }
+            | Operator::V128Load16x4U { .. }
+            | Operator::V128Load32x2S { .. }
+            | Operator::V128Load32x2U { .. }
+            | Operator::V128Load8Splat { .. }
+            | Operator::V128Load16Splat { .. }
+            | Operator::V128Load32Splat { .. }
+            | Operator::V128Load64Splat { .. }
+            | Operator::V128Load32Zero { .. }
+            | Operator::V128Load64Zero { .. }
+            | Operator::V128Load8Lane { .. }
+            | Operator::V128Load16Lane { .. }
+            | Operator::V128Load32Lane { .. }
+            | Operator::V128Load64Lane { .. } => model.load,
+
+            Operator::I32Store { .. }
+            | Operator::I64Store { .. }
+            | Operator::F32Store { .. }
+            | Operator::F64Store { .. }
+            | Operator::I32Store8 { .. }
+            | Operator::I32Store16 { .. }
+            | Operator::I64Store8 { .. }
+            | Operator::I64Store16 { .. }
+            | Operator::I64Store32 { .. }
+            | Operator::V128Store { .. }
+            | Operator::V128Store8Lane { .. }
+            | Operator::V128Store16Lane { .. }
+            | Operator::V128Store32Lane { .. }
+            | Operator::V128Store64Lane { .. } => model.store,
+
+            Operator::I32DivS
+            | Operator::I32DivU
+            | Operator::I32RemS
+            | Operator::I32RemU
+            | Operator::I64DivS
+            | Operator::I64DivU
+            | Operator::I64RemS
+            | Operator::I64RemU => model.division,
+
+            Operator::MemoryGrow { .. } => model.memory_grow,
+
+            Operator::MemoryCopy { .. }
+            | Operator::MemoryFill { .. }
+            | Operator::MemoryInit { .. }
+            | Operator::DataDrop { .. } => model.bulk_memory,
+
+            Operator::TableGet { .. }
+            | Operator::TableSet { .. }
+            | Operator::TableSize { .. }
+            | Operator::TableGrow { .. }
+            | Operator::TableFill { .. }
+            | Operator::TableCopy { .. }
+            | Operator::TableInit { .. }
+            | Operator::ElemDrop { .. } => model.table,
+
+            // everything else, just call it one operation.
+            _ => model.default,
+        };
+        i64::from(cost)
+    }
+
+    /// Consumes one unit of fuel for every `units_per_fuel` in the dynamic
+    /// `len` operand of a bulk operation, and then checks whether fuel ran
+    /// out so the operation itself isn't performed without enough fuel.
+    fn fuel_charge_length(
+        &mut self,
+        builder: &mut FunctionBuilder<'_>,
+        len: ir::Value,
+        units_per_fuel: u32,
+    ) {
+        let len = if builder.func.dfg.value_type(len) == I64 {
+            len
+        } else {
+            builder.ins().uextend(I64, len)
+        };
+        let cost = if units_per_fuel.is_power_of_two() {
+            builder
+                .ins()
+                .ushr_imm(len, i64::from(units_per_fuel.trailing_zeros()))
+        } else {
+            let divisor = builder.ins().iconst(I64, i64::from(units_per_fuel));
+            builder.ins().udiv(len, divisor)
+        };
+
+        self.fuel_increment_var(builder);
+        let fuel = builder.use_var(self.fuel_var);
+        let fuel = builder.ins().iadd(fuel, cost);
+        builder.def_var(self.fuel_var, fuel);
+        self.fuel_check(builder);
+    }
+
     fn fuel_after_op(&mut self, op: &Operator<'_>, builder: &mut FunctionBuilder<'_>) {
         // After a function call we need to reload our fuel value since the
         // function may have changed it.
@@ -1229,6 +1598,10 @@ impl<'module_environment> TargetEnvironment for FuncEnvironment<'module_environm
     fn proof_carrying_code(&self) -> bool {
         self.isa.flags().enable_pcc()
     }
+
+    fn bounds_check_elimination(&self) -> bool {
+        self.isa.flags().enable_bounds_check_elimination()
+    }
 }
 
 impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'module_environment> {
@@ -1247,6 +1620,7 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         self.fuel_var = Variable::new(num_locals + 1);
         self.epoch_deadline_var = Variable::new(num_locals + 2);
         self.epoch_ptr_var = Variable::new(num_locals + 3);
+        self.coverage_counters_var = Variable::new(num_locals + 4);
     }
 
     fn make_table(&mut self, func: &mut ir::Function, index: TableIndex) -> WasmResult<ir::Table> {
@@ -2543,7 +2917,13 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         state: &FuncTranslationState,
     ) -> WasmResult<()> {
         if self.tunables.consume_fuel {
-            self.fuel_before_op(op, builder, state.reachable());
+            self.fuel_before_op(op, builder, state);
+        }
+        if self.tunables.coverage_instrumentation {
+            self.coverage_before_op(builder, state.reachable());
+        }
+        if self.tunables.debug_breakpoints && state.reachable() {
+            self.debug_break_check(builder);
         }
         Ok(())
     }
@@ -2557,6 +2937,14 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         if self.tunables.consume_fuel && state.reachable() {
             self.fuel_after_op(op, builder);
         }
+        if self.tunables.coverage_instrumentation {
+            self.coverage_after_op(op);
+        }
+        if self.records_frame_locals() && state.reachable() {
+            if let Operator::LocalSet { local_index } | Operator::LocalTee { local_index } = op {
+                self.coredump_record_local(builder, *local_index);
+            }
+        }
         Ok(())
     }
 
@@ -2578,7 +2966,10 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
     ) -> WasmResult<()> {
         // If the `vmruntime_limits_ptr` variable will get used then we initialize
         // it here.
-        if self.tunables.consume_fuel || self.tunables.epoch_interruption {
+        if self.tunables.consume_fuel
+            || self.tunables.epoch_interruption
+            || self.tunables.debug_breakpoints
+        {
             self.declare_vmruntime_limits_ptr(builder);
         }
         // Additionally we initialize `fuel_var` if it will get used.
@@ -2589,6 +2980,14 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         if self.tunables.epoch_interruption {
             self.epoch_function_entry(builder);
         }
+        // Look up where this function's coverage counters live.
+        if self.tunables.coverage_instrumentation {
+            self.coverage_function_entry(builder);
+        }
+        // Start keeping copies of locals for coredumps and debuggers.
+        if self.records_frame_locals() {
+            self.coredump_locals_function_entry(builder);
+        }
 
         let func_name = self.current_func_name(builder);
         if func_name == Some("malloc") {
@@ -67,6 +67,9 @@ macro_rules! foreach_builtin_function {
             update_stack_pointer(vmctx: vmctx, value: i32);
             /// Invoked before memory.grow is called.
             update_mem_size(vmctx: vmctx, num_bytes: i32);
+            /// Invoked before the wasm instruction at `offset` when a debugger
+            /// has breakpoints set or is single-stepping.
+            debug_break(vmctx: vmctx, offset: i32);
         }
     };
 }
@@ -4,7 +4,7 @@
 use crate::{obj, Tunables};
 use crate::{
     DefinedFuncIndex, FilePos, FuncIndex, FunctionBodyData, ModuleTranslation, ModuleTypesBuilder,
-    PrimaryMap, StackMap, WasmError, WasmFuncType,
+    PrimaryMap, StackMap, WasmError, WasmFuncType, WasmType,
 };
 use anyhow::Result;
 use object::write::{Object, SymbolId};
@@ -24,6 +24,40 @@ use thiserror::Error;
 pub struct WasmFunctionInfo {
     pub start_srcloc: FilePos,
     pub stack_maps: Box<[StackMapInformation]>,
+    pub coverage: Box<[CoverageRegion]>,
+    pub frame_locals: Option<FrameLocals>,
+}
+
+/// Where a function's stack frame keeps a copy of its wasm locals, for
+/// recovering them in coredumps.
+///
+/// Each local, parameters first, is kept in its own `SLOT_SIZE`-byte slot and
+/// the copy is updated whenever the local is set, so it's accurate at every
+/// call and trap site in the function.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FrameLocals {
+    /// The offset of the first local's slot from the frame pointer.
+    pub fp_offset: i32,
+    /// The type of each local.
+    pub types: Box<[WasmType]>,
+}
+
+impl FrameLocals {
+    /// The size, in bytes, of the slot kept for each local.
+    pub const SLOT_SIZE: u32 = 16;
+}
+
+/// A straight-line region of a function's wasm code whose executions are
+/// counted by a single coverage counter.
+///
+/// Regions are listed in the order of their counters, which is the order in
+/// which the function's counters are laid out in memory.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoverageRegion {
+    /// The offset of the region's first instruction in the original wasm file.
+    pub start: FilePos,
+    /// The offset just past the region's last instruction.
+    pub end: FilePos,
 }
 
 /// Description of where a function is located in the text section of a
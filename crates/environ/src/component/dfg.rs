@@ -79,6 +79,15 @@ pub struct ComponentDfg {
     /// phase (this is not edited after creation).
     pub num_runtime_component_instances: u32,
 
+    /// The component instance that instantiated each of the core wasm
+    /// instances in `instances`.
+    pub instance_owners: PrimaryMap<InstanceId, RuntimeComponentInstanceIndex>,
+
+    /// The component instance that instantiated each component instance, or
+    /// `None` for the root component instance.
+    pub component_instance_parents:
+        PrimaryMap<RuntimeComponentInstanceIndex, Option<RuntimeComponentInstanceIndex>>,
+
     /// Known adapter modules and how they are instantiated.
     ///
     /// This map is not filled in on the initial creation of a `ComponentDfg`.
@@ -366,6 +375,24 @@ impl ComponentDfg {
             .map(|(name, export)| (name.clone(), linearize.export(export)))
             .collect();
 
+        // Adapter modules are instantiated on-demand while linearizing, so
+        // the origin of each runtime instance is recovered from the final
+        // mapping.
+        let mut runtime_instance_origins = linearize
+            .runtime_instances
+            .iter()
+            .map(|(instance, index)| {
+                let origin = match instance {
+                    RuntimeInstance::Normal(id) => {
+                        RuntimeInstanceOrigin::Module(self.instance_owners[*id])
+                    }
+                    RuntimeInstance::Adapter(_) => RuntimeInstanceOrigin::Adapter,
+                };
+                (*index, origin)
+            })
+            .collect::<Vec<_>>();
+        runtime_instance_origins.sort_by_key(|(index, _)| *index);
+
         // With all those pieces done the results of the dataflow-based
         // linearization are recorded into the `Component`. The number of
         // runtime values used for each index space is used from the `linearize`
@@ -385,6 +412,11 @@ impl ComponentDfg {
                 imports: self.imports,
                 import_types: self.import_types,
                 num_runtime_component_instances: self.num_runtime_component_instances,
+                component_instance_parents: self.component_instance_parents,
+                runtime_instance_origins: runtime_instance_origins
+                    .into_iter()
+                    .map(|(_, origin)| origin)
+                    .collect(),
                 num_resource_tables: self.num_resource_tables,
                 num_resources: (self.resources.len() + self.imported_resources.len()) as u32,
                 imported_resources: self.imported_resources,
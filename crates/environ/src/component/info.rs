@@ -127,6 +127,17 @@ pub struct Component {
     /// instead.
     pub num_runtime_component_instances: u32,
 
+    /// The component instance that instantiated each component instance, or
+    /// `None` for the root component instance.
+    ///
+    /// Components are flattened at compile time, so this is what remains of
+    /// the tree of nested component instances.
+    pub component_instance_parents:
+        PrimaryMap<RuntimeComponentInstanceIndex, Option<RuntimeComponentInstanceIndex>>,
+
+    /// Where each core wasm instance created during instantiation comes from.
+    pub runtime_instance_origins: PrimaryMap<RuntimeInstanceIndex, RuntimeInstanceOrigin>,
+
     /// The number of runtime memories (maximum `RuntimeMemoryIndex`) needed to
     /// instantiate this component.
     ///
@@ -306,6 +317,18 @@ pub enum InstantiateModule {
     ),
 }
 
+/// Where a core wasm instance created while instantiating a component comes
+/// from.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum RuntimeInstanceOrigin {
+    /// A module instantiated by the listed component instance.
+    Module(RuntimeComponentInstanceIndex),
+
+    /// An adapter module generated by Wasmtime to fuse calls between
+    /// component instances.
+    Adapter,
+}
+
 /// Definition of a core wasm item and where it can come from within a
 /// component.
 ///
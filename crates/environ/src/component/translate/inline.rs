@@ -125,6 +125,7 @@ pub(super) fn run(
     // the root frame which are then used for recording the exports of the
     // component.
     inliner.result.num_runtime_component_instances += 1;
+    inliner.result.component_instance_parents.push(None);
     let frame = InlinerFrame::new(index, result, ComponentClosure::default(), args, None);
     let resources_snapshot = types.resources_mut().clone();
     let mut frames = vec![(frame, resources_snapshot)];
@@ -722,6 +723,8 @@ impl<'a> Inliner<'a> {
                     .push(dfg::SideEffect::Instance(idx));
                 let idx2 = self.runtime_instances.push(instance_module);
                 assert_eq!(idx, idx2);
+                let idx2 = self.result.instance_owners.push(frame.instance);
+                assert_eq!(idx, idx2);
                 frame
                     .module_instances
                     .push(ModuleInstanceDef::Instantiated(idx, *module));
@@ -771,6 +774,11 @@ impl<'a> Inliner<'a> {
                     self.result.num_runtime_component_instances,
                 );
                 self.result.num_runtime_component_instances += 1;
+                let index2 = self
+                    .result
+                    .component_instance_parents
+                    .push(Some(frame.instance));
+                assert_eq!(index, index2);
                 let frame = InlinerFrame::new(
                     index,
                     &self.nested_components[component.index],
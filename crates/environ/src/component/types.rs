@@ -461,11 +461,17 @@ impl ComponentTypesBuilder {
         id: types::ComponentFuncTypeId,
     ) -> Result<TypeFuncIndex> {
         let ty = &types[id];
+        let param_names = ty.params.iter().map(|(name, _)| name.to_string()).collect();
         let params = ty
             .params
             .iter()
             .map(|(_name, ty)| self.valtype(types, ty))
             .collect::<Result<_>>()?;
+        let result_names = ty
+            .results
+            .iter()
+            .filter_map(|(name, _)| name.as_ref().map(|n| n.to_string()))
+            .collect();
         let results = ty
             .results
             .iter()
@@ -474,6 +480,8 @@ impl ComponentTypesBuilder {
         let ty = TypeFunc {
             params: self.new_tuple_type(params),
             results: self.new_tuple_type(results),
+            param_names,
+            result_names,
         };
         Ok(self.add_func_type(ty))
     }
@@ -1012,6 +1020,11 @@ pub struct TypeFunc {
     pub params: TypeTupleIndex,
     /// Results of the function represented as a tuple.
     pub results: TypeTupleIndex,
+    /// The names of the parameters, in the same order as `params`.
+    pub param_names: Vec<String>,
+    /// The names of the results, in the same order as `results`, or empty if
+    /// the function has an unnamed result.
+    pub result_names: Vec<String>,
 }
 
 /// All possible interface types that values can have.
@@ -30,7 +30,7 @@ pub use crate::ref_bits::*;
 pub use crate::scopevec::ScopeVec;
 pub use crate::stack_map::StackMap;
 pub use crate::trap_encoding::*;
-pub use crate::tunables::Tunables;
+pub use crate::tunables::{FuelCostModel, Tunables};
 pub use crate::vmoffsets::*;
 pub use object;
 
@@ -18,7 +18,7 @@ use wasmparser::types::{CoreTypeId, Types};
 use wasmparser::{
     CompositeType, CustomSectionReader, DataKind, ElementItems, ElementKind, Encoding,
     ExternalKind, FuncToValidate, FunctionBody, NameSectionReader, Naming, Operator, Parser,
-    Payload, TypeRef, Validator, ValidatorResources,
+    Payload, TypeRef, Validator, ValidatorResources, WasmFeatures,
 };
 
 /// Object containing the standalone environment information.
@@ -52,6 +52,17 @@ pub struct ModuleTranslation<'data> {
     /// References to the function bodies.
     pub function_body_inputs: PrimaryMap<DefinedFuncIndex, FunctionBodyData<'data>>,
 
+    /// Bodies of the functions which are small enough to be inlined into
+    /// their direct callers.
+    ///
+    /// This is only populated when `Tunables::inlining` is enabled, and unlike
+    /// `function_body_inputs` it is still available while functions are being
+    /// compiled.
+    pub inlining_candidates: HashMap<FuncIndex, FunctionBody<'data>>,
+
+    /// The WebAssembly features this module was validated with.
+    pub wasm_features: WasmFeatures,
+
     /// A list of type signatures which are considered exported from this
     /// module, or those that can possibly be called. This list is sorted, and
     /// trampolines for each of these signatures are required.
@@ -185,6 +196,7 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
         data: &'data [u8],
     ) -> WasmResult<ModuleTranslation<'data>> {
         self.result.wasm = data;
+        self.result.wasm_features = *self.validator.features();
 
         for payload in parser.parse_all(data) {
             self.translate_payload(payload?)?;
@@ -560,6 +572,14 @@ impl<'a, 'data> ModuleEnvironment<'a, 'data> {
                         });
                 }
                 body.allow_memarg64(self.validator.features().memory64);
+                if self.tunables.inlining
+                    && !self.tunables.generate_native_debuginfo
+                    && body.range().len() <= self.tunables.inlining_max_callee_size as usize
+                {
+                    self.result
+                        .inlining_candidates
+                        .insert(func_index, body.clone());
+                }
                 self.result
                     .function_body_inputs
                     .push(FunctionBodyData { validator, body });
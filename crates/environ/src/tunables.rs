@@ -29,6 +29,10 @@ pub struct Tunables {
     /// will be consumed every time a wasm instruction is executed.
     pub consume_fuel: bool,
 
+    /// How much fuel each wasm instruction consumes when `consume_fuel` is
+    /// enabled.
+    pub fuel_cost_model: FuelCostModel,
+
     /// Whether or not we use epoch-based interruption.
     pub epoch_interruption: bool,
 
@@ -54,6 +58,26 @@ pub struct Tunables {
 
     /// Whether or not Wasm functions can be tail-called or not.
     pub tail_callable: bool,
+
+    /// Whether or not small functions are inlined into their direct callers
+    /// within the same module.
+    pub inlining: bool,
+
+    /// The maximum size, in bytes of the wasm function body, of a callee for
+    /// it to be considered for inlining.
+    pub inlining_max_callee_size: u32,
+
+    /// Whether or not generated code counts how many times each basic block
+    /// of wasm code is executed.
+    pub coverage_instrumentation: bool,
+
+    /// Whether or not generated code keeps a copy of each function's wasm
+    /// locals in its stack frame so coredumps can recover them.
+    pub coredump_locals: bool,
+
+    /// Whether or not generated code checks whether a debugger wants to stop
+    /// before each wasm instruction.
+    pub debug_breakpoints: bool,
 }
 
 impl Default for Tunables {
@@ -105,6 +129,7 @@ impl Default for Tunables {
             generate_native_debuginfo: false,
             parse_wasm_debuginfo: true,
             consume_fuel: false,
+            fuel_cost_model: FuelCostModel::default(),
             epoch_interruption: false,
             static_memory_bound_is_maximum: false,
             guard_before_linear_memory: true,
@@ -112,6 +137,81 @@ impl Default for Tunables {
             debug_adapter_modules: false,
             relaxed_simd_deterministic: false,
             tail_callable: false,
+            inlining: false,
+            inlining_max_callee_size: 64,
+            coverage_instrumentation: false,
+            coredump_locals: false,
+            debug_breakpoints: false,
+        }
+    }
+}
+
+/// The amount of fuel charged for executing each class of wasm operator.
+///
+/// The default model charges one unit of fuel for every operator except those
+/// which generate no code, such as `nop`, `drop` and structured control flow
+/// markers, which are free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FuelCostModel {
+    /// Cost of operators not covered by any other class below.
+    pub default: u32,
+
+    /// Cost of `nop` and `drop`.
+    pub nop: u32,
+
+    /// Cost of `block`, `loop`, `else`, `end`, `return` and `unreachable`.
+    pub control: u32,
+
+    /// Cost of `if`, `br`, `br_if` and `br_table`.
+    pub branch: u32,
+
+    /// Cost of direct, indirect and tail calls.
+    pub call: u32,
+
+    /// Cost of loads from linear memory.
+    pub load: u32,
+
+    /// Cost of stores to linear memory.
+    pub store: u32,
+
+    /// Cost of integer division and remainder.
+    pub division: u32,
+
+    /// Cost of `memory.grow`.
+    pub memory_grow: u32,
+
+    /// Fixed cost of `memory.copy`, `memory.fill` and `memory.init`.
+    pub bulk_memory: u32,
+
+    /// Number of bytes processed by bulk memory operations per additional unit
+    /// of fuel, or 0 to not charge for the length of the operation.
+    pub bulk_memory_bytes_per_fuel: u32,
+
+    /// Fixed cost of the `table.*` operators.
+    pub table: u32,
+
+    /// Number of elements processed by `table.copy`, `table.fill`,
+    /// `table.init` and `table.grow` per additional unit of fuel, or 0 to not
+    /// charge for the length of the operation.
+    pub table_elements_per_fuel: u32,
+}
+
+impl Default for FuelCostModel {
+    fn default() -> Self {
+        Self {
+            default: 1,
+            nop: 0,
+            control: 0,
+            branch: 1,
+            call: 1,
+            load: 1,
+            store: 1,
+            division: 1,
+            memory_grow: 1,
+            bulk_memory: 1,
+            bulk_memory_bytes_per_fuel: 0,
+            table: 1,
+            table_elements_per_fuel: 0,
         }
     }
 }
@@ -12,6 +12,7 @@
 //      store: *mut dyn Store,
 //      builtins: *mut VMBuiltinFunctionsArray,
 //      signature_ids: *const VMSharedSignatureIndex,
+//      coverage_counters: *const *mut u64,
 //      imported_functions: [VMFunctionImport; module.num_imported_functions],
 //      imported_tables: [VMTableImport; module.num_imported_tables],
 //      imported_memories: [VMMemoryImport; module.num_imported_memories],
@@ -81,6 +82,7 @@ pub struct VMOffsets<P> {
     store: u32,
     builtin_functions: u32,
     signature_ids: u32,
+    coverage_counters: u32,
     imported_functions: u32,
     imported_tables: u32,
     imported_memories: u32,
@@ -185,6 +187,11 @@ pub trait PtrSize {
         self.vmruntime_limits_last_wasm_exit_pc() + self.size()
     }
 
+    /// Return the offset of the `debug_break` field of `VMRuntimeLimits`.
+    fn vmruntime_limits_debug_break(&self) -> u8 {
+        self.vmruntime_limits_last_wasm_entry_sp() + self.size()
+    }
+
     // Offsets within `VMMemoryDefinition`
 
     /// The offset of the `base` field.
@@ -355,6 +362,7 @@ impl<P: PtrSize> VMOffsets<P> {
             imported_memories: "imported memories",
             imported_tables: "imported tables",
             imported_functions: "imported functions",
+            coverage_counters: "coverage counters",
             signature_ids: "module types",
             builtin_functions: "jit builtin functions state",
             store: "jit store state",
@@ -388,6 +396,7 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
             store: 0,
             builtin_functions: 0,
             signature_ids: 0,
+            coverage_counters: 0,
             imported_functions: 0,
             imported_tables: 0,
             imported_memories: 0,
@@ -439,6 +448,7 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
             size(store) = ret.ptr.size() * 2,
             size(builtin_functions) = ret.pointer_size(),
             size(signature_ids) = ret.ptr.size(),
+            size(coverage_counters) = ret.ptr.size(),
             size(imported_functions)
                 = cmul(ret.num_imported_functions, ret.size_of_vmfunction_import()),
             size(imported_tables)
@@ -649,6 +659,13 @@ impl<P: PtrSize> VMOffsets<P> {
         self.signature_ids
     }
 
+    /// The offset of the `*const *mut u64` pointer to the per-function
+    /// coverage counter arrays.
+    #[inline]
+    pub fn vmctx_coverage_counters(&self) -> u32 {
+        self.coverage_counters
+    }
+
     /// The offset of the `tables` array.
     #[inline]
     pub fn vmctx_imported_functions_begin(&self) -> u32 {
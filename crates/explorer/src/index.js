@@ -1,13 +1,15 @@
 /*** State *********************************************************************/
 
 class State {
-  constructor(wat, asm) {
+  constructor(wat, clif, asm, winchAsm) {
     this.wat = wat;
+    this.clif = clif;
     this.asm = asm;
+    this.winchAsm = winchAsm;
   }
 }
 
-const state = window.STATE = new State(window.WAT, window.ASM);
+const state = window.STATE = new State(window.WAT, window.CLIF, window.ASM, window.WINCH_ASM);
 
 /*** Hues for Offsets **********************************************************/
 
@@ -67,7 +69,7 @@ const watByOffset = new Map();
 // Get asm instruction elements by Wasm offset.
 const asmByOffset = new Map();
 
-// Get all (WAT chunk or asm instruction) elements by offset.
+// Get all (WAT chunk, CLIF line or asm instruction) elements by offset.
 const anyByOffset = new Map();
 
 const addWatElem = (offset, elem) => {
@@ -115,13 +117,14 @@ watElem.addEventListener("click", event => {
   });
 }, { passive: true });
 
-const asmElem = document.getElementById("asm");
-asmElem.addEventListener("click", event => {
-  if (event.target.dataset.wasmOffset == null) {
+const scrollToWat = event => {
+  // Instructions may contain a nested element for their ISLE rules.
+  const target = event.target.closest("[data-wasm-offset]");
+  if (target == null) {
     return;
   }
 
-  const offset = parseInt(event.target.dataset.wasmOffset);
+  const offset = parseInt(target.dataset.wasmOffset);
   if (!watByOffset.get(offset)) {
     return;
   }
@@ -132,7 +135,18 @@ asmElem.addEventListener("click", event => {
     block: "center",
     inline: "nearest",
   });
-}, { passive: true });
+};
+
+const asmElem = document.getElementById("asm");
+asmElem.addEventListener("click", scrollToWat, { passive: true });
+
+const clifElem = document.getElementById("clif");
+clifElem.addEventListener("click", scrollToWat, { passive: true });
+
+const winchAsmElem = document.getElementById("winch-asm");
+if (winchAsmElem) {
+  winchAsmElem.addEventListener("click", scrollToWat, { passive: true });
+}
 
 const onMouseEnter = event => {
   if (event.target.dataset.wasmOffset == null) {
@@ -189,34 +203,97 @@ const renderInst = (mnemonic, operands) => {
   }
 };
 
+// Render the lowering rule that produced an instruction and, if different, the
+// innermost rule (typically a helper) that constructed it.
+const renderIsleRules = rules => {
+  if (rules.lowering == rules.inner) {
+    return rules.lowering;
+  } else {
+    return `${rules.lowering} -> ${rules.inner}`;
+  }
+};
+
+const renderPaneHeader = (paneElem, title) => {
+  const header = document.createElement("h2");
+  header.textContent = title;
+  paneElem.appendChild(header);
+};
+
 // Render the ASM.
 
-let nthFunc = 0;
-for (const func of state.asm.functions) {
+const renderAsm = (asm, paneElem) => {
+  let nthFunc = 0;
+  for (const func of asm.functions) {
+    const funcElem = document.createElement("div");
+
+    const funcHeader = document.createElement("h3");
+    funcHeader.textContent = `Defined Function ${nthFunc}`;
+    funcElem.appendChild(funcHeader);
+
+    const bodyElem = document.createElement("pre");
+    for (const inst of func.instructions) {
+      const instElem = document.createElement("span");
+      instElem.textContent = `${renderAddress(inst.address)}    ${renderBytes(inst.bytes)}    ${renderInst(inst.mnemonic, inst.operands)}`;
+      if (inst.isle_rules != null) {
+        const rulesElem = document.createElement("span");
+        rulesElem.className = "isle-rules";
+        rulesElem.textContent = `    ; ${renderIsleRules(inst.isle_rules)}`;
+        instElem.appendChild(rulesElem);
+      }
+      instElem.appendChild(document.createTextNode("\n"));
+      if (inst.wasm_offset != null) {
+        instElem.setAttribute("data-wasm-offset", inst.wasm_offset);
+        const hue = hueForOffset(inst.wasm_offset);
+        instElem.style.backgroundColor = `hsl(${hue} 50% 90%)`;
+        instElem.addEventListener("mouseenter", onMouseEnter);
+        instElem.addEventListener("mouseleave", onMouseLeave);
+        addAsmElem(inst.wasm_offset, instElem);
+      }
+      bodyElem.appendChild(instElem);
+    }
+    funcElem.appendChild(bodyElem);
+
+    paneElem.appendChild(funcElem);
+    nthFunc++;
+  }
+};
+
+renderPaneHeader(asmElem, "Cranelift");
+renderAsm(state.asm, asmElem);
+if (state.winchAsm != null) {
+  renderPaneHeader(winchAsmElem, "Winch");
+  renderAsm(state.winchAsm, winchAsmElem);
+}
+
+// Render the CLIF.
+
+renderPaneHeader(clifElem, "Optimized CLIF");
+let nthClifFunc = 0;
+for (const func of state.clif.functions) {
   const funcElem = document.createElement("div");
 
   const funcHeader = document.createElement("h3");
-  funcHeader.textContent = `Defined Function ${nthFunc}`;
+  funcHeader.textContent = `Defined Function ${nthClifFunc}`;
   funcElem.appendChild(funcHeader);
 
   const bodyElem = document.createElement("pre");
-  for (const inst of func.instructions) {
-    const instElem = document.createElement("span");
-    instElem.textContent = `${renderAddress(inst.address)}    ${renderBytes(inst.bytes)}    ${renderInst(inst.mnemonic, inst.operands)}\n`;
-    if (inst.wasm_offset != null) {
-      instElem.setAttribute("data-wasm-offset", inst.wasm_offset);
-      const hue = hueForOffset(inst.wasm_offset);
-      instElem.style.backgroundColor = `hsl(${hue} 50% 90%)`;
-      instElem.addEventListener("mouseenter", onMouseEnter);
-      instElem.addEventListener("mouseleave", onMouseLeave);
-      addAsmElem(inst.wasm_offset, instElem);
+  for (const line of func.lines) {
+    const lineElem = document.createElement("span");
+    lineElem.textContent = line.clif;
+    if (line.wasm_offset != null) {
+      lineElem.setAttribute("data-wasm-offset", line.wasm_offset);
+      const hue = hueForOffset(line.wasm_offset);
+      lineElem.style.backgroundColor = `hsl(${hue} 50% 90%)`;
+      lineElem.addEventListener("mouseenter", onMouseEnter);
+      lineElem.addEventListener("mouseleave", onMouseLeave);
+      addAsmElem(line.wasm_offset, lineElem);
     }
-    bodyElem.appendChild(instElem);
+    bodyElem.appendChild(lineElem);
   }
   funcElem.appendChild(bodyElem);
 
-  asmElem.appendChild(funcElem);
-  nthFunc++;
+  clifElem.appendChild(funcElem);
+  nthClifFunc++;
 }
 
 // Render the WAT.
@@ -1,10 +1,18 @@
 use anyhow::Result;
 use capstone::arch::BuildsCapstone;
 use serde_derive::Serialize;
+use std::path::Path;
 use std::{io::Write, str::FromStr};
 
+/// Generates the explorer's HTML for `wasm` compiled with `config`.
+///
+/// Alongside the WAT and the disassembly, this shows the optimized CLIF of
+/// each function and the ISLE rules that produced each instruction. When
+/// `winch_config` is provided the module is also compiled with it, and its
+/// disassembly is shown next to the one of `config` for comparison.
 pub fn generate(
     config: &wasmtime::Config,
+    winch_config: Option<&wasmtime::Config>,
     target: Option<&str>,
     wasm: &[u8],
     dest: &mut dyn Write,
@@ -14,10 +22,33 @@ pub fn generate(
         Some(target) => target_lexicon::Triple::from_str(target)?,
     };
 
+    // Have Cranelift write out the CLIF and ISLE rules of each function to a
+    // scratch directory as it compiles them.
+    let clif_dir = tempfile::tempdir()?;
+    let mut config = config.clone();
+    config.emit_clif(clif_dir.path());
+    // Safety: this setting only generates metadata on the side and doesn't
+    // affect the generated code.
+    unsafe {
+        config.cranelift_flag_enable("machine_code_isle_rule_info");
+    }
+
     let wat = annotate_wat(wasm)?;
     let wat_json = serde_json::to_string(&wat)?;
-    let asm = annotate_asm(config, &target, wasm)?;
+    let module = compile(&config, wasm)?;
+    let asm = annotate_asm(&module, &target, Some(clif_dir.path()))?;
     let asm_json = serde_json::to_string(&asm)?;
+    let clif = annotate_clif(&module, clif_dir.path());
+    let clif_json = serde_json::to_string(&clif)?;
+    let winch_asm = winch_config
+        .map(|winch_config| annotate_asm(&compile(winch_config, wasm)?, &target, None))
+        .transpose()?;
+    let winch_asm_json = serde_json::to_string(&winch_asm)?;
+    let winch_pane = if winch_asm.is_some() {
+        r#"<div id="winch-asm" class="pane"></div>"#
+    } else {
+        ""
+    };
 
     let index_css = include_str!("./index.css");
     let index_js = include_str!("./index.js");
@@ -34,11 +65,15 @@ pub fn generate(
     </style>
   </head>
   <body class="hbox">
-    <pre id="wat"></pre>
-    <div id="asm"></div>
+    <pre id="wat" class="pane"></pre>
+    <div id="clif" class="pane"></div>
+    <div id="asm" class="pane"></div>
+    {winch_pane}
     <script>
       window.WAT = {wat_json};
+      window.CLIF = {clif_json};
       window.ASM = {asm_json};
+      window.WINCH_ASM = {winch_asm_json};
     </script>
     <script>
       {index_js}
@@ -76,6 +111,98 @@ fn annotate_wat(wasm: &[u8]) -> Result<AnnotatedWat> {
     Ok(AnnotatedWat { chunks })
 }
 
+#[derive(Serialize, Debug)]
+struct AnnotatedClif {
+    functions: Vec<AnnotatedClifFunction>,
+}
+
+#[derive(Serialize, Debug)]
+struct AnnotatedClifFunction {
+    lines: Vec<AnnotatedClifLine>,
+}
+
+#[derive(Serialize, Debug)]
+struct AnnotatedClifLine {
+    wasm_offset: Option<WasmOffset>,
+    clif: String,
+}
+
+fn compile(config: &wasmtime::Config, wasm: &[u8]) -> Result<wasmtime::Module> {
+    let engine = wasmtime::Engine::new(config)?;
+    wasmtime::Module::new(&engine, wasm)
+}
+
+/// Returns the index that Cranelift names the CLIF files of the first
+/// defined function after, since function indices count imports first.
+fn first_defined_func_index(module: &wasmtime::Module) -> usize {
+    module
+        .imports()
+        .filter(|import| matches!(import.ty(), wasmtime::ExternType::Func(_)))
+        .count()
+}
+
+fn annotate_clif(module: &wasmtime::Module, clif_dir: &Path) -> AnnotatedClif {
+    let first_defined = first_defined_func_index(module);
+
+    let functions = (0..module.function_locations().len())
+        .map(|i| {
+            // Functions that weren't compiled with Cranelift, e.g. when
+            // using Winch, don't have any CLIF.
+            let path = clif_dir.join(format!("wasm_func_{}.opt.clif", first_defined + i));
+            let clif = std::fs::read_to_string(path).unwrap_or_default();
+            let lines = clif
+                .lines()
+                .map(|line| AnnotatedClifLine {
+                    wasm_offset: clif_line_wasm_offset(line),
+                    clif: format!("{line}\n"),
+                })
+                .collect();
+            AnnotatedClifFunction { lines }
+        })
+        .collect();
+    AnnotatedClif { functions }
+}
+
+/// Parses the `@xxxx` source location that CLIF instructions are prefixed
+/// with, which Wasmtime sets to the instruction's offset in the Wasm module.
+fn clif_line_wasm_offset(line: &str) -> Option<WasmOffset> {
+    let srcloc = line.trim_start().strip_prefix('@')?;
+    let end = srcloc
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(srcloc.len());
+    u32::from_str_radix(&srcloc[..end], 16).ok().map(WasmOffset)
+}
+
+#[derive(Serialize, Debug)]
+struct IsleRules {
+    lowering: String,
+    inner: String,
+}
+
+/// Reads the ISLE rules that produced each range of a function's code, as
+/// written by Cranelift next to the function's CLIF.
+fn read_isle_rules(path: &Path) -> Vec<(u32, u32, String, String)> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => parse_isle_rules(&contents),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Parses the lines of a `wasm_func_N.isle` file, each of the form
+/// `start end lowering|inner`, skipping any line that doesn't match.
+fn parse_isle_rules(contents: &str) -> Vec<(u32, u32, String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let start = parts.next()?.parse().ok()?;
+            let end = parts.next()?.parse().ok()?;
+            let (lowering, inner) = parts.next()?.split_once('|')?;
+            Some((start, end, lowering.to_string(), inner.to_string()))
+        })
+        .collect()
+}
+
 #[derive(Serialize, Debug)]
 struct AnnotatedAsm {
     functions: Vec<AnnotatedFunction>,
@@ -93,15 +220,15 @@ struct AnnotatedInstruction {
     bytes: Vec<u8>,
     mnemonic: Option<String>,
     operands: Option<String>,
+    isle_rules: Option<IsleRules>,
 }
 
 fn annotate_asm(
-    config: &wasmtime::Config,
+    module: &wasmtime::Module,
     target: &target_lexicon::Triple,
-    wasm: &[u8],
+    clif_dir: Option<&Path>,
 ) -> Result<AnnotatedAsm> {
-    let engine = wasmtime::Engine::new(config)?;
-    let module = wasmtime::Module::new(&engine, wasm)?;
+    let first_defined = first_defined_func_index(module);
 
     let text = module.text();
     let address_map: Vec<_> = module
@@ -131,8 +258,14 @@ fn annotate_asm(
     let functions = module
         .function_locations()
         .into_iter()
-        .map(|(start, len)| {
+        .enumerate()
+        .map(|(i, (start, len))| {
             let body = &text[start..][..len];
+            let isle_rules = clif_dir
+                .map(|dir| {
+                    read_isle_rules(&dir.join(format!("wasm_func_{}.isle", first_defined + i)))
+                })
+                .unwrap_or_default();
 
             let mut cs = match target.architecture {
                 target_lexicon::Architecture::Aarch64(_) => capstone::Capstone::new()
@@ -172,12 +305,21 @@ fn annotate_asm(
                 .map(|inst| {
                     let address = u32::try_from(inst.address()).unwrap();
                     let wasm_offset = wasm_offset_for_address(start, address);
+                    let func_offset = address - u32::try_from(start).unwrap();
+                    let isle_rules = isle_rules
+                        .iter()
+                        .find(|(from, to, _, _)| (*from..*to).contains(&func_offset))
+                        .map(|(_, _, lowering, inner)| IsleRules {
+                            lowering: lowering.clone(),
+                            inner: inner.clone(),
+                        });
                     Ok(AnnotatedInstruction {
                         wasm_offset,
                         address,
                         bytes: inst.bytes().to_vec(),
                         mnemonic: inst.mnemonic().map(ToString::to_string),
                         operands: inst.op_str().map(ToString::to_string),
+                        isle_rules,
                     })
                 })
                 .collect::<Result<Vec<_>>>()?;
@@ -187,3 +329,91 @@ fn annotate_asm(
 
     Ok(AnnotatedAsm { functions })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_isle_rules_lines() {
+        let rules = parse_isle_rules(
+            "0 4 lower.isle line 12|inst.isle line 345\n\
+             4 9 lower.isle line 20|lower.isle line 20\n\
+             not a range\n\
+             9 x lower.isle line 1|lower.isle line 1\n\
+             9 12 missing separator\n",
+        );
+        assert_eq!(
+            rules,
+            [
+                (
+                    0,
+                    4,
+                    "lower.isle line 12".to_string(),
+                    "inst.isle line 345".to_string()
+                ),
+                (
+                    4,
+                    9,
+                    "lower.isle line 20".to_string(),
+                    "lower.isle line 20".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn clif_line_offsets() {
+        assert_eq!(
+            clif_line_wasm_offset("@002e                               v3 = iadd v1, v2")
+                .map(|o| o.0),
+            Some(0x2e)
+        );
+        assert_eq!(
+            clif_line_wasm_offset("    @001a v3 = iadd v1, v2").map(|o| o.0),
+            Some(0x1a)
+        );
+        assert!(clif_line_wasm_offset("block0(v0: i64, v1: i64):").is_none());
+        assert!(clif_line_wasm_offset("    v3 = iadd v1, v2").is_none());
+    }
+
+    #[test]
+    fn isle_rules_of_compiled_module() -> Result<()> {
+        let wasm = wat::parse_str(
+            r#"
+                (module
+                    (func (export "f") (param i32 i32) (result i32)
+                        local.get 0
+                        local.get 1
+                        i32.add))
+            "#,
+        )?;
+        let dir = tempfile::tempdir()?;
+        let mut config = wasmtime::Config::new();
+        config.emit_clif(dir.path());
+        unsafe {
+            config.cranelift_flag_enable("machine_code_isle_rule_info");
+        }
+        let module = compile(&config, &wasm)?;
+        let (_, len) = module.function_locations()[0];
+
+        let rules = read_isle_rules(&dir.path().join("wasm_func_0.isle"));
+        assert!(!rules.is_empty());
+        let mut prev_end = 0;
+        for (start, end, lowering, inner) in &rules {
+            assert!(prev_end <= *start && start < end, "{rules:?}");
+            assert!(lowering.contains(".isle line "), "{rules:?}");
+            assert!(inner.contains(".isle line "), "{rules:?}");
+            prev_end = *end;
+        }
+        assert!(prev_end as usize <= len);
+
+        let html = {
+            let mut html = Vec::new();
+            generate(&config, None, None, &wasm, &mut html)?;
+            String::from_utf8(html)?
+        };
+        assert!(html.contains(r#""isle_rules":{"lowering":"#));
+        Ok(())
+    }
+}
@@ -568,6 +568,15 @@ impl ComponentInstance {
         &mut self.component_resource_tables
     }
 
+    /// Same as `component_resource_tables`, but without the ability to
+    /// modify the tables.
+    #[inline]
+    pub fn component_resource_tables_ref(
+        &self,
+    ) -> &PrimaryMap<TypeResourceTableIndex, ResourceTable> {
+        &self.component_resource_tables
+    }
+
     /// Returns the destructor and instance flags for the specified resource
     /// table type.
     ///
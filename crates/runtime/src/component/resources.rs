@@ -278,6 +278,19 @@ impl ResourceTables<'_> {
 }
 
 impl ResourceTable {
+    /// Returns the live handles in this table in index order, each as its
+    /// index, its representation, and whether it's an `own` handle.
+    pub fn handles(&self) -> impl Iterator<Item = (u32, u32, bool)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, slot)| match slot {
+                Slot::Free { .. } => None,
+                Slot::Own { rep, .. } => Some((idx as u32, *rep, true)),
+                Slot::Borrow { rep, .. } => Some((idx as u32, *rep, false)),
+            })
+    }
+
     fn next(&self) -> usize {
         self.next as usize
     }
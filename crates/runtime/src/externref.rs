@@ -110,7 +110,7 @@ use std::mem;
 use std::ops::Deref;
 use std::ptr::{self, NonNull};
 use std::sync::atomic::{self, AtomicUsize, Ordering};
-use wasmtime_environ::StackMap;
+use wasmtime_environ::{FrameLocals, StackMap};
 
 /// An external reference to some opaque data.
 ///
@@ -803,6 +803,10 @@ pub trait ModuleInfoLookup {
 pub trait ModuleInfo {
     /// Lookup the stack map at a program counter value.
     fn lookup_stack_map(&self, pc: usize) -> Option<&StackMap>;
+
+    /// Lookup where the frame of the function containing a program counter
+    /// value keeps copies of its locals, if it does.
+    fn lookup_frame_locals(&self, pc: usize) -> Option<&FrameLocals>;
 }
 
 #[derive(Debug, Default)]
@@ -436,6 +436,12 @@ impl Instance {
         unsafe { self.vmctx_plus_offset_mut(self.offsets().vmctx_epoch_ptr()) }
     }
 
+    /// Return a pointer to the per-function coverage counter arrays used by
+    /// this instance.
+    fn coverage_counters(&mut self) -> *mut *const *mut u64 {
+        unsafe { self.vmctx_plus_offset_mut(self.offsets().vmctx_coverage_counters()) }
+    }
+
     /// Return a pointer to the `VMExternRefActivationsTable`.
     pub fn externref_activations_table(&mut self) -> *mut *mut VMExternRefActivationsTable {
         unsafe { self.vmctx_plus_offset_mut(self.offsets().vmctx_externref_activations_table()) }
@@ -1123,6 +1129,10 @@ impl Instance {
         let signatures = self.runtime_info.signature_ids();
         *self.vmctx_plus_offset_mut(offsets.vmctx_signature_ids_array()) = signatures.as_ptr();
 
+        // Coverage counters, if any, are configured by the embedder after
+        // allocation.
+        *self.coverage_counters() = ptr::null();
+
         // Initialize the built-in functions
         *self.vmctx_plus_offset_mut(offsets.vmctx_builtin_functions()) =
             &VMBuiltinFunctionsArray::INIT;
@@ -1443,6 +1453,16 @@ impl InstanceHandle {
         self.instance_mut().set_store(Some(store));
     }
 
+    /// Configure where this instance's coverage counters live.
+    ///
+    /// `counters` points to an array, indexed by `DefinedFuncIndex`, of
+    /// pointers to each function's counters. It must be set before any code
+    /// compiled with coverage instrumentation runs in this instance, and it
+    /// must stay valid for as long as the instance may run.
+    pub unsafe fn set_coverage_counters(&mut self, counters: *const *mut u64) {
+        *self.instance_mut().coverage_counters() = counters;
+    }
+
     /// Returns a clone of this instance.
     ///
     /// This is unsafe because the returned handle here is just a cheap clone
@@ -144,6 +144,10 @@ pub unsafe trait Store {
     /// number. Cannot fail; cooperative epoch-based yielding is
     /// completely semantically transparent. Returns the new deadline.
     fn new_epoch(&mut self) -> Result<u64, Error>;
+    /// Callback invoked before the wasm instruction at `offset` of the
+    /// current module when a debugger has breakpoints set or is
+    /// single-stepping. If an error is returned it's raised as a trap.
+    fn debug_break(&mut self, offset: u32) -> Result<(), Error>;
 
     /// Metadata required for resources for the component model.
     #[cfg(feature = "component-model")]
@@ -496,6 +496,11 @@ unsafe fn new_epoch(instance: &mut Instance) -> Result<u64> {
     (*instance.store()).new_epoch()
 }
 
+// Hook for when a debugger may want to stop before a wasm instruction.
+unsafe fn debug_break(instance: &mut Instance, offset: u32) -> Result<()> {
+    (*instance.store()).debug_break(offset)
+}
+
 cfg_if! {
     if #[cfg(feature = "wmemcheck")] {
         // Hook for validating malloc using wmemcheck_state.
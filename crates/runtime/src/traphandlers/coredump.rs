@@ -1,6 +1,7 @@
 use wasm_encoder::CoreDumpValue;
+use wasmtime_environ::{FrameLocals, WasmType};
 
-use crate::{Backtrace, VMRuntimeLimits};
+use crate::{Backtrace, Frame, ModuleInfoLookup, VMRuntimeLimits};
 
 use super::CallThreadState;
 
@@ -10,14 +11,15 @@ pub struct CoreDumpStack {
     /// The backtrace containing the stack frames for the CoreDump
     pub bt: Backtrace,
 
-    /// Unimplemented
-    /// The indices of the locals and operand_stack all map to each other (ie.
-    /// index 0 is the locals for the first frame in the backtrace, etc)
+    /// The locals for each stack frame.
+    ///
+    /// Index 0 is the locals for the first frame in the backtrace, etc. A
+    /// frame's locals are empty if its function wasn't compiled to record
+    /// them.
+    ///
+    /// Values on the operand stack of each frame aren't recorded by generated
+    /// code, so they aren't captured.
     pub locals: Vec<Vec<CoreDumpValue>>,
-
-    /// Unimplemented
-    /// The operands for each stack frame
-    pub operand_stack: Vec<Vec<CoreDumpValue>>,
 }
 
 impl CoreDumpStack {
@@ -29,10 +31,76 @@ impl CoreDumpStack {
     ) -> Self {
         let bt = unsafe { Backtrace::new_with_trap_state(limits, cts, trap_pc_and_fp) };
 
-        Self {
-            bt,
-            locals: vec![],
-            operand_stack: vec![],
-        }
+        // Locals have to be read now, while the frames are still on the stack,
+        // rather than after unwinding.
+        let (_, lookup) = unsafe { (*cts.store).externref_activations_table() };
+        unsafe { Self::with_locals(bt, lookup, limits) }
+    }
+
+    /// Capture the wasm frames on the stack, and their locals, from a host
+    /// function or libcall called by wasm.
+    ///
+    /// # Unsafety
+    ///
+    /// `limits` must be the runtime limits of the store whose modules
+    /// `lookup` looks up, and wasm in that store must be on the stack.
+    pub unsafe fn capture(limits: *const VMRuntimeLimits, lookup: &dyn ModuleInfoLookup) -> Self {
+        Self::with_locals(Backtrace::new(limits), lookup, limits)
+    }
+
+    unsafe fn with_locals(
+        bt: Backtrace,
+        lookup: &dyn ModuleInfoLookup,
+        limits: *const VMRuntimeLimits,
+    ) -> Self {
+        let stack_limit = *(*limits).stack_limit.get();
+        let locals = bt
+            .frames()
+            .map(|frame| frame_locals(lookup, frame, stack_limit))
+            .collect();
+        Self { bt, locals }
+    }
+}
+
+/// Reads the copies of its locals that `frame`'s function keeps in its frame.
+///
+/// # Unsafety
+///
+/// `frame` must be a live wasm frame.
+unsafe fn frame_locals(
+    lookup: &dyn ModuleInfoLookup,
+    frame: &Frame,
+    stack_limit: usize,
+) -> Vec<CoreDumpValue> {
+    let Some(locals) = lookup
+        .lookup(frame.pc())
+        .and_then(|module| module.lookup_frame_locals(frame.pc()))
+    else {
+        return Vec::new();
+    };
+
+    // Wasm never uses stack below the stack limit, so if the copies would be
+    // there then the frame hasn't been set up yet, for example when a stack
+    // overflow is detected in the function's prologue.
+    let base = frame.fp().wrapping_add_signed(locals.fp_offset as isize);
+    if base < stack_limit {
+        return vec![CoreDumpValue::Missing; locals.types.len()];
     }
+
+    locals
+        .types
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| {
+            let ptr = (base + i * FrameLocals::SLOT_SIZE as usize) as *const u8;
+            match ty {
+                WasmType::I32 => CoreDumpValue::I32(ptr.cast::<i32>().read_unaligned()),
+                WasmType::I64 => CoreDumpValue::I64(ptr.cast::<i64>().read_unaligned()),
+                WasmType::F32 => CoreDumpValue::F32(ptr.cast::<f32>().read_unaligned()),
+                WasmType::F64 => CoreDumpValue::F64(ptr.cast::<f64>().read_unaligned()),
+                // The coredump format can't represent these.
+                WasmType::V128 | WasmType::Ref(_) => CoreDumpValue::Missing,
+            }
+        })
+        .collect()
 }
@@ -824,6 +824,13 @@ pub struct VMRuntimeLimits {
     /// Used to find the end of a contiguous sequence of Wasm frames when
     /// walking the stack.
     pub last_wasm_entry_sp: UnsafeCell<usize>,
+
+    /// Whether code compiled with debug breakpoints should call into the host
+    /// before each wasm instruction, because a debugger has breakpoints set
+    /// or is single-stepping.
+    ///
+    /// This field is nonzero to call into the host.
+    pub debug_break: UnsafeCell<u32>,
 }
 
 // The `VMRuntimeLimits` type is a pod-type with no destructor, and we don't
@@ -842,6 +849,7 @@ impl Default for VMRuntimeLimits {
             last_wasm_exit_fp: UnsafeCell::new(0),
             last_wasm_exit_pc: UnsafeCell::new(0),
             last_wasm_entry_sp: UnsafeCell::new(0),
+            debug_break: UnsafeCell::new(0),
         }
     }
 }
@@ -891,6 +899,10 @@ mod test_vmruntime_limits {
             offset_of!(VMRuntimeLimits, last_wasm_entry_sp),
             usize::from(offsets.ptr.vmruntime_limits_last_wasm_entry_sp())
         );
+        assert_eq!(
+            offset_of!(VMRuntimeLimits, debug_break),
+            usize::from(offsets.ptr.vmruntime_limits_debug_break())
+        );
     }
 }
 
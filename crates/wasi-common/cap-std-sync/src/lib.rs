@@ -47,14 +47,22 @@ pub use clocks::clocks_ctx;
 pub use sched::sched_ctx;
 
 use crate::net::Socket;
+use crate::sched::VirtualSched;
 use cap_rand::{Rng, RngCore, SeedableRng};
 use std::mem;
 use std::path::Path;
-use wasi_common::{file::FileAccessMode, table::Table, Error, WasiCtx, WasiFile};
+use wasi_common::{
+    clocks::{VirtualClock, WasiClocks},
+    file::FileAccessMode,
+    table::Table,
+    Error, ErrorExt, WasiCtx, WasiFile,
+};
 
 pub struct WasiCtxBuilder {
     ctx: WasiCtx,
     built: bool,
+    deterministic: bool,
+    sockets: bool,
 }
 
 impl WasiCtxBuilder {
@@ -62,6 +70,8 @@ impl WasiCtxBuilder {
         WasiCtxBuilder {
             ctx: WasiCtx::new(random_ctx(), clocks_ctx(), sched_ctx(), Table::new()),
             built: false,
+            deterministic: false,
+            sockets: false,
         }
     }
     pub fn env(
@@ -141,12 +151,39 @@ impl WasiCtxBuilder {
         fd: u32,
         socket: impl Into<Socket>,
     ) -> Result<&mut Self, Error> {
+        if self.deterministic {
+            return Err(Error::not_supported()
+                .context("sockets cannot be preopened in a deterministic context"));
+        }
+        self.sockets = true;
         let socket: Socket = socket.into();
         let file: Box<dyn WasiFile> = socket.into();
         self.ctx
             .insert_file(fd, file, FileAccessMode::READ | FileAccessMode::WRITE);
         Ok(self)
     }
+    /// Makes the context deterministic: randomness comes from a generator
+    /// seeded with `seed`, and both clocks are a [`VirtualClock`] which starts
+    /// at zero and advances one nanosecond per unit of fuel reported to
+    /// [`VirtualClock::consume_fuel`], or when the guest sleeps. Sockets
+    /// can't be used with a deterministic context, so this fails if one was
+    /// preopened and [`WasiCtxBuilder::preopened_socket`] fails afterwards.
+    ///
+    /// Files and directories are still the host's, so a guest using them
+    /// is only deterministic if they are.
+    pub fn deterministic(&mut self, seed: u64) -> Result<&mut Self, Error> {
+        if self.sockets {
+            return Err(Error::not_supported()
+                .context("a context with preopened sockets cannot be deterministic"));
+        }
+        let clock = VirtualClock::new(1);
+        self.ctx
+            .set_random(Box::new(cap_rand::rngs::StdRng::seed_from_u64(seed)));
+        self.ctx.set_clocks(WasiClocks::new_virtual(clock.clone()));
+        self.ctx.set_sched(Box::new(VirtualSched::new(clock)));
+        self.deterministic = true;
+        Ok(self)
+    }
     pub fn build(&mut self) -> WasiCtx {
         assert!(!self.built);
         let WasiCtxBuilder { ctx, .. } = mem::replace(self, Self::new());
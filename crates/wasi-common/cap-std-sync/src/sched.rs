@@ -11,6 +11,7 @@ pub use windows::poll_oneoff;
 use std::thread;
 use std::time::Duration;
 use wasi_common::{
+    clocks::VirtualClock,
     sched::{Poll, WasiSched},
     Error,
 };
@@ -38,3 +39,35 @@ impl WasiSched for SyncSched {
 pub fn sched_ctx() -> Box<dyn WasiSched> {
     Box::new(SyncSched::new())
 }
+
+/// A scheduler whose sleeps and timeouts move a [`VirtualClock`] forward
+/// instead of waiting, used by deterministic contexts.
+pub struct VirtualSched {
+    clock: VirtualClock,
+}
+impl VirtualSched {
+    pub fn new(clock: VirtualClock) -> Self {
+        Self { clock }
+    }
+}
+#[async_trait::async_trait]
+impl WasiSched for VirtualSched {
+    async fn poll_oneoff<'a>(&self, poll: &mut Poll<'a>) -> Result<(), Error> {
+        // The earliest timeout expires right away, so this only waits for
+        // files which are ready now.
+        if let Some(deadline) = poll.earliest_clock_deadline().map(|t| t.deadline) {
+            self.clock.advance_to_instant(deadline);
+            if poll.rw_subscriptions().next().is_none() {
+                return Ok(());
+            }
+        }
+        poll_oneoff(poll).await
+    }
+    async fn sched_yield(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    async fn sleep(&self, duration: Duration) -> Result<(), Error> {
+        self.clock.advance(duration);
+        Ok(())
+    }
+}
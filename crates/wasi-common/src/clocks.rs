@@ -1,5 +1,7 @@
 use crate::{Error, ErrorExt};
 use cap_std::time::{Duration, Instant, SystemTime};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 pub enum SystemTimeSpec {
     SymbolicNow,
@@ -30,9 +32,89 @@ impl WasiMonotonicOffsetClock {
     }
 }
 
+/// A virtual clock whose time is a function of the guest's progress rather
+/// than of the host's, for deterministic WASI contexts.
+///
+/// The clock starts at zero and reading it doesn't move it. Instead it moves
+/// forward by a fixed number of nanoseconds for each unit of fuel reported to
+/// [`VirtualClock::consume_fuel`], which embedders typically call from a
+/// `Store::fuel_hook` in `wasmtime`, and sleeps requested by the guest
+/// complete immediately by moving the clock forward to their deadline.
+///
+/// Clones of a `VirtualClock` share the same time.
+#[derive(Clone)]
+pub struct VirtualClock {
+    nanos: Arc<AtomicU64>,
+    nanos_per_fuel: u64,
+    base: Instant,
+}
+
+impl VirtualClock {
+    /// Creates a new clock starting at zero which advances `nanos_per_fuel`
+    /// nanoseconds per unit of fuel consumed.
+    pub fn new(nanos_per_fuel: u64) -> Self {
+        Self {
+            nanos: Arc::new(AtomicU64::new(0)),
+            nanos_per_fuel,
+            // Guests only observe monotonic time relative to when their
+            // clocks were created, so the host's time doesn't leak through.
+            base: Instant::from_std(std::time::Instant::now()),
+        }
+    }
+
+    /// Moves the clock forward for `fuel` units of fuel consumed by the guest.
+    pub fn consume_fuel(&self, fuel: u64) {
+        self.nanos
+            .fetch_add(fuel.saturating_mul(self.nanos_per_fuel), Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let nanos = duration.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.nanos.fetch_add(nanos, Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward to `nanos`, if it's not already past it.
+    pub fn advance_to(&self, nanos: u64) {
+        self.nanos.fetch_max(nanos, Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward to the monotonic `instant`, as returned by
+    /// its [`WasiMonotonicClock`] implementation, if it's not already past
+    /// it.
+    pub fn advance_to_instant(&self, instant: Instant) {
+        let nanos = instant.saturating_duration_since(self.base).as_nanos();
+        self.advance_to(nanos.try_into().unwrap_or(u64::MAX));
+    }
+
+    /// Returns the time of this clock, in nanoseconds since it was created.
+    pub fn nanos(&self) -> u64 {
+        self.nanos.load(Ordering::SeqCst)
+    }
+}
+
+impl WasiSystemClock for VirtualClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+    fn now(&self, _precision: Duration) -> SystemTime {
+        SystemTime::from_std(std::time::UNIX_EPOCH + Duration::from_nanos(self.nanos()))
+    }
+}
+
+impl WasiMonotonicClock for VirtualClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+    fn now(&self, _precision: Duration) -> Instant {
+        self.base + Duration::from_nanos(self.nanos())
+    }
+}
+
 pub struct WasiClocks {
     pub system: Option<Box<dyn WasiSystemClock>>,
     pub monotonic: Option<WasiMonotonicOffsetClock>,
+    virtual_clock: Option<VirtualClock>,
 }
 
 impl WasiClocks {
@@ -40,9 +122,24 @@ impl WasiClocks {
         Self {
             system: None,
             monotonic: None,
+            virtual_clock: None,
         }
     }
 
+    /// Creates clocks which are both backed by the virtual `clock`.
+    pub fn new_virtual(clock: VirtualClock) -> Self {
+        Self {
+            virtual_clock: Some(clock.clone()),
+            ..Self::new().with_system(clock.clone()).with_monotonic(clock)
+        }
+    }
+
+    /// Returns the virtual clock backing these clocks, if they were created
+    /// with [`WasiClocks::new_virtual`].
+    pub fn virtual_clock(&self) -> Option<&VirtualClock> {
+        self.virtual_clock.as_ref()
+    }
+
     pub fn with_system(mut self, clock: impl 'static + WasiSystemClock) -> Self {
         self.system = Some(Box::new(clock));
         self
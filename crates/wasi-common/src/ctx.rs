@@ -97,6 +97,27 @@ impl WasiCtx {
         Ok(())
     }
 
+    pub fn set_random(&mut self, random: Box<dyn RngCore + Send + Sync>) {
+        let s = Arc::get_mut(&mut self.0).expect(
+            "`set_random` should only be used during initialization before the context is cloned",
+        );
+        s.random = Mutex::new(random);
+    }
+
+    pub fn set_clocks(&mut self, clocks: WasiClocks) {
+        let s = Arc::get_mut(&mut self.0).expect(
+            "`set_clocks` should only be used during initialization before the context is cloned",
+        );
+        s.clocks = clocks;
+    }
+
+    pub fn set_sched(&mut self, sched: Box<dyn WasiSched>) {
+        let s = Arc::get_mut(&mut self.0).expect(
+            "`set_sched` should only be used during initialization before the context is cloned",
+        );
+        s.sched = sched;
+    }
+
     pub fn set_stdin(&self, f: Box<dyn WasiFile>) {
         self.insert_file(0, f, FileAccessMode::READ);
     }
@@ -0,0 +1,489 @@
+//! Implement [`wasi-threads`] for components.
+//!
+//! A component using threads imports a `thread-spawn` function from a `wasi`
+//! instance and exports a `wasi-thread-start` function with the same
+//! signatures as the core wasm versions of these functions. Each thread runs
+//! in a new instance of the component, in its own store, whose core wasm
+//! instances use the shared memories of the original instance. For this to
+//! work the shared memory must be defined by a core module of its own and
+//! imported by the modules which run code, see
+//! [`InstancePre::instantiate_with_shared_memories`].
+//!
+//! The preview2 `WasiCtx` and resource table of the original instance are
+//! shared with the threads through [`SharedWasi`], so that all threads use
+//! the same host state and its limits. Host calls of different threads which
+//! use it don't run concurrently. Handles to resources are still local to the
+//! instance of each thread, as for any component instance.
+//!
+//! [`wasi-threads`]: https://github.com/WebAssembly/wasi-threads
+
+use anyhow::{anyhow, Error, Result};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+use wasmtime::component::{Instance, InstancePre, Linker, ResourceTable, SharedMemories};
+use wasmtime::{AsContextMut, CallHook, Engine, Store, StoreContextMut};
+use wasmtime_wasi::maybe_exit_on_error;
+use wasmtime_wasi::preview2::{WasiCtx, WasiView};
+
+// The component export which is the entry point of new threads, named after
+// the `wasi_thread_start` export of core modules.
+const WASI_ENTRY_POINT: &str = "wasi-thread-start";
+
+/// The state of wasi-threads for the instances of one component.
+///
+/// This is cheaply cloneable and clones refer to the same state, so that the
+/// store of every thread can hold the same context.
+pub struct WasiThreadsCtx<T>(Arc<Inner<T>>);
+
+struct Inner<T> {
+    instance_pre: InstancePre<T>,
+    parent: OnceLock<Parent>,
+    new_store: Box<NewStore<T>>,
+    async_support: bool,
+    max_threads: Option<usize>,
+    running: AtomicUsize,
+    on_error: OnError,
+    error: Mutex<Option<Error>>,
+}
+
+/// What happens when a thread traps or panics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnError {
+    /// Exit the whole process, as the wasi-threads specification requires
+    /// and as is done for core modules.
+    ExitProcess,
+    /// Record the error and fail the original instance: later spawns trap
+    /// with the error and [`WasiThreadsCtx::take_error`] returns it, for
+    /// embedders running many instances in one process.
+    FailInstance,
+}
+
+type NewStore<T> = dyn Fn(&WasiThreadsCtx<T>, &Engine) -> Result<Store<T>> + Send + Sync;
+
+/// What threads need to know about the original instance of the component.
+struct Parent {
+    engine: Engine,
+    shared_memories: SharedMemories,
+    has_entry_point: bool,
+}
+
+impl<T> Clone for WasiThreadsCtx<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Send + 'static> WasiThreadsCtx<T> {
+    /// Creates a context for spawning threads in new instances of
+    /// `instance_pre`.
+    ///
+    /// Each new thread runs in the store returned by `new_store`, which is
+    /// given this context and the engine of the original instance. The
+    /// store should use the preview2 state of the original store through
+    /// [`SharedWasi::share`], and be configured as the original store was,
+    /// e.g. with limits or an epoch deadline.
+    pub fn new(
+        instance_pre: InstancePre<T>,
+        new_store: impl Fn(&WasiThreadsCtx<T>, &Engine) -> Result<Store<T>> + Send + Sync + 'static,
+    ) -> Self {
+        Self::new_(instance_pre, Box::new(new_store), false)
+    }
+
+    /// Same as [`WasiThreadsCtx::new`] except for components which are
+    /// instantiated in stores with async support enabled.
+    ///
+    /// Each thread drives its own instance to completion on the Tokio
+    /// runtime used by `wasmtime-wasi`.
+    pub fn new_async(
+        instance_pre: InstancePre<T>,
+        new_store: impl Fn(&WasiThreadsCtx<T>, &Engine) -> Result<Store<T>> + Send + Sync + 'static,
+    ) -> Self {
+        Self::new_(instance_pre, Box::new(new_store), true)
+    }
+
+    fn new_(
+        instance_pre: InstancePre<T>,
+        new_store: Box<NewStore<T>>,
+        async_support: bool,
+    ) -> Self {
+        Self(Arc::new(Inner {
+            instance_pre,
+            parent: OnceLock::new(),
+            new_store,
+            async_support,
+            max_threads: None,
+            running: AtomicUsize::new(0),
+            on_error: OnError::ExitProcess,
+            error: Mutex::new(None),
+        }))
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner<T> {
+        Arc::get_mut(&mut self.0).expect("context must be configured before it's cloned")
+    }
+
+    /// Limits the number of threads running at the same time.
+    ///
+    /// Spawning a thread beyond the limit fails, which the guest sees as a
+    /// negative result of `thread-spawn`. By default there's no limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this context was already cloned.
+    pub fn max_threads(mut self, max: usize) -> Self {
+        self.inner_mut().max_threads = Some(max);
+        self
+    }
+
+    /// Configures what happens when a thread traps or panics, which by
+    /// default is [`OnError::ExitProcess`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if this context was already cloned.
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.inner_mut().on_error = on_error;
+        self
+    }
+
+    /// Returns the error of the first thread which trapped or panicked, with
+    /// [`OnError::FailInstance`].
+    pub fn take_error(&self) -> Option<Error> {
+        self.0.error.lock().unwrap().take()
+    }
+
+    /// Fails if a thread trapped or panicked, with [`OnError::FailInstance`].
+    fn check_error(&self) -> Result<()> {
+        match &*self.0.error.lock().unwrap() {
+            Some(error) => Err(anyhow!("a thread of this instance failed: {error:#}")),
+            None => Ok(()),
+        }
+    }
+
+    /// Handles the failure of a thread according to the configured
+    /// [`OnError`].
+    fn fail(&self, error: Error) {
+        match self.0.on_error {
+            OnError::ExitProcess => {
+                let error = maybe_exit_on_error(error);
+                eprintln!("Error: {:?}", error);
+                std::process::exit(1);
+            }
+            OnError::FailInstance => {
+                log::error!("{:?}", error);
+                self.0.error.lock().unwrap().get_or_insert(error);
+            }
+        }
+    }
+
+    /// Records the original instance of the component, whose shared
+    /// memories all threads use.
+    ///
+    /// This must be called after instantiating the component and before it
+    /// spawns any threads.
+    pub fn set_instance(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+        instance: &Instance,
+    ) -> Result<()> {
+        let shared_memories = instance.shared_memories(&mut store);
+        if shared_memories.is_empty() {
+            return Err(anyhow!(
+                "component does not have a shared memory; a `wasi-threads` component \
+                 must use a shared memory"
+            ));
+        }
+        // As with core modules the entry point is only checked when a thread
+        // is spawned, so that components which never spawn threads don't
+        // need it.
+        let has_entry_point = instance
+            .get_typed_func::<(i32, i32), ()>(&mut store, WASI_ENTRY_POINT)
+            .is_ok();
+        let parent = Parent {
+            engine: store.as_context_mut().engine().clone(),
+            shared_memories,
+            has_entry_point,
+        };
+        self.0
+            .parent
+            .set(parent)
+            .map_err(|_| anyhow!("the instance of this component was already set"))
+    }
+
+    /// Spawns a thread running `wasi-thread-start` with `thread_start_arg`
+    /// in a new instance of the component.
+    ///
+    /// Returns the ID of the new thread, or a negative value if the component
+    /// has no entry point.
+    pub fn spawn(&self, thread_start_arg: i32) -> Result<i32> {
+        let parent = self
+            .0
+            .parent
+            .get()
+            .ok_or_else(|| anyhow!("the instance of this component was not set"))?;
+
+        // As defined in the wasi-threads specification, returning a negative
+        // result here indicates to the guest that the spawn failed.
+        if !parent.has_entry_point {
+            log::error!("failed to find a wasi-threads entry point function; expected an export with name `{WASI_ENTRY_POINT}` and type `(i32, i32) -> ()`");
+            return Ok(-1);
+        }
+
+        let running = ThreadCount::new(self)?;
+        let store = (self.0.new_store)(self, &parent.engine)?;
+        let ctx = self.clone();
+
+        // Start a Rust thread running a new instance of the current component.
+        let wasi_thread_id = super::random_thread_id();
+        let builder = thread::Builder::new().name(format!("wasi-thread-{}", wasi_thread_id));
+        builder.spawn(move || {
+            let _running = running;
+            // Catch any panic failures in host code; e.g., if a WASI module
+            // were to crash, we want all threads to exit, not just this one.
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                log::trace!(
+                    "spawned thread id = {}; calling start function `{}` with: {}",
+                    wasi_thread_id,
+                    WASI_ENTRY_POINT,
+                    thread_start_arg
+                );
+                let result = if ctx.0.async_support {
+                    wasmtime_wasi::preview2::in_tokio(
+                        ctx.run_async(store, (wasi_thread_id, thread_start_arg)),
+                    )
+                } else {
+                    ctx.run(store, (wasi_thread_id, thread_start_arg))
+                };
+
+                // Any traps or calls to `proc_exit`, by specification, should
+                // end execution for all threads, as with core modules.
+                match result {
+                    Ok(()) => log::trace!("exiting thread id = {} normally", wasi_thread_id),
+                    Err(e) => {
+                        log::trace!("exiting thread id = {} due to error", wasi_thread_id);
+                        ctx.fail(e);
+                    }
+                }
+            }));
+
+            if let Err(e) = result {
+                ctx.fail(anyhow!("wasi-thread-{} panicked: {:?}", wasi_thread_id, e));
+            }
+        })?;
+
+        Ok(wasi_thread_id)
+    }
+
+    fn parent(&self) -> &Parent {
+        self.0.parent.get().unwrap()
+    }
+
+    fn run(&self, mut store: Store<T>, args: (i32, i32)) -> Result<()> {
+        // Each new instance is created in its own store.
+        let parent = self.parent();
+        let instance = self
+            .0
+            .instance_pre
+            .instantiate_with_shared_memories(&mut store, &parent.shared_memories)?;
+        let func = instance.get_typed_func::<(i32, i32), ()>(&mut store, WASI_ENTRY_POINT)?;
+        func.call(&mut store, args)?;
+        func.post_return(&mut store)
+    }
+
+    async fn run_async(&self, mut store: Store<T>, args: (i32, i32)) -> Result<()> {
+        let parent = self.parent();
+        let instance = self
+            .0
+            .instance_pre
+            .instantiate_with_shared_memories_async(&mut store, &parent.shared_memories)
+            .await?;
+        let func = instance.get_typed_func::<(i32, i32), ()>(&mut store, WASI_ENTRY_POINT)?;
+        func.call_async(&mut store, args).await?;
+        func.post_return_async(&mut store).await
+    }
+}
+
+/// The preview2 `WasiCtx` and resource table of an instance, shared with the
+/// stores of its threads.
+///
+/// [`WasiView`] hands out plain references to this state, so it can't be
+/// locked for just the duration of each access. Instead a store checks the
+/// state out while it runs host code and checks it back in while it runs
+/// wasm, which [`SharedWasi::call_hook`] does when it's installed with
+/// [`Store::call_hook`] on every store using the state. Host calls of
+/// different threads therefore don't run concurrently: a thread blocking in
+/// a host call, e.g. in `poll`, holds up the host calls of the other threads
+/// until it returns.
+pub struct SharedWasi {
+    shared: Arc<Shared>,
+    state: Option<WasiState>,
+}
+
+struct Shared {
+    state: Mutex<Option<WasiState>>,
+    checked_in: Condvar,
+}
+
+struct WasiState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl SharedWasi {
+    /// Creates the shared state for the store of the original instance.
+    ///
+    /// The store starts out holding the state, so that the embedder can use
+    /// it before calling into wasm.
+    pub fn new(ctx: WasiCtx, table: ResourceTable) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(None),
+                checked_in: Condvar::new(),
+            }),
+            state: Some(WasiState { ctx, table }),
+        }
+    }
+
+    /// Returns a view of the same state for the store of a new thread.
+    ///
+    /// The new view doesn't hold the state until the thread calls the host.
+    pub fn share(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            state: None,
+        }
+    }
+
+    /// Checks the state out on calls to the host and in on calls to wasm;
+    /// this must be called by the call hook of the store using this view.
+    pub fn call_hook(&mut self, hook: CallHook) -> Result<()> {
+        match hook {
+            CallHook::CallingHost | CallHook::ReturningFromWasm => self.check_out(),
+            CallHook::CallingWasm | CallHook::ReturningFromHost => self.check_in(),
+        }
+        Ok(())
+    }
+
+    fn check_out(&mut self) {
+        if self.state.is_some() {
+            return;
+        }
+        let mut shared = self.shared.state.lock().unwrap();
+        loop {
+            if let Some(state) = shared.take() {
+                self.state = Some(state);
+                return;
+            }
+            shared = self.shared.checked_in.wait(shared).unwrap();
+        }
+    }
+
+    fn check_in(&mut self) {
+        if let Some(state) = self.state.take() {
+            *self.shared.state.lock().unwrap() = Some(state);
+            self.shared.checked_in.notify_one();
+        }
+    }
+
+    fn held(&self) -> &WasiState {
+        self.state
+            .as_ref()
+            .expect("the shared WASI state is only available to host calls")
+    }
+
+    fn held_mut(&mut self) -> &mut WasiState {
+        self.state
+            .as_mut()
+            .expect("the shared WASI state is only available to host calls")
+    }
+}
+
+/// Same as [`SharedWasi::share`].
+impl Clone for SharedWasi {
+    fn clone(&self) -> Self {
+        self.share()
+    }
+}
+
+impl Drop for SharedWasi {
+    fn drop(&mut self) {
+        self.check_in();
+    }
+}
+
+/// # Panics
+///
+/// The methods of this view panic when the state isn't checked out, i.e.
+/// outside of host calls in the store of a thread.
+impl WasiView for SharedWasi {
+    fn table(&self) -> &ResourceTable {
+        &self.held().table
+    }
+
+    fn table_mut(&mut self) -> &mut ResourceTable {
+        &mut self.held_mut().table
+    }
+
+    fn ctx(&self) -> &WasiCtx {
+        &self.held().ctx
+    }
+
+    fn ctx_mut(&mut self) -> &mut WasiCtx {
+        &mut self.held_mut().ctx
+    }
+}
+
+/// Counts a running thread of a context for as long as it's alive.
+struct ThreadCount<T>(WasiThreadsCtx<T>);
+
+impl<T> ThreadCount<T> {
+    fn new(ctx: &WasiThreadsCtx<T>) -> Result<Self> {
+        let running = ctx.0.running.fetch_add(1, Ordering::SeqCst);
+        let count = ThreadCount(ctx.clone());
+        if let Some(max) = ctx.0.max_threads {
+            if running >= max {
+                return Err(anyhow!("the maximum of {max} running threads was reached"));
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl<T> Drop for ThreadCount<T> {
+    fn drop(&mut self) {
+        self.0 .0.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Add the `wasi` `thread-spawn` function to a component linker.
+///
+/// Unlike [`add_to_linker`](crate::add_to_linker) this doesn't define the
+/// shared memory, which components define internally; the context returned
+/// by `get_cx` must be given the memories of the instance with
+/// [`WasiThreadsCtx::set_instance`] instead.
+pub fn add_to_linker<T: Send + 'static>(
+    linker: &mut Linker<T>,
+    get_cx: impl Fn(&mut T) -> &WasiThreadsCtx<T> + Send + Sync + Copy + 'static,
+) -> Result<()> {
+    linker.instance("wasi")?.func_wrap(
+        "thread-spawn",
+        move |mut store: StoreContextMut<'_, T>, (start_arg,): (i32,)| -> Result<(i32,)> {
+            log::trace!("new thread requested via `wasi::thread-spawn` call");
+            let ctx = get_cx(store.data_mut());
+            // Once a thread has failed, so does the instance spawning it.
+            ctx.check_error()?;
+            match ctx.spawn(start_arg) {
+                Ok(thread_id) => {
+                    assert!(thread_id >= 0, "thread_id = {}", thread_id);
+                    Ok((thread_id,))
+                }
+                Err(e) => {
+                    log::error!("failed to spawn thread: {}", e);
+                    Ok((-1,))
+                }
+            }
+        },
+    )
+}
@@ -10,6 +10,9 @@ use std::thread;
 use wasmtime::{Caller, ExternType, InstancePre, Linker, Module, SharedMemory, Store, ValType};
 use wasmtime_wasi::maybe_exit_on_error;
 
+#[cfg(feature = "component-model")]
+pub mod component;
+
 // This name is a function export designated by the wasi-threads specification:
 // https://github.com/WebAssembly/wasi-threads/#detailed-design-discussion
 const WASI_ENTRY_POINT: &str = "wasi_thread_start";
@@ -10,3 +10,51 @@ pub trait HostMonotonicClock: Send + Sync {
     fn resolution(&self) -> u64;
     fn now(&self) -> u64;
 }
+
+/// The clock used by [`WasiCtxBuilder::deterministic`], which advances with
+/// the fuel consumed by the guest rather than with the host's time.
+///
+/// [`WasiCtxBuilder::deterministic`]: crate::preview2::WasiCtxBuilder::deterministic
+pub use wasi_common::clocks::VirtualClock;
+
+impl HostWallClock for VirtualClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos())
+    }
+}
+
+impl HostMonotonicClock for VirtualClock {
+    fn resolution(&self) -> u64 {
+        1
+    }
+
+    fn now(&self) -> u64 {
+        self.nanos()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn virtual_clock() {
+        let clock = VirtualClock::new(10);
+        assert_eq!(HostMonotonicClock::now(&clock), 0);
+        assert_eq!(HostMonotonicClock::now(&clock), 0);
+
+        let other = clock.clone();
+        other.consume_fuel(3);
+        assert_eq!(HostMonotonicClock::now(&clock), 30);
+        other.advance(Duration::from_nanos(100));
+        assert_eq!(HostWallClock::now(&clock), Duration::from_nanos(130));
+        other.advance_to(50);
+        assert_eq!(HostMonotonicClock::now(&clock), 130);
+        other.advance_to(1000);
+        assert_eq!(HostMonotonicClock::now(&clock), 1000);
+    }
+}
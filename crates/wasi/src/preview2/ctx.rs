@@ -1,7 +1,7 @@
 use crate::preview2::{
     clocks::{
         host::{monotonic_clock, wall_clock},
-        HostMonotonicClock, HostWallClock,
+        HostMonotonicClock, HostWallClock, VirtualClock,
     },
     filesystem::Dir,
     network::{SocketAddrCheck, SocketAddrUse},
@@ -28,6 +28,7 @@ pub struct WasiCtxBuilder {
     wall_clock: Box<dyn HostWallClock + Send + Sync>,
     monotonic_clock: Box<dyn HostMonotonicClock + Send + Sync>,
     allowed_network_uses: AllowedNetworkUses,
+    deterministic_seed: Option<u64>,
     built: bool,
 }
 
@@ -78,6 +79,7 @@ impl WasiCtxBuilder {
             wall_clock: wall_clock(),
             monotonic_clock: monotonic_clock(),
             allowed_network_uses: AllowedNetworkUses::default(),
+            deterministic_seed: None,
             built: false,
         }
     }
@@ -222,6 +224,40 @@ impl WasiCtxBuilder {
         self
     }
 
+    /// Configures this context so that everything the guest can observe
+    /// through it is a deterministic function of `seed` and the guest's own
+    /// behavior.
+    ///
+    /// This is intended to be used in conjunction with
+    /// [`Config::deterministic`](wasmtime::Config::deterministic) to record
+    /// and replay executions. When enabled the final `WasiCtx` has:
+    ///
+    /// * secure and insecure random number generators, and the insecure
+    ///   random seed, all derived from `seed`;
+    /// * wall and monotonic clocks backed by a shared [`VirtualClock`] which
+    ///   starts at zero, advances one nanosecond per unit of fuel consumed by
+    ///   the guest, and jumps forward to the deadline of any sleep instead of
+    ///   waiting. Reading the clocks doesn't advance them. Fuel is only
+    ///   reported to the clock once [`add_fuel_clock_to_store`] has been
+    ///   called on the store, and otherwise time only moves when the guest
+    ///   sleeps;
+    /// * no networking: getting the instance network or creating a socket
+    ///   traps instead of returning an error to the guest.
+    ///
+    /// These settings take precedence over any random number generator, clock
+    /// or network configuration done on this builder, regardless of the order
+    /// the methods are called in.
+    ///
+    /// [`add_fuel_clock_to_store`]: crate::preview2::add_fuel_clock_to_store
+    ///
+    /// Note that stdio and preopened directories are passed through as
+    /// configured, so embeddings must ensure they are deterministic
+    /// themselves, for example by using in-memory pipes.
+    pub fn deterministic(&mut self, seed: u64) -> &mut Self {
+        self.deterministic_seed = Some(seed);
+        self
+    }
+
     /// Uses the configured context so far to construct the final `WasiCtx`.
     ///
     /// Note that each `WasiCtxBuilder` can only be used to "build" once, and
@@ -240,17 +276,38 @@ impl WasiCtxBuilder {
             env,
             args,
             preopens,
-            socket_addr_check,
-            random,
-            insecure_random,
-            insecure_random_seed,
-            wall_clock,
-            monotonic_clock,
-            allowed_network_uses,
+            mut socket_addr_check,
+            mut random,
+            mut insecure_random,
+            mut insecure_random_seed,
+            mut wall_clock,
+            mut monotonic_clock,
+            mut allowed_network_uses,
+            deterministic_seed,
             built: _,
         } = mem::replace(self, Self::new());
         self.built = true;
 
+        let mut virtual_clock = None;
+        if let Some(seed) = deterministic_seed {
+            let mut rng = cap_rand::rngs::StdRng::seed_from_u64(seed);
+            insecure_random = Box::new(cap_rand::rngs::SmallRng::seed_from_u64(rng.gen()));
+            insecure_random_seed = rng.gen();
+            random = Box::new(rng);
+
+            let clock = VirtualClock::new(1);
+            wall_clock = Box::new(clock.clone());
+            monotonic_clock = Box::new(clock.clone());
+            virtual_clock = Some(clock);
+
+            socket_addr_check = SocketAddrCheck::default();
+            allowed_network_uses = AllowedNetworkUses {
+                ip_name_lookup: false,
+                udp: false,
+                tcp: false,
+            };
+        }
+
         WasiCtx {
             stdin,
             stdout,
@@ -264,6 +321,7 @@ impl WasiCtxBuilder {
             insecure_random_seed,
             wall_clock,
             monotonic_clock,
+            virtual_clock,
             allowed_network_uses,
         }
     }
@@ -282,6 +340,7 @@ pub struct WasiCtx {
     pub(crate) insecure_random_seed: u128,
     pub(crate) wall_clock: Box<dyn HostWallClock + Send + Sync>,
     pub(crate) monotonic_clock: Box<dyn HostMonotonicClock + Send + Sync>,
+    pub(crate) virtual_clock: Option<VirtualClock>,
     pub(crate) env: Vec<(String, String)>,
     pub(crate) args: Vec<String>,
     pub(crate) preopens: Vec<(Dir, String)>,
@@ -292,6 +351,39 @@ pub struct WasiCtx {
     pub(crate) allowed_network_uses: AllowedNetworkUses,
 }
 
+impl WasiCtx {
+    /// Returns the virtual clock used by this context if it was built with
+    /// [`WasiCtxBuilder::deterministic`].
+    ///
+    /// Embedders may use this to advance time in step with guest progress.
+    pub fn virtual_clock(&self) -> Option<&VirtualClock> {
+        self.virtual_clock.as_ref()
+    }
+
+    /// Fails if this context doesn't allow the guest to use the network at
+    /// all, which is the case for deterministic contexts.
+    pub(crate) fn check_network_allowed(&self) -> anyhow::Result<()> {
+        if self.virtual_clock.is_some() {
+            anyhow::bail!("networking is not available in a deterministic WASI context");
+        }
+        Ok(())
+    }
+}
+
+/// Advances the virtual clock of deterministic contexts in `store` with the
+/// fuel consumed by the guest, one nanosecond per unit of fuel.
+///
+/// This installs a [`Store::fuel_hook`](wasmtime::Store::fuel_hook), replacing
+/// any previously configured one, and has no effect unless
+/// [`Config::consume_fuel`](wasmtime::Config::consume_fuel) is enabled.
+pub fn add_fuel_clock_to_store<T: WasiView + 'static>(store: &mut wasmtime::Store<T>) {
+    store.fuel_hook(|data, fuel| {
+        if let Some(clock) = data.ctx().virtual_clock() {
+            clock.consume_fuel(fuel);
+        }
+    });
+}
+
 pub struct AllowedNetworkUses {
     pub ip_name_lookup: bool,
     pub udp: bool,
@@ -71,6 +71,10 @@ impl<T: WasiView> monotonic_clock::Host for T {
     }
 
     fn subscribe_instant(&mut self, when: Instant) -> anyhow::Result<Resource<Pollable>> {
+        if let Some(clock) = &self.ctx().virtual_clock {
+            clock.advance_to(when);
+            return subscribe_to_duration(&mut self.table_mut(), Duration::from_nanos(0));
+        }
         let clock_now = self.ctx().monotonic_clock.now();
         let duration = if when > clock_now {
             Duration::from_nanos(when - clock_now)
@@ -81,6 +85,10 @@ impl<T: WasiView> monotonic_clock::Host for T {
     }
 
     fn subscribe_duration(&mut self, duration: WasiDuration) -> anyhow::Result<Resource<Pollable>> {
+        if let Some(clock) = &self.ctx().virtual_clock {
+            clock.advance(Duration::from_nanos(duration));
+            return subscribe_to_duration(&mut self.table_mut(), Duration::from_nanos(0));
+        }
         subscribe_to_duration(&mut self.table_mut(), Duration::from_nanos(duration))
     }
 }
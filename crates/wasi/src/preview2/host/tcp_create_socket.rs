@@ -1,6 +1,6 @@
 use crate::preview2::bindings::{sockets::network::IpAddressFamily, sockets::tcp_create_socket};
 use crate::preview2::tcp::TcpSocket;
-use crate::preview2::{SocketResult, WasiView};
+use crate::preview2::{SocketError, SocketResult, WasiView};
 use wasmtime::component::Resource;
 
 impl<T: WasiView> tcp_create_socket::Host for T {
@@ -8,6 +8,9 @@ impl<T: WasiView> tcp_create_socket::Host for T {
         &mut self,
         address_family: IpAddressFamily,
     ) -> SocketResult<Resource<TcpSocket>> {
+        self.ctx()
+            .check_network_allowed()
+            .map_err(SocketError::trap)?;
         let socket = TcpSocket::new(address_family.into())?;
         let socket = self.table_mut().push(socket)?;
         Ok(socket)
@@ -1,6 +1,6 @@
 use crate::preview2::bindings::{sockets::network::IpAddressFamily, sockets::udp_create_socket};
 use crate::preview2::udp::UdpSocket;
-use crate::preview2::{SocketResult, WasiView};
+use crate::preview2::{SocketError, SocketResult, WasiView};
 use wasmtime::component::Resource;
 
 impl<T: WasiView> udp_create_socket::Host for T {
@@ -8,6 +8,9 @@ impl<T: WasiView> udp_create_socket::Host for T {
         &mut self,
         address_family: IpAddressFamily,
     ) -> SocketResult<Resource<UdpSocket>> {
+        self.ctx()
+            .check_network_allowed()
+            .map_err(SocketError::trap)?;
         let socket = UdpSocket::new(address_family.into())?;
         let socket = self.table_mut().push(socket)?;
         Ok(socket)
@@ -40,8 +40,8 @@ mod tcp;
 mod udp;
 mod write_stream;
 
-pub use self::clocks::{HostMonotonicClock, HostWallClock};
-pub use self::ctx::{WasiCtx, WasiCtxBuilder, WasiView};
+pub use self::clocks::{HostMonotonicClock, HostWallClock, VirtualClock};
+pub use self::ctx::{add_fuel_clock_to_store, WasiCtx, WasiCtxBuilder, WasiView};
 pub use self::error::{I32Exit, TrappableError};
 pub use self::filesystem::{DirPerms, FilePerms, FsError, FsResult};
 pub use self::network::{Network, SocketError, SocketResult};
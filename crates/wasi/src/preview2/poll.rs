@@ -107,6 +107,10 @@ impl<T: WasiView> poll::Host for T {
                     }
                 }
                 if any_ready {
+                    // Report ready pollables in the order they were passed in
+                    // rather than the order of the table's entries so the
+                    // result doesn't depend on resource allocation.
+                    results.sort_unstable();
                     Poll::Ready(results)
                 } else {
                     Poll::Pending
@@ -1,4 +1,7 @@
 use crate::code::CodeObject;
+use crate::component::matching::InstanceType;
+use crate::component::types::{self, ComponentItem};
+use crate::component::ResourceType;
 use crate::signatures::SignatureCollection;
 use crate::{Engine, Module, ResourcesRequired};
 use anyhow::{bail, Context, Result};
@@ -9,8 +12,8 @@ use std::path::Path;
 use std::ptr::NonNull;
 use std::sync::Arc;
 use wasmtime_environ::component::{
-    AllCallFunc, ComponentTypes, GlobalInitializer, InstantiateModule, StaticModuleIndex,
-    TrampolineIndex, Translator, VMComponentOffsets,
+    AllCallFunc, ComponentTypes, Export, GlobalInitializer, InstantiateModule, ResourceIndex,
+    StaticModuleIndex, TrampolineIndex, Translator, TypeDef, VMComponentOffsets,
 };
 use wasmtime_environ::{FunctionLoc, HostPtr, ObjectKind, PrimaryMap, ScopeVec};
 use wasmtime_jit::{CodeMemory, CompiledModuleInfo};
@@ -326,10 +329,22 @@ impl Component {
         &self.inner.info.component
     }
 
+    /// Returns whether `a` and `b` refer to the same compiled component.
+    pub(crate) fn same(a: &Component, b: &Component) -> bool {
+        Arc::ptr_eq(&a.inner, &b.inner)
+    }
+
     pub(crate) fn static_module(&self, idx: StaticModuleIndex) -> &Module {
         &self.inner.static_modules[idx]
     }
 
+    /// Returns every core wasm module within this component, including the
+    /// adapter modules generated to fuse calls between component instances.
+    #[cfg(feature = "profiling")]
+    pub(crate) fn static_modules(&self) -> impl ExactSizeIterator<Item = &Module> {
+        self.inner.static_modules.values()
+    }
+
     #[inline]
     pub(crate) fn types(&self) -> &Arc<ComponentTypes> {
         self.inner.component_types()
@@ -496,6 +511,99 @@ impl Component {
         }
         Some(resources)
     }
+
+    /// Returns the type of this component: the names and types of its
+    /// imports and exports.
+    ///
+    /// Resources imported into or defined by this component are represented
+    /// by [`ResourceType`]s which are unique to this component, since the
+    /// actual types of resources are only known once it's instantiated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> wasmtime::Result<()> {
+    /// use wasmtime::{Config, Engine, component::{Component, types::ComponentItem}};
+    ///
+    /// let mut config = Config::new();
+    /// config.wasm_component_model(true);
+    /// let engine = Engine::new(&config)?;
+    ///
+    /// let component = Component::new(&engine, &r#"
+    ///     (component
+    ///         (import "log" (func (param "msg" string)))
+    ///     )
+    /// "#)?;
+    ///
+    /// let ty = component.component_type();
+    /// let (name, item) = ty.imports().next().unwrap();
+    /// assert_eq!(name, "log");
+    /// match item {
+    ///     ComponentItem::ComponentFunc(func) => {
+    ///         let (param, _ty) = func.params().next().unwrap();
+    ///         assert_eq!(param, "msg");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn component_type(&self) -> types::Component {
+        let info = self.env_component();
+        let resources = Arc::new(
+            (0..info.num_resources)
+                .map(|i| ResourceType::uninstantiated(self.types(), ResourceIndex::from_u32(i)))
+                .collect::<PrimaryMap<ResourceIndex, _>>(),
+        );
+        let ty = InstanceType {
+            types: self.types(),
+            resources: &resources,
+        };
+        let imports = info
+            .import_types
+            .values()
+            .map(|(name, def)| (name.clone(), ComponentItem::from(def, &ty)))
+            .collect();
+        let exports = info
+            .exports
+            .iter()
+            .map(|(name, export)| (name.clone(), self.export_item(export, &ty)))
+            .collect();
+        types::Component::new(imports, exports)
+    }
+
+    fn export_item(&self, export: &Export, ty: &InstanceType<'_>) -> ComponentItem {
+        match export {
+            Export::LiftedFunction { ty: func, .. } => {
+                ComponentItem::from(&TypeDef::ComponentFunc(*func), ty)
+            }
+            Export::ModuleStatic(index) => {
+                ComponentItem::Module(types::Module::from_module(self.static_module(*index)))
+            }
+            Export::ModuleImport(index) => {
+                // Find the type of the module within the type of the import it
+                // comes from.
+                let info = self.env_component();
+                let (import, path) = &info.imports[*index];
+                let mut def = &info.import_types[*import].1;
+                for name in path {
+                    def = match def {
+                        TypeDef::ComponentInstance(i) => &self.types()[*i].exports[name.as_str()],
+                        _ => unreachable!(),
+                    };
+                }
+                ComponentItem::from(def, ty)
+            }
+            Export::Instance(exports) => {
+                ComponentItem::ComponentInstance(types::ComponentInstance::new(
+                    exports
+                        .iter()
+                        .map(|(name, export)| (name.clone(), self.export_item(export, ty)))
+                        .collect(),
+                ))
+            }
+            Export::Type(def) => ComponentItem::from(def, ty),
+        }
+    }
 }
 
 impl ComponentRuntimeInfo for ComponentInner {
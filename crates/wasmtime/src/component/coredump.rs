@@ -0,0 +1,173 @@
+use crate::component::instance::InstanceData;
+use crate::store::StoreOpaque;
+use wasmtime_environ::component::RuntimeInstanceOrigin;
+
+/// The state of a component [`Instance`](crate::component::Instance) that
+/// was recorded in a [`WasmCoreDump`](crate::WasmCoreDump).
+///
+/// Components are flattened when they're compiled, so a component instance
+/// is recorded as the tree of component instances it created along with the
+/// core wasm instances each of them created.
+#[derive(Debug)]
+pub struct ComponentCoreDump {
+    component_instances: Vec<Option<usize>>,
+    core_instances: Vec<ComponentCoreInstance>,
+    resource_tables: Vec<ComponentResourceTable>,
+}
+
+impl ComponentCoreDump {
+    pub(crate) fn new(
+        store: &StoreOpaque,
+        data: &InstanceData,
+        core_instances: &[crate::Instance],
+    ) -> ComponentCoreDump {
+        let component = data.component().env_component();
+        let component_instances = component
+            .component_instance_parents
+            .values()
+            .map(|parent| parent.map(|p| p.as_u32() as usize))
+            .collect();
+
+        let core_instances = data
+            .core_instances()
+            .filter_map(|(index, instance)| {
+                let id = instance.id(store);
+                let instance = core_instances.iter().position(|i| i.id(store) == id)?;
+                let component_instance = match component.runtime_instance_origins[index] {
+                    RuntimeInstanceOrigin::Module(i) => Some(i.as_u32() as usize),
+                    RuntimeInstanceOrigin::Adapter => None,
+                };
+                Some(ComponentCoreInstance {
+                    instance,
+                    component_instance,
+                })
+            })
+            .collect();
+
+        let types = data.component_types();
+        let resource_tables = data
+            .instance()
+            .component_resource_tables_ref()
+            .iter()
+            .map(|(index, table)| ComponentResourceTable {
+                component_instance: types[index].instance.as_u32() as usize,
+                resource: types[index].ty.as_u32(),
+                handles: table
+                    .handles()
+                    .map(|(index, rep, own)| ComponentResourceHandle { index, rep, own })
+                    .collect(),
+            })
+            .collect();
+
+        ComponentCoreDump {
+            component_instances,
+            core_instances,
+            resource_tables,
+        }
+    }
+
+    /// The tree of component instances, as the index of each component
+    /// instance's parent.
+    ///
+    /// The first entry is the root component instance, which has no parent,
+    /// and every other component instance was instantiated by the component
+    /// instance at the index of its parent.
+    pub fn component_instances(&self) -> &[Option<usize>] {
+        &self.component_instances
+    }
+
+    /// The core wasm instances created by this component instance, in the
+    /// order they were instantiated.
+    pub fn core_instances(&self) -> &[ComponentCoreInstance] {
+        &self.core_instances
+    }
+
+    /// The resource tables of each of the component instances, with the
+    /// handles that were live when the core dump was created.
+    pub fn resource_tables(&self) -> &[ComponentResourceTable] {
+        &self.resource_tables
+    }
+}
+
+/// A core wasm instance created by a component instance, within a
+/// [`ComponentCoreDump`].
+#[derive(Debug, Clone)]
+pub struct ComponentCoreInstance {
+    instance: usize,
+    component_instance: Option<usize>,
+}
+
+impl ComponentCoreInstance {
+    /// The index of this instance within
+    /// [`WasmCoreDump::instances`](crate::WasmCoreDump::instances).
+    pub fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// The index, within [`ComponentCoreDump::component_instances`], of the
+    /// component instance that instantiated this instance.
+    ///
+    /// This is `None` for adapter modules, which Wasmtime generates to fuse
+    /// calls between component instances.
+    pub fn component_instance(&self) -> Option<usize> {
+        self.component_instance
+    }
+
+    /// Returns whether this is an instance of an adapter module generated by
+    /// Wasmtime rather than a module of the component.
+    pub fn is_adapter(&self) -> bool {
+        self.component_instance.is_none()
+    }
+}
+
+/// A resource table of a component instance within a [`ComponentCoreDump`].
+#[derive(Debug, Clone)]
+pub struct ComponentResourceTable {
+    component_instance: usize,
+    resource: u32,
+    handles: Vec<ComponentResourceHandle>,
+}
+
+impl ComponentResourceTable {
+    /// The index, within [`ComponentCoreDump::component_instances`], of the
+    /// component instance that owns this table.
+    pub fn component_instance(&self) -> usize {
+        self.component_instance
+    }
+
+    /// The index of the resource type of this table's handles within the
+    /// component, counting imported resources first.
+    pub fn resource(&self) -> u32 {
+        self.resource
+    }
+
+    /// The handles in this table.
+    pub fn handles(&self) -> &[ComponentResourceHandle] {
+        &self.handles
+    }
+}
+
+/// A handle to a resource within a [`ComponentResourceTable`].
+#[derive(Debug, Clone)]
+pub struct ComponentResourceHandle {
+    index: u32,
+    rep: u32,
+    own: bool,
+}
+
+impl ComponentResourceHandle {
+    /// The index of this handle, which is how the component refers to it.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The representation of the resource this handle refers to.
+    pub fn rep(&self) -> u32 {
+        self.rep
+    }
+
+    /// Returns whether this is an `own` handle rather than a `borrow`.
+    pub fn is_own(&self) -> bool {
+        self.own
+    }
+}
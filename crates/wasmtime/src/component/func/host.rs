@@ -2,7 +2,7 @@ use crate::component::func::{LiftContext, LowerContext, Options};
 use crate::component::matching::InstanceType;
 use crate::component::storage::slice_to_storage_mut;
 use crate::component::{ComponentNamedList, ComponentType, Lift, Lower, Type, Val};
-use crate::{AsContextMut, StoreContextMut, ValRaw};
+use crate::{AsContextMut, CallHook, StoreContextMut, ValRaw};
 use anyhow::{anyhow, bail, Context, Result};
 use std::any::Any;
 use std::mem::{self, MaybeUninit};
@@ -78,9 +78,55 @@ impl HostFunc {
     ) -> Arc<HostFunc>
     where
         F: Fn(StoreContextMut<'_, T>, &[Val], &mut [Val]) -> Result<()> + Send + Sync + 'static,
+    {
+        Self::_new_dynamic(dynamic_entrypoint::<T, F>, Box::new(func), index, types)
+    }
+
+    pub(crate) fn new_dynamic_with_result_types<T, F>(
+        func: F,
+        index: TypeFuncIndex,
+        types: &Arc<ComponentTypes>,
+    ) -> Arc<HostFunc>
+    where
+        F: Fn(StoreContextMut<'_, T>, &[Val], &[Type], &mut [Val]) -> Result<()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self::_new_dynamic(
+            dynamic_with_result_types_entrypoint::<T, F>,
+            Box::new(func),
+            index,
+            types,
+        )
+    }
+
+    /// Same as [`HostFunc::new_dynamic`] except that the type of the function
+    /// is not tied to a particular component's type information.
+    ///
+    /// Instead `typecheck` is used to determine whether this function can
+    /// satisfy an import, which is used when forwarding to the exports of
+    /// another component instance.
+    pub(crate) fn new_dynamic_with_typecheck<T, F, C>(func: F, typecheck: C) -> Arc<HostFunc>
+    where
+        F: Fn(StoreContextMut<'_, T>, &[Val], &mut [Val]) -> Result<()> + Send + Sync + 'static,
+        C: Fn(TypeFuncIndex, &InstanceType<'_>) -> Result<()> + Send + Sync + 'static,
     {
         Arc::new(HostFunc {
             entrypoint: dynamic_entrypoint::<T, F>,
+            typecheck: Box::new(typecheck),
+            func: Box::new(func),
+        })
+    }
+
+    fn _new_dynamic(
+        entrypoint: VMLoweringCallee,
+        func: Box<dyn Any + Send + Sync>,
+        index: TypeFuncIndex,
+        types: &Arc<ComponentTypes>,
+    ) -> Arc<HostFunc> {
+        Arc::new(HostFunc {
+            entrypoint,
             typecheck: Box::new({
                 let types = types.clone();
 
@@ -92,7 +138,7 @@ impl HostFunc {
                     }
                 }
             }),
-            func: Box::new(func),
+            func,
         })
     }
 
@@ -193,6 +239,9 @@ where
         bail!("cannot leave component instance");
     }
 
+    cx.0.call_fuel_hook();
+    cx.0.call_hook(CallHook::CallingHost)?;
+
     let types = (*instance).component_types();
     let ty = &types[ty];
     let param_tys = InterfaceType::Tuple(ty.params);
@@ -232,6 +281,10 @@ where
 
     lower.exit_call()?;
 
+    // Lowering the results may call `realloc`, so the host is only left once
+    // they're lowered.
+    lower.store.0.call_hook(CallHook::ReturningFromHost)?;
+
     return Ok(());
 
     enum Storage<'a, P: ComponentType, R: ComponentType> {
@@ -317,10 +370,11 @@ unsafe fn call_host_dynamic<T, F>(
     realloc: *mut VMFuncRef,
     string_encoding: StringEncoding,
     storage: &mut [MaybeUninit<ValRaw>],
+    with_result_types: bool,
     closure: F,
 ) -> Result<()>
 where
-    F: FnOnce(StoreContextMut<'_, T>, &[Val], &mut [Val]) -> Result<()>,
+    F: FnOnce(StoreContextMut<'_, T>, &[Val], &[Type], &mut [Val]) -> Result<()>,
 {
     let cx = VMComponentContext::from_opaque(cx);
     let instance = (*cx).instance();
@@ -340,6 +394,9 @@ where
         bail!("cannot leave component instance");
     }
 
+    store.0.call_fuel_hook();
+    store.0.call_hook(CallHook::CallingHost)?;
+
     let args;
     let ret_index;
 
@@ -375,11 +432,27 @@ where
         ret_index = 1;
     };
 
+    let result_types = if with_result_types {
+        let instance = cx.instance_type();
+        result_tys
+            .types
+            .iter()
+            .map(|ty| Type::from(ty, &instance))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     let mut result_vals = Vec::with_capacity(result_tys.types.len());
     for _ in result_tys.types.iter() {
         result_vals.push(Val::Bool(false));
     }
-    closure(store.as_context_mut(), &args, &mut result_vals)?;
+    closure(
+        store.as_context_mut(),
+        &args,
+        &result_types,
+        &mut result_vals,
+    )?;
     flags.set_may_leave(false);
 
     let mut cx = LowerContext::new(store, &options, types, instance);
@@ -406,6 +479,8 @@ where
 
     cx.exit_call()?;
 
+    cx.store.0.call_hook(CallHook::ReturningFromHost)?;
+
     return Ok(());
 }
 
@@ -449,7 +524,44 @@ extern "C" fn dynamic_entrypoint<T, F>(
                 realloc,
                 string_encoding,
                 std::slice::from_raw_parts_mut(storage, storage_len),
-                |store, params, results| (*data)(store, params, results),
+                false,
+                |store, params, _, results| (*data)(store, params, results),
+            )
+        })
+    }
+}
+
+extern "C" fn dynamic_with_result_types_entrypoint<T, F>(
+    cx: *mut VMOpaqueContext,
+    data: *mut u8,
+    ty: TypeFuncIndex,
+    flags: InstanceFlags,
+    memory: *mut VMMemoryDefinition,
+    realloc: *mut VMFuncRef,
+    string_encoding: StringEncoding,
+    storage: *mut MaybeUninit<ValRaw>,
+    storage_len: usize,
+) where
+    F: Fn(StoreContextMut<'_, T>, &[Val], &[Type], &mut [Val]) -> Result<()>
+        + Send
+        + Sync
+        + 'static,
+{
+    let data = data as *const F;
+    unsafe {
+        handle_result(|| {
+            call_host_dynamic::<T, _>(
+                cx,
+                ty,
+                flags,
+                memory,
+                realloc,
+                string_encoding,
+                std::slice::from_raw_parts_mut(storage, storage_len),
+                true,
+                |store, params, result_types, results| {
+                    (*data)(store, params, result_types, results)
+                },
             )
         })
     }
@@ -1,19 +1,23 @@
 use crate::component::func::HostFunc;
+use crate::component::linker::Definition;
 use crate::component::matching::InstanceType;
-use crate::component::{Component, ComponentNamedList, Func, Lift, Lower, ResourceType, TypedFunc};
+use crate::component::{
+    Component, ComponentNamedList, Func, Lift, Lower, ResourceType, TypedFunc, Val,
+};
 use crate::instance::OwnedImports;
 use crate::linker::DefinitionType;
 use crate::store::{StoreOpaque, Stored};
-use crate::{AsContextMut, Module, StoreContextMut};
-use anyhow::{anyhow, Context, Result};
+use crate::{AsContextMut, Engine, Module, StoreContextMut, Trap, ValRaw};
+use anyhow::{anyhow, bail, Context, Result};
 use indexmap::IndexMap;
+use std::collections::HashMap;
 use std::marker;
 use std::ptr::NonNull;
 use std::sync::Arc;
 use wasmtime_environ::component::*;
-use wasmtime_environ::{EntityIndex, EntityType, Global, PrimaryMap, WasmType};
+use wasmtime_environ::{EntityIndex, EntityType, Global, MemoryIndex, PrimaryMap, WasmType};
 use wasmtime_runtime::component::{ComponentInstance, OwnedComponentInstance};
-use wasmtime_runtime::VMFuncRef;
+use wasmtime_runtime::{SendSyncPtr, VMFuncRef};
 
 /// An instantiated component.
 ///
@@ -143,6 +147,90 @@ impl Instance {
     pub fn get_resource(&self, mut store: impl AsContextMut, name: &str) -> Option<ResourceType> {
         self.exports(store.as_context_mut()).root().resource(name)
     }
+
+    /// Returns the shared memories of the core wasm instances within this
+    /// component instance.
+    ///
+    /// The returned [`SharedMemories`] can be passed to
+    /// [`InstancePre::instantiate_with_shared_memories`] to instantiate the
+    /// same component again, typically in a store on another thread, with
+    /// its core wasm instances using these memories instead of fresh ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this instance.
+    pub fn shared_memories(&self, mut store: impl AsContextMut) -> SharedMemories {
+        let store = store.as_context_mut().0;
+        let data = store[self.0].take().unwrap();
+        let mut memories = HashMap::new();
+        let mut defined_with_code = false;
+        for (idx, instance) in data.instances.iter() {
+            let id = instance.id(store);
+            let module = store.instance(id).module().clone();
+            let indices = module
+                .memory_plans
+                .iter()
+                .filter(|(_, plan)| plan.memory.shared)
+                .map(|(memory, _)| memory)
+                .collect::<Vec<_>>();
+            defined_with_code |= module.functions.len() > module.num_imported_funcs
+                && indices.iter().any(|m| !module.is_imported_memory(*m));
+            for memory in indices {
+                let export = store.instance_mut(id).get_exported_memory(memory);
+                // SAFETY: the export was just loaded from a live instance and
+                // its plan says that the memory is shared.
+                let shared = unsafe { crate::SharedMemory::from_wasmtime_memory(export, store) };
+                memories.insert((idx, memory), shared);
+            }
+        }
+        let component = data.component.clone();
+        store[self.0] = Some(data);
+        SharedMemories {
+            component: Some(component),
+            memories,
+            defined_with_code,
+        }
+    }
+}
+
+/// The shared memories of a component [`Instance`], as returned by
+/// [`Instance::shared_memories`].
+///
+/// This is used with [`InstancePre::instantiate_with_shared_memories`] to
+/// create further instances of a component which share memory with the
+/// original instance, for example to implement threads.
+#[derive(Clone, Default)]
+pub struct SharedMemories {
+    component: Option<Component>,
+    memories: HashMap<(RuntimeInstanceIndex, MemoryIndex), crate::SharedMemory>,
+    /// Whether a shared memory is defined by a core module which also
+    /// defines functions.
+    defined_with_code: bool,
+}
+
+impl SharedMemories {
+    /// Returns the number of shared memories.
+    pub fn len(&self) -> usize {
+        self.memories.len()
+    }
+
+    /// Returns whether there are no shared memories.
+    pub fn is_empty(&self) -> bool {
+        self.memories.is_empty()
+    }
+
+    /// Returns an iterator over the shared memories.
+    pub fn iter(&self) -> impl Iterator<Item = &crate::SharedMemory> + '_ {
+        self.memories.values()
+    }
+}
+
+impl std::fmt::Debug for SharedMemories {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedMemories")
+            .field("len", &self.memories.len())
+            .finish_non_exhaustive()
+    }
 }
 
 impl InstanceData {
@@ -171,13 +259,24 @@ impl InstanceData {
         store: &mut StoreOpaque,
         item: &CoreExport<T>,
     ) -> wasmtime_runtime::Export
+    where
+        T: Copy + Into<EntityIndex>,
+    {
+        let idx = self.export_index(store, item);
+        let id = self.instances[item.instance].id(store);
+        store.instance_mut(id).get_export_by_index(idx)
+    }
+
+    /// Resolves the index within its core wasm instance of the item that
+    /// `item` refers to.
+    fn export_index<T>(&self, store: &mut StoreOpaque, item: &CoreExport<T>) -> EntityIndex
     where
         T: Copy + Into<EntityIndex>,
     {
         let instance = &self.instances[item.instance];
         let id = instance.id(store);
         let instance = store.instance_mut(id);
-        let idx = match &item.item {
+        match &item.item {
             ExportItem::Index(idx) => (*idx).into(),
 
             // FIXME: ideally at runtime we don't actually do any name lookups
@@ -190,8 +289,7 @@ impl InstanceData {
             // skips all string lookups. This should probably only be
             // investigated if this becomes a performance issue though.
             ExportItem::Name(name) => instance.module().exports[name],
-        };
-        instance.get_export_by_index(idx)
+        }
     }
 
     #[inline]
@@ -209,6 +307,17 @@ impl InstanceData {
         self.component.types()
     }
 
+    #[inline]
+    pub fn component(&self) -> &Component {
+        &self.component
+    }
+
+    pub fn core_instances(
+        &self,
+    ) -> impl Iterator<Item = (RuntimeInstanceIndex, &crate::Instance)> + '_ {
+        self.instances.iter()
+    }
+
     #[inline]
     pub fn ty(&self) -> InstanceType<'_> {
         InstanceType::new(self.instance())
@@ -232,6 +341,7 @@ struct Instantiator<'a> {
     data: InstanceData,
     core_imports: OwnedImports,
     imports: &'a PrimaryMap<RuntimeImportIndex, RuntimeImport>,
+    shared_memories: Option<&'a SharedMemories>,
 }
 
 pub(crate) enum RuntimeImport {
@@ -265,6 +375,7 @@ impl<'a> Instantiator<'a> {
         component: &'a Component,
         store: &mut StoreOpaque,
         imports: &'a Arc<PrimaryMap<RuntimeImportIndex, RuntimeImport>>,
+        shared_memories: Option<&'a SharedMemories>,
     ) -> Instantiator<'a> {
         let env_component = component.env_component();
         store.modules_mut().register_component(component);
@@ -273,6 +384,7 @@ impl<'a> Instantiator<'a> {
         Instantiator {
             component,
             imports,
+            shared_memories,
             core_imports: OwnedImports::empty(),
             data: InstanceData {
                 instances: PrimaryMap::with_capacity(env_component.num_runtime_instances as usize),
@@ -415,8 +527,35 @@ impl<'a> Instantiator<'a> {
         debug_assert_eq!(i, index);
     }
 
+    /// Same as [`InstanceData::lookup_def`] except that exports of shared
+    /// memories are replaced with the memories in `self.shared_memories`.
+    fn lookup_def(&self, store: &mut StoreOpaque, def: &CoreDef) -> wasmtime_runtime::Export {
+        match def {
+            CoreDef::Export(e) => self.lookup_export(store, e),
+            _ => self.data.lookup_def(store, def),
+        }
+    }
+
+    fn lookup_export<T>(
+        &self,
+        store: &mut StoreOpaque,
+        item: &CoreExport<T>,
+    ) -> wasmtime_runtime::Export
+    where
+        T: Copy + Into<EntityIndex>,
+    {
+        if let Some(shared) = self.shared_memories {
+            if let EntityIndex::Memory(idx) = self.data.export_index(store, item) {
+                if let Some(memory) = shared.memories.get(&(item.instance, idx)) {
+                    return wasmtime_runtime::Export::Memory(memory.vmexport(store));
+                }
+            }
+        }
+        self.data.lookup_export(store, item)
+    }
+
     fn extract_memory(&mut self, store: &mut StoreOpaque, memory: &ExtractMemory) {
-        let mem = match self.data.lookup_export(store, &memory.export) {
+        let mem = match self.lookup_export(store, &memory.export) {
             wasmtime_runtime::Export::Memory(m) => m,
             _ => unreachable!(),
         };
@@ -467,7 +606,7 @@ impl<'a> Instantiator<'a> {
             // The unsafety here should be ok since the `export` is loaded
             // directly from an instance which should only give us valid export
             // items.
-            let export = self.data.lookup_def(store, arg);
+            let export = self.lookup_def(store, arg);
             unsafe {
                 self.core_imports.push_export(&export);
             }
@@ -484,7 +623,7 @@ impl<'a> Instantiator<'a> {
         arg: &CoreDef,
         expected: EntityType,
     ) {
-        let export = self.data.lookup_def(store, arg);
+        let export = self.lookup_def(store, arg);
 
         // If this value is a core wasm function then the type check is inlined
         // here. This can otherwise fail `Extern::from_wasmtime_export` because
@@ -565,8 +704,80 @@ impl<T> InstancePre<T> {
             !store.as_context().async_support(),
             "must use async instantiation when async support is enabled"
         );
-        self.instantiate_impl(store)
+        self.instantiate_impl(store, None)
+    }
+
+    /// Performs the instantiation process into the store specified, using
+    /// `shared_memories` in place of the shared memories that the core wasm
+    /// instances of this component would otherwise create.
+    ///
+    /// The `shared_memories` must come from [`Instance::shared_memories`] on
+    /// an instance of the same component, and this is intended to be used to
+    /// run code on another thread which shares memory with that instance, as
+    /// in the wasi-threads proposal.
+    ///
+    /// Note that only core wasm modules which import a shared memory use the
+    /// original memory. A core wasm module which defines a shared memory
+    /// creates a new memory as usual, so components using threads must
+    /// define their shared memory in a module of its own, without any
+    /// functions, which is imported by all other modules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shared_memories` came from an instance of a
+    /// different component, or if one of the shared memories is defined by
+    /// a core module which also defines functions, since those functions
+    /// would use a new memory rather than the shared one.
+    pub fn instantiate_with_shared_memories(
+        &self,
+        store: impl AsContextMut<Data = T>,
+        shared_memories: &SharedMemories,
+    ) -> Result<Instance> {
+        assert!(
+            !store.as_context().async_support(),
+            "must use async instantiation when async support is enabled"
+        );
+        self.check_shared_memories(shared_memories)?;
+        self.instantiate_impl(store, Some(shared_memories))
+    }
+
+    /// Same as [`Self::instantiate_with_shared_memories`] except for use on
+    /// async stores.
+    #[cfg(feature = "async")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "async")))]
+    pub async fn instantiate_with_shared_memories_async(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+        shared_memories: &SharedMemories,
+    ) -> Result<Instance>
+    where
+        T: Send,
+    {
+        let mut store = store.as_context_mut();
+        assert!(
+            store.0.async_support(),
+            "must use sync instantiation when async support is disabled"
+        );
+        self.check_shared_memories(shared_memories)?;
+        store
+            .on_fiber(|store| self.instantiate_impl(store, Some(shared_memories)))
+            .await?
+    }
+
+    fn check_shared_memories(&self, shared_memories: &SharedMemories) -> Result<()> {
+        match &shared_memories.component {
+            Some(component) if !Component::same(component, &self.component) => {
+                bail!("shared memories belong to an instance of a different component")
+            }
+            _ if shared_memories.defined_with_code => bail!(
+                "a shared memory of this component is defined by a core module which also \
+                 defines functions; it must be defined by a module of its own and imported \
+                 by the modules which use it"
+            ),
+            _ => Ok(()),
+        }
     }
+
     /// Performs the instantiation process into the store specified.
     ///
     /// Exactly like [`Self::instantiate`] except for use on async stores.
@@ -586,16 +797,23 @@ impl<T> InstancePre<T> {
             store.0.async_support(),
             "must use sync instantiation when async support is disabled"
         );
-        store.on_fiber(|store| self.instantiate_impl(store)).await?
+        store
+            .on_fiber(|store| self.instantiate_impl(store, None))
+            .await?
     }
 
-    fn instantiate_impl(&self, mut store: impl AsContextMut<Data = T>) -> Result<Instance> {
+    fn instantiate_impl(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+        shared_memories: Option<&SharedMemories>,
+    ) -> Result<Instance> {
         let mut store = store.as_context_mut();
         store
             .engine()
             .allocator()
             .increment_component_instance_count()?;
-        let mut instantiator = Instantiator::new(&self.component, store.0, &self.imports);
+        let mut instantiator =
+            Instantiator::new(&self.component, store.0, &self.imports, shared_memories);
         instantiator.run(&mut store).map_err(|e| {
             store
                 .engine()
@@ -794,4 +1012,127 @@ impl<'a, 'store> ExportInstance<'a, 'store> {
             _ => None,
         }
     }
+
+    /// Creates definitions for a [`Linker`](crate::component::Linker) which
+    /// forward to each item exported from this instance.
+    ///
+    /// This is the implementation of
+    /// [`LinkerInstance::instance_from`](crate::component::LinkerInstance::instance_from).
+    /// Functions are forwarded with dynamic calls to the exported function
+    /// and resources are defined as the exact same resource type, with a
+    /// destructor that runs this instance's destructor for the resource.
+    /// Nested instances and types which aren't resources are skipped.
+    pub(crate) fn forwarded_definitions<T>(
+        &mut self,
+        engine: &Engine,
+    ) -> Vec<(String, Definition)> {
+        let store_id = self.store.id();
+        let data = self.data;
+        let instance_ty = data.ty();
+        let exports = self.exports;
+        let mut definitions = Vec::new();
+        for (name, export) in exports {
+            let definition = match export {
+                Export::LiftedFunction { ty, .. } => {
+                    let func = self.func(name).unwrap();
+                    let actual = *ty;
+                    let types = instance_ty.types.clone();
+                    let resources = instance_ty.resources.clone();
+                    Definition::Func(HostFunc::new_dynamic_with_typecheck(
+                        move |mut store: StoreContextMut<'_, T>,
+                              params: &[Val],
+                              results: &mut [Val]| {
+                            if store.0.id() != store_id {
+                                bail!("forwarded function called with a different store");
+                            }
+                            func.call(&mut store, params, results)?;
+                            func.post_return(&mut store)?;
+                            // Borrows passed to the host must be dropped
+                            // before it returns.
+                            for param in params {
+                                drop_borrows(&mut store, param)?;
+                            }
+                            Ok(())
+                        },
+                        move |expected, expected_ty| {
+                            let actual_ty = InstanceType {
+                                types: &types,
+                                resources: &resources,
+                            };
+                            expected_ty.func(expected, &actual_ty, actual)
+                        },
+                    ))
+                }
+                Export::Type(TypeDef::Resource(id)) => {
+                    let ty = instance_ty.resource_type(*id);
+                    let (dtor, flags) = data.instance().dtor_and_flags(*id);
+                    let dtor = dtor.map(SendSyncPtr::new);
+                    let dtor = crate::func::HostFunc::wrap(
+                        engine,
+                        move |mut caller: crate::Caller<'_, T>, rep: u32| -> Result<()> {
+                            let mut store = caller.as_context_mut();
+                            if store.0.id() != store_id {
+                                bail!("forwarded resource dropped in a different store");
+                            }
+
+                            // Like `ResourceAny::resource_drop` the exporting
+                            // instance must be enterable to run its destructor.
+                            if let Some(flags) = flags {
+                                if unsafe { !flags.may_enter() } {
+                                    bail!(Trap::CannotEnterComponent);
+                                }
+                            }
+                            let dtor = match dtor {
+                                Some(dtor) => dtor.as_non_null(),
+                                None => return Ok(()),
+                            };
+                            let mut args = [ValRaw::u32(rep)];
+
+                            // This should be safe because `dtor` belongs to the
+                            // store checked above and destructors are always
+                            // type-checked to take a single `i32` argument.
+                            unsafe {
+                                crate::Func::call_unchecked_raw(
+                                    &mut store,
+                                    dtor,
+                                    args.as_mut_ptr(),
+                                    args.len(),
+                                )
+                            }
+                        },
+                    );
+                    Definition::Resource(ty, Arc::new(dtor))
+                }
+                Export::ModuleStatic(_) | Export::ModuleImport(_) => {
+                    Definition::Module(self.module(name).unwrap().clone())
+                }
+                Export::Type(_) | Export::Instance(_) => continue,
+            };
+            definitions.push((name.clone(), definition));
+        }
+        definitions
+    }
+}
+
+/// Drops the borrowed resources within `val`, which were lifted into the host
+/// when calling a forwarded function.
+fn drop_borrows<T>(store: &mut StoreContextMut<'_, T>, val: &Val) -> Result<()> {
+    let vals: Box<dyn Iterator<Item = &Val>> = match val {
+        Val::Resource(resource) if !resource.owned() => {
+            return resource.resource_drop(&mut *store);
+        }
+        Val::List(list) => Box::new(list.iter()),
+        Val::Record(record) => Box::new(record.fields().map(|(_, val)| val)),
+        Val::Tuple(tuple) => Box::new(tuple.values().iter()),
+        Val::Variant(variant) => Box::new(variant.payload().into_iter()),
+        Val::Option(option) => Box::new(option.value().into_iter()),
+        Val::Result(result) => match result.value() {
+            Ok(val) | Err(val) => Box::new(val.into_iter()),
+        },
+        _ => return Ok(()),
+    };
+    for val in vals {
+        drop_borrows(store, val)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,310 @@
+//! Conversion of component model values to and from JSON.
+//!
+//! The encoding is driven by the component model type of a value so that
+//! every value round-trips exactly, see [`Val::from_json`] for the details.
+//! The same encoding is available for other serde data formats through the
+//! [`Serialize`] implementation of [`Val`] and the [`DeserializeSeed`]
+//! implementation of [`Type`].
+
+use crate::component::{Type, Val};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::{self, DeserializeSeed, Deserializer};
+use serde::ser::{self, SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+impl Val {
+    /// Converts this value to JSON.
+    ///
+    /// See [`Val::from_json`] for the encoding used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this value is, or contains, a resource.
+    pub fn to_json(&self) -> Result<Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// Converts `json` to a value of type `ty`.
+    ///
+    /// Each kind of type has one JSON encoding:
+    ///
+    /// * `bool`, integers, and `string` are the corresponding JSON values.
+    /// * `float32` and `float64` are numbers or one of the strings `"nan"`,
+    ///   `"inf"`, and `"-inf"`.
+    /// * `char` is a string with exactly one character.
+    /// * `list` and `tuple` are arrays.
+    /// * `record` is an object with exactly one key per field.
+    /// * `flags` is an array of the names of the flags which are set.
+    /// * `enum` is the name of the case as a string.
+    /// * `variant`, `option`, and `result` are the name of the case as a
+    ///   string if the case has no payload, and otherwise an object with the
+    ///   name of the case as its single key, for example `"none"`,
+    ///   `{"some": 1}`, `"ok"`, or `{"err": "message"}`. Unlike encoding
+    ///   `none` as `null` this keeps types such as `option<option<u32>>`
+    ///   unambiguous.
+    ///
+    /// This is the same encoding that [`Val::to_json`] and the [`Serialize`]
+    /// implementation of [`Val`] produce.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` doesn't match the encoding of `ty`, or if
+    /// `ty` is, or contains, a resource.
+    pub fn from_json(ty: &Type, json: &Value) -> Result<Val> {
+        Ok(match ty {
+            Type::Bool => Val::Bool(json.as_bool().ok_or_else(|| mismatch("bool", json))?),
+            Type::S8 => Val::S8(integer(json, "s8")?),
+            Type::U8 => Val::U8(integer(json, "u8")?),
+            Type::S16 => Val::S16(integer(json, "s16")?),
+            Type::U16 => Val::U16(integer(json, "u16")?),
+            Type::S32 => Val::S32(integer(json, "s32")?),
+            Type::U32 => Val::U32(integer(json, "u32")?),
+            Type::S64 => Val::S64(integer(json, "s64")?),
+            Type::U64 => Val::U64(integer(json, "u64")?),
+            Type::Float32 => Val::Float32(float(json, "float32")? as f32),
+            Type::Float64 => Val::Float64(float(json, "float64")?),
+            Type::Char => {
+                let s = json.as_str().ok_or_else(|| mismatch("char", json))?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Val::Char(c),
+                    _ => bail!("expected a single character, found {json}"),
+                }
+            }
+            Type::String => Val::String(
+                json.as_str()
+                    .ok_or_else(|| mismatch("string", json))?
+                    .into(),
+            ),
+            Type::List(list) => {
+                let ty = list.ty();
+                let values = json
+                    .as_array()
+                    .ok_or_else(|| mismatch("list", json))?
+                    .iter()
+                    .enumerate()
+                    .map(|(i, json)| {
+                        Val::from_json(&ty, json).with_context(|| format!("invalid element {i}"))
+                    })
+                    .collect::<Result<Box<[_]>>>()?;
+                list.new_val(values)?
+            }
+            Type::Record(record) => {
+                let object = json.as_object().ok_or_else(|| mismatch("record", json))?;
+                let mut values = Vec::with_capacity(object.len());
+                for field in record.fields() {
+                    let json = object
+                        .get(field.name)
+                        .ok_or_else(|| anyhow!("missing field `{}`", field.name))?;
+                    let value = Val::from_json(&field.ty, json)
+                        .with_context(|| format!("invalid field `{}`", field.name))?;
+                    values.push((field.name, value));
+                }
+                if let Some(name) = object
+                    .keys()
+                    .find(|name| !record.fields().any(|f| f.name == name.as_str()))
+                {
+                    bail!("unknown field `{name}`");
+                }
+                record.new_val(values)?
+            }
+            Type::Tuple(tuple) => {
+                let array = json.as_array().ok_or_else(|| mismatch("tuple", json))?;
+                if array.len() != tuple.types().len() {
+                    bail!(
+                        "expected {} element(s) in tuple, found {}",
+                        tuple.types().len(),
+                        array.len()
+                    );
+                }
+                let values = tuple
+                    .types()
+                    .zip(array)
+                    .enumerate()
+                    .map(|(i, (ty, json))| {
+                        Val::from_json(&ty, json).with_context(|| format!("invalid element {i}"))
+                    })
+                    .collect::<Result<Box<[_]>>>()?;
+                tuple.new_val(values)?
+            }
+            Type::Variant(variant) => {
+                let (name, payload) = tagged(json, "variant")?;
+                let case = variant
+                    .cases()
+                    .find(|c| c.name == name)
+                    .ok_or_else(|| anyhow!("unknown variant case `{name}`"))?;
+                let payload = self::payload(name, case.ty.as_ref(), payload)?;
+                variant.new_val(name, payload)?
+            }
+            Type::Enum(e) => e.new_val(json.as_str().ok_or_else(|| mismatch("enum", json))?)?,
+            Type::Option(option) => {
+                let value = match tagged(json, "option")? {
+                    ("none", None) => None,
+                    ("some", Some(json)) => {
+                        Some(Val::from_json(&option.ty(), json).context("invalid `some` payload")?)
+                    }
+                    _ => bail!("expected `\"none\"` or `{{\"some\": ...}}`, found {json}"),
+                };
+                option.new_val(value)?
+            }
+            Type::Result(result) => {
+                let value = match tagged(json, "result")? {
+                    ("ok", payload) => Ok(self::payload("ok", result.ok().as_ref(), payload)?),
+                    ("err", payload) => Err(self::payload("err", result.err().as_ref(), payload)?),
+                    (name, _) => bail!("expected `ok` or `err`, found `{name}`"),
+                };
+                result.new_val(value)?
+            }
+            Type::Flags(flags) => {
+                let names = json
+                    .as_array()
+                    .ok_or_else(|| mismatch("flags", json))?
+                    .iter()
+                    .map(|json| json.as_str().ok_or_else(|| mismatch("flag name", json)))
+                    .collect::<Result<Vec<_>>>()?;
+                flags.new_val(&names)?
+            }
+            Type::Own(_) | Type::Borrow(_) => bail!("resources cannot be converted from JSON"),
+        })
+    }
+}
+
+fn mismatch(expected: &str, json: &Value) -> anyhow::Error {
+    anyhow!("expected {expected}, found {json}")
+}
+
+fn integer<T>(json: &Value, ty: &str) -> Result<T>
+where
+    T: TryFrom<i64> + TryFrom<u64>,
+{
+    let n = match json {
+        Value::Number(n) => n,
+        _ => return Err(mismatch(ty, json)),
+    };
+    let value = if let Some(n) = n.as_u64() {
+        T::try_from(n).ok()
+    } else if let Some(n) = n.as_i64() {
+        T::try_from(n).ok()
+    } else {
+        None
+    };
+    value.ok_or_else(|| anyhow!("`{n}` is not a valid `{ty}`"))
+}
+
+fn float(json: &Value, ty: &str) -> Result<f64> {
+    match json {
+        Value::Number(n) => n.as_f64().ok_or_else(|| mismatch(ty, json)),
+        Value::String(s) => match s.as_str() {
+            "nan" => Ok(f64::NAN),
+            "inf" => Ok(f64::INFINITY),
+            "-inf" => Ok(f64::NEG_INFINITY),
+            _ => Err(mismatch(ty, json)),
+        },
+        _ => Err(mismatch(ty, json)),
+    }
+}
+
+/// Splits an externally tagged case into its name and optional payload.
+fn tagged<'a>(json: &'a Value, what: &str) -> Result<(&'a str, Option<&'a Value>)> {
+    match json {
+        Value::String(name) => Ok((name, None)),
+        Value::Object(object) if object.len() == 1 => {
+            let (name, payload) = object.iter().next().unwrap();
+            Ok((name, Some(payload)))
+        }
+        _ => Err(mismatch(what, json)),
+    }
+}
+
+fn payload(name: &str, ty: Option<&Type>, json: Option<&Value>) -> Result<Option<Val>> {
+    match (ty, json) {
+        (Some(ty), Some(json)) => Ok(Some(
+            Val::from_json(ty, json).with_context(|| format!("invalid `{name}` payload"))?,
+        )),
+        (None, None) => Ok(None),
+        (Some(_), None) => bail!("case `{name}` requires a payload"),
+        (None, Some(_)) => bail!("case `{name}` does not have a payload"),
+    }
+}
+
+/// Serializes a value using the encoding described in [`Val::from_json`].
+///
+/// This works with any serde data format, not only JSON.
+impl Serialize for Val {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Val::Bool(b) => serializer.serialize_bool(*b),
+            Val::S8(v) => serializer.serialize_i8(*v),
+            Val::U8(v) => serializer.serialize_u8(*v),
+            Val::S16(v) => serializer.serialize_i16(*v),
+            Val::U16(v) => serializer.serialize_u16(*v),
+            Val::S32(v) => serializer.serialize_i32(*v),
+            Val::U32(v) => serializer.serialize_u32(*v),
+            Val::S64(v) => serializer.serialize_i64(*v),
+            Val::U64(v) => serializer.serialize_u64(*v),
+            Val::Float32(v) if v.is_finite() => serializer.serialize_f32(*v),
+            Val::Float64(v) if v.is_finite() => serializer.serialize_f64(*v),
+            Val::Float32(v) => serializer.serialize_str(non_finite(f64::from(*v))),
+            Val::Float64(v) => serializer.serialize_str(non_finite(*v)),
+            Val::Char(c) => serializer.serialize_char(*c),
+            Val::String(s) => serializer.serialize_str(s),
+            Val::List(list) => serializer.collect_seq(list.iter()),
+            Val::Record(record) => serializer.collect_map(record.fields()),
+            Val::Tuple(tuple) => serializer.collect_seq(tuple.values()),
+            Val::Variant(variant) => {
+                serialize_case(serializer, variant.discriminant(), variant.payload())
+            }
+            Val::Enum(e) => serializer.serialize_str(e.discriminant()),
+            Val::Option(option) => match option.value() {
+                Some(value) => serialize_case(serializer, "some", Some(value)),
+                None => serialize_case(serializer, "none", None),
+            },
+            Val::Result(result) => match result.value() {
+                Ok(value) => serialize_case(serializer, "ok", value),
+                Err(value) => serialize_case(serializer, "err", value),
+            },
+            Val::Flags(flags) => serializer.collect_seq(flags.flags()),
+            Val::Resource(_) => Err(ser::Error::custom("resources cannot be serialized")),
+        }
+    }
+}
+
+fn non_finite(v: f64) -> &'static str {
+    if v.is_nan() {
+        "nan"
+    } else if v > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    }
+}
+
+fn serialize_case<S: Serializer>(
+    serializer: S,
+    name: &str,
+    payload: Option<&Val>,
+) -> Result<S::Ok, S::Error> {
+    match payload {
+        Some(payload) => {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(name, payload)?;
+            map.end()
+        }
+        None => serializer.serialize_str(name),
+    }
+}
+
+/// Deserializes a value of this type using the encoding described in
+/// [`Val::from_json`].
+///
+/// The input is first deserialized into a JSON value, so this requires a
+/// self-describing data format.
+impl<'de> DeserializeSeed<'de> for &Type {
+    type Value = Val;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Val, D::Error> {
+        let json = Value::deserialize(deserializer)?;
+        Val::from_json(self, &json).map_err(|e| de::Error::custom(format!("{e:#}")))
+    }
+}
@@ -2,18 +2,19 @@ use crate::component::func::HostFunc;
 use crate::component::instance::RuntimeImport;
 use crate::component::matching::TypeChecker;
 use crate::component::{
-    Component, ComponentNamedList, Instance, InstancePre, Lift, Lower, ResourceType, Val,
+    Component, ComponentNamedList, Instance, InstancePre, Lift, Lower, ResourceType, Type, Val,
 };
 use crate::{AsContextMut, Engine, Module, StoreContextMut};
 use anyhow::{anyhow, bail, Context, Result};
 use indexmap::IndexMap;
 use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
 use std::future::Future;
 use std::marker;
 use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::Arc;
-use wasmtime_environ::component::TypeDef;
+use wasmtime_environ::component::{ComponentTypes, ResourceIndex, TypeDef, TypeFuncIndex};
 use wasmtime_environ::PrimaryMap;
 
 /// A type used to instantiate [`Component`]s.
@@ -126,6 +127,95 @@ impl<T> Linker<T> {
         self.root().into_instance(name)
     }
 
+    /// Implement any imports of the given [`Component`] which aren't yet
+    /// defined in this linker with stubs that trap.
+    ///
+    /// Every missing function import, including those nested within imported
+    /// instances, is defined as a function which traps when called. Missing
+    /// resource imports are defined as a placeholder host resource type,
+    /// though no values of it can be created. Imports of core modules can't be
+    /// stubbed and are left undefined.
+    ///
+    /// This method can be used to instantiate components whose imports are not
+    /// all used at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wasmtime::*;
+    /// # use wasmtime::component::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut config = Config::new();
+    /// # config.wasm_component_model(true);
+    /// # let engine = Engine::new(&config)?;
+    /// # let component = Component::new(&engine, r#"
+    /// #     (component (import "unknown" (func)))
+    /// # "#)?;
+    /// # let mut store = Store::new(&engine, ());
+    /// let mut linker = Linker::new(&engine);
+    /// linker.define_unknown_imports_as_traps(&component)?;
+    /// linker.instantiate(&mut store, &component)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn define_unknown_imports_as_traps(&mut self, component: &Component) -> Result<()> {
+        self.define_unknown_imports(component, false);
+        Ok(())
+    }
+
+    /// Implement any imports of the given [`Component`] which aren't yet
+    /// defined in this linker with stubs that ignore their arguments and
+    /// return default values.
+    ///
+    /// This is the same as [`Linker::define_unknown_imports_as_traps`] except
+    /// that function stubs return a default value of each of their result
+    /// types instead of trapping. Default values are `false`, zero, the empty
+    /// string or list, `none`, the first case of an `enum` or `variant`, `ok`
+    /// for a `result`, and no flags, with any payloads or fields in turn
+    /// defaulted. Resource handles have no default value, so stubs returning
+    /// them trap when called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wasmtime::*;
+    /// # use wasmtime::component::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut config = Config::new();
+    /// # config.wasm_component_model(true);
+    /// # let engine = Engine::new(&config)?;
+    /// # let component = Component::new(&engine, r#"
+    /// #     (component (import "unknown" (func (result u32))))
+    /// # "#)?;
+    /// # let mut store = Store::new(&engine, ());
+    /// let mut linker = Linker::new(&engine);
+    /// linker.define_unknown_imports_as_default_values(&component)?;
+    /// linker.instantiate(&mut store, &component)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn define_unknown_imports_as_default_values(
+        &mut self,
+        component: &Component,
+    ) -> Result<()> {
+        self.define_unknown_imports(component, true);
+        Ok(())
+    }
+
+    fn define_unknown_imports(&mut self, component: &Component, default_values: bool) {
+        let mut stubs = Stubs {
+            engine: &self.engine,
+            strings: &mut self.strings,
+            types: component.types(),
+            resources: HashSet::new(),
+            default_values,
+            _marker: marker::PhantomData::<fn() -> T>,
+        };
+        for (_idx, (name, ty)) in component.env_component().import_types.iter() {
+            stubs.define(&mut self.map, name, name, ty);
+        }
+    }
+
     /// Performs a "pre-instantiation" to resolve the imports of the
     /// [`Component`] specified with the items defined within this linker.
     ///
@@ -343,6 +433,46 @@ impl<T> LinkerInstance<'_, T> {
         name: &str,
         func: F,
     ) -> Result<()> {
+        let index = self.import_func_index(component, name)?;
+        let name = self.strings.intern(name);
+        self.insert(
+            name,
+            Definition::Func(HostFunc::new_dynamic(func, index, component.types())),
+        )
+    }
+
+    /// Same as [`Self::func_new`], except that `func` is additionally given
+    /// the types of the values it must write into its results.
+    ///
+    /// This is useful for bindings to other languages, which need the
+    /// expected types to construct compound results such as records or lists.
+    pub fn func_new_with_result_types<F>(
+        &mut self,
+        component: &Component,
+        name: &str,
+        func: F,
+    ) -> Result<()>
+    where
+        F: Fn(StoreContextMut<'_, T>, &[Val], &[Type], &mut [Val]) -> Result<()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let index = self.import_func_index(component, name)?;
+        let name = self.strings.intern(name);
+        self.insert(
+            name,
+            Definition::Func(HostFunc::new_dynamic_with_result_types(
+                func,
+                index,
+                component.types(),
+            )),
+        )
+    }
+
+    /// Looks up the type of the function import `name` within this instance
+    /// of `component`.
+    fn import_func_index(&self, component: &Component, name: &str) -> Result<TypeFuncIndex> {
         let mut map = &component
             .env_component()
             .import_types
@@ -363,18 +493,10 @@ impl<T> LinkerInstance<'_, T> {
             }
         }
 
-        if let Some(ty) = map.get(name) {
-            if let TypeDef::ComponentFunc(index) = ty {
-                let name = self.strings.intern(name);
-                return self.insert(
-                    name,
-                    Definition::Func(HostFunc::new_dynamic(func, *index, component.types())),
-                );
-            } else {
-                bail!("import `{name}` has the wrong type (expected a function)");
-            }
-        } else {
-            Err(anyhow!("import `{name}` not found"))
+        match map.get(name) {
+            Some(TypeDef::ComponentFunc(index)) => Ok(*index),
+            Some(_) => bail!("import `{name}` has the wrong type (expected a function)"),
+            None => Err(anyhow!("import `{name}` not found")),
         }
     }
 
@@ -453,6 +575,63 @@ impl<T> LinkerInstance<'_, T> {
         self.insert(name, Definition::Resource(ty, dtor))
     }
 
+    /// Defines the instance `name` within this instance as the instance of
+    /// the same name exported from `instance`.
+    ///
+    /// This can be used to compose components at runtime: one component is
+    /// instantiated first and then its exported interface is used to satisfy
+    /// the imports of another component instantiated afterwards, without
+    /// needing to fuse the two together ahead of time with a tool such as
+    /// `wasm-compose`.
+    ///
+    /// Functions exported by `instance` are forwarded dynamically and are
+    /// type-checked structurally against the importing component's
+    /// expectations when it's instantiated. Resources exported by `instance`
+    /// are defined as the exact same [`ResourceType`], so handles flow between
+    /// the two components with the exporter remaining their owner. Dropping
+    /// an `own` handle in the importer runs the exporter's destructor.
+    /// Exported core modules are defined as-is while nested instances and
+    /// other exported types are not forwarded.
+    ///
+    /// Note that the definitions created here refer to `instance` and are
+    /// only valid for use with `store`. Instantiating a component within a
+    /// different store with this linker will fail when a forwarded item is
+    /// used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `instance` doesn't export an instance named `name`
+    /// or if `name` is already defined in this linker and shadowing is
+    /// disallowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` has async support enabled, or if `instance` doesn't
+    /// belong to `store`.
+    pub fn instance_from(
+        &mut self,
+        mut store: impl AsContextMut<Data = T>,
+        instance: &Instance,
+        name: &str,
+    ) -> Result<()> {
+        let mut store = store.as_context_mut();
+        assert!(
+            !store.0.async_support(),
+            "cannot use `instance_from` with async support enabled in the config"
+        );
+        let definitions = instance
+            .exports(&mut store)
+            .instance(name)
+            .ok_or_else(|| anyhow!("instance export `{name}` not found"))?
+            .forwarded_definitions::<T>(self.engine);
+        let mut dst = self.instance(name)?;
+        for (name, definition) in definitions {
+            let name = dst.strings.intern(&name);
+            dst.insert(name, definition)?;
+        }
+        Ok(())
+    }
+
     /// Defines a nested instance within this instance.
     ///
     /// This can be used to describe arbitrarily nested levels of instances
@@ -503,6 +682,129 @@ impl<T> LinkerInstance<'_, T> {
     }
 }
 
+/// Helper for [`Linker::define_unknown_imports_as_traps`] and
+/// [`Linker::define_unknown_imports_as_default_values`].
+struct Stubs<'a, T> {
+    engine: &'a Engine,
+    strings: &'a mut Strings,
+    types: &'a Arc<ComponentTypes>,
+    /// Resources seen so far, which later `(eq ...)` imports must not be
+    /// redefined as a fresh placeholder.
+    resources: HashSet<ResourceIndex>,
+    /// Whether function stubs return default values rather than trapping.
+    default_values: bool,
+    _marker: marker::PhantomData<fn() -> T>,
+}
+
+/// Placeholder type for stubbed resource imports.
+struct UnknownResource;
+
+impl<T> Stubs<'_, T> {
+    fn define(&mut self, map: &mut NameMap, path: &str, name: &str, ty: &TypeDef) {
+        let key = self.strings.intern(name);
+        match *ty {
+            TypeDef::ComponentInstance(index) => {
+                let slot = map
+                    .entry(key)
+                    .or_insert_with(|| Definition::Instance(NameMap::default()));
+                if let Definition::Instance(map) = slot {
+                    let types = self.types.clone();
+                    for (name, ty) in types[index].exports.iter() {
+                        self.define(map, &format!("{path}#{name}"), name, ty);
+                    }
+                }
+            }
+            TypeDef::ComponentFunc(index) if !map.contains_key(&key) => {
+                let func = if self.default_values {
+                    HostFunc::new_dynamic_with_result_types(
+                        |_: StoreContextMut<'_, T>,
+                         _: &[Val],
+                         tys: &[Type],
+                         results: &mut [Val]| {
+                            for (result, ty) in results.iter_mut().zip(tys) {
+                                *result = default_value(ty)?;
+                            }
+                            Ok(())
+                        },
+                        index,
+                        self.types,
+                    )
+                } else {
+                    let msg = format!("unknown import `{path}` has not been defined");
+                    HostFunc::new_dynamic(
+                        move |_: StoreContextMut<'_, T>, _: &[Val], _: &mut [Val]| bail!("{msg}"),
+                        index,
+                        self.types,
+                    )
+                };
+                map.insert(key, Definition::Func(func));
+            }
+            TypeDef::Resource(index) => {
+                let resource = self.types[index].ty;
+                if self.resources.insert(resource) && !map.contains_key(&key) {
+                    let dtor = Arc::new(crate::func::HostFunc::wrap(
+                        self.engine,
+                        |_: crate::Caller<'_, T>, _: u32| {},
+                    ));
+                    map.insert(
+                        key,
+                        Definition::Resource(ResourceType::host::<UnknownResource>(), dtor),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the default value of `ty` used by
+/// [`Linker::define_unknown_imports_as_default_values`].
+fn default_value(ty: &Type) -> Result<Val> {
+    Ok(match ty {
+        Type::Bool => Val::Bool(false),
+        Type::S8 => Val::S8(0),
+        Type::U8 => Val::U8(0),
+        Type::S16 => Val::S16(0),
+        Type::U16 => Val::U16(0),
+        Type::S32 => Val::S32(0),
+        Type::U32 => Val::U32(0),
+        Type::S64 => Val::S64(0),
+        Type::U64 => Val::U64(0),
+        Type::Float32 => Val::Float32(0.0),
+        Type::Float64 => Val::Float64(0.0),
+        Type::Char => Val::Char('\0'),
+        Type::String => Val::String("".into()),
+        Type::List(list) => list.new_val(Box::new([]))?,
+        Type::Record(record) => {
+            let fields = record
+                .fields()
+                .map(|field| Ok((field.name, default_value(&field.ty)?)))
+                .collect::<Result<Vec<_>>>()?;
+            record.new_val(fields)?
+        }
+        Type::Tuple(tuple) => {
+            let values = tuple
+                .types()
+                .map(|ty| default_value(&ty))
+                .collect::<Result<Box<[_]>>>()?;
+            tuple.new_val(values)?
+        }
+        Type::Variant(variant) => {
+            let case = variant.cases().next().unwrap();
+            let payload = case.ty.as_ref().map(default_value).transpose()?;
+            variant.new_val(case.name, payload)?
+        }
+        Type::Enum(e) => e.new_val(e.names().next().unwrap())?,
+        Type::Option(option) => option.new_val(None)?,
+        Type::Result(result) => {
+            let payload = result.ok().as_ref().map(default_value).transpose()?;
+            result.new_val(Ok(payload))?
+        }
+        Type::Flags(flags) => flags.new_val(&[])?,
+        Type::Own(_) | Type::Borrow(_) => bail!("resources have no default value"),
+    })
+}
+
 impl Strings {
     fn intern(&mut self, string: &str) -> usize {
         if let Some(idx) = self.string2idx.get(string) {
@@ -1,5 +1,6 @@
 use crate::component::func::HostFunc;
 use crate::component::linker::{Definition, NameMap, Strings};
+use crate::component::types;
 use crate::component::ResourceType;
 use crate::types::matching;
 use crate::Module;
@@ -198,6 +199,29 @@ impl<'a> InstanceType<'a> {
         let index = self.types[index].ty;
         self.resources[index]
     }
+
+    /// Checks that the function type `expected` within this instance type is
+    /// satisfied by the function type `actual` within `actual_ty`.
+    ///
+    /// Unlike host functions the two types generally live in different
+    /// components, so this is a structural comparison where resources must be
+    /// the exact same runtime type on both sides.
+    pub fn func(
+        &self,
+        expected: TypeFuncIndex,
+        actual_ty: &InstanceType<'_>,
+        actual: TypeFuncIndex,
+    ) -> Result<()> {
+        let expected = &self.types[expected];
+        let actual = &actual_ty.types[actual];
+        if !types::tuples_equal(expected.params, self, actual.params, actual_ty) {
+            bail!("type mismatch with parameters");
+        }
+        if !types::tuples_equal(expected.results, self, actual.results, actual_ty) {
+            bail!("type mismatch with results");
+        }
+        Ok(())
+    }
 }
 
 /// Small helper method to downcast an `Arc` borrow into a borrow of a concrete
@@ -6,8 +6,11 @@
 #![cfg_attr(nightlydoc, doc(cfg(feature = "component-model")))]
 
 mod component;
+#[cfg(feature = "coredump")]
+mod coredump;
 mod func;
 mod instance;
+mod json;
 mod linker;
 mod matching;
 mod resource_table;
@@ -17,10 +20,14 @@ mod store;
 pub mod types;
 mod values;
 pub use self::component::Component;
+#[cfg(feature = "coredump")]
+pub use self::coredump::{
+    ComponentCoreDump, ComponentCoreInstance, ComponentResourceHandle, ComponentResourceTable,
+};
 pub use self::func::{
     ComponentNamedList, ComponentType, Func, Lift, Lower, TypedFunc, WasmList, WasmStr,
 };
-pub use self::instance::{ExportInstance, Exports, Instance, InstancePre};
+pub use self::instance::{ExportInstance, Exports, Instance, InstancePre, SharedMemories};
 pub use self::linker::{Linker, LinkerInstance};
 pub use self::resource_table::{ResourceTable, ResourceTableError};
 pub use self::resources::{Resource, ResourceAny};
@@ -1,11 +1,14 @@
 use super::Resource;
-use std::any::Any;
+use crate::ResourceLimiter;
+use std::any::{Any, TypeId};
 use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
 
 #[derive(Debug)]
 /// Errors returned by operations on `ResourceTable`
 pub enum ResourceTableError {
-    /// ResourceTable has no free keys
+    /// ResourceTable has no free keys, or has reached its configured maximum
+    /// number of entries
     Full,
     /// Resource not present in table
     NotPresent,
@@ -29,10 +32,27 @@ impl std::fmt::Display for ResourceTableError {
 impl std::error::Error for ResourceTableError {}
 
 /// The `ResourceTable` type maps a `Resource<T>` to its `T`.
+///
+/// By default a table can grow without bound. Hosts exposing resources to
+/// untrusted components can limit the number of live entries with
+/// [`ResourceTable::set_max_entries`] or [`ResourceTable::apply_limits`], in
+/// which case creating more resources fails with [`ResourceTableError::Full`].
+///
+/// When a table that still contains entries is dropped, for example along with
+/// the `Store` that owns it, a report of the outstanding entries is logged at
+/// the debug level to help track down leaked handles. The same report is
+/// available through [`ResourceTable::report`].
 #[derive(Debug)]
 pub struct ResourceTable {
     entries: Vec<Entry>,
     free_head: Option<usize>,
+    /// The number of occupied entries.
+    len: usize,
+    /// The maximum number of occupied entries, if limited.
+    max_entries: Option<usize>,
+    /// The number of occupied entries of each type, along with the type's
+    /// name.
+    counts: HashMap<TypeId, (&'static str, usize)>,
 }
 
 #[derive(Debug)]
@@ -70,6 +90,8 @@ impl Entry {
 struct TableEntry {
     /// The entry in the table, as a boxed dynamically-typed object
     entry: Box<dyn Any + Send + Sync>,
+    /// The name of the type of `entry`, used for diagnostics.
+    type_name: &'static str,
     /// The index of the parent of this entry, if it has one.
     parent: Option<u32>,
     /// The indicies of any children of this entry.
@@ -77,9 +99,13 @@ struct TableEntry {
 }
 
 impl TableEntry {
-    fn new(entry: Box<dyn Any + Send + Sync>, parent: Option<u32>) -> Self {
+    fn new<T>(entry: T, parent: Option<u32>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
         Self {
-            entry,
+            entry: Box::new(entry),
+            type_name: std::any::type_name::<T>(),
             parent,
             children: BTreeSet::new(),
         }
@@ -92,15 +118,15 @@ impl TableEntry {
         let was_removed = self.children.remove(&child);
         debug_assert!(was_removed);
     }
+    fn type_id(&self) -> TypeId {
+        (*self.entry).type_id()
+    }
 }
 
 impl ResourceTable {
     /// Create an empty table
     pub fn new() -> Self {
-        ResourceTable {
-            entries: Vec::new(),
-            free_head: None,
-        }
+        ResourceTable::with_capacity(0)
     }
 
     /// Create an empty table with at least the specified capacity.
@@ -108,6 +134,92 @@ impl ResourceTable {
         ResourceTable {
             entries: Vec::with_capacity(capacity),
             free_head: None,
+            len: 0,
+            max_entries: None,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Limits the number of entries which may be live in this table at once,
+    /// or removes the limit with `None`.
+    ///
+    /// Once the limit is reached [`ResourceTable::push`] and
+    /// [`ResourceTable::push_child`] return [`ResourceTableError::Full`],
+    /// which host functions typically propagate as a trap. Lowering the limit
+    /// below the current number of entries doesn't remove any entries.
+    pub fn set_max_entries(&mut self, max: Option<usize>) {
+        self.max_entries = max;
+    }
+
+    /// Returns the limit configured with [`ResourceTable::set_max_entries`].
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
+    /// Configures the limit on entries in this table from
+    /// [`ResourceLimiter::resource_table_entries`].
+    ///
+    /// This is useful to configure a table with the same limiter, such as
+    /// [`StoreLimits`](crate::StoreLimits), which is used for the `Store` that
+    /// the table is part of.
+    pub fn apply_limits(&mut self, limiter: &dyn ResourceLimiter) {
+        self.set_max_entries(limiter.resource_table_entries());
+    }
+
+    /// Returns the number of live entries in this table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether this table has no live entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of live entries in this table of type `T`.
+    pub fn count<T: Any>(&self) -> usize {
+        self.counts
+            .get(&TypeId::of::<T>())
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the name and number of live entries of each type with live
+    /// entries in this table.
+    pub fn counts(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.counts.values().copied()
+    }
+
+    /// Returns a human-readable report of all live entries in this table.
+    ///
+    /// Each entry is listed with its index and type, and children created
+    /// with [`ResourceTable::push_child`] are nested beneath their parent.
+    pub fn report(&self) -> String {
+        let mut report = format!("{} live resource(s)", self.len);
+        let mut counts = self.counts().collect::<Vec<_>>();
+        counts.sort();
+        for (name, count) in counts {
+            write!(report, "\n  {count} x {name}").unwrap();
+        }
+        for (index, entry) in self.entries.iter().enumerate() {
+            match entry.occupied() {
+                Some(entry) if entry.parent.is_none() => {
+                    self.report_entry(&mut report, index as u32, entry, 1)
+                }
+                _ => {}
+            }
+        }
+        report
+    }
+
+    fn report_entry(&self, report: &mut String, index: u32, entry: &TableEntry, depth: usize) {
+        let indent = "  ".repeat(depth);
+        write!(report, "\n{indent}[{index}] {}", entry.type_name).unwrap();
+        if !entry.children.is_empty() {
+            write!(report, " ({} child(ren))", entry.children.len()).unwrap();
+        }
+        for child in entry.children.iter() {
+            let child_entry = self.occupied(*child).expect("missing child");
+            self.report_entry(report, *child, child_entry, depth + 1);
         }
     }
 
@@ -117,7 +229,7 @@ impl ResourceTable {
     where
         T: Send + Sync + 'static,
     {
-        let idx = self.push_(TableEntry::new(Box::new(entry), None))?;
+        let idx = self.push_(TableEntry::new(entry, None))?;
         Ok(Resource::new_own(idx))
     }
 
@@ -148,6 +260,15 @@ impl ResourceTable {
         };
 
         self.free_head = Some(ix);
+        self.len -= 1;
+        if let std::collections::hash_map::Entry::Occupied(mut count) =
+            self.counts.entry(entry.type_id())
+        {
+            count.get_mut().1 -= 1;
+            if count.get().1 == 0 {
+                count.remove();
+            }
+        }
 
         entry
     }
@@ -155,9 +276,13 @@ impl ResourceTable {
     /// Push a new entry into the table, returning its handle. This will prefer to use free entries
     /// if they exist, falling back on pushing new entries onto the end of the table.
     fn push_(&mut self, e: TableEntry) -> Result<u32, ResourceTableError> {
-        if let Some(free) = self.pop_free_list() {
+        if self.max_entries.map_or(false, |max| self.len >= max) {
+            return Err(ResourceTableError::Full);
+        }
+        let (type_id, type_name) = (e.type_id(), e.type_name);
+        let ix = if let Some(free) = self.pop_free_list() {
             self.entries[free] = Entry::Occupied { entry: e };
-            Ok(free as u32)
+            free as u32
         } else {
             let ix = self
                 .entries
@@ -165,8 +290,11 @@ impl ResourceTable {
                 .try_into()
                 .map_err(|_| ResourceTableError::Full)?;
             self.entries.push(Entry::Occupied { entry: e });
-            Ok(ix)
-        }
+            ix
+        };
+        self.len += 1;
+        self.counts.entry(type_id).or_insert((type_name, 0)).1 += 1;
+        Ok(ix)
     }
 
     fn occupied(&self, key: u32) -> Result<&TableEntry, ResourceTableError> {
@@ -214,7 +342,7 @@ impl ResourceTable {
     {
         let parent = parent.rep();
         self.occupied(parent)?;
-        let child = self.push_(TableEntry::new(Box::new(entry), Some(parent)))?;
+        let child = self.push_(TableEntry::new(entry, Some(parent)))?;
         self.occupied_mut(parent)?.add_child(child);
         Ok(Resource::new_own(child))
     }
@@ -319,6 +447,17 @@ impl Default for ResourceTable {
     }
 }
 
+impl Drop for ResourceTable {
+    fn drop(&mut self) {
+        if !self.is_empty() && log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "resource table dropped with outstanding entries: {}",
+                self.report()
+            );
+        }
+    }
+}
+
 #[test]
 pub fn test_free_list() {
     let mut table = ResourceTable::new();
@@ -348,3 +487,64 @@ pub fn test_free_list() {
     let x = table.push(()).unwrap();
     assert_eq!(x.rep(), 2);
 }
+
+#[test]
+pub fn test_max_entries() {
+    let mut table = ResourceTable::new();
+    table.set_max_entries(Some(2));
+
+    let x = table.push(()).unwrap();
+    let y = table.push_child(1u32, &x).unwrap();
+    assert!(matches!(table.push(()), Err(ResourceTableError::Full)));
+    assert!(matches!(
+        table.push_child(2u32, &x),
+        Err(ResourceTableError::Full)
+    ));
+
+    // Deleting an entry makes room for another.
+    table.delete(y).unwrap();
+    table.push(()).unwrap();
+    assert_eq!(table.len(), 2);
+
+    table.set_max_entries(None);
+    table.push(()).unwrap();
+    assert_eq!(table.len(), 3);
+}
+
+#[test]
+pub fn test_counts_and_report() {
+    let mut table = ResourceTable::new();
+    let parent = table.push(String::from("parent")).unwrap();
+    let child = table.push_child(1u32, &parent).unwrap();
+    table.push(2u32).unwrap();
+
+    assert_eq!(table.count::<String>(), 1);
+    assert_eq!(table.count::<u32>(), 2);
+    assert_eq!(table.count::<()>(), 0);
+    let mut counts = table.counts().map(|(_, count)| count).collect::<Vec<_>>();
+    counts.sort();
+    assert_eq!(counts, [1, 2]);
+
+    // The report lists the number of entries of each type, followed by each
+    // entry with its children nested beneath it. The exact type names aren't
+    // stable, so only the structure is checked here.
+    let report = table.report();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6, "{report}");
+    assert_eq!(lines[0], "3 live resource(s)");
+    let mut count_lines = lines[1..3]
+        .iter()
+        .map(|line| line.split(" x ").next().unwrap())
+        .collect::<Vec<_>>();
+    count_lines.sort();
+    assert_eq!(count_lines, ["  1", "  2"], "{report}");
+    assert!(lines[3].starts_with("  [0] "), "{report}");
+    assert!(lines[3].ends_with(" (1 child(ren))"), "{report}");
+    assert!(lines[4].starts_with("    [1] "), "{report}");
+    assert!(lines[5].starts_with("  [2] "), "{report}");
+    assert!(!lines[5].contains("child"), "{report}");
+
+    table.delete(child).unwrap();
+    assert_eq!(table.count::<u32>(), 1);
+    assert_eq!(table.counts().count(), 2);
+}
@@ -9,7 +9,9 @@ use std::fmt;
 use std::marker;
 use std::mem::MaybeUninit;
 use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
-use wasmtime_environ::component::{CanonicalAbiInfo, DefinedResourceIndex, InterfaceType};
+use wasmtime_environ::component::{
+    CanonicalAbiInfo, ComponentTypes, DefinedResourceIndex, InterfaceType, ResourceIndex,
+};
 use wasmtime_runtime::component::{ComponentInstance, InstanceFlags, ResourceTables};
 use wasmtime_runtime::{SendSyncPtr, VMFuncRef, ValRaw};
 
@@ -65,6 +67,15 @@ impl ResourceType {
             },
         }
     }
+
+    pub(crate) fn uninstantiated(types: &ComponentTypes, index: ResourceIndex) -> ResourceType {
+        ResourceType {
+            kind: ResourceTypeKind::Uninstantiated {
+                component: types as *const _ as usize,
+                index,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -78,6 +89,14 @@ enum ResourceTypeKind {
         instance: usize,
         id: DefinedResourceIndex,
     },
+    /// A resource of a component which hasn't been instantiated, as seen in
+    /// the type returned by `Component::component_type`.
+    Uninstantiated {
+        // The `*const ComponentTypes` of the component, to distinguish the
+        // resources of different components.
+        component: usize,
+        index: ResourceIndex,
+    },
 }
 
 /// A host-defined resource in the component model.
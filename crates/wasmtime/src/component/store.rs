@@ -26,3 +26,18 @@ component_store_data! {
     funcs => crate::component::func::FuncData,
     instances => Option<Box<crate::component::instance::InstanceData>>,
 }
+
+impl ComponentStoreData {
+    /// Returns the state of all component instances within the store that
+    /// have finished instantiating.
+    ///
+    /// The state of a component instance lives in its `Instantiator` until
+    /// instantiation succeeds, so instances that are still instantiating are
+    /// not part of this.
+    #[cfg(feature = "coredump")]
+    pub(crate) fn instances(
+        &self,
+    ) -> impl Iterator<Item = &crate::component::instance::InstanceData> + '_ {
+        self.instances.iter().filter_map(|i| i.as_deref())
+    }
+}
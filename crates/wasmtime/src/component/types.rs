@@ -1,16 +1,20 @@
-//! This module defines the `Type` type, representing the dynamic form of a component interface type.
+//! This module defines the `Type` type, representing the dynamic form of a component interface type,
+//! and the types of components and their imports and exports.
 
 use crate::component::matching::InstanceType;
 use crate::component::values::{self, Val};
+use crate::{ExternType, FuncType};
 use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
 use std::fmt;
 use std::mem;
 use std::ops::Deref;
 use std::sync::Arc;
 use wasmtime_environ::component::{
-    CanonicalAbiInfo, ComponentTypes, InterfaceType, ResourceIndex, TypeEnumIndex, TypeFlagsIndex,
-    TypeListIndex, TypeOptionIndex, TypeRecordIndex, TypeResourceTableIndex, TypeResultIndex,
-    TypeTupleIndex, TypeVariantIndex,
+    CanonicalAbiInfo, ComponentTypes, InterfaceType, ResourceIndex, TypeComponentIndex,
+    TypeComponentInstanceIndex, TypeDef, TypeEnumIndex, TypeFlagsIndex, TypeFuncIndex,
+    TypeListIndex, TypeModuleIndex, TypeOptionIndex, TypeRecordIndex, TypeResourceTableIndex,
+    TypeResultIndex, TypeTupleIndex, TypeVariantIndex,
 };
 use wasmtime_environ::PrimaryMap;
 
@@ -89,6 +93,23 @@ impl<T: fmt::Debug> fmt::Debug for Handle<T> {
     }
 }
 
+/// Tests whether the tuple type `a` within `a_ty` is structurally equal to the
+/// tuple type `b` within `b_ty`, where resources are compared by identity.
+pub(crate) fn tuples_equal(
+    a: TypeTupleIndex,
+    a_ty: &InstanceType<'_>,
+    b: TypeTupleIndex,
+    b_ty: &InstanceType<'_>,
+) -> bool {
+    TypeChecker {
+        a_types: a_ty.types,
+        a_resource: a_ty.resources,
+        b_types: b_ty.types,
+        b_resource: b_ty.resources,
+    }
+    .tuples_equal(a, b)
+}
+
 /// Type checker between two `Handle`s
 struct TypeChecker<'a> {
     a_types: &'a ComponentTypes,
@@ -158,7 +179,10 @@ impl TypeChecker<'_> {
     fn resources_equal(&self, o1: TypeResourceTableIndex, o2: TypeResourceTableIndex) -> bool {
         let a = &self.a_types[o1];
         let b = &self.b_types[o2];
-        self.a_resource[a.ty] == self.b_resource[b.ty]
+        match (self.a_resource.get(a.ty), self.b_resource.get(b.ty)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
     }
 
     fn records_equal(&self, r1: TypeRecordIndex, r2: TypeRecordIndex) -> bool {
@@ -750,3 +774,229 @@ impl Type {
         }
     }
 }
+
+/// The type of a component: the names and types of its imports and exports.
+///
+/// This is returned by [`Component::component_type`] and describes a
+/// component before it's instantiated. Resources imported into or defined by
+/// the component are represented by [`ResourceType`]s which are unique to the
+/// component and don't compare equal to the resource types of its instances.
+///
+/// [`Component::component_type`]: crate::component::Component::component_type
+#[derive(Clone, Debug)]
+pub struct Component {
+    imports: Vec<(String, ComponentItem)>,
+    exports: Vec<(String, ComponentItem)>,
+}
+
+impl Component {
+    pub(crate) fn new(
+        imports: Vec<(String, ComponentItem)>,
+        exports: Vec<(String, ComponentItem)>,
+    ) -> Self {
+        Component { imports, exports }
+    }
+
+    fn from(index: TypeComponentIndex, ty: &InstanceType<'_>) -> Self {
+        let component = &ty.types[index];
+        Component {
+            imports: items(&component.imports, ty),
+            exports: items(&component.exports, ty),
+        }
+    }
+
+    /// Returns the imports of this component, in the order they're declared.
+    pub fn imports(&self) -> impl ExactSizeIterator<Item = (&str, &ComponentItem)> {
+        self.imports
+            .iter()
+            .map(|(name, item)| (name.as_str(), item))
+    }
+
+    /// Returns the exports of this component, in the order they're declared.
+    pub fn exports(&self) -> impl ExactSizeIterator<Item = (&str, &ComponentItem)> {
+        self.exports
+            .iter()
+            .map(|(name, item)| (name.as_str(), item))
+    }
+
+    /// Returns the import named `name`, if any.
+    pub fn get_import(&self, name: &str) -> Option<&ComponentItem> {
+        lookup(&self.imports, name)
+    }
+
+    /// Returns the export named `name`, if any.
+    pub fn get_export(&self, name: &str) -> Option<&ComponentItem> {
+        lookup(&self.exports, name)
+    }
+}
+
+/// The type of a component instance: the names and types of its exports.
+#[derive(Clone, Debug)]
+pub struct ComponentInstance {
+    exports: Vec<(String, ComponentItem)>,
+}
+
+impl ComponentInstance {
+    pub(crate) fn new(exports: Vec<(String, ComponentItem)>) -> Self {
+        ComponentInstance { exports }
+    }
+
+    fn from(index: TypeComponentInstanceIndex, ty: &InstanceType<'_>) -> Self {
+        ComponentInstance {
+            exports: items(&ty.types[index].exports, ty),
+        }
+    }
+
+    /// Returns the exports of this instance, in the order they're declared.
+    pub fn exports(&self) -> impl ExactSizeIterator<Item = (&str, &ComponentItem)> {
+        self.exports
+            .iter()
+            .map(|(name, item)| (name.as_str(), item))
+    }
+
+    /// Returns the export named `name`, if any.
+    pub fn get_export(&self, name: &str) -> Option<&ComponentItem> {
+        lookup(&self.exports, name)
+    }
+}
+
+/// The type of a component function.
+#[derive(Clone, Debug)]
+pub struct ComponentFunc {
+    params: Vec<(String, Type)>,
+    results: Vec<(Option<String>, Type)>,
+}
+
+impl ComponentFunc {
+    fn from(index: TypeFuncIndex, ty: &InstanceType<'_>) -> Self {
+        let func = &ty.types[index];
+        let params = ty.types[func.params]
+            .types
+            .iter()
+            .zip(&func.param_names)
+            .map(|(param, name)| (name.clone(), Type::from(param, ty)))
+            .collect();
+        let results = ty.types[func.results]
+            .types
+            .iter()
+            .enumerate()
+            .map(|(i, result)| (func.result_names.get(i).cloned(), Type::from(result, ty)))
+            .collect();
+        ComponentFunc { params, results }
+    }
+
+    /// Returns the names and types of the parameters of this function.
+    pub fn params(&self) -> impl ExactSizeIterator<Item = (&str, &Type)> {
+        self.params.iter().map(|(name, ty)| (name.as_str(), ty))
+    }
+
+    /// Returns the types of the results of this function, along with their
+    /// names if the results are named.
+    pub fn results(&self) -> impl ExactSizeIterator<Item = (Option<&str>, &Type)> {
+        self.results.iter().map(|(name, ty)| (name.as_deref(), ty))
+    }
+}
+
+/// The type of a core wasm module: the types of its imports and exports.
+#[derive(Clone, Debug)]
+pub struct Module {
+    imports: Vec<(String, String, ExternType)>,
+    exports: Vec<(String, ExternType)>,
+}
+
+impl Module {
+    pub(crate) fn from_module(module: &crate::Module) -> Self {
+        Module {
+            imports: module
+                .imports()
+                .map(|i| (i.module().to_string(), i.name().to_string(), i.ty()))
+                .collect(),
+            exports: module
+                .exports()
+                .map(|e| (e.name().to_string(), e.ty()))
+                .collect(),
+        }
+    }
+
+    fn from(index: TypeModuleIndex, ty: &InstanceType<'_>) -> Self {
+        let module = &ty.types[index];
+        let types = ty.types.module_types();
+        Module {
+            imports: module
+                .imports
+                .iter()
+                .map(|((module, name), entity)| {
+                    let ty = ExternType::from_wasmtime(types, entity);
+                    (module.clone(), name.clone(), ty)
+                })
+                .collect(),
+            exports: module
+                .exports
+                .iter()
+                .map(|(name, entity)| (name.clone(), ExternType::from_wasmtime(types, entity)))
+                .collect(),
+        }
+    }
+
+    /// Returns the module names, names and types of the imports of this
+    /// module.
+    pub fn imports(&self) -> impl ExactSizeIterator<Item = (&str, &str, &ExternType)> {
+        self.imports
+            .iter()
+            .map(|(module, name, ty)| (module.as_str(), name.as_str(), ty))
+    }
+
+    /// Returns the names and types of the exports of this module.
+    pub fn exports(&self) -> impl ExactSizeIterator<Item = (&str, &ExternType)> {
+        self.exports.iter().map(|(name, ty)| (name.as_str(), ty))
+    }
+}
+
+/// The type of an item imported or exported by a component.
+#[derive(Clone, Debug)]
+pub enum ComponentItem {
+    /// A component function.
+    ComponentFunc(ComponentFunc),
+    /// A core wasm function using only core wasm types.
+    CoreFunc(FuncType),
+    /// A core wasm module.
+    Module(Module),
+    /// A component.
+    Component(Component),
+    /// A component instance.
+    ComponentInstance(ComponentInstance),
+    /// An interface type.
+    Type(Type),
+    /// A resource type.
+    Resource(ResourceType),
+}
+
+impl ComponentItem {
+    pub(crate) fn from(def: &TypeDef, ty: &InstanceType<'_>) -> Self {
+        match def {
+            TypeDef::Component(index) => ComponentItem::Component(Component::from(*index, ty)),
+            TypeDef::ComponentInstance(index) => {
+                ComponentItem::ComponentInstance(ComponentInstance::from(*index, ty))
+            }
+            TypeDef::ComponentFunc(index) => {
+                ComponentItem::ComponentFunc(ComponentFunc::from(*index, ty))
+            }
+            TypeDef::Interface(iface) => ComponentItem::Type(Type::from(iface, ty)),
+            TypeDef::Module(index) => ComponentItem::Module(Module::from(*index, ty)),
+            TypeDef::CoreFunc(index) => ComponentItem::CoreFunc(FuncType::from_wasm_func_type(
+                ty.types.module_types()[*index].clone(),
+            )),
+            TypeDef::Resource(index) => ComponentItem::Resource(ty.resource_type(*index)),
+        }
+    }
+}
+
+fn items(defs: &IndexMap<String, TypeDef>, ty: &InstanceType<'_>) -> Vec<(String, ComponentItem)> {
+    defs.iter()
+        .map(|(name, def)| (name.clone(), ComponentItem::from(def, ty)))
+        .collect()
+}
+
+fn lookup<'a>(items: &'a [(String, ComponentItem)], name: &str) -> Option<&'a ComponentItem> {
+    items.iter().find(|(n, _)| n == name).map(|(_, item)| item)
+}
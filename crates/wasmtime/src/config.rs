@@ -21,7 +21,7 @@ use crate::stack::{StackCreator, StackCreatorProxy};
 #[cfg(feature = "async")]
 use wasmtime_fiber::RuntimeFiberStackCreator;
 
-pub use wasmtime_environ::CacheStore;
+pub use wasmtime_environ::{CacheStore, FuelCostModel};
 pub use wasmtime_runtime::MpkEnabled;
 
 /// Represents the module instance allocation strategy to use.
@@ -57,7 +57,7 @@ impl Default for InstanceAllocationStrategy {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 /// Configure the strategy used for versioning in serializing and deserializing [`crate::Module`].
 pub enum ModuleVersionStrategy {
     /// Use the wasmtime crate's Cargo package version.
@@ -121,6 +121,7 @@ pub struct Config {
     pub(crate) wmemcheck: bool,
     pub(crate) coredump_on_trap: bool,
     pub(crate) macos_use_mach_ports: bool,
+    pub(crate) deterministic: bool,
 }
 
 /// User-provided configuration for the compiler.
@@ -217,6 +218,7 @@ impl Config {
             wmemcheck: false,
             coredump_on_trap: false,
             macos_use_mach_ports: true,
+            deterministic: false,
         };
         #[cfg(any(feature = "cranelift", feature = "winch"))]
         {
@@ -472,6 +474,58 @@ impl Config {
         self
     }
 
+    /// Configures how much fuel each WebAssembly operator consumes when
+    /// [`Config::consume_fuel`] is enabled.
+    ///
+    /// The [`FuelCostModel`] assigns a cost to classes of operators such as
+    /// calls, loads, stores and branches. Bulk memory and table operations,
+    /// such as `memory.copy` or `table.fill`, can additionally be charged in
+    /// proportion to the number of bytes or elements they process.
+    ///
+    /// Host functions defined with [`Linker::func_wrap_with_fuel`] or
+    /// [`Linker::func_new_with_fuel`] can also declare their own cost.
+    ///
+    /// Modules compiled with one cost model can't be deserialized into an
+    /// engine configured with a different one.
+    ///
+    /// By default [`FuelCostModel::default`] is used, which charges one unit
+    /// of fuel for most operators. This is the only model supported by
+    /// [`Strategy::Winch`], and configuring another one with it makes engine
+    /// creation fail.
+    ///
+    /// [`Linker::func_wrap_with_fuel`]: crate::Linker::func_wrap_with_fuel
+    /// [`Linker::func_new_with_fuel`]: crate::Linker::func_new_with_fuel
+    pub fn fuel_cost_model(&mut self, model: FuelCostModel) -> &mut Self {
+        self.tunables.fuel_cost_model = model;
+        self
+    }
+
+    /// Configures whether generated code counts how many times each basic
+    /// block of WebAssembly executes, for measuring code coverage.
+    ///
+    /// When enabled each function increments a counter owned by the
+    /// [`Store`] whenever control enters one of its basic blocks, and the
+    /// counts can be read back with [`Store::coverage`]. A [`Coverage`]
+    /// report can then be written in the `lcov` format with
+    /// [`Coverage::write_lcov`], mapping counts back to source lines through
+    /// the DWARF debugging information in the WebAssembly module. That
+    /// information is only retained when [`Config::wasm_backtrace_details`]
+    /// is enabled.
+    ///
+    /// This isn't supported by [`Strategy::Winch`], and enabling it with
+    /// Winch makes engine creation fail.
+    ///
+    /// By default this option is `false`.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::coverage`]: crate::Store::coverage
+    /// [`Coverage`]: crate::Coverage
+    /// [`Coverage::write_lcov`]: crate::Coverage::write_lcov
+    pub fn coverage_instrumentation(&mut self, enable: bool) -> &mut Self {
+        self.tunables.coverage_instrumentation = enable;
+        self
+    }
+
     /// Enables epoch-based interruption.
     ///
     /// When executing code in async mode, we sometimes want to
@@ -727,6 +781,10 @@ impl Config {
     /// below to be available on the target platform. Compilation will fail if
     /// the compile target does not include SSE 4.2.
     ///
+    /// [`Strategy::Winch`] doesn't support this proposal on AArch64 yet, so
+    /// it must be disabled, along with [`Config::wasm_relaxed_simd`], to
+    /// compile with Winch for that target.
+    ///
     /// This is `true` by default.
     ///
     /// [proposal]: https://github.com/webassembly/simd
@@ -784,6 +842,41 @@ impl Config {
         self
     }
 
+    /// Configures whether execution of WebAssembly must be bit-for-bit
+    /// deterministic across hosts and runs.
+    ///
+    /// This is a convenience for embeddings which record and replay guest
+    /// executions. Enabling it configures, and requires, the following:
+    ///
+    /// * [`Config::cranelift_nan_canonicalization`] is enabled so NaN bit
+    ///   patterns don't depend on the host architecture.
+    /// * [`Config::relaxed_simd_deterministic`] is enabled so relaxed SIMD
+    ///   instructions always have the same behavior.
+    /// * [`Config::wasm_threads`] is disabled since shared memories allow
+    ///   observing the interleaving of host threads.
+    /// * The compilation [`Strategy`] must not be [`Strategy::Winch`], which
+    ///   doesn't canonicalize NaNs.
+    ///
+    /// If any of these settings are changed back after this option is enabled
+    /// then [`Engine::new`](crate::Engine::new) will return an error.
+    ///
+    /// Note that this only covers code generated by Wasmtime. Host functions,
+    /// including WASI, must be configured separately to be deterministic, for
+    /// example with the `deterministic` mode of the `WasiCtxBuilder` in the
+    /// `wasmtime-wasi` crate.
+    ///
+    /// This is `false` by default.
+    pub fn deterministic(&mut self, enable: bool) -> &mut Self {
+        self.deterministic = enable;
+        if enable {
+            self.features.threads = false;
+            self.tunables.relaxed_simd_deterministic = true;
+            #[cfg(any(feature = "cranelift", feature = "winch"))]
+            self.cranelift_nan_canonicalization(true);
+        }
+        self
+    }
+
     /// Configures whether the [WebAssembly bulk memory operations
     /// proposal][proposal] will be enabled for compilation.
     ///
@@ -977,6 +1070,78 @@ impl Config {
         self
     }
 
+    /// Configures whether Cranelift removes redundant bounds checks on
+    /// linear memory accesses.
+    ///
+    /// Memories that can't rely on guard pages, such as dynamic memories
+    /// (see [`Config::static_memory_forced`] and
+    /// [`Config::static_memory_maximum_size`]) and 64-bit memories, need an
+    /// explicit bounds check on every access. With this option the
+    /// checks are annotated with the same facts used by
+    /// [`Config::cranelift_pcc`], and Cranelift uses them, along with a range
+    /// analysis of the accessed indices, to remove checks that can never
+    /// fail or that are implied by an earlier check on the same index.
+    ///
+    /// Spectre guards on memory accesses are only removed when the access
+    /// is statically in bounds, and not at all when PCC is enabled so that
+    /// the remaining accesses can still be verified.
+    ///
+    /// This option has no effect with the Winch compiler.
+    ///
+    /// The default value for this is `false`.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    #[cfg_attr(nightlydoc, doc(cfg(any(feature = "cranelift", feature = "winch"))))]
+    pub fn cranelift_bounds_check_elimination(&mut self, enable: bool) -> &mut Self {
+        let val = if enable { "true" } else { "false" };
+        self.compiler_config.settings.insert(
+            "enable_bounds_check_elimination".to_string(),
+            val.to_string(),
+        );
+        self
+    }
+
+    /// Configures whether Cranelift inlines small functions into their
+    /// callers.
+    ///
+    /// Cranelift compiles every wasm function on its own, so calls to small
+    /// helper functions, such as the accessors and wrappers that toolchains
+    /// emit in large numbers, always pay the full cost of a call. When this
+    /// option is enabled, direct `call` instructions to functions defined in
+    /// the same module whose body is small enough are replaced with a copy of
+    /// the callee, which is then optimized along with the caller. Calls made
+    /// from an inlined body are not themselves inlined.
+    ///
+    /// Inlined functions do not have frames of their own, so they will not
+    /// show up in wasm backtraces, profiles or core dumps: their code is
+    /// attributed to the call site in the caller instead. For this reason
+    /// inlining is not performed when native debug information is
+    /// generated, see [`Config::debug_info`].
+    ///
+    /// This option has no effect with the Winch compiler.
+    ///
+    /// The default value for this is `false`.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    #[cfg_attr(nightlydoc, doc(cfg(any(feature = "cranelift", feature = "winch"))))]
+    pub fn cranelift_inlining(&mut self, enable: bool) -> &mut Self {
+        self.tunables.inlining = enable;
+        self
+    }
+
+    /// Configures the maximum size of a function, in bytes of its wasm
+    /// function body, for it to be inlined into its callers when
+    /// [`Config::cranelift_inlining`] is enabled.
+    ///
+    /// Larger values inline more calls, at the price of larger and slower to
+    /// compile callers.
+    ///
+    /// The default value for this is `64`.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    #[cfg_attr(nightlydoc, doc(cfg(any(feature = "cranelift", feature = "winch"))))]
+    pub fn inlining_max_callee_size(&mut self, size: u32) -> &mut Self {
+        self.tunables.inlining_max_callee_size = size;
+        self
+    }
+
     /// Allows setting a Cranelift boolean flag or preset. This allows
     /// fine-tuning of Cranelift settings.
     ///
@@ -1524,6 +1689,52 @@ impl Config {
         self
     }
 
+    /// Configures whether generated code keeps copies of wasm locals in its
+    /// stack frames so that coredumps can include their values.
+    ///
+    /// Without this option the locals of each frame in a
+    /// [`WasmCoreDump`](crate::WasmCoreDump) are not recovered. With it each
+    /// function stores its locals to a dedicated area of its stack frame on
+    /// entry and whenever a local is set, which costs some performance, and
+    /// functions aren't inlined into their callers so that every frame
+    /// remains visible. `v128` and reference-typed locals are not recovered,
+    /// and neither are the values on each frame's operand stack.
+    ///
+    /// This is only supported by [`Strategy::Cranelift`] on x86_64, AArch64
+    /// and riscv64, and enabling it otherwise makes engine creation fail.
+    ///
+    /// This option is disabled by default.
+    #[cfg(feature = "coredump")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "coredump")))]
+    pub fn coredump_locals(&mut self, enable: bool) -> &mut Self {
+        self.tunables.coredump_locals = enable;
+        self
+    }
+
+    /// Configures whether generated code can stop at breakpoints and
+    /// single-step for a debugger.
+    ///
+    /// With this option each wasm instruction is preceded by a check of
+    /// whether the store has any breakpoints or is single-stepping, in which
+    /// case the callback registered with [`Store::debug_callback`] is invoked
+    /// with the state of the guest. Locals are kept in stack frames as with
+    /// [`Config::coredump_locals`] and functions aren't inlined into their
+    /// callers. This makes generated code significantly slower even when no
+    /// breakpoints are set.
+    ///
+    /// This is only supported by [`Strategy::Cranelift`] on x86_64, AArch64
+    /// and riscv64, and enabling it otherwise makes engine creation fail.
+    ///
+    /// This option is disabled by default.
+    ///
+    /// [`Store::debug_callback`]: crate::Store::debug_callback
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn debug_breakpoints(&mut self, enable: bool) -> &mut Self {
+        self.tunables.debug_breakpoints = enable;
+        self
+    }
+
     /// Enables memory error checking for wasm programs.
     ///
     /// This option is disabled by default.
@@ -1593,6 +1804,66 @@ impl Config {
         {
             bail!("static memory guard size cannot be smaller than dynamic memory guard size");
         }
+        if self.deterministic {
+            if self.features.threads {
+                bail!("feature 'threads' cannot be enabled in deterministic mode");
+            }
+            if !self.tunables.relaxed_simd_deterministic {
+                bail!("deterministic mode requires 'relaxed_simd_deterministic' to be enabled");
+            }
+            // Winch doesn't implement NaN canonicalization.
+            #[cfg(any(feature = "cranelift", feature = "winch"))]
+            if self.compiler_config.strategy == Strategy::Winch {
+                bail!("the Winch compiler cannot be used in deterministic mode");
+            }
+        }
+        // Winch hardcodes the default cost of each operator.
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.compiler_config.strategy == Strategy::Winch
+            && self.tunables.fuel_cost_model != FuelCostModel::default()
+        {
+            bail!("the Winch compiler only supports the default fuel cost model");
+        }
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.compiler_config.strategy == Strategy::Winch
+            && self.tunables.coverage_instrumentation
+        {
+            bail!("the Winch compiler doesn't support coverage instrumentation");
+        }
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.compiler_config.strategy == Strategy::Winch && self.tunables.debug_breakpoints {
+            bail!("the Winch compiler doesn't support debug breakpoints");
+        }
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.compiler_config.strategy == Strategy::Winch && self.tunables.coredump_locals {
+            bail!("the Winch compiler doesn't support recording locals in coredumps");
+        }
+        // Locals are found relative to the frame pointer, whose position in
+        // the frame is only known for these targets.
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.tunables.coredump_locals || self.tunables.debug_breakpoints {
+            let host = target_lexicon::Triple::host();
+            let target = self.compiler_config.target.as_ref().unwrap_or(&host);
+            match target.architecture {
+                Architecture::X86_64 | Architecture::Aarch64(_) | Architecture::Riscv64(_) => {}
+                arch => bail!(
+                    "recording locals in coredumps and debug breakpoints aren't \
+                     supported on {arch}"
+                ),
+            }
+        }
+        #[cfg(any(feature = "cranelift", feature = "winch"))]
+        if self.compiler_config.strategy == Strategy::Winch && self.features.simd {
+            let host = target_lexicon::Triple::host();
+            let target = self.compiler_config.target.as_ref().unwrap_or(&host);
+            if let Architecture::Aarch64(_) = target.architecture {
+                bail!(
+                    "the Winch compiler doesn't support SIMD on AArch64, \
+                     disable it with `Config::wasm_simd(false)` and \
+                     `Config::wasm_relaxed_simd(false)`"
+                );
+            }
+        }
         #[cfg(not(feature = "wmemcheck"))]
         if self.wmemcheck {
             bail!("wmemcheck (memory checker) was requested but is not enabled in this build");
@@ -1736,6 +2007,14 @@ impl Config {
             }
         }
 
+        if self.deterministic
+            && !self
+                .compiler_config
+                .ensure_setting_unset_or_given("enable_nan_canonicalization", "true")
+        {
+            bail!("compiler option 'enable_nan_canonicalization' must be enabled in deterministic mode");
+        }
+
         if self.features.relaxed_simd && !self.features.simd {
             bail!("cannot disable the simd proposal but enable the relaxed simd proposal");
         }
@@ -1768,6 +2047,20 @@ impl Config {
     }
 
     /// Enables clif output when compiling a WebAssembly module.
+    ///
+    /// For each function `N` compiled with Cranelift, this writes to `path`:
+    ///
+    /// * `wasm_func_N.clif`: the CLIF the function was translated to.
+    /// * `wasm_func_N.opt.clif`: the optimized CLIF that was lowered to
+    ///   machine code.
+    /// * `wasm_func_N.isle`: only if the `machine_code_isle_rule_info`
+    ///   Cranelift flag is enabled, and Cranelift was built with its
+    ///   `isle-rule-info` feature. One line per range of machine code, in
+    ///   order, of the form `start end lowering|inner`: `start` and `end` are
+    ///   decimal offsets from the start of the function, `end` exclusive, and
+    ///   `lowering` and `inner` are the ISLE rules that lowered the CLIF
+    ///   instruction and that built the machine instructions, such as
+    ///   `lower.isle line 12`.
     #[cfg(any(feature = "cranelift", feature = "winch"))]
     pub fn emit_clif(&mut self, path: &Path) -> &mut Self {
         self.compiler_config.clif_dir = Some(path.to_path_buf());
@@ -1853,7 +2146,8 @@ impl fmt::Debug for Config {
                 "guard_before_linear_memory",
                 &self.tunables.guard_before_linear_memory,
             )
-            .field("parallel_compilation", &self.parallel_compilation);
+            .field("parallel_compilation", &self.parallel_compilation)
+            .field("deterministic", &self.deterministic);
         #[cfg(any(feature = "cranelift", feature = "winch"))]
         {
             f.field("compiler_config", &self.compiler_config);
@@ -1,4 +1,5 @@
 use std::{collections::HashMap, fmt};
+use wasm_encoder::CoreDumpValue;
 
 use crate::{
     store::StoreOpaque, AsContextMut, FrameInfo, Global, Instance, Memory, Module, StoreContextMut,
@@ -15,9 +16,9 @@ use crate::{
 /// error returned this will get printed along with the rest of the error when
 /// the error is logged.
 ///
-/// Note that some state, such as Wasm locals or values on the operand stack,
-/// may be optimized away by the compiler or otherwise not recovered in the
-/// coredump.
+/// Note that values on the operand stack are not recovered in the coredump,
+/// and Wasm locals are only recovered for functions compiled with
+/// [`Config::coredump_locals`][crate::Config::coredump_locals] enabled.
 ///
 /// Capturing of wasm coredumps can be configured through the
 /// [`Config::coredump_on_trap`][crate::Config::coredump_on_trap] method.
@@ -34,14 +35,46 @@ pub struct WasmCoreDump {
     memories: Vec<Memory>,
     globals: Vec<Global>,
     backtrace: WasmBacktrace,
+    locals: Vec<Vec<Option<Val>>>,
+    #[cfg(feature = "component-model")]
+    components: Vec<crate::component::ComponentCoreDump>,
 }
 
 impl WasmCoreDump {
-    pub(crate) fn new(store: &mut StoreOpaque, backtrace: WasmBacktrace) -> WasmCoreDump {
+    pub(crate) fn new(
+        store: &mut StoreOpaque,
+        backtrace: WasmBacktrace,
+        locals: Vec<Vec<CoreDumpValue>>,
+    ) -> WasmCoreDump {
         let modules: Vec<_> = store.modules().all_modules().cloned().collect();
         let instances: Vec<Instance> = store.all_instances().collect();
         let store_memories: Vec<Memory> = store.all_memories().collect();
         let store_globals: Vec<Global> = store.all_globals().collect();
+        let locals = locals
+            .into_iter()
+            .map(|frame| {
+                frame
+                    .into_iter()
+                    .map(|value| match value {
+                        CoreDumpValue::I32(x) => Some(Val::I32(x)),
+                        CoreDumpValue::I64(x) => Some(Val::I64(x)),
+                        CoreDumpValue::F32(x) => Some(Val::F32(x.to_bits())),
+                        CoreDumpValue::F64(x) => Some(Val::F64(x.to_bits())),
+                        CoreDumpValue::Missing => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        #[cfg(feature = "component-model")]
+        let components = {
+            let store: &StoreOpaque = store;
+            store
+                .store_data()
+                .components
+                .instances()
+                .map(|data| crate::component::ComponentCoreDump::new(store, data, &instances))
+                .collect()
+        };
 
         WasmCoreDump {
             name: String::from("store_name"),
@@ -50,6 +83,9 @@ impl WasmCoreDump {
             memories: store_memories,
             globals: store_globals,
             backtrace,
+            locals,
+            #[cfg(feature = "component-model")]
+            components,
         }
     }
 
@@ -61,6 +97,17 @@ impl WasmCoreDump {
         self.backtrace.frames()
     }
 
+    /// The Wasm locals of each of the [stack frames][WasmCoreDump::frames],
+    /// in the same order as the frames.
+    ///
+    /// Locals are only recorded for functions compiled with
+    /// [`Config::coredump_locals`][crate::Config::coredump_locals] enabled, so
+    /// other frames have no locals. A local is `None` if its value couldn't be
+    /// recovered, which is currently the case for `v128` and reference locals.
+    pub fn frame_locals(&self) -> &[Vec<Option<Val>>] {
+        self.locals.as_ref()
+    }
+
     /// All modules instantiated inside the store when the core dump was
     /// created.
     pub fn modules(&self) -> &[Module] {
@@ -84,6 +131,20 @@ impl WasmCoreDump {
         self.memories.as_ref()
     }
 
+    /// All component instances within the store when the core dump was
+    /// created, recording which of the [instances][WasmCoreDump::instances]
+    /// each of them created.
+    ///
+    /// Only component instances which finished instantiating are recorded.
+    /// If the trap happened while instantiating a component, for example in
+    /// the start function of one of its core modules, the core instances it
+    /// created so far are part of [`WasmCoreDump::instances`] but there is no
+    /// entry for the component instance itself.
+    #[cfg(feature = "component-model")]
+    pub fn components(&self) -> &[crate::component::ComponentCoreDump] {
+        self.components.as_ref()
+    }
+
     /// Serialize this core dump into [the standard core dump binary
     /// format][spec].
     ///
@@ -94,6 +155,10 @@ impl WasmCoreDump {
     /// network, or pass it to other debugging tools that consume Wasm core
     /// dumps.
     ///
+    /// If the store contains component instances then their structure is
+    /// additionally recorded in a Wasmtime-specific `corecomponents` custom
+    /// section, which other tools will ignore.
+    ///
     /// [spec]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
     pub fn serialize(&self, mut store: impl AsContextMut, name: &str) -> Vec<u8> {
         let store = store.as_context_mut();
@@ -249,7 +314,7 @@ impl WasmCoreDump {
         {
             let thread_name = "main";
             let mut stack = wasm_encoder::CoreDumpStackSection::new(thread_name);
-            for frame in self.frames() {
+            for (frame, locals) in self.frames().iter().zip(self.frame_locals()) {
                 // This isn't necessarily the right instance if there are
                 // multiple instances of the same module. See comment above
                 // `module_to_instance` for details.
@@ -262,9 +327,15 @@ impl WasmCoreDump {
                     .and_then(|o| u32::try_from(o).ok())
                     .unwrap_or(0);
 
-                // We can't currently recover locals and the operand stack. We
-                // should eventually be able to do that with Winch though.
-                let locals = [];
+                let locals = locals.iter().map(|local| match local {
+                    Some(Val::I32(x)) => CoreDumpValue::I32(*x),
+                    Some(Val::I64(x)) => CoreDumpValue::I64(*x),
+                    Some(Val::F32(x)) => CoreDumpValue::F32(f32::from_bits(*x)),
+                    Some(Val::F64(x)) => CoreDumpValue::F64(f64::from_bits(*x)),
+                    _ => CoreDumpValue::Missing,
+                });
+
+                // We can't currently recover the operand stack.
                 let operand_stack = [];
 
                 stack.frame(instance, func, offset, locals, operand_stack);
@@ -272,8 +343,70 @@ impl WasmCoreDump {
             core_dump.section(&stack);
         }
 
+        #[cfg(feature = "component-model")]
+        if !self.components.is_empty() {
+            core_dump.section(&wasm_encoder::CustomSection {
+                name: "corecomponents".into(),
+                data: self.encode_components().into(),
+            });
+        }
+
         core_dump.finish()
     }
+
+    /// Encodes the `corecomponents` custom section's contents:
+    ///
+    /// ```text
+    /// corecomponents ::= vec(component)
+    /// component      ::= vec(parent) vec(coreinstance) vec(resourcetable)
+    /// parent         ::= 0x00 | 0x01 componentinstanceidx:u32
+    /// coreinstance   ::= instanceidx:u32 (0x00 | 0x01 componentinstanceidx:u32)
+    /// resourcetable  ::= componentinstanceidx:u32 resourceidx:u32 vec(handle)
+    /// handle         ::= handleidx:u32 rep:u32 (0x00 | 0x01)
+    /// ```
+    ///
+    /// where a core instance without a component instance is an adapter
+    /// module, and handles are `own` (0x00) or `borrow` (0x01) handles.
+    #[cfg(feature = "component-model")]
+    fn encode_components(&self) -> Vec<u8> {
+        use wasm_encoder::Encode;
+
+        fn option(data: &mut Vec<u8>, value: Option<usize>) {
+            match value {
+                Some(value) => {
+                    data.push(0x01);
+                    (value as u32).encode(data);
+                }
+                None => data.push(0x00),
+            }
+        }
+
+        let mut data = Vec::new();
+        self.components.len().encode(&mut data);
+        for component in self.components.iter() {
+            component.component_instances().len().encode(&mut data);
+            for parent in component.component_instances() {
+                option(&mut data, *parent);
+            }
+            component.core_instances().len().encode(&mut data);
+            for instance in component.core_instances() {
+                instance.instance().encode(&mut data);
+                option(&mut data, instance.component_instance());
+            }
+            component.resource_tables().len().encode(&mut data);
+            for table in component.resource_tables() {
+                table.component_instance().encode(&mut data);
+                table.resource().encode(&mut data);
+                table.handles().len().encode(&mut data);
+                for handle in table.handles() {
+                    handle.index().encode(&mut data);
+                    handle.rep().encode(&mut data);
+                    data.push(if handle.is_own() { 0x00 } else { 0x01 });
+                }
+            }
+        }
+        data
+    }
 }
 
 impl fmt::Display for WasmCoreDump {
@@ -299,6 +432,26 @@ impl fmt::Display for WasmCoreDump {
             writeln!(f, "  {:?}", global)?;
         }
 
+        #[cfg(feature = "component-model")]
+        if !self.components.is_empty() {
+            writeln!(f, "components:")?;
+            for (i, component) in self.components.iter().enumerate() {
+                writeln!(f, "  component {i}:")?;
+                for instance in component.core_instances() {
+                    match instance.component_instance() {
+                        Some(c) => writeln!(
+                            f,
+                            "    {:?} (component instance {c})",
+                            self.instances[instance.instance()]
+                        )?,
+                        None => {
+                            writeln!(f, "    {:?} (adapter)", self.instances[instance.instance()])?
+                        }
+                    }
+                }
+            }
+        }
+
         writeln!(f, "backtrace:")?;
         write!(f, "{}", self.backtrace)?;
 
@@ -0,0 +1,230 @@
+use crate::Module;
+use anyhow::Result;
+use std::cell::UnsafeCell;
+use std::collections::BTreeMap;
+use std::io::Write;
+use wasmtime_environ::DefinedFuncIndex;
+
+/// Execution counters for the modules instantiated within a store, written to
+/// by code compiled with
+/// [`Config::coverage_instrumentation`](crate::Config::coverage_instrumentation).
+#[derive(Default)]
+pub(crate) struct CoverageCounters {
+    modules: Vec<ModuleCounters>,
+}
+
+struct ModuleCounters {
+    module: Module,
+    /// One array of counters for each defined function, in the order of the
+    /// function's `WasmFunctionInfo::coverage` regions.
+    counters: Box<[Box<[UnsafeCell<u64>]>]>,
+    /// The table of pointers into `counters` that instances of `module` have
+    /// in their vmctx.
+    table: Box<[*mut u64]>,
+}
+
+// Safety: the counters are only written by wasm running within the store that
+// owns this structure, so they're accessed the same way as the rest of the
+// store's state.
+unsafe impl Send for ModuleCounters {}
+unsafe impl Sync for ModuleCounters {}
+
+impl CoverageCounters {
+    /// Returns the table of counters that instances of `module` should use,
+    /// or `None` if `module` wasn't compiled with coverage instrumentation.
+    pub(crate) fn table_for(&mut self, module: &Module) -> Option<*const *mut u64> {
+        if let Some(m) = self
+            .modules
+            .iter()
+            .find(|m| Module::same(&m.module, module))
+        {
+            return Some(m.table.as_ptr());
+        }
+
+        let compiled_module = module.compiled_module();
+        let counters = compiled_module
+            .finished_functions()
+            .map(|(index, _)| {
+                let regions = compiled_module.wasm_func_info(index).coverage.len();
+                (0..regions).map(|_| UnsafeCell::new(0)).collect()
+            })
+            .collect::<Box<[Box<[UnsafeCell<u64>]>]>>();
+        if counters.iter().all(|c| c.is_empty()) {
+            return None;
+        }
+        let table = counters
+            .iter()
+            .map(|c| UnsafeCell::raw_get(c.as_ptr()))
+            .collect::<Box<[_]>>();
+        let ret = table.as_ptr();
+        self.modules.push(ModuleCounters {
+            module: module.clone(),
+            counters,
+            table,
+        });
+        Some(ret)
+    }
+
+    pub(crate) fn report(&self) -> Coverage {
+        let modules = self
+            .modules
+            .iter()
+            .map(|m| {
+                let compiled_module = m.module.compiled_module();
+                let mut blocks = Vec::new();
+                for (i, counters) in m.counters.iter().enumerate() {
+                    let def_index = DefinedFuncIndex::from_u32(i as u32);
+                    let func_index = compiled_module.module().func_index(def_index);
+                    let regions = &compiled_module.wasm_func_info(def_index).coverage;
+                    for (region, count) in regions.iter().zip(counters.iter()) {
+                        let (Some(start), Some(end)) =
+                            (region.start.file_offset(), region.end.file_offset())
+                        else {
+                            continue;
+                        };
+                        blocks.push(CoverageBlock {
+                            func_index: func_index.as_u32(),
+                            start,
+                            end,
+                            count: unsafe { *count.get() },
+                        });
+                    }
+                }
+                ModuleCoverage {
+                    module: m.module.clone(),
+                    blocks,
+                }
+            })
+            .collect();
+        Coverage { modules }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        for counters in self.modules.iter_mut().flat_map(|m| m.counters.iter_mut()) {
+            for count in counters.iter_mut() {
+                *count.get_mut() = 0;
+            }
+        }
+    }
+}
+
+/// A snapshot of how many times each basic block of wasm code ran within a
+/// [`Store`](crate::Store).
+///
+/// Coverage is only recorded for modules compiled with
+/// [`Config::coverage_instrumentation`](crate::Config::coverage_instrumentation)
+/// enabled, and is created with [`Store::coverage`](crate::Store::coverage).
+#[derive(Debug)]
+pub struct Coverage {
+    modules: Vec<ModuleCoverage>,
+}
+
+impl Coverage {
+    /// Returns the coverage of each module instantiated within the store, in
+    /// the order they were first instantiated.
+    pub fn modules(&self) -> &[ModuleCoverage] {
+        &self.modules
+    }
+
+    /// Writes this coverage in the `lcov` tracefile format.
+    ///
+    /// Blocks are mapped back to source lines through the DWARF debugging
+    /// information embedded in each module, and a line's count is the largest
+    /// count of the blocks covering it. Modules without DWARF are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a module's DWARF can't be parsed or if writing to
+    /// `out` fails.
+    pub fn write_lcov(&self, mut out: impl Write) -> Result<()> {
+        #[cfg_attr(not(feature = "addr2line"), allow(unused_mut))]
+        let mut files = BTreeMap::<String, BTreeMap<u32, u64>>::new();
+        #[cfg(feature = "addr2line")]
+        for module in self.modules.iter() {
+            let compiled_module = module.module.compiled_module();
+            let Some(cx) = compiled_module.symbolize_context()? else {
+                continue;
+            };
+            for block in module.blocks.iter() {
+                let start = u64::from(block.start) - cx.code_section_offset();
+                let end = u64::from(block.end.max(block.start + 1)) - cx.code_section_offset();
+                for (_, _, location) in cx.addr2line().find_location_range(start, end)? {
+                    let (Some(file), Some(line)) = (location.file, location.line) else {
+                        continue;
+                    };
+                    let count = files
+                        .entry(file.to_string())
+                        .or_default()
+                        .entry(line)
+                        .or_default();
+                    *count = (*count).max(block.count);
+                }
+            }
+        }
+
+        for (file, lines) in files.iter() {
+            writeln!(out, "SF:{file}")?;
+            for (line, count) in lines.iter() {
+                writeln!(out, "DA:{line},{count}")?;
+            }
+            writeln!(out, "LH:{}", lines.values().filter(|c| **c > 0).count())?;
+            writeln!(out, "LF:{}", lines.len())?;
+            writeln!(out, "end_of_record")?;
+        }
+        Ok(())
+    }
+}
+
+/// The coverage of a single [`Module`] within a [`Coverage`] report.
+#[derive(Debug)]
+pub struct ModuleCoverage {
+    module: Module,
+    blocks: Vec<CoverageBlock>,
+}
+
+impl ModuleCoverage {
+    /// Returns the module this coverage was recorded for.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Returns the counted blocks of this module, ordered by function and
+    /// then by position within the function.
+    pub fn blocks(&self) -> &[CoverageBlock] {
+        &self.blocks
+    }
+}
+
+/// A straight-line range of wasm code along with how many times it started
+/// executing.
+#[derive(Debug, Clone)]
+pub struct CoverageBlock {
+    func_index: u32,
+    start: u32,
+    end: u32,
+    count: u64,
+}
+
+impl CoverageBlock {
+    /// Returns the index of the function this block belongs to.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the offset, within the original wasm module, of this block's
+    /// first instruction.
+    pub fn start_offset(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the offset, within the original wasm module, just past this
+    /// block's last instruction.
+    pub fn end_offset(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns how many times this block started executing.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
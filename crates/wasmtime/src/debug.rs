@@ -0,0 +1,233 @@
+use crate::store::StoreOpaque;
+use crate::{Global, Instance, Memory, Module, Val, WasmBacktrace};
+use wasm_encoder::CoreDumpValue;
+use wasmtime_runtime::CoreDumpStack;
+
+/// The breakpoints and stepping state of a store, for code compiled with
+/// [`Config::debug_breakpoints`](crate::Config::debug_breakpoints).
+#[derive(Default)]
+pub(crate) struct DebugState {
+    breakpoints: Vec<(Module, usize)>,
+    single_step: bool,
+}
+
+impl DebugState {
+    pub(crate) fn add_breakpoint(&mut self, module: &Module, offset: usize) {
+        if !self.has_breakpoint(module, offset) {
+            self.breakpoints.push((module.clone(), offset));
+        }
+    }
+
+    pub(crate) fn remove_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.breakpoints
+            .retain(|(m, o)| !(*o == offset && Module::same(m, module)));
+    }
+
+    pub(crate) fn set_single_step(&mut self, enable: bool) {
+        self.single_step = enable;
+    }
+
+    /// Whether wasm needs to call into the host before each instruction.
+    pub(crate) fn active(&self) -> bool {
+        self.single_step || !self.breakpoints.is_empty()
+    }
+
+    fn has_breakpoint(&self, module: &Module, offset: usize) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|(m, o)| *o == offset && Module::same(m, module))
+    }
+}
+
+/// Why a guest stopped to hand control to a debugger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugStopReason {
+    /// A breakpoint added with
+    /// [`Store::add_breakpoint`](crate::Store::add_breakpoint) was reached.
+    Breakpoint,
+    /// The guest is single-stepping and reached the next instruction.
+    Step,
+}
+
+/// How a guest continues once the callback registered with
+/// [`Store::debug_callback`](crate::Store::debug_callback) returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugResume {
+    /// Run until the next breakpoint is reached.
+    Continue,
+    /// Stop again before the next instruction, which may be in a called
+    /// function.
+    Step,
+}
+
+/// The state of a guest that stopped before executing an instruction, passed
+/// to the callback registered with
+/// [`Store::debug_callback`](crate::Store::debug_callback).
+#[derive(Debug)]
+pub struct DebugStop {
+    reason: DebugStopReason,
+    frames: Vec<DebugFrame>,
+}
+
+impl DebugStop {
+    /// Captures the state of the guest stopped before the instruction at
+    /// `offset`, if a debugger wants to stop there.
+    pub(crate) fn capture(
+        store: &mut StoreOpaque,
+        debug: &DebugState,
+        offset: u32,
+    ) -> Option<DebugStop> {
+        let offset = offset as usize;
+        let reason = if debug.single_step {
+            DebugStopReason::Step
+        } else if debug.breakpoints.iter().any(|(_, o)| *o == offset) {
+            DebugStopReason::Breakpoint
+        } else {
+            return None;
+        };
+
+        // Safety: this is called from the `debug_break` libcall, so wasm in
+        // this store is on the stack.
+        let stack = unsafe { CoreDumpStack::capture(store.vmruntime_limits(), store.modules()) };
+        let mut captured_locals = stack.locals.into_iter().map(Some).collect::<Vec<_>>();
+        let mut locals = Vec::new();
+        let bt = WasmBacktrace::from_captured_with(store, stack.bt, None, |index| {
+            locals.push(
+                captured_locals
+                    .get_mut(index)
+                    .and_then(Option::take)
+                    .unwrap_or_default(),
+            );
+        });
+
+        let top = bt.frames().first()?;
+        if reason == DebugStopReason::Breakpoint && !debug.has_breakpoint(top.module(), offset) {
+            return None;
+        }
+
+        // Like coredumps, frames can't be mapped back to the instance they're
+        // running in, so pick the most recent instance of each frame's module.
+        let instances = store.all_instances().collect::<Vec<_>>();
+        let frames = bt
+            .frames()
+            .iter()
+            .zip(locals)
+            .enumerate()
+            .map(|(i, (frame, locals))| {
+                let instance = instances
+                    .iter()
+                    .rev()
+                    .find(|instance| Module::same(instance._module(store), frame.module()))
+                    .copied();
+                let (memories, globals) = match instance {
+                    Some(instance) => (
+                        instance.all_memories(store).map(|(_, m)| m).collect(),
+                        instance.all_globals(store).map(|(_, g)| g).collect(),
+                    ),
+                    None => (Vec::new(), Vec::new()),
+                };
+                DebugFrame {
+                    module: frame.module().clone(),
+                    instance,
+                    func_index: frame.func_index(),
+                    // The innermost frame stopped at exactly `offset`, while
+                    // the others are at the call they're waiting on.
+                    module_offset: if i == 0 {
+                        Some(offset)
+                    } else {
+                        frame.module_offset()
+                    },
+                    locals: locals
+                        .into_iter()
+                        .map(|value| match value {
+                            CoreDumpValue::I32(x) => Some(Val::I32(x)),
+                            CoreDumpValue::I64(x) => Some(Val::I64(x)),
+                            CoreDumpValue::F32(x) => Some(Val::F32(x.to_bits())),
+                            CoreDumpValue::F64(x) => Some(Val::F64(x.to_bits())),
+                            CoreDumpValue::Missing => None,
+                        })
+                        .collect(),
+                    memories,
+                    globals,
+                }
+            })
+            .collect();
+
+        Some(DebugStop { reason, frames })
+    }
+
+    /// Returns why the guest stopped.
+    pub fn reason(&self) -> DebugStopReason {
+        self.reason
+    }
+
+    /// Returns the wasm frames on the stack, starting with the frame that
+    /// stopped.
+    pub fn frames(&self) -> &[DebugFrame] {
+        &self.frames
+    }
+}
+
+/// A wasm stack frame of a guest that stopped for a debugger.
+#[derive(Debug)]
+pub struct DebugFrame {
+    module: Module,
+    instance: Option<Instance>,
+    func_index: u32,
+    module_offset: Option<usize>,
+    locals: Vec<Option<Val>>,
+    memories: Vec<Memory>,
+    globals: Vec<Global>,
+}
+
+impl DebugFrame {
+    /// Returns the module of the function running in this frame.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Returns the instance this frame is running in.
+    ///
+    /// Frames can't currently be mapped back to their instance, so when
+    /// there are multiple instances of this frame's module this is the most
+    /// recently created one. This is `None` if there are no instances of the
+    /// module in the store.
+    pub fn instance(&self) -> Option<Instance> {
+        self.instance
+    }
+
+    /// Returns the index of the function running in this frame, within its
+    /// module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the offset, within the original wasm module, of the
+    /// instruction this frame is about to execute or, for frames other than
+    /// the innermost, of the call it's waiting on.
+    ///
+    /// This is `None` if the module was compiled without an address map.
+    pub fn module_offset(&self) -> Option<usize> {
+        self.module_offset
+    }
+
+    /// Returns the values of this frame's locals, parameters first.
+    ///
+    /// Values of `v128` and reference-typed locals aren't recovered and are
+    /// `None`.
+    pub fn locals(&self) -> &[Option<Val>] {
+        &self.locals
+    }
+
+    /// Returns the memory of this frame's instance at `index`, counting
+    /// imported memories first.
+    pub fn memory(&self, index: u32) -> Option<Memory> {
+        self.memories.get(index as usize).copied()
+    }
+
+    /// Returns the global of this frame's instance at `index`, counting
+    /// imported globals first.
+    pub fn global(&self, index: u32) -> Option<Global> {
+        self.globals.get(index as usize).copied()
+    }
+}
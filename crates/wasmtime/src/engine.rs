@@ -1,11 +1,12 @@
 use crate::signatures::SignatureRegistry;
-use crate::Config;
+use crate::{Config, ModuleVersionStrategy};
 use anyhow::{Context, Result};
 use object::write::{Object, StandardSegment};
 use object::SectionKind;
 use once_cell::sync::OnceCell;
 #[cfg(feature = "parallel-compilation")]
 use rayon::prelude::*;
+use std::fmt;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -418,11 +419,13 @@ impl Engine {
             | "enable_float"
             | "enable_verifier"
             | "enable_pcc"
+            | "enable_bounds_check_elimination"
             | "regalloc_checker"
             | "regalloc_verbose_logs"
             | "is_pic"
             | "bb_padding_log2_minus_one"
             | "machine_code_cfg_info"
+            | "machine_code_isle_rule_info"
             | "tls_model" // wasmtime doesn't use tls right now
             | "opt_level" // opt level doesn't change semantics
             | "enable_alias_analysis" // alias analysis-based opts don't change semantics
@@ -656,6 +659,35 @@ impl Engine {
     pub fn detect_precompiled_file(&self, path: impl AsRef<Path>) -> Result<Option<Precompiled>> {
         serialization::detect_precompiled_file(path)
     }
+
+    /// Reads how the precompiled artifact `bytes` was compiled: the Wasmtime
+    /// version, target, compiler settings and wasm features recorded in it.
+    ///
+    /// Unlike [`Module::deserialize`](crate::Module::deserialize) this doesn't
+    /// check that the artifact is compatible with this engine, and it doesn't
+    /// load any of its code, so it can be used to describe artifacts produced
+    /// for other hosts or by other versions of Wasmtime as long as they use
+    /// the same artifact format.
+    pub fn precompiled_info(&self, bytes: &[u8]) -> Result<PrecompiledInfo> {
+        serialization::precompiled_info(bytes)
+    }
+
+    /// Checks whether the precompiled artifact `bytes` can be loaded into
+    /// this engine, and if not reports every reason why not.
+    ///
+    /// [`Module::deserialize`](crate::Module::deserialize) stops at the first
+    /// setting recorded in an artifact which doesn't match this engine, while
+    /// this returns all of them: a different Wasmtime version, target,
+    /// compiler settings, CPU features which aren't available on this host,
+    /// and configuration or WebAssembly features which differ from this
+    /// engine's. An empty list means that the artifact is compatible. If the
+    /// version doesn't match then the other settings can't be read, so the
+    /// version is the only mismatch reported.
+    ///
+    /// An error is returned if `bytes` isn't a precompiled artifact at all.
+    pub fn detect_precompiled_compat(&self, bytes: &[u8]) -> Result<Vec<Incompatibility>> {
+        serialization::detect_compat(self, bytes)
+    }
 }
 
 impl Default for Engine {
@@ -673,6 +705,191 @@ pub enum Precompiled {
     Component,
 }
 
+/// Return value from the [`Engine::precompiled_info`] API.
+#[derive(Clone, Debug)]
+pub struct PrecompiledInfo {
+    pub(crate) kind: Precompiled,
+    pub(crate) version: String,
+    pub(crate) target: String,
+    pub(crate) shared_flags: Vec<(String, String)>,
+    pub(crate) isa_flags: Vec<(String, String)>,
+    pub(crate) features: Vec<(&'static str, bool)>,
+}
+
+impl PrecompiledInfo {
+    /// Whether the artifact is a module or a component.
+    pub fn kind(&self) -> Precompiled {
+        self.kind
+    }
+
+    /// The version string the artifact was compiled with, see
+    /// [`Config::module_version`].
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The target triple the artifact was compiled for.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The target-independent compiler settings the artifact was compiled
+    /// with, as names and values.
+    pub fn shared_flags(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.shared_flags
+            .iter()
+            .map(|(name, val)| (name.as_str(), val.as_str()))
+    }
+
+    /// The target-specific compiler settings the artifact was compiled with,
+    /// as names and values.
+    pub fn isa_flags(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.isa_flags
+            .iter()
+            .map(|(name, val)| (name.as_str(), val.as_str()))
+    }
+
+    /// The WebAssembly proposals the artifact was compiled with, as names and
+    /// whether each was enabled.
+    pub fn features(&self) -> impl ExactSizeIterator<Item = (&'static str, bool)> + '_ {
+        self.features.iter().copied()
+    }
+}
+
+/// A reason why a precompiled artifact can't be loaded into an [`Engine`], as
+/// reported by [`Engine::detect_precompiled_compat`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Incompatibility {
+    /// The artifact was produced by another version, see
+    /// [`Config::module_version`].
+    Version {
+        /// The version the artifact was produced by.
+        found: String,
+        /// The version the engine expects.
+        expected: String,
+        /// The strategy the engine is configured with, which `expected` was
+        /// derived from.
+        strategy: ModuleVersionStrategy,
+    },
+    /// The artifact was compiled for another architecture.
+    Architecture {
+        /// The architecture the artifact was compiled for.
+        found: String,
+        /// The architecture of the engine's target.
+        expected: String,
+    },
+    /// The artifact was compiled for another operating system.
+    OperatingSystem {
+        /// The operating system the artifact was compiled for.
+        found: String,
+        /// The operating system of the engine's target.
+        expected: String,
+    },
+    /// A target-independent compiler setting of the artifact isn't supported.
+    SharedFlag {
+        /// The name of the setting.
+        name: String,
+        /// The value the artifact was compiled with.
+        value: String,
+        /// Why the value isn't supported.
+        reason: String,
+    },
+    /// A target-specific compiler setting of the artifact isn't supported,
+    /// typically because the artifact uses a CPU feature which this host
+    /// doesn't have.
+    IsaFlag {
+        /// The name of the setting, for example `has_avx2`.
+        name: String,
+        /// The value the artifact was compiled with.
+        value: String,
+        /// Why the value isn't supported.
+        reason: String,
+    },
+    /// A configuration setting of the artifact differs from the engine's.
+    Setting {
+        /// A description of the setting.
+        name: &'static str,
+        /// The value the artifact was compiled with.
+        found: String,
+        /// The value the engine is configured with.
+        expected: String,
+    },
+    /// A configuration option or WebAssembly feature is enabled for the
+    /// artifact but not for the engine, or vice versa.
+    Enabled {
+        /// A description of the option or feature.
+        name: &'static str,
+        /// Whether it's enabled for the artifact.
+        found: bool,
+        /// Whether it's enabled for the engine.
+        expected: bool,
+    },
+    /// The artifact was compiled with fuel consumption and a different fuel
+    /// cost model than the engine's.
+    FuelCostModel,
+}
+
+impl Incompatibility {
+    pub(crate) fn into_error(self) -> anyhow::Error {
+        match self {
+            Incompatibility::SharedFlag { reason, .. }
+            | Incompatibility::IsaFlag { reason, .. } => anyhow::Error::msg(reason)
+                .context("compilation settings of module incompatible with native host"),
+            other => anyhow::Error::msg(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Incompatibility::Version { found, strategy, .. } => match strategy {
+                ModuleVersionStrategy::WasmtimeVersion => {
+                    write!(f, "Module was compiled with incompatible Wasmtime version '{found}'")
+                }
+                ModuleVersionStrategy::Custom(_) | ModuleVersionStrategy::None => {
+                    write!(f, "Module was compiled with incompatible version '{found}'")
+                }
+            },
+            Incompatibility::Architecture { found, .. } => {
+                write!(f, "Module was compiled for architecture '{found}'")
+            }
+            Incompatibility::OperatingSystem { found, .. } => {
+                write!(f, "Module was compiled for operating system '{found}'")
+            }
+            Incompatibility::SharedFlag { reason, .. } | Incompatibility::IsaFlag { reason, .. } => {
+                write!(
+                    f,
+                    "compilation settings of module incompatible with native host: {reason}"
+                )
+            }
+            Incompatibility::Setting {
+                name,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Module was compiled with a {name} of '{found}' but '{expected}' is expected for the host"
+            ),
+            Incompatibility::Enabled {
+                name,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Module was compiled {} {name} but it {} enabled for the host",
+                if *found { "with" } else { "without" },
+                if *expected { "is" } else { "is not" }
+            ),
+            Incompatibility::FuelCostModel => write!(
+                f,
+                "Module was compiled with a different fuel cost model than the host's"
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
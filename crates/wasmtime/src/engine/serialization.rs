@@ -21,7 +21,7 @@
 //! other random ELF files, as well as provide better error messages for
 //! using wasmtime artifacts across versions.
 
-use crate::{Engine, ModuleVersionStrategy, Precompiled};
+use crate::{Engine, Incompatibility, ModuleVersionStrategy, Precompiled, PrecompiledInfo};
 use anyhow::{anyhow, bail, Context, Result};
 use object::write::{Object, StandardSegment};
 use object::{File, FileFlags, Object as _, ObjectSection, SectionKind};
@@ -100,52 +100,15 @@ pub fn check_compatible(engine: &Engine, mmap: &MmapVec, expected: ObjectKind) -
         _ => bail!("incompatible object file format"),
     }
 
-    let data = obj
-        .section_by_name(obj::ELF_WASM_ENGINE)
-        .ok_or_else(|| anyhow!("failed to find section `{}`", obj::ELF_WASM_ENGINE))?
-        .data()?;
-    let (first, data) = data
-        .split_first()
-        .ok_or_else(|| anyhow!("invalid engine section"))?;
-    if *first != VERSION {
-        bail!("mismatched version in engine section");
-    }
-    let (len, data) = data
-        .split_first()
-        .ok_or_else(|| anyhow!("invalid engine section"))?;
-    let len = usize::from(*len);
-    let (version, data) = if data.len() < len + 1 {
-        bail!("engine section too small")
-    } else {
-        data.split_at(len)
-    };
-
-    match &engine.config().module_version {
-        ModuleVersionStrategy::WasmtimeVersion => {
-            let version = std::str::from_utf8(version)?;
-            if version != env!("CARGO_PKG_VERSION") {
-                bail!(
-                    "Module was compiled with incompatible Wasmtime version '{}'",
-                    version
-                );
-            }
-        }
-        ModuleVersionStrategy::Custom(v) => {
-            let version = std::str::from_utf8(&version)?;
-            if version != v {
-                bail!(
-                    "Module was compiled with incompatible version '{}'",
-                    version
-                );
-            }
-        }
-        ModuleVersionStrategy::None => { /* ignore the version info, accept all */ }
+    let (version, data) = engine_section(&obj)?;
+    if let Some(incompatibility) = check_version(engine, version)? {
+        return Err(incompatibility.into_error());
     }
     bincode::deserialize::<Metadata<'_>>(data)?.check_compatible(engine)
 }
 
 fn detect_precompiled<'data, R: object::ReadRef<'data>>(
-    obj: File<'data, R>,
+    obj: &File<'data, R>,
 ) -> Option<Precompiled> {
     match obj.flags() {
         FileFlags::Elf {
@@ -163,13 +126,100 @@ fn detect_precompiled<'data, R: object::ReadRef<'data>>(
 }
 
 pub fn detect_precompiled_bytes(bytes: &[u8]) -> Option<Precompiled> {
-    detect_precompiled(File::parse(bytes).ok()?)
+    detect_precompiled(&File::parse(bytes).ok()?)
 }
 
 pub fn detect_precompiled_file(path: impl AsRef<std::path::Path>) -> Result<Option<Precompiled>> {
     let read_cache = object::ReadCache::new(std::fs::File::open(path)?);
     let obj = File::parse(&read_cache)?;
-    Ok(detect_precompiled(obj))
+    Ok(detect_precompiled(&obj))
+}
+
+/// Splits the engine section of `obj` into the version string the artifact
+/// was produced with and the serialized `Metadata` which follows it.
+fn engine_section<'data>(obj: &File<'data>) -> Result<(&'data [u8], &'data [u8])> {
+    let data = obj
+        .section_by_name(obj::ELF_WASM_ENGINE)
+        .ok_or_else(|| anyhow!("failed to find section `{}`", obj::ELF_WASM_ENGINE))?
+        .data()?;
+    let (first, data) = data
+        .split_first()
+        .ok_or_else(|| anyhow!("invalid engine section"))?;
+    if *first != VERSION {
+        bail!("mismatched version in engine section");
+    }
+    let (len, data) = data
+        .split_first()
+        .ok_or_else(|| anyhow!("invalid engine section"))?;
+    let len = usize::from(*len);
+    if data.len() < len + 1 {
+        bail!("engine section too small")
+    }
+    Ok(data.split_at(len))
+}
+
+/// Reads the engine configuration recorded in the precompiled artifact
+/// `bytes` without checking it against any particular engine.
+pub fn precompiled_info(bytes: &[u8]) -> Result<PrecompiledInfo> {
+    let obj = File::parse(bytes).context("failed to parse precompiled artifact as an ELF")?;
+    let kind = detect_precompiled(&obj)
+        .ok_or_else(|| anyhow!("not a precompiled Wasmtime module or component"))?;
+    let (version, data) = engine_section(&obj)?;
+    let version = std::str::from_utf8(version)?.to_string();
+    let metadata =
+        bincode::deserialize::<Metadata<'_>>(data).context("failed to decode engine section")?;
+    let flags = |flags: &[(&str, FlagValue<'_>)]| {
+        flags
+            .iter()
+            .map(|(name, val)| (name.to_string(), val.to_string()))
+            .collect()
+    };
+    Ok(PrecompiledInfo {
+        kind,
+        version,
+        shared_flags: flags(&metadata.shared_flags),
+        isa_flags: flags(&metadata.isa_flags),
+        features: metadata.features.list(),
+        target: metadata.target,
+    })
+}
+
+/// Checks the version string recorded in an artifact against the version
+/// `engine` expects according to its `ModuleVersionStrategy`.
+fn check_version(engine: &Engine, version: &[u8]) -> Result<Option<Incompatibility>> {
+    let strategy = &engine.config().module_version;
+    let expected = match strategy {
+        ModuleVersionStrategy::WasmtimeVersion => env!("CARGO_PKG_VERSION"),
+        ModuleVersionStrategy::Custom(v) => v,
+        ModuleVersionStrategy::None => return Ok(None), // accept all
+    };
+    let found = std::str::from_utf8(version)?;
+    if found == expected {
+        return Ok(None);
+    }
+    Ok(Some(Incompatibility::Version {
+        found: found.to_string(),
+        expected: expected.to_string(),
+        strategy: strategy.clone(),
+    }))
+}
+
+/// Lists every setting recorded in the precompiled artifact `bytes` which
+/// prevents loading it into `engine`.
+pub fn detect_compat(engine: &Engine, bytes: &[u8]) -> Result<Vec<Incompatibility>> {
+    let obj = File::parse(bytes).context("failed to parse precompiled artifact as an ELF")?;
+    if detect_precompiled(&obj).is_none() {
+        bail!("not a precompiled Wasmtime module or component");
+    }
+    let (version, data) = engine_section(&obj)?;
+    match check_version(engine, version)? {
+        // The metadata of other versions may not even be decodable, so the
+        // version is the only thing reported.
+        Some(incompatibility) => Ok(vec![incompatibility]),
+        None => bincode::deserialize::<Metadata<'_>>(data)
+            .context("failed to decode engine section")?
+            .incompatibilities(engine),
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -201,6 +251,43 @@ struct WasmFeatures {
     function_references: bool,
 }
 
+impl WasmFeatures {
+    /// Lists the features by the name `wasmparser` uses for them and whether
+    /// each is enabled.
+    fn list(&self) -> Vec<(&'static str, bool)> {
+        let WasmFeatures {
+            reference_types,
+            multi_value,
+            bulk_memory,
+            component_model,
+            simd,
+            tail_call,
+            threads,
+            multi_memory,
+            exceptions,
+            memory64,
+            relaxed_simd,
+            extended_const,
+            function_references,
+        } = *self;
+        vec![
+            ("reference_types", reference_types),
+            ("multi_value", multi_value),
+            ("bulk_memory", bulk_memory),
+            ("component_model", component_model),
+            ("simd", simd),
+            ("tail_call", tail_call),
+            ("threads", threads),
+            ("multi_memory", multi_memory),
+            ("exceptions", exceptions),
+            ("memory64", memory64),
+            ("relaxed_simd", relaxed_simd),
+            ("extended_const", extended_const),
+            ("function_references", function_references),
+        ]
+    }
+}
+
 impl Metadata<'_> {
     #[cfg(any(feature = "cranelift", feature = "winch"))]
     fn new(engine: &Engine) -> Metadata<'static> {
@@ -256,84 +343,107 @@ impl Metadata<'_> {
         }
     }
 
-    fn check_compatible(mut self, engine: &Engine) -> Result<()> {
-        self.check_triple(engine)?;
-        self.check_shared_flags(engine)?;
-        self.check_isa_flags(engine)?;
-        self.check_tunables(&engine.config().tunables)?;
-        self.check_features(&engine.config().features)?;
-        Ok(())
+    fn check_compatible(self, engine: &Engine) -> Result<()> {
+        match self.incompatibilities(engine)?.into_iter().next() {
+            Some(incompatibility) => Err(incompatibility.into_error()),
+            None => Ok(()),
+        }
     }
 
-    fn check_triple(&self, engine: &Engine) -> Result<()> {
+    /// Lists every way in which this metadata is incompatible with `engine`,
+    /// in the order in which `check_compatible` reports them.
+    fn incompatibilities(&self, engine: &Engine) -> Result<Vec<Incompatibility>> {
+        let mut report = Vec::new();
+        // Compiler settings are only meaningful for the target they were
+        // recorded for, so they're skipped if the target doesn't match.
+        if self.check_triple(engine, &mut report)? {
+            self.check_shared_flags(engine, &mut report);
+            self.check_isa_flags(engine, &mut report);
+        }
+        self.check_tunables(&engine.config().tunables, &mut report);
+        self.check_features(&engine.config().features, &mut report);
+        Ok(report)
+    }
+
+    /// Returns whether the target of this metadata matches `engine`'s.
+    fn check_triple(&self, engine: &Engine, report: &mut Vec<Incompatibility>) -> Result<bool> {
         let engine_target = engine.target();
         let module_target =
             target_lexicon::Triple::from_str(&self.target).map_err(|e| anyhow!(e))?;
+        let len = report.len();
 
         if module_target.architecture != engine_target.architecture {
-            bail!(
-                "Module was compiled for architecture '{}'",
-                module_target.architecture
-            );
+            report.push(Incompatibility::Architecture {
+                found: module_target.architecture.to_string(),
+                expected: engine_target.architecture.to_string(),
+            });
         }
 
         if module_target.operating_system != engine_target.operating_system {
-            bail!(
-                "Module was compiled for operating system '{}'",
-                module_target.operating_system
-            );
+            report.push(Incompatibility::OperatingSystem {
+                found: module_target.operating_system.to_string(),
+                expected: engine_target.operating_system.to_string(),
+            });
         }
 
-        Ok(())
+        Ok(report.len() == len)
     }
 
-    fn check_shared_flags(&mut self, engine: &Engine) -> Result<()> {
+    fn check_shared_flags(&self, engine: &Engine, report: &mut Vec<Incompatibility>) {
         for (name, val) in self.shared_flags.iter() {
-            engine
-                .check_compatible_with_shared_flag(name, val)
-                .map_err(|s| anyhow::Error::msg(s))
-                .context("compilation settings of module incompatible with native host")?;
+            if let Err(reason) = engine.check_compatible_with_shared_flag(name, val) {
+                report.push(Incompatibility::SharedFlag {
+                    name: name.to_string(),
+                    value: val.to_string(),
+                    reason,
+                });
+            }
         }
-        Ok(())
     }
 
-    fn check_isa_flags(&mut self, engine: &Engine) -> Result<()> {
+    fn check_isa_flags(&self, engine: &Engine, report: &mut Vec<Incompatibility>) {
         for (name, val) in self.isa_flags.iter() {
-            engine
-                .check_compatible_with_isa_flag(name, val)
-                .map_err(|s| anyhow::Error::msg(s))
-                .context("compilation settings of module incompatible with native host")?;
+            if let Err(reason) = engine.check_compatible_with_isa_flag(name, val) {
+                report.push(Incompatibility::IsaFlag {
+                    name: name.to_string(),
+                    value: val.to_string(),
+                    reason,
+                });
+            }
         }
-        Ok(())
     }
 
-    fn check_int<T: Eq + std::fmt::Display>(found: T, expected: T, feature: &str) -> Result<()> {
-        if found == expected {
-            return Ok(());
+    fn check_int<T: Eq + std::fmt::Display>(
+        found: T,
+        expected: T,
+        setting: &'static str,
+        report: &mut Vec<Incompatibility>,
+    ) {
+        if found != expected {
+            report.push(Incompatibility::Setting {
+                name: setting,
+                found: found.to_string(),
+                expected: expected.to_string(),
+            });
         }
-
-        bail!(
-            "Module was compiled with a {} of '{}' but '{}' is expected for the host",
-            feature,
-            found,
-            expected
-        );
     }
 
-    fn check_bool(found: bool, expected: bool, feature: &str) -> Result<()> {
-        if found == expected {
-            return Ok(());
+    fn check_bool(
+        found: bool,
+        expected: bool,
+        feature: &'static str,
+        report: &mut Vec<Incompatibility>,
+    ) {
+        if found != expected {
+            report.push(Incompatibility::Enabled {
+                name: feature,
+                found,
+                expected,
+            });
         }
-
-        bail!(
-            "Module was compiled {} {} but it {} enabled for the host",
-            if found { "with" } else { "without" },
-            feature,
-            if expected { "is" } else { "is not" }
-        );
     }
 
-    fn check_tunables(&mut self, other: &Tunables) -> Result<()> {
+    fn check_tunables(&self, other: &Tunables, report: &mut Vec<Incompatibility>) {
         let Tunables {
             static_memory_bound,
             static_memory_offset_guard_size,
@@ -341,6 +451,7 @@ impl Metadata<'_> {
             generate_native_debuginfo,
             parse_wasm_debuginfo,
             consume_fuel,
+            fuel_cost_model,
             epoch_interruption,
             static_memory_bound_is_maximum,
             guard_before_linear_memory,
@@ -358,60 +469,93 @@ impl Metadata<'_> {
 
             // Just a debugging aid, doesn't affect functionality at all.
             debug_adapter_modules: _,
-        } = self.tunables;
+
+            // These only affect how the generated code is optimized, not its
+            // behavior or the runtime data structures it relies on.
+            inlining: _,
+            inlining_max_callee_size: _,
+
+            // Coverage counters are described by each function's metadata, so
+            // instrumented and uninstrumented modules can be mixed freely.
+            coverage_instrumentation: _,
+
+            // Likewise each function's metadata says whether, and where, its
+            // locals are recorded for coredumps.
+            coredump_locals: _,
+
+            // Code compiled without breakpoint checks just never stops, and
+            // the checks in code compiled with them are inert unless a store
+            // has breakpoints, so these can be mixed freely too.
+            debug_breakpoints: _,
+        } = self.tunables.clone();
 
         Self::check_int(
             static_memory_bound,
             other.static_memory_bound,
             "static memory bound",
-        )?;
+            report,
+        );
         Self::check_int(
             static_memory_offset_guard_size,
             other.static_memory_offset_guard_size,
             "static memory guard size",
-        )?;
+            report,
+        );
         Self::check_int(
             dynamic_memory_offset_guard_size,
             other.dynamic_memory_offset_guard_size,
             "dynamic memory guard size",
-        )?;
+            report,
+        );
         Self::check_bool(
             generate_native_debuginfo,
             other.generate_native_debuginfo,
             "debug information support",
-        )?;
+            report,
+        );
         Self::check_bool(
             parse_wasm_debuginfo,
             other.parse_wasm_debuginfo,
             "WebAssembly backtrace support",
-        )?;
-        Self::check_bool(consume_fuel, other.consume_fuel, "fuel support")?;
+            report,
+        );
+        Self::check_bool(consume_fuel, other.consume_fuel, "fuel support", report);
+        if consume_fuel && fuel_cost_model != other.fuel_cost_model {
+            report.push(Incompatibility::FuelCostModel);
+        }
         Self::check_bool(
             epoch_interruption,
             other.epoch_interruption,
             "epoch interruption",
-        )?;
+            report,
+        );
         Self::check_bool(
             static_memory_bound_is_maximum,
             other.static_memory_bound_is_maximum,
             "pooling allocation support",
-        )?;
+            report,
+        );
         Self::check_bool(
             guard_before_linear_memory,
             other.guard_before_linear_memory,
             "guard before linear memory",
-        )?;
+            report,
+        );
         Self::check_bool(
             relaxed_simd_deterministic,
             other.relaxed_simd_deterministic,
             "relaxed simd deterministic semantics",
-        )?;
-        Self::check_bool(tail_callable, other.tail_callable, "WebAssembly tail calls")?;
-
-        Ok(())
+            report,
+        );
+        Self::check_bool(
+            tail_callable,
+            other.tail_callable,
+            "WebAssembly tail calls",
+            report,
+        );
     }
 
-    fn check_features(&mut self, other: &wasmparser::WasmFeatures) -> Result<()> {
+    fn check_features(&self, other: &wasmparser::WasmFeatures, report: &mut Vec<Incompatibility>) {
         let WasmFeatures {
             reference_types,
             multi_value,
@@ -432,57 +576,75 @@ impl Metadata<'_> {
             reference_types,
             other.reference_types,
             "WebAssembly reference types support",
-        )?;
+            report,
+        );
         Self::check_bool(
             multi_value,
             other.multi_value,
             "WebAssembly multi-value support",
-        )?;
+            report,
+        );
         Self::check_bool(
             bulk_memory,
             other.bulk_memory,
             "WebAssembly bulk memory support",
-        )?;
+            report,
+        );
         Self::check_bool(
             component_model,
             other.component_model,
             "WebAssembly component model support",
-        )?;
-        Self::check_bool(simd, other.simd, "WebAssembly SIMD support")?;
-        Self::check_bool(tail_call, other.tail_call, "WebAssembly tail calls support")?;
-        Self::check_bool(threads, other.threads, "WebAssembly threads support")?;
+            report,
+        );
+        Self::check_bool(simd, other.simd, "WebAssembly SIMD support", report);
+        Self::check_bool(
+            tail_call,
+            other.tail_call,
+            "WebAssembly tail calls support",
+            report,
+        );
+        Self::check_bool(
+            threads,
+            other.threads,
+            "WebAssembly threads support",
+            report,
+        );
         Self::check_bool(
             multi_memory,
             other.multi_memory,
             "WebAssembly multi-memory support",
-        )?;
+            report,
+        );
         Self::check_bool(
             exceptions,
             other.exceptions,
             "WebAssembly exceptions support",
-        )?;
+            report,
+        );
         Self::check_bool(
             memory64,
             other.memory64,
             "WebAssembly 64-bit memory support",
-        )?;
+            report,
+        );
         Self::check_bool(
             extended_const,
             other.extended_const,
             "WebAssembly extended-const support",
-        )?;
+            report,
+        );
         Self::check_bool(
             relaxed_simd,
             other.relaxed_simd,
             "WebAssembly relaxed-simd support",
-        )?;
+            report,
+        );
         Self::check_bool(
             function_references,
             other.function_references,
             "WebAssembly function-references support",
-        )?;
-
-        Ok(())
+            report,
+        );
     }
 }
 
@@ -508,6 +670,117 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_multiple_mismatches() -> Result<()> {
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        config.wasm_threads(true);
+        let engine = Engine::new(&config)?;
+
+        // Each incompatibility is reported, and `check_compatible` fails with
+        // the first one.
+        let check = |metadata: Metadata<'_>| -> Result<Vec<Incompatibility>> {
+            let report = metadata.incompatibilities(&engine)?;
+            let first = report.first().cloned().unwrap().into_error();
+            match metadata.check_compatible(&engine) {
+                Ok(_) => unreachable!(),
+                Err(e) => assert_eq!(e.to_string(), first.to_string()),
+            }
+            Ok(report)
+        };
+
+        let mut metadata = Metadata::new(&engine);
+        metadata.target = "unknown-generic-linux".to_string();
+        metadata
+            .isa_flags
+            .push(("not_a_flag", FlagValue::Bool(true)));
+        metadata.tunables.epoch_interruption = false;
+        metadata.features.threads = false;
+        let report = check(metadata)?;
+        assert_eq!(report.len(), 3, "{report:?}");
+        assert!(
+            matches!(&report[0], Incompatibility::Architecture { found, .. } if found == "unknown"),
+            "{report:?}"
+        );
+        assert!(matches!(
+            report[1],
+            Incompatibility::Enabled {
+                name: "epoch interruption",
+                ..
+            }
+        ));
+        assert!(matches!(
+            report[2],
+            Incompatibility::Enabled {
+                name: "WebAssembly threads support",
+                ..
+            }
+        ));
+
+        // With a matching target the flags are checked too.
+        let mut metadata = Metadata::new(&engine);
+        metadata
+            .isa_flags
+            .push(("not_a_flag", FlagValue::Bool(true)));
+        metadata.tunables.static_memory_offset_guard_size = 0;
+        metadata.features.threads = false;
+        let report = check(metadata)?;
+        assert_eq!(report.len(), 3, "{report:?}");
+        assert!(
+            matches!(&report[0], Incompatibility::IsaFlag { name, .. } if name == "not_a_flag"),
+            "{report:?}"
+        );
+        assert!(
+            matches!(&report[1], Incompatibility::Setting { found, .. } if found == "0"),
+            "{report:?}"
+        );
+        assert!(matches!(
+            report[2],
+            Incompatibility::Enabled {
+                name: "WebAssembly threads support",
+                ..
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_version_mismatch() -> Result<()> {
+        let engine = Engine::default();
+        let bytes = engine.precompile_module(b"\0asm\x01\0\0\0")?;
+
+        let mut config = Config::new();
+        config.module_version(ModuleVersionStrategy::Custom("custom".to_string()))?;
+        let custom = Engine::new(&config)?;
+        let report = custom.detect_precompiled_compat(&bytes)?;
+        assert_eq!(
+            report[0].to_string(),
+            format!(
+                "Module was compiled with incompatible version '{}'",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        let bytes = custom.precompile_module(b"\0asm\x01\0\0\0")?;
+        let report = engine.detect_precompiled_compat(&bytes)?;
+        assert_eq!(
+            report,
+            [Incompatibility::Version {
+                found: "custom".to_string(),
+                expected: env!("CARGO_PKG_VERSION").to_string(),
+                strategy: ModuleVersionStrategy::WasmtimeVersion,
+            }]
+        );
+        assert_eq!(
+            report[0].to_string(),
+            "Module was compiled with incompatible Wasmtime version 'custom'"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_os_mismatch() -> Result<()> {
         let engine = Engine::default();
@@ -626,6 +899,110 @@ Caused by:
         Ok(())
     }
 
+    #[test]
+    fn test_incompatibilities_report() -> Result<()> {
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        config.wasm_threads(true);
+        let engine = Engine::new(&config)?;
+        let mut metadata = Metadata::new(&engine);
+        metadata.tunables.epoch_interruption = false;
+        metadata.features.threads = false;
+        metadata
+            .isa_flags
+            .push(("not_a_flag", FlagValue::Bool(true)));
+
+        let report = metadata.incompatibilities(&engine)?;
+        assert_eq!(report.len(), 3, "{report:?}");
+        assert!(
+            matches!(&report[0], Incompatibility::IsaFlag { name, .. } if name == "not_a_flag"),
+            "{report:?}"
+        );
+        assert_eq!(
+            report[1],
+            Incompatibility::Enabled {
+                name: "epoch interruption",
+                found: false,
+                expected: true,
+            }
+        );
+        assert_eq!(
+            report[2],
+            Incompatibility::Enabled {
+                name: "WebAssembly threads support",
+                found: false,
+                expected: true,
+            }
+        );
+
+        // Compiler settings for another target aren't checked.
+        metadata.target = "unknown-generic-linux".to_string();
+        let report = metadata.incompatibilities(&engine)?;
+        assert!(
+            matches!(report[0], Incompatibility::Architecture { .. }),
+            "{report:?}"
+        );
+        assert!(!report
+            .iter()
+            .any(|i| matches!(i, Incompatibility::IsaFlag { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_detect_precompiled_compat() -> Result<()> {
+        let engine = Engine::default();
+        let bytes = engine.precompile_module(b"\0asm\x01\0\0\0")?;
+        assert!(engine.detect_precompiled_compat(&bytes)?.is_empty());
+
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        config.module_version(ModuleVersionStrategy::Custom("custom".to_string()))?;
+        let other = Engine::new(&config)?;
+        assert_eq!(
+            other.detect_precompiled_compat(&bytes)?,
+            [Incompatibility::Version {
+                found: env!("CARGO_PKG_VERSION").to_string(),
+                expected: "custom".to_string(),
+                strategy: ModuleVersionStrategy::Custom("custom".to_string()),
+            }]
+        );
+
+        config.module_version(ModuleVersionStrategy::WasmtimeVersion)?;
+        let other = Engine::new(&config)?;
+        assert_eq!(
+            other.detect_precompiled_compat(&bytes)?,
+            [Incompatibility::Enabled {
+                name: "epoch interruption",
+                found: false,
+                expected: true,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_precompiled_info() -> Result<()> {
+        let mut config = Config::new();
+        config.wasm_threads(true);
+        let engine = Engine::new(&config)?;
+        let empty_module = b"\0asm\x01\0\0\0";
+        let bytes = engine.precompile_module(empty_module)?;
+
+        let info = engine.precompiled_info(&bytes)?;
+        assert_eq!(info.kind(), Precompiled::Module);
+        assert_eq!(info.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.target(), engine.target().to_string());
+        assert!(info.features().any(|f| f == ("threads", true)));
+        assert!(info.shared_flags().len() > 0);
+
+        assert!(engine.precompiled_info(empty_module).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_feature_mismatch() -> Result<()> {
         let mut config = Config::new();
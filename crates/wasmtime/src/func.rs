@@ -1751,6 +1751,11 @@ pub trait IntoFunc<T, Params, Results>: Send + Sync + 'static {
     /// internal `VMFuncRef`.
     #[doc(hidden)]
     fn into_func(self, engine: &Engine) -> HostContext;
+
+    /// Same as `into_func`, but the resulting function consumes `fuel` from
+    /// the store every time it's called.
+    #[doc(hidden)]
+    fn into_func_with_fuel(self, engine: &Engine, fuel: u64) -> HostContext;
 }
 
 /// A structure representing the caller's context when creating a function
@@ -1913,6 +1918,14 @@ macro_rules! impl_into_func {
 
                 f.into_func(engine)
             }
+
+            fn into_func_with_fuel(self, engine: &Engine, fuel: u64) -> HostContext {
+                let f = move |_: Caller<'_, T>, $($args:$args),*| {
+                    self($($args),*)
+                };
+
+                f.into_func_with_fuel(engine, fuel)
+            }
         }
 
         #[allow(non_snake_case)]
@@ -1922,6 +1935,17 @@ macro_rules! impl_into_func {
             $($args: WasmTy,)*
             R: WasmRet,
         {
+            fn into_func_with_fuel(self, engine: &Engine, fuel: u64) -> HostContext {
+                let f = move |caller: Caller<'_, T>, $($args:$args),*| {
+                    match caller.store.0.consume_fuel(fuel) {
+                        Ok(()) => self(caller, $($args),*).into_fallible(),
+                        Err(e) => R::fallible_from_error(e),
+                    }
+                };
+
+                f.into_func(engine)
+            }
+
             fn into_func(self, engine: &Engine) -> HostContext {
                 /// This shim is a regular, non-closure function we can stuff
                 /// inside `VMFuncRef::native_call`.
@@ -2157,6 +2181,17 @@ impl HostFunc {
         HostFunc::_new(engine, ctx)
     }
 
+    /// Analog of [`Func::wrap`] for a function which consumes `fuel` each
+    /// time it's called.
+    pub fn wrap_with_fuel<T, Params, Results>(
+        engine: &Engine,
+        func: impl IntoFunc<T, Params, Results>,
+        fuel: u64,
+    ) -> Self {
+        let ctx = func.into_func_with_fuel(engine, fuel);
+        HostFunc::_new(engine, ctx)
+    }
+
     /// Requires that this function's signature is already registered within
     /// `Engine`. This happens automatically during the above two constructors.
     fn _new(engine: &Engine, ctx: HostContext) -> Self {
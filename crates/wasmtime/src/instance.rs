@@ -287,6 +287,12 @@ impl Instance {
                     pkey: store.get_pkey(),
                 })?;
 
+        // Point the instance at the counters shared by all instances of this
+        // module if it counts the basic blocks it executes.
+        if let Some(counters) = store.coverage_counters(module) {
+            instance_handle.set_coverage_counters(counters);
+        }
+
         // The instance still has lots of setup, for example
         // data/elements/start/etc. This can all fail, but even on failure
         // the instance may persist some state via previous successful
@@ -369,7 +375,7 @@ impl Instance {
         self._module(store.into().0)
     }
 
-    fn _module<'a>(&self, store: &'a StoreOpaque) -> &'a Module {
+    pub(crate) fn _module<'a>(&self, store: &'a StoreOpaque) -> &'a Module {
         let InstanceData { id, .. } = store[self.0];
         store.module_for_instance(id).unwrap()
     }
@@ -296,6 +296,11 @@
 //!   a core dump when a trap happens. This can be configured via
 //!   [`Config::coredump_on_trap`].
 //!
+//! * `debugger` - Enabled by default, this will provide support for stopping
+//!   wasm at breakpoints and inspecting its state via
+//!   [`Store::debug_callback`]. This must also be enabled at compile time via
+//!   [`Config::debug_breakpoints`].
+//!
 //! * `addr2line` - Enabled by default, this feature configures whether traps
 //!   will attempt to parse DWARF debug information and convert WebAssembly
 //!   addresses to source filenames and line numbers.
@@ -412,6 +417,7 @@ mod compiler;
 
 mod code;
 mod config;
+mod coverage;
 mod engine;
 mod externals;
 mod instance;
@@ -435,6 +441,7 @@ mod values;
 mod stack;
 
 pub use crate::config::*;
+pub use crate::coverage::{Coverage, CoverageBlock, ModuleCoverage};
 pub use crate::engine::*;
 pub use crate::externals::*;
 pub use crate::func::*;
@@ -444,7 +451,9 @@ pub use crate::linker::*;
 pub use crate::memory::*;
 pub use crate::module::Module;
 #[cfg(feature = "profiling")]
-pub use crate::profiling::GuestProfiler;
+pub use crate::profiling::{GuestProfileFormat, GuestProfiler, GuestProfilerThread};
+#[cfg(all(feature = "profiling", target_os = "linux"))]
+pub use crate::profiling::{PerfEvent, PerfEventFormat, PerfEventProfiler};
 pub use crate::r#ref::ExternRef;
 pub use crate::resources::*;
 #[cfg(feature = "async")]
@@ -464,6 +473,10 @@ pub use crate::stack::*;
 mod coredump;
 #[cfg(feature = "coredump")]
 pub use crate::coredump::*;
+#[cfg(feature = "debugger")]
+mod debug;
+#[cfg(feature = "debugger")]
+pub use crate::debug::{DebugFrame, DebugResume, DebugStop, DebugStopReason};
 
 /// A convenience wrapper for `Result<T, anyhow::Error>`.
 ///
@@ -146,6 +146,21 @@ pub trait ResourceLimiter {
     fn memories(&self) -> usize {
         DEFAULT_MEMORY_LIMIT
     }
+
+    /// The maximum number of live entries in a component
+    /// [`ResourceTable`](crate::component::ResourceTable).
+    ///
+    /// Resource tables are owned by the host rather than by a `Store`, so this
+    /// limit is advisory: the `Store` never enforces it itself, and it only
+    /// takes effect for tables configured with
+    /// [`ResourceTable::apply_limits`](crate::component::ResourceTable::apply_limits).
+    /// For example hosts using `wasmtime-wasi` should apply it to the table
+    /// returned by their `WasiView` implementation when creating a `Store`.
+    ///
+    /// This value defaults to `None`, or no limit.
+    fn resource_table_entries(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Used by hosts to limit resource consumption of instances, blocking
@@ -210,6 +225,11 @@ pub trait ResourceLimiterAsync {
     fn memories(&self) -> usize {
         DEFAULT_MEMORY_LIMIT
     }
+
+    /// Identical to [`ResourceLimiter::resource_table_entries`]
+    fn resource_table_entries(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Used to build [`StoreLimits`].
@@ -279,6 +299,20 @@ impl StoreLimitsBuilder {
         self
     }
 
+    /// The maximum number of live entries in a component
+    /// [`ResourceTable`](crate::component::ResourceTable) configured with
+    /// these limits through
+    /// [`ResourceTable::apply_limits`](crate::component::ResourceTable::apply_limits).
+    ///
+    /// This limit is advisory and isn't enforced for tables which haven't been
+    /// configured with it, see [`ResourceLimiter::resource_table_entries`].
+    ///
+    /// By default, resource table entries will not be limited.
+    pub fn resource_table_entries(mut self, limit: usize) -> Self {
+        self.0.resource_table_entries = Some(limit);
+        self
+    }
+
     /// Indicates that a trap should be raised whenever a growth operation
     /// would fail.
     ///
@@ -314,6 +348,7 @@ pub struct StoreLimits {
     instances: usize,
     tables: usize,
     memories: usize,
+    resource_table_entries: Option<usize>,
     trap_on_grow_failure: bool,
 }
 
@@ -325,6 +360,7 @@ impl Default for StoreLimits {
             instances: DEFAULT_INSTANCE_LIMIT,
             tables: DEFAULT_TABLE_LIMIT,
             memories: DEFAULT_MEMORY_LIMIT,
+            resource_table_entries: None,
             trap_on_grow_failure: false,
         }
     }
@@ -395,4 +431,8 @@ impl ResourceLimiter for StoreLimits {
     fn memories(&self) -> usize {
         self.memories
     }
+
+    fn resource_table_entries(&self) -> Option<usize> {
+        self.resource_table_entries
+    }
 }
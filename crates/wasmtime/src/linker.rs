@@ -430,6 +430,26 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Same as [`Linker::func_new`], except that every call to the function
+    /// consumes `fuel` from the calling store before `func` runs.
+    ///
+    /// For more information see [`Linker::func_wrap_with_fuel`].
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    #[cfg_attr(nightlydoc, doc(cfg(any(feature = "cranelift", feature = "winch"))))]
+    pub fn func_new_with_fuel(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        fuel: u64,
+        func: impl Fn(Caller<'_, T>, &[Val], &mut [Val]) -> Result<()> + Send + Sync + 'static,
+    ) -> Result<&mut Self> {
+        self.func_new(module, name, ty, move |caller, params, results| {
+            caller.store.0.consume_fuel(fuel)?;
+            func(caller, params, results)
+        })
+    }
+
     /// Creates a [`Func::new_unchecked`]-style function named in this linker.
     ///
     /// For more information see [`Linker::func_wrap`].
@@ -557,6 +577,48 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Same as [`Linker::func_wrap`], except that every call to the function
+    /// consumes `fuel` from the calling store before `func` runs.
+    ///
+    /// This allows host functions to be accounted for in the same way as
+    /// WebAssembly operators are with [`Config::fuel_cost_model`]. If the
+    /// store doesn't have enough fuel left then the call traps with
+    /// [`Trap::OutOfFuel`] and `func` isn't invoked. If fuel isn't enabled
+    /// with [`Config::consume_fuel`] this behaves the same as
+    /// [`Linker::func_wrap`].
+    ///
+    /// [`Config::fuel_cost_model`]: crate::Config::fuel_cost_model
+    /// [`Config::consume_fuel`]: crate::Config::consume_fuel
+    /// [`Trap::OutOfFuel`]: crate::Trap::OutOfFuel
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wasmtime::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut config = Config::new();
+    /// config.consume_fuel(true);
+    /// let engine = Engine::new(&config)?;
+    /// let mut linker = Linker::<()>::new(&engine);
+    /// linker.func_wrap_with_fuel("host", "expensive", 1_000, || {
+    ///     // ...
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn func_wrap_with_fuel<Params, Args>(
+        &mut self,
+        module: &str,
+        name: &str,
+        fuel: u64,
+        func: impl IntoFunc<T, Params, Args>,
+    ) -> Result<&mut Self> {
+        let func = HostFunc::wrap_with_fuel(&self.engine, func, fuel);
+        let key = self.import_key(module, Some(name));
+        self.insert(key, Definition::HostFunc(Arc::new(func)))?;
+        Ok(self)
+    }
+
     for_each_function_signature!(generate_wrap_async_func);
 
     /// Convenience wrapper to define an entire [`Instance`] in this linker.
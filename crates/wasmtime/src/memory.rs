@@ -910,6 +910,14 @@ impl SharedMemory {
         }
     }
 
+    /// Construct a single-memory instance to provide a way to export this
+    /// [`SharedMemory`] in place of another memory, as when a component is
+    /// instantiated with the shared memories of another instance.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn vmexport(&self, store: &mut StoreOpaque) -> wasmtime_runtime::ExportMemory {
+        generate_memory_export(store, &self.ty(), Some(&self.0)).unwrap()
+    }
+
     /// Create a [`SharedMemory`] from an [`ExportMemory`] definition. This
     /// function is available to handle the case in which a Wasm module exports
     /// shared memory and the user wants host-side access to it.
@@ -3,7 +3,7 @@ use crate::{
     resources::ResourcesRequired,
     signatures::SignatureCollection,
     types::{ExportType, ExternType, ImportType},
-    Engine,
+    Engine, FrameSymbol,
 };
 use anyhow::{bail, Context, Result};
 use once_cell::sync::OnceCell;
@@ -15,8 +15,8 @@ use std::ptr::NonNull;
 use std::sync::Arc;
 use wasmparser::{Parser, ValidPayload, Validator};
 use wasmtime_environ::{
-    DefinedFuncIndex, DefinedMemoryIndex, HostPtr, ModuleEnvironment, ModuleTypes, ObjectKind,
-    VMOffsets,
+    DefinedFuncIndex, DefinedMemoryIndex, FuncIndex, HostPtr, ModuleEnvironment, ModuleTypes,
+    ObjectKind, VMOffsets,
 };
 use wasmtime_jit::{CodeMemory, CompiledModule, CompiledModuleInfo};
 use wasmtime_runtime::{
@@ -680,6 +680,16 @@ impl Module {
         &self.inner.code
     }
 
+    /// Returns whether `a` and `b` refer to the same compiled module.
+    ///
+    /// Clones of a [`Module`] are the same module, as are the modules that
+    /// frames of a backtrace report for functions of this module. Separately
+    /// compiled modules are never the same, even if they were compiled from
+    /// the same wasm binary.
+    pub fn same(a: &Module, b: &Module) -> bool {
+        Arc::ptr_eq(&a.inner, &b.inner)
+    }
+
     pub(crate) fn env_module(&self) -> &wasmtime_environ::Module {
         self.compiled_module().module()
     }
@@ -1088,6 +1098,38 @@ impl Module {
         })
     }
 
+    /// Looks up the DWARF debug symbols for an instruction `func_offset`
+    /// bytes into the body of the function `func_index`.
+    ///
+    /// This is the same information that [`FrameInfo::symbols`] reports for
+    /// a live frame, and is useful to symbolize frames recorded elsewhere,
+    /// such as in a [`WasmCoreDump`]. An empty list is returned if
+    /// `func_index` isn't defined by this module or if no debug information
+    /// covers the instruction.
+    ///
+    /// [`FrameInfo::symbols`]: crate::FrameInfo::symbols
+    /// [`WasmCoreDump`]: crate::WasmCoreDump
+    pub fn frame_symbols(&self, func_index: u32, func_offset: usize) -> Vec<FrameSymbol> {
+        let compiled_module = self.compiled_module();
+        let Some(index) = compiled_module
+            .module()
+            .defined_func_index(FuncIndex::from_u32(func_index))
+        else {
+            return Vec::new();
+        };
+        let Some(start) = compiled_module
+            .wasm_func_info(index)
+            .start_srcloc
+            .file_offset()
+        else {
+            return Vec::new();
+        };
+        match u32::try_from(func_offset) {
+            Ok(offset) => FrameSymbol::lookup(self, start.saturating_add(offset)),
+            Err(_) => Vec::new(),
+        }
+    }
+
     pub(crate) fn id(&self) -> CompiledModuleId {
         self.inner.module.unique_id()
     }
@@ -1242,6 +1284,12 @@ impl wasmtime_runtime::ModuleInfo for ModuleInner {
 
         Some(&info.stack_maps[index].stack_map)
     }
+
+    fn lookup_frame_locals(&self, pc: usize) -> Option<&wasmtime_environ::FrameLocals> {
+        let text_offset = pc - self.module.text().as_ptr() as usize;
+        let (index, _) = self.module.func_by_text_offset(text_offset)?;
+        self.module.wasm_func_info(index).frame_locals.as_ref()
+    }
 }
 
 /// A barebones implementation of ModuleRuntimeInfo that is useful for
@@ -1,16 +1,27 @@
-use crate::{AsContext, Module};
+#[cfg(feature = "component-model")]
+use crate::component::Component;
+use crate::{AsContext, FrameInfo as WasmFrameInfo, Module};
 use anyhow::Result;
 use fxprof_processed_profile::debugid::DebugId;
 use fxprof_processed_profile::{
-    CategoryHandle, CpuDelta, Frame, FrameFlags, FrameInfo, LibraryInfo, Profile,
-    ReferenceTimestamp, Symbol, SymbolTable, Timestamp,
+    CategoryHandle, CpuDelta, Frame, FrameFlags, FrameInfo, LibraryInfo, Profile, Symbol,
+    SymbolTable, Timestamp,
 };
+use std::collections::HashMap;
 use std::ops::Range;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
+use wasmtime_environ::DefinedFuncIndex;
 use wasmtime_jit::CompiledModule;
 use wasmtime_runtime::Backtrace;
 
+#[cfg(target_os = "linux")]
+mod perf_event;
+mod pprof;
+
+#[cfg(target_os = "linux")]
+pub use perf_event::{PerfEvent, PerfEventFormat, PerfEventProfiler};
+
 // TODO: collect more data
 // - Provide additional hooks for recording host-guest transitions, to be
 //   invoked from a Store::call_hook
@@ -53,6 +64,16 @@ use wasmtime_runtime::Backtrace;
 /// method is not currently async-signal-safe, so doing this correctly is not
 /// easy.
 ///
+/// # Threads
+///
+/// A profile can hold samples from several guests, such as one per thread or
+/// one per request in a server, as long as they all run the modules the
+/// profiler was created with. Each guest is recorded as a separate thread
+/// in the profile, created with [`GuestProfiler::add_thread`] and sampled
+/// with [`GuestProfiler::sample_thread`]. Sampling requires `&mut self`, so
+/// guests running concurrently will need to share the profiler behind a
+/// lock such as a [`Mutex`](std::sync::Mutex).
+///
 /// # Security
 ///
 /// Profiles produced using this profiler do not include any configuration
@@ -72,13 +93,73 @@ use wasmtime_runtime::Backtrace;
 /// should only include user-provided modules in profiles.
 #[derive(Debug)]
 pub struct GuestProfiler {
-    profile: Profile,
-    modules: Vec<(Range<usize>, fxprof_processed_profile::LibraryHandle)>,
-    process: fxprof_processed_profile::ProcessHandle,
-    thread: fxprof_processed_profile::ThreadHandle,
+    name: String,
+    code: Vec<ProfiledCode>,
+    /// The threads of the profile. The first one is the guest the profiler
+    /// was created for.
+    threads: Vec<ProfiledThread>,
+    interval: Duration,
     start: Instant,
+    start_time: SystemTime,
+    samples: Vec<Sample>,
 }
 
+/// Code whose frames appear in a profile.
+#[derive(Debug)]
+struct ProfiledCode {
+    range: Range<usize>,
+    lib: LibraryInfo,
+    /// The modules defined in this code, with the prefix of their function
+    /// names, if any.
+    modules: Vec<(Module, Option<String>)>,
+}
+
+/// A thread of a profile, which is running until it has an `end`.
+#[derive(Debug)]
+struct ProfiledThread {
+    name: String,
+    start: Timestamp,
+    end: Option<Timestamp>,
+}
+
+/// A sample of the stack of a thread.
+#[derive(Debug)]
+struct Sample {
+    thread: usize,
+    time: Timestamp,
+    /// The frames of the stack, oldest first, as indices into
+    /// `GuestProfiler::code` and offsets into its text.
+    frames: Vec<(usize, usize)>,
+}
+
+/// The formats a [`GuestProfiler`] can write profiles in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestProfileFormat {
+    /// The JSON [Firefox "processed profile format"][fmt], which may be
+    /// visualized at <https://profiler.firefox.com/>.
+    ///
+    /// [fmt]: https://github.com/firefox-devtools/profiler/blob/main/docs-developer/processed-profile-format.md
+    Firefox,
+    /// The protobuf-encoded [pprof] format, whose samples record both their
+    /// count and the wall-clock time they represent. Samples are labeled with
+    /// the name of their thread under the `thread` key.
+    ///
+    /// [pprof]: https://github.com/google/pprof/blob/main/proto/profile.proto
+    Pprof,
+    /// The collapsed stacks format of [FlameGraph], with a line for each
+    /// distinct stack listing the name of its thread and then its frames,
+    /// outermost first, separated by semicolons, followed by the number of
+    /// samples of that stack.
+    ///
+    /// [FlameGraph]: https://github.com/brendangregg/FlameGraph
+    Collapsed,
+}
+
+/// A thread within the profile of a [`GuestProfiler`], created with
+/// [`GuestProfiler::add_thread`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuestProfilerThread(usize);
+
 impl GuestProfiler {
     /// Begin profiling a new guest. When this function is called, the current
     /// wall-clock time is recorded as the start time for the guest.
@@ -96,42 +177,124 @@ impl GuestProfiler {
     /// "Security" section of the [`GuestProfiler`] documentation for guidance
     /// on what modules should not be included in this list.
     pub fn new(module_name: &str, interval: Duration, modules: Vec<(String, Module)>) -> Self {
-        let zero = ReferenceTimestamp::from_millis_since_unix_epoch(0.0);
-        let mut profile = Profile::new(module_name, zero, interval.into());
+        let libs = modules
+            .into_iter()
+            .filter_map(|(name, module)| module_library(name, &module))
+            .collect();
+        Self::with_libs(module_name, interval, libs)
+    }
+
+    /// Begin profiling a new guest component. This is like
+    /// [`GuestProfiler::new`], but frames from every core wasm module within
+    /// `component` appear in stack traces, including the adapter modules
+    /// Wasmtime generates to fuse calls between component instances.
+    ///
+    /// Functions are named in the profile after the core wasm module that
+    /// defines them, so `component_name` is only used to identify the
+    /// profile itself. The `extra_modules` are any other core modules whose
+    /// frames should appear in this profile, as with [`GuestProfiler::new`].
+    #[cfg(feature = "component-model")]
+    pub fn new_component(
+        component_name: &str,
+        interval: Duration,
+        component: Component,
+        extra_modules: Vec<(String, Module)>,
+    ) -> Self {
+        // All of a component's core modules share the component's text
+        // section, so they're registered as a single library.
+        let modules = component
+            .static_modules()
+            .enumerate()
+            .map(|(i, module)| {
+                let prefix = match module.name() {
+                    Some(name) => name.to_string(),
+                    None => format!("module{i}"),
+                };
+                (module.clone(), Some(prefix))
+            })
+            .collect();
+        let libs = library(component_name.to_string(), component.text(), modules)
+            .into_iter()
+            .chain(
+                extra_modules
+                    .into_iter()
+                    .filter_map(|(name, module)| module_library(name, &module)),
+            )
+            .collect();
+        Self::with_libs(component_name, interval, libs)
+    }
 
-        let mut modules: Vec<_> = modules
+    fn with_libs(name: &str, interval: Duration, libs: Vec<Library>) -> Self {
+        let mut code: Vec<_> = libs
             .into_iter()
-            .filter_map(|(name, module)| {
-                let compiled = module.compiled_module();
-                let text = compiled.text().as_ptr_range();
-                let address_range = text.start as usize..text.end as usize;
-                module_symbols(name, compiled).map(|lib| (address_range, profile.add_lib(lib)))
+            .map(|(range, lib, modules)| ProfiledCode {
+                range,
+                lib,
+                modules,
             })
             .collect();
 
-        modules.sort_unstable_by_key(|(range, _)| range.start);
+        code.sort_unstable_by_key(|code| code.range.start);
 
-        profile.set_reference_timestamp(std::time::SystemTime::now().into());
-        let process = profile.add_process(module_name, 0, Timestamp::from_nanos_since_reference(0));
-        let thread = profile.add_thread(process, 0, Timestamp::from_nanos_since_reference(0), true);
-        let start = Instant::now();
         Self {
-            profile,
-            modules,
-            process,
-            thread,
-            start,
+            name: name.to_string(),
+            code,
+            threads: vec![ProfiledThread {
+                name: name.to_string(),
+                start: Timestamp::from_nanos_since_reference(0),
+                end: None,
+            }],
+            interval,
+            start: Instant::now(),
+            start_time: SystemTime::now(),
+            samples: Vec::new(),
         }
     }
 
+    fn now(&self) -> Timestamp {
+        Timestamp::from_nanos_since_reference(self.start.elapsed().as_nanos().try_into().unwrap())
+    }
+
+    /// Add a new thread to this profile for a guest that starts running
+    /// now, such as a guest in another [`Store`](crate::Store) or on another
+    /// OS thread.
+    ///
+    /// The `name` is shown in the profile to tell threads apart. The
+    /// returned thread should be passed to [`GuestProfiler::sample_thread`]
+    /// to record samples for this guest, and to
+    /// [`GuestProfiler::finish_thread`] when the guest is done.
+    pub fn add_thread(&mut self, name: &str) -> GuestProfilerThread {
+        let start = self.now();
+        self.threads.push(ProfiledThread {
+            name: name.to_string(),
+            start,
+            end: None,
+        });
+        GuestProfilerThread(self.threads.len() - 1)
+    }
+
+    /// Record that the guest of `thread` has finished running.
+    ///
+    /// Threads which are still running when [`GuestProfiler::finish`] is
+    /// called are finished then.
+    pub fn finish_thread(&mut self, thread: GuestProfilerThread) {
+        let now = self.now();
+        self.threads[thread.0].end = Some(now);
+    }
+
     /// Add a sample to the profile. This function collects a backtrace from
     /// any stack frames for allowed modules on the current stack. It should
     /// typically be called from a callback registered using
     /// [`Store::epoch_deadline_callback()`](crate::Store::epoch_deadline_callback).
     pub fn sample(&mut self, store: impl AsContext) {
-        let now = Timestamp::from_nanos_since_reference(
-            self.start.elapsed().as_nanos().try_into().unwrap(),
-        );
+        self.sample_thread(GuestProfilerThread(0), store)
+    }
+
+    /// Add a sample to the given `thread` of the profile. This is like
+    /// [`GuestProfiler::sample`], for guests other than the one this profiler
+    /// was created for.
+    pub fn sample_thread(&mut self, thread: GuestProfilerThread, store: impl AsContext) {
+        let time = self.now();
 
         let backtrace = Backtrace::new(store.as_context().0.vmruntime_limits());
         let frames = backtrace
@@ -141,26 +304,23 @@ impl GuestProfiler {
             .rev()
             .filter_map(|frame| {
                 // Find the first module whose start address includes this PC.
-                let module_idx = self
-                    .modules
-                    .partition_point(|(range, _)| range.start > frame.pc());
-                if let Some((range, lib)) = self.modules.get(module_idx) {
-                    if range.contains(&frame.pc()) {
-                        return Some(FrameInfo {
-                            frame: Frame::RelativeAddressFromReturnAddress(
-                                *lib,
-                                u32::try_from(frame.pc() - range.start).unwrap(),
-                            ),
-                            category_pair: CategoryHandle::OTHER.into(),
-                            flags: FrameFlags::empty(),
-                        });
-                    }
+                let code_idx = self
+                    .code
+                    .partition_point(|code| code.range.start > frame.pc());
+                let code = self.code.get(code_idx)?;
+                if code.range.contains(&frame.pc()) {
+                    Some((code_idx, frame.pc() - code.range.start))
+                } else {
+                    None
                 }
-                None
-            });
+            })
+            .collect::<Vec<_>>();
 
-        self.profile
-            .add_sample(self.thread, now, frames, CpuDelta::ZERO, 1);
+        self.samples.push(Sample {
+            thread: thread.0,
+            time,
+            frames,
+        });
     }
 
     /// When the guest finishes running, call this function to write the
@@ -169,37 +329,251 @@ impl GuestProfiler {
     /// be visualized at <https://profiler.firefox.com/>.
     ///
     /// [fmt]: https://github.com/firefox-devtools/profiler/blob/main/docs-developer/processed-profile-format.md
-    pub fn finish(mut self, output: impl std::io::Write) -> Result<()> {
-        let now = Timestamp::from_nanos_since_reference(
-            self.start.elapsed().as_nanos().try_into().unwrap(),
-        );
-        self.profile.set_thread_end_time(self.thread, now);
-        self.profile.set_process_end_time(self.process, now);
-
-        serde_json::to_writer(output, &self.profile)?;
+    pub fn finish(self, output: impl std::io::Write) -> Result<()> {
+        self.finish_with_format(GuestProfileFormat::Firefox, output)
+    }
+
+    /// When the guest finishes running, call this function to write the
+    /// profile to the given `output` in the given `format`.
+    ///
+    /// Functions are named after the `name` section of their module when it
+    /// has one. In the pprof and collapsed formats, frames are additionally
+    /// attributed to source lines when the module has DWARF debugging
+    /// information and was compiled with
+    /// [`Config::wasm_backtrace_details`](crate::Config::wasm_backtrace_details)
+    /// enabled.
+    pub fn finish_with_format(
+        mut self,
+        format: GuestProfileFormat,
+        mut output: impl std::io::Write,
+    ) -> Result<()> {
+        let now = self.now();
+        for thread in self.threads.iter_mut() {
+            thread.end.get_or_insert(now);
+        }
+
+        match format {
+            GuestProfileFormat::Firefox => {
+                serde_json::to_writer(output, &self.firefox_profile(now))?
+            }
+            GuestProfileFormat::Pprof => {
+                let interval = i64::try_from(self.interval.as_nanos()).unwrap();
+                let mut pprof = pprof::PprofBuilder::new(
+                    &[("samples", "count"), ("wall", "nanoseconds")],
+                    ("wall", "nanoseconds"),
+                    interval,
+                );
+                let mut locations = HashMap::new();
+                for sample in &self.samples {
+                    // pprof lists the innermost frame first.
+                    let stack = sample
+                        .frames
+                        .iter()
+                        .rev()
+                        .map(|frame| {
+                            *locations.entry(*frame).or_insert_with(|| {
+                                let loc = self.frame_location(*frame);
+                                let file = loc.file.as_deref().unwrap_or(loc.code_name);
+                                pprof.location(&loc.func_name, file, loc.line)
+                            })
+                        })
+                        .collect();
+                    let thread = &self.threads[sample.thread].name;
+                    pprof.add_sample(stack, &[("thread", thread)], &[1, interval]);
+                }
+                let time = self
+                    .start_time
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default();
+                pprof.finish(
+                    time.as_nanos().try_into().unwrap(),
+                    self.start.elapsed().as_nanos().try_into().unwrap(),
+                    output,
+                )?;
+            }
+            GuestProfileFormat::Collapsed => {
+                let mut names = HashMap::new();
+                let mut stacks = HashMap::<String, u64>::new();
+                for sample in &self.samples {
+                    let thread = self.threads[sample.thread].name.replace(';', ":");
+                    let frames = sample.frames.iter().map(|frame| {
+                        names
+                            .entry(*frame)
+                            .or_insert_with(|| self.frame_location(*frame).collapsed_name())
+                            .clone()
+                    });
+                    let mut stack = std::iter::once(thread).chain(frames).collect::<Vec<_>>();
+                    // Samples without any frames from the profiled modules
+                    // still take up time.
+                    if stack.len() == 1 {
+                        stack.push("<host>".to_string());
+                    }
+                    *stacks.entry(stack.join(";")).or_default() += 1;
+                }
+                let mut stacks = stacks.into_iter().collect::<Vec<_>>();
+                stacks.sort();
+                for (stack, count) in stacks {
+                    writeln!(output, "{stack} {count}")?;
+                }
+                output.flush()?;
+            }
+        }
         Ok(())
     }
+
+    /// Builds the Firefox profile of the samples, for a profile finishing at
+    /// `end`.
+    fn firefox_profile(&self, end: Timestamp) -> Profile {
+        let mut profile = Profile::new(&self.name, self.start_time.into(), self.interval.into());
+        let libs = self
+            .code
+            .iter()
+            .map(|code| profile.add_lib(code.lib.clone()))
+            .collect::<Vec<_>>();
+        let process = profile.add_process(&self.name, 0, Timestamp::from_nanos_since_reference(0));
+        let threads = self
+            .threads
+            .iter()
+            .enumerate()
+            .map(|(i, thread)| {
+                let handle = profile.add_thread(process, 0, thread.start, i == 0);
+                // The guest the profiler was created for is named after the
+                // process.
+                if i != 0 {
+                    profile.set_thread_name(handle, &thread.name);
+                }
+                profile.set_thread_end_time(handle, thread.end.unwrap_or(end));
+                handle
+            })
+            .collect::<Vec<_>>();
+        for sample in &self.samples {
+            let frames = sample.frames.iter().map(|(code_idx, offset)| FrameInfo {
+                frame: Frame::RelativeAddressFromReturnAddress(
+                    libs[*code_idx],
+                    u32::try_from(*offset).unwrap(),
+                ),
+                category_pair: CategoryHandle::OTHER.into(),
+                flags: FrameFlags::empty(),
+            });
+            profile.add_sample(
+                threads[sample.thread],
+                sample.time,
+                frames,
+                CpuDelta::ZERO,
+                1,
+            );
+        }
+        profile.set_process_end_time(process, end);
+        profile
+    }
+
+    /// Returns where the frame at `offset` within `self.code[code_idx]` is.
+    fn frame_location(&self, (code_idx, offset): (usize, usize)) -> FrameLocation<'_> {
+        let code = &self.code[code_idx];
+        // Frames are at return addresses, so look up the call instruction
+        // just before them instead.
+        let offset = offset.saturating_sub(1);
+        for (module, prefix) in &code.modules {
+            let compiled = module.compiled_module();
+            let Some((defined_idx, _)) = compiled.func_by_text_offset(offset) else {
+                continue;
+            };
+            let (file, line) = WasmFrameInfo::new(module.clone(), offset)
+                .and_then(|frame| {
+                    let symbol = frame.symbols().first()?;
+                    Some((symbol.file().map(|file| file.to_string()), symbol.line()))
+                })
+                .unwrap_or_default();
+            return FrameLocation {
+                code_name: &code.lib.name,
+                func_name: function_name(compiled, defined_idx, prefix.as_deref()),
+                file,
+                line,
+            };
+        }
+        FrameLocation {
+            code_name: &code.lib.name,
+            func_name: format!("{}+{offset:#x}", code.lib.name),
+            file: None,
+            line: None,
+        }
+    }
 }
 
-fn module_symbols(name: String, compiled: &CompiledModule) -> Option<LibraryInfo> {
-    let symbols = Vec::from_iter(compiled.finished_functions().map(|(defined_idx, _)| {
-        let loc = compiled.func_loc(defined_idx);
-        let func_idx = compiled.module().func_index(defined_idx);
-        let name = match compiled.func_name(func_idx) {
-            None => format!("wasm_function_{}", defined_idx.as_u32()),
-            Some(name) => name.to_string(),
+/// The function, and source line if known, of a frame in a profile.
+struct FrameLocation<'a> {
+    code_name: &'a str,
+    func_name: String,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+impl FrameLocation<'_> {
+    /// Returns the name of this frame in the collapsed stacks format, which
+    /// can't contain semicolons.
+    fn collapsed_name(&self) -> String {
+        let name = match (&self.file, self.line) {
+            (Some(file), Some(line)) => format!("{} ({file}:{line})", self.func_name),
+            _ => self.func_name.clone(),
         };
+        name.replace(';', ":")
+    }
+}
+
+/// A library to add to a profile, along with the address range of its code
+/// and the modules it contains.
+type Library = (Range<usize>, LibraryInfo, Vec<(Module, Option<String>)>);
+
+fn module_library(name: String, module: &Module) -> Option<Library> {
+    library(
+        name,
+        module.compiled_module().text(),
+        vec![(module.clone(), None)],
+    )
+}
+
+/// Returns the name of a function in a profile.
+fn function_name(
+    compiled: &CompiledModule,
+    defined_idx: DefinedFuncIndex,
+    prefix: Option<&str>,
+) -> String {
+    let func_idx = compiled.module().func_index(defined_idx);
+    let name = match compiled.func_name(func_idx) {
+        None => format!("wasm_function_{}", defined_idx.as_u32()),
+        Some(name) => name.to_string(),
+    };
+    match prefix {
+        None => name,
+        Some(prefix) => format!("{prefix}!{name}"),
+    }
+}
+
+/// Returns a symbol for each function defined by `module`, at its offset
+/// within the text section containing it.
+fn function_symbols(module: &Module, prefix: Option<&str>) -> Vec<Symbol> {
+    let compiled = module.compiled_module();
+    Vec::from_iter(compiled.finished_functions().map(|(defined_idx, _)| {
+        let loc = compiled.func_loc(defined_idx);
         Symbol {
             address: loc.start,
             size: Some(loc.length),
-            name,
+            name: function_name(compiled, defined_idx, prefix),
         }
-    }));
+    }))
+}
+
+fn library(name: String, text: &[u8], modules: Vec<(Module, Option<String>)>) -> Option<Library> {
+    let symbols = modules
+        .iter()
+        .flat_map(|(module, prefix)| function_symbols(module, prefix.as_deref()))
+        .collect::<Vec<_>>();
     if symbols.is_empty() {
         return None;
     }
 
-    Some(LibraryInfo {
+    let text = text.as_ptr_range();
+    let lib = LibraryInfo {
         name,
         debug_name: String::new(),
         path: String::new(),
@@ -208,5 +582,6 @@ fn module_symbols(name: String, compiled: &CompiledModule) -> Option<LibraryInfo
         code_id: None,
         arch: None,
         symbol_table: Some(Arc::new(SymbolTable::new(symbols))),
-    })
+    };
+    Some((text.start as usize..text.end as usize, lib, modules))
 }
@@ -0,0 +1,693 @@
+//! Sampling of hardware performance counters with Linux's `perf_event_open`.
+
+use super::pprof::PprofBuilder;
+use crate::AsContext;
+use anyhow::{anyhow, bail, Context, Result};
+use fxprof_processed_profile::{
+    CategoryHandle, CpuDelta, Frame, FrameFlags, FrameInfo, Profile, ReferenceTimestamp, Timestamp,
+};
+use std::collections::HashMap;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// A hardware event sampled by a [`PerfEventProfiler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PerfEvent {
+    /// CPU cycles.
+    Cycles,
+    /// Retired instructions.
+    Instructions,
+    /// Cache misses, usually of the last level cache.
+    CacheMisses,
+    /// Mispredicted branch instructions.
+    BranchMisses,
+}
+
+impl PerfEvent {
+    /// Returns the name `perf` uses for this event, such as `cache-misses`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PerfEvent::Cycles => "cycles",
+            PerfEvent::Instructions => "instructions",
+            PerfEvent::CacheMisses => "cache-misses",
+            PerfEvent::BranchMisses => "branch-misses",
+        }
+    }
+
+    fn config(&self) -> u64 {
+        match self {
+            PerfEvent::Cycles => PERF_COUNT_HW_CPU_CYCLES,
+            PerfEvent::Instructions => PERF_COUNT_HW_INSTRUCTIONS,
+            PerfEvent::CacheMisses => PERF_COUNT_HW_CACHE_MISSES,
+            PerfEvent::BranchMisses => PERF_COUNT_HW_BRANCH_MISSES,
+        }
+    }
+}
+
+impl FromStr for PerfEvent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "cycles" => Ok(PerfEvent::Cycles),
+            "instructions" => Ok(PerfEvent::Instructions),
+            "cache-misses" => Ok(PerfEvent::CacheMisses),
+            "branch-misses" => Ok(PerfEvent::BranchMisses),
+            _ => bail!("unknown performance counter event: {s}"),
+        }
+    }
+}
+
+/// The formats a [`PerfEventProfiler`] can write profiles in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfEventFormat {
+    /// The JSON [Firefox "processed profile format"][fmt], which may be
+    /// visualized at <https://profiler.firefox.com/>. Each event is recorded
+    /// as a separate thread.
+    ///
+    /// [fmt]: https://github.com/firefox-devtools/profiler/blob/main/docs-developer/processed-profile-format.md
+    Firefox,
+    /// The protobuf-encoded [pprof] format, with a sample type for each
+    /// event.
+    ///
+    /// [pprof]: https://github.com/google/pprof/blob/main/proto/profile.proto
+    Pprof,
+}
+
+/// Profiles WebAssembly guests by sampling hardware performance counters,
+/// using Linux's `perf_event_open`.
+///
+/// Unlike [`GuestProfiler`](crate::GuestProfiler), which samples stacks at
+/// the points where you interrupt the guest, this profiler has the kernel
+/// record the stack every time a counter, such as the number of CPU cycles,
+/// crosses a multiple of the sampling period. This works without having the
+/// `perf` tool installed, but requires that the
+/// `/proc/sys/kernel/perf_event_paranoid` setting allows unprivileged
+/// processes to profile themselves, which it does by default.
+///
+/// Counters only count events on the thread that created the profiler, and
+/// only in user space. The kernel writes samples to a fixed-size buffer, so
+/// you'll need to call [`PerfEventProfiler::collect`] regularly while the
+/// guest runs, such as from a callback registered with
+/// [`Store::epoch_deadline_callback()`](crate::Store::epoch_deadline_callback),
+/// and once more when it's done. Samples that don't fit in the buffer before
+/// they're collected are lost.
+///
+/// Samples are attributed to the wasm functions on the stack by looking up
+/// their addresses in the modules of the store they're collected with, and
+/// any other frames are omitted. Samples taken outside of wasm are recorded
+/// with a single `<host>` frame. Stacks are recovered by walking frame
+/// pointers, so they may be truncated when host code which omits frame
+/// pointers is on the stack.
+#[derive(Debug)]
+pub struct PerfEventProfiler {
+    counters: Vec<Counter>,
+    period: u64,
+    frames: Vec<(String, String)>,
+    frame_ids: HashMap<(String, String), usize>,
+    samples: Vec<Sample>,
+    lost: u64,
+    start: Duration,
+    start_time: SystemTime,
+}
+
+#[derive(Debug)]
+struct Sample {
+    event: usize,
+    time: Duration,
+    /// Indices into `frames`, innermost frame first.
+    stack: Vec<usize>,
+}
+
+impl PerfEventProfiler {
+    /// Starts sampling the `events` on the current thread, once every
+    /// `period` occurrences of each event.
+    pub fn new(events: &[PerfEvent], period: u64) -> Result<Self> {
+        if events.is_empty() {
+            bail!("at least one performance counter event must be sampled");
+        }
+        if period == 0 {
+            bail!("the sampling period must be greater than zero");
+        }
+        let counters = events
+            .iter()
+            .map(|event| {
+                Counter::open(*event, period).with_context(|| {
+                    format!(
+                        "failed to open the `{}` performance counter; check that \
+                         `/proc/sys/kernel/perf_event_paranoid` is 2 or less",
+                        event.name()
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for counter in &counters {
+            counter.enable()?;
+        }
+        Ok(PerfEventProfiler {
+            counters,
+            period,
+            frames: Vec::new(),
+            frame_ids: HashMap::new(),
+            samples: Vec::new(),
+            lost: 0,
+            start: monotonic_now(),
+            start_time: SystemTime::now(),
+        })
+    }
+
+    /// Collects the samples recorded since the last call, attributing them
+    /// to the functions of the modules registered in `store`.
+    pub fn collect(&mut self, store: impl AsContext) {
+        let store = store.as_context().0;
+        for event in 0..self.counters.len() {
+            for record in self.counters[event].drain() {
+                match record {
+                    Record::Sample { time, ips } => {
+                        let mut stack = Vec::new();
+                        for ip in ips {
+                            if let Some((frame, _)) = store.modules().lookup_frame_info(ip) {
+                                let module = frame.module().name().unwrap_or("").to_string();
+                                let func = match frame.func_name() {
+                                    Some(name) => name.to_string(),
+                                    None => format!("wasm-function[{}]", frame.func_index()),
+                                };
+                                stack.push(self.frame(module, func));
+                            }
+                        }
+                        if stack.is_empty() {
+                            stack.push(self.frame(String::new(), "<host>".to_string()));
+                        }
+                        self.samples.push(Sample {
+                            event,
+                            time: time.saturating_sub(self.start),
+                            stack,
+                        });
+                    }
+                    Record::Lost(lost) => self.lost += lost,
+                }
+            }
+        }
+    }
+
+    fn frame(&mut self, module: String, func: String) -> usize {
+        let key = (module, func);
+        if let Some(id) = self.frame_ids.get(&key) {
+            return *id;
+        }
+        let id = self.frames.len();
+        self.frames.push(key.clone());
+        self.frame_ids.insert(key, id);
+        id
+    }
+
+    /// Returns the number of samples lost so far because they weren't
+    /// collected before the kernel's buffer filled up.
+    pub fn lost_samples(&self) -> u64 {
+        self.lost
+    }
+
+    /// Stops sampling and writes the profile of the samples collected so far
+    /// to `output` in the given `format`.
+    pub fn finish(self, format: PerfEventFormat, output: impl std::io::Write) -> Result<()> {
+        for counter in &self.counters {
+            counter.disable()?;
+        }
+        if self.lost > 0 {
+            log::warn!(
+                "{} performance counter samples were lost; collect samples more often",
+                self.lost
+            );
+        }
+        let duration = monotonic_now().saturating_sub(self.start);
+        match format {
+            PerfEventFormat::Firefox => self.finish_firefox(duration, output),
+            PerfEventFormat::Pprof => self.finish_pprof(duration, output),
+        }
+    }
+
+    fn frame_name(&self, frame: usize) -> String {
+        match &self.frames[frame] {
+            (module, func) if module.is_empty() => func.clone(),
+            (module, func) => format!("{module}!{func}"),
+        }
+    }
+
+    fn finish_firefox(self, duration: Duration, output: impl std::io::Write) -> Result<()> {
+        let mut profile = Profile::new(
+            "wasmtime",
+            ReferenceTimestamp::from(self.start_time),
+            Duration::from_millis(1).into(),
+        );
+        let zero = Timestamp::from_nanos_since_reference(0);
+        let end = Timestamp::from_nanos_since_reference(duration.as_nanos().try_into().unwrap());
+        let process = profile.add_process("wasmtime", std::process::id(), zero);
+        let threads = self
+            .counters
+            .iter()
+            .enumerate()
+            .map(|(i, counter)| {
+                let thread = profile.add_thread(process, i as u32, zero, i == 0);
+                profile.set_thread_name(thread, counter.event.name());
+                profile.set_thread_end_time(thread, end);
+                thread
+            })
+            .collect::<Vec<_>>();
+        let labels = (0..self.frames.len())
+            .map(|frame| profile.intern_string(&self.frame_name(frame)))
+            .collect::<Vec<_>>();
+        for sample in &self.samples {
+            // The profile lists the oldest frame first.
+            let frames = sample.stack.iter().rev().map(|frame| FrameInfo {
+                frame: Frame::Label(labels[*frame]),
+                category_pair: CategoryHandle::OTHER.into(),
+                flags: FrameFlags::empty(),
+            });
+            let time =
+                Timestamp::from_nanos_since_reference(sample.time.as_nanos().try_into().unwrap());
+            profile.add_sample(threads[sample.event], time, frames, CpuDelta::ZERO, 1);
+        }
+        profile.set_process_end_time(process, end);
+
+        serde_json::to_writer(output, &profile)?;
+        Ok(())
+    }
+
+    fn finish_pprof(self, duration: Duration, output: impl std::io::Write) -> Result<()> {
+        let sample_types = self
+            .counters
+            .iter()
+            .map(|counter| (counter.event.name(), "count"))
+            .collect::<Vec<_>>();
+        let mut pprof = PprofBuilder::new(
+            &sample_types,
+            (self.counters[0].event.name(), "count"),
+            self.period as i64,
+        );
+        let locations = self
+            .frames
+            .iter()
+            .map(|(module, func)| pprof.location(func, module, None))
+            .collect::<Vec<_>>();
+        let mut values = vec![0; self.counters.len()];
+        for sample in &self.samples {
+            values[sample.event] = self.period as i64;
+            let stack = sample.stack.iter().map(|frame| locations[*frame]).collect();
+            pprof.add_sample(stack, &[], &values);
+            values[sample.event] = 0;
+        }
+        let time = self
+            .start_time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        pprof.finish(
+            time.as_nanos().try_into().unwrap(),
+            duration.as_nanos().try_into().unwrap(),
+            output,
+        )?;
+        Ok(())
+    }
+}
+
+/// A counter opened with `perf_event_open`, along with the ring buffer the
+/// kernel writes its samples to.
+#[derive(Debug)]
+struct Counter {
+    event: PerfEvent,
+    fd: OwnedFd,
+    mmap: *mut u8,
+    mmap_len: usize,
+    page_size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Record {
+    Sample { time: Duration, ips: Vec<usize> },
+    Lost(u64),
+}
+
+/// The number of pages in a counter's ring buffer, which must be a power of
+/// two.
+const RING_BUFFER_PAGES: usize = 64;
+
+impl Counter {
+    fn open(event: PerfEvent, period: u64) -> Result<Counter> {
+        let attr = PerfEventAttr {
+            type_: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config: event.config(),
+            sample_period: period,
+            sample_type: PERF_SAMPLE_IP | PERF_SAMPLE_TIME | PERF_SAMPLE_CALLCHAIN,
+            flags: ATTR_FLAG_DISABLED
+                | ATTR_FLAG_EXCLUDE_KERNEL
+                | ATTR_FLAG_EXCLUDE_HV
+                | ATTR_FLAG_EXCLUDE_CALLCHAIN_KERNEL
+                | ATTR_FLAG_USE_CLOCKID,
+            clockid: libc::CLOCK_MONOTONIC,
+            ..PerfEventAttr::default()
+        };
+
+        // Safety: `attr` is a valid `perf_event_attr` whose `size` is that of
+        // the fields it defines. Profiling the calling thread (pid 0) on any
+        // CPU (-1) outside of a group (-1) requires no other arguments.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0,
+                -1,
+                -1,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        // Safety: the syscall succeeded so `fd` is a new file descriptor.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+
+        // Safety: `sysconf` has no preconditions.
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let mmap_len = page_size * (1 + RING_BUFFER_PAGES);
+        // Safety: mapping a perf event's file descriptor shares its metadata
+        // page and ring buffer with the kernel.
+        let mmap = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                mmap_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if mmap == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        Ok(Counter {
+            event,
+            fd,
+            mmap: mmap.cast(),
+            mmap_len,
+            page_size,
+        })
+    }
+
+    fn ioctl(&self, request: libc::c_ulong) -> Result<()> {
+        // The type of `request` differs between libcs.
+        #[allow(trivial_numeric_casts)]
+        let request = request as _;
+        // Safety: `request` is one of the perf event ioctls, which take no
+        // argument.
+        if unsafe { libc::ioctl(self.fd.as_raw_fd(), request, 0) } < 0 {
+            return Err(anyhow!(std::io::Error::last_os_error()).context(format!(
+                "failed to control the `{}` counter",
+                self.event.name()
+            )));
+        }
+        Ok(())
+    }
+
+    fn enable(&self) -> Result<()> {
+        self.ioctl(PERF_EVENT_IOC_ENABLE)
+    }
+
+    fn disable(&self) -> Result<()> {
+        self.ioctl(PERF_EVENT_IOC_DISABLE)
+    }
+
+    /// Reads the records the kernel has written to the ring buffer since the
+    /// last call, and frees their space for new records.
+    fn drain(&mut self) -> Vec<Record> {
+        // Safety: the metadata page of the mapping starts with a
+        // `perf_event_mmap_page`, whose `data_head` and `data_tail` fields are
+        // shared with the kernel and are naturally aligned.
+        let (head, tail) = unsafe {
+            (
+                &*(self.mmap.add(DATA_HEAD_OFFSET) as *const AtomicU64),
+                &*(self.mmap.add(DATA_TAIL_OFFSET) as *const AtomicU64),
+            )
+        };
+        // The kernel's writes to the buffer happen before it updates the
+        // head, and our reads must happen before we update the tail.
+        let end = head.load(Ordering::Acquire);
+        let start = tail.load(Ordering::Relaxed);
+        // Safety: the ring buffer follows the metadata page, and the kernel
+        // doesn't write to the part of it between the tail and the head.
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                self.mmap.add(self.page_size),
+                self.mmap_len - self.page_size,
+            )
+        };
+        let (records, pos) = parse_records(data, start, end);
+        tail.store(pos, Ordering::Release);
+        records
+    }
+}
+
+/// Parses the records in the ring buffer `data` between the positions `pos`
+/// and `end`, which wrap around the end of the buffer. Returns the records
+/// along with the position after the last complete one.
+fn parse_records(data: &[u8], mut pos: u64, end: u64) -> (Vec<Record>, u64) {
+    let mut records = Vec::new();
+    while pos < end {
+        let header = read_ring(data, pos, 8);
+        let ty = u32::from_ne_bytes(header[0..4].try_into().unwrap());
+        let size = u16::from_ne_bytes(header[6..8].try_into().unwrap()) as u64;
+        if size < 8 || pos + size > end {
+            break;
+        }
+        let body = read_ring(data, pos + 8, size as usize - 8);
+        let words = body
+            .chunks_exact(8)
+            .map(|word| u64::from_ne_bytes(word.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        match ty {
+            // The fields of a sample are in the order of their bits in
+            // `sample_type`: `ip`, `time`, then `nr` and `ips[nr]`.
+            PERF_RECORD_SAMPLE if words.len() >= 3 => {
+                let time = Duration::from_nanos(words[1]);
+                let nr = (words[2] as usize).min(words.len() - 3);
+                let mut ips = words[3..][..nr]
+                    .iter()
+                    .filter(|ip| **ip < PERF_CONTEXT_MAX)
+                    .map(|ip| *ip as usize)
+                    .collect::<Vec<_>>();
+                if ips.is_empty() {
+                    ips.push(words[0] as usize);
+                }
+                records.push(Record::Sample { time, ips });
+            }
+            PERF_RECORD_LOST if words.len() >= 2 => records.push(Record::Lost(words[1])),
+            _ => {}
+        }
+        pos += size;
+    }
+    (records, pos)
+}
+
+/// Copies `len` bytes at position `pos` of the ring buffer `data`, which may
+/// wrap around its end.
+fn read_ring(data: &[u8], pos: u64, len: usize) -> Vec<u8> {
+    let start = (pos % data.len() as u64) as usize;
+    let first = len.min(data.len() - start);
+    let mut bytes = Vec::with_capacity(len);
+    bytes.extend_from_slice(&data[start..][..first]);
+    bytes.extend_from_slice(&data[..len - first]);
+    bytes
+}
+
+// Safety: the mapping is only accessed through `&mut self` or for reads of
+// data the kernel doesn't change.
+unsafe impl Send for Counter {}
+unsafe impl Sync for Counter {}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        // Safety: `mmap` was mapped with `mmap_len` by `Counter::open`.
+        unsafe {
+            libc::munmap(self.mmap.cast(), self.mmap_len);
+        }
+    }
+}
+
+fn monotonic_now() -> Duration {
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Safety: `now` is a valid `timespec` to write to.
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now);
+    }
+    Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+}
+
+/// The `perf_event_attr` structure, as of `PERF_ATTR_SIZE_VER5`.
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+    type_: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+    config2: u64,
+    branch_sample_type: u64,
+    sample_regs_user: u64,
+    sample_stack_user: u32,
+    clockid: i32,
+    sample_regs_intr: u64,
+    aux_watermark: u32,
+    sample_max_stack: u16,
+    reserved: u16,
+}
+
+const PERF_TYPE_HARDWARE: u32 = 0;
+
+const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+const PERF_SAMPLE_IP: u64 = 1 << 0;
+const PERF_SAMPLE_TIME: u64 = 1 << 2;
+const PERF_SAMPLE_CALLCHAIN: u64 = 1 << 5;
+
+const ATTR_FLAG_DISABLED: u64 = 1 << 0;
+const ATTR_FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+const ATTR_FLAG_EXCLUDE_HV: u64 = 1 << 6;
+const ATTR_FLAG_EXCLUDE_CALLCHAIN_KERNEL: u64 = 1 << 21;
+const ATTR_FLAG_USE_CLOCKID: u64 = 1 << 25;
+
+const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+
+const PERF_RECORD_LOST: u32 = 2;
+const PERF_RECORD_SAMPLE: u32 = 9;
+
+/// Callchain entries at or above this value mark the start of the kernel or
+/// user part of the chain rather than being addresses.
+const PERF_CONTEXT_MAX: u64 = -4095i64 as u64;
+
+/// Offsets of `data_head` and `data_tail` in `perf_event_mmap_page`.
+const DATA_HEAD_OFFSET: usize = 1024;
+const DATA_TAIL_OFFSET: usize = 1032;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(ty: u32, words: &[u64]) -> Vec<u8> {
+        let size = 8 + words.len() as u16 * 8;
+        let mut out = Vec::new();
+        out.extend_from_slice(&ty.to_ne_bytes());
+        out.extend_from_slice(&0u16.to_ne_bytes());
+        out.extend_from_slice(&size.to_ne_bytes());
+        for word in words {
+            out.extend_from_slice(&word.to_ne_bytes());
+        }
+        out
+    }
+
+    /// Writes `bytes` to the ring buffer `data` at position `pos`, wrapping
+    /// around its end like the kernel does.
+    fn write_ring(data: &mut [u8], pos: u64, bytes: &[u8]) {
+        for (i, b) in bytes.iter().enumerate() {
+            let len = data.len() as u64;
+            data[((pos + i as u64) % len) as usize] = *b;
+        }
+    }
+
+    #[test]
+    fn parses_records() {
+        const PERF_CONTEXT_USER: u64 = -512i64 as u64;
+        let mut data = Vec::new();
+        data.extend(record(
+            PERF_RECORD_SAMPLE,
+            &[0x10, 100, 3, PERF_CONTEXT_USER, 0x10, 0x20],
+        ));
+        data.extend(record(PERF_RECORD_LOST, &[1, 7]));
+        // A sample without a callchain falls back to its `ip`.
+        data.extend(record(PERF_RECORD_SAMPLE, &[0x30, 200, 0]));
+        // Other records are skipped.
+        data.extend(record(3, &[1, 2]));
+        let end = data.len() as u64;
+        data.resize(256, 0);
+
+        let (records, pos) = parse_records(&data, 0, end);
+        assert_eq!(pos, end);
+        assert_eq!(
+            records,
+            [
+                Record::Sample {
+                    time: Duration::from_nanos(100),
+                    ips: vec![0x10, 0x20],
+                },
+                Record::Lost(7),
+                Record::Sample {
+                    time: Duration::from_nanos(200),
+                    ips: vec![0x30],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_records_wrapping_around() {
+        let mut data = vec![0; 128];
+        let first = record(PERF_RECORD_SAMPLE, &[0x10, 1, 1, 0x11]);
+        let second = record(PERF_RECORD_SAMPLE, &[0x20, 2, 2, 0x21, 0x22]);
+        // Positions keep growing past the end of the buffer, and the second
+        // record's header wraps around it.
+        let start = 128 * 3 + 84;
+        write_ring(&mut data, start, &first);
+        write_ring(&mut data, start + first.len() as u64, &second);
+        let end = start + (first.len() + second.len()) as u64;
+
+        let (records, pos) = parse_records(&data, start, end);
+        assert_eq!(pos, end);
+        assert_eq!(
+            records,
+            [
+                Record::Sample {
+                    time: Duration::from_nanos(1),
+                    ips: vec![0x11],
+                },
+                Record::Sample {
+                    time: Duration::from_nanos(2),
+                    ips: vec![0x21, 0x22],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_at_incomplete_records() {
+        let mut data = record(PERF_RECORD_LOST, &[1, 7]);
+        data.extend(record(PERF_RECORD_LOST, &[1, 8]));
+        let end = data.len() as u64;
+        data.resize(64, 0);
+
+        // The head is in the middle of the second record.
+        let (records, pos) = parse_records(&data, 0, end - 8);
+        assert_eq!(records, [Record::Lost(7)]);
+        assert_eq!(pos, 24);
+
+        // A corrupt header ends parsing rather than looping forever.
+        let (records, pos) = parse_records(&[0; 64], 0, 32);
+        assert!(records.is_empty());
+        assert_eq!(pos, 0);
+    }
+}
@@ -0,0 +1,226 @@
+//! A minimal encoder for the [pprof] profile format.
+//!
+//! Profiles are protobuf messages, which are simple enough to encode by hand
+//! that pulling in a protobuf implementation isn't worth it.
+//!
+//! [pprof]: https://github.com/google/pprof/blob/main/proto/profile.proto
+
+use std::collections::HashMap;
+use std::io::Write;
+
+/// A pprof profile under construction.
+pub(crate) struct PprofBuilder {
+    strings: Vec<String>,
+    string_ids: HashMap<String, i64>,
+    sample_types: Vec<(i64, i64)>,
+    period_type: (i64, i64),
+    period: i64,
+    functions: Vec<(i64, i64)>,
+    function_ids: HashMap<(i64, i64), u64>,
+    locations: Vec<(u64, Option<u32>)>,
+    location_ids: HashMap<(u64, Option<u32>), u64>,
+    samples: HashMap<(Vec<u64>, Vec<(i64, i64)>), Vec<i64>>,
+}
+
+impl PprofBuilder {
+    /// Creates a profile whose samples have a value for each of
+    /// `sample_types`, given as `(type, unit)` pairs such as
+    /// `("cycles", "count")`. Samples are taken every `period` of
+    /// `period_type`.
+    pub(crate) fn new(
+        sample_types: &[(&str, &str)],
+        period_type: (&str, &str),
+        period: i64,
+    ) -> Self {
+        let mut builder = PprofBuilder {
+            // The string table must start with the empty string.
+            strings: vec![String::new()],
+            string_ids: HashMap::from([(String::new(), 0)]),
+            sample_types: Vec::new(),
+            period_type: (0, 0),
+            period,
+            functions: Vec::new(),
+            function_ids: HashMap::new(),
+            locations: Vec::new(),
+            location_ids: HashMap::new(),
+            samples: HashMap::new(),
+        };
+        builder.sample_types = sample_types
+            .iter()
+            .map(|(ty, unit)| (builder.string(ty), builder.string(unit)))
+            .collect();
+        builder.period_type = (builder.string(period_type.0), builder.string(period_type.1));
+        builder
+    }
+
+    fn string(&mut self, s: &str) -> i64 {
+        if let Some(id) = self.string_ids.get(s) {
+            return *id;
+        }
+        let id = self.strings.len() as i64;
+        self.strings.push(s.to_string());
+        self.string_ids.insert(s.to_string(), id);
+        id
+    }
+
+    /// Returns the id of the location within the function `name` defined in
+    /// `file`, at `line` if known.
+    pub(crate) fn location(&mut self, name: &str, file: &str, line: Option<u32>) -> u64 {
+        let key = (self.string(name), self.string(file));
+        let next_function = self.functions.len() as u64 + 1;
+        let function = *self.function_ids.entry(key).or_insert_with(|| {
+            self.functions.push(key);
+            next_function
+        });
+        let next_location = self.locations.len() as u64 + 1;
+        *self
+            .location_ids
+            .entry((function, line))
+            .or_insert_with(|| {
+                self.locations.push((function, line));
+                next_location
+            })
+    }
+
+    /// Adds `values`, one for each sample type, to the samples of the stack
+    /// of `locations`, which lists the innermost location first, with the
+    /// given `(key, value)` string `labels`.
+    pub(crate) fn add_sample(
+        &mut self,
+        locations: Vec<u64>,
+        labels: &[(&str, &str)],
+        values: &[i64],
+    ) {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| (self.string(key), self.string(value)))
+            .collect();
+        let totals = self
+            .samples
+            .entry((locations, labels))
+            .or_insert_with(|| vec![0; values.len()]);
+        for (total, value) in totals.iter_mut().zip(values) {
+            *total += value;
+        }
+    }
+
+    /// Encodes the profile, which started at `time_nanos` since the Unix
+    /// epoch and lasted `duration_nanos`, to `output`.
+    pub(crate) fn finish(
+        self,
+        time_nanos: i64,
+        duration_nanos: i64,
+        mut output: impl Write,
+    ) -> std::io::Result<()> {
+        let mut profile = Vec::new();
+        for (ty, unit) in &self.sample_types {
+            let mut value_type = Vec::new();
+            int_field(&mut value_type, 1, *ty as u64);
+            int_field(&mut value_type, 2, *unit as u64);
+            bytes_field(&mut profile, 1, &value_type);
+        }
+        let mut samples = self.samples.into_iter().collect::<Vec<_>>();
+        samples.sort();
+        for ((locations, labels), values) in samples {
+            let mut sample = Vec::new();
+            packed_field(&mut sample, 1, locations.iter().copied());
+            packed_field(&mut sample, 2, values.iter().map(|v| *v as u64));
+            for (key, value) in labels {
+                let mut label = Vec::new();
+                int_field(&mut label, 1, key as u64);
+                int_field(&mut label, 2, value as u64);
+                bytes_field(&mut sample, 3, &label);
+            }
+            bytes_field(&mut profile, 2, &sample);
+        }
+        for (i, (function, line)) in self.locations.iter().enumerate() {
+            let mut location = Vec::new();
+            int_field(&mut location, 1, i as u64 + 1);
+            let mut line_info = Vec::new();
+            int_field(&mut line_info, 1, *function);
+            if let Some(line) = line {
+                int_field(&mut line_info, 2, u64::from(*line));
+            }
+            bytes_field(&mut location, 4, &line_info);
+            bytes_field(&mut profile, 4, &location);
+        }
+        for (i, (name, file)) in self.functions.iter().enumerate() {
+            let mut function = Vec::new();
+            int_field(&mut function, 1, i as u64 + 1);
+            int_field(&mut function, 2, *name as u64);
+            int_field(&mut function, 3, *name as u64);
+            int_field(&mut function, 4, *file as u64);
+            bytes_field(&mut profile, 5, &function);
+        }
+        for string in &self.strings {
+            bytes_field(&mut profile, 6, string.as_bytes());
+        }
+        int_field(&mut profile, 9, time_nanos as u64);
+        int_field(&mut profile, 10, duration_nanos as u64);
+        let mut period_type = Vec::new();
+        int_field(&mut period_type, 1, self.period_type.0 as u64);
+        int_field(&mut period_type, 2, self.period_type.1 as u64);
+        bytes_field(&mut profile, 11, &period_type);
+        int_field(&mut profile, 12, self.period as u64);
+        output.write_all(&profile)?;
+        output.flush()
+    }
+}
+
+fn varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn int_field(out: &mut Vec<u8>, field: u32, value: u64) {
+    varint(out, u64::from(field) << 3);
+    varint(out, value);
+}
+
+fn bytes_field(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    varint(out, u64::from(field) << 3 | 2);
+    varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn packed_field(out: &mut Vec<u8>, field: u32, values: impl Iterator<Item = u64>) {
+    let mut packed = Vec::new();
+    for value in values {
+        varint(&mut packed, value);
+    }
+    bytes_field(out, field, &packed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_varints() {
+        let mut out = Vec::new();
+        varint(&mut out, 1);
+        varint(&mut out, 300);
+        assert_eq!(out, [0x01, 0xac, 0x02]);
+    }
+
+    #[test]
+    fn deduplicates_locations() {
+        let mut pprof = PprofBuilder::new(&[("samples", "count")], ("samples", "count"), 1);
+        let a = pprof.location("a", "m.wasm", None);
+        let b = pprof.location("b", "m.wasm", None);
+        assert_ne!(a, b);
+        assert_eq!(pprof.location("a", "m.wasm", None), a);
+        pprof.add_sample(vec![a, b], &[], &[1]);
+        pprof.add_sample(vec![a, b], &[], &[2]);
+        assert_eq!(pprof.samples[&(vec![a, b], vec![])], [3]);
+
+        // Samples with different labels are kept apart.
+        pprof.add_sample(vec![a, b], &[("thread", "t1")], &[4]);
+        let labels = vec![(pprof.string("thread"), pprof.string("t1"))];
+        assert_eq!(pprof.samples[&(vec![a, b], labels)], [4]);
+        assert_eq!(pprof.samples.len(), 2);
+    }
+}
@@ -76,6 +76,9 @@
 //! contents of `StoreOpaque`. This is an invariant that we, as the authors of
 //! `wasmtime`, must uphold for the public interface to be safe.
 
+use crate::coverage::{Coverage, CoverageCounters};
+#[cfg(feature = "debugger")]
+use crate::debug::{DebugResume, DebugState, DebugStop};
 use crate::instance::InstanceData;
 use crate::linker::Definition;
 use crate::module::{BareModuleInfo, RegisteredModuleId};
@@ -205,8 +208,12 @@ pub struct StoreInner<T> {
 
     limiter: Option<ResourceLimiterInner<T>>,
     call_hook: Option<CallHookInner<T>>,
+    fuel_hook: Option<Box<dyn FnMut(&mut T, u64) + Send + Sync>>,
     epoch_deadline_behavior:
         Option<Box<dyn FnMut(StoreContextMut<T>) -> Result<UpdateDeadline> + Send + Sync>>,
+    #[cfg(feature = "debugger")]
+    debug_callback:
+        Option<Box<dyn FnMut(StoreContextMut<T>, &DebugStop) -> Result<DebugResume> + Send + Sync>>,
     // for comments about `ManuallyDrop`, see `Store::into_data`
     data: ManuallyDrop<T>,
 }
@@ -314,6 +321,16 @@ pub struct StoreOpaque {
     // until the reserve is empty.
     fuel_reserve: u64,
     fuel_yield_interval: Option<NonZeroU64>,
+    /// The fuel that was left when the hook configured with
+    /// `Store::fuel_hook` was last called, or when fuel was last set.
+    fuel_at_last_hook: u64,
+    /// Execution counters written by modules compiled with coverage
+    /// instrumentation.
+    coverage: CoverageCounters,
+    /// Breakpoints and single-stepping state for modules compiled with
+    /// breakpoint checks.
+    #[cfg(feature = "debugger")]
+    debug: DebugState,
     /// Indexed data within this `Store`, used to store information about
     /// globals, functions, memories, etc.
     ///
@@ -498,6 +515,10 @@ impl<T> Store<T> {
                 },
                 fuel_reserve: 0,
                 fuel_yield_interval: None,
+                fuel_at_last_hook: 0,
+                coverage: CoverageCounters::default(),
+                #[cfg(feature = "debugger")]
+                debug: DebugState::default(),
                 store_data: ManuallyDrop::new(StoreData::new()),
                 default_caller: InstanceHandle::null(),
                 hostcall_val_storage: Vec::new(),
@@ -511,7 +532,10 @@ impl<T> Store<T> {
             },
             limiter: None,
             call_hook: None,
+            fuel_hook: None,
             epoch_deadline_behavior: None,
+            #[cfg(feature = "debugger")]
+            debug_callback: None,
             data: ManuallyDrop::new(data),
         });
 
@@ -766,6 +790,22 @@ impl<T> Store<T> {
         self.inner.call_hook = Some(CallHookInner::Sync(Box::new(hook)));
     }
 
+    /// Configures a function that's told how much fuel wasm consumed whenever
+    /// it calls a host function.
+    ///
+    /// The function is passed the fuel consumed since it was last called, or
+    /// since fuel was last set with [`Store::set_fuel`]. This lets host state
+    /// follow the progress of the guest, for example to derive a virtual
+    /// clock from the amount of work it has done.
+    ///
+    /// The function is called for calls to core wasm imports as well as to
+    /// component model imports, before the host function itself runs. It's
+    /// never called if fuel consumption isn't enabled via
+    /// [`Config::consume_fuel`](crate::Config::consume_fuel).
+    pub fn fuel_hook(&mut self, hook: impl FnMut(&mut T, u64) + Send + Sync + 'static) {
+        self.inner.fuel_hook = Some(Box::new(hook));
+    }
+
     /// Returns the [`Engine`] that this store is associated with.
     pub fn engine(&self) -> &Engine {
         self.inner.engine()
@@ -845,6 +885,73 @@ impl<T> Store<T> {
         self.inner.fuel_async_yield_interval(interval)
     }
 
+    /// Returns how many times each basic block of wasm code has executed
+    /// within this [`Store`].
+    ///
+    /// Only modules compiled with
+    /// [`Config::coverage_instrumentation`](crate::Config::coverage_instrumentation)
+    /// enabled count their blocks, and counts are shared between all
+    /// instances of the same [`Module`](crate::Module) within this store.
+    pub fn coverage(&self) -> Coverage {
+        self.inner.coverage()
+    }
+
+    /// Resets the counters reported by [`Store::coverage`] to zero.
+    pub fn reset_coverage(&mut self) {
+        self.inner.reset_coverage()
+    }
+
+    /// Configures a callback that's invoked whenever wasm in this store stops
+    /// at a breakpoint or while single-stepping.
+    ///
+    /// Only modules compiled with
+    /// [`Config::debug_breakpoints`](crate::Config::debug_breakpoints)
+    /// enabled can stop. The callback receives the state of the stopped guest
+    /// and returns how it should continue; returning an error raises it as a
+    /// trap in the guest. Breakpoints may be added and removed from within
+    /// the callback.
+    ///
+    /// If no callback is configured then stopped guests simply continue.
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn debug_callback(
+        &mut self,
+        callback: impl FnMut(StoreContextMut<T>, &DebugStop) -> Result<DebugResume>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.inner.debug_callback = Some(Box::new(callback));
+    }
+
+    /// Stops wasm in this store before it executes the instruction at
+    /// `offset`, in bytes from the start of `module`'s original wasm binary.
+    ///
+    /// For more information see [`Store::debug_callback`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn add_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.inner.add_breakpoint(module, offset)
+    }
+
+    /// Removes a breakpoint previously added with
+    /// [`Store::add_breakpoint`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn remove_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.inner.remove_breakpoint(module, offset)
+    }
+
+    /// Configures whether wasm in this store stops before every instruction.
+    ///
+    /// This is also updated by the value returned from the callback
+    /// configured with [`Store::debug_callback`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn single_step(&mut self, enable: bool) {
+        self.inner.single_step(enable)
+    }
+
     /// Sets the epoch deadline to a certain number of ticks in the future.
     ///
     /// When the Wasm guest code is compiled with epoch-interruption
@@ -992,6 +1099,13 @@ impl<'a, T> StoreContext<'a, T> {
     pub fn get_fuel(&self) -> Result<u64> {
         self.0.get_fuel()
     }
+
+    /// Returns how many times each basic block of wasm code has executed.
+    ///
+    /// For more information see [`Store::coverage`].
+    pub fn coverage(&self) -> Coverage {
+        self.0.coverage()
+    }
 }
 
 impl<'a, T> StoreContextMut<'a, T> {
@@ -1042,6 +1156,47 @@ impl<'a, T> StoreContextMut<'a, T> {
         self.0.fuel_async_yield_interval(interval)
     }
 
+    /// Returns how many times each basic block of wasm code has executed.
+    ///
+    /// For more information see [`Store::coverage`].
+    pub fn coverage(&self) -> Coverage {
+        self.0.coverage()
+    }
+
+    /// Resets this store's coverage counters to zero.
+    ///
+    /// For more information see [`Store::reset_coverage`].
+    pub fn reset_coverage(&mut self) {
+        self.0.reset_coverage()
+    }
+
+    /// Adds a breakpoint to this store.
+    ///
+    /// For more information see [`Store::add_breakpoint`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn add_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.0.add_breakpoint(module, offset)
+    }
+
+    /// Removes a breakpoint from this store.
+    ///
+    /// For more information see [`Store::remove_breakpoint`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn remove_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.0.remove_breakpoint(module, offset)
+    }
+
+    /// Configures whether wasm in this store stops before every instruction.
+    ///
+    /// For more information see [`Store::single_step`].
+    #[cfg(feature = "debugger")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "debugger")))]
+    pub fn single_step(&mut self, enable: bool) {
+        self.0.single_step(enable)
+    }
+
     /// Sets the epoch deadline to a certain number of ticks in the future.
     ///
     /// For more information see [`Store::set_epoch_deadline`].
@@ -1079,6 +1234,20 @@ impl<T> StoreInner<T> {
         &mut self.data
     }
 
+    /// Calls the hook configured with [`Store::fuel_hook`], if any, with the
+    /// fuel consumed since it was last called.
+    pub fn call_fuel_hook(&mut self) {
+        let hook = match &mut self.fuel_hook {
+            Some(hook) => hook,
+            None => return,
+        };
+        if let Ok(fuel) = self.inner.get_fuel() {
+            let consumed = self.inner.fuel_at_last_hook.saturating_sub(fuel);
+            self.inner.fuel_at_last_hook = fuel;
+            hook(&mut self.data, consumed);
+        }
+    }
+
     pub fn call_hook(&mut self, s: CallHook) -> Result<()> {
         if let Some(pkey) = &self.inner.pkey {
             let allocator = self.engine().allocator();
@@ -1090,6 +1259,10 @@ impl<T> StoreInner<T> {
             }
         }
 
+        if let CallHook::CallingHost = s {
+            self.call_fuel_hook();
+        }
+
         match &mut self.call_hook {
             Some(CallHookInner::Sync(hook)) => hook(&mut self.data, s),
 
@@ -1396,6 +1569,49 @@ impl StoreOpaque {
         Ok(get_fuel(injected_fuel, self.fuel_reserve))
     }
 
+    pub fn coverage(&self) -> Coverage {
+        self.coverage.report()
+    }
+
+    pub fn reset_coverage(&mut self) {
+        self.coverage.reset()
+    }
+
+    #[cfg(feature = "debugger")]
+    pub fn add_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.debug.add_breakpoint(module, offset);
+        self.update_debug_break();
+    }
+
+    #[cfg(feature = "debugger")]
+    pub fn remove_breakpoint(&mut self, module: &Module, offset: usize) {
+        self.debug.remove_breakpoint(module, offset);
+        self.update_debug_break();
+    }
+
+    #[cfg(feature = "debugger")]
+    pub fn single_step(&mut self, enable: bool) {
+        self.debug.set_single_step(enable);
+        self.update_debug_break();
+    }
+
+    /// Tells wasm whether it needs to call into the host before each
+    /// instruction.
+    #[cfg(feature = "debugger")]
+    fn update_debug_break(&mut self) {
+        // Safety: as with the epoch deadline, the flag is only accessed here
+        // and by wasm running in this store, and we have a `&mut self`.
+        unsafe {
+            *self.runtime_limits.debug_break.get() = u32::from(self.debug.active());
+        }
+    }
+
+    /// Returns the coverage counters table for instances of `module`, if it
+    /// was compiled with coverage instrumentation.
+    pub(crate) fn coverage_counters(&mut self, module: &Module) -> Option<*const *mut u64> {
+        self.coverage.table_for(module)
+    }
+
     fn refuel(&mut self) -> bool {
         let injected_fuel = unsafe { &mut *self.runtime_limits.fuel_consumed.get() };
         refuel(
@@ -1417,9 +1633,28 @@ impl StoreOpaque {
             self.fuel_yield_interval,
             fuel,
         );
+        self.fuel_at_last_hook = fuel;
         Ok(())
     }
 
+    /// Consumes `fuel` on behalf of a host function, raising an out-of-fuel
+    /// trap if there isn't enough left.
+    ///
+    /// This does nothing if fuel isn't enabled for this store.
+    pub(crate) fn consume_fuel(&mut self, fuel: u64) -> Result<()> {
+        if fuel == 0 || !self.engine().config().tunables.consume_fuel {
+            return Ok(());
+        }
+        let remaining = self.get_fuel()?;
+        match remaining.checked_sub(fuel) {
+            Some(left) => self.set_fuel(left),
+            None => {
+                self.set_fuel(0)?;
+                Err(Trap::OutOfFuel.into())
+            }
+        }
+    }
+
     pub fn fuel_async_yield_interval(&mut self, interval: Option<u64>) -> Result<()> {
         anyhow::ensure!(
             self.engine().config().tunables.consume_fuel,
@@ -2137,6 +2372,35 @@ unsafe impl<T> wasmtime_runtime::Store for StoreInner<T> {
         delta_result
     }
 
+    #[cfg(feature = "debugger")]
+    fn debug_break(&mut self, offset: u32) -> Result<()> {
+        // Temporarily take the debugging state so the stopped guest can be
+        // captured from the rest of the store.
+        let debug = mem::take(&mut self.debug);
+        let stop = DebugStop::capture(&mut self.inner, &debug, offset);
+        self.debug = debug;
+        let stop = match stop {
+            Some(stop) => stop,
+            None => return Ok(()),
+        };
+
+        // Likewise take the callback to avoid mutably borrowing multiple
+        // times, and put it back afterwards.
+        let mut callback = self.debug_callback.take();
+        let result = match &mut callback {
+            None => Ok(DebugResume::Continue),
+            Some(callback) => callback((&mut *self).as_context_mut(), &stop),
+        };
+        self.debug_callback = callback;
+        self.single_step(result? == DebugResume::Step);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "debugger"))]
+    fn debug_break(&mut self, _offset: u32) -> Result<()> {
+        Ok(())
+    }
+
     #[cfg(feature = "component-model")]
     fn component_calls(&mut self) -> &mut wasmtime_runtime::component::CallContexts {
         &mut self.component_calls
@@ -138,8 +138,17 @@ pub(crate) fn from_runtime_box(
     let _ = &coredumpstack;
     #[cfg(feature = "coredump")]
     if let Some(coredump) = coredumpstack {
-        let bt = WasmBacktrace::from_captured(store, coredump.bt, pc);
-        let cd = WasmCoreDump::new(store, bt);
+        let mut captured_locals = coredump.locals.into_iter().map(Some).collect::<Vec<_>>();
+        let mut locals = Vec::new();
+        let bt = WasmBacktrace::from_captured_with(store, coredump.bt, pc, |index| {
+            locals.push(
+                captured_locals
+                    .get_mut(index)
+                    .and_then(Option::take)
+                    .unwrap_or_default(),
+            );
+        });
+        let cd = WasmCoreDump::new(store, bt, locals);
         error = error.context(cd);
     }
 
@@ -286,13 +295,24 @@ impl WasmBacktrace {
         store: &StoreOpaque,
         runtime_trace: wasmtime_runtime::Backtrace,
         trap_pc: Option<usize>,
+    ) -> Self {
+        Self::from_captured_with(store, runtime_trace, trap_pc, |_| {})
+    }
+
+    /// Same as `from_captured`, but additionally invokes `on_wasm_frame` with
+    /// the index within `runtime_trace` of each frame that's kept.
+    pub(crate) fn from_captured_with(
+        store: &StoreOpaque,
+        runtime_trace: wasmtime_runtime::Backtrace,
+        trap_pc: Option<usize>,
+        mut on_wasm_frame: impl FnMut(usize),
     ) -> Self {
         let mut wasm_trace = Vec::<FrameInfo>::with_capacity(runtime_trace.frames().len());
         let mut hint_wasm_backtrace_details_env = false;
         let wasm_backtrace_details_env_used =
             store.engine().config().wasm_backtrace_details_env_used;
 
-        for frame in runtime_trace.frames() {
+        for (index, frame) in runtime_trace.frames().enumerate() {
             debug_assert!(frame.pc() != 0);
 
             // Note that we need to be careful about the pc we pass in
@@ -337,6 +357,7 @@ impl WasmBacktrace {
             // store's module registry.
             if let Some((info, module)) = store.modules().lookup_frame_info(pc_to_lookup) {
                 wasm_trace.push(info);
+                on_wasm_frame(index);
 
                 // If this frame has unparsed debug information and the
                 // store's configuration indicates that we were
@@ -476,33 +497,10 @@ impl FrameInfo {
         // from the location of `instr`. Also note that all errors are ignored
         // here for now since technically wasm modules can always have any
         // custom section contents.
-        let mut symbols = Vec::new();
-
-        let _ = &mut symbols;
-        #[cfg(feature = "addr2line")]
-        if let Some(s) = &compiled_module.symbolize_context().ok().and_then(|c| c) {
-            if let Some(offset) = instr.and_then(|i| i.file_offset()) {
-                let to_lookup = u64::from(offset) - s.code_section_offset();
-                if let Ok(mut frames) = s.addr2line().find_frames(to_lookup).skip_all_loads() {
-                    while let Ok(Some(frame)) = frames.next() {
-                        symbols.push(FrameSymbol {
-                            name: frame
-                                .function
-                                .as_ref()
-                                .and_then(|l| l.raw_name().ok())
-                                .map(|s| s.to_string()),
-                            file: frame
-                                .location
-                                .as_ref()
-                                .and_then(|l| l.file)
-                                .map(|s| s.to_string()),
-                            line: frame.location.as_ref().and_then(|l| l.line),
-                            column: frame.location.as_ref().and_then(|l| l.column),
-                        });
-                    }
-                }
-            }
-        }
+        let symbols = instr
+            .and_then(|i| i.file_offset())
+            .map(|offset| FrameSymbol::lookup(&module, offset))
+            .unwrap_or_default();
 
         Some(FrameInfo {
             module,
@@ -605,6 +603,42 @@ pub struct FrameSymbol {
 }
 
 impl FrameSymbol {
+    /// Looks up the symbols for the instruction at `offset` within the
+    /// original wasm module, innermost inlined frame first.
+    pub(crate) fn lookup(module: &Module, offset: u32) -> Vec<FrameSymbol> {
+        let mut symbols = Vec::new();
+
+        let _ = (&mut symbols, module, offset);
+        #[cfg(feature = "addr2line")]
+        if let Some(s) = &module
+            .compiled_module()
+            .symbolize_context()
+            .ok()
+            .and_then(|c| c)
+        {
+            let to_lookup = u64::from(offset) - s.code_section_offset();
+            if let Ok(mut frames) = s.addr2line().find_frames(to_lookup).skip_all_loads() {
+                while let Ok(Some(frame)) = frames.next() {
+                    symbols.push(FrameSymbol {
+                        name: frame
+                            .function
+                            .as_ref()
+                            .and_then(|l| l.raw_name().ok())
+                            .map(|s| s.to_string()),
+                        file: frame
+                            .location
+                            .as_ref()
+                            .and_then(|l| l.file)
+                            .map(|s| s.to_string()),
+                        line: frame.location.as_ref().and_then(|l| l.line),
+                        column: frame.location.as_ref().and_then(|l| l.column),
+                    });
+                }
+            }
+        }
+        symbols
+    }
+
     /// Returns the function name associated with this symbol.
     ///
     /// Note that this may not be present with malformed debug information, or
@@ -7,7 +7,7 @@ use wasmparser::FuncValidatorAllocations;
 use wasmtime_cranelift_shared::{CompiledFunction, ModuleTextBuilder};
 use wasmtime_environ::{
     CompileError, DefinedFuncIndex, FilePos, FuncIndex, FunctionBodyData, FunctionLoc,
-    ModuleTranslation, ModuleTypesBuilder, PrimaryMap, TrapEncodingBuilder, VMOffsets,
+    ModuleTranslation, ModuleTypesBuilder, PrimaryMap, TrapEncodingBuilder, Tunables, VMOffsets,
     WasmFunctionInfo,
 };
 use winch_codegen::{BuiltinFunctions, TargetIsa, TrampolineKind};
@@ -24,6 +24,7 @@ struct CompilationContext {
 
 pub(crate) struct Compiler {
     isa: Box<dyn TargetIsa>,
+    tunables: Tunables,
     contexts: Mutex<Vec<CompilationContext>>,
 }
 
@@ -39,9 +40,10 @@ impl wasmtime_cranelift_shared::CompiledFuncEnv for CompiledFuncEnv {
 }
 
 impl Compiler {
-    pub fn new(isa: Box<dyn TargetIsa>) -> Self {
+    pub fn new(isa: Box<dyn TargetIsa>, tunables: Tunables) -> Self {
         Self {
             isa,
+            tunables,
             contexts: Mutex::new(Vec::new()),
         }
     }
@@ -92,6 +94,7 @@ impl wasmtime_environ::Compiler for Compiler {
                 &body,
                 translation,
                 types,
+                &self.tunables,
                 &mut context.builtins,
                 &mut validator,
             )
@@ -105,6 +108,8 @@ impl wasmtime_environ::Compiler for Compiler {
             WasmFunctionInfo {
                 start_srcloc,
                 stack_maps: Box::new([]),
+                coverage: Box::new([]),
+                frame_locals: None,
             },
             Box::new(compiled_function),
         ))
@@ -0,0 +1,207 @@
+/*
+Example of instantiating a WebAssembly component, defining a host function for
+one of its imports and calling one of its exports with compound values such as
+records, lists and variants.
+
+You can compile and run this example on Linux with:
+
+   cargo build --release -p wasmtime-c-api
+   cc examples/component.c \
+       -I crates/c-api/include \
+       -I crates/c-api/wasm-c-api/include \
+       target/release/libwasmtime.a \
+       -lpthread -ldl -lm \
+       -o component
+   ./component
+
+Note that on Windows and macOS the command will be similar, but you'll need
+to tweak the `-lpthread` and such annotations as well as the name of the
+`libwasmtime.a` file on Windows.
+
+You can also build using cmake:
+
+mkdir build && cd build && cmake .. && cmake --build . --target wasmtime-component
+*/
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <wasm.h>
+#include <wasmtime.h>
+
+static void exit_with_error(const char *message, wasmtime_error_t *error);
+
+// Initializes `out` with the bytes of `s`, without a nul terminator.
+static void set_name(wasm_name_t *out, const char *s) {
+  wasm_name_new(out, strlen(s), s);
+}
+
+// Initializes `out` as a `point` record.
+static void new_point(wasmtime_component_val_t *out, int32_t x, int32_t y) {
+  out->kind = WASMTIME_COMPONENT_RECORD;
+  wasmtime_component_val_record_new_uninitialized(&out->of.record, 2);
+  wasmtime_component_val_record_field_t *fields = out->of.record.data;
+  set_name(&fields[0].name, "x");
+  fields[0].val.kind = WASMTIME_COMPONENT_S32;
+  fields[0].val.of.s32 = x;
+  set_name(&fields[1].name, "y");
+  fields[1].val.kind = WASMTIME_COMPONENT_S32;
+  fields[1].val.of.s32 = y;
+}
+
+// Implementation of the `centroid` import, which takes a `list<point>` and
+// returns the `center` variant.
+static wasmtime_error_t *centroid_callback(void *env,
+                                           wasmtime_context_t *context,
+                                           const wasmtime_component_val_t *args,
+                                           size_t nargs,
+                                           wasmtime_component_val_t *results,
+                                           size_t nresults) {
+  assert(nargs == 1 && nresults == 1);
+  assert(args[0].kind == WASMTIME_COMPONENT_LIST);
+  const wasmtime_component_val_vec_t *points = &args[0].of.list;
+  printf("Host received %zu points\n", points->size);
+
+  results[0].kind = WASMTIME_COMPONENT_VARIANT;
+  if (points->size == 0) {
+    set_name(&results[0].of.variant.discriminant, "empty");
+    results[0].of.variant.val = NULL;
+    return NULL;
+  }
+
+  int64_t x = 0, y = 0;
+  for (size_t i = 0; i < points->size; i++) {
+    const wasmtime_component_val_t *point = &points->data[i];
+    assert(point->kind == WASMTIME_COMPONENT_RECORD);
+    assert(point->of.record.size == 2);
+    x += point->of.record.data[0].val.of.s32;
+    y += point->of.record.data[1].val.of.s32;
+  }
+
+  // Payloads of variants are heap-allocated and owned by the variant.
+  set_name(&results[0].of.variant.discriminant, "point");
+  results[0].of.variant.val = wasmtime_component_val_new();
+  new_point(results[0].of.variant.val, (int32_t)(x / (int64_t)points->size),
+            (int32_t)(y / (int64_t)points->size));
+  return NULL;
+}
+
+int main() {
+  // The component model is disabled by default, so turn it on first.
+  wasm_config_t *config = wasm_config_new();
+  assert(config != NULL);
+  wasmtime_config_wasm_component_model_set(config, true);
+  wasm_engine_t *engine = wasm_engine_new_with_config(config);
+  assert(engine != NULL);
+  wasmtime_store_t *store = wasmtime_store_new(engine, NULL, NULL);
+  assert(store != NULL);
+  wasmtime_context_t *context = wasmtime_store_context(store);
+
+  // Read our input file, which in this case is a wasm text file.
+  FILE *file = fopen("examples/component.wat", "r");
+  assert(file != NULL);
+  fseek(file, 0L, SEEK_END);
+  size_t file_size = ftell(file);
+  fseek(file, 0L, SEEK_SET);
+  wasm_byte_vec_t wat;
+  wasm_byte_vec_new_uninitialized(&wat, file_size);
+  assert(fread(wat.data, file_size, 1, file) == 1);
+  fclose(file);
+
+  // Parse the wat into the binary wasm format
+  wasm_byte_vec_t wasm;
+  wasmtime_error_t *error = wasmtime_wat2wasm(wat.data, wat.size, &wasm);
+  if (error != NULL)
+    exit_with_error("failed to parse wat", error);
+  wasm_byte_vec_delete(&wat);
+
+  printf("Compiling component...\n");
+  wasmtime_component_t *component = NULL;
+  error = wasmtime_component_new(engine, (uint8_t *)wasm.data, wasm.size,
+                                 &component);
+  wasm_byte_vec_delete(&wasm);
+  if (error != NULL)
+    exit_with_error("failed to compile component", error);
+
+  // Define the `centroid` function of the imported `host` instance. Its type
+  // is taken from the component, which is how results returned by the
+  // callback are converted.
+  printf("Defining host function...\n");
+  wasmtime_component_linker_t *linker = wasmtime_component_linker_new(engine);
+  wasmtime_component_linker_instance_t *root =
+      wasmtime_component_linker_root(linker);
+  wasmtime_component_linker_instance_t *host = NULL;
+  error = wasmtime_component_linker_instance_add_instance(
+      root, "host", strlen("host"), &host);
+  if (error != NULL)
+    exit_with_error("failed to define host instance", error);
+  error = wasmtime_component_linker_instance_add_func(
+      host, component, "centroid", strlen("centroid"), centroid_callback, NULL,
+      NULL);
+  wasmtime_component_linker_instance_delete(host);
+  wasmtime_component_linker_instance_delete(root);
+  if (error != NULL)
+    exit_with_error("failed to define host function", error);
+
+  printf("Instantiating component...\n");
+  wasmtime_component_instance_t *instance = NULL;
+  error = wasmtime_component_linker_instantiate(linker, context, component,
+                                                &instance);
+  if (error != NULL)
+    exit_with_error("failed to instantiate component", error);
+
+  wasmtime_component_func_t *run = NULL;
+  bool ok = wasmtime_component_instance_get_func(instance, context, NULL, 0,
+                                                 "run", strlen("run"), &run);
+  assert(ok);
+
+  // Build the `list<point>` argument.
+  wasmtime_component_val_t arg;
+  arg.kind = WASMTIME_COMPONENT_LIST;
+  wasmtime_component_val_vec_new_uninitialized(&arg.of.list, 3);
+  new_point(&arg.of.list.data[0], 0, 0);
+  new_point(&arg.of.list.data[1], 4, 2);
+  new_point(&arg.of.list.data[2], 8, 7);
+
+  printf("Calling export...\n");
+  wasmtime_component_val_t result;
+  error = wasmtime_component_func_call(context, run, &arg, 1, &result, 1);
+  if (error != NULL)
+    exit_with_error("failed to call function", error);
+
+  assert(result.kind == WASMTIME_COMPONENT_VARIANT);
+  printf("Result: %.*s",
+         (int)result.of.variant.discriminant.size,
+         result.of.variant.discriminant.data);
+  if (result.of.variant.val != NULL) {
+    const wasmtime_component_val_record_t *point =
+        &result.of.variant.val->of.record;
+    printf("(x: %d, y: %d)", point->data[0].val.of.s32,
+           point->data[1].val.of.s32);
+  }
+  printf("\n");
+
+  // Values own all of their contents and must be deallocated explicitly.
+  wasmtime_component_val_delete(&arg);
+  wasmtime_component_val_delete(&result);
+
+  printf("All finished!\n");
+  wasmtime_component_func_delete(run);
+  wasmtime_component_instance_delete(instance);
+  wasmtime_component_linker_delete(linker);
+  wasmtime_component_delete(component);
+  wasmtime_store_delete(store);
+  wasm_engine_delete(engine);
+  return 0;
+}
+
+static void exit_with_error(const char *message, wasmtime_error_t *error) {
+  fprintf(stderr, "error: %s\n", message);
+  wasm_byte_vec_t error_message;
+  wasmtime_error_message(error, &error_message);
+  wasmtime_error_delete(error);
+  fprintf(stderr, "%.*s\n", (int)error_message.size, error_message.data);
+  wasm_byte_vec_delete(&error_message);
+  exit(1);
+}
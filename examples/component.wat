@@ -0,0 +1,53 @@
+(component
+  ;; The host defines the types used below and computes the centroid of a
+  ;; list of points.
+  (import "host" (instance $host
+    (type $point' (record (field "x" s32) (field "y" s32)))
+    (export "point" (type $point (eq $point')))
+    (type $center' (variant (case "empty") (case "point" $point)))
+    (export "center" (type $center (eq $center')))
+    (export "centroid" (func (param "points" (list $point)) (result $center)))))
+  (alias export $host "point" (type $point))
+  (alias export $host "center" (type $center))
+  (alias export $host "centroid" (func $centroid))
+
+  ;; Memory and a bump allocator shared by the lowered import and the lifted
+  ;; export.
+  (core module $libc
+    (memory (export "memory") 1)
+    (global $next (mut i32) (i32.const 8))
+    (func (export "realloc") (param i32 i32) (param $align i32) (param $size i32) (result i32)
+      (local $ret i32)
+      (local.set $ret
+        (i32.and
+          (i32.add (global.get $next) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get $align))))
+      (global.set $next (i32.add (local.get $ret) (local.get $size)))
+      (local.get $ret)))
+  (core instance $libc (instantiate $libc))
+
+  (core func $centroid_lower
+    (canon lower (func $centroid)
+      (memory $libc "memory")
+      (realloc (func $libc "realloc"))))
+
+  ;; `run` forwards its list of points to the host and returns the result,
+  ;; which is written to a 12-byte return area.
+  (core module $m
+    (import "libc" "realloc" (func $realloc (param i32 i32 i32 i32) (result i32)))
+    (import "host" "centroid" (func $centroid (param i32 i32 i32)))
+    (func (export "run") (param $ptr i32) (param $len i32) (result i32)
+      (local $ret i32)
+      (local.set $ret
+        (call $realloc (i32.const 0) (i32.const 0) (i32.const 4) (i32.const 12)))
+      (call $centroid (local.get $ptr) (local.get $len) (local.get $ret))
+      (local.get $ret)))
+  (core instance $m (instantiate $m
+    (with "libc" (instance $libc))
+    (with "host" (instance (export "centroid" (func $centroid_lower))))))
+
+  (func (export "run") (param "points" (list $point)) (result $center)
+    (canon lift (core func $m "run")
+      (memory $libc "memory")
+      (realloc (func $libc "realloc"))))
+)
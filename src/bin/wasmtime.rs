@@ -58,10 +58,18 @@ enum Subcommand {
     #[cfg(feature = "cranelift")]
     Compile(wasmtime_cli::commands::CompileCommand),
 
+    /// Inspects a WebAssembly coredump
+    #[cfg(feature = "coredump")]
+    Coredump(wasmtime_cli::commands::CoredumpCommand),
+
     /// Explore the compilation of a WebAssembly module to native code.
     #[cfg(feature = "explore")]
     Explore(wasmtime_cli::commands::ExploreCommand),
 
+    /// Inspects a WebAssembly module, component, or precompiled artifact
+    #[cfg(feature = "inspect")]
+    Inspect(wasmtime_cli::commands::InspectCommand),
+
     /// Serves requests from a wasi-http proxy component.
     #[cfg(feature = "serve")]
     Serve(wasmtime_cli::commands::ServeCommand),
@@ -88,9 +96,15 @@ impl Wasmtime {
             #[cfg(feature = "cranelift")]
             Subcommand::Compile(c) => c.execute(),
 
+            #[cfg(feature = "coredump")]
+            Subcommand::Coredump(c) => c.execute(),
+
             #[cfg(feature = "explore")]
             Subcommand::Explore(c) => c.execute(),
 
+            #[cfg(feature = "inspect")]
+            Subcommand::Inspect(c) => c.execute(),
+
             #[cfg(feature = "serve")]
             Subcommand::Serve(c) => c.execute(),
 
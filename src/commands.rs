@@ -8,6 +8,16 @@ mod serve;
 #[cfg(feature = "serve")]
 pub use self::serve::*;
 
+#[cfg(feature = "coredump")]
+mod coredump;
+#[cfg(feature = "coredump")]
+pub use self::coredump::*;
+
+#[cfg(feature = "inspect")]
+mod inspect;
+#[cfg(feature = "inspect")]
+pub use self::inspect::*;
+
 #[cfg(feature = "explore")]
 mod explore;
 #[cfg(feature = "explore")]
@@ -24,7 +24,11 @@ static AFTER_HELP: Lazy<String> = Lazy::new(|| {
         \n\
         Compiling for a specific platform (Linux) and CPU preset (Skylake):\n\
         \n  \
-        wasmtime compile --target x86_64-unknown-linux -Ccranelift-skylake foo.wasm\n",
+        wasmtime compile --target x86_64-unknown-linux -Ccranelift-skylake foo.wasm\n\
+        \n\
+        Compiling for any x86-64 host which supports the x86-64-v2 baseline:\n\
+        \n  \
+        wasmtime compile --target-cpu-baseline x86-64-v2 foo.wasm\n",
     )
 });
 
@@ -43,6 +47,16 @@ pub struct CompileCommand {
     #[arg(long, value_name = "TARGET")]
     pub target: Option<String>,
 
+    /// Restrict the CPU features used by the compiled code to a named
+    /// baseline, such as `x86-64-v2`, instead of using those of this host
+    ///
+    /// The compiled module can then be loaded on any host which supports
+    /// the baseline. Besides the x86-64 microarchitecture levels `x86-64`,
+    /// `x86-64-v2`, `x86-64-v3` and `x86-64-v4`, any CPU preset listed by
+    /// `wasmtime settings` may be used.
+    #[arg(long, value_name = "CPU")]
+    pub target_cpu_baseline: Option<String>,
+
     /// The path of the output compiled module; defaults to <MODULE>.cwasm
     #[arg(short = 'o', long, value_name = "OUTPUT")]
     pub output: Option<PathBuf>,
@@ -61,7 +75,19 @@ impl CompileCommand {
     pub fn execute(mut self) -> Result<()> {
         self.common.init_logging()?;
 
-        let mut config = self.common.config(self.target.as_deref())?;
+        // Without an explicit target the compiler would use every CPU feature
+        // of this host, so a baseline starts from no features at all on the
+        // host's target.
+        let target = match (&self.target, &self.target_cpu_baseline) {
+            (None, Some(_)) => Some(target_lexicon::Triple::host().to_string()),
+            (target, _) => target.clone(),
+        };
+        let mut config = self.common.config(target.as_deref())?;
+        if let Some(baseline) = &self.target_cpu_baseline {
+            unsafe {
+                config.cranelift_flag_enable(baseline_preset(baseline));
+            }
+        }
 
         if let Some(path) = self.emit_clif {
             if !path.exists() {
@@ -111,6 +137,19 @@ impl CompileCommand {
     }
 }
 
+/// Returns the Cranelift preset for the `--target-cpu-baseline` named
+/// `baseline`.
+///
+/// The x86-64 microarchitecture levels are accepted with the names LLVM and
+/// GCC use for them, which not all of Cranelift's presets follow.
+fn baseline_preset(baseline: &str) -> &str {
+    match baseline {
+        "x86-64-v3" => "x84_64_v3",
+        "x86-64-v4" => "x86_64_v4",
+        other => other,
+    }
+}
+
 #[cfg(all(test, not(miri)))]
 mod test {
     use super::*;
@@ -241,6 +280,39 @@ mod test {
         Ok(())
     }
 
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_x64_cpu_baseline_compile() -> Result<()> {
+        let (mut input, input_path) = NamedTempFile::new()?.into_parts();
+        input.write_all("(module)".as_bytes())?;
+        drop(input);
+
+        let output_path = NamedTempFile::new()?.into_temp_path();
+
+        for (baseline, avx2) in [("x86-64-v2", "false"), ("x86-64-v3", "true")] {
+            let command = CompileCommand::try_parse_from(vec![
+                "compile",
+                "-Dlogging=n",
+                "--target-cpu-baseline",
+                baseline,
+                "-o",
+                output_path.to_str().unwrap(),
+                input_path.to_str().unwrap(),
+            ])?;
+            command.execute()?;
+
+            let engine = Engine::default();
+            let contents = std::fs::read(&output_path)?;
+            let info = engine.precompiled_info(&contents)?;
+            let flags = info.isa_flags().collect::<Vec<_>>();
+            assert!(flags.contains(&("has_sse42", "true")), "{flags:?}");
+            assert!(flags.contains(&("has_avx2", avx2)), "{flags:?}");
+            assert!(!flags.contains(&("has_avx512f", "true")), "{flags:?}");
+        }
+
+        Ok(())
+    }
+
     #[cfg(target_arch = "x86_64")]
     #[test]
     fn test_x64_presets_compile() -> Result<()> {
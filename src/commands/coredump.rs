@@ -0,0 +1,609 @@
+//! The module that implements the `wasmtime coredump` command.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use wasmparser::{
+    BinaryReader, CoreDumpInstancesSection, CoreDumpModulesSection, CoreDumpSection,
+    CoreDumpStackSection, CoreDumpValue, DataKind, Operator, Payload, ValType,
+};
+use wasmtime::{Engine, Module, WasmBacktraceDetails};
+use wasmtime_cli_flags::CommonOptions;
+
+const WASM_PAGE_SIZE: u64 = 0x10000;
+
+/// Parses `MEMORY[:START..END]`, where addresses are decimal or
+/// `0x`-prefixed hexadecimal.
+fn parse_memory_range(s: &str) -> Result<(u32, Option<Range<u64>>)> {
+    fn parse_address(s: &str) -> Result<u64> {
+        match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => s.parse(),
+        }
+        .with_context(|| format!("invalid address `{s}`"))
+    }
+
+    let (memory, range) = match s.split_once(':') {
+        Some((memory, range)) => (memory, Some(range)),
+        None => (s, None),
+    };
+    let memory = memory
+        .parse()
+        .with_context(|| format!("invalid memory index `{memory}`"))?;
+    let range = match range {
+        Some(range) => {
+            let Some((start, end)) = range.split_once("..") else {
+                bail!("address range must be of the form `START..END`");
+            };
+            Some(parse_address(start)?..parse_address(end)?)
+        }
+        None => None,
+    };
+    Ok((memory, range))
+}
+
+/// Inspects a WebAssembly coredump, such as one written by
+/// `wasmtime run -D coredump=<path>`
+#[derive(Parser, PartialEq)]
+pub struct CoredumpCommand {
+    #[command(flatten)]
+    common: CommonOptions,
+
+    /// An original WebAssembly module of the coredump, used to symbolize its
+    /// frames with the module's DWARF debug information.
+    ///
+    /// Modules are matched with the coredump by the name in their `name`
+    /// section. A module without a name can only be used when the coredump
+    /// contains a single module.
+    #[arg(short, long = "module", value_name = "MODULE")]
+    modules: Vec<PathBuf>,
+
+    /// Hex-dump a memory of the coredump, or a range of addresses within it.
+    ///
+    /// Memories are numbered as in the coredump's memory section, for example
+    /// `--dump-memory 0:0x1000..0x1100`.
+    #[arg(
+        long,
+        value_name = "MEMORY[:START..END]",
+        value_parser = parse_memory_range,
+    )]
+    dump_memory: Vec<(u32, Option<Range<u64>>)>,
+
+    /// Write the contents of the first memory of `--frame`'s instance to
+    /// this file.
+    #[arg(long, value_name = "PATH")]
+    export_memory: Option<PathBuf>,
+
+    /// The frame, counting from the innermost, whose instance's memory is
+    /// written by `--export-memory`.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 0,
+        requires = "export_memory"
+    )]
+    frame: usize,
+
+    /// The path of the coredump to inspect
+    #[arg(required = true, value_name = "COREDUMP")]
+    coredump: PathBuf,
+}
+
+impl CoredumpCommand {
+    /// Executes the command.
+    pub fn execute(mut self) -> Result<()> {
+        self.common.init_logging()?;
+
+        let bytes = std::fs::read(&self.coredump)
+            .with_context(|| format!("failed to read coredump: {}", self.coredump.display()))?;
+        let dump = CoreDump::parse(&bytes)
+            .with_context(|| format!("failed to parse coredump: {}", self.coredump.display()))?;
+        let modules = self.load_modules(&dump)?;
+
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        dump.write_stack(&mut out, &modules)?;
+        dump.write_globals(&mut out)?;
+        dump.write_memories(&mut out)?;
+        dump.write_components(&mut out)?;
+        for (memory, range) in self.dump_memory.iter() {
+            let Some(data) = dump.memories.get(*memory as usize) else {
+                bail!("coredump has no memory {memory}");
+            };
+            let range = range.clone().unwrap_or(0..data.size as u64);
+            if range.start > range.end || range.end > data.size as u64 {
+                bail!(
+                    "range {:#x}..{:#x} is out of bounds of memory {memory}, which is {:#x} bytes",
+                    range.start,
+                    range.end,
+                    data.size
+                );
+            }
+            writeln!(
+                out,
+                "\nmemory[{memory}] {:#x}..{:#x}:",
+                range.start, range.end
+            )?;
+            hexdump(
+                &mut out,
+                range.start,
+                &data.read(range.start as usize..range.end as usize),
+            )?;
+        }
+
+        if let Some(path) = &self.export_memory {
+            let Some(frame) = dump.frames.get(self.frame) else {
+                bail!("coredump has no frame {}", self.frame);
+            };
+            let memory = dump
+                .instances
+                .get(frame.instance as usize)
+                .and_then(|i| i.memories.first())
+                .with_context(|| format!("the instance of frame {} has no memory", self.frame))?;
+            let data = dump
+                .memories
+                .get(*memory as usize)
+                .with_context(|| format!("coredump has no memory {memory}"))?;
+            std::fs::File::create(path)
+                .and_then(|mut file| data.write_to(&mut file))
+                .with_context(|| format!("failed to write memory to: {}", path.display()))?;
+            eprintln!("Memory {memory} written to {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Compiles the `--module`s, keyed by the index of the coredump module
+    /// they're the original of.
+    fn load_modules(&mut self, dump: &CoreDump) -> Result<HashMap<usize, Module>> {
+        let mut modules = HashMap::new();
+        if self.modules.is_empty() {
+            return Ok(modules);
+        }
+        let mut config = self.common.config(None)?;
+        // Symbolizing frames needs the modules' DWARF, which is otherwise only
+        // parsed with `WASMTIME_BACKTRACE_DETAILS=1`.
+        config.wasm_backtrace_details(WasmBacktraceDetails::Enable);
+        let engine = Engine::new(&config)?;
+        for path in self.modules.iter() {
+            let module = Module::from_file(&engine, path)
+                .with_context(|| format!("failed to compile module: {}", path.display()))?;
+            let index = match module.name() {
+                Some(name) => dump.modules.iter().position(|m| m == name),
+                None if dump.modules.len() == 1 => Some(0),
+                None => bail!(
+                    "module `{}` has no name to match it with one of the coredump's {} modules",
+                    path.display(),
+                    dump.modules.len()
+                ),
+            };
+            match index {
+                Some(index) => {
+                    modules.insert(index, module);
+                }
+                None => bail!(
+                    "coredump has no module named `{}`",
+                    module.name().unwrap_or_default()
+                ),
+            }
+        }
+        Ok(modules)
+    }
+}
+
+/// The contents of a coredump, as described by the [tool conventions].
+///
+/// [tool conventions]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+#[derive(Default)]
+struct CoreDump {
+    name: String,
+    modules: Vec<String>,
+    instances: Vec<Instance>,
+    frames: Vec<Frame>,
+    globals: Vec<(ValType, bool, String)>,
+    memories: Vec<Memory>,
+    components: Vec<Component>,
+}
+
+/// A memory in a coredump.
+///
+/// Only the contents of its data segments are kept, keyed by their offset,
+/// and the rest of the memory is zero.
+struct Memory {
+    size: usize,
+    segments: Vec<(usize, Vec<u8>)>,
+}
+
+impl Memory {
+    /// Returns the bytes of `range`, which must be in bounds.
+    fn read(&self, range: Range<usize>) -> Vec<u8> {
+        let mut bytes = vec![0; range.len()];
+        for (offset, data) in &self.segments {
+            let start = range.start.max(*offset);
+            let end = range.end.min(offset + data.len());
+            if start < end {
+                bytes[start - range.start..end - range.start]
+                    .copy_from_slice(&data[start - offset..end - offset]);
+            }
+        }
+        bytes
+    }
+
+    /// Writes the whole memory to `file`.
+    fn write_to(&self, file: &mut std::fs::File) -> io::Result<()> {
+        use std::io::{Seek, SeekFrom};
+        for (offset, data) in &self.segments {
+            file.seek(SeekFrom::Start(*offset as u64))?;
+            file.write_all(data)?;
+        }
+        file.set_len(self.size as u64)
+    }
+}
+
+struct Instance {
+    module: u32,
+    memories: Vec<u32>,
+}
+
+/// A component instance, from Wasmtime's `corecomponents` section.
+struct Component {
+    parents: Vec<Option<u32>>,
+    core_instances: Vec<(u32, Option<u32>)>,
+    resource_tables: Vec<ResourceTable>,
+}
+
+struct ResourceTable {
+    component_instance: u32,
+    resource: u32,
+    handles: Vec<(u32, u32, bool)>,
+}
+
+struct Frame {
+    instance: u32,
+    func: u32,
+    code_offset: u32,
+    locals: Vec<Option<String>>,
+}
+
+impl CoreDump {
+    fn parse(bytes: &[u8]) -> Result<CoreDump> {
+        let mut dump = CoreDump::default();
+        let mut seen_core = false;
+        for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+            match payload? {
+                Payload::MemorySection(reader) => {
+                    for ty in reader {
+                        // Only the data segments are kept in memory, so the
+                        // declared size of a malformed coredump can't exhaust
+                        // memory.
+                        let size = ty?
+                            .initial
+                            .checked_mul(WASM_PAGE_SIZE)
+                            .and_then(|size| usize::try_from(size).ok())
+                            .context("memory is too large")?;
+                        dump.memories.push(Memory {
+                            size,
+                            segments: Vec::new(),
+                        });
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        let global = global?;
+                        let value = const_expr(global.init_expr.get_operators_reader())?;
+                        let value = match value {
+                            Some(value) => value.to_string(),
+                            None => "null".to_string(),
+                        };
+                        dump.globals
+                            .push((global.ty.content_type, global.ty.mutable, value));
+                    }
+                }
+                Payload::DataSection(reader) => {
+                    for data in reader {
+                        let data = data?;
+                        let DataKind::Active {
+                            memory_index,
+                            offset_expr,
+                        } = data.kind
+                        else {
+                            continue;
+                        };
+                        let offset = match const_expr(offset_expr.get_operators_reader())? {
+                            Some(Value::I32(offset)) => u64::from(offset as u32),
+                            Some(Value::I64(offset)) => offset as u64,
+                            _ => bail!("unsupported data segment offset"),
+                        };
+                        let memory = dump
+                            .memories
+                            .get_mut(memory_index as usize)
+                            .context("data segment for a memory that doesn't exist")?;
+                        let offset = usize::try_from(offset)
+                            .ok()
+                            .filter(|start| {
+                                start
+                                    .checked_add(data.data.len())
+                                    .is_some_and(|end| end <= memory.size)
+                            })
+                            .context("data segment is out of bounds")?;
+                        memory.segments.push((offset, data.data.to_vec()));
+                    }
+                }
+                Payload::CustomSection(section) => {
+                    let mut reader =
+                        BinaryReader::new_with_offset(section.data(), section.data_offset());
+                    match section.name() {
+                        "core" => {
+                            dump.name = reader.read::<CoreDumpSection>()?.name.to_string();
+                            seen_core = true;
+                        }
+                        "coremodules" => {
+                            let section = reader.read::<CoreDumpModulesSection>()?;
+                            dump.modules = section.modules.iter().map(|m| m.to_string()).collect();
+                        }
+                        "coreinstances" => {
+                            let section = reader.read::<CoreDumpInstancesSection>()?;
+                            dump.instances = section
+                                .instances
+                                .into_iter()
+                                .map(|i| Instance {
+                                    module: i.module_index,
+                                    memories: i.memories,
+                                })
+                                .collect();
+                        }
+                        "corecomponents" => dump.components = parse_components(reader)?,
+                        "corestack" => {
+                            let section = reader.read::<CoreDumpStackSection>()?;
+                            dump.frames = section
+                                .frames
+                                .into_iter()
+                                .map(|f| Frame {
+                                    instance: f.instanceidx,
+                                    func: f.funcidx,
+                                    code_offset: f.codeoffset,
+                                    locals: f
+                                        .locals
+                                        .iter()
+                                        .map(|v| match v {
+                                            CoreDumpValue::Missing => None,
+                                            CoreDumpValue::I32(x) => Some(format!("i32 {x}")),
+                                            CoreDumpValue::I64(x) => Some(format!("i64 {x}")),
+                                            CoreDumpValue::F32(x) => Some(format!("f32 {x}")),
+                                            CoreDumpValue::F64(x) => Some(format!("f64 {x}")),
+                                        })
+                                        .collect(),
+                                })
+                                .collect();
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !seen_core {
+            bail!("missing `core` custom section; this is not a wasm coredump");
+        }
+        Ok(dump)
+    }
+
+    fn write_stack(&self, out: &mut impl Write, modules: &HashMap<usize, Module>) -> Result<()> {
+        writeln!(out, "wasm coredump generated while executing {}", self.name)?;
+        writeln!(out, "stack:")?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let module = self
+                .instances
+                .get(frame.instance as usize)
+                .map(|i| i.module as usize);
+            let module_name = module
+                .and_then(|m| self.modules.get(m))
+                .map_or("<unknown>", |s| s.as_str());
+            writeln!(
+                out,
+                "  {i:>3}: {module_name}!func[{}] +{:#x}",
+                frame.func, frame.code_offset
+            )?;
+
+            let symbols = module
+                .and_then(|m| modules.get(&m))
+                .map(|m| m.frame_symbols(frame.func, frame.code_offset as usize))
+                .unwrap_or_default();
+            for symbol in symbols.iter() {
+                write!(out, "         at {}", symbol.name().unwrap_or("<unknown>"))?;
+                if let Some(file) = symbol.file() {
+                    write!(out, " ({file}")?;
+                    if let Some(line) = symbol.line() {
+                        write!(out, ":{line}")?;
+                        if let Some(column) = symbol.column() {
+                            write!(out, ":{column}")?;
+                        }
+                    }
+                    write!(out, ")")?;
+                }
+                writeln!(out)?;
+            }
+
+            for (index, local) in frame.locals.iter().enumerate() {
+                let local = local.as_deref().unwrap_or("<missing>");
+                writeln!(out, "         local[{index}] = {local}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_globals(&self, out: &mut impl Write) -> Result<()> {
+        writeln!(out, "globals:")?;
+        for (i, (ty, mutable, value)) in self.globals.iter().enumerate() {
+            let ty = match ty {
+                ValType::I32 => "i32",
+                ValType::I64 => "i64",
+                ValType::F32 => "f32",
+                ValType::F64 => "f64",
+                ValType::V128 => "v128",
+                ValType::Ref(_) => "ref",
+            };
+            let mutability = if *mutable { "mut " } else { "" };
+            writeln!(out, "  global[{i}]: {mutability}{ty} = {value}")?;
+        }
+        Ok(())
+    }
+
+    fn write_components(&self, out: &mut impl Write) -> Result<()> {
+        if self.components.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "components:")?;
+        for (i, component) in self.components.iter().enumerate() {
+            writeln!(out, "  component[{i}]:")?;
+            for (j, parent) in component.parents.iter().enumerate() {
+                match parent {
+                    Some(parent) => writeln!(
+                        out,
+                        "    component-instance[{j}]: instantiated by component-instance[{parent}]"
+                    )?,
+                    None => writeln!(out, "    component-instance[{j}]: root")?,
+                }
+            }
+            for (instance, owner) in component.core_instances.iter() {
+                match owner {
+                    Some(owner) => writeln!(
+                        out,
+                        "    instance[{instance}]: instantiated by component-instance[{owner}]"
+                    )?,
+                    None => writeln!(out, "    instance[{instance}]: adapter")?,
+                }
+            }
+            for table in component.resource_tables.iter() {
+                write!(
+                    out,
+                    "    component-instance[{}] resource[{}] handles:",
+                    table.component_instance, table.resource
+                )?;
+                if table.handles.is_empty() {
+                    write!(out, " none")?;
+                }
+                for (index, rep, own) in table.handles.iter() {
+                    let kind = if *own { "own" } else { "borrow" };
+                    write!(out, " {index}={kind}(rep {rep})")?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_memories(&self, out: &mut impl Write) -> Result<()> {
+        writeln!(out, "memories:")?;
+        for (i, memory) in self.memories.iter().enumerate() {
+            let pages = memory.size as u64 / WASM_PAGE_SIZE;
+            writeln!(
+                out,
+                "  memory[{i}]: {pages} pages ({:#x} bytes)",
+                memory.size
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses Wasmtime's `corecomponents` section, as documented on
+/// `WasmCoreDump::serialize`.
+fn parse_components(mut reader: BinaryReader<'_>) -> Result<Vec<Component>> {
+    fn option(reader: &mut BinaryReader<'_>) -> Result<Option<u32>> {
+        match reader.read_u8()? {
+            0x00 => Ok(None),
+            0x01 => Ok(Some(reader.read_var_u32()?)),
+            byte => bail!("invalid option discriminant {byte:#x}"),
+        }
+    }
+
+    fn vec<T>(
+        reader: &mut BinaryReader<'_>,
+        mut f: impl FnMut(&mut BinaryReader<'_>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        (0..reader.read_var_u32()?).map(|_| f(reader)).collect()
+    }
+
+    vec(&mut reader, |reader| {
+        Ok(Component {
+            parents: vec(reader, option)?,
+            core_instances: vec(reader, |r| Ok((r.read_var_u32()?, option(r)?)))?,
+            resource_tables: vec(reader, |r| {
+                Ok(ResourceTable {
+                    component_instance: r.read_var_u32()?,
+                    resource: r.read_var_u32()?,
+                    handles: vec(r, |r| {
+                        Ok((r.read_var_u32()?, r.read_var_u32()?, r.read_u8()? == 0x00))
+                    })?,
+                })
+            })?,
+        })
+    })
+}
+
+/// A value of a constant expression in a coredump.
+enum Value {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    V128(i128),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::I32(x) => write!(f, "{x}"),
+            Value::I64(x) => write!(f, "{x}"),
+            Value::F32(x) => write!(f, "{x}"),
+            Value::F64(x) => write!(f, "{x}"),
+            Value::V128(x) => write!(f, "{:#034x}", *x as u128),
+        }
+    }
+}
+
+/// Evaluates the single-instruction constant expressions a coredump uses,
+/// returning `None` for null references.
+fn const_expr(mut ops: wasmparser::OperatorsReader<'_>) -> Result<Option<Value>> {
+    let value = match ops.read()? {
+        Operator::I32Const { value } => Some(Value::I32(value)),
+        Operator::I64Const { value } => Some(Value::I64(value)),
+        Operator::F32Const { value } => Some(Value::F32(f32::from_bits(value.bits()))),
+        Operator::F64Const { value } => Some(Value::F64(f64::from_bits(value.bits()))),
+        Operator::V128Const { value } => Some(Value::V128(value.i128())),
+        Operator::RefNull { .. } => None,
+        op => bail!("unsupported constant expression: {op:?}"),
+    };
+    Ok(value)
+}
+
+/// Writes `bytes`, which start at `address`, in the style of `xxd`.
+fn hexdump(out: &mut impl Write, address: u64, bytes: &[u8]) -> Result<()> {
+    for (i, line) in bytes.chunks(16).enumerate() {
+        write!(out, "{:08x}: ", address + i as u64 * 16)?;
+        for j in 0..16 {
+            match line.get(j) {
+                Some(byte) => write!(out, "{byte:02x}")?,
+                None => write!(out, "  ")?,
+            }
+            if j % 2 == 1 {
+                write!(out, " ")?;
+            }
+        }
+        let ascii = line
+            .iter()
+            .map(|b| {
+                if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        writeln!(out, " {ascii}")?;
+    }
+    Ok(())
+}
@@ -23,6 +23,11 @@ pub struct ExploreCommand {
     /// provided)
     #[arg(short, long)]
     output: Option<PathBuf>,
+
+    /// Also compile the module with Winch and show its disassembly next to
+    /// Cranelift's
+    #[arg(long)]
+    compare_winch: bool,
 }
 
 impl ExploreCommand {
@@ -30,7 +35,17 @@ impl ExploreCommand {
     pub fn execute(mut self) -> Result<()> {
         self.common.init_logging()?;
 
+        // The explorer inspects the output of compiling the module, so it
+        // can't be loaded from the cache.
+        self.common.codegen.cache = Some(false);
         let config = self.common.config(self.target.as_deref())?;
+        let winch_config = if self.compare_winch {
+            let mut winch_config = config.clone();
+            winch_config.strategy(wasmtime::Strategy::Winch);
+            Some(winch_config)
+        } else {
+            None
+        };
 
         let wasm = std::fs::read(&self.module)
             .with_context(|| format!("failed to read Wasm module: {}", self.module.display()))?;
@@ -43,7 +58,13 @@ impl ExploreCommand {
             .with_context(|| format!("failed to create file: {}", output.display()))?;
         let mut output_file = std::io::BufWriter::new(output_file);
 
-        wasmtime_explorer::generate(&config, self.target.as_deref(), &wasm, &mut output_file)?;
+        wasmtime_explorer::generate(
+            &config,
+            winch_config.as_ref(),
+            self.target.as_deref(),
+            &wasm,
+            &mut output_file,
+        )?;
         println!("Exploration written to {}", output.display());
         Ok(())
     }
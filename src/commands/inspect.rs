@@ -0,0 +1,488 @@
+//! The module that implements the `wasmtime inspect` command.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use wasmparser::{Payload, ProducersSectionReader, TypeRef};
+use wasmtime::component::types::{self, ComponentFunc, ComponentItem};
+use wasmtime::component::{Component, ResourceType, Type};
+use wasmtime::{Engine, ExternType, Module, Mutability, Precompiled, PrecompiledInfo};
+use wasmtime_cli_flags::CommonOptions;
+
+/// Inspects a WebAssembly module, component, or precompiled artifact
+///
+/// This prints the imports and exports of modules and components along with
+/// their types, the memories and tables of modules, and the custom sections
+/// and producers of both. For precompiled `.cwasm` artifacts this prints the
+/// Wasmtime version, target, compiler settings and WebAssembly features they
+/// were compiled with.
+#[derive(Parser, PartialEq)]
+pub struct InspectCommand {
+    #[command(flatten)]
+    common: CommonOptions,
+
+    /// Also load precompiled artifacts to print their imports and exports.
+    ///
+    /// This is only possible for artifacts compiled for this host with
+    /// compatible settings, and as with `wasmtime run --allow-precompiled`
+    /// the artifact must be trusted.
+    #[arg(long)]
+    allow_precompiled: bool,
+
+    /// The WebAssembly module, component, or precompiled artifact to inspect
+    #[arg(required = true, value_name = "FILE")]
+    file: PathBuf,
+}
+
+impl InspectCommand {
+    /// Executes the command.
+    pub fn execute(mut self) -> Result<()> {
+        self.common.init_logging()?;
+
+        // Components are always accepted here since nothing is run.
+        if self.common.wasm.component_model.is_none() {
+            self.common.wasm.component_model = Some(true);
+        }
+        let engine = Engine::new(&self.common.config(None)?)?;
+
+        let bytes = std::fs::read(&self.file)
+            .with_context(|| format!("failed to read file: {}", self.file.display()))?;
+
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+
+        if engine.detect_precompiled(&bytes).is_some() {
+            let info = engine.precompiled_info(&bytes)?;
+            write_precompiled_info(&mut out, &info)?;
+            writeln!(out, "\ncompatibility with this host:")?;
+            let report = engine.detect_precompiled_compat(&bytes)?;
+            if report.is_empty() {
+                writeln!(out, "  compatible")?;
+            }
+            for incompatibility in report {
+                writeln!(out, "  {incompatibility}")?;
+            }
+            if self.allow_precompiled {
+                match info.kind() {
+                    Precompiled::Module => {
+                        let module = unsafe { Module::deserialize(&engine, &bytes) }
+                            .context("failed to load precompiled module")?;
+                        write_module_type(&mut out, &module)?;
+                    }
+                    Precompiled::Component => {
+                        let component = unsafe { Component::deserialize(&engine, &bytes) }
+                            .context("failed to load precompiled component")?;
+                        write_component_type(&mut out, &component.component_type())?;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // Parse the text format here specifically to add the path to the
+        // error message if there's a syntax error.
+        #[cfg(feature = "wat")]
+        let bytes = wat::parse_bytes(&bytes).map_err(|mut e| {
+            e.set_path(&self.file);
+            e
+        })?;
+
+        if wasmparser::Parser::is_component(&bytes) {
+            let component = Component::new(&engine, &bytes)?;
+            writeln!(out, "component")?;
+            write_component_type(&mut out, &component.component_type())?;
+        } else {
+            let module = Module::new(&engine, &bytes)?;
+            writeln!(out, "core module")?;
+            write_module_type(&mut out, &module)?;
+            write_memories_and_tables(&mut out, &bytes)?;
+        }
+        write_custom_sections(&mut out, &bytes)?;
+
+        Ok(())
+    }
+}
+
+fn write_precompiled_info(out: &mut impl Write, info: &PrecompiledInfo) -> Result<()> {
+    let kind = match info.kind() {
+        Precompiled::Module => "module",
+        Precompiled::Component => "component",
+    };
+    writeln!(out, "precompiled {kind}")?;
+    writeln!(out, "  version: {}", info.version())?;
+    writeln!(out, "  target: {}", info.target())?;
+    writeln!(out, "\nshared flags:")?;
+    for (name, value) in info.shared_flags() {
+        writeln!(out, "  {name} = {value}")?;
+    }
+    writeln!(out, "\nisa flags:")?;
+    for (name, value) in info.isa_flags() {
+        writeln!(out, "  {name} = {value}")?;
+    }
+    writeln!(out, "\nwasm features:")?;
+    for (name, enabled) in info.features() {
+        writeln!(out, "  {name} = {enabled}")?;
+    }
+    Ok(())
+}
+
+fn write_module_type(out: &mut impl Write, module: &Module) -> Result<()> {
+    writeln!(out, "\nimports:")?;
+    for import in module.imports() {
+        writeln!(
+            out,
+            "  {}::{}: {}",
+            import.module(),
+            import.name(),
+            extern_type(&import.ty())
+        )?;
+    }
+    writeln!(out, "\nexports:")?;
+    for export in module.exports() {
+        writeln!(out, "  {}: {}", export.name(), extern_type(&export.ty()))?;
+    }
+    Ok(())
+}
+
+/// Prints all memories and tables of the module `wasm`, including those which
+/// are neither imported nor exported.
+fn write_memories_and_tables(out: &mut impl Write, wasm: &[u8]) -> Result<()> {
+    let mut memories = Vec::new();
+    let mut tables = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::ImportSection(imports) => {
+                for import in imports {
+                    let import = import?;
+                    let from = format!(" (imported from {}::{})", import.module, import.name);
+                    match import.ty {
+                        TypeRef::Memory(ty) => memories.push(memory_type(&ty) + &from),
+                        TypeRef::Table(ty) => tables.push(table_type(&ty) + &from),
+                        _ => {}
+                    }
+                }
+            }
+            Payload::MemorySection(section) => {
+                for ty in section {
+                    memories.push(memory_type(&ty?));
+                }
+            }
+            Payload::TableSection(section) => {
+                for table in section {
+                    tables.push(table_type(&table?.ty));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    writeln!(out, "\nmemories:")?;
+    for (i, memory) in memories.iter().enumerate() {
+        writeln!(out, "  {i}: {memory}")?;
+    }
+    writeln!(out, "\ntables:")?;
+    for (i, table) in tables.iter().enumerate() {
+        writeln!(out, "  {i}: {table}")?;
+    }
+    Ok(())
+}
+
+/// Prints the custom sections of `wasm` and the contents of its `producers`
+/// section.
+///
+/// Only the sections of the outermost module or component are printed, not
+/// those of modules and components nested within a component.
+fn write_custom_sections(out: &mut impl Write, wasm: &[u8]) -> Result<()> {
+    let mut sections = Vec::new();
+    let mut producers = Vec::new();
+    let mut depth = 0;
+    for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => depth += 1,
+            Payload::End(_) => depth -= 1,
+            Payload::CustomSection(section) if depth == 0 => {
+                sections.push((section.name(), section.data().len()));
+                if section.name() == "producers" {
+                    let reader =
+                        ProducersSectionReader::new(section.data(), section.data_offset())?;
+                    for field in reader {
+                        let field = field?;
+                        for value in field.values {
+                            let value = value?;
+                            producers.push((field.name, value.name, value.version));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    writeln!(out, "\ncustom sections:")?;
+    for (name, size) in sections {
+        writeln!(out, "  {name}: {size} bytes")?;
+    }
+    if !producers.is_empty() {
+        writeln!(out, "\nproducers:")?;
+        for (field, name, version) in producers {
+            if version.is_empty() {
+                writeln!(out, "  {field}: {name}")?;
+            } else {
+                writeln!(out, "  {field}: {name} {version}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_component_type(out: &mut impl Write, ty: &types::Component) -> Result<()> {
+    let mut printer = ComponentPrinter {
+        resources: Vec::new(),
+    };
+    for (name, item) in ty.imports().chain(ty.exports()) {
+        printer.name_resources(name, item);
+    }
+
+    writeln!(out, "\nimports:")?;
+    for (name, item) in ty.imports() {
+        printer.item(out, 1, name, item)?;
+    }
+    writeln!(out, "\nexports:")?;
+    for (name, item) in ty.exports() {
+        printer.item(out, 1, name, item)?;
+    }
+    Ok(())
+}
+
+/// Prints the types of components in a WIT-like syntax.
+struct ComponentPrinter {
+    /// The names resources are imported or exported with, used to refer to
+    /// them in the types of functions.
+    resources: Vec<(ResourceType, String)>,
+}
+
+impl ComponentPrinter {
+    fn name_resources(&mut self, name: &str, item: &ComponentItem) {
+        match item {
+            ComponentItem::Resource(ty) => {
+                if !self.resources.iter().any(|(r, _)| r == ty) {
+                    self.resources.push((*ty, name.to_string()));
+                }
+            }
+            ComponentItem::ComponentInstance(instance) => {
+                for (name, item) in instance.exports() {
+                    self.name_resources(name, item);
+                }
+            }
+            ComponentItem::Component(component) => {
+                for (name, item) in component.imports().chain(component.exports()) {
+                    self.name_resources(name, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn item(
+        &self,
+        out: &mut impl Write,
+        indent: usize,
+        name: &str,
+        item: &ComponentItem,
+    ) -> Result<()> {
+        let pad = "  ".repeat(indent);
+        match item {
+            ComponentItem::ComponentFunc(func) => {
+                writeln!(out, "{pad}{name}: {}", self.func(func))?;
+            }
+            ComponentItem::CoreFunc(ty) => {
+                writeln!(
+                    out,
+                    "{pad}{name}: core {}",
+                    extern_type(&ExternType::Func(ty.clone()))
+                )?;
+            }
+            ComponentItem::Module(module) => {
+                writeln!(out, "{pad}{name}: core module {{")?;
+                for (module, field, ty) in module.imports() {
+                    writeln!(out, "{pad}  import {module}::{field}: {}", extern_type(ty))?;
+                }
+                for (field, ty) in module.exports() {
+                    writeln!(out, "{pad}  export {field}: {}", extern_type(ty))?;
+                }
+                writeln!(out, "{pad}}}")?;
+            }
+            ComponentItem::Component(component) => {
+                writeln!(out, "{pad}{name}: component {{")?;
+                for (name, item) in component.imports() {
+                    self.item(out, indent + 1, &format!("import {name}"), item)?;
+                }
+                for (name, item) in component.exports() {
+                    self.item(out, indent + 1, &format!("export {name}"), item)?;
+                }
+                writeln!(out, "{pad}}}")?;
+            }
+            ComponentItem::ComponentInstance(instance) => {
+                writeln!(out, "{pad}{name}: instance {{")?;
+                for (name, item) in instance.exports() {
+                    self.item(out, indent + 1, name, item)?;
+                }
+                writeln!(out, "{pad}}}")?;
+            }
+            ComponentItem::Type(ty) => writeln!(out, "{pad}{name}: type {}", self.ty(ty))?,
+            ComponentItem::Resource(_) => writeln!(out, "{pad}{name}: resource")?,
+        }
+        Ok(())
+    }
+
+    fn func(&self, func: &ComponentFunc) -> String {
+        let params = func
+            .params()
+            .map(|(name, ty)| format!("{name}: {}", self.ty(ty)))
+            .collect::<Vec<_>>();
+        let mut s = format!("func({})", params.join(", "));
+        let results = func.results().collect::<Vec<_>>();
+        match results.as_slice() {
+            [] => {}
+            [(None, ty)] => s.push_str(&format!(" -> {}", self.ty(ty))),
+            results => {
+                let results = results
+                    .iter()
+                    .map(|(name, ty)| format!("{}: {}", name.unwrap_or("_"), self.ty(ty)))
+                    .collect::<Vec<_>>();
+                s.push_str(&format!(" -> ({})", results.join(", ")));
+            }
+        }
+        s
+    }
+
+    fn ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::Bool => "bool".to_string(),
+            Type::S8 => "s8".to_string(),
+            Type::U8 => "u8".to_string(),
+            Type::S16 => "s16".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::S32 => "s32".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::S64 => "s64".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::Float32 => "float32".to_string(),
+            Type::Float64 => "float64".to_string(),
+            Type::Char => "char".to_string(),
+            Type::String => "string".to_string(),
+            Type::List(list) => format!("list<{}>", self.ty(&list.ty())),
+            Type::Record(record) => {
+                let fields = record
+                    .fields()
+                    .map(|field| format!("{}: {}", field.name, self.ty(&field.ty)))
+                    .collect::<Vec<_>>();
+                format!("record {{ {} }}", fields.join(", "))
+            }
+            Type::Tuple(tuple) => {
+                let types = tuple.types().map(|ty| self.ty(&ty)).collect::<Vec<_>>();
+                format!("tuple<{}>", types.join(", "))
+            }
+            Type::Variant(variant) => {
+                let cases = variant
+                    .cases()
+                    .map(|case| match &case.ty {
+                        Some(ty) => format!("{}({})", case.name, self.ty(ty)),
+                        None => case.name.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("variant {{ {} }}", cases.join(", "))
+            }
+            Type::Enum(enum_) => {
+                format!(
+                    "enum {{ {} }}",
+                    enum_.names().collect::<Vec<_>>().join(", ")
+                )
+            }
+            Type::Option(option) => format!("option<{}>", self.ty(&option.ty())),
+            Type::Result(result) => match (result.ok(), result.err()) {
+                (None, None) => "result".to_string(),
+                (Some(ok), None) => format!("result<{}>", self.ty(&ok)),
+                (None, Some(err)) => format!("result<_, {}>", self.ty(&err)),
+                (Some(ok), Some(err)) => format!("result<{}, {}>", self.ty(&ok), self.ty(&err)),
+            },
+            Type::Flags(flags) => {
+                format!(
+                    "flags {{ {} }}",
+                    flags.names().collect::<Vec<_>>().join(", ")
+                )
+            }
+            Type::Own(resource) => format!("own<{}>", self.resource(resource)),
+            Type::Borrow(resource) => format!("borrow<{}>", self.resource(resource)),
+        }
+    }
+
+    fn resource(&self, ty: &ResourceType) -> &str {
+        self.resources
+            .iter()
+            .find(|(r, _)| r == ty)
+            .map(|(_, name)| name.as_str())
+            .unwrap_or("resource")
+    }
+}
+
+fn extern_type(ty: &ExternType) -> String {
+    match ty {
+        ExternType::Func(ty) => {
+            let params = ty.params().map(|ty| ty.to_string()).collect::<Vec<_>>();
+            let results = ty.results().map(|ty| ty.to_string()).collect::<Vec<_>>();
+            match results.as_slice() {
+                [] => format!("func({})", params.join(", ")),
+                [result] => format!("func({}) -> {result}", params.join(", ")),
+                _ => format!("func({}) -> ({})", params.join(", "), results.join(", ")),
+            }
+        }
+        ExternType::Global(ty) => match ty.mutability() {
+            Mutability::Const => format!("global {}", ty.content()),
+            Mutability::Var => format!("global mut {}", ty.content()),
+        },
+        ExternType::Table(ty) => format!(
+            "table {} {}",
+            ty.element(),
+            limits(ty.minimum().into(), ty.maximum().map(Into::into))
+        ),
+        ExternType::Memory(ty) => {
+            let mut s = format!("memory {} pages", limits(ty.minimum(), ty.maximum()));
+            if ty.is_64() {
+                s.push_str(", 64-bit");
+            }
+            if ty.is_shared() {
+                s.push_str(", shared");
+            }
+            s
+        }
+    }
+}
+
+fn memory_type(ty: &wasmparser::MemoryType) -> String {
+    let mut s = format!("{} pages", limits(ty.initial, ty.maximum));
+    if ty.memory64 {
+        s.push_str(", 64-bit");
+    }
+    if ty.shared {
+        s.push_str(", shared");
+    }
+    s
+}
+
+fn table_type(ty: &wasmparser::TableType) -> String {
+    format!(
+        "{} {}",
+        ty.element_type,
+        limits(ty.initial.into(), ty.maximum.map(Into::into))
+    )
+}
+
+fn limits(min: u64, max: Option<u64>) -> String {
+    match max {
+        Some(max) if max == min => min.to_string(),
+        Some(max) => format!("{min}..{max}"),
+        None => format!("{min}.."),
+    }
+}
@@ -23,13 +23,19 @@ use tracing::info;
 #[cfg(feature = "wasi-nn")]
 use wasmtime_wasi_nn::WasiNnCtx;
 
+#[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+use wasmtime_wasi_threads::component::SharedWasi;
 #[cfg(feature = "wasi-threads")]
 use wasmtime_wasi_threads::WasiThreadsCtx;
 
 #[cfg(feature = "wasi-http")]
 use wasmtime_wasi_http::WasiHttpCtx;
 
+#[cfg(feature = "gdb-server")]
+mod gdb_server;
 mod wali;
+#[cfg(feature = "component-model")]
+mod wave;
 
 fn parse_env_var(s: &str) -> Result<(String, Option<String>)> {
     let mut parts = s.splitn(2, '=');
@@ -84,6 +90,11 @@ pub struct RunCommand {
     pub vars: Vec<(String, Option<String>)>,
 
     /// The name of the function to run
+    ///
+    /// For components this is a call expression such as
+    /// `iface#func(arg, ...)` whose arguments and results are written in the
+    /// WebAssembly Value Encoding (WAVE), for example `add(1, 2)` or
+    /// `wasi:cli/run@0.2.0#run()`.
     #[arg(long, value_name = "FUNCTION")]
     pub invoke: Option<String>,
 
@@ -96,6 +107,22 @@ pub struct RunCommand {
     )]
     pub preloads: Vec<(String, PathBuf)>,
 
+    /// Count how many times each basic block of wasm code executes and write
+    /// the resulting line coverage to the given path in the `lcov` format.
+    ///
+    /// Lines are recovered from the DWARF debugging information embedded in
+    /// the wasm modules, so modules without it won't be reported.
+    #[arg(long, value_name = "PATH")]
+    pub coverage: Option<PathBuf>,
+
+    /// Wait for a debugger to connect to the given address before running,
+    /// and let it debug wasm using the GDB remote serial protocol.
+    ///
+    /// The guest stops before its first instruction. LLDB can connect with
+    /// `process connect --plugin wasm connect://ADDR`.
+    #[arg(long, value_name = "ADDR")]
+    pub gdb_server: Option<String>,
+
     /// The WebAssembly module to run and arguments to pass to it.
     ///
     /// Arguments passed to the wasm module will be configured as WASI CLI
@@ -139,12 +166,24 @@ impl RunCommand {
             Some(Profile::Native(s)) => {
                 config.profiler(s);
             }
-            Some(Profile::Guest { .. }) => {
+            Some(Profile::Guest { .. }) | Some(Profile::Counters { .. }) => {
                 // Further configured down below as well.
                 config.epoch_interruption(true);
             }
             None => {}
         }
+        if self.coverage.is_some() {
+            config.coverage_instrumentation(true);
+            // Lines are found through the DWARF of the module, which is only
+            // retained along with backtrace details.
+            config.wasm_backtrace_details(wasmtime::WasmBacktraceDetails::Enable);
+        }
+        if self.gdb_server.is_some() {
+            #[cfg(feature = "gdb-server")]
+            config.debug_breakpoints(true);
+            #[cfg(not(feature = "gdb-server"))]
+            bail!("support for `--gdb-server` disabled at compile time");
+        }
 
         let engine = Engine::new(&config)?;
 
@@ -207,7 +246,11 @@ impl RunCommand {
     ) -> Result<(), Error> {
         let mut store = Store::new(&engine, host);
         self.populate_with_wasi(linker, &mut store, main)?;
-        store.data_mut().limits = self.run.store_limits();
+        let host = store.data_mut();
+        host.limits = self.run.store_limits();
+        if let Some(table) = Arc::get_mut(&mut host.preview2_table) {
+            table.apply_limits(&host.limits);
+        }
         store.limiter(|t| &mut t.limits);
         if let Some(fuel) = self.run.common.wasm.fuel {
             store.set_fuel(fuel)?;
@@ -320,17 +363,32 @@ impl RunCommand {
     fn setup_epoch_handler(
         &self,
         store: &mut Store<Host>,
+        main: &RunTarget,
         modules: Vec<(String, Module)>,
     ) -> Result<Box<dyn FnOnce(&mut Store<Host>)>> {
         if let Some(Profile::Guest { path, interval }) = &self.run.profile {
             #[cfg(feature = "profiling")]
-            return Ok(self.setup_guest_profiler(store, modules, path, *interval));
+            return Ok(self.setup_guest_profiler(store, main, modules, path, *interval));
             #[cfg(not(feature = "profiling"))]
             {
-                let _ = (modules, path, interval);
+                let _ = (main, modules, path, interval);
                 bail!("support for profiling disabled at compile time");
             }
         }
+        if let Some(Profile::Counters {
+            path,
+            events,
+            period,
+        }) = &self.run.profile
+        {
+            #[cfg(all(feature = "profiling", target_os = "linux"))]
+            return self.setup_counters_profiler(store, path, events, *period);
+            #[cfg(not(all(feature = "profiling", target_os = "linux")))]
+            {
+                let _ = (path, events, period);
+                bail!("support for profiling with performance counters is not available");
+            }
+        }
 
         if let Some(timeout) = self.run.common.wasm.timeout {
             store.set_epoch_deadline(1);
@@ -344,10 +402,66 @@ impl RunCommand {
         Ok(Box::new(|_store| {}))
     }
 
+    fn setup_gdb_server(
+        &self,
+        store: &mut Store<Host>,
+        modules: &[(String, Module)],
+    ) -> Result<Box<dyn FnOnce(&Result<()>)>> {
+        let addr = match &self.gdb_server {
+            Some(addr) => addr,
+            None => return Ok(Box::new(|_| {})),
+        };
+        #[cfg(feature = "gdb-server")]
+        {
+            use std::sync::Mutex;
+
+            // The debugger reads the original wasm binaries to find their
+            // debugging information, so load them again for it.
+            let mut debug_modules = Vec::new();
+            for (name, module) in modules {
+                let path = if name.is_empty() {
+                    Path::new(&self.module_and_args[0])
+                } else {
+                    self.preloads
+                        .iter()
+                        .find(|(preload, _)| preload == name)
+                        .map(|(_, path)| path.as_path())
+                        .unwrap()
+                };
+                let bytes = std::fs::read(path).unwrap_or_default();
+                #[cfg(feature = "wat")]
+                let bytes = wat::parse_bytes(&bytes)
+                    .map(|b| b.into_owned())
+                    .unwrap_or_default();
+                let name = path.file_name().unwrap_or(path.as_os_str());
+                debug_modules.push((name.to_string_lossy().into_owned(), module.clone(), bytes));
+            }
+
+            let server = Arc::new(Mutex::new(gdb_server::GdbServer::accept(
+                addr,
+                debug_modules,
+            )?));
+            let callback_server = server.clone();
+            store.debug_callback(move |store, stop| {
+                callback_server.lock().unwrap().stopped(store, stop)
+            });
+            store.single_step(true);
+            return Ok(Box::new(move |result| {
+                server.lock().unwrap().exited(result);
+            }));
+        }
+        #[cfg(not(feature = "gdb-server"))]
+        {
+            let _ = (store, modules, addr);
+            bail!("support for `--gdb-server` disabled at compile time");
+        }
+    }
+
     #[cfg(feature = "profiling")]
     fn setup_guest_profiler(
         &self,
         store: &mut Store<Host>,
+        main: &RunTarget,
         modules: Vec<(String, Module)>,
         path: &str,
         interval: std::time::Duration,
@@ -355,8 +469,14 @@ impl RunCommand {
         use wasmtime::{AsContextMut, GuestProfiler, UpdateDeadline};
 
         let module_name = self.module_and_args[0].to_str().unwrap_or("<main module>");
-        store.data_mut().guest_profiler =
-            Some(Arc::new(GuestProfiler::new(module_name, interval, modules)));
+        let profiler = match main {
+            RunTarget::Core(_) => GuestProfiler::new(module_name, interval, modules),
+            #[cfg(feature = "component-model")]
+            RunTarget::Component(component) => {
+                GuestProfiler::new_component(module_name, interval, component.clone(), modules)
+            }
+        };
+        store.data_mut().guest_profiler = Some(Arc::new(profiler));
 
         fn sample(mut store: impl AsContextMut<Data = Host>) {
             let mut profiler = store
@@ -400,17 +520,75 @@ impl RunCommand {
         return Box::new(move |store| {
             let profiler = Arc::try_unwrap(store.data_mut().guest_profiler.take().unwrap())
                 .expect("profiling doesn't support threads yet");
+            let format = crate::common::guest_profile_format(&path);
+            crate::common::write_guest_profile(profiler, format, &path);
+        });
+    }
+
+    #[cfg(all(feature = "profiling", target_os = "linux"))]
+    fn setup_counters_profiler(
+        &self,
+        store: &mut Store<Host>,
+        path: &str,
+        events: &[String],
+        period: u64,
+    ) -> Result<Box<dyn FnOnce(&mut Store<Host>)>> {
+        use std::sync::Mutex;
+        use wasmtime::{PerfEvent, PerfEventFormat, PerfEventProfiler, UpdateDeadline};
+
+        let events = events
+            .iter()
+            .map(|event| event.parse())
+            .collect::<Result<Vec<PerfEvent>>>()?;
+        let profiler = Arc::new(Mutex::new(Some(PerfEventProfiler::new(&events, period)?)));
+
+        // Samples are collected from the kernel's buffer on every epoch tick,
+        // which also counts down the timeout.
+        let interval = std::time::Duration::from_millis(10);
+        let mut timeout =
+            self.run.common.wasm.timeout.map(|timeout| {
+                ((timeout.as_secs_f64() / interval.as_secs_f64()).ceil() as u64).max(1)
+            });
+        let callback_profiler = profiler.clone();
+        store.epoch_deadline_callback(move |store| {
+            if let Some(profiler) = callback_profiler.lock().unwrap().as_mut() {
+                profiler.collect(&store);
+            }
+            if let Some(timeout) = &mut timeout {
+                *timeout -= 1;
+                if *timeout == 0 {
+                    bail!("timeout exceeded");
+                }
+            }
+            Ok(UpdateDeadline::Continue(1))
+        });
+
+        store.set_epoch_deadline(1);
+        let engine = store.engine().clone();
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            engine.increment_epoch();
+        });
+
+        let path = path.to_string();
+        Ok(Box::new(move |store| {
+            let mut profiler = profiler.lock().unwrap().take().unwrap();
+            profiler.collect(&*store);
+            let format = if path.ends_with(".pb") {
+                PerfEventFormat::Pprof
+            } else {
+                PerfEventFormat::Firefox
+            };
             if let Err(e) = std::fs::File::create(&path)
                 .map_err(anyhow::Error::new)
-                .and_then(|output| profiler.finish(std::io::BufWriter::new(output)))
+                .and_then(|output| profiler.finish(format, std::io::BufWriter::new(output)))
             {
                 eprintln!("failed writing profile at {path}: {e:#}");
             } else {
                 eprintln!();
                 eprintln!("Profile written to: {path}");
-                eprintln!("View this profile at https://profiler.firefox.com/.");
             }
-        });
+        }))
     }
 
     fn load_main_module(
@@ -423,31 +601,38 @@ impl RunCommand {
         // The main module might be allowed to have unknown imports, which
         // should be defined as traps:
         if self.run.common.wasm.unknown_imports_trap == Some(true) {
-            #[cfg(feature = "cranelift")]
             match linker {
                 CliLinker::Core(linker) => {
+                    #[cfg(feature = "cranelift")]
                     linker.define_unknown_imports_as_traps(module.unwrap_core())?;
+                    #[cfg(not(feature = "cranelift"))]
+                    bail!("support for `unknown-imports-trap` disabled at compile time");
+                }
+                #[cfg(feature = "component-model")]
+                CliLinker::Component(linker) => {
+                    linker.define_unknown_imports_as_traps(module.unwrap_component())?;
                 }
-                _ => bail!("cannot use `--trap-unknown-imports` with components"),
             }
-            #[cfg(not(feature = "cranelift"))]
-            bail!("support for `unknown-imports-trap` disabled at compile time");
         }
 
         // ...or as default values.
         if self.run.common.wasm.unknown_imports_default == Some(true) {
-            #[cfg(feature = "cranelift")]
             match linker {
                 CliLinker::Core(linker) => {
+                    #[cfg(feature = "cranelift")]
                     linker.define_unknown_imports_as_default_values(module.unwrap_core())?;
+                    #[cfg(not(feature = "cranelift"))]
+                    bail!("support for `unknown-imports-default` disabled at compile time");
+                }
+                #[cfg(feature = "component-model")]
+                CliLinker::Component(linker) => {
+                    linker.define_unknown_imports_as_default_values(module.unwrap_component())?;
                 }
-                _ => bail!("cannot use `--default-values-unknown-imports` with components"),
             }
-            #[cfg(not(feature = "cranelift"))]
-            bail!("support for `unknown-imports-trap` disabled at compile time");
         }
 
-        let finish_epoch_handler = self.setup_epoch_handler(store, modules)?;
+        let finish_gdb_server = self.setup_gdb_server(store, &modules)?;
+        let finish_epoch_handler = self.setup_epoch_handler(store, module, modules)?;
 
         let result = match linker {
             CliLinker::Core(linker) => {
@@ -484,33 +669,198 @@ impl RunCommand {
             }
             #[cfg(feature = "component-model")]
             CliLinker::Component(linker) => {
-                if self.invoke.is_some() {
-                    bail!("using `--invoke` with components is not supported");
-                }
-
                 let component = module.unwrap_component();
 
-                let (command, _instance) =
-                    preview2::command::sync::Command::instantiate(&mut *store, component, linker)?;
-                let result = command
-                    .wasi_cli_run()
-                    .call_run(&mut *store)
-                    .context("failed to invoke `run` function")
-                    .map_err(|e| self.handle_core_dump(&mut *store, e));
-
-                // Translate the `Result<(),()>` produced by wasm into a feigned
-                // explicit exit here with status 1 if `Err(())` is returned.
-                result.and_then(|wasm_result| match wasm_result {
-                    Ok(()) => Ok(()),
-                    Err(()) => Err(wasmtime_wasi::I32Exit(1).into()),
-                })
+                let instance_pre = self.instantiate_component_pre(store, linker, component)?;
+
+                if let Some(invoke) = &self.invoke {
+                    instance_pre
+                        .instantiate(&mut *store)
+                        .and_then(|instance| {
+                            self.set_component_threads_instance(store, &instance)?;
+                            Ok(instance)
+                        })
+                        .map_err(|e| self.handle_core_dump(&mut *store, e))
+                        .and_then(|instance| self.invoke_component_func(store, &instance, invoke))
+                } else {
+                    let (command, instance) = preview2::command::sync::Command::instantiate_pre(
+                        &mut *store,
+                        &instance_pre,
+                    )
+                    .map_err(|e| self.handle_core_dump(&mut *store, e))?;
+                    self.set_component_threads_instance(store, &instance)?;
+                    let result = command
+                        .wasi_cli_run()
+                        .call_run(&mut *store)
+                        .context("failed to invoke `run` function")
+                        .map_err(|e| self.handle_core_dump(&mut *store, e));
+
+                    // Translate the `Result<(),()>` produced by wasm into a
+                    // feigned explicit exit here with status 1 if `Err(())` is
+                    // returned.
+                    result.and_then(|wasm_result| match wasm_result {
+                        Ok(()) => Ok(()),
+                        Err(()) => Err(wasmtime_wasi::I32Exit(1).into()),
+                    })
+                }
             }
         };
         finish_epoch_handler(store);
+        finish_gdb_server(&result);
+
+        if let Some(path) = &self.coverage {
+            if let Err(e) = std::fs::File::create(path)
+                .map_err(anyhow::Error::new)
+                .and_then(|output| store.coverage().write_lcov(std::io::BufWriter::new(output)))
+            {
+                eprintln!("failed writing coverage at {}: {e:#}", path.display());
+            }
+        }
 
         result
     }
 
+    /// Pre-instantiates `component`, and creates the wasi-threads context for
+    /// it if threads are enabled.
+    #[cfg(feature = "component-model")]
+    fn instantiate_component_pre(
+        &self,
+        store: &mut Store<Host>,
+        linker: &wasmtime::component::Linker<Host>,
+        component: &wasmtime::component::Component,
+    ) -> Result<wasmtime::component::InstancePre<Host>> {
+        let instance_pre = linker.instantiate_pre(component)?;
+
+        #[cfg(feature = "wasi-threads")]
+        if self.run.common.wasi.threads == Some(true) {
+            // Threads share the preview2 context and resource table of the
+            // main thread, taking turns using them in host calls.
+            let host = store.data_mut();
+            let ctx = host
+                .preview2_ctx
+                .take()
+                .and_then(Arc::into_inner)
+                .expect("preview2 context is not shared yet");
+            let table = Arc::into_inner(std::mem::take(&mut host.preview2_table))
+                .expect("preview2 resource table is not shared yet");
+            let wasi = SharedWasi::new(ctx, table);
+            let shared = wasi.share();
+            host.preview2_shared = Some(wasi);
+            store.call_hook(Host::shared_wasi_call_hook);
+
+            #[cfg(feature = "wasi-http")]
+            let http = store.data().wasi_http.is_some();
+            let mut ctx = wasmtime_wasi_threads::component::WasiThreadsCtx::new(
+                instance_pre.clone(),
+                move |ctx, engine| {
+                    let mut host = Host::default();
+                    host.preview2_shared = Some(shared.share());
+                    #[cfg(feature = "wasi-http")]
+                    if http {
+                        host.wasi_http = Some(Arc::new(WasiHttpCtx {}));
+                    }
+                    host.wasi_threads_component = Some(ctx.clone());
+                    let mut store = Store::new(engine, host);
+                    store.call_hook(Host::shared_wasi_call_hook);
+                    Ok(store)
+                },
+            );
+            if let Some(max) = self.run.common.wasi.max_threads {
+                ctx = ctx.max_threads(max);
+            }
+            store.data_mut().wasi_threads_component = Some(ctx);
+        }
+        #[cfg(not(feature = "wasi-threads"))]
+        let _ = store;
+
+        Ok(instance_pre)
+    }
+
+    /// Gives the wasi-threads context, if any, the shared memories of the
+    /// main instance of the component.
+    #[cfg(feature = "component-model")]
+    fn set_component_threads_instance(
+        &self,
+        store: &mut Store<Host>,
+        instance: &wasmtime::component::Instance,
+    ) -> Result<()> {
+        #[cfg(feature = "wasi-threads")]
+        if let Some(ctx) = store.data().wasi_threads_component.clone() {
+            ctx.set_instance(&mut *store, instance)?;
+        }
+        #[cfg(not(feature = "wasi-threads"))]
+        let _ = (store, instance);
+        Ok(())
+    }
+
+    /// Invokes the component export described by `invoke`, an expression
+    /// such as `iface#func(arg, ...)` whose arguments are written in the WAVE
+    /// syntax, and prints the results in the same syntax.
+    #[cfg(feature = "component-model")]
+    fn invoke_component_func<Context>(
+        &self,
+        store: &mut Store<Context>,
+        instance: &wasmtime::component::Instance,
+        invoke: &str,
+    ) -> Result<()> {
+        let call = wave::parse_call(invoke)?;
+        let func = {
+            let mut exports = instance.exports(&mut *store);
+            match call.instance {
+                Some(name) => exports
+                    .instance(name)
+                    .ok_or_else(|| anyhow!("no instance export named `{name}` found"))?
+                    .func(call.func),
+                None => exports.root().func(call.func),
+            }
+        }
+        .ok_or_else(|| anyhow!("no func export named `{}` found", call.func))?;
+
+        let params = func.params(&*store);
+        let args = self.module_and_args.iter().skip(1);
+        let values = match call.args {
+            Some(text) => {
+                if args.len() > 0 {
+                    bail!("arguments must be passed within `--invoke` when it contains `(...)`");
+                }
+                wave::parse_values(text, &params)
+                    .with_context(|| format!("failed to parse arguments for `{invoke}`"))?
+            }
+            None => {
+                if args.len() != params.len() {
+                    bail!(
+                        "`{invoke}` takes {} arguments but {} were given",
+                        params.len(),
+                        args.len()
+                    );
+                }
+                args.zip(params.iter())
+                    .map(|(arg, ty)| {
+                        let arg = arg
+                            .to_str()
+                            .ok_or_else(|| anyhow!("argument is not valid utf-8: {arg:?}"))?;
+                        wave::parse_value(arg, ty)
+                            .with_context(|| format!("failed to parse argument `{arg}`"))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+        };
+
+        let mut results = vec![wasmtime::component::Val::Bool(false); func.results(&*store).len()];
+        let invoke_res = func
+            .call(&mut *store, &values, &mut results)
+            .and_then(|()| func.post_return(&mut *store))
+            .with_context(|| format!("failed to invoke `{invoke}`"));
+        if let Err(err) = invoke_res {
+            return Err(self.handle_core_dump(&mut *store, err));
+        }
+
+        for result in results {
+            println!("{}", wave::to_string(&result));
+        }
+        Ok(())
+    }
+
     fn invoke_func<Context>(&self, store: &mut Store<Context>, func: Func) -> Result<()> {
         let ty = func.ty(&store);
         if ty.params().len() > 0 {
@@ -599,7 +949,9 @@ impl RunCommand {
             .to_str()
             .unwrap_or_else(|| "unknown");
 
-        if let Err(coredump_err) = write_core_dump(store, &err, &source_name, coredump_path) {
+        if let Err(coredump_err) =
+            crate::common::write_core_dump(store, &err, &source_name, coredump_path)
+        {
             eprintln!("warning: coredump failed to generate: {}", coredump_err);
             err
         } else {
@@ -709,19 +1061,28 @@ impl RunCommand {
                 );
             }
             #[cfg(feature = "wasi-threads")]
-            {
-                let linker = match linker {
-                    CliLinker::Core(linker) => linker,
-                    _ => bail!("wasi-threads does not support components yet"),
-                };
-                let module = module.unwrap_core();
-                wasmtime_wasi_threads::add_to_linker(linker, store, &module, |host| {
-                    host.wasi_threads.as_ref().unwrap()
-                })?;
-                store.data_mut().wasi_threads = Some(Arc::new(WasiThreadsCtx::new(
-                    module.clone(),
-                    Arc::new(linker.clone()),
-                )?));
+            match linker {
+                CliLinker::Core(linker) => {
+                    if self.run.common.wasi.max_threads.is_some() {
+                        bail!("`-S max-threads` is only supported for components");
+                    }
+                    let module = module.unwrap_core();
+                    wasmtime_wasi_threads::add_to_linker(linker, store, &module, |host| {
+                        host.wasi_threads.as_ref().unwrap()
+                    })?;
+                    store.data_mut().wasi_threads = Some(Arc::new(WasiThreadsCtx::new(
+                        module.clone(),
+                        Arc::new(linker.clone()),
+                    )?));
+                }
+                #[cfg(feature = "component-model")]
+                CliLinker::Component(linker) => {
+                    // The context itself needs the pre-instantiated component
+                    // so it's created in `instantiate_component_pre`.
+                    wasmtime_wasi_threads::component::add_to_linker(linker, |host| {
+                        host.wasi_threads_component.as_ref().unwrap()
+                    })?;
+                }
             }
         }
 
@@ -845,6 +1206,12 @@ struct Host {
     wasi_nn: Option<Arc<WasiNnCtx>>,
     #[cfg(feature = "wasi-threads")]
     wasi_threads: Option<Arc<WasiThreadsCtx<Host>>>,
+    #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+    wasi_threads_component: Option<wasmtime_wasi_threads::component::WasiThreadsCtx<Host>>,
+    // Preview2 state shared with the threads of a component, used instead of
+    // `preview2_ctx` and `preview2_table` when set.
+    #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+    preview2_shared: Option<SharedWasi>,
     #[cfg(feature = "wasi-http")]
     wasi_http: Option<Arc<WasiHttpCtx>>,
     limits: StoreLimits,
@@ -852,20 +1219,46 @@ struct Host {
     guest_profiler: Option<Arc<wasmtime::GuestProfiler>>,
 }
 
+#[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+impl Host {
+    fn shared_wasi_call_hook(&mut self, hook: wasmtime::CallHook) -> Result<()> {
+        match &mut self.preview2_shared {
+            Some(wasi) => wasi.call_hook(hook),
+            None => Ok(()),
+        }
+    }
+}
+
 impl preview2::WasiView for Host {
     fn table(&self) -> &wasmtime::component::ResourceTable {
+        #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+        if let Some(wasi) = &self.preview2_shared {
+            return wasi.table();
+        }
         &self.preview2_table
     }
 
     fn table_mut(&mut self) -> &mut wasmtime::component::ResourceTable {
+        #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+        if let Some(wasi) = &mut self.preview2_shared {
+            return wasi.table_mut();
+        }
         Arc::get_mut(&mut self.preview2_table).expect("preview2 is not compatible with threads")
     }
 
     fn ctx(&self) -> &preview2::WasiCtx {
+        #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+        if let Some(wasi) = &self.preview2_shared {
+            return wasi.ctx();
+        }
         self.preview2_ctx.as_ref().unwrap()
     }
 
     fn ctx_mut(&mut self) -> &mut preview2::WasiCtx {
+        #[cfg(all(feature = "wasi-threads", feature = "component-model"))]
+        if let Some(wasi) = &mut self.preview2_shared {
+            return wasi.ctx_mut();
+        }
         let ctx = self.preview2_ctx.as_mut().unwrap();
         Arc::get_mut(ctx).expect("preview2 is not compatible with threads")
     }
@@ -889,7 +1282,7 @@ impl wasmtime_wasi_http::types::WasiHttpView for Host {
     }
 
     fn table(&mut self) -> &mut wasmtime::component::ResourceTable {
-        Arc::get_mut(&mut self.preview2_table).expect("preview2 is not compatible with threads")
+        preview2::WasiView::table_mut(self)
     }
 }
 
@@ -921,27 +1314,3 @@ fn ctx_set_listenfd(mut num_fd: usize, builder: &mut WasiCtxBuilder) -> Result<u
 
     Ok(num_fd)
 }
-
-#[cfg(feature = "coredump")]
-fn write_core_dump<Context>(
-    store: &mut Store<Context>,
-    err: &anyhow::Error,
-    name: &str,
-    path: &str,
-) -> Result<()> {
-    use std::fs::File;
-    use std::io::Write;
-
-    let core_dump = err
-        .downcast_ref::<wasmtime::WasmCoreDump>()
-        .expect("should have been configured to capture core dumps");
-
-    let core_dump = core_dump.serialize(store, name);
-
-    let mut core_dump_file =
-        File::create(path).context(format!("failed to create file at `{}`", path))?;
-    core_dump_file
-        .write_all(&core_dump)
-        .with_context(|| format!("failed to write core dump file at `{}`", path))?;
-    Ok(())
-}
@@ -0,0 +1,633 @@
+//! A GDB remote serial protocol server for debugging wasm guests with LLDB.
+//!
+//! This implements the subset of the protocol, along with LLDB's wasm
+//! extensions, that LLDB needs to debug wasm: code addresses identify an
+//! offset within a module and memory addresses an offset within the linear
+//! memory of the instance a frame is running in. Both are 64-bit values laid
+//! out as:
+//!
+//! ```text
+//! | 63..62: space (0 = memory, 1 = code) | 61..32: module id | 31..0: offset |
+//! ```
+//!
+//! Only a single thread is reported and interrupting a running guest isn't
+//! supported; set a breakpoint instead.
+
+use anyhow::{bail, Context as _, Result};
+use std::fmt::Write as _;
+use std::io::{BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use wasmtime::{AsContext, DebugResume, DebugStop, Module, StoreContextMut, Val};
+
+const CODE_SPACE: u64 = 1 << 62;
+const SPACE_MASK: u64 = 3 << 62;
+const MODULE_MASK: u64 = (1 << 30) - 1;
+
+/// A wasm module that the debugger knows about.
+struct DebugModule {
+    name: String,
+    module: Module,
+    /// The original wasm binary, which LLDB reads to find debugging
+    /// information. This is empty when it isn't available, for example for
+    /// precompiled modules.
+    bytes: Vec<u8>,
+}
+
+pub struct GdbServer {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    no_ack: bool,
+    modules: Vec<DebugModule>,
+    /// Breakpoints set by the debugger, which are removed when it detaches.
+    breakpoints: Vec<(Module, usize)>,
+    /// Whether the debugger resumed the guest, and so is waiting for a stop
+    /// reply before it sends any more packets.
+    resumed: bool,
+    detached: bool,
+}
+
+/// What to do after handling a packet.
+enum Action {
+    Reply(String),
+    Resume(DebugResume),
+    Kill,
+}
+
+impl GdbServer {
+    /// Waits for a debugger to connect to `addr`.
+    pub fn accept(addr: &str, modules: Vec<(String, Module, Vec<u8>)>) -> Result<GdbServer> {
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("failed to bind to `{addr}`"))?;
+        eprintln!(
+            "waiting for a debugger to connect to {}",
+            listener.local_addr()?
+        );
+        let (stream, peer) = listener.accept()?;
+        eprintln!("debugger connected from {peer}");
+        GdbServer::new(stream, modules)
+    }
+
+    fn new(stream: TcpStream, modules: Vec<(String, Module, Vec<u8>)>) -> Result<GdbServer> {
+        Ok(GdbServer {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            no_ack: false,
+            modules: modules
+                .into_iter()
+                .map(|(name, module, bytes)| DebugModule {
+                    name,
+                    module,
+                    bytes,
+                })
+                .collect(),
+            breakpoints: Vec::new(),
+            resumed: false,
+            detached: false,
+        })
+    }
+
+    /// Hands control to the debugger until it resumes the guest.
+    pub fn stopped<T>(
+        &mut self,
+        mut store: StoreContextMut<T>,
+        stop: &DebugStop,
+    ) -> Result<DebugResume> {
+        if self.detached {
+            return Ok(DebugResume::Continue);
+        }
+        for frame in stop.frames() {
+            self.module_id(frame.module());
+        }
+        if self.resumed {
+            self.send(&stop_reply())?;
+            self.resumed = false;
+        }
+        loop {
+            let packet = match self.recv()? {
+                Some(packet) => packet,
+                // The debugger went away, so let the guest run to completion.
+                None => {
+                    self.detached = true;
+                    return Ok(DebugResume::Continue);
+                }
+            };
+            match self.handle(&mut store, stop, &packet) {
+                Action::Reply(reply) => self.send(&reply)?,
+                Action::Resume(resume) => {
+                    self.resumed = true;
+                    return Ok(resume);
+                }
+                Action::Kill => bail!("guest killed by the debugger"),
+            }
+        }
+    }
+
+    /// Tells a debugger that's waiting for the guest that it exited.
+    pub fn exited(&mut self, result: &Result<()>) {
+        if self.detached || !self.resumed {
+            return;
+        }
+        let reply = match result {
+            Ok(()) => "W00",
+            Err(_) => "W01",
+        };
+        let _ = self.send(reply);
+    }
+
+    fn handle<T>(
+        &mut self,
+        store: &mut StoreContextMut<T>,
+        stop: &DebugStop,
+        packet: &str,
+    ) -> Action {
+        let reply = |s: &str| Action::Reply(s.to_string());
+        let error = || reply("E01");
+
+        if packet.starts_with("qSupported") {
+            return reply("PacketSize=4000;QStartNoAckMode+;qXfer:libraries:read+");
+        }
+        if let Some(rest) = packet.strip_prefix("qXfer:libraries:read::") {
+            return match parse_pair(rest, ',') {
+                Some((offset, len)) => Action::Reply(self.libraries(offset, len)),
+                None => error(),
+            };
+        }
+        if packet.starts_with("qWasmCallStack") {
+            let mut out = String::new();
+            for frame in stop.frames() {
+                let pc = self.code_address(frame.module(), frame.module_offset().unwrap_or(0));
+                push_hex(&mut out, &pc.to_le_bytes());
+            }
+            return Action::Reply(out);
+        }
+        if let Some(rest) = packet.strip_prefix("qWasmLocal:") {
+            let value = parse_indices(rest).and_then(|(frame, index)| {
+                let frame = stop.frames().get(frame)?;
+                frame.locals().get(index)?.clone()
+            });
+            return match value.and_then(|v| val_bytes(&v)) {
+                Some(bytes) => Action::Reply(hex(&bytes)),
+                None => error(),
+            };
+        }
+        if let Some(rest) = packet.strip_prefix("qWasmGlobal:") {
+            let global = parse_indices(rest).and_then(|(frame, index)| {
+                stop.frames().get(frame)?.global(u32::try_from(index).ok()?)
+            });
+            return match global.and_then(|g| val_bytes(&g.get(&mut *store))) {
+                Some(bytes) => Action::Reply(hex(&bytes)),
+                None => error(),
+            };
+        }
+        if let Some(rest) = packet.strip_prefix("qWasmMem:") {
+            let bytes = rest.split_once(';').and_then(|(frame, rest)| {
+                let frame = frame.parse().ok()?;
+                let (addr, len) = parse_pair(rest, ';')?;
+                self.read_memory(store, stop, Some(frame), addr, len)
+            });
+            return match bytes {
+                Some(bytes) => Action::Reply(hex(&bytes)),
+                None => error(),
+            };
+        }
+
+        match packet.as_bytes().first() {
+            Some(b'?') => Action::Reply(stop_reply()),
+            Some(b'g') | Some(b'p') => {
+                if packet.starts_with('p') && packet != "p0" {
+                    return error();
+                }
+                let pc = match stop.frames().first() {
+                    Some(frame) => {
+                        self.code_address(frame.module(), frame.module_offset().unwrap_or(0))
+                    }
+                    None => 0,
+                };
+                Action::Reply(hex(&pc.to_le_bytes()))
+            }
+            Some(b'm') => match parse_pair(&packet[1..], ',') {
+                Some((addr, len)) => {
+                    let bytes = if addr & SPACE_MASK == CODE_SPACE {
+                        self.read_code(addr, len)
+                    } else {
+                        self.read_memory(store, stop, None, addr, len)
+                    };
+                    match bytes {
+                        Some(bytes) => Action::Reply(hex(&bytes)),
+                        None => error(),
+                    }
+                }
+                None => error(),
+            },
+            Some(b'Z') | Some(b'z') if packet[1..].starts_with("0,") => {
+                let addr = packet[3..]
+                    .split(',')
+                    .next()
+                    .and_then(|a| u64::from_str_radix(a, 16).ok());
+                let (module, offset) = match addr.and_then(|a| self.code_location(a)) {
+                    Some(location) => location,
+                    None => return error(),
+                };
+                if packet.starts_with('Z') {
+                    store.add_breakpoint(&module, offset);
+                    self.breakpoints.push((module, offset));
+                } else {
+                    store.remove_breakpoint(&module, offset);
+                    self.breakpoints
+                        .retain(|(m, o)| !(*o == offset && Module::same(m, &module)));
+                }
+                reply("OK")
+            }
+            Some(b'c') => Action::Resume(DebugResume::Continue),
+            Some(b's') => Action::Resume(DebugResume::Step),
+            Some(b'k') => Action::Kill,
+            Some(b'D') => {
+                self.detached = true;
+                for (module, offset) in self.breakpoints.drain(..) {
+                    store.remove_breakpoint(&module, offset);
+                }
+                // Answer before resuming since the debugger doesn't expect a
+                // stop reply after detaching.
+                let _ = self.send("OK");
+                Action::Resume(DebugResume::Continue)
+            }
+            _ => match packet {
+                "QStartNoAckMode" => {
+                    // This packet itself has already been acknowledged, and
+                    // the acknowledgement of the reply is skipped over.
+                    self.no_ack = true;
+                    reply("OK")
+                }
+                "qHostInfo" => Action::Reply(format!(
+                    "triple:{};ptrsize:4;endian:little;",
+                    hex(b"wasm32-unknown-unknown-wasm")
+                )),
+                "qProcessInfo" => Action::Reply(format!(
+                    "pid:1;parent-pid:1;triple:{};ptrsize:4;endian:little;",
+                    hex(b"wasm32-unknown-unknown-wasm")
+                )),
+                "qRegisterInfo0" => reply(
+                    "name:pc;alt-name:pc;bitsize:64;offset:0;encoding:uint;format:hex;\
+                     set:General Purpose Registers;gcc:16;dwarf:16;generic:pc;",
+                ),
+                "qC" => reply("QC1"),
+                "qfThreadInfo" => reply("m1"),
+                "qsThreadInfo" => reply("l"),
+                "vCont?" => reply("vCont;c;s"),
+                "vCont;c" | "vCont;c:1" => Action::Resume(DebugResume::Continue),
+                "vCont;s" | "vCont;s:1" => Action::Resume(DebugResume::Step),
+                p if p.starts_with("qRegisterInfo") => reply("E45"),
+                p if p.starts_with('H') => reply("OK"),
+                _ => reply(""),
+            },
+        }
+    }
+
+    /// Returns the id of `module`, starting to track it if it's new.
+    fn module_id(&mut self, module: &Module) -> u64 {
+        if let Some(i) = self
+            .modules
+            .iter()
+            .position(|m| Module::same(&m.module, module))
+        {
+            return i as u64;
+        }
+        self.modules.push(DebugModule {
+            name: format!("module{}", self.modules.len()),
+            module: module.clone(),
+            bytes: Vec::new(),
+        });
+        self.modules.len() as u64 - 1
+    }
+
+    fn code_address(&mut self, module: &Module, offset: usize) -> u64 {
+        CODE_SPACE | (self.module_id(module) << 32) | offset as u64
+    }
+
+    fn code_location(&self, addr: u64) -> Option<(Module, usize)> {
+        if addr & SPACE_MASK != CODE_SPACE {
+            return None;
+        }
+        let module = self.modules.get(((addr >> 32) & MODULE_MASK) as usize)?;
+        Some((module.module.clone(), (addr & 0xffff_ffff) as usize))
+    }
+
+    fn read_code(&self, addr: u64, len: u64) -> Option<Vec<u8>> {
+        let module = self.modules.get(((addr >> 32) & MODULE_MASK) as usize)?;
+        let start = (addr & 0xffff_ffff) as usize;
+        let bytes = module.bytes.get(start..)?;
+        Some(bytes[..bytes.len().min(len as usize)].to_vec())
+    }
+
+    /// Reads linear memory 0 of the instance running in `frame`, or of the
+    /// innermost frame of the module that `addr` names.
+    fn read_memory<T>(
+        &self,
+        store: &mut StoreContextMut<T>,
+        stop: &DebugStop,
+        frame: Option<usize>,
+        addr: u64,
+        len: u64,
+    ) -> Option<Vec<u8>> {
+        let frame = match frame {
+            Some(i) => stop.frames().get(i)?,
+            None => {
+                let module = &self
+                    .modules
+                    .get(((addr >> 32) & MODULE_MASK) as usize)?
+                    .module;
+                stop.frames()
+                    .iter()
+                    .find(|f| Module::same(f.module(), module))?
+            }
+        };
+        let memory = frame.memory(0)?;
+        let data = memory.data(store.as_context());
+        let start = (addr & 0xffff_ffff) as usize;
+        let bytes = data.get(start..)?;
+        Some(bytes[..bytes.len().min(len as usize)].to_vec())
+    }
+
+    fn libraries(&self, offset: u64, len: u64) -> String {
+        let mut xml = String::from("<library-list>");
+        for (i, module) in self.modules.iter().enumerate() {
+            let _ = write!(
+                xml,
+                "<library name=\"{}\"><section address=\"0x{:x}\"/></library>",
+                module.name,
+                CODE_SPACE | ((i as u64) << 32)
+            );
+        }
+        xml.push_str("</library-list>");
+        let start = (offset as usize).min(xml.len());
+        let end = (start + len as usize).min(xml.len());
+        let more = if end < xml.len() { 'm' } else { 'l' };
+        format!("{more}{}", &xml[start..end])
+    }
+
+    /// Reads the next packet, acknowledging it unless acknowledgements are
+    /// disabled. Returns `None` when the connection is closed.
+    fn recv(&mut self) -> Result<Option<String>> {
+        let mut byte = [0];
+        loop {
+            // Skip acknowledgements and anything else before a packet.
+            loop {
+                if self.reader.read(&mut byte)? == 0 {
+                    return Ok(None);
+                }
+                if byte[0] == b'$' {
+                    break;
+                }
+            }
+            let mut data = Vec::new();
+            loop {
+                if self.reader.read(&mut byte)? == 0 {
+                    return Ok(None);
+                }
+                if byte[0] == b'#' {
+                    break;
+                }
+                data.push(byte[0]);
+            }
+            let mut checksum = [0; 2];
+            self.reader.read_exact(&mut checksum)?;
+            let valid = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+                == Some(data.iter().fold(0u8, |a, b| a.wrapping_add(*b)));
+            if !self.no_ack {
+                self.writer.write_all(if valid { b"+" } else { b"-" })?;
+            }
+            if valid || self.no_ack {
+                return Ok(Some(String::from_utf8_lossy(&unescape(&data)).into_owned()));
+            }
+        }
+    }
+
+    fn send(&mut self, data: &str) -> Result<()> {
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        for b in data.bytes() {
+            // These characters must be escaped within packets.
+            if matches!(b, b'#' | b'$' | b'}' | b'*') {
+                packet.push(b'}');
+                packet.push(b ^ 0x20);
+            } else {
+                packet.push(b);
+            }
+        }
+        let checksum = packet[1..].iter().fold(0u8, |a, b| a.wrapping_add(*b));
+        packet.extend_from_slice(format!("#{checksum:02x}").as_bytes());
+        self.writer.write_all(&packet)?;
+        Ok(())
+    }
+}
+
+/// Undoes the escaping of `}`, `#`, `$` and `*` within a packet, which is
+/// included in its checksum.
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut bytes = data.iter();
+    while let Some(&b) = bytes.next() {
+        match b {
+            b'}' => match bytes.next() {
+                Some(&escaped) => out.push(escaped ^ 0x20),
+                None => out.push(b),
+            },
+            _ => out.push(b),
+        }
+    }
+    out
+}
+
+fn stop_reply() -> String {
+    "T05thread:1;".to_string()
+}
+
+fn val_bytes(val: &Val) -> Option<Vec<u8>> {
+    Some(match val {
+        Val::I32(x) => x.to_le_bytes().to_vec(),
+        Val::I64(x) => x.to_le_bytes().to_vec(),
+        Val::F32(x) => x.to_le_bytes().to_vec(),
+        Val::F64(x) => x.to_le_bytes().to_vec(),
+        Val::V128(x) => x.as_u128().to_le_bytes().to_vec(),
+        _ => return None,
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    push_hex(&mut out, bytes);
+    out
+}
+
+fn push_hex(out: &mut String, bytes: &[u8]) {
+    for b in bytes {
+        let _ = write!(out, "{b:02x}");
+    }
+}
+
+fn parse_pair(s: &str, sep: char) -> Option<(u64, u64)> {
+    let (a, b) = s.split_once(sep)?;
+    Some((
+        u64::from_str_radix(a, 16).ok()?,
+        u64::from_str_radix(b, 16).ok()?,
+    ))
+}
+
+/// Parses the decimal `frame;index` arguments of `qWasmLocal` and
+/// `qWasmGlobal`.
+fn parse_indices(s: &str) -> Option<(usize, usize)> {
+    let (frame, index) = s.split_once(';')?;
+    Some((frame.parse().ok()?, index.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use wasmtime::{Config, DebugStopReason, Engine, Instance, Store};
+
+    fn connect(modules: Vec<(String, Module, Vec<u8>)>) -> (GdbServer, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        (GdbServer::new(stream, modules).unwrap(), client)
+    }
+
+    fn packet(data: &str) -> String {
+        let checksum = data.bytes().fold(0u8, |a, b| a.wrapping_add(b));
+        format!("${data}#{checksum:02x}")
+    }
+
+    fn read(client: &mut TcpStream, len: usize) -> String {
+        let mut buf = vec![0; len];
+        client.read_exact(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn reply(action: Action) -> String {
+        match action {
+            Action::Reply(reply) => reply,
+            Action::Resume(_) => panic!("unexpected resume"),
+            Action::Kill => panic!("unexpected kill"),
+        }
+    }
+
+    #[test]
+    fn recv_acknowledges_packets() {
+        let (mut server, mut client) = connect(Vec::new());
+        client.write_all(b"+").unwrap();
+        client.write_all(packet("qC").as_bytes()).unwrap();
+        assert_eq!(server.recv().unwrap().as_deref(), Some("qC"));
+        assert_eq!(read(&mut client, 1), "+");
+
+        // A packet with a bad checksum is rejected and the debugger sends it
+        // again.
+        client.write_all(b"$qC#00").unwrap();
+        client.write_all(packet("qC").as_bytes()).unwrap();
+        assert_eq!(server.recv().unwrap().as_deref(), Some("qC"));
+        assert_eq!(read(&mut client, 2), "-+");
+
+        drop(client);
+        assert_eq!(server.recv().unwrap(), None);
+    }
+
+    #[test]
+    fn recv_without_acks() {
+        let (mut server, mut client) = connect(Vec::new());
+        server.no_ack = true;
+        client.write_all(b"$qC#00").unwrap();
+        assert_eq!(server.recv().unwrap().as_deref(), Some("qC"));
+        server.send("OK").unwrap();
+        assert_eq!(read(&mut client, 6), packet("OK"));
+    }
+
+    #[test]
+    fn escaping() {
+        let (mut server, mut client) = connect(Vec::new());
+        server.no_ack = true;
+        client.write_all(packet("a}]b}\x03c").as_bytes()).unwrap();
+        assert_eq!(server.recv().unwrap().as_deref(), Some("a}b#c"));
+
+        server.send("a}b#c$d*").unwrap();
+        let expected = packet("a}]b}\x03c}\x04d}\x0a");
+        assert_eq!(read(&mut client, expected.len()), expected);
+    }
+
+    #[test]
+    fn wasm_queries_and_breakpoints() -> Result<()> {
+        let mut config = Config::new();
+        config.debug_breakpoints(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(
+            &engine,
+            r#"
+                (module
+                    (memory 1)
+                    (data (i32.const 16) "hello")
+                    (global i64 (i64.const 42))
+                    (func (export "run") (param i32) (result i32)
+                        (local i32)
+                        i32.const 5
+                        local.set 1
+                        local.get 0
+                        local.get 1
+                        i32.add))
+            "#,
+        )?;
+        let (server, _client) = connect(vec![("m".to_string(), module.clone(), Vec::new())]);
+        let server = Arc::new(Mutex::new(server));
+
+        let mut store = Store::new(&engine, ());
+        let instance = Instance::new(&mut store, &module, &[])?;
+        let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+
+        let stops = Arc::new(Mutex::new(Vec::new()));
+        let callback_stops = stops.clone();
+        store.debug_callback(move |mut store, stop| {
+            let mut server = server.lock().unwrap();
+            let mut stops = callback_stops.lock().unwrap();
+            let offset = stop.frames()[0].module_offset().unwrap() as u64;
+            let pc = CODE_SPACE | offset;
+            stops.push((stop.reason(), offset));
+            let mut query = |packet: &str| reply(server.handle(&mut store, stop, packet));
+
+            if stops.len() == 1 {
+                assert_eq!(query("qWasmCallStack"), hex(&pc.to_le_bytes()));
+                assert_eq!(query("p0"), hex(&pc.to_le_bytes()));
+                assert_eq!(query("qWasmLocal:0;0"), "03000000");
+                assert_eq!(query("qWasmLocal:0;1"), "00000000");
+                assert_eq!(query("qWasmLocal:0;2"), "E01");
+                assert_eq!(query("qWasmLocal:1;0"), "E01");
+                assert_eq!(query("qWasmGlobal:0;0"), "2a00000000000000");
+                assert_eq!(query("qWasmGlobal:0;1"), "E01");
+                assert_eq!(query("qWasmMem:0;10;5"), hex(b"hello"));
+                assert_eq!(query("m10,5"), hex(b"hello"));
+
+                // Stop again after `local.set 1`, which is four bytes on.
+                assert_eq!(query(&format!("Z0,{:x},1", pc + 4)), "OK");
+                assert_eq!(query(&format!("Z0,{:x},1", offset + 4)), "E01");
+            } else {
+                assert_eq!(query("qWasmLocal:0;1"), "05000000");
+                assert_eq!(query(&format!("z0,{:x},1", pc)), "OK");
+                assert!(server.breakpoints.is_empty());
+            }
+            match server.handle(&mut store, stop, "c") {
+                Action::Resume(resume) => Ok(resume),
+                _ => panic!("expected the guest to resume"),
+            }
+        });
+
+        store.single_step(true);
+        assert_eq!(run.call(&mut store, 3)?, 8);
+        // The breakpoint has been removed, so this doesn't stop.
+        assert_eq!(run.call(&mut store, 3)?, 8);
+
+        let stops = stops.lock().unwrap();
+        assert_eq!(stops.len(), 2);
+        assert_eq!(stops[0].0, DebugStopReason::Step);
+        assert_eq!(stops[1], (DebugStopReason::Breakpoint, stops[0].1 + 4));
+        Ok(())
+    }
+}
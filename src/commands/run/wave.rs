@@ -0,0 +1,751 @@
+//! Parsing and printing of component values using the WebAssembly Value
+//! Encoding (WAVE), a human-readable syntax used by `--invoke`.
+//!
+//! Parsing is directed by the expected [`Type`], so for example a label such as
+//! `red` is parsed as an enum case or a variant case depending on the type of
+//! the parameter it's passed to. Some examples of the syntax are:
+//!
+//! * `true`, `-7`, `1.5`, `nan`, `'x'`, `"hello\n"`
+//! * lists `[1, 2, 3]` and tuples `(1, "a")`
+//! * records `{name: "x", size: 4}`, where `option` fields may be omitted
+//! * variants and enums `circle(1.0)`, `none-of-them`
+//! * options `some(1)`, `none`, and results `ok`, `err("oops")`
+//! * flags `{read, write}`
+//!
+//! Labels which collide with keywords are written with a leading `%`, for
+//! example `%true`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::fmt::Write;
+use wasmtime::component::{Type, Val};
+
+const KEYWORDS: &[&str] = &["true", "false", "some", "none", "ok", "err", "inf", "nan"];
+
+/// A parsed `--invoke` function call.
+pub struct Call<'a> {
+    /// The exported instance containing the function, if any.
+    pub instance: Option<&'a str>,
+    /// The name of the function.
+    pub func: &'a str,
+    /// The unparsed text of the arguments, if the call used parentheses.
+    pub args: Option<&'a str>,
+}
+
+/// Splits an `--invoke` string such as `iface#func(1, 2)` into its parts.
+pub fn parse_call(s: &str) -> Result<Call<'_>> {
+    let s = s.trim();
+    let (path, args) = match s.find('(') {
+        Some(i) => {
+            let args = s[i + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| anyhow!("missing closing `)` in call `{s}`"))?;
+            (s[..i].trim_end(), Some(args))
+        }
+        None => (s, None),
+    };
+    let (instance, func) = match path.rsplit_once('#') {
+        Some((instance, func)) => (Some(instance), func),
+        None => (None, path),
+    };
+    if func.is_empty() {
+        bail!("missing function name in call `{s}`");
+    }
+    Ok(Call {
+        instance,
+        func,
+        args,
+    })
+}
+
+/// Parses a single value of type `ty` from `s`.
+pub fn parse_value(s: &str, ty: &Type) -> Result<Val> {
+    let mut parser = Parser { s, pos: 0 };
+    let val = parser.value(ty)?;
+    parser.finish()?;
+    Ok(val)
+}
+
+/// Parses a comma-separated list of values, one for each of `tys`.
+pub fn parse_values(s: &str, tys: &[Type]) -> Result<Vec<Val>> {
+    let mut parser = Parser { s, pos: 0 };
+    let mut vals = Vec::with_capacity(tys.len());
+    for (i, ty) in tys.iter().enumerate() {
+        if i > 0 {
+            parser.expect(',')?;
+        }
+        vals.push(parser.value(ty)?);
+    }
+    parser.eat(',');
+    parser.finish().context("too many arguments")?;
+    Ok(vals)
+}
+
+/// Renders `val` using the WAVE syntax.
+pub fn to_string(val: &Val) -> String {
+    let mut out = String::new();
+    write_value(&mut out, val);
+    out
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("unexpected trailing input")),
+        }
+    }
+
+    fn error(&self, msg: &str) -> anyhow::Error {
+        anyhow!("{msg} at offset {} in `{}`", self.pos, self.s)
+    }
+
+    /// Consumes a run of characters which may make up a number or a label.
+    fn token(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "-+._%".contains(c)))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes a label, stripping a leading `%` used to escape keywords.
+    fn label(&mut self) -> Result<&'a str> {
+        let token = self.token();
+        let label = token.strip_prefix('%').unwrap_or(token);
+        if label.is_empty() {
+            return Err(self.error("expected a label"));
+        }
+        Ok(label)
+    }
+
+    /// Consumes an optional parenthesized payload of type `ty`.
+    fn payload(&mut self, ty: Option<Type>, what: &str) -> Result<Option<Val>> {
+        match ty {
+            Some(ty) => {
+                self.expect('(')
+                    .with_context(|| format!("{what} requires a payload"))?;
+                let val = self.value(&ty)?;
+                self.expect(')')?;
+                Ok(Some(val))
+            }
+            None => {
+                if self.peek() == Some('(') {
+                    return Err(self.error(&format!("{what} does not have a payload")));
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn int<T: std::str::FromStr>(&mut self) -> Result<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let token = self.token();
+        token
+            .parse()
+            .map_err(|e| self.error(&format!("invalid integer `{token}`: {e}")))
+    }
+
+    /// Parses a float, including `nan`, `inf` and `-inf`, which Rust's
+    /// float parsing already accepts.
+    fn float<T: std::str::FromStr>(&mut self) -> Result<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let token = self.token();
+        token
+            .parse()
+            .map_err(|e| self.error(&format!("invalid number `{token}`: {e}")))
+    }
+
+    /// Parses the body of a quoted char or string, up to the `quote`.
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        self.expect(quote)?;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        loop {
+            let (i, c) = chars
+                .next()
+                .ok_or_else(|| self.error(&format!("missing closing `{quote}`")))?;
+            match c {
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let (_, escape) = chars
+                        .next()
+                        .ok_or_else(|| self.error("unterminated escape"))?;
+                    out.push(match escape {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '\\' | '\'' | '"' => escape,
+                        'u' => {
+                            let rest = chars.as_str();
+                            let end = rest
+                                .strip_prefix('{')
+                                .and_then(|r| r.find('}'))
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            let code = u32::from_str_radix(&rest[1..end + 1], 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            for _ in 0..end + 2 {
+                                chars.next();
+                            }
+                            code
+                        }
+                        _ => return Err(self.error(&format!("unknown escape `\\{escape}`"))),
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// Parses comma-separated items until `close`, allowing a trailing comma.
+    fn seq(&mut self, close: char, mut item: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        loop {
+            if self.eat(close) {
+                return Ok(());
+            }
+            item(self)?;
+            if !self.eat(',') {
+                return self.expect(close);
+            }
+        }
+    }
+
+    fn value(&mut self, ty: &Type) -> Result<Val> {
+        Ok(match ty {
+            Type::Bool => match self.token() {
+                "true" => Val::Bool(true),
+                "false" => Val::Bool(false),
+                _ => return Err(self.error("expected `true` or `false`")),
+            },
+            Type::S8 => Val::S8(self.int()?),
+            Type::U8 => Val::U8(self.int()?),
+            Type::S16 => Val::S16(self.int()?),
+            Type::U16 => Val::U16(self.int()?),
+            Type::S32 => Val::S32(self.int()?),
+            Type::U32 => Val::U32(self.int()?),
+            Type::S64 => Val::S64(self.int()?),
+            Type::U64 => Val::U64(self.int()?),
+            Type::Float32 => Val::Float32(self.float()?),
+            Type::Float64 => Val::Float64(self.float()?),
+            Type::Char => {
+                let s = self.quoted('\'')?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Val::Char(c),
+                    _ => return Err(self.error("expected a single character")),
+                }
+            }
+            Type::String => Val::String(self.quoted('"')?.into()),
+            Type::List(list) => {
+                let elem = list.ty();
+                let mut vals = Vec::new();
+                self.expect('[')?;
+                self.seq(']', |p| {
+                    vals.push(p.value(&elem)?);
+                    Ok(())
+                })?;
+                list.new_val(vals.into())?
+            }
+            Type::Tuple(tuple) => {
+                self.expect('(')?;
+                let mut vals = Vec::new();
+                for (i, ty) in tuple.types().enumerate() {
+                    if i > 0 {
+                        self.expect(',')?;
+                    }
+                    vals.push(self.value(&ty)?);
+                }
+                self.eat(',');
+                self.expect(')')?;
+                tuple.new_val(vals.into())?
+            }
+            Type::Record(record) => {
+                let fields = record.fields().collect::<Vec<_>>();
+                let mut vals: Vec<Option<Val>> = vec![None; fields.len()];
+                self.expect('{')?;
+                if !self.eat(':') {
+                    self.seq('}', |p| {
+                        let name = p.label()?;
+                        let i = fields
+                            .iter()
+                            .position(|f| f.name == name)
+                            .ok_or_else(|| p.error(&format!("unknown field `{name}`")))?;
+                        p.expect(':')?;
+                        vals[i] = Some(p.value(&fields[i].ty)?);
+                        Ok(())
+                    })?;
+                } else {
+                    self.expect('}')?;
+                }
+                let mut values = Vec::with_capacity(fields.len());
+                for (field, val) in fields.iter().zip(vals) {
+                    let val = match (val, &field.ty) {
+                        (Some(val), _) => val,
+                        (None, Type::Option(option)) => option.new_val(None)?,
+                        (None, _) => bail!("missing field `{}`", field.name),
+                    };
+                    values.push((field.name, val));
+                }
+                record.new_val(values)?
+            }
+            Type::Variant(variant) => {
+                let name = self.label()?;
+                let case = variant
+                    .cases()
+                    .find(|c| c.name == name)
+                    .ok_or_else(|| self.error(&format!("unknown variant case `{name}`")))?;
+                let payload = self.payload(case.ty, &format!("case `{name}`"))?;
+                variant.new_val(name, payload)?
+            }
+            Type::Enum(e) => {
+                let name = self.label()?;
+                e.new_val(name)?
+            }
+            Type::Option(option) => {
+                let save = self.pos;
+                match self.token() {
+                    "none" => option.new_val(None)?,
+                    "some" => {
+                        let payload = self.payload(Some(option.ty()), "`some`")?;
+                        option.new_val(payload)?
+                    }
+                    // Values other than `some(..)` and `none` are shorthand
+                    // for `some(..)`, unless that would be ambiguous.
+                    _ if !matches!(option.ty(), Type::Option(_) | Type::Result(_)) => {
+                        self.pos = save;
+                        option.new_val(Some(self.value(&option.ty())?))?
+                    }
+                    _ => return Err(self.error("expected `some` or `none`")),
+                }
+            }
+            Type::Result(result) => match self.token() {
+                "ok" => result.new_val(Ok(self.payload(result.ok(), "`ok`")?))?,
+                "err" => result.new_val(Err(self.payload(result.err(), "`err`")?))?,
+                _ => return Err(self.error("expected `ok` or `err`")),
+            },
+            Type::Flags(flags) => {
+                let mut names = Vec::new();
+                self.expect('{')?;
+                self.seq('}', |p| {
+                    names.push(p.label()?);
+                    Ok(())
+                })?;
+                flags.new_val(&names)?
+            }
+            Type::Own(_) | Type::Borrow(_) => {
+                bail!("resources cannot be written as values")
+            }
+        })
+    }
+}
+
+fn write_label(out: &mut String, label: &str) {
+    if KEYWORDS.contains(&label) {
+        out.push('%');
+    }
+    out.push_str(label);
+}
+
+fn write_quoted(out: &mut String, s: &str, quote: char) {
+    out.push(quote);
+    for c in s.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\\' | '\'' | '"' if c == '\\' || c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+}
+
+/// Writes `f` using its own shortest round-trippable representation, so that
+/// for example `float32` values aren't printed with the noise of widening
+/// them to `f64`.
+fn write_float<F: Into<f64> + std::fmt::Display + Copy>(out: &mut String, f: F) {
+    let wide: f64 = f.into();
+    if wide.is_nan() {
+        out.push_str("nan");
+    } else if wide.is_infinite() {
+        out.push_str(if wide > 0.0 { "inf" } else { "-inf" });
+    } else {
+        let _ = write!(out, "{f}");
+    }
+}
+
+fn write_seq<'a>(out: &mut String, open: char, close: char, vals: impl Iterator<Item = &'a Val>) {
+    out.push(open);
+    for (i, val) in vals.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_value(out, val);
+    }
+    out.push(close);
+}
+
+fn write_payload(out: &mut String, val: Option<&Val>) {
+    if let Some(val) = val {
+        out.push('(');
+        write_value(out, val);
+        out.push(')');
+    }
+}
+
+fn write_value(out: &mut String, val: &Val) {
+    match val {
+        Val::Bool(b) => {
+            let _ = write!(out, "{b}");
+        }
+        Val::S8(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::U8(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::S16(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::U16(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::S32(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::U32(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::S64(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::U64(v) => {
+            let _ = write!(out, "{v}");
+        }
+        Val::Float32(f) => write_float(out, *f),
+        Val::Float64(f) => write_float(out, *f),
+        Val::Char(c) => write_quoted(out, c.encode_utf8(&mut [0; 4]), '\''),
+        Val::String(s) => write_quoted(out, s, '"'),
+        Val::List(list) => write_seq(out, '[', ']', list.iter()),
+        Val::Tuple(tuple) => write_seq(out, '(', ')', tuple.values().iter()),
+        Val::Record(record) => {
+            let mut fields = record.fields().peekable();
+            if fields.peek().is_none() {
+                out.push_str("{:}");
+                return;
+            }
+            out.push('{');
+            for (i, (name, val)) in fields.enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_label(out, name);
+                out.push_str(": ");
+                write_value(out, val);
+            }
+            out.push('}');
+        }
+        Val::Variant(variant) => {
+            write_label(out, variant.discriminant());
+            write_payload(out, variant.payload());
+        }
+        Val::Enum(e) => write_label(out, e.discriminant()),
+        Val::Option(option) => match option.value() {
+            Some(val) => {
+                out.push_str("some");
+                write_payload(out, Some(val));
+            }
+            None => out.push_str("none"),
+        },
+        Val::Result(result) => {
+            let (name, val) = match result.value() {
+                Ok(val) => ("ok", val),
+                Err(val) => ("err", val),
+            };
+            out.push_str(name);
+            write_payload(out, val);
+        }
+        Val::Flags(flags) => {
+            out.push('{');
+            for (i, name) in flags.flags().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_label(out, name);
+            }
+            out.push('}');
+        }
+        Val::Resource(_) => out.push_str("<resource>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasmtime::component::{Component, Linker};
+    use wasmtime::{Config, Engine, Store};
+
+    /// Returns the type `ty`, defined in a component along with the type
+    /// definitions in `defs`.
+    fn ty(defs: &str, ty: &str) -> Type {
+        // Wrapping the type in a list means that the exported function always
+        // flattens to two core parameters.
+        let wat = format!(
+            r#"
+                (component
+                    {defs}
+                    (core module $m
+                        (memory (export "memory") 1)
+                        (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                            unreachable)
+                        (func (export "f") (param i32 i32))
+                    )
+                    (core instance $i (instantiate $m))
+                    (func (export "f") (param "x" (list {ty}))
+                        (canon lift (core func $i "f") (memory $i "memory")
+                            (realloc (func $i "realloc"))))
+                )
+            "#
+        );
+        let mut config = Config::new();
+        config.wasm_component_model(true);
+        let engine = Engine::new(&config).unwrap();
+        let component = Component::new(&engine, wat).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::new(&engine)
+            .instantiate(&mut store, &component)
+            .unwrap();
+        let func = instance.get_func(&mut store, "f").unwrap();
+        let list = func.params(&store)[0].clone();
+        list.unwrap_list().ty()
+    }
+
+    /// Asserts that `s` parses as a value of `ty` and is printed back as
+    /// `expected`, which in turn parses to the same value.
+    fn round_trip(ty: &Type, s: &str, expected: &str) -> Val {
+        let val = parse_value(s, ty).unwrap();
+        assert_eq!(to_string(&val), expected);
+        let reparsed = parse_value(expected, ty).unwrap();
+        assert_eq!(to_string(&reparsed), expected);
+        val
+    }
+
+    #[test]
+    fn strings_and_chars() {
+        let string = ty("", "string");
+        round_trip(&string, r#""hello""#, r#""hello""#);
+        round_trip(&string, r#""a\nb\tc\\d""#, r#""a\nb\tc\\d""#);
+        round_trip(&string, r#""quote \" 'single'""#, r#""quote \" 'single'""#);
+        let val = round_trip(&string, r#""\u{1F980}\u{7}""#, "\"🦀\\u{7}\"");
+        assert_eq!(val, Val::String("🦀\u{7}".into()));
+        assert!(parse_value(r#""\q""#, &string).is_err());
+        assert!(parse_value(r#""\u{110000}""#, &string).is_err());
+        assert!(parse_value(r#""unterminated"#, &string).is_err());
+
+        let char = ty("", "char");
+        assert_eq!(round_trip(&char, "'x'", "'x'"), Val::Char('x'));
+        round_trip(&char, r"'\''", r"'\''");
+        round_trip(&char, r#"'"'"#, r#"'"'"#);
+        round_trip(&char, r"'\u{1F980}'", "'🦀'");
+        assert!(parse_value("'ab'", &char).is_err());
+        assert!(parse_value("''", &char).is_err());
+    }
+
+    #[test]
+    fn floats() {
+        let f32 = ty("", "float32");
+        assert_eq!(round_trip(&f32, "1.1", "1.1"), Val::Float32(1.1));
+        round_trip(&f32, "-0.5", "-0.5");
+        round_trip(&f32, "inf", "inf");
+        round_trip(&f32, "-inf", "-inf");
+        let Val::Float32(nan) = round_trip(&f32, "nan", "nan") else {
+            unreachable!()
+        };
+        assert!(nan.is_nan());
+
+        let f64 = ty("", "float64");
+        assert_eq!(round_trip(&f64, "0.1", "0.1"), Val::Float64(0.1));
+        round_trip(&f64, "-inf", "-inf");
+        round_trip(&f64, "nan", "nan");
+        assert!(parse_value("one", &f64).is_err());
+    }
+
+    #[test]
+    fn records() {
+        let defs = r#"
+            (type $r' (record (field "a" u32) (field "b" (option string))))
+            (export $r "r" (type $r'))
+            (type $opts' (record (field "x" (option u8)) (field "y" (option u8))))
+            (export $opts "opts" (type $opts'))
+        "#;
+        let r = ty(defs, "$r");
+        round_trip(&r, r#"{a: 1, b: "x"}"#, r#"{a: 1, b: some("x")}"#);
+        round_trip(&r, r#"{ b: some("x"), a: 1, }"#, r#"{a: 1, b: some("x")}"#);
+        // Omitted `option` fields are `none`.
+        round_trip(&r, "{a: 2}", "{a: 2, b: none}");
+        assert!(parse_value(r#"{b: "x"}"#, &r).is_err());
+        assert!(parse_value("{a: 1, c: 2}", &r).is_err());
+
+        let opts = ty(defs, "$opts");
+        round_trip(&opts, "{:}", "{x: none, y: none}");
+        round_trip(&opts, "{}", "{x: none, y: none}");
+        round_trip(&opts, "{y: 3}", "{x: none, y: some(3)}");
+    }
+
+    #[test]
+    fn keywords_are_escaped() {
+        let defs = r#"
+            (type $r' (record (field "true" bool) (field "none" u8)))
+            (export $r "r" (type $r'))
+            (type $e' (enum "inf" "nan" "plain"))
+            (export $e "e" (type $e'))
+            (type $f' (flags "ok" "err" "read"))
+            (export $f "fl" (type $f'))
+        "#;
+        let r = ty(defs, "$r");
+        round_trip(&r, "{%true: true, %none: 1}", "{%true: true, %none: 1}");
+
+        let e = ty(defs, "$e");
+        round_trip(&e, "%inf", "%inf");
+        round_trip(&e, "%nan", "%nan");
+        round_trip(&e, "plain", "plain");
+        round_trip(&e, "%plain", "plain");
+
+        let f = ty(defs, "$f");
+        round_trip(&f, "{%err, read, %ok}", "{%ok, %err, read}");
+        round_trip(&f, "{}", "{}");
+        assert!(parse_value("{write}", &f).is_err());
+    }
+
+    #[test]
+    fn variants() {
+        let defs = r#"
+            (type $v' (variant (case "none-of-them") (case "circle" float64) (case "some" u32)))
+            (export $v "v" (type $v'))
+        "#;
+        let v = ty(defs, "$v");
+        round_trip(&v, "none-of-them", "none-of-them");
+        round_trip(&v, "circle(1.5)", "circle(1.5)");
+        round_trip(&v, "%some(4)", "%some(4)");
+        assert!(parse_value("circle", &v).is_err());
+        assert!(parse_value("none-of-them(1)", &v).is_err());
+        assert!(parse_value("square(1)", &v).is_err());
+    }
+
+    #[test]
+    fn results() {
+        let result = ty("", "(result u32 (error string))");
+        round_trip(&result, "ok(1)", "ok(1)");
+        round_trip(&result, r#"err("oops")"#, r#"err("oops")"#);
+        assert!(parse_value("ok", &result).is_err());
+
+        let empty = ty("", "(result)");
+        round_trip(&empty, "ok", "ok");
+        round_trip(&empty, "err", "err");
+        assert!(parse_value("ok(1)", &empty).is_err());
+    }
+
+    #[test]
+    fn options() {
+        let option = ty("", "(option u32)");
+        round_trip(&option, "none", "none");
+        round_trip(&option, "some(1)", "some(1)");
+        // A bare value is shorthand for `some`.
+        round_trip(&option, "1", "some(1)");
+
+        let nested = ty("", "(option (option u32))");
+        round_trip(&nested, "none", "none");
+        round_trip(&nested, "some(none)", "some(none)");
+        round_trip(&nested, "some(some(2))", "some(some(2))");
+        round_trip(&nested, "some(2)", "some(some(2))");
+        // The shorthand would be ambiguous for nested options.
+        assert!(parse_value("2", &nested).is_err());
+
+        let result = ty("", "(option (result))");
+        round_trip(&result, "some(ok)", "some(ok)");
+        assert!(parse_value("ok", &result).is_err());
+    }
+
+    #[test]
+    fn lists_and_tuples() {
+        let list = ty("", "(list (tuple u8 string))");
+        round_trip(&list, "[]", "[]");
+        round_trip(&list, r#"[(1, "a"), (2, "b"),]"#, r#"[(1, "a"), (2, "b")]"#);
+        assert!(parse_value("[(1)]", &list).is_err());
+        assert!(parse_value("[(256, \"a\")]", &list).is_err());
+    }
+
+    #[test]
+    fn calls() {
+        let call = parse_call("wasi:cli/run@0.2.0#run").unwrap();
+        assert_eq!(call.instance, Some("wasi:cli/run@0.2.0"));
+        assert_eq!(call.func, "run");
+        assert_eq!(call.args, None);
+
+        let call = parse_call(" add(1, 2) ").unwrap();
+        assert_eq!(call.instance, None);
+        assert_eq!(call.func, "add");
+        assert_eq!(call.args, Some("1, 2"));
+
+        assert!(parse_call("add(1, 2").is_err());
+        assert!(parse_call("iface#(1)").is_err());
+
+        let u32 = ty("", "u32");
+        let string = ty("", "string");
+        let vals = parse_values(r#"1, "a,b","#, &[u32.clone(), string]).unwrap();
+        assert_eq!(vals, [Val::U32(1), Val::String("a,b".into())]);
+        assert!(parse_values("1, 2", &[u32]).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::common::{Profile, RunCommon, RunTarget};
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, Error, Result};
 use clap::Parser;
 use std::{
     path::PathBuf,
@@ -21,38 +21,95 @@ use wasmtime_wasi_http::{
 #[cfg(feature = "wasi-nn")]
 use wasmtime_wasi_nn::WasiNnCtx;
 
+#[cfg(feature = "wasi-threads")]
+use wasmtime_wasi_threads::component::{OnError, SharedWasi, WasiThreadsCtx};
+
+/// The default of `-S max-threads`, so that a single request can't exhaust
+/// the threads of the host.
+#[cfg(feature = "wasi-threads")]
+const DEFAULT_MAX_THREADS_PER_REQUEST: usize = 16;
+
 struct Host {
-    table: wasmtime::component::ResourceTable,
-    ctx: WasiCtx,
+    wasi: HostWasi,
     http: WasiHttpCtx,
 
     limits: StoreLimits,
 
     #[cfg(feature = "wasi-nn")]
     nn: Option<WasiNnCtx>,
+
+    #[cfg(feature = "wasi-threads")]
+    wasi_threads: Option<WasiThreadsCtx<Host>>,
+
+    #[cfg(feature = "profiling")]
+    guest_profiler: Option<RequestProfiler>,
+}
+
+/// The preview2 state of a request.
+enum HostWasi {
+    Own(WasiCtx, wasmtime::component::ResourceTable),
+    /// State shared by the request's store and the stores of its threads.
+    #[cfg(feature = "wasi-threads")]
+    Shared(SharedWasi),
+}
+
+/// Where the samples of a request are recorded with `--profile=guest`.
+#[cfg(feature = "profiling")]
+enum RequestProfiler {
+    /// A profile of just this request, written when the request finishes.
+    Own(Box<wasmtime::GuestProfiler>),
+    /// A thread of the profile shared by all requests.
+    Shared(SharedProfile, wasmtime::GuestProfilerThread),
+}
+
+/// The profile that every request is recorded in with `--profile=guest`,
+/// unless `--profile-per-request` is passed.
+///
+/// This profile is written on shutdown, at which point it's taken from any
+/// requests that are still running.
+#[derive(Clone, Default)]
+struct SharedProfile {
+    #[cfg(feature = "profiling")]
+    profiler: Arc<std::sync::Mutex<Option<wasmtime::GuestProfiler>>>,
 }
 
 impl WasiView for Host {
     fn table(&self) -> &wasmtime::component::ResourceTable {
-        &self.table
+        match &self.wasi {
+            HostWasi::Own(_, table) => table,
+            #[cfg(feature = "wasi-threads")]
+            HostWasi::Shared(wasi) => wasi.table(),
+        }
     }
 
     fn table_mut(&mut self) -> &mut wasmtime::component::ResourceTable {
-        &mut self.table
+        match &mut self.wasi {
+            HostWasi::Own(_, table) => table,
+            #[cfg(feature = "wasi-threads")]
+            HostWasi::Shared(wasi) => wasi.table_mut(),
+        }
     }
 
     fn ctx(&self) -> &WasiCtx {
-        &self.ctx
+        match &self.wasi {
+            HostWasi::Own(ctx, _) => ctx,
+            #[cfg(feature = "wasi-threads")]
+            HostWasi::Shared(wasi) => wasi.ctx(),
+        }
     }
 
     fn ctx_mut(&mut self) -> &mut WasiCtx {
-        &mut self.ctx
+        match &mut self.wasi {
+            HostWasi::Own(ctx, _) => ctx,
+            #[cfg(feature = "wasi-threads")]
+            HostWasi::Shared(wasi) => wasi.ctx_mut(),
+        }
     }
 }
 
 impl WasiHttpView for Host {
     fn table(&mut self) -> &mut wasmtime::component::ResourceTable {
-        &mut self.table
+        WasiView::table_mut(self)
     }
 
     fn ctx(&mut self) -> &mut WasiHttpCtx {
@@ -71,6 +128,15 @@ pub struct ServeCommand {
     #[command(flatten)]
     run: RunCommon,
 
+    /// Write a separate guest profile for each request when using
+    /// `--profile=guest`.
+    ///
+    /// Each request's profile is written to the profile's path with the id
+    /// of the request appended. Otherwise every request is recorded as a
+    /// thread of a single profile, which is written on shutdown.
+    #[arg(long = "profile-per-request")]
+    profile_per_request: bool,
+
     /// Socket address for the web server to bind to.
     #[arg(long = "addr", value_name = "SOCKADDR", default_value_t = DEFAULT_ADDR )]
     addr: std::net::SocketAddr,
@@ -94,8 +160,9 @@ impl ServeCommand {
             }
         }
 
+        #[cfg(not(feature = "profiling"))]
         if let Some(Profile::Guest { .. }) = &self.run.profile {
-            bail!("Cannot use the guest profiler with components");
+            bail!("support for profiling disabled at compile time");
         }
 
         if self.run.common.wasi.nn == Some(true) {
@@ -106,7 +173,18 @@ impl ServeCommand {
         }
 
         if self.run.common.wasi.threads == Some(true) {
-            bail!("wasi-threads does not support components yet")
+            #[cfg(not(feature = "wasi-threads"))]
+            {
+                bail!(
+                    "Cannot enable wasi-threads when the binary is not compiled with this feature."
+                );
+            }
+        }
+
+        if let Some(path) = &self.run.common.debug.coredump {
+            if path.contains("%") {
+                bail!("the coredump-on-trap path does not support patterns yet.")
+            }
         }
 
         // The serve command requires both wasi-http and the component model, so we enable those by
@@ -123,22 +201,39 @@ impl ServeCommand {
             .enable_io()
             .build()?;
 
-        runtime.block_on(async move {
-            tokio::select! {
-                _ = tokio::signal::ctrl_c() => {
-                    Ok::<_, anyhow::Error>(())
+        let shared_profile = SharedProfile::default();
+        #[cfg(feature = "profiling")]
+        let profile = self.run.profile.clone();
+
+        let result = runtime.block_on({
+            let shared_profile = shared_profile.clone();
+            async move {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {
+                        Ok::<_, anyhow::Error>(())
+                    }
+
+                    res = self.serve(shared_profile) => {
+                        res
+                    }
                 }
+            }
+        });
 
-                res = self.serve() => {
-                    res
-                }
+        #[cfg(feature = "profiling")]
+        if let Some(Profile::Guest { path, .. }) = &profile {
+            if let Some(profiler) = shared_profile.profiler.lock().unwrap().take() {
+                let format = crate::common::guest_profile_format(path);
+                crate::common::write_guest_profile(profiler, format, path);
             }
-        })?;
+        }
 
-        Ok(())
+        result
     }
 
-    fn new_store(&self, engine: &Engine, req_id: u64) -> Result<Store<Host>> {
+    /// Creates the preview2 state of a request, which its threads share if
+    /// wasi-threads is enabled.
+    fn new_wasi(&self, req_id: u64) -> HostWasi {
         let mut builder = WasiCtxBuilder::new();
 
         builder.envs(&[("REQUEST_ID", req_id.to_string())]);
@@ -153,15 +248,31 @@ impl ServeCommand {
             output: Output::Stderr,
         });
 
+        let mut table = wasmtime::component::ResourceTable::new();
+        table.apply_limits(&self.run.store_limits());
+
+        #[cfg(feature = "wasi-threads")]
+        if self.run.common.wasi.threads == Some(true) {
+            return HostWasi::Shared(SharedWasi::new(builder.build(), table));
+        }
+        HostWasi::Own(builder.build(), table)
+    }
+
+    fn new_store(&self, engine: &Engine, wasi: HostWasi) -> Result<Store<Host>> {
         let mut host = Host {
-            table: wasmtime::component::ResourceTable::new(),
-            ctx: builder.build(),
+            wasi,
             http: WasiHttpCtx,
 
             limits: StoreLimits::default(),
 
             #[cfg(feature = "wasi-nn")]
             nn: None,
+
+            #[cfg(feature = "wasi-threads")]
+            wasi_threads: None,
+
+            #[cfg(feature = "profiling")]
+            guest_profiler: None,
         };
 
         if self.run.common.wasi.nn == Some(true) {
@@ -189,6 +300,15 @@ impl ServeCommand {
         store.data_mut().limits = self.run.store_limits();
         store.limiter(|t| &mut t.limits);
 
+        // Stores sharing preview2 state take turns using it in host calls.
+        #[cfg(feature = "wasi-threads")]
+        if let HostWasi::Shared(_) = store.data().wasi {
+            store.call_hook(|host, hook| match &mut host.wasi {
+                HostWasi::Shared(wasi) => wasi.call_hook(hook),
+                HostWasi::Own(..) => Ok(()),
+            });
+        }
+
         // If fuel has been configured, we want to add the configured
         // fuel amount to this store.
         if let Some(fuel) = self.run.common.wasm.fuel {
@@ -198,6 +318,35 @@ impl ServeCommand {
         Ok(store)
     }
 
+    #[cfg(feature = "coredump")]
+    fn handle_core_dump(&self, store: &mut Store<Host>, req_id: u64, err: Error) -> Error {
+        let coredump_path = match &self.run.common.debug.coredump {
+            Some(path) => path,
+            None => return err,
+        };
+        if !err.is::<wasmtime::Trap>() {
+            return err;
+        }
+        // Requests are handled concurrently, so each request that traps gets
+        // a core dump of its own.
+        let coredump_path = format!("{coredump_path}.{req_id}");
+        let source_name = self.component.to_str().unwrap_or_else(|| "unknown");
+
+        if let Err(coredump_err) =
+            crate::common::write_core_dump(store, &err, source_name, &coredump_path)
+        {
+            eprintln!("warning: coredump failed to generate: {}", coredump_err);
+            err
+        } else {
+            err.context(format!("core dumped at {}", coredump_path))
+        }
+    }
+
+    #[cfg(not(feature = "coredump"))]
+    fn handle_core_dump(&self, _store: &mut Store<Host>, _req_id: u64, err: Error) -> Error {
+        err
+    }
+
     fn add_to_linker(&self, linker: &mut Linker<Host>) -> Result<()> {
         // Repurpose the `-Scommon` flag of `wasmtime run` for `wasmtime serve`
         // to serve as a signal to enable all WASI interfaces instead of just
@@ -227,7 +376,16 @@ impl ServeCommand {
         }
 
         if self.run.common.wasi.threads == Some(true) {
-            bail!("support for wasi-threads is not available with components");
+            #[cfg(not(feature = "wasi-threads"))]
+            {
+                bail!("support for wasi-threads was disabled at compile time");
+            }
+            #[cfg(feature = "wasi-threads")]
+            {
+                wasmtime_wasi_threads::component::add_to_linker(linker, |host| {
+                    host.wasi_threads.as_ref().unwrap()
+                })?;
+            }
         }
 
         if self.run.common.wasi.http == Some(false) {
@@ -237,7 +395,7 @@ impl ServeCommand {
         Ok(())
     }
 
-    async fn serve(mut self) -> Result<()> {
+    async fn serve(mut self, shared_profile: SharedProfile) -> Result<()> {
         use hyper::server::conn::http1;
 
         let mut config = self.run.common.config(None)?;
@@ -253,8 +411,14 @@ impl ServeCommand {
                 config.profiler(s);
             }
 
-            // We bail early in `execute` if the guest profiler is configured.
-            Some(Profile::Guest { .. }) => unreachable!(),
+            Some(Profile::Guest { .. }) => {
+                // Further configured down below as well.
+                config.epoch_interruption(true);
+            }
+
+            Some(Profile::Counters { .. }) => {
+                bail!("profiling with performance counters is only supported by `wasmtime run`");
+            }
 
             None => {}
         }
@@ -269,21 +433,38 @@ impl ServeCommand {
             RunTarget::Component(c) => c,
         };
 
+        #[cfg(feature = "profiling")]
+        if let Some(Profile::Guest { interval, .. }) = &self.run.profile {
+            if !self.profile_per_request {
+                *shared_profile.profiler.lock().unwrap() =
+                    Some(wasmtime::GuestProfiler::new_component(
+                        self.component.to_str().unwrap_or("<component>"),
+                        *interval,
+                        component.clone(),
+                        Vec::new(),
+                    ));
+            }
+        }
+
         let instance = linker.instantiate_pre(&component)?;
 
         let listener = tokio::net::TcpListener::bind(self.addr).await?;
 
         eprintln!("Serving HTTP on http://{}/", listener.local_addr()?);
 
-        let _epoch_thread = if let Some(timeout) = self.run.common.wasm.timeout {
-            Some(EpochThread::spawn(timeout, engine.clone()))
-        } else {
-            None
+        // The guest profiler samples on every epoch tick, counting down the
+        // timeout itself.
+        let _epoch_thread = match (&self.run.profile, self.run.common.wasm.timeout) {
+            (Some(Profile::Guest { interval, .. }), _) => {
+                Some(EpochThread::spawn(*interval, engine.clone()))
+            }
+            (_, Some(timeout)) => Some(EpochThread::spawn(timeout, engine.clone())),
+            _ => None,
         };
 
         log::info!("Listening on {}", self.addr);
 
-        let handler = ProxyHandler::new(self, engine, instance);
+        let handler = ProxyHandler::new(self, engine, instance, shared_profile);
 
         loop {
             let (stream, _) = listener.accept().await?;
@@ -308,13 +489,13 @@ struct EpochThread {
 }
 
 impl EpochThread {
-    fn spawn(timeout: std::time::Duration, engine: Engine) -> Self {
+    fn spawn(interval: std::time::Duration, engine: Engine) -> Self {
         let shutdown = Arc::new(AtomicBool::new(false));
         let handle = {
             let shutdown = Arc::clone(&shutdown);
             let handle = std::thread::spawn(move || {
                 while !shutdown.load(Ordering::Relaxed) {
-                    std::thread::sleep(timeout);
+                    std::thread::sleep(interval);
                     engine.increment_epoch();
                 }
             });
@@ -339,30 +520,169 @@ struct ProxyHandlerInner {
     engine: Engine,
     instance_pre: InstancePre<Host>,
     next_id: AtomicU64,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    shared_profile: SharedProfile,
 }
 
 impl ProxyHandlerInner {
     fn next_req_id(&self) -> u64 {
         self.next_id.fetch_add(1, Ordering::Relaxed)
     }
+
+    #[cfg(feature = "profiling")]
+    fn setup_guest_profiler(&self, store: &mut Store<Host>, req_id: u64) {
+        use wasmtime::{AsContextMut, GuestProfiler, UpdateDeadline};
+
+        let Some(Profile::Guest { interval, .. }) = &self.cmd.run.profile else {
+            return;
+        };
+        let interval = *interval;
+
+        let profiler = if self.cmd.profile_per_request {
+            RequestProfiler::Own(Box::new(GuestProfiler::new_component(
+                self.cmd.component.to_str().unwrap_or("<component>"),
+                interval,
+                self.instance_pre.component().clone(),
+                Vec::new(),
+            )))
+        } else {
+            let thread = match self.shared_profile.profiler.lock().unwrap().as_mut() {
+                Some(profiler) => profiler.add_thread(&format!("request {req_id}")),
+                // The profile was already written on shutdown.
+                None => return,
+            };
+            RequestProfiler::Shared(self.shared_profile.clone(), thread)
+        };
+        store.data_mut().guest_profiler = Some(profiler);
+
+        fn sample(mut store: impl AsContextMut<Data = Host>) {
+            let mut profiler = store.as_context_mut().data_mut().guest_profiler.take();
+            match &mut profiler {
+                Some(RequestProfiler::Own(profiler)) => profiler.sample(&store),
+                Some(RequestProfiler::Shared(shared, thread)) => {
+                    if let Some(profiler) = shared.profiler.lock().unwrap().as_mut() {
+                        profiler.sample_thread(*thread, &store);
+                    }
+                }
+                None => {}
+            }
+            store.as_context_mut().data_mut().guest_profiler = profiler;
+        }
+
+        // The epoch ticks once per sampling interval, so the timeout is
+        // counted in ticks and rounded up to a multiple of the interval.
+        let mut ticks = self
+            .cmd
+            .run
+            .common
+            .wasm
+            .timeout
+            .map(|timeout| (timeout.as_secs_f64() / interval.as_secs_f64()).ceil() as u64);
+        store.epoch_deadline_callback(move |mut store| {
+            sample(&mut store);
+            if let Some(ticks) = &mut ticks {
+                *ticks = ticks.saturating_sub(1);
+                if *ticks == 0 {
+                    bail!("timeout exceeded");
+                }
+            }
+            Ok(UpdateDeadline::Continue(1))
+        });
+        store.set_epoch_deadline(1);
+    }
+
+    /// Creates the wasi-threads context of a request, whose threads each run
+    /// in a store set up like the request's own store and sharing its
+    /// preview2 state.
+    ///
+    /// A thread which traps or panics fails just its request, rather than
+    /// exiting the server.
+    #[cfg(feature = "wasi-threads")]
+    fn setup_wasi_threads(self: &Arc<Self>, store: &mut Store<Host>) {
+        if self.cmd.run.common.wasi.threads != Some(true) {
+            return;
+        }
+        let HostWasi::Shared(wasi) = &store.data().wasi else {
+            unreachable!("the preview2 state of requests is shared with threads")
+        };
+        let wasi = wasi.share();
+        let inner = self.clone();
+        let ctx = WasiThreadsCtx::new_async(self.instance_pre.clone(), move |ctx, engine| {
+            let mut store = inner
+                .cmd
+                .new_store(engine, HostWasi::Shared(wasi.share()))?;
+            store.data_mut().wasi_threads = Some(ctx.clone());
+            Ok(store)
+        })
+        .on_error(OnError::FailInstance)
+        .max_threads(
+            self.cmd
+                .run
+                .common
+                .wasi
+                .max_threads
+                .unwrap_or(DEFAULT_MAX_THREADS_PER_REQUEST),
+        );
+        store.data_mut().wasi_threads = Some(ctx);
+    }
+
+    #[cfg(feature = "profiling")]
+    fn finish_guest_profile(&self, store: &mut Store<Host>, req_id: u64) {
+        match store.data_mut().guest_profiler.take() {
+            Some(RequestProfiler::Own(profiler)) => {
+                let Some(Profile::Guest { path, .. }) = &self.cmd.run.profile else {
+                    unreachable!()
+                };
+                let format = crate::common::guest_profile_format(path);
+                crate::common::write_guest_profile(*profiler, format, &format!("{path}.{req_id}"));
+            }
+            Some(RequestProfiler::Shared(shared, thread)) => {
+                if let Some(profiler) = shared.profiler.lock().unwrap().as_mut() {
+                    profiler.finish_thread(thread);
+                }
+            }
+            None => {}
+        }
+    }
 }
 
 #[derive(Clone)]
 struct ProxyHandler(Arc<ProxyHandlerInner>);
 
 impl ProxyHandler {
-    fn new(cmd: ServeCommand, engine: Engine, instance_pre: InstancePre<Host>) -> Self {
+    fn new(
+        cmd: ServeCommand,
+        engine: Engine,
+        instance_pre: InstancePre<Host>,
+        shared_profile: SharedProfile,
+    ) -> Self {
         Self(Arc::new(ProxyHandlerInner {
             cmd,
             engine,
             instance_pre,
             next_id: AtomicU64::from(0),
+            shared_profile,
         }))
     }
 }
 
 type Request = hyper::Request<hyper::body::Incoming>;
 
+/// Gives the wasi-threads context of a request, if any, the shared memories
+/// of the request's instance.
+fn set_threads_instance(
+    store: &mut Store<Host>,
+    instance: &wasmtime::component::Instance,
+) -> Result<()> {
+    #[cfg(feature = "wasi-threads")]
+    if let Some(ctx) = store.data().wasi_threads.clone() {
+        ctx.set_instance(&mut *store, instance)?;
+    }
+    #[cfg(not(feature = "wasi-threads"))]
+    let _ = (store, instance);
+    Ok(())
+}
+
 impl hyper::service::Service<Request> for ProxyHandler {
     type Response = hyper::Response<HyperOutgoingBody>;
     type Error = anyhow::Error;
@@ -416,20 +736,45 @@ impl hyper::service::Service<Request> for ProxyHandler {
                 req.uri()
             );
 
-            let mut store = inner.cmd.new_store(&inner.engine, req_id)?;
+            let mut store = inner
+                .cmd
+                .new_store(&inner.engine, inner.cmd.new_wasi(req_id))?;
+            #[cfg(feature = "profiling")]
+            inner.setup_guest_profiler(&mut store, req_id);
+            #[cfg(feature = "wasi-threads")]
+            inner.setup_wasi_threads(&mut store);
 
             let req = store.data_mut().new_incoming_request(req)?;
             let out = store.data_mut().new_response_outparam(sender)?;
 
-            let (proxy, _inst) =
-                wasmtime_wasi_http::proxy::Proxy::instantiate_pre(&mut store, &inner.instance_pre)
-                    .await?;
-
-            if let Err(e) = proxy
-                .wasi_http_incoming_handler()
-                .call_handle(store, req, out)
-                .await
+            let result = match wasmtime_wasi_http::proxy::Proxy::instantiate_pre(
+                &mut store,
+                &inner.instance_pre,
+            )
+            .await
             {
+                Ok((proxy, instance)) => match set_threads_instance(&mut store, &instance) {
+                    Ok(()) => {
+                        proxy
+                            .wasi_http_incoming_handler()
+                            .call_handle(&mut store, req, out)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+            #[cfg(feature = "wasi-threads")]
+            let result = result.and_then(|()| match &store.data().wasi_threads {
+                Some(ctx) => ctx.take_error().map_or(Ok(()), Err),
+                None => Ok(()),
+            });
+
+            #[cfg(feature = "profiling")]
+            inner.finish_guest_profile(&mut store, req_id);
+
+            if let Err(e) = result {
+                let e = inner.cmd.handle_core_dump(&mut store, req_id, e);
                 log::error!("[{req_id}] :: {:#?}", e);
                 return Err(e);
             }
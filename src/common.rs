@@ -49,7 +49,8 @@ pub struct RunCommon {
     #[arg(long = "allow-precompiled")]
     pub allow_precompiled: bool,
 
-    /// Profiling strategy (valid options are: perfmap, jitdump, vtune, guest)
+    /// Profiling strategy (valid options are: perfmap, jitdump, vtune, guest,
+    /// counters)
     ///
     /// The perfmap, jitdump, and vtune profiling strategies integrate Wasmtime
     /// with external profilers such as `perf`. The guest profiling strategy
@@ -63,7 +64,22 @@ pub struct RunCommon {
     ///
     /// where `path` is where to write the profile and `interval` is the
     /// duration between samples. When used with `--wasm-timeout` the timeout
-    /// will be rounded up to the nearest multiple of this interval.
+    /// will be rounded up to the nearest multiple of this interval. The
+    /// profile is written in the pprof format if `path` ends in `.pb`, and in
+    /// the collapsed stacks format of flamegraph tools if it ends in
+    /// `.folded` or `.collapsed`.
+    ///
+    /// The counters profiling strategy, only available on Linux, samples
+    /// hardware performance counters in-process with `perf_event_open` and
+    /// will write the captured profile to `wasmtime-counters-profile.json` by
+    /// default. It can be additionally configured as:
+    ///
+    ///     --profile=counters[,path[,events[,period]]]
+    ///
+    /// where `events` is a `+`-separated list of `cycles`, `instructions`,
+    /// `cache-misses` and `branch-misses`, defaulting to `cycles`, and
+    /// `period` is the number of events between samples. Profiles are
+    /// written in the pprof format if `path` ends in `.pb`.
     #[arg(
         long,
         value_name = "STRATEGY",
@@ -90,6 +106,9 @@ impl RunCommon {
         if let Some(max) = self.common.wasm.max_memories {
             limits = limits.memories(max);
         }
+        if let Some(max) = self.common.wasm.max_resource_table_entries {
+            limits = limits.resource_table_entries(max);
+        }
         if let Some(enable) = self.common.wasm.trap_on_grow_failure {
             limits = limits.trap_on_grow_failure(enable);
         }
@@ -219,7 +238,15 @@ impl RunCommon {
 #[derive(Clone, PartialEq)]
 pub enum Profile {
     Native(wasmtime::ProfilingStrategy),
-    Guest { path: String, interval: Duration },
+    Guest {
+        path: String,
+        interval: Duration,
+    },
+    Counters {
+        path: String,
+        events: Vec<String>,
+        period: u64,
+    },
 }
 
 impl Profile {
@@ -242,7 +269,141 @@ impl Profile {
                 path: path.to_string(),
                 interval: WasmtimeOptionValue::parse(Some(dur))?,
             }),
+            ["counters", rest @ ..] if rest.len() <= 3 => Ok(Profile::Counters {
+                path: rest
+                    .first()
+                    .unwrap_or(&"wasmtime-counters-profile.json")
+                    .to_string(),
+                events: rest
+                    .get(1)
+                    .unwrap_or(&"cycles")
+                    .split('+')
+                    .map(|event| event.to_string())
+                    .collect(),
+                period: match rest.get(2) {
+                    Some(period) => period.parse()?,
+                    None => 1_000_000,
+                },
+            }),
             _ => bail!("unknown profiling strategy: {s}"),
         }
     }
 }
+
+/// Returns the format to write a guest profile in, based on the extension of
+/// the `path` given with `--profile`.
+#[cfg(feature = "profiling")]
+pub fn guest_profile_format(path: &str) -> wasmtime::GuestProfileFormat {
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("pb") => wasmtime::GuestProfileFormat::Pprof,
+        Some("folded" | "collapsed") => wasmtime::GuestProfileFormat::Collapsed,
+        _ => wasmtime::GuestProfileFormat::Firefox,
+    }
+}
+
+/// Finishes `profiler` and writes the profile to `path` in `format`,
+/// reporting where it was written or why it couldn't be.
+#[cfg(feature = "profiling")]
+pub fn write_guest_profile(
+    profiler: wasmtime::GuestProfiler,
+    format: wasmtime::GuestProfileFormat,
+    path: &str,
+) {
+    if let Err(e) = std::fs::File::create(path)
+        .map_err(anyhow::Error::new)
+        .and_then(|output| profiler.finish_with_format(format, std::io::BufWriter::new(output)))
+    {
+        eprintln!("failed writing profile at {path}: {e:#}");
+    } else {
+        eprintln!();
+        eprintln!("Profile written to: {path}");
+        if format == wasmtime::GuestProfileFormat::Firefox {
+            eprintln!("View this profile at https://profiler.firefox.com/.");
+        }
+    }
+}
+
+/// Writes the core dump attached to `err` to `path`.
+#[cfg(feature = "coredump")]
+pub fn write_core_dump<Context>(
+    store: &mut wasmtime::Store<Context>,
+    err: &anyhow::Error,
+    name: &str,
+    path: &str,
+) -> Result<()> {
+    use std::fs::File;
+    use std::io::Write;
+
+    let core_dump = err
+        .downcast_ref::<wasmtime::WasmCoreDump>()
+        .expect("should have been configured to capture core dumps");
+
+    let core_dump = core_dump.serialize(store, name);
+
+    let mut core_dump_file =
+        File::create(path).context(format!("failed to create file at `{}`", path))?;
+    core_dump_file
+        .write_all(&core_dump)
+        .with_context(|| format!("failed to write core dump file at `{}`", path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_counters_profile() {
+        let counters = |path: &str, events: &[&str], period| Profile::Counters {
+            path: path.to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            period,
+        };
+        assert!(
+            Profile::parse("counters").unwrap()
+                == counters("wasmtime-counters-profile.json", &["cycles"], 1_000_000)
+        );
+        assert!(
+            Profile::parse("counters,out.pb").unwrap()
+                == counters("out.pb", &["cycles"], 1_000_000)
+        );
+        assert!(
+            Profile::parse("counters,out.json,instructions+cache-misses").unwrap()
+                == counters("out.json", &["instructions", "cache-misses"], 1_000_000)
+        );
+        assert!(
+            Profile::parse("counters,out.json,branch-misses,5000").unwrap()
+                == counters("out.json", &["branch-misses"], 5000)
+        );
+        assert!(Profile::parse("counters,out.json,cycles,lots").is_err());
+        assert!(Profile::parse("counters,out.json,cycles,1,extra").is_err());
+    }
+
+    #[test]
+    fn parse_other_profiles() {
+        assert!(
+            Profile::parse("perfmap").unwrap()
+                == Profile::Native(wasmtime::ProfilingStrategy::PerfMap)
+        );
+        assert!(
+            Profile::parse("guest,out.json,5ms").unwrap()
+                == Profile::Guest {
+                    path: "out.json".to_string(),
+                    interval: Duration::from_millis(5),
+                }
+        );
+        assert!(Profile::parse("unknown").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "profiling")]
+    fn guest_profile_format_from_extension() {
+        use wasmtime::GuestProfileFormat::*;
+        assert_eq!(guest_profile_format("out.pb"), Pprof);
+        assert_eq!(guest_profile_format("dir/out.folded"), Collapsed);
+        assert_eq!(guest_profile_format("out.collapsed"), Collapsed);
+        assert_eq!(guest_profile_format("out.json"), Firefox);
+        assert_eq!(guest_profile_format("out"), Firefox);
+        assert_eq!(guest_profile_format("pb"), Firefox);
+    }
+}
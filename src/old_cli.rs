@@ -235,7 +235,7 @@ fn parse_module(s: OsString) -> anyhow::Result<PathBuf> {
     // Do not accept wasmtime subcommand names as the module name
     match s.to_str() {
         Some("help") | Some("run") | Some("compile") | Some("serve") | Some("explore")
-        | Some("settings") | Some("wast") | Some("config") => {
+        | Some("settings") | Some("wast") | Some("config") | Some("coredump") | Some("inspect") => {
             bail!("module name cannot be the same as a subcommand")
         }
         _ => Ok(s.into()),
@@ -707,6 +707,7 @@ impl CompileCommand {
             output,
             emit_clif,
             module,
+            target_cpu_baseline: None,
         }
     }
 }
@@ -828,6 +829,8 @@ impl RunCommand {
             preloads,
             module_and_args,
             wali: false,
+            coverage: None,
+            gdb_server: None,
         }
     }
 }
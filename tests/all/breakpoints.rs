@@ -0,0 +1,146 @@
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use wasmtime::*;
+
+fn engine() -> Engine {
+    let mut config = Config::new();
+    config.debug_breakpoints(true);
+    Engine::new(&config).unwrap()
+}
+
+const WAT: &str = r#"
+    (module
+        (global $g (mut i32) (i32.const 7))
+        (func $double (param i32) (result i32)
+            local.get 0
+            local.get 0
+            i32.add)
+        (func (export "run") (param i32) (result i32)
+            (local i32)
+            local.get 0
+            call $double
+            local.set 1
+            local.get 1
+            global.get $g
+            i32.add))
+"#;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn single_step() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, WAT)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+
+    let stops = Arc::new(Mutex::new(Vec::new()));
+    let stops2 = stops.clone();
+    store.debug_callback(move |_store, stop| {
+        assert_eq!(stop.reason(), DebugStopReason::Step);
+        let frames = stop
+            .frames()
+            .iter()
+            .map(|f| (f.func_index(), f.module_offset().unwrap()))
+            .collect::<Vec<_>>();
+        stops2.lock().unwrap().push(frames);
+        Ok(DebugResume::Step)
+    });
+
+    // Without stepping or breakpoints wasm doesn't stop.
+    assert_eq!(run.call(&mut store, 3)?, 13);
+    assert!(stops.lock().unwrap().is_empty());
+
+    store.single_step(true);
+    assert_eq!(run.call(&mut store, 3)?, 13);
+    let stops = std::mem::take(&mut *stops.lock().unwrap());
+
+    // Each instruction of `run` and `$double` stops, including their `end`s,
+    // with `$double` stopping on top of `run`.
+    let funcs = stops.iter().map(|f| f[0].0).collect::<Vec<_>>();
+    assert_eq!(funcs, [1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+    for frames in &stops {
+        assert_eq!(frames.len(), if frames[0].0 == 0 { 2 } else { 1 });
+    }
+    let offsets = stops.iter().map(|f| f[0].1).collect::<Vec<_>>();
+    assert!(offsets.windows(2).all(|w| w[0] != w[1]));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn breakpoints_and_state() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, WAT)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+
+    // Find the offset of `global.get` in `run` by stepping through it.
+    let offsets = Arc::new(Mutex::new(Vec::new()));
+    let offsets2 = offsets.clone();
+    store.debug_callback(move |_store, stop| {
+        let frame = &stop.frames()[0];
+        if frame.func_index() == 1 {
+            offsets2
+                .lock()
+                .unwrap()
+                .push(frame.module_offset().unwrap());
+        }
+        Ok(DebugResume::Step)
+    });
+    store.single_step(true);
+    run.call(&mut store, 3)?;
+    store.single_step(false);
+    let global_get = offsets.lock().unwrap()[4];
+
+    let hits = Arc::new(Mutex::new(0));
+    let hits2 = hits.clone();
+    store.debug_callback(move |mut store, stop| {
+        assert_eq!(stop.reason(), DebugStopReason::Breakpoint);
+        let frame = &stop.frames()[0];
+        assert_eq!(frame.module_offset(), Some(global_get));
+        assert_eq!(frame.locals().len(), 2);
+        let x = frame.locals()[0].as_ref().unwrap().unwrap_i32();
+        assert_eq!(frame.locals()[1].as_ref().unwrap().unwrap_i32(), x * 2);
+        assert_eq!(frame.global(0).unwrap().get(&mut store).unwrap_i32(), 7);
+        assert!(frame.memory(0).is_none());
+        *hits2.lock().unwrap() += 1;
+        Ok(DebugResume::Continue)
+    });
+
+    store.add_breakpoint(&module, global_get);
+    assert_eq!(run.call(&mut store, 3)?, 13);
+    assert_eq!(run.call(&mut store, 4)?, 15);
+    assert_eq!(*hits.lock().unwrap(), 2);
+
+    store.remove_breakpoint(&module, global_get);
+    assert_eq!(run.call(&mut store, 5)?, 17);
+    assert_eq!(*hits.lock().unwrap(), 2);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn callback_errors_trap() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, WAT)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+
+    store.debug_callback(|_store, _stop| anyhow::bail!("stopped by the debugger"));
+    store.single_step(true);
+    let err = run.call(&mut store, 3).unwrap_err();
+    assert!(format!("{err:?}").contains("stopped by the debugger"));
+    Ok(())
+}
+
+#[test]
+fn debug_breakpoints_rejects_winch() {
+    let mut config = Config::new();
+    config.debug_breakpoints(true);
+    config.strategy(Strategy::Winch);
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("breakpoints"), "{err}");
+}
@@ -304,6 +304,61 @@ async fn call_linked_func_async() -> Result<(), Error> {
     Ok(())
 }
 
+// Define a component host func, call it through a component:
+#[cfg(feature = "component-model")]
+#[test]
+fn call_component_host_func() -> Result<(), Error> {
+    use wasmtime::component::{Component, Linker};
+
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::new(&engine, State::default());
+    store.call_hook(State::call_hook);
+    let mut linker = Linker::new(&engine);
+
+    linker
+        .root()
+        .func_wrap("f", |store: StoreContextMut<State>, (a,): (u32,)| {
+            assert_eq!(store.data().context, vec![Context::Wasm, Context::Host]);
+            assert_eq!(a, 1);
+            Ok((String::from("hello"),))
+        })?;
+
+    let wat = r#"
+        (component
+            (import "f" (func $f (param "a" u32) (result string)))
+            (core module $libc
+                (memory (export "memory") 1)
+                (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                    i32.const 64))
+            (core instance $libc (instantiate $libc))
+            (core func $f (canon lower (func $f)
+                (memory $libc "memory") (realloc (func $libc "realloc"))))
+            (core module $m
+                (import "" "f" (func $f (param i32 i32)))
+                (func (export "export") (call $f (i32.const 1) (i32.const 8))))
+            (core instance $m (instantiate $m
+                (with "" (instance (export "f" (func $f))))))
+            (func (export "export") (canon lift (core func $m "export")))
+        )
+    "#;
+    let component = Component::new(&engine, wat)?;
+
+    let inst = linker.instantiate(&mut store, &component)?;
+    let export = inst.get_typed_func::<(), ()>(&mut store, "export")?;
+    export.call(&mut store, ())?;
+
+    // Lowering the result of f calls `realloc`, which is a call into wasm
+    // from within the call to the host.
+    assert_eq!(store.data().calls_into_host, 1);
+    assert_eq!(store.data().returns_from_host, 1);
+    assert_eq!(store.data().calls_into_wasm, 2);
+    assert_eq!(store.data().returns_from_wasm, 2);
+
+    Ok(())
+}
+
 #[test]
 fn instantiate() -> Result<(), Error> {
     let mut store = Store::<State>::default();
@@ -126,6 +126,255 @@ fn run_coredump_smoketest() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn coredump_command() -> Result<()> {
+    let wasm = build_wasm("tests/all/cli_tests/coredump_memory.wat")?;
+    let coredump_file = NamedTempFile::new()?;
+    let coredump_arg = format!("-Dcoredump={}", coredump_file.path().display());
+    run_wasmtime(&[
+        "run",
+        "--invoke",
+        "a",
+        "-Ccache=n",
+        &coredump_arg,
+        wasm.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+
+    let memory_file = NamedTempFile::new()?;
+    let stdout = run_wasmtime(&[
+        "coredump",
+        "-Ccache=n",
+        "--module",
+        wasm.path().to_str().unwrap(),
+        "--dump-memory",
+        "0:0x10..0x20",
+        "--export-memory",
+        memory_file.path().to_str().unwrap(),
+        coredump_file.path().to_str().unwrap(),
+    ])?;
+    assert!(
+        stdout.contains("0: <anonymous-module-0>!func[1] +0x"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("1: <anonymous-module-0>!func[0] +0x"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("global[0]: mut i32 = 42"), "{stdout}");
+    assert!(stdout.contains("memory[0]: 1 pages"), "{stdout}");
+    assert!(stdout.contains("hello coredump"), "{stdout}");
+
+    let memory = std::fs::read(memory_file.path())?;
+    assert_eq!(memory.len(), 0x10000);
+    assert_eq!(&memory[16..30], b"hello coredump");
+    Ok(())
+}
+
+#[test]
+fn coredump_command_large_memory() -> Result<()> {
+    // Only the data segments of a memory are kept, so a 4GiB memory can be
+    // dumped without allocating all of it.
+    let coredump = build_wasm("tests/all/cli_tests/coredump_sparse_memory.wat")?;
+    let stdout = run_wasmtime(&[
+        "coredump",
+        "--dump-memory",
+        "0:0xffffffe0..0x100000000",
+        coredump.path().to_str().unwrap(),
+    ])?;
+    assert!(stdout.contains("memory[0]: 65536 pages"), "{stdout}");
+    assert!(
+        stdout.contains("ffffffe0: 0000 0000 0000 0000 0000 0000 0000 0000"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("fffffff0: 7370 6172 7365"), "{stdout}");
+
+    // A memory whose size overflows is rejected.
+    let mut coredump = NamedTempFile::new()?;
+    coredump.write_all(&wat::parse_str(
+        r#"(module (@custom "core" "\00\00") (memory i64 0x1000000000000))"#,
+    )?)?;
+    let output = run_wasmtime_for_output(&["coredump", coredump.path().to_str().unwrap()], None)?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("memory is too large"), "{stderr}");
+    Ok(())
+}
+
+#[test]
+fn coredump_command_symbolizes_frames() -> Result<()> {
+    let wasm = build_wasm("tests/all/cli_tests/coredump_dwarf.wat")?;
+    let coredump_file = NamedTempFile::new()?;
+    let coredump_arg = format!("-Dcoredump={}", coredump_file.path().display());
+    run_wasmtime(&[
+        "run",
+        "--invoke",
+        "a",
+        "-Ccache=n",
+        &coredump_arg,
+        wasm.path().to_str().unwrap(),
+    ])
+    .unwrap_err();
+
+    let stdout = run_wasmtime(&[
+        "coredump",
+        "-Ccache=n",
+        "--module",
+        wasm.path().to_str().unwrap(),
+        coredump_file.path().to_str().unwrap(),
+    ])?;
+    assert!(stdout.contains("at do_trap (coredump.c:5"), "{stdout}");
+    assert!(stdout.contains("at do_call (coredump.c:2"), "{stdout}");
+    assert!(
+        stdout.find("do_trap").unwrap() < stdout.find("do_call").unwrap(),
+        "{stdout}"
+    );
+    Ok(())
+}
+
+#[test]
+fn inspect_command() -> Result<()> {
+    let stdout = run_wasmtime(&["inspect", "-Ccache=n", "tests/all/cli_tests/inspect.wat"])?;
+    for expected in [
+        "core module",
+        "env::log: func(i32, i32)",
+        "env::table: table funcref 1..",
+        "memory: memory 1..2 pages",
+        "counter: global mut i32",
+        "run: func(i32) -> i32",
+        "0: 1..2 pages",
+        "0: funcref 1.. (imported from env::table)",
+        "note: 5 bytes",
+    ] {
+        assert!(stdout.contains(expected), "{expected:?} not in:\n{stdout}");
+    }
+
+    let stdout = run_wasmtime(&[
+        "inspect",
+        "-Ccache=n",
+        "tests/all/cli_tests/component-invoke.wat",
+    ])?;
+    for expected in [
+        "component",
+        "add: func(a: u32, b: u32) -> u32",
+        "math: instance {",
+        "is-negative: func(x: s32) -> bool",
+    ] {
+        assert!(stdout.contains(expected), "{expected:?} not in:\n{stdout}");
+    }
+
+    let td = TempDir::new()?;
+    let cwasm = td.path().join("component.cwasm");
+    run_wasmtime(&[
+        "compile",
+        "-Wcomponent-model",
+        "tests/all/cli_tests/component-invoke.wat",
+        "-o",
+        cwasm.to_str().unwrap(),
+    ])?;
+    let stdout = run_wasmtime(&["inspect", "--allow-precompiled", cwasm.to_str().unwrap()])?;
+    let version = format!("version: {}", env!("CARGO_PKG_VERSION"));
+    for expected in [
+        "precompiled component",
+        version.as_str(),
+        "component_model = true",
+        "compatibility with this host:\n  compatible\n",
+        "add: func(a: u32, b: u32) -> u32",
+    ] {
+        assert!(stdout.contains(expected), "{expected:?} not in:\n{stdout}");
+    }
+    Ok(())
+}
+
+/// Drives `wasmtime run --gdb-server` the way a debugger would: stop at the
+/// first instruction, set a breakpoint, continue to it and read a local.
+#[test]
+fn run_gdb_server() -> Result<()> {
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+    use std::time::{Duration, Instant};
+
+    fn request(stream: &mut TcpStream, data: &str) -> Result<String> {
+        let checksum = data.bytes().fold(0u8, |a, b| a.wrapping_add(b));
+        write!(stream, "${data}#{checksum:02x}")?;
+        // Skip acknowledgements up to the reply, then read it and its
+        // checksum.
+        let mut byte = [0];
+        while byte[0] != b'$' {
+            stream.read_exact(&mut byte)?;
+        }
+        let mut reply = Vec::new();
+        loop {
+            stream.read_exact(&mut byte)?;
+            if byte[0] == b'#' {
+                break;
+            }
+            reply.push(byte[0]);
+        }
+        stream.read_exact(&mut [0; 2])?;
+        Ok(String::from_utf8(reply)?)
+    }
+
+    let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?.to_string();
+    let mut child = get_wasmtime_command()?
+        .args([
+            "run",
+            "-Ccache=n",
+            "--gdb-server",
+            &addr,
+            "tests/all/cli_tests/gdb_server.wat",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let start = Instant::now();
+    let mut stream = loop {
+        match TcpStream::connect(&addr) {
+            Ok(stream) => break stream,
+            Err(_) if start.elapsed() < Duration::from_secs(60) => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(e) => {
+                child.kill()?;
+                return Err(e.into());
+            }
+        }
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(60)))?;
+
+    assert_eq!(request(&mut stream, "QStartNoAckMode")?, "OK");
+    assert_eq!(request(&mut stream, "?")?, "T05thread:1;");
+    let libraries = request(&mut stream, "qXfer:libraries:read::0,1000")?;
+    assert!(libraries.contains("gdb_server.wat"), "{libraries}");
+
+    // The guest stopped at `i32.const 5`, and `local.set 0` is four bytes on.
+    let pc = |stack: String| -> Result<u64> {
+        let bytes = (0..8)
+            .map(|i| u8::from_str_radix(&stack[i * 2..i * 2 + 2], 16))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+    let start_pc = pc(request(&mut stream, "qWasmCallStack")?)?;
+    assert_eq!(start_pc >> 62, 1, "not a code address: {start_pc:x}");
+    assert_eq!(request(&mut stream, "qWasmLocal:0;0")?, "00000000");
+    let breakpoint = start_pc + 4;
+    assert_eq!(request(&mut stream, &format!("Z0,{breakpoint:x},1"))?, "OK");
+
+    assert_eq!(request(&mut stream, "c")?, "T05thread:1;");
+    assert_eq!(pc(request(&mut stream, "qWasmCallStack")?)?, breakpoint);
+    assert_eq!(request(&mut stream, "qWasmLocal:0;0")?, "05000000");
+
+    assert_eq!(request(&mut stream, "c")?, "W00");
+    let output = child.wait_with_output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
 // Running simple wat
 #[test]
 fn run_wasmtime_simple_wat() -> Result<()> {
@@ -609,6 +858,32 @@ fn run_simple_with_wasi_threads() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "wasi-threads")]
+#[test]
+fn run_component_threads() -> Result<()> {
+    let path = "tests/all/cli_tests/component-threads.wat";
+    let flags = ["-Wcomponent-model,threads", "-Sthreads", "-Ccache=n"];
+
+    let args = [&flags[..], &["--invoke", "run(42)", path]].concat();
+    assert_eq!(run_wasmtime(&args)?, "42\n");
+
+    // Beyond the maximum number of threads spawning fails.
+    let args = [
+        &flags[..],
+        &["-Smax-threads=0", "--invoke", "run(42)", path],
+    ]
+    .concat();
+    assert_eq!(run_wasmtime(&args)?, "-1\n");
+
+    // A trap in a thread exits the process.
+    let args = [&flags[..], &["--invoke", "run(0)", path]].concat();
+    let output = run_wasmtime_for_output(&args, None)?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unreachable"), "{stderr}");
+    Ok(())
+}
+
 #[test]
 fn wasm_flags() -> Result<()> {
     // Any argument after the wasm module should be interpreted as for the
@@ -818,6 +1093,84 @@ fn run_basic_component() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg_attr(not(feature = "component-model"), ignore)]
+fn run_component_invoke() -> Result<()> {
+    let path = "tests/all/cli_tests/component-invoke.wat";
+
+    let stdout = run_wasmtime(&["-Wcomponent-model", "--invoke", "add(1, 2)", path])?;
+    assert_eq!(stdout, "3\n");
+
+    // Arguments may also be passed after the component, one per value.
+    let stdout = run_wasmtime(&["-Wcomponent-model", "--invoke", "add", path, "40", "2"])?;
+    assert_eq!(stdout, "42\n");
+
+    let stdout = run_wasmtime(&[
+        "-Wcomponent-model",
+        "--invoke",
+        "math#is-negative(-3)",
+        path,
+    ])?;
+    assert_eq!(stdout, "true\n");
+
+    let output = run_wasmtime_for_output(
+        &["-Wcomponent-model", "--invoke", "add(1, true)", path],
+        None,
+    )?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("failed to parse arguments"),
+        "bad stderr: {stderr}"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(not(feature = "component-model"), ignore)]
+fn run_component_unknown_imports() -> Result<()> {
+    let path = "tests/all/cli_tests/component-unknown-imports.wat";
+
+    // Without any flags the unknown import fails instantiation.
+    let output = run_wasmtime_for_output(&["-Wcomponent-model", "--invoke", "answer", path], None)?;
+    assert!(!output.status.success());
+
+    let stdout = run_wasmtime(&[
+        "-Wcomponent-model,unknown-imports-trap",
+        "--invoke",
+        "answer",
+        path,
+    ])?;
+    assert_eq!(stdout, "42\n");
+
+    let output = run_wasmtime_for_output(
+        &[
+            "-Wcomponent-model,unknown-imports-trap",
+            "--invoke",
+            "call-unknown",
+            path,
+        ],
+        None,
+    )?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown import `host:api/unknown#get` has not been defined"),
+        "bad stderr: {stderr}"
+    );
+
+    let stdout = run_wasmtime(&[
+        "-Wcomponent-model,unknown-imports-default",
+        "--invoke",
+        "call-unknown",
+        path,
+    ])?;
+    assert_eq!(stdout, "0\n");
+
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(not(feature = "component-model"), ignore)]
 fn run_precompiled_component() -> Result<()> {
@@ -1546,4 +1899,120 @@ mod test_programs {
         assert!(output.status.success());
         Ok(())
     }
+
+    /// Starts `wasmtime serve` with `args` on a free local port and waits
+    /// until it accepts connections, returning the server and its address.
+    fn start_serve(args: &[&str]) -> Result<(std::process::Child, std::net::SocketAddr)> {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let mut child = get_wasmtime_command()?
+            .arg("serve")
+            .arg(format!("--addr={addr}"))
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let start = std::time::Instant::now();
+        while std::net::TcpStream::connect(addr).is_err() {
+            if let Some(status) = child.try_wait()? {
+                let mut stderr = String::new();
+                child.stderr.take().unwrap().read_to_string(&mut stderr)?;
+                anyhow::bail!("server exited with {status}:\n{stderr}");
+            }
+            if start.elapsed() > std::time::Duration::from_secs(60) {
+                child.kill()?;
+                anyhow::bail!("server didn't start listening on {addr}");
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        Ok((child, addr))
+    }
+
+    /// Sends a request to the server at `addr` and returns its response.
+    fn send_request(addr: std::net::SocketAddr) -> Result<String> {
+        let mut stream = std::net::TcpStream::connect(addr)?;
+        write!(
+            stream,
+            "GET / HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    /// Waits for the file at `path` to be written.
+    fn wait_for_file(path: &std::path::Path) -> Result<Vec<u8>> {
+        let start = std::time::Instant::now();
+        loop {
+            if let Ok(contents) = std::fs::read(path) {
+                if !contents.is_empty() {
+                    return Ok(contents);
+                }
+            }
+            if start.elapsed() > std::time::Duration::from_secs(60) {
+                anyhow::bail!("{} was never written", path.display());
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn serve_guest_profile_per_request() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("profile.json");
+        let (mut server, addr) = start_serve(&[
+            &format!("--profile=guest,{}", path.display()),
+            "--profile-per-request",
+            API_PROXY_COMPONENT,
+        ])?;
+        let result = (|| -> Result<()> {
+            for _ in 0..2 {
+                let response = send_request(addr)?;
+                assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            }
+            // Each request gets a profile of its own, named after its id.
+            for id in 0..2 {
+                let profile = wait_for_file(&dir.path().join(format!("profile.json.{id}")))?;
+                let profile: serde_json::Value = serde_json::from_slice(&profile)?;
+                assert_eq!(profile["threads"].as_array().unwrap().len(), 1);
+            }
+            Ok(())
+        })();
+        server.kill()?;
+        server.wait()?;
+        result
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn serve_guest_profile_shared() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("profile.json");
+        let (mut server, addr) = start_serve(&[
+            &format!("--profile=guest,{}", path.display()),
+            API_PROXY_COMPONENT,
+        ])?;
+        let result = (|| -> Result<()> {
+            for _ in 0..2 {
+                let response = send_request(addr)?;
+                assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            }
+            Ok(())
+        })();
+        // The profile of all requests is written on shutdown.
+        unsafe {
+            libc::kill(server.id() as libc::pid_t, libc::SIGINT);
+        }
+        server.wait()?;
+        result?;
+
+        let profile: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?)?;
+        let threads = profile["threads"].as_array().unwrap();
+        for name in ["request 0", "request 1"] {
+            assert!(
+                threads.iter().any(|thread| thread["name"] == name),
+                "no thread named {name}: {threads:?}"
+            );
+        }
+        Ok(())
+    }
 }
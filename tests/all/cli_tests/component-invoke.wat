@@ -0,0 +1,21 @@
+(component
+  (core module $m
+    (func (export "add") (param i32 i32) (result i32)
+      local.get 0
+      local.get 1
+      i32.add)
+    (func (export "is-negative") (param i32) (result i32)
+      local.get 0
+      i32.const 0
+      i32.lt_s)
+  )
+  (core instance $i (instantiate $m))
+
+  (func (export "add") (param "a" u32) (param "b" u32) (result u32)
+    (canon lift (core func $i "add")))
+
+  (func $is-negative (param "x" s32) (result bool)
+    (canon lift (core func $i "is-negative")))
+  (instance (export "math")
+    (export "is-negative" (func $is-negative)))
+)
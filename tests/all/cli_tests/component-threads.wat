@@ -0,0 +1,59 @@
+(component
+  (import "wasi" (instance $wasi
+    (export "thread-spawn" (func (param "start-arg" s32) (result s32)))
+  ))
+  (import "wasi:clocks/monotonic-clock@0.2.0-rc-2023-11-10" (instance $clock
+    (export "now" (func (result u64)))
+  ))
+
+  ;; The shared memory is defined by a module of its own so that the instances
+  ;; of the component running the threads use it too.
+  (core module $mem
+    (memory (export "memory") 1 1 shared)
+  )
+  (core instance $mem (instantiate $mem))
+
+  (core func $thread-spawn (canon lower (func $wasi "thread-spawn")))
+  (core func $now (canon lower (func $clock "now")))
+
+  (core module $m
+    (import "" "memory" (memory 1 1 shared))
+    (import "wasi" "thread-spawn" (func $thread-spawn (param i32) (result i32)))
+    (import "clock" "now" (func $now (result i64)))
+
+    ;; Stores the argument of the thread, trapping if it's zero, and wakes up
+    ;; the spawning thread. The call to the host uses the WASI state shared
+    ;; with the spawning thread.
+    (func (export "wasi-thread-start") (param $tid i32) (param $arg i32)
+      (if (i32.eqz (local.get $arg)) (then unreachable))
+      (drop (call $now))
+      (i32.atomic.store (i32.const 0) (local.get $arg))
+      (drop (memory.atomic.notify (i32.const 0) (i32.const 1)))
+    )
+
+    ;; Spawns a thread with `arg` and returns what it stored, or -1 if the
+    ;; thread couldn't be spawned.
+    (func (export "run") (param $arg i32) (result i32)
+      (if (i32.lt_s (call $thread-spawn (local.get $arg)) (i32.const 0))
+        (then (return (i32.const -1))))
+      (block $done
+        (loop $wait
+          (br_if $done (i32.atomic.load (i32.const 0)))
+          (drop (memory.atomic.wait32 (i32.const 0) (i32.const 0) (i64.const -1)))
+          (br $wait)
+        )
+      )
+      (i32.atomic.load (i32.const 0))
+    )
+  )
+  (core instance $m (instantiate $m
+    (with "" (instance $mem))
+    (with "wasi" (instance (export "thread-spawn" (func $thread-spawn))))
+    (with "clock" (instance (export "now" (func $now))))
+  ))
+
+  (func (export "wasi-thread-start") (param "tid" s32) (param "arg" s32)
+    (canon lift (core func $m "wasi-thread-start")))
+  (func (export "run") (param "arg" s32) (result s32)
+    (canon lift (core func $m "run")))
+)
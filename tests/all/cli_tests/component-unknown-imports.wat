@@ -0,0 +1,23 @@
+(component
+  (import "host:api/unknown" (instance $i
+    (export "get" (func (result u32)))
+  ))
+  (alias export $i "get" (func $get))
+  (core func $get (canon lower (func $get)))
+
+  (core module $m
+    (import "" "get" (func $get (result i32)))
+    (func (export "answer") (result i32)
+      i32.const 42)
+    (func (export "call-unknown") (result i32)
+      call $get)
+  )
+  (core instance $m (instantiate $m
+    (with "" (instance (export "get" (func $get))))
+  ))
+
+  (func (export "answer") (result u32)
+    (canon lift (core func $m "answer")))
+  (func (export "call-unknown") (result u32)
+    (canon lift (core func $m "call-unknown")))
+)
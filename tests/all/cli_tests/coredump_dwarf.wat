@@ -0,0 +1,46 @@
+;; A module with hand-written DWARF for this C source, `coredump.c`:
+;;
+;;     1 | void do_call(void) {
+;;     2 |   do_trap();
+;;     3 | }
+;;     4 | void do_trap(void) {
+;;     5 |   __builtin_trap();
+;;     6 | }
+;;
+;; DWARF addresses are offsets in the code section: the body of `do_call` is
+;; at 2..6, and the one of `do_trap` at 7..10.
+(module
+  (func $do_call (export "a")
+    call $do_trap)
+  (func $do_trap
+    unreachable)
+
+  ;; 1: compile unit with name, stmt_list, low_pc and high_pc
+  ;; 2: subprogram with name, low_pc and high_pc
+  (@custom ".debug_abbrev" (after last)
+    "\01\11\01\03\08\10\17\11\01\12\06\00\00"
+    "\02\2e\00\03\08\11\01\12\06\00\00"
+    "\00")
+
+  ;; DWARF 4, 4-byte addresses
+  (@custom ".debug_info" (after last)
+    "\42\00\00\00\04\00\00\00\00\00\04"
+    "\01" "coredump.c\00" "\00\00\00\00" "\00\00\00\00" "\0a\00\00\00"
+    "\02" "do_call\00" "\02\00\00\00" "\04\00\00\00"
+    "\02" "do_trap\00" "\07\00\00\00" "\03\00\00\00"
+    "\00")
+
+  (@custom ".debug_line" (after last)
+    ;; header: DWARF 4, line_base -5, line_range 14, opcode_base 13
+    "\3c\00\00\00\04\00\22\00\00\00\01\01\01\fb\0e\0d"
+    "\00\01\01\01\01\00\00\00\01\00\00\01"
+    "\00"
+    "coredump.c\00" "\00\00\00"
+    "\00"
+    ;; address 2: line 2
+    "\00\05\02\02\00\00\00" "\03\01" "\01"
+    ;; address 7: line 5
+    "\02\05" "\03\03" "\01"
+    ;; end at address 10
+    "\02\03" "\00\01\01")
+)
@@ -0,0 +1,11 @@
+(module
+    (memory 1)
+    (global (mut i32) (i32.const 42))
+    (data (i32.const 16) "hello coredump")
+    (func (export "a")
+        call $b
+    )
+    (func $b
+        unreachable
+    )
+)
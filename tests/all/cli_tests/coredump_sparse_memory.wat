@@ -0,0 +1,5 @@
+(module
+    (@custom "core" "\00\06sparse")
+    (memory 65536)
+    (data (i32.const 0xfffffff0) "sparse")
+)
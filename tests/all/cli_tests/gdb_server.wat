@@ -0,0 +1,7 @@
+(module
+  (func (export "_start")
+    (local i32)
+    i32.const 5
+    local.set 0
+    nop
+    nop))
@@ -0,0 +1,9 @@
+(module
+  (import "env" "log" (func (param i32 i32)))
+  (import "env" "table" (table 1 funcref))
+  (memory (export "memory") 1 2)
+  (global (export "counter") (mut i32) (i32.const 0))
+  (func (export "run") (param i32) (result i32)
+    local.get 0)
+  (@custom "note" "hello")
+)
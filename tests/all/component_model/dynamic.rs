@@ -551,3 +551,197 @@ fn everything() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn json() -> Result<()> {
+    use serde::de::DeserializeSeed;
+    use serde_json::json;
+
+    let engine = super::engine();
+    let mut store = Store::new(&engine, ());
+
+    let component = Component::new(
+        &engine,
+        make_echo_component(
+            r#"
+                (type $v' (variant (case "a") (case "b" u32)))
+                (export $v "v" (type $v'))
+                (type $e' (enum "x" "y"))
+                (export $e "e" (type $e'))
+                (type $f' (flags "p" "q" "r"))
+                (export $f "f" (type $f'))
+                (type $r' (record
+                    (field "flag" bool)
+                    (field "small" s8)
+                    (field "big" u64)
+                    (field "float" float32)
+                    (field "c" char)
+                    (field "s" string)
+                    (field "t" (tuple u8 string))
+                    (field "v" $v)
+                    (field "e" $e)
+                    (field "flags" $f)
+                    (field "opt" (option (option u32)))
+                    (field "res" (result string (error u8)))
+                    (field "unit" (result))
+                ))
+                (export $r "r" (type $r'))
+                (type $Foo' (list $r))
+            "#,
+            8,
+        ),
+    )?;
+    let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+    let func = instance.get_func(&mut store, "echo").unwrap();
+    let ty = &func.params(&store)[0];
+
+    let input = json!([
+        {
+            "flag": true,
+            "small": -3,
+            "big": u64::MAX,
+            "float": "inf",
+            "c": "ß",
+            "s": "hello",
+            "t": [7, "x"],
+            "v": {"b": 42},
+            "e": "y",
+            "flags": ["p", "r"],
+            "opt": {"some": "none"},
+            "res": {"err": 2},
+            "unit": "ok",
+        },
+        {
+            "flag": false,
+            "small": 127,
+            "big": 0,
+            "float": 1.5,
+            "c": "z",
+            "s": "",
+            "t": [0, "y"],
+            "v": "a",
+            "e": "x",
+            "flags": [],
+            "opt": "none",
+            "res": {"ok": "fine"},
+            "unit": "err",
+        },
+    ]);
+    let val = Val::from_json(ty, &input)?;
+    assert_eq!(val.to_json()?, input);
+    assert_eq!(
+        ty.deserialize(&mut serde_json::Deserializer::from_str(&input.to_string()))?,
+        val
+    );
+
+    let mut output = [Val::Bool(false)];
+    func.call_and_post_return(&mut store, &[val.clone()], &mut output)?;
+    assert_eq!(output[0], val);
+    assert_eq!(serde_json::to_value(&output[0])?, input);
+
+    // Sad paths
+
+    let record = &ty.unwrap_list().ty();
+    let mut fields = input[0].clone();
+    fields["extra"] = json!(1);
+    let err = Val::from_json(record, &fields).unwrap_err();
+    assert!(err.to_string().contains("unknown field `extra`"), "{err}");
+
+    let mut fields = input[0].clone();
+    fields["opt"] = json!(null);
+    let err = Val::from_json(record, &fields).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("invalid field `opt`"),
+        "{err:?}"
+    );
+
+    let mut fields = input[0].clone();
+    fields["small"] = json!(128);
+    let err = Val::from_json(record, &fields).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("`128` is not a valid `s8`"),
+        "{err:?}"
+    );
+
+    let mut fields = input[0].clone();
+    fields["v"] = json!({"a": 1});
+    let err = Val::from_json(record, &fields).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("case `a` does not have a payload"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn component_type() -> Result<()> {
+    use wasmtime::component::types::ComponentItem;
+    use wasmtime::component::Type;
+    use wasmtime::ExternType;
+
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+        (component
+            (import "host" (instance $host
+                (export "r" (type $r (sub resource)))
+                (export "make" (func (param "name" string) (result (own $r))))
+            ))
+            (core module $m
+                (func (export "add") (param i32 i32) (result i32) unreachable)
+                (memory (export "memory") 1)
+            )
+            (core instance $i (instantiate $m))
+            (func $add (param "a" u32) (param "b" u32) (result u32)
+                (canon lift (core func $i "add")))
+            (export "add" (func $add))
+            (export "m" (core module $m))
+        )
+        "#,
+    )?;
+
+    let ty = component.component_type();
+    assert_eq!(ty.imports().len(), 1);
+    let host = match ty.get_import("host") {
+        Some(ComponentItem::ComponentInstance(host)) => host,
+        other => panic!("unexpected import {other:?}"),
+    };
+    let resource = match host.get_export("r") {
+        Some(ComponentItem::Resource(r)) => *r,
+        other => panic!("unexpected export {other:?}"),
+    };
+    let make = match host.get_export("make") {
+        Some(ComponentItem::ComponentFunc(f)) => f,
+        other => panic!("unexpected export {other:?}"),
+    };
+    assert_eq!(make.params().collect::<Vec<_>>(), [("name", &Type::String)]);
+    assert_eq!(
+        make.results().collect::<Vec<_>>(),
+        [(None, &Type::Own(resource))]
+    );
+
+    assert_eq!(
+        ty.exports().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["add", "m"]
+    );
+    let add = match ty.get_export("add") {
+        Some(ComponentItem::ComponentFunc(f)) => f,
+        other => panic!("unexpected export {other:?}"),
+    };
+    assert_eq!(
+        add.params().collect::<Vec<_>>(),
+        [("a", &Type::U32), ("b", &Type::U32)]
+    );
+    let m = match ty.get_export("m") {
+        Some(ComponentItem::Module(m)) => m,
+        other => panic!("unexpected export {other:?}"),
+    };
+    assert_eq!(m.imports().len(), 0);
+    let exports = m.exports().collect::<Vec<_>>();
+    assert!(matches!(exports[0], ("add", ExternType::Func(_))));
+    assert!(matches!(exports[1], ("memory", ExternType::Memory(_))));
+
+    Ok(())
+}
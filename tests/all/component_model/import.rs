@@ -2,6 +2,7 @@
 
 use super::REALLOC_AND_FREE;
 use anyhow::Result;
+use component_test_util::FuncExt;
 use std::ops::Deref;
 use wasmtime::component::*;
 use wasmtime::{Store, StoreContextMut, Trap, WasmBacktrace};
@@ -752,6 +753,128 @@ fn stack_and_heap_args_and_rets() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn func_new_with_result_types() -> Result<()> {
+    let component = r#"
+(component
+  (import "host" (instance $host
+    (type $point' (record (field "x" s32) (field "y" s32)))
+    (export "point" (type $point (eq $point')))
+    (type $center' (variant (case "empty") (case "point" $point)))
+    (export "center" (type $center (eq $center')))
+    (export "centroid" (func (param "points" (list $point)) (result $center)))))
+  (alias export $host "point" (type $point))
+  (alias export $host "center" (type $center))
+  (alias export $host "centroid" (func $centroid))
+
+  (core module $libc
+    (memory (export "memory") 1)
+    (global $next (mut i32) (i32.const 8))
+    (func (export "realloc") (param i32 i32) (param $align i32) (param $size i32) (result i32)
+      (local $ret i32)
+      (local.set $ret
+        (i32.and
+          (i32.add (global.get $next) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get $align))))
+      (global.set $next (i32.add (local.get $ret) (local.get $size)))
+      (local.get $ret)))
+  (core instance $libc (instantiate $libc))
+  (core func $centroid_lower
+    (canon lower (func $centroid) (memory $libc "memory") (realloc (func $libc "realloc"))))
+  (core module $m
+    (import "libc" "realloc" (func $realloc (param i32 i32 i32 i32) (result i32)))
+    (import "host" "centroid" (func $centroid (param i32 i32 i32)))
+    (func (export "run") (param $ptr i32) (param $len i32) (result i32)
+      (local $ret i32)
+      (local.set $ret
+        (call $realloc (i32.const 0) (i32.const 0) (i32.const 4) (i32.const 12)))
+      (call $centroid (local.get $ptr) (local.get $len) (local.get $ret))
+      (local.get $ret)))
+  (core instance $m (instantiate $m
+    (with "libc" (instance $libc))
+    (with "host" (instance (export "centroid" (func $centroid_lower))))))
+  (func (export "run") (param "points" (list $point)) (result $center)
+    (canon lift (core func $m "run") (memory $libc "memory") (realloc (func $libc "realloc"))))
+)
+"#;
+
+    let engine = super::engine();
+    let component = Component::new(&engine, component)?;
+
+    let mut linker = Linker::<()>::new(&engine);
+    linker.instance("host")?.func_new_with_result_types(
+        &component,
+        "centroid",
+        |_, args, result_tys, results| {
+            let Val::List(points) = &args[0] else {
+                panic!("unexpected argument {:?}", args[0]);
+            };
+            assert_eq!(result_tys.len(), 1);
+            let center = result_tys[0].unwrap_variant();
+            if points.is_empty() {
+                results[0] = center.new_val("empty", None)?;
+                return Ok(());
+            }
+
+            let (mut x, mut y) = (0, 0);
+            for point in points.iter() {
+                let Val::Record(point) = point else {
+                    panic!("unexpected point {point:?}");
+                };
+                for (name, val) in point.fields() {
+                    match (name, val) {
+                        ("x", Val::S32(v)) => x += v,
+                        ("y", Val::S32(v)) => y += v,
+                        _ => panic!("unexpected field {name}: {val:?}"),
+                    }
+                }
+            }
+            let n = i32::try_from(points.len())?;
+            let point_ty = center
+                .cases()
+                .find(|case| case.name == "point")
+                .and_then(|case| case.ty)
+                .unwrap();
+            let point = point_ty
+                .unwrap_record()
+                .new_val([("x", Val::S32(x / n)), ("y", Val::S32(y / n))])?;
+            results[0] = center.new_val("point", Some(point))?;
+            Ok(())
+        },
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let instance = linker.instantiate(&mut store, &component)?;
+    let run = instance.get_func(&mut store, "run").unwrap();
+    let list_ty = run.params(&store)[0].unwrap_list().clone();
+    let point_ty = list_ty.ty();
+    let point = |x, y| {
+        point_ty
+            .unwrap_record()
+            .new_val([("x", Val::S32(x)), ("y", Val::S32(y))])
+            .unwrap()
+    };
+
+    let mut results = [Val::Bool(false)];
+    let points = list_ty.new_val(vec![point(0, 0), point(4, 2), point(8, 7)].into())?;
+    run.call_and_post_return(&mut store, &[points], &mut results)?;
+    let Val::Variant(center) = &results[0] else {
+        panic!("unexpected result {:?}", results[0]);
+    };
+    assert_eq!(center.discriminant(), "point");
+    assert_eq!(center.payload(), Some(&point(4, 3)));
+
+    let empty = list_ty.new_val(Box::new([]))?;
+    run.call_and_post_return(&mut store, &[empty], &mut results)?;
+    let Val::Variant(center) = &results[0] else {
+        panic!("unexpected result {:?}", results[0]);
+    };
+    assert_eq!(center.discriminant(), "empty");
+    assert_eq!(center.payload(), None);
+
+    Ok(())
+}
+
 #[test]
 fn bad_import_alignment() -> Result<()> {
     let component = format!(
@@ -985,3 +1108,307 @@ fn use_types_across_component_boundaries() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn define_unknown_imports_as_traps() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"(component
+            (import "defined" (func $defined (result u32)))
+            (import "unknown" (func $unknown))
+            (import "unknown-instance" (instance $i
+                (export "r" (type (sub resource)))
+                (export "f" (func (result u32)))
+            ))
+            (alias export $i "r" (type $r))
+            (alias export $i "f" (func $f))
+            (export "r" (type $r))
+
+            (core func $defined (canon lower (func $defined)))
+            (core func $unknown (canon lower (func $unknown)))
+            (core func $f (canon lower (func $f)))
+            (core module $m
+                (import "" "defined" (func $defined (result i32)))
+                (import "" "unknown" (func $unknown))
+                (import "" "f" (func $f (result i32)))
+                (func (export "call-defined") (result i32)
+                    call $defined)
+                (func (export "call-unknown")
+                    call $unknown)
+                (func (export "call-f") (result i32)
+                    call $f)
+            )
+            (core instance $m (instantiate $m
+                (with "" (instance
+                    (export "defined" (func $defined))
+                    (export "unknown" (func $unknown))
+                    (export "f" (func $f))
+                ))
+            ))
+
+            (func (export "call-defined") (result u32)
+                (canon lift (core func $m "call-defined")))
+            (func (export "call-unknown")
+                (canon lift (core func $m "call-unknown")))
+            (func (export "call-f") (result u32)
+                (canon lift (core func $m "call-f")))
+        )"#,
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    assert!(linker.instantiate(&mut store, &component).is_err());
+
+    linker.root().func_wrap("defined", |_, ()| Ok((42u32,)))?;
+    linker.define_unknown_imports_as_traps(&component)?;
+    let instance = linker.instantiate(&mut store, &component)?;
+
+    let call_defined = instance.get_typed_func::<(), (u32,)>(&mut store, "call-defined")?;
+    assert_eq!(call_defined.call(&mut store, ())?, (42,));
+    call_defined.post_return(&mut store)?;
+
+    let call_unknown = instance.get_typed_func::<(), ()>(&mut store, "call-unknown")?;
+    let err = call_unknown.call(&mut store, ()).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("unknown import `unknown` has not been defined"),
+        "{err:?}"
+    );
+
+    // Functions nested within unknown instances trap too, which is checked
+    // in a fresh instance since the trap above poisoned this one.
+    let mut store = Store::new(&engine, ());
+    let instance = linker.instantiate(&mut store, &component)?;
+    let call_f = instance.get_typed_func::<(), (u32,)>(&mut store, "call-f")?;
+    let err = call_f.call(&mut store, ()).unwrap_err();
+    assert!(
+        format!("{err:?}").contains("unknown import `unknown-instance#f` has not been defined"),
+        "{err:?}"
+    );
+
+    // The unknown resource is defined as a placeholder host resource, which
+    // unlike a resource defined by a component is the same type in every
+    // instance.
+    let r = instance.get_resource(&mut store, "r").unwrap();
+    let mut store2 = Store::new(&engine, ());
+    let instance2 = linker.instantiate(&mut store2, &component)?;
+    assert_eq!(instance2.get_resource(&mut store2, "r"), Some(r));
+    assert_ne!(r, ResourceType::host::<()>());
+
+    Ok(())
+}
+
+#[test]
+fn define_unknown_imports_as_default_values() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"(component
+            (import "host" (instance $host
+                (type $r' (record (field "a" u32) (field "b" (option string))))
+                (export "r" (type $r (eq $r')))
+                (type $v' (variant (case "first" s64) (case "second")))
+                (export "v" (type $v (eq $v')))
+                (type $e' (enum "x" "y"))
+                (export "e" (type $e (eq $e')))
+                (export "number" (func (param "x" u32) (result float64)))
+                (export "values" (func (result (tuple $r $v $e (result char (error u8))))))
+            ))
+            (alias export $host "r" (type $r))
+            (alias export $host "v" (type $v))
+            (alias export $host "e" (type $e))
+            (alias export $host "number" (func $number))
+            (alias export $host "values" (func $values))
+
+            (core module $libc
+                (memory (export "memory") 1)
+                (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                    i32.const 64)
+            )
+            (core instance $libc (instantiate $libc))
+            (core func $number (canon lower (func $number)))
+            (core func $values (canon lower (func $values)
+                (memory $libc "memory") (realloc (func $libc "realloc"))))
+            (core module $m
+                (import "" "number" (func $number (param i32) (result f64)))
+                (import "" "values" (func $values (param i32)))
+                (func (export "number") (result f64)
+                    (call $number (i32.const 1)))
+                (func (export "values") (result i32)
+                    (call $values (i32.const 8))
+                    i32.const 8)
+            )
+            (core instance $m (instantiate $m
+                (with "" (instance
+                    (export "number" (func $number))
+                    (export "values" (func $values))
+                ))
+            ))
+
+            (func (export "number") (result float64)
+                (canon lift (core func $m "number")))
+            (func (export "values") (result (tuple $r $v $e (result char (error u8))))
+                (canon lift (core func $m "values") (memory $libc "memory")))
+        )"#,
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let mut linker = Linker::new(&engine);
+    linker.define_unknown_imports_as_default_values(&component)?;
+    let instance = linker.instantiate(&mut store, &component)?;
+
+    let number = instance.get_typed_func::<(), (f64,)>(&mut store, "number")?;
+    assert_eq!(number.call(&mut store, ())?, (0.0,));
+    number.post_return(&mut store)?;
+
+    let values = instance.get_func(&mut store, "values").unwrap();
+    let mut results = [Val::Bool(false)];
+    values.call_and_post_return(&mut store, &[], &mut results)?;
+    let Val::Tuple(tuple) = &results[0] else {
+        panic!("unexpected result {:?}", results[0]);
+    };
+    let [Val::Record(r), Val::Variant(v), Val::Enum(e), Val::Result(result)] = tuple.values()
+    else {
+        panic!("unexpected result {tuple:?}");
+    };
+    let fields = r.fields().collect::<Vec<_>>();
+    assert_eq!(fields[0], ("a", &Val::U32(0)));
+    let Val::Option(b) = fields[1].1 else {
+        panic!("unexpected field {:?}", fields[1]);
+    };
+    assert_eq!(b.value(), None);
+    assert_eq!(v.discriminant(), "first");
+    assert_eq!(v.payload(), Some(&Val::S64(0)));
+    assert_eq!(e.discriminant(), "x");
+    assert_eq!(result.value(), Ok(Some(&Val::Char('\0'))));
+
+    Ok(())
+}
+
+#[test]
+fn instance_from() -> Result<()> {
+    let engine = super::engine();
+    let exporter = Component::new(
+        &engine,
+        r#"(component
+            (core module $m
+                (global $drops (mut i32) (i32.const 0))
+                (func (export "dtor") (param i32)
+                    (global.set $drops (i32.add (global.get $drops) (i32.const 1))))
+                (func (export "drops") (result i32)
+                    global.get $drops)
+                (func (export "add") (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1)))
+                ;; borrows of a resource owned by this component are passed
+                ;; as their representation directly
+                (func (export "get") (param i32) (result i32)
+                    local.get 0)
+            )
+            (core instance $m (instantiate $m))
+
+            (type $r (resource (rep i32) (dtor (func $m "dtor"))))
+            (core func $new (canon resource.new $r))
+            (core module $m2
+                (import "" "new" (func $new (param i32) (result i32)))
+                (func (export "make") (param i32) (result i32)
+                    (call $new (local.get 0)))
+            )
+            (core instance $m2 (instantiate $m2
+                (with "" (instance (export "new" (func $new))))
+            ))
+
+            (func $add (param "a" u32) (param "b" u32) (result u32)
+                (canon lift (core func $m "add")))
+            (func $make (param "x" u32) (result (own $r))
+                (canon lift (core func $m2 "make")))
+            (func $get (param "r" (borrow $r)) (result u32)
+                (canon lift (core func $m "get")))
+            (instance $api
+                (export "r" (type $r))
+                (export "add" (func $add))
+                (export "make" (func $make))
+                (export "get" (func $get))
+            )
+            (export "api" (instance $api))
+            (func (export "drops") (result u32)
+                (canon lift (core func $m "drops")))
+        )"#,
+    )?;
+    let importer = Component::new(
+        &engine,
+        r#"(component
+            (import "api" (instance $api
+                (export "r" (type $r (sub resource)))
+                (export "add" (func (param "a" u32) (param "b" u32) (result u32)))
+                (export "make" (func (param "x" u32) (result (own $r))))
+                (export "get" (func (param "r" (borrow $r)) (result u32)))
+            ))
+            (alias export $api "r" (type $r))
+
+            (core func $add (canon lower (func $api "add")))
+            (core func $make (canon lower (func $api "make")))
+            (core func $get (canon lower (func $api "get")))
+            (core func $drop (canon resource.drop $r))
+            (core module $m
+                (import "" "add" (func $add (param i32 i32) (result i32)))
+                (import "" "make" (func $make (param i32) (result i32)))
+                (import "" "get" (func $get (param i32) (result i32)))
+                (import "" "drop" (func $drop (param i32)))
+                (func (export "run") (param i32) (result i32)
+                    (local $r i32)
+                    (local $v i32)
+                    (local.set $r (call $make (local.get 0)))
+                    (local.set $v (call $get (local.get $r)))
+                    (call $drop (local.get $r))
+                    (call $add (local.get $v) (i32.const 1)))
+            )
+            (core instance $m (instantiate $m
+                (with "" (instance
+                    (export "add" (func $add))
+                    (export "make" (func $make))
+                    (export "get" (func $get))
+                    (export "drop" (func $drop))
+                ))
+            ))
+
+            (func (export "run") (param "x" u32) (result u32)
+                (canon lift (core func $m "run")))
+        )"#,
+    )?;
+    let mismatched = Component::new(
+        &engine,
+        r#"(component
+            (import "api" (instance
+                (export "add" (func (param "a" u64) (param "b" u64) (result u64)))
+            ))
+        )"#,
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let exporter = Linker::new(&engine).instantiate(&mut store, &exporter)?;
+
+    let mut linker = Linker::new(&engine);
+    linker.root().instance_from(&mut store, &exporter, "api")?;
+    assert!(linker
+        .root()
+        .instance_from(&mut store, &exporter, "missing")
+        .is_err());
+
+    let err = linker.instantiate(&mut store, &mismatched).err().unwrap();
+    assert!(
+        format!("{err:?}").contains("type mismatch with parameters"),
+        "{err:?}"
+    );
+
+    let importer = linker.instantiate(&mut store, &importer)?;
+    let run = importer.get_typed_func::<(u32,), (u32,)>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, (41,))?, (42,));
+    run.post_return(&mut store)?;
+
+    // Dropping the handle in the importer ran the exporter's destructor.
+    let drops = exporter.get_typed_func::<(), (u32,)>(&mut store, "drops")?;
+    assert_eq!(drops.call(&mut store, ())?, (1,));
+    drops.post_return(&mut store)?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use component_test_util::TypedFuncExt;
 use wasmtime::component::*;
 use wasmtime::{Module, Store};
 
@@ -55,3 +56,88 @@ fn instance_exports() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn instantiate_with_shared_memories() -> Result<()> {
+    let mut config = component_test_util::config();
+    config.wasm_threads(true);
+    let engine = wasmtime::Engine::new(&config)?;
+    let component = r#"
+        (component
+            (core module $mem
+                (memory (export "memory") 1 1 shared)
+            )
+            (core module $m
+                (import "" "memory" (memory 1 1 shared))
+                (func (export "store") (param i32)
+                    i32.const 0
+                    local.get 0
+                    i32.atomic.store)
+                (func (export "load") (result i32)
+                    i32.const 0
+                    i32.atomic.load)
+            )
+            (core instance $mem (instantiate $mem))
+            (core instance $m (instantiate $m (with "" (instance $mem))))
+            (func (export "store") (param "x" u32) (canon lift (core func $m "store")))
+            (func (export "load") (result u32) (canon lift (core func $m "load")))
+        )
+    "#;
+    let component = Component::new(&engine, component)?;
+    let instance_pre = Linker::new(&engine).instantiate_pre(&component)?;
+
+    let mut store1 = Store::new(&engine, ());
+    let instance1 = instance_pre.instantiate(&mut store1)?;
+    let shared_memories = instance1.shared_memories(&mut store1);
+    assert_eq!(shared_memories.len(), 2);
+
+    let mut store2 = Store::new(&engine, ());
+    let instance2 = instance_pre.instantiate_with_shared_memories(&mut store2, &shared_memories)?;
+
+    let set = instance1.get_typed_func::<(u32,), ()>(&mut store1, "store")?;
+    let load = instance2.get_typed_func::<(), (u32,)>(&mut store2, "load")?;
+    set.call_and_post_return(&mut store1, (42,))?;
+    assert_eq!(load.call_and_post_return(&mut store2, ())?, (42,));
+
+    // A separate instance without the shared memories has memory of its own.
+    let mut store3 = Store::new(&engine, ());
+    let instance3 = instance_pre.instantiate(&mut store3)?;
+    let load = instance3.get_typed_func::<(), (u32,)>(&mut store3, "load")?;
+    assert_eq!(load.call_and_post_return(&mut store3, ())?, (0,));
+
+    // Shared memories can only be used with the same component.
+    let other = Component::new(&engine, "(component)")?;
+    let other_pre = Linker::new(&engine).instantiate_pre(&other)?;
+    let mut store4 = Store::new(&engine, ());
+    assert!(other_pre
+        .instantiate_with_shared_memories(&mut store4, &shared_memories)
+        .is_err());
+
+    // A module defining a shared memory can't have functions, which would
+    // use a new memory in other instances.
+    let component = r#"
+        (component
+            (core module $m
+                (memory (export "memory") 1 1 shared)
+                (func (export "load") (result i32)
+                    i32.const 0
+                    i32.atomic.load)
+            )
+            (core instance $m (instantiate $m))
+            (func (export "load") (result u32) (canon lift (core func $m "load")))
+        )
+    "#;
+    let component = Component::new(&engine, component)?;
+    let instance_pre = Linker::new(&engine).instantiate_pre(&component)?;
+    let mut store1 = Store::new(&engine, ());
+    let instance1 = instance_pre.instantiate(&mut store1)?;
+    let shared_memories = instance1.shared_memories(&mut store1);
+    let mut store2 = Store::new(&engine, ());
+    let err = instance_pre
+        .instantiate_with_shared_memories(&mut store2, &shared_memories)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("defines functions"), "{err}");
+
+    Ok(())
+}
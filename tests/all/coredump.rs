@@ -258,3 +258,297 @@ fn multiple_globals_memories_and_instances() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coredump_has_locals() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.coredump_locals(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module
+          (memory 1)
+          (func $a (export "a") (param i32)
+              (local i64 f32)
+              i64.const 42
+              local.set 1
+              f32.const 1.5
+              local.set 2
+              local.get 0
+              call $b
+          )
+          (func $b (param i32)
+              (local f64 v128)
+              f64.const 2.5
+              local.set 1
+              local.get 0
+              i32.const 1
+              i32.add
+              call $c
+          )
+          (func $c (param i32)
+              local.get 0
+              i32.load
+              drop
+          )
+      )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<i32, ()>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, -16).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert_eq!(cd.frames().len(), 3);
+    let locals = cd.frame_locals();
+    assert_eq!(locals.len(), 3);
+
+    assert_eq!(locals[0].len(), 1);
+    assert_eq!(locals[0][0].as_ref().unwrap().unwrap_i32(), -15);
+
+    assert_eq!(locals[1].len(), 3);
+    assert_eq!(locals[1][0].as_ref().unwrap().unwrap_i32(), -16);
+    assert_eq!(locals[1][1].as_ref().unwrap().unwrap_f64(), 2.5);
+    assert!(locals[1][2].is_none());
+
+    assert_eq!(locals[2].len(), 3);
+    assert_eq!(locals[2][0].as_ref().unwrap().unwrap_i32(), -16);
+    assert_eq!(locals[2][1].as_ref().unwrap().unwrap_i64(), 42);
+    assert_eq!(locals[2][2].as_ref().unwrap().unwrap_f32(), 1.5);
+
+    // The locals are written to the `corestack` section of the serialized
+    // coredump too, while operand stacks are left empty.
+    use wasmparser::{BinaryReader, CoreDumpStackSection, CoreDumpValue, FromReader};
+    let serialized = cd.serialize(&mut store, "locals");
+    let section = wasmparser::Parser::new(0)
+        .parse_all(&serialized)
+        .find_map(|payload| match payload {
+            Ok(wasmparser::Payload::CustomSection(s)) if s.name() == "corestack" => Some(s),
+            _ => None,
+        })
+        .unwrap();
+    let stack = CoreDumpStackSection::from_reader(&mut BinaryReader::new(section.data()))?;
+    assert_eq!(stack.name, "main");
+    assert_eq!(stack.frames.len(), 3);
+    assert!(stack.frames.iter().all(|f| f.stack.is_empty()));
+
+    let frame_locals = stack
+        .frames
+        .iter()
+        .map(|f| {
+            f.locals
+                .iter()
+                .map(|l| match l {
+                    CoreDumpValue::Missing => "missing".to_string(),
+                    CoreDumpValue::I32(x) => format!("i32 {x}"),
+                    CoreDumpValue::I64(x) => format!("i64 {x}"),
+                    CoreDumpValue::F32(x) => format!("f32 {x}"),
+                    CoreDumpValue::F64(x) => format!("f64 {x}"),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        frame_locals,
+        [
+            vec!["i32 -15"],
+            vec!["i32 -16", "f64 2.5", "missing"],
+            vec!["i32 -16", "i64 42", "f32 1.5"],
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn coredump_locals_rejects_winch() {
+    let mut config = Config::new();
+    config.coredump_locals(true);
+    config.strategy(Strategy::Winch);
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("coredumps"), "{err}");
+}
+
+#[test]
+fn coredump_locals_rejects_s390x() -> Result<()> {
+    let mut config = Config::new();
+    config.coredump_locals(true);
+    config.target("s390x-unknown-linux-gnu")?;
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("s390x"), "{err}");
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coredump_locals_are_opt_in() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    let engine = Engine::new(&config).unwrap();
+    let mut store = Store::<()>::new(&engine, ());
+
+    let wat = r#"
+      (module
+          (func $a (export "a") (param i32)
+              unreachable
+          )
+      )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let a_func = instance.get_typed_func::<i32, ()>(&mut store, "a")?;
+
+    let e = a_func.call(&mut store, 1).unwrap_err();
+    let cd = e.downcast_ref::<WasmCoreDump>().unwrap();
+    assert_eq!(cd.frames().len(), 1);
+    assert_eq!(cd.frame_locals().len(), 1);
+    assert!(cd.frame_locals()[0].is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coredump_records_component_instances() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::<()>::new(&engine, ());
+
+    let component = component::Component::new(
+        &engine,
+        r#"
+            (component
+                (component $inner
+                    (core module $m (func (export "f")))
+                    (core instance (instantiate $m))
+                )
+                (instance (instantiate $inner))
+
+                (core module $m
+                    (func (export "run") unreachable)
+                )
+                (core instance $i (instantiate $m))
+                (func (export "run") (canon lift (core func $i "run")))
+            )
+        "#,
+    )?;
+    let linker = component::Linker::new(&engine);
+    let instance = linker.instantiate(&mut store, &component)?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    let e = run.call(&mut store, ()).unwrap_err();
+    let core_dump = e.downcast_ref::<WasmCoreDump>().unwrap();
+
+    assert_eq!(core_dump.components().len(), 1);
+    let component = &core_dump.components()[0];
+    assert_eq!(component.component_instances(), [None, Some(0)]);
+
+    // The nested component's module is instantiated first.
+    let core_instances = component.core_instances();
+    assert_eq!(core_instances.len(), 2);
+    assert_eq!(core_instances[0].component_instance(), Some(1));
+    assert_eq!(core_instances[1].component_instance(), Some(0));
+    assert!(core_instances.iter().all(|i| !i.is_adapter()));
+    let trapping = core_instances[1].instance();
+    assert_eq!(
+        core_dump.instances()[trapping].module(&store).name(),
+        core_dump.frames()[0].module().name()
+    );
+    assert!(component.resource_tables().is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coredump_records_component_resources() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::<()>::new(&engine, ());
+
+    let component = component::Component::new(
+        &engine,
+        r#"
+            (component
+                (type $r (resource (rep i32)))
+                (core func $new (canon resource.new $r))
+                (core module $m
+                    (import "" "new" (func $new (param i32) (result i32)))
+                    (func (export "run")
+                        (drop (call $new (i32.const 100)))
+                        unreachable)
+                )
+                (core instance $i (instantiate $m
+                    (with "" (instance (export "new" (func $new))))
+                ))
+                (func (export "run") (canon lift (core func $i "run")))
+            )
+        "#,
+    )?;
+    let linker = component::Linker::new(&engine);
+    let instance = linker.instantiate(&mut store, &component)?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    let e = run.call(&mut store, ()).unwrap_err();
+    let core_dump = e.downcast_ref::<WasmCoreDump>().unwrap();
+
+    let tables = core_dump.components()[0].resource_tables();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].component_instance(), 0);
+    let handles = tables[0].handles();
+    assert_eq!(handles.len(), 1);
+    assert_eq!(handles[0].rep(), 100);
+    assert!(handles[0].is_own());
+
+    // The component structure is serialized too.
+    let serialized = core_dump.serialize(&mut store, "component");
+    let found = wasmparser::Parser::new(0)
+        .parse_all(&serialized)
+        .filter_map(|payload| match payload {
+            Ok(wasmparser::Payload::CustomSection(s)) => Some(s.name().to_string()),
+            _ => None,
+        })
+        .any(|name| name == "corecomponents");
+    assert!(found);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coredump_during_component_instantiation() -> Result<()> {
+    let mut config = Config::default();
+    config.coredump_on_trap(true);
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::<()>::new(&engine, ());
+
+    let component = component::Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $ok)
+                (core instance (instantiate $ok))
+                (core module $trap
+                    (func $start unreachable)
+                    (start $start)
+                )
+                (core instance (instantiate $trap))
+            )
+        "#,
+    )?;
+    let linker = component::Linker::new(&engine);
+    let e = linker.instantiate(&mut store, &component).err().unwrap();
+    let core_dump = e.downcast_ref::<WasmCoreDump>().unwrap();
+
+    // The trap is recorded along with the core instances created so far, but
+    // the component instance hadn't finished instantiating so its structure
+    // isn't.
+    assert_eq!(core_dump.frames().len(), 1);
+    assert_eq!(core_dump.instances().len(), 2);
+    assert!(core_dump.components().is_empty());
+    Ok(())
+}
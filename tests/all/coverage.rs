@@ -0,0 +1,179 @@
+use anyhow::Result;
+use wasmtime::*;
+
+fn engine() -> Engine {
+    let mut config = Config::new();
+    config.coverage_instrumentation(true);
+    Engine::new(&config).unwrap()
+}
+
+fn counts(store: &Store<()>, func_index: u32) -> Vec<u64> {
+    let coverage = store.coverage();
+    assert_eq!(coverage.modules().len(), 1);
+    coverage.modules()[0]
+        .blocks()
+        .iter()
+        .filter(|b| b.func_index() == func_index)
+        .map(|b| b.count())
+        .collect()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn counts_loop_iterations() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (func (export "run") (param i32)
+                    (loop $l
+                        local.get 0
+                        i32.const 1
+                        i32.sub
+                        local.tee 0
+                        br_if $l)))
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<u32, ()>(&mut store, "run")?;
+
+    run.call(&mut store, 10)?;
+    assert_eq!(counts(&store, 0), [1, 10, 1, 1]);
+
+    // Instances of the same module share their counters.
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<u32, ()>(&mut store, "run")?;
+    run.call(&mut store, 5)?;
+    assert_eq!(counts(&store, 0), [2, 15, 2, 2]);
+
+    store.reset_coverage();
+    assert_eq!(counts(&store, 0), [0, 0, 0, 0]);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn counts_branches() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (func $trap unreachable)
+                (func (export "pick") (param i32) (result i32)
+                    local.get 0
+                    if (result i32)
+                        i32.const 1
+                    else
+                        i32.const 2
+                    end)
+                (func (export "maybe-trap") (param i32)
+                    local.get 0
+                    if
+                        call $trap
+                        nop
+                    end))
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let pick = instance.get_typed_func::<u32, u32>(&mut store, "pick")?;
+    let maybe_trap = instance.get_typed_func::<u32, ()>(&mut store, "maybe-trap")?;
+
+    for arg in [1, 1, 1, 0] {
+        pick.call(&mut store, arg)?;
+    }
+    assert_eq!(counts(&store, 1), [4, 3, 1, 4]);
+
+    maybe_trap.call(&mut store, 0)?;
+    assert!(maybe_trap.call(&mut store, 1).is_err());
+    // The code after the call is counted separately from the call since it
+    // doesn't run when the callee traps.
+    assert_eq!(counts(&store, 2), [2, 1, 0, 1]);
+    assert_eq!(counts(&store, 0), [1]);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn blocks_are_module_offsets() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, r#"(module (func (export "f") nop))"#)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    f.call(&mut store, ())?;
+
+    let coverage = store.coverage();
+    let blocks = coverage.modules()[0].blocks();
+    assert_eq!(blocks.len(), 1);
+    assert!(blocks[0].start_offset() < blocks[0].end_offset());
+    // Offsets are past the 8-byte module header, not function-relative.
+    assert!(blocks[0].start_offset() > 8);
+    assert_eq!(blocks[0].count(), 1);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn uninstrumented_modules_are_not_reported() -> Result<()> {
+    let engine = Engine::default();
+    let module = Module::new(&engine, r#"(module (func (export "f")))"#)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    f.call(&mut store, ())?;
+    assert!(store.coverage().modules().is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn lcov_without_dwarf_is_empty() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, r#"(module (func (export "f")))"#)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    f.call(&mut store, ())?;
+
+    let mut lcov = Vec::new();
+    store.coverage().write_lcov(&mut lcov)?;
+    assert!(lcov.is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn lcov_maps_blocks_to_lines() -> Result<()> {
+    let mut config = Config::new();
+    config.coverage_instrumentation(true);
+    config.wasm_backtrace_details(WasmBacktraceDetails::Enable);
+    let engine = Engine::new(&config)?;
+    let module = Module::from_file(&engine, "tests/all/debug/testsuite/fib-wasm.wasm")?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let fib = instance.get_typed_func::<i32, i32>(&mut store, "fib")?;
+    assert_eq!(fib.call(&mut store, 5)?, 8);
+
+    let mut lcov = Vec::new();
+    store.coverage().write_lcov(&mut lcov)?;
+    let lcov = String::from_utf8(lcov)?;
+    assert!(lcov.contains("fib-wasm.c\n"), "{lcov}");
+    // `int t, a = 0, b = 1;` runs once and the loop body runs five times.
+    assert!(lcov.contains("\nDA:9,1\n"), "{lcov}");
+    assert!(lcov.contains("\nDA:11,5\n"), "{lcov}");
+    assert!(lcov.ends_with("end_of_record\n"), "{lcov}");
+    Ok(())
+}
+
+#[test]
+fn coverage_rejects_winch() {
+    let mut config = Config::new();
+    config.coverage_instrumentation(true);
+    config.strategy(Strategy::Winch);
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("coverage"), "{err}");
+}
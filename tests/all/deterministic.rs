@@ -0,0 +1,246 @@
+use anyhow::Result;
+use wasmtime::component::ResourceTable;
+use wasmtime::*;
+use wasmtime_wasi::preview2::preview1::{self, WasiPreview1Adapter, WasiPreview1View};
+use wasmtime_wasi::preview2::{add_fuel_clock_to_store, WasiCtx, WasiCtxBuilder, WasiView};
+
+#[test]
+fn deterministic_disables_threads() -> Result<()> {
+    let mut config = Config::new();
+    config.deterministic(true);
+    let engine = Engine::new(&config)?;
+    assert!(Module::new(&engine, "(module (memory 1 1 shared))").is_err());
+
+    config.wasm_threads(true);
+    assert!(Engine::new(&config).is_err());
+    Ok(())
+}
+
+#[test]
+fn deterministic_requires_relaxed_simd_deterministic() -> Result<()> {
+    let mut config = Config::new();
+    config.deterministic(true);
+    config.relaxed_simd_deterministic(false);
+    assert!(Engine::new(&config).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deterministic_canonicalizes_nans() -> Result<()> {
+    let mut config = Config::new();
+    config.deterministic(true);
+    let engine = Engine::new(&config)?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (func (export "nan") (param f32) (result i32)
+                    (i32.reinterpret_f32 (f32.div (local.get 0) (local.get 0))))
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let nan = instance.get_typed_func::<f32, i32>(&mut store, "nan")?;
+    assert_eq!(nan.call(&mut store, 0.0)? as u32, 0x7fc0_0000);
+    Ok(())
+}
+
+#[test]
+fn deterministic_rejects_winch() {
+    let mut config = Config::new();
+    config.deterministic(true);
+    config.strategy(Strategy::Winch);
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("deterministic"), "{err}");
+}
+
+#[test]
+fn fuel_hook_reports_consumed_fuel() -> Result<()> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config)?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (import "" "host" (func $host))
+                (func (export "run")
+                    call $host
+                    nop nop nop nop
+                    call $host)
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, Vec::new());
+    store.set_fuel(1000)?;
+    store.fuel_hook(|reports: &mut Vec<u64>, fuel| reports.push(fuel));
+    let host = Func::wrap(&mut store, || {});
+    let instance = Instance::new(&mut store, &module, &[host.into()])?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    run.call(&mut store, ())?;
+
+    let reports = store.data();
+    assert_eq!(reports.len(), 2);
+    assert!(reports.iter().all(|fuel| *fuel > 0), "{reports:?}");
+    assert!(reports.iter().sum::<u64>() <= 1000 - store.get_fuel()?);
+    Ok(())
+}
+
+/// A guest which writes 16 random bytes at address 0, then the monotonic
+/// time at 16, does some work, and writes the monotonic time again at 24.
+const CLOCK_AND_RANDOM: &str = r#"
+    (module
+        (import "wasi_snapshot_preview1" "random_get"
+            (func $random_get (param i32 i32) (result i32)))
+        (import "wasi_snapshot_preview1" "clock_time_get"
+            (func $clock_time_get (param i32 i64 i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "run") (local i32)
+            (drop (call $random_get (i32.const 0) (i32.const 16)))
+            (drop (call $clock_time_get (i32.const 1) (i64.const 1) (i32.const 16)))
+            (loop
+                (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                (br_if 0 (i32.lt_u (local.get 0) (i32.const 1000))))
+            (drop (call $clock_time_get (i32.const 1) (i64.const 1) (i32.const 24))))
+    )
+"#;
+
+fn run_clock_and_random<T>(store: &mut Store<T>, linker: &Linker<T>) -> Result<Vec<u8>> {
+    let module = Module::new(store.engine(), CLOCK_AND_RANDOM)?;
+    let instance = linker.instantiate(&mut *store, &module)?;
+    let run = instance.get_typed_func::<(), ()>(&mut *store, "run")?;
+    run.call(&mut *store, ())?;
+    let memory = instance.get_memory(&mut *store, "memory").unwrap();
+    let output = memory.data(&store)[..32].to_vec();
+
+    let before = u64::from_le_bytes(output[16..24].try_into().unwrap());
+    let after = u64::from_le_bytes(output[24..32].try_into().unwrap());
+    assert!(before < after, "{before} >= {after}");
+    Ok(output)
+}
+
+fn deterministic_engine() -> Result<Engine> {
+    let mut config = Config::new();
+    config.deterministic(true);
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deterministic_preview1_ctx() -> Result<()> {
+    let engine = deterministic_engine()?;
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::add_to_linker(&mut linker, |cx| cx)?;
+
+    let run = |seed| {
+        let ctx = wasmtime_wasi::sync::WasiCtxBuilder::new()
+            .deterministic(seed)?
+            .build();
+        let mut store = Store::new(&engine, ctx);
+        store.set_fuel(u64::MAX)?;
+        store.fuel_hook(|cx: &mut wasmtime_wasi::WasiCtx, fuel| {
+            cx.clocks.virtual_clock().unwrap().consume_fuel(fuel)
+        });
+        run_clock_and_random(&mut store, &linker)
+    };
+    let output = run(42)?;
+    assert_eq!(output, run(42)?);
+    assert_ne!(output[..16], run(43)?[..16]);
+    Ok(())
+}
+
+#[test]
+fn deterministic_preview1_ctx_rejects_sockets() -> Result<()> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let socket = wasmtime_wasi::sync::TcpListener::from_std(listener.try_clone()?);
+    let mut builder = wasmtime_wasi::sync::WasiCtxBuilder::new();
+    builder.deterministic(0)?;
+    assert!(builder.preopened_socket(3, socket).is_err());
+
+    let socket = wasmtime_wasi::sync::TcpListener::from_std(listener);
+    let mut builder = wasmtime_wasi::sync::WasiCtxBuilder::new();
+    builder.preopened_socket(3, socket)?;
+    assert!(builder.deterministic(0).is_err());
+    Ok(())
+}
+
+struct Preview2Ctx {
+    table: ResourceTable,
+    wasi: WasiCtx,
+    adapter: WasiPreview1Adapter,
+}
+
+impl WasiView for Preview2Ctx {
+    fn table(&self) -> &ResourceTable {
+        &self.table
+    }
+    fn table_mut(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+    fn ctx(&self) -> &WasiCtx {
+        &self.wasi
+    }
+    fn ctx_mut(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl WasiPreview1View for Preview2Ctx {
+    fn adapter(&self) -> &WasiPreview1Adapter {
+        &self.adapter
+    }
+    fn adapter_mut(&mut self) -> &mut WasiPreview1Adapter {
+        &mut self.adapter
+    }
+}
+
+fn preview2_ctx(seed: u64) -> Preview2Ctx {
+    Preview2Ctx {
+        table: ResourceTable::new(),
+        wasi: WasiCtxBuilder::new()
+            .inherit_network()
+            .deterministic(seed)
+            .build(),
+        adapter: WasiPreview1Adapter::new(),
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deterministic_preview2_ctx() -> Result<()> {
+    let engine = deterministic_engine()?;
+    let mut linker = Linker::new(&engine);
+    preview1::add_to_linker_sync(&mut linker)?;
+
+    let run = |seed| {
+        let mut store = Store::new(&engine, preview2_ctx(seed));
+        store.set_fuel(u64::MAX)?;
+        add_fuel_clock_to_store(&mut store);
+        run_clock_and_random(&mut store, &linker)
+    };
+    let output = run(42)?;
+    assert_eq!(output, run(42)?);
+    assert_ne!(output[..16], run(43)?[..16]);
+    Ok(())
+}
+
+#[test]
+fn deterministic_preview2_ctx_traps_on_sockets() {
+    use wasmtime_wasi::preview2::bindings::sockets::{
+        instance_network, network::IpAddressFamily, tcp_create_socket, udp_create_socket,
+    };
+
+    let mut cx = preview2_ctx(0);
+    assert!(instance_network::Host::instance_network(&mut cx).is_err());
+    let err = tcp_create_socket::Host::create_tcp_socket(&mut cx, IpAddressFamily::Ipv4)
+        .err()
+        .unwrap();
+    assert!(err.downcast().is_err());
+    let err = udp_create_socket::Host::create_udp_socket(&mut cx, IpAddressFamily::Ipv4)
+        .err()
+        .unwrap();
+    assert!(err.downcast().is_err());
+}
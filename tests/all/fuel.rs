@@ -31,24 +31,39 @@ fn run() -> Result<()> {
     let buf = ParseBuffer::new(&test)?;
     let mut wast = parser::parse::<FuelWast<'_>>(&buf)?;
     for (span, fuel, module) in wast.assertions.iter_mut() {
-        let consumed = fuel_consumed(&module.encode()?);
-        if consumed == *fuel {
-            continue;
+        let wasm = module.encode()?;
+        // Fuel consumption must not depend on the compiler.
+        for strategy in strategies() {
+            let consumed = fuel_consumed(strategy, &wasm);
+            if consumed == *fuel {
+                continue;
+            }
+            let (line, col) = span.linecol_in(&test);
+            panic!(
+                "tests/all/fuel.wast:{}:{} - expected {} fuel, found {} with {:?}",
+                line + 1,
+                col + 1,
+                fuel,
+                consumed,
+                strategy,
+            );
         }
-        let (line, col) = span.linecol_in(&test);
-        panic!(
-            "tests/all/fuel.wast:{}:{} - expected {} fuel, found {}",
-            line + 1,
-            col + 1,
-            fuel,
-            consumed
-        );
     }
     Ok(())
 }
 
-fn fuel_consumed(wasm: &[u8]) -> u64 {
+fn strategies() -> Vec<Strategy> {
+    let mut strategies = vec![Strategy::Cranelift];
+    // Currently Winch is only supported in x86_64.
+    if cfg!(target_arch = "x86_64") {
+        strategies.push(Strategy::Winch);
+    }
+    strategies
+}
+
+fn fuel_consumed(strategy: Strategy, wasm: &[u8]) -> u64 {
     let mut config = Config::new();
+    config.strategy(strategy);
     config.consume_fuel(true);
     let engine = Engine::new(&config).unwrap();
     let module = Module::new(&engine, wasm).unwrap();
@@ -257,3 +272,138 @@ fn get_fuel_clamps_at_zero() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cost_model_charges_bulk_memory_by_length() -> Result<()> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    config.fuel_cost_model(FuelCostModel {
+        bulk_memory_bytes_per_fuel: 1024,
+        ..FuelCostModel::default()
+    });
+    let engine = Engine::new(&config)?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (memory 32)
+                (func (export "fill") (param i32)
+                    (memory.fill (i32.const 0) (i32.const 0) (local.get 0)))
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let fill = instance.get_typed_func::<i32, ()>(&mut store, "fill")?;
+
+    let mut consumed = |len| -> Result<u64> {
+        store.set_fuel(u64::MAX)?;
+        fill.call(&mut store, len)?;
+        Ok(u64::MAX - store.get_fuel()?)
+    };
+    let small = consumed(0)?;
+    assert_eq!(consumed(1 << 20)?, small + 1024);
+
+    // Running out of fuel happens before the operation takes place.
+    store.set_fuel(100)?;
+    let trap = fill
+        .call(&mut store, 1 << 20)
+        .unwrap_err()
+        .downcast::<Trap>()?;
+    assert_eq!(trap, Trap::OutOfFuel);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cost_model_per_operator_class() -> Result<()> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    config.fuel_cost_model(FuelCostModel {
+        load: 10,
+        ..FuelCostModel::default()
+    });
+    let engine = Engine::new(&config)?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (memory 1)
+                (func (export "add") (result i32)
+                    (i32.add (i32.const 1) (i32.const 2)))
+                (func (export "load") (result i32)
+                    (i32.load (i32.const 0)))
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let add = instance.get_typed_func::<(), i32>(&mut store, "add")?;
+    let load = instance.get_typed_func::<(), i32>(&mut store, "load")?;
+
+    store.set_fuel(1000)?;
+    add.call(&mut store, ())?;
+    let add_cost = 1000 - store.get_fuel()?;
+    store.set_fuel(1000)?;
+    load.call(&mut store, ())?;
+    let load_cost = 1000 - store.get_fuel()?;
+    // `add` executes two constants and an add, `load` a constant and a load.
+    assert_eq!(load_cost, add_cost - 2 + 10);
+    Ok(())
+}
+
+#[test]
+fn cost_model_requires_cranelift() -> Result<()> {
+    let mut config = Config::new();
+    config.strategy(Strategy::Winch);
+    config.consume_fuel(true);
+    config.fuel_cost_model(FuelCostModel::default());
+    if cfg!(target_arch = "x86_64") {
+        Engine::new(&config)?;
+    }
+
+    config.fuel_cost_model(FuelCostModel {
+        load: 10,
+        ..FuelCostModel::default()
+    });
+    let err = Engine::new(&config).err().unwrap();
+    assert!(err.to_string().contains("fuel cost model"), "{err}");
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn host_function_fuel_cost() -> Result<()> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config)?;
+    let mut linker = Linker::new(&engine);
+    linker.func_wrap_with_fuel("host", "f", 100, || {})?;
+    linker.func_new_with_fuel("host", "g", FuncType::new([], []), 100, |_, _, _| Ok(()))?;
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (import "host" "f" (func $f))
+                (import "host" "g" (func $g))
+                (func (export "f") call $f)
+                (func (export "g") call $g)
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = linker.instantiate(&mut store, &module)?;
+    for name in ["f", "g"] {
+        let func = instance.get_typed_func::<(), ()>(&mut store, name)?;
+        store.set_fuel(1000)?;
+        func.call(&mut store, ())?;
+        let consumed = 1000 - store.get_fuel()?;
+        assert!(consumed > 100 && consumed < 110, "{name}: {consumed}");
+
+        store.set_fuel(50)?;
+        let trap = func.call(&mut store, ()).unwrap_err().downcast::<Trap>()?;
+        assert_eq!(trap, Trap::OutOfFuel);
+    }
+    Ok(())
+}
@@ -0,0 +1,286 @@
+#![cfg(not(miri))]
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wasmtime::component::{Component, Linker};
+use wasmtime::*;
+
+/// A component which calls its `sample` import, where the host takes a sample
+/// of the guest's stack, from within its `run` export.
+const COMPONENT: &str = r#"
+    (component
+        (import "sample" (func $sample))
+        (core func $sample_lowered (canon lower (func $sample)))
+        (core module $m
+            (import "" "sample" (func $sample))
+            (func $work
+                call $sample)
+            (func $run (export "run")
+                call $work
+                call $work)
+        )
+        (core instance $i (instantiate $m
+            (with "" (instance (export "sample" (func $sample_lowered))))
+        ))
+        (func (export "run") (canon lift (core func $i "run")))
+    )
+"#;
+
+type SharedProfiler = (Arc<Mutex<GuestProfiler>>, GuestProfilerThread);
+
+#[test]
+fn component_profile_with_threads() -> Result<()> {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let component = Component::new(&engine, COMPONENT)?;
+    let mut linker = Linker::<SharedProfiler>::new(&engine);
+    linker.root().func_wrap(
+        "sample",
+        |store: StoreContextMut<'_, SharedProfiler>, ()| {
+            let (profiler, thread) = store.data().clone();
+            profiler.lock().unwrap().sample_thread(thread, &store);
+            Ok(())
+        },
+    )?;
+
+    let profiler = Arc::new(Mutex::new(GuestProfiler::new_component(
+        "component",
+        Duration::from_millis(10),
+        component.clone(),
+        vec![],
+    )));
+
+    let handles = ["first", "second"]
+        .into_iter()
+        .map(|name| {
+            let thread = profiler.lock().unwrap().add_thread(name);
+            let profiler = profiler.clone();
+            let engine = engine.clone();
+            let linker = linker.clone();
+            let component = component.clone();
+            std::thread::spawn(move || -> Result<()> {
+                let mut store = Store::new(&engine, (profiler.clone(), thread));
+                let instance = linker.instantiate(&mut store, &component)?;
+                let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+                for _ in 0..3 {
+                    run.call(&mut store, ())?;
+                    run.post_return(&mut store)?;
+                }
+                profiler.lock().unwrap().finish_thread(thread);
+                Ok(())
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap()?;
+    }
+
+    let profiler = Arc::try_unwrap(profiler).unwrap().into_inner().unwrap();
+    let mut output = Vec::new();
+    profiler.finish(&mut output)?;
+    let profile: serde_json::Value = serde_json::from_slice(&output)?;
+
+    // Both threads appear next to the main one, each with a sample for
+    // every call of `$work`.
+    let threads = profile["threads"].as_array().unwrap();
+    assert_eq!(threads.len(), 3);
+    for name in ["first", "second"] {
+        let thread = threads
+            .iter()
+            .find(|thread| thread["name"] == name)
+            .unwrap_or_else(|| panic!("no thread named {name}: {threads:?}"));
+        assert_eq!(thread["samples"]["length"], 6);
+    }
+
+    // The component's module is registered as a library of the profile.
+    let libs = profile["libs"].as_array().unwrap();
+    assert_eq!(libs.len(), 1);
+    assert_eq!(libs[0]["name"], "component");
+    Ok(())
+}
+
+/// Profiles a module whose `run` export calls its `sample` import, where the
+/// host takes a sample, twice from `$work` and once directly. `run` is
+/// called once for the main thread and once for a thread named `other`.
+fn profile_module(format: GuestProfileFormat) -> Result<Vec<u8>> {
+    let engine = Engine::default();
+    let module = Module::new(
+        &engine,
+        r#"
+            (module $m
+                (import "" "sample" (func $sample))
+                (func $work
+                    call $sample)
+                (func $run (export "run")
+                    call $work
+                    call $work
+                    call $sample)
+            )
+        "#,
+    )?;
+    let mut profiler = GuestProfiler::new(
+        "main",
+        Duration::from_millis(10),
+        vec![("m".to_string(), module.clone())],
+    );
+    let other = profiler.add_thread("other");
+    let profiler = Arc::new(Mutex::new(profiler));
+    let mut store = Store::new(&engine, (profiler.clone(), None));
+    let sample = Func::wrap(
+        &mut store,
+        |caller: Caller<'_, (Arc<Mutex<GuestProfiler>>, Option<GuestProfilerThread>)>| {
+            let (profiler, thread) = caller.data().clone();
+            let mut profiler = profiler.lock().unwrap();
+            match thread {
+                Some(thread) => profiler.sample_thread(thread, &caller),
+                None => profiler.sample(&caller),
+            }
+        },
+    );
+    let instance = Instance::new(&mut store, &module, &[sample.into()])?;
+    let run = instance.get_typed_func::<(), ()>(&mut store, "run")?;
+    run.call(&mut store, ())?;
+    store.data_mut().1 = Some(other);
+    run.call(&mut store, ())?;
+    drop(store);
+
+    let profiler = Arc::try_unwrap(profiler).unwrap().into_inner().unwrap();
+    let mut output = Vec::new();
+    profiler.finish_with_format(format, &mut output)?;
+    Ok(output)
+}
+
+#[test]
+fn collapsed_profile() -> Result<()> {
+    let output = String::from_utf8(profile_module(GuestProfileFormat::Collapsed)?)?;
+    let stacks = output
+        .lines()
+        .map(|line| {
+            let (stack, count) = line.rsplit_once(' ').unwrap();
+            (stack, count.parse::<u32>().unwrap())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stacks,
+        [
+            ("main;run", 1),
+            ("main;run;work", 2),
+            ("other;run", 1),
+            ("other;run;work", 2),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn pprof_profile() -> Result<()> {
+    let output = profile_module(GuestProfileFormat::Pprof)?;
+    let profile = protobuf_fields(&output);
+    let strings = profile
+        .iter()
+        .filter(|(field, _)| *field == 6)
+        .map(|(_, value)| String::from_utf8(value.bytes().to_vec()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(strings[0], "");
+
+    // Samples are `(locations, values, [(key, value)])` with string indices
+    // for labels.
+    let mut samples = Vec::new();
+    for (_, sample) in profile.iter().filter(|(field, _)| *field == 2) {
+        let mut locations = Vec::new();
+        let mut values = Vec::new();
+        let mut labels = Vec::new();
+        for (field, value) in protobuf_fields(sample.bytes()) {
+            match field {
+                1 => locations = packed_varints(value.bytes()),
+                2 => values = packed_varints(value.bytes()),
+                3 => {
+                    let label = protobuf_fields(value.bytes());
+                    let string = |i: usize| strings[label[i].1.int() as usize].clone();
+                    labels.push((string(0), string(1)));
+                }
+                _ => {}
+            }
+        }
+        samples.push((locations.len(), values[0], labels));
+    }
+    samples.sort();
+    let thread = |name: &str| vec![("thread".to_string(), name.to_string())];
+    assert_eq!(
+        samples,
+        [
+            (1, 1, thread("main")),
+            (1, 1, thread("other")),
+            (2, 2, thread("main")),
+            (2, 2, thread("other")),
+        ]
+    );
+    assert!(strings.iter().any(|s| s == "run"), "{strings:?}");
+    assert!(strings.iter().any(|s| s == "work"), "{strings:?}");
+    Ok(())
+}
+
+enum ProtobufValue<'a> {
+    Int(u64),
+    Bytes(&'a [u8]),
+}
+
+impl ProtobufValue<'_> {
+    fn int(&self) -> u64 {
+        match self {
+            ProtobufValue::Int(i) => *i,
+            ProtobufValue::Bytes(_) => panic!("not an integer"),
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self {
+            ProtobufValue::Bytes(b) => b,
+            ProtobufValue::Int(_) => panic!("not a length-delimited field"),
+        }
+    }
+}
+
+fn varint(data: &mut &[u8]) -> u64 {
+    let mut value = 0;
+    for shift in (0..).step_by(7) {
+        let byte = data[0];
+        *data = &data[1..];
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    value
+}
+
+/// Decodes the fields of a protobuf message, which only uses varint and
+/// length-delimited fields in pprof.
+fn protobuf_fields(mut data: &[u8]) -> Vec<(u64, ProtobufValue<'_>)> {
+    let mut fields = Vec::new();
+    while !data.is_empty() {
+        let key = varint(&mut data);
+        let value = match key & 7 {
+            0 => ProtobufValue::Int(varint(&mut data)),
+            2 => {
+                let len = varint(&mut data) as usize;
+                let (bytes, rest) = data.split_at(len);
+                data = rest;
+                ProtobufValue::Bytes(bytes)
+            }
+            ty => panic!("unexpected wire type {ty}"),
+        };
+        fields.push((key >> 3, value));
+    }
+    fields
+}
+
+fn packed_varints(mut data: &[u8]) -> Vec<u64> {
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        values.push(varint(&mut data));
+    }
+    values
+}
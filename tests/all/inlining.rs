@@ -0,0 +1,200 @@
+#![cfg(not(miri))]
+
+use anyhow::Result;
+use wasmtime::*;
+
+const MODULE: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (global $g (mut i32) (i32.const 0))
+
+        (func $get (param i32) (result i32)
+            local.get 0
+            i32.load)
+        (func $set (param i32 i32)
+            local.get 0
+            local.get 1
+            i32.store)
+        (func $add (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add)
+        (func $swap (param i32 i32) (result i32 i32)
+            local.get 1
+            local.get 0)
+        (func $pick (param i32) (result i32)
+            block
+                block
+                    block
+                        local.get 0
+                        br_table 0 1 2
+                    end
+                    i32.const 10
+                    return
+                end
+                i32.const 20
+                return
+            end
+            i32.const 30)
+        (func $bump
+            global.get $g
+            i32.const 1
+            i32.add
+            global.set $g)
+        (func $sum (param i32) (result i32) (local i32)
+            loop
+                local.get 1
+                local.get 0
+                i32.add
+                local.set 1
+                local.get 0
+                i32.const 1
+                i32.sub
+                local.tee 0
+                br_if 0
+            end
+            local.get 1)
+        (func $trap (param i32)
+            local.get 0
+            if
+                unreachable
+            end)
+
+        (func (export "run") (param i32) (result i32)
+            i32.const 8
+            local.get 0
+            call $set
+            i32.const 8
+            call $get
+            i32.const 2
+            call $add
+            i32.const 3
+            call $swap
+            i32.sub
+            call $bump
+            call $bump
+            global.get $g
+            i32.add
+            local.get 0
+            i32.const 3
+            i32.rem_u
+            call $pick
+            i32.add
+            i32.const 4
+            call $sum
+            i32.add)
+
+        (func (export "maybe_trap") (param i32)
+            local.get 0
+            call $trap)
+    )
+"#;
+
+fn engine(inlining: bool, configure: impl FnOnce(&mut Config)) -> Result<Engine> {
+    let mut config = Config::new();
+    config.cranelift_inlining(inlining);
+    config.cranelift_opt_level(OptLevel::Speed);
+    configure(&mut config);
+    Engine::new(&config)
+}
+
+fn run(engine: &Engine, arg: i32) -> Result<i32> {
+    let module = Module::new(engine, MODULE)?;
+    let mut store = Store::new(engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+    run.call(&mut store, arg)
+}
+
+#[test]
+fn inlined_calls_compute_the_same_results() -> Result<()> {
+    let inlined = engine(true, |_| {})?;
+    let not_inlined = engine(false, |_| {})?;
+    for arg in 0..6 {
+        assert_eq!(run(&inlined, arg)?, run(&not_inlined, arg)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn traps_in_inlined_calls() -> Result<()> {
+    let engine = engine(true, |_| {})?;
+    let module = Module::new(&engine, MODULE)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let maybe_trap = instance.get_typed_func::<i32, ()>(&mut store, "maybe_trap")?;
+
+    maybe_trap.call(&mut store, 0)?;
+    let err = maybe_trap.call(&mut store, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<Trap>()?,
+        Trap::UnreachableCodeReached,
+        "inlined traps keep their trap code"
+    );
+    Ok(())
+}
+
+#[test]
+fn inlining_preserves_fuel_consumption() -> Result<()> {
+    let consumed = |inlining: bool| -> Result<u64> {
+        let engine = engine(inlining, |config| {
+            config.consume_fuel(true);
+        })?;
+        let module = Module::new(&engine, MODULE)?;
+        let mut store = Store::new(&engine, ());
+        store.set_fuel(10_000)?;
+        let instance = Instance::new(&mut store, &module, &[])?;
+        let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+        run.call(&mut store, 5)?;
+        Ok(10_000 - store.get_fuel()?)
+    };
+    assert_eq!(consumed(true)?, consumed(false)?);
+    Ok(())
+}
+
+#[test]
+fn inlining_with_epoch_interruption() -> Result<()> {
+    let engine = engine(true, |config| {
+        config.epoch_interruption(true);
+    })?;
+    let module = Module::new(&engine, MODULE)?;
+    let mut store = Store::new(&engine, ());
+    store.set_epoch_deadline(1);
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32>(&mut store, "run")?;
+    engine.increment_epoch();
+    let err = run.call(&mut store, 5).unwrap_err();
+    assert_eq!(err.downcast::<Trap>()?, Trap::Interrupt);
+    Ok(())
+}
+
+#[test]
+fn inlined_callees_disappear_from_clif() -> Result<()> {
+    // Returns the CLIF of the exported `run` function, which is the ninth
+    // function in `MODULE`.
+    let run_clif = |inlining: bool, max_callee_size: u32| -> Result<String> {
+        let dir = tempfile::tempdir()?;
+        let engine = engine(inlining, |config| {
+            config.inlining_max_callee_size(max_callee_size);
+            config.emit_clif(dir.path());
+        })?;
+        Module::new(&engine, MODULE)?;
+        Ok(std::fs::read_to_string(
+            dir.path().join("wasm_func_8.clif"),
+        )?)
+    };
+
+    let clif = run_clif(true, 64)?;
+    assert!(!clif.contains("call fn"), "all calls are inlined:\n{clif}");
+    // The bodies of `$get` and `$set` now appear in `run` itself.
+    assert!(clif.contains("load.i32"), "callee body is inlined:\n{clif}");
+    assert!(clif.contains("store"), "callee body is inlined:\n{clif}");
+
+    // Every callee is larger than a single byte.
+    let clif = run_clif(true, 1)?;
+    assert_eq!(clif.matches("call fn").count(), 8, "{clif}");
+
+    let clif = run_clif(false, 64)?;
+    assert_eq!(clif.matches("call fn").count(), 8, "{clif}");
+    Ok(())
+}
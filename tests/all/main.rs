@@ -1,12 +1,15 @@
 #![cfg_attr(miri, allow(dead_code, unused_imports))]
 
 mod async_functions;
+mod breakpoints;
 mod call_hook;
 mod cli_tests;
 mod code_too_large;
 mod component_model;
 mod coredump;
+mod coverage;
 mod debug;
+mod deterministic;
 mod epoch_interruption;
 mod externals;
 mod fuel;
@@ -14,10 +17,12 @@ mod func;
 mod funcref;
 mod gc;
 mod globals;
+mod guest_profiler;
 mod host_funcs;
 mod iloop;
 mod import_calling_export;
 mod import_indexes;
+mod inlining;
 mod instance;
 mod invoke_func_via_table;
 mod limits;
@@ -27,6 +32,7 @@ mod memory_creator;
 mod module;
 mod module_serialize;
 mod name;
+mod perf_event_profiler;
 mod pooling_allocator;
 mod relocs;
 mod stack_creator;
@@ -38,8 +44,8 @@ mod traps;
 mod wait_notify;
 mod wasi_testsuite;
 mod wast;
-// Currently Winch is only supported in x86_64.
-#[cfg(all(target_arch = "x86_64"))]
+// Currently Winch is only supported in x86_64 and aarch64.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 mod winch;
 
 /// A helper to compile a module in a new store with reference types enabled.
@@ -660,3 +660,80 @@ fn init_with_negative_segment() -> Result<()> {
     Instance::new(&mut store, &module, &[])?;
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bounds_check_elimination() -> Result<()> {
+    let mut config = Config::new();
+    config.wasm_memory64(true);
+    config.static_memory_maximum_size(0);
+    config.dynamic_memory_guard_size(0);
+    config.cranelift_opt_level(OptLevel::Speed);
+    config.cranelift_bounds_check_elimination(true);
+    config.cranelift_debug_verifier(true);
+    let engine = Engine::new(&config)?;
+
+    for index_ty in ["i32", "i64"] {
+        let memory = if index_ty == "i64" { "i64 1" } else { "1" };
+        let module = Module::new(
+            &engine,
+            &format!(
+                r#"
+                    (module
+                        (memory (export "memory") {memory})
+
+                        ;; Several accesses off the same index: only the widest
+                        ;; check is needed.
+                        (func (export "sum") (param {index_ty}) (result i32)
+                            local.get 0
+                            i32.load8_u offset=3
+                            local.get 0
+                            i32.load8_u offset=1
+                            i32.add
+                            local.get 0
+                            i32.load8_u offset=2
+                            i32.add)
+
+                        ;; Masked indices below the minimum size never trap.
+                        (func (export "masked") (param {index_ty}) (result i32)
+                            local.get 0
+                            {index_ty}.const 0xfff
+                            {index_ty}.and
+                            i32.load)
+                    )
+                "#
+            ),
+        )?;
+        let mut store = Store::new(&engine, ());
+        let instance = Instance::new(&mut store, &module, &[])?;
+        let memory = instance.get_memory(&mut store, "memory").unwrap();
+        for (i, byte) in memory.data_mut(&mut store).iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let sum = instance.get_func(&mut store, "sum").unwrap();
+        let masked = instance.get_func(&mut store, "masked").unwrap();
+        let arg = |n: u64| match index_ty {
+            "i32" => Val::I32(n as i32),
+            _ => Val::I64(n as i64),
+        };
+        let call = |store: &mut Store<()>, func: Func, n: u64| -> Result<i32> {
+            let mut results = [Val::I32(0)];
+            func.call(store, &[arg(n)], &mut results)?;
+            Ok(results[0].unwrap_i32())
+        };
+
+        assert_eq!(call(&mut store, sum, 0)?, 1 + 2 + 3);
+        assert_eq!(call(&mut store, sum, 65532)?, 253 + 254 + 255);
+        assert!(call(&mut store, sum, 65533).is_err());
+        assert!(call(&mut store, sum, u32::MAX.into()).is_err());
+        assert_eq!(
+            call(&mut store, masked, 0x1_0004)?,
+            i32::from_le_bytes([4, 5, 6, 7])
+        );
+
+        memory.grow(&mut store, 1)?;
+        assert!(call(&mut store, sum, 65533).is_ok());
+        assert!(call(&mut store, sum, 2 * 65536 - 3).is_err());
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+#![cfg(all(target_os = "linux", not(miri)))]
+
+use anyhow::Result;
+use wasmtime::*;
+
+/// Opens a profiler of `events`, or returns `None` if this system can't
+/// count them.
+fn profiler(events: &[PerfEvent]) -> Result<Option<PerfEventProfiler>> {
+    match PerfEventProfiler::new(events, 10_000) {
+        Ok(profiler) => Ok(Some(profiler)),
+        Err(e) => {
+            // Hardware counters are missing in many VMs (`ENOENT`), may be
+            // restricted by `perf_event_paranoid` (`EACCES`) and are often
+            // blocked in containers (`EPERM`).
+            let errno = e
+                .root_cause()
+                .downcast_ref::<std::io::Error>()
+                .and_then(|e| e.raw_os_error());
+            match errno {
+                Some(libc::ENOENT | libc::EACCES | libc::EPERM) => {
+                    eprintln!("skipping test, performance counters are unavailable: {e:#}");
+                    Ok(None)
+                }
+                _ => Err(e),
+            }
+        }
+    }
+}
+
+#[test]
+fn samples_wasm_functions() -> Result<()> {
+    let mut profiler = match profiler(&[PerfEvent::Instructions])? {
+        Some(profiler) => profiler,
+        None => return Ok(()),
+    };
+
+    let engine = Engine::default();
+    let module = Module::new(
+        &engine,
+        r#"
+            (module $m
+                (func $hot (export "hot") (param i32)
+                    (loop $l
+                        (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                        (br_if $l (local.get 0)))))
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let hot = instance.get_typed_func::<i32, ()>(&mut store, "hot")?;
+    for _ in 0..10 {
+        hot.call(&mut store, 1_000_000)?;
+        profiler.collect(&store);
+    }
+
+    let mut json = Vec::new();
+    profiler.finish(PerfEventFormat::Firefox, &mut json)?;
+    let json: serde_json::Value = serde_json::from_slice(&json)?;
+    let thread = &json["threads"][0];
+    assert_eq!(thread["name"], "instructions");
+    assert!(
+        thread["samples"]["length"].as_u64().unwrap() > 0,
+        "{thread}"
+    );
+    let strings = thread["stringArray"].as_array().unwrap();
+    assert!(strings.iter().any(|s| s == "m!hot"), "{strings:?}");
+    Ok(())
+}
+
+#[test]
+fn writes_pprof() -> Result<()> {
+    let profiler = match profiler(&[PerfEvent::Cycles, PerfEvent::Instructions])? {
+        Some(profiler) => profiler,
+        None => return Ok(()),
+    };
+    let mut pprof = Vec::new();
+    profiler.finish(PerfEventFormat::Pprof, &mut pprof)?;
+    // The sample types are interned in the profile's string table.
+    let contains = |s: &[u8]| pprof.windows(s.len()).any(|w| w == s);
+    assert!(contains(b"cycles"));
+    assert!(contains(b"instructions"));
+    Ok(())
+}
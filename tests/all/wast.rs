@@ -54,6 +54,9 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
 
     if is_cranelift {
         cfg.cranelift_debug_verifier(true);
+    } else if cfg!(target_arch = "aarch64") {
+        // Winch doesn't support SIMD on AArch64 yet.
+        cfg.wasm_simd(false);
     }
 
     cfg.wasm_component_model(feature_found(wast, "component-model"));
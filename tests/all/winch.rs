@@ -60,6 +60,17 @@ const MIXED: &'static str = r#"
       (export "call_id_float" (func $call_id_float)))
 "#;
 
+/// Returns a configuration compiling with Winch, which doesn't support SIMD
+/// on AArch64 yet.
+fn winch_config() -> Config {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    if cfg!(target_arch = "aarch64") {
+        c.wasm_simd(false).wasm_relaxed_simd(false);
+    }
+    c
+}
+
 fn add_fn(store: impl AsContextMut) -> Func {
     Func::wrap(store, |a: i32, b: i32| a + b)
 }
@@ -74,8 +85,7 @@ fn id_float(store: impl AsContextMut) -> Func {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn array_to_wasm() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let mut store = Store::new(&engine, ());
     let module = Module::new(&engine, MODULE)?;
@@ -107,8 +117,7 @@ fn array_to_wasm() -> Result<()> {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn native_to_wasm() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let mut store = Store::new(&engine, ());
     let module = Module::new(&engine, MODULE)?;
@@ -130,8 +139,7 @@ fn native_to_wasm() -> Result<()> {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn wasm_to_native() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let mut store = Store::new(&engine, ());
 
@@ -150,8 +158,7 @@ fn wasm_to_native() -> Result<()> {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn mixed_roundtrip() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let mut store = Store::new(&engine, ());
 
@@ -190,8 +197,7 @@ fn mixed_roundtrip() -> Result<()> {
 // FIXME The following two tests are also temporarily ignored on Windows, since
 // we are not emitting the require unwind information yet.
 fn native_to_wasm_trap() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let wat = r#"
         (module
@@ -217,8 +223,7 @@ fn native_to_wasm_trap() -> Result<()> {
 #[cfg_attr(miri, ignore)]
 #[cfg_attr(windows, ignore)]
 fn wasm_to_native_trap() -> Result<()> {
-    let mut c = Config::new();
-    c.strategy(Strategy::Winch);
+    let c = winch_config();
     let engine = Engine::new(&c)?;
     let wat = r#"
         (module
@@ -239,3 +244,272 @@ fn wasm_to_native_trap() -> Result<()> {
 
     Ok(())
 }
+
+const FUEL: &'static str = r#"
+    (module
+      (func $loop (param $n i32) (result i32)
+        (local $sum i32)
+        (block $done
+          (loop $loop
+            (br_if $done (i32.eqz (local.get $n)))
+            (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+            (br $loop)))
+        (local.get $sum))
+
+      (func $call_loop (param i32) (result i32)
+        (call $loop (local.get 0)))
+
+      (export "loop" (func $loop))
+      (export "call_loop" (func $call_loop)))
+"#;
+
+fn fuel_consumed(strategy: Strategy, name: &str, n: i32) -> Result<u64> {
+    let mut c = winch_config();
+    c.strategy(strategy);
+    c.consume_fuel(true);
+    let engine = Engine::new(&c)?;
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(u64::MAX)?;
+    let module = Module::new(&engine, FUEL)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<i32, i32>(&mut store, name)?;
+    assert_eq!(f.call(&mut store, n)?, (1..=n).sum::<i32>());
+    Ok(u64::MAX - store.get_fuel()?)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fuel_consumption_matches_cranelift() -> Result<()> {
+    for name in ["loop", "call_loop"] {
+        for n in [0, 1, 10, 100] {
+            assert_eq!(
+                fuel_consumed(Strategy::Winch, name, n)?,
+                fuel_consumed(Strategy::Cranelift, name, n)?,
+                "fuel consumed by `{name}({n})`"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg_attr(windows, ignore)]
+fn out_of_fuel() -> Result<()> {
+    let mut c = winch_config();
+    c.consume_fuel(true);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func $spin
+            (loop $l (br $l)))
+
+          (export "spin" (func $spin)))
+    "#;
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(10_000)?;
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "spin")?;
+    let err = f.call(&mut store, ()).unwrap_err();
+
+    assert_eq!(err.downcast::<Trap>()?, Trap::OutOfFuel);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg_attr(windows, ignore)]
+fn epoch_interruption() -> Result<()> {
+    let mut c = winch_config();
+    c.epoch_interruption(true);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func $spin (param $n i32)
+            (loop $l
+              (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+              (br_if $l (local.get $n))))
+
+          (export "spin" (func $spin)))
+    "#;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let f = instance.get_typed_func::<i32, ()>(&mut store, "spin")?;
+
+    // The deadline hasn't been reached, the function runs to completion.
+    store.set_epoch_deadline(1);
+    f.call(&mut store, 1000)?;
+
+    // Once the epoch reaches the deadline, the function is interrupted.
+    engine.increment_epoch();
+    let err = f.call(&mut store, 1000).unwrap_err();
+    assert_eq!(err.downcast::<Trap>()?, Trap::Interrupt);
+
+    // Yielding on deadline lets the function make progress.
+    store.epoch_deadline_callback(|_| Ok(UpdateDeadline::Continue(1)));
+    f.call(&mut store, 1000)?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_arch = "x86_64")]
+fn v128_arithmetic() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func $i32x4_add (param v128 v128) (result v128)
+            (i32x4.add (local.get 0) (local.get 1)))
+
+          (func $i64x2_sub_const (param v128) (result v128)
+            (i64x2.sub (local.get 0) (v128.const i64x2 1 2)))
+
+          (func $f64x2_mul (param v128 v128) (result v128)
+            (f64x2.mul (local.get 0) (local.get 1)))
+
+          (func $andnot_not (param v128 v128) (result v128)
+            (v128.not (v128.andnot (local.get 0) (local.get 1))))
+
+          (export "i32x4_add" (func $i32x4_add))
+          (export "i64x2_sub_const" (func $i64x2_sub_const))
+          (export "f64x2_mul" (func $f64x2_mul))
+          (export "andnot_not" (func $andnot_not)))
+    "#;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+
+    let i32x4 = |lanes: [u32; 4]| {
+        V128::from(
+            lanes
+                .iter()
+                .rev()
+                .fold(0u128, |acc, l| (acc << 32) | u128::from(*l)),
+        )
+    };
+    let i64x2 = |lo: u64, hi: u64| V128::from((u128::from(hi) << 64) | u128::from(lo));
+    let f64x2 = |lo: f64, hi: f64| i64x2(lo.to_bits(), hi.to_bits());
+
+    let f = instance.get_typed_func::<(V128, V128), V128>(&mut store, "i32x4_add")?;
+    let result = f.call(
+        &mut store,
+        (i32x4([1, 2, 3, u32::MAX]), i32x4([5, 6, 7, 1])),
+    )?;
+    assert_eq!(result, i32x4([6, 8, 10, 0]));
+
+    let f = instance.get_typed_func::<V128, V128>(&mut store, "i64x2_sub_const")?;
+    let result = f.call(&mut store, i64x2(10, 1))?;
+    assert_eq!(result, i64x2(9, u64::MAX));
+
+    let f = instance.get_typed_func::<(V128, V128), V128>(&mut store, "f64x2_mul")?;
+    let result = f.call(&mut store, (f64x2(1.5, -2.0), f64x2(2.0, 4.0)))?;
+    assert_eq!(result, f64x2(3.0, -8.0));
+
+    let f = instance.get_typed_func::<(V128, V128), V128>(&mut store, "andnot_not")?;
+    let result = f.call(&mut store, (V128::from(0b1100), V128::from(0b1010)))?;
+    assert_eq!(result, V128::from(!0b0100u128));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_arch = "x86_64")]
+fn v128_lanes_compares_and_shifts() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func $splat_extract (param i32) (result i32)
+            (i16x8.extract_lane_s 3 (i16x8.splat (local.get 0))))
+
+          (func $replace_lane (param v128 f64) (result v128)
+            (f64x2.replace_lane 1 (local.get 0) (local.get 1)))
+
+          (func $lt_u (param v128 v128) (result v128)
+            (i32x4.lt_u (local.get 0) (local.get 1)))
+
+          (func $shr_s (param v128 i32) (result v128)
+            (i8x16.shr_s (local.get 0) (local.get 1)))
+
+          (func $i64x2_mul (param v128 v128) (result v128)
+            (i64x2.mul (local.get 0) (local.get 1)))
+
+          (export "splat_extract" (func $splat_extract))
+          (export "replace_lane" (func $replace_lane))
+          (export "lt_u" (func $lt_u))
+          (export "shr_s" (func $shr_s))
+          (export "i64x2_mul" (func $i64x2_mul)))
+    "#;
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+
+    let i32x4 = |lanes: [u32; 4]| {
+        V128::from(
+            lanes
+                .iter()
+                .rev()
+                .fold(0u128, |acc, l| (acc << 32) | u128::from(*l)),
+        )
+    };
+    let i64x2 = |lo: u64, hi: u64| V128::from((u128::from(hi) << 64) | u128::from(lo));
+
+    let f = instance.get_typed_func::<i32, i32>(&mut store, "splat_extract")?;
+    assert_eq!(f.call(&mut store, 0x1_8000)?, -0x8000);
+
+    let f = instance.get_typed_func::<(V128, f64), V128>(&mut store, "replace_lane")?;
+    let result = f.call(&mut store, (i64x2(7, 8), 2.5))?;
+    assert_eq!(result, i64x2(7, 2.5f64.to_bits()));
+
+    let f = instance.get_typed_func::<(V128, V128), V128>(&mut store, "lt_u")?;
+    let result = f.call(
+        &mut store,
+        (i32x4([1, u32::MAX, 5, 0]), i32x4([2, 1, 5, u32::MAX])),
+    )?;
+    assert_eq!(result, i32x4([u32::MAX, 0, 0, u32::MAX]));
+
+    let f = instance.get_typed_func::<(V128, i32), V128>(&mut store, "shr_s")?;
+    let result = f.call(&mut store, (V128::from(0x80_40), 9))?;
+    assert_eq!(result, V128::from(0xc0_20));
+
+    let f = instance.get_typed_func::<(V128, V128), V128>(&mut store, "i64x2_mul")?;
+    let result = f.call(&mut store, (i64x2(u64::MAX, 1 << 32), i64x2(3, 1 << 33)))?;
+    assert_eq!(result, i64x2(u64::MAX - 2, 0));
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+#[cfg(target_arch = "x86_64")]
+fn unsupported_operator_is_a_compile_error() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    let engine = Engine::new(&c)?;
+    let wat = r#"
+        (module
+          (func (param v128) (result v128)
+            (i8x16.popcnt (local.get 0))))
+    "#;
+    let err = Module::new(&engine, wat).err().unwrap();
+    assert!(
+        format!("{err:?}").contains("Unsupported Wasm operator: I8x16Popcnt"),
+        "{err:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn simd_rejected_on_aarch64() -> Result<()> {
+    let mut c = Config::new();
+    c.strategy(Strategy::Winch);
+    c.target("aarch64-unknown-linux-gnu")?;
+    let err = Engine::new(&c).err().unwrap();
+    assert!(err.to_string().contains("SIMD"), "{err}");
+    Ok(())
+}
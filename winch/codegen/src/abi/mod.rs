@@ -112,7 +112,7 @@ pub(crate) trait ABI {
                 heap_type: WasmHeapType::Func,
                 ..
             }) => Self::scratch_reg(),
-            WasmType::F32 | WasmType::F64 => Self::float_scratch_reg(),
+            WasmType::F32 | WasmType::F64 | WasmType::V128 => Self::float_scratch_reg(),
             _ => unimplemented!(),
         }
     }
@@ -127,6 +127,11 @@ pub(crate) trait ABI {
     /// the `VMContext`.
     fn vmctx_reg() -> Reg;
 
+    /// Returns true if the [`ABI::vmctx_reg`] is preserved across calls
+    /// to functions using the system calling convention, such as builtins.
+    /// Otherwise it must be restored after each call.
+    fn vmctx_reg_preserved() -> bool;
+
     /// Returns the callee-saved registers for the given
     /// calling convention.
     fn callee_saved_regs(call_conv: &CallingConvention) -> SmallVec<[(Reg, OperandSize); 18]>;
@@ -82,7 +82,8 @@ impl FnCall {
     /// 3. Spills the value stack.
     /// 4. Creates the stack space needed for the return area.
     /// 5. Emits the call.
-    /// 6. Cleans up the stack space.
+    /// 6. Restores the `VMContext` register, if the callee may clobber it.
+    /// 7. Cleans up the stack space.
     pub fn emit<M: MacroAssembler, P: PtrSize, R>(
         masm: &mut M,
         context: &mut CodeGenContext,
@@ -104,6 +105,11 @@ impl FnCall {
             kind
         });
 
+        if !<M::ABI as ABI>::vmctx_reg_preserved() {
+            let vmctx_addr = masm.local_address(&context.frame.vmctx_slot);
+            masm.load_ptr(vmctx_addr, <M::ABI as ABI>::vmctx_reg());
+        }
+
         match kind {
             CalleeKind::Indirect(r) => context.free_reg(r),
             _ => {}
@@ -74,7 +74,7 @@ impl<'a, 'builtins> CodeGenContext<'a, 'builtins> {
         use WasmType::*;
         match ty {
             I32 | I64 => self.reg_for_class(RegClass::Int, masm),
-            F32 | F64 => self.reg_for_class(RegClass::Float, masm),
+            F32 | F64 | V128 => self.reg_for_class(RegClass::Float, masm),
             Ref(rt) => match rt.heap_type {
                 WasmHeapType::Func => self.reg_for_class(RegClass::Int, masm),
                 ht => unimplemented!("Support for WasmHeapType: {ht}"),
@@ -182,6 +182,7 @@ impl<'a, 'builtins> CodeGenContext<'a, 'builtins> {
             Val::I64(v) => masm.store(RegImm::i64(v), addr, size),
             Val::F32(v) => masm.store(RegImm::f32(v.bits()), addr, size),
             Val::F64(v) => masm.store(RegImm::f64(v.bits()), addr, size),
+            Val::V128(v) => masm.store(RegImm::v128(v), addr, size),
             Val::Local(local) => {
                 let slot = self
                     .frame
@@ -209,6 +210,7 @@ impl<'a, 'builtins> CodeGenContext<'a, 'builtins> {
             Val::I64(imm) => masm.mov(RegImm::i64(*imm), dst, size),
             Val::F32(imm) => masm.mov(RegImm::f32(imm.bits()), dst, size),
             Val::F64(imm) => masm.mov(RegImm::f64(imm.bits()), dst, size),
+            Val::V128(imm) => masm.mov(RegImm::v128(*imm), dst, size),
             Val::Local(local) => {
                 let slot = self
                     .frame
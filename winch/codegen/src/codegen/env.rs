@@ -10,7 +10,7 @@ use std::collections::{
 use wasmparser::BlockType;
 use wasmtime_environ::{
     FuncIndex, GlobalIndex, ModuleTranslation, ModuleTypesBuilder, PtrSize, TableIndex, TablePlan,
-    TypeConvert, TypeIndex, VMOffsets, WasmFuncType, WasmHeapType, WasmType,
+    Tunables, TypeConvert, TypeIndex, VMOffsets, WasmFuncType, WasmHeapType, WasmType,
 };
 
 /// Table metadata.
@@ -93,6 +93,9 @@ pub struct FuncEnv<'a, 'translation: 'a, 'data: 'translation, P: PtrSize> {
     pub translation: &'translation ModuleTranslation<'data>,
     /// The module's function types.
     pub types: &'translation ModuleTypesBuilder,
+    /// Compilation tunables, used to determine if fuel metering or epoch
+    /// interruption instrumentation should be emitted.
+    pub tunables: &'translation Tunables,
     /// Track resolved table information.
     resolved_tables: HashMap<TableIndex, TableData>,
 }
@@ -109,11 +112,13 @@ impl<'a, 'translation, 'data, P: PtrSize> FuncEnv<'a, 'translation, 'data, P> {
         vmoffsets: &'a VMOffsets<P>,
         translation: &'translation ModuleTranslation<'data>,
         types: &'translation ModuleTypesBuilder,
+        tunables: &'translation Tunables,
     ) -> Self {
         Self {
             vmoffsets,
             translation,
             types,
+            tunables,
             resolved_tables: HashMap::new(),
         }
     }
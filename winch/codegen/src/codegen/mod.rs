@@ -42,6 +42,13 @@ where
     // NB The 64 is set arbitrarily, we can adjust it as
     // we see fit.
     pub control_frames: SmallVec<[ControlStackFrame; 64]>,
+
+    /// The amount of fuel consumed by the operators visited since the last
+    /// time the fuel counter in `VMRuntimeLimits` was updated.
+    fuel_consumed: i64,
+
+    /// The error found while lowering the operator being visited, if any.
+    lowering_error: Option<anyhow::Error>,
 }
 
 impl<'a, 'translation, 'data, M> CodeGen<'a, 'translation, 'data, M>
@@ -60,6 +67,20 @@ where
             masm,
             env,
             control_frames: Default::default(),
+            // Start with one unit of fuel consumed, like Cranelift, so that
+            // even empty functions consume some fuel.
+            fuel_consumed: 1,
+            lowering_error: None,
+        }
+    }
+
+    /// Records the result of lowering the operator being visited.
+    ///
+    /// The operator visitors can't return errors, so the error is reported
+    /// by [`Self::emit_body`] right after the operator has been visited.
+    pub fn handle_lowering_result(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.lowering_error = Some(e);
         }
     }
 
@@ -178,9 +199,17 @@ where
             }
         });
 
+        // Check for fuel exhaustion or epoch changes when entering the
+        // function, bounding the time between checks even in the absence of
+        // loops (e.g. in deep call trees).
+        self.emit_fuel_and_epoch_checks();
+
         while !body.eof() {
             let offset = body.original_position();
             body.visit_operator(&mut ValidateThenVisit(validator.visitor(offset), self))??;
+            if let Some(e) = self.lowering_error.take() {
+                return Err(e);
+            }
         }
         validator.finish(body.original_position())?;
         return Ok(());
@@ -197,8 +226,10 @@ where
                         // ignored except for If, Block, Loop, Else and End. These operators need
                         // to be observed in order to keep the control stack frames balanced and to
                         // determine if reachability should be restored.
-                        let visit_when_unreachable = visit_op_when_unreachable(Operator::$op $({ $($arg: $arg.clone()),* })?);
+                        let op = Operator::$op $({ $($arg: $arg.clone()),* })?;
+                        let visit_when_unreachable = visit_op_when_unreachable(&op);
                         if self.1.is_reachable() || visit_when_unreachable  {
+                            self.1.before_visit_op(&op);
                             Ok(self.1.$visit($($($arg),*)?))
                         } else {
                             Ok(U::Output::default())
@@ -208,7 +239,7 @@ where
             };
         }
 
-        fn visit_op_when_unreachable(op: Operator) -> bool {
+        fn visit_op_when_unreachable(op: &Operator) -> bool {
             use Operator::*;
             match op {
                 If { .. } | Block { .. } | Loop { .. } | Else | End => true,
@@ -230,10 +261,27 @@ where
             }
         }
 
+        /// Trait to emit code that must precede the lowering of every
+        /// operator, independently of the operator itself.
+        trait VisitorHooks {
+            /// Invoked right before visiting the given operator.
+            fn before_visit_op(&mut self, operator: &Operator);
+        }
+
+        impl<'a, 'translation, 'data, M: MacroAssembler> VisitorHooks
+            for CodeGen<'a, 'translation, 'data, M>
+        {
+            fn before_visit_op(&mut self, operator: &Operator) {
+                if self.env.tunables.consume_fuel {
+                    self.fuel_before_visit_op(operator);
+                }
+            }
+        }
+
         impl<'a, T, U> VisitOperator<'a> for ValidateThenVisit<'_, T, U>
         where
             T: VisitOperator<'a, Output = wasmparser::Result<()>>,
-            U: VisitOperator<'a> + ReachableState,
+            U: VisitOperator<'a> + ReachableState + VisitorHooks,
             U::Output: Default,
         {
             type Output = Result<U::Output>;
@@ -286,6 +334,196 @@ where
         self.context.free_reg(caller_id);
     }
 
+    /// Emits the fuel and epoch checks, if enabled. Invoked at function entry
+    /// and at each loop header.
+    ///
+    /// The value stack is expected to be fully spilled when calling this
+    /// function, given that the builtin calls emitted by the checks are only
+    /// conditionally executed.
+    pub fn emit_fuel_and_epoch_checks(&mut self) {
+        if self.env.tunables.consume_fuel {
+            self.emit_fuel_check();
+        }
+
+        if self.env.tunables.epoch_interruption {
+            self.emit_epoch_check();
+        }
+    }
+
+    /// Accounts for the fuel consumed by the given operator and, if the
+    /// operator may transfer control out of the current basic block, flushes
+    /// the accumulated fuel to `VMRuntimeLimits`. The costs are those of the
+    /// default `FuelCostModel`, so that fuel consumption matches Cranelift's
+    /// with that model; `Config::validate` rejects any other model when
+    /// compiling with Winch.
+    fn fuel_before_visit_op(&mut self, op: &Operator) {
+        if !self.context.reachable {
+            // The operator that made the code unreachable must have already
+            // flushed the accumulated fuel.
+            debug_assert_eq!(self.fuel_consumed, 0);
+            return;
+        }
+
+        self.fuel_consumed += match op {
+            Operator::Nop | Operator::Drop => 0,
+            Operator::Block { .. }
+            | Operator::Loop { .. }
+            | Operator::Unreachable
+            | Operator::Return
+            | Operator::Else
+            | Operator::End => 0,
+            _ => 1,
+        };
+
+        match op {
+            Operator::Unreachable
+            | Operator::Return
+            | Operator::CallIndirect { .. }
+            | Operator::Call { .. }
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. }
+            | Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. }
+            | Operator::End
+            | Operator::Else => self.emit_fuel_increment(),
+            _ => {}
+        }
+    }
+
+    /// Adds the fuel consumed so far to the fuel counter in
+    /// `VMRuntimeLimits`.
+    fn emit_fuel_increment(&mut self) {
+        let fuel_consumed = std::mem::take(&mut self.fuel_consumed);
+        if fuel_consumed == 0 {
+            return;
+        }
+
+        let limits = self.context.any_gpr(self.masm);
+        let fuel = self.context.any_gpr(self.masm);
+        self.emit_load_runtime_limits(limits);
+        let fuel_addr = self.masm.address_at_reg(
+            limits,
+            self.env
+                .vmoffsets
+                .ptr
+                .vmruntime_limits_fuel_consumed()
+                .into(),
+        );
+        self.masm.load(fuel_addr, fuel, OperandSize::S64);
+        self.masm
+            .add(fuel, fuel, RegImm::i64(fuel_consumed), OperandSize::S64);
+        self.masm.store(fuel.into(), fuel_addr, OperandSize::S64);
+        self.context.free_reg(fuel);
+        self.context.free_reg(limits);
+    }
+
+    /// Emits a call to the `out_of_gas` builtin if the fuel counter in
+    /// `VMRuntimeLimits` is not negative.
+    fn emit_fuel_check(&mut self) {
+        let fuel = self.context.any_gpr(self.masm);
+        self.emit_load_runtime_limits(fuel);
+        self.masm.load(
+            self.masm.address_at_reg(
+                fuel,
+                self.env
+                    .vmoffsets
+                    .ptr
+                    .vmruntime_limits_fuel_consumed()
+                    .into(),
+            ),
+            fuel,
+            OperandSize::S64,
+        );
+
+        // Fuel is stored as a negative amount which increases towards zero as
+        // it's consumed; skip the builtin call while it's negative.
+        let cont = self.masm.get_label();
+        self.masm.branch(
+            IntCmpKind::LtS,
+            RegImm::i64(0),
+            fuel,
+            cont,
+            OperandSize::S64,
+        );
+        self.context.free_reg(fuel);
+
+        let builtin = self.context.builtins.out_of_gas::<M::ABI, M::Ptr>();
+        self.emit_builtin_call_with_vmctx(builtin);
+        self.masm.bind(cont);
+    }
+
+    /// Emits a call to the `new_epoch` builtin if the current epoch has
+    /// reached the epoch deadline stored in `VMRuntimeLimits`.
+    fn emit_epoch_check(&mut self) {
+        let epoch = self.context.any_gpr(self.masm);
+        let deadline = self.context.any_gpr(self.masm);
+
+        self.masm.load_ptr(
+            self.masm
+                .address_at_vmctx(self.env.vmoffsets.vmctx_epoch_ptr()),
+            epoch,
+        );
+        self.masm
+            .load(self.masm.address_at_reg(epoch, 0), epoch, OperandSize::S64);
+        self.emit_load_runtime_limits(deadline);
+        self.masm.load(
+            self.masm.address_at_reg(
+                deadline,
+                self.env
+                    .vmoffsets
+                    .ptr
+                    .vmruntime_limits_epoch_deadline()
+                    .into(),
+            ),
+            deadline,
+            OperandSize::S64,
+        );
+
+        // Skip the builtin call if the current epoch is below the deadline.
+        let cont = self.masm.get_label();
+        self.masm.branch(
+            IntCmpKind::LtU,
+            deadline.into(),
+            epoch,
+            cont,
+            OperandSize::S64,
+        );
+        self.context.free_reg(epoch);
+        self.context.free_reg(deadline);
+
+        let builtin = self.context.builtins.new_epoch::<M::ABI, M::Ptr>();
+        self.emit_builtin_call_with_vmctx(builtin);
+        // The new deadline is written to `VMRuntimeLimits` by the builtin,
+        // so its result can be discarded.
+        let deadline = self.context.pop_to_reg(self.masm, None);
+        self.context.free_reg(deadline);
+        self.masm.bind(cont);
+    }
+
+    /// Loads the `VMRuntimeLimits` pointer into the given register.
+    fn emit_load_runtime_limits(&mut self, dst: Reg) {
+        self.masm.load_ptr(
+            self.masm
+                .address_at_vmctx(self.env.vmoffsets.vmctx_runtime_limits()),
+            dst,
+        );
+    }
+
+    /// Emits a call to a builtin function whose only parameter is the
+    /// `VMContext` pointer.
+    fn emit_builtin_call_with_vmctx(&mut self, builtin: BuiltinFunction) {
+        let ptr_type = self.env.ptr_type();
+        self.context
+            .stack
+            .push(TypedReg::new(ptr_type, <M::ABI as ABI>::vmctx_reg()).into());
+        FnCall::emit::<M, M::Ptr, _>(self.masm, &mut self.context, |_| {
+            Callee::Builtin(builtin.clone())
+        });
+    }
+
     /// Emit the usual function end instruction sequence.
     fn emit_end(&mut self) -> Result<()> {
         assert!(self.context.stack.len() == 0);
@@ -315,7 +553,7 @@ where
                     .expect("arg should be associated to a register");
 
                 match &ty {
-                    I32 | I64 | F32 | F64 => self.masm.store(src.into(), addr, ty.into()),
+                    I32 | I64 | F32 | F64 | V128 => self.masm.store(src.into(), addr, ty.into()),
                     Ref(rt) => match rt.heap_type {
                         WasmHeapType::Func => self.masm.store_ptr(src.into(), addr),
                         ht => unimplemented!("Support for WasmHeapType: {ht}"),
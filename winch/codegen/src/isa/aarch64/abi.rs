@@ -92,7 +92,7 @@ impl ABI for Aarch64ABI {
         returns: &[WasmType],
         call_conv: &CallingConvention,
     ) -> ABISig {
-        assert!(call_conv.is_apple_aarch64() || call_conv.is_default());
+        assert!(call_conv.is_apple_aarch64() || call_conv.is_systemv() || call_conv.is_default());
 
         let mut params_index_env = RegIndexEnv::default();
         let results = Self::abi_results(returns, call_conv);
@@ -114,7 +114,7 @@ impl ABI for Aarch64ABI {
     }
 
     fn abi_results(returns: &[WasmType], call_conv: &CallingConvention) -> ABIResults {
-        assert!(call_conv.is_apple_aarch64() || call_conv.is_default());
+        assert!(call_conv.is_apple_aarch64() || call_conv.is_systemv() || call_conv.is_default());
 
         let mut returns_index_env = RegIndexEnv::with_limit(1);
         ABIResults::from(returns, call_conv, |ty, stack_offset| {
@@ -132,11 +132,11 @@ impl ABI for Aarch64ABI {
     }
 
     fn float_scratch_reg() -> Reg {
-        todo!()
+        regs::float_scratch()
     }
 
     fn sp_reg() -> Reg {
-        todo!()
+        regs::sp()
     }
 
     fn fp_reg() -> Reg {
@@ -147,6 +147,11 @@ impl ABI for Aarch64ABI {
         regs::xreg(9)
     }
 
+    fn vmctx_reg_preserved() -> bool {
+        // x9 is a temporary register in AAPCS64.
+        false
+    }
+
     fn callee_saved_regs(_call_conv: &CallingConvention) -> SmallVec<[(Reg, OperandSize); 18]> {
         regs::callee_saved()
     }
@@ -176,6 +181,11 @@ impl Aarch64ABI {
         params_or_returns: ParamsOrReturns,
     ) -> (ABIOperand, u32) {
         let (reg, ty) = match wasm_arg {
+            ty @ WasmType::Ref(rt) => match rt.heap_type {
+                WasmHeapType::Func => (index_env.next_xreg().map(regs::xreg), ty),
+                ht => unimplemented!("Support for WasmHeapType: {ht}"),
+            },
+
             ty @ (WasmType::I32 | WasmType::I64) => (index_env.next_xreg().map(regs::xreg), ty),
 
             ty @ (WasmType::F32 | WasmType::F64) => (index_env.next_vreg().map(regs::vreg), ty),
@@ -7,7 +7,7 @@ use cranelift_codegen::{
 };
 
 use super::regs;
-use crate::reg::Reg;
+use crate::{masm::OperandSize, reg::Reg};
 
 /// Aarch64 indexing mode.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -83,6 +83,24 @@ impl Address {
         );
         Self::Offset { base, offset }
     }
+
+    /// Get the addressing mode of a load or store of `size` at this
+    /// address.
+    ///
+    /// Immediate offsets of loads and stores are scaled by the size of the
+    /// access, so the type of a register and offset addressing mode must
+    /// match it.
+    pub fn to_amode(self, size: OperandSize) -> Result<AMode> {
+        let mut mem: AMode = self.try_into()?;
+        if let AMode::RegOffset { ty, .. } = &mut mem {
+            *ty = match size {
+                OperandSize::S32 => types::I32,
+                OperandSize::S64 => types::I64,
+                OperandSize::S128 => types::I8X16,
+            };
+        }
+        Ok(mem)
+    }
 }
 
 // Conversions between `winch-codegen`'s addressing mode representation
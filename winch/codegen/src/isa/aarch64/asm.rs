@@ -1,17 +1,31 @@
 //! Assembler library implementation for Aarch64.
 
 use super::{address::Address, regs};
-use crate::{masm::OperandSize, reg::Reg};
+use crate::{
+    masm::{
+        DivKind, ExtendKind, FloatCmpKind, IntCmpKind, OperandSize, RemKind, RoundingMode,
+        ShiftKind,
+    },
+    reg::Reg,
+};
 use cranelift_codegen::{
-    ir::MemFlags,
-    isa::aarch64::inst::{
-        self,
-        emit::{EmitInfo, EmitState},
-        ALUOp, ALUOp3, AMode, ExtendOp, Imm12, Inst, PairAMode,
+    entity::EntityRef,
+    ir::{types, ExternalName, LibCall, MemFlags, Opcode, TrapCode, UserExternalNameRef},
+    isa::{
+        aarch64::inst::{
+            self,
+            emit::{EmitInfo, EmitState},
+            ALUOp, ALUOp3, AMode, BitOp, BranchTarget, CallIndInfo, CallInfo, Cond, CondBrKind,
+            ExtendOp, FPULeftShiftImm, FPUOp1, FPUOp2, FPUOpRI, FPUOpRIMod, FPURightShiftImm,
+            FpuRoundMode, Imm12, ImmLogic, ImmShift, Inst, PairAMode, ScalarSize, UImm5,
+            VecLanesOp, VecMisc2, VectorSize, NZCV,
+        },
+        CallConv,
     },
     settings, Final, MachBuffer, MachBufferFinalized, MachInstEmit, MachInstEmitState, MachLabel,
     Writable,
 };
+use smallvec::smallvec;
 
 impl From<OperandSize> for inst::OperandSize {
     fn from(size: OperandSize) -> Self {
@@ -23,6 +37,49 @@ impl From<OperandSize> for inst::OperandSize {
     }
 }
 
+impl From<OperandSize> for ScalarSize {
+    fn from(size: OperandSize) -> Self {
+        match size {
+            OperandSize::S32 => Self::Size32,
+            OperandSize::S64 => Self::Size64,
+            OperandSize::S128 => Self::Size128,
+        }
+    }
+}
+
+impl From<IntCmpKind> for Cond {
+    fn from(value: IntCmpKind) -> Self {
+        match value {
+            IntCmpKind::Eq => Cond::Eq,
+            IntCmpKind::Ne => Cond::Ne,
+            IntCmpKind::LtS => Cond::Lt,
+            IntCmpKind::LtU => Cond::Lo,
+            IntCmpKind::GtS => Cond::Gt,
+            IntCmpKind::GtU => Cond::Hi,
+            IntCmpKind::LeS => Cond::Le,
+            IntCmpKind::LeU => Cond::Ls,
+            IntCmpKind::GeS => Cond::Ge,
+            IntCmpKind::GeU => Cond::Hs,
+        }
+    }
+}
+
+// The float condition codes are chosen so that unordered results
+// (i.e. when one of the operands is NaN) produce `false` for every
+// comparison except for `Ne`, matching WebAssembly's semantics.
+impl From<FloatCmpKind> for Cond {
+    fn from(value: FloatCmpKind) -> Self {
+        match value {
+            FloatCmpKind::Eq => Cond::Eq,
+            FloatCmpKind::Ne => Cond::Ne,
+            FloatCmpKind::Lt => Cond::Mi,
+            FloatCmpKind::Gt => Cond::Gt,
+            FloatCmpKind::Le => Cond::Ls,
+            FloatCmpKind::Ge => Cond::Ge,
+        }
+    }
+}
+
 /// Low level assembler implementation for Aarch64.
 pub(crate) struct Assembler {
     /// The machine instruction buffer.
@@ -79,17 +136,27 @@ impl Assembler {
 
     /// Store a register.
     pub fn str(&mut self, reg: Reg, addr: Address, size: OperandSize) {
-        let mem: AMode = addr.try_into().unwrap();
+        let mem = addr.to_amode(size).unwrap();
         let flags = MemFlags::trusted();
 
         use OperandSize::*;
-        let inst = match size {
-            S64 => Inst::Store64 {
+        let inst = match (reg.is_int(), size) {
+            (true, S64) => Inst::Store64 {
+                rd: reg.into(),
+                mem,
+                flags,
+            },
+            (true, S32) => Inst::Store32 {
                 rd: reg.into(),
                 mem,
                 flags,
             },
-            S32 => Inst::Store32 {
+            (false, S64) => Inst::FpuStore64 {
+                rd: reg.into(),
+                mem,
+                flags,
+            },
+            (false, S32) => Inst::FpuStore32 {
                 rd: reg.into(),
                 mem,
                 flags,
@@ -105,16 +172,26 @@ impl Assembler {
     pub fn ldr(&mut self, addr: Address, rd: Reg, size: OperandSize) {
         use OperandSize::*;
         let writable_reg = Writable::from_reg(rd.into());
-        let mem: AMode = addr.try_into().unwrap();
+        let mem = addr.to_amode(size).unwrap();
         let flags = MemFlags::trusted();
 
-        let inst = match size {
-            S64 => Inst::ULoad64 {
+        let inst = match (rd.is_int(), size) {
+            (true, S64) => Inst::ULoad64 {
                 rd: writable_reg,
                 mem,
                 flags,
             },
-            S32 => Inst::ULoad32 {
+            (true, S32) => Inst::ULoad32 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (false, S64) => Inst::FpuLoad64 {
+                rd: writable_reg,
+                mem,
+                flags,
+            },
+            (false, S32) => Inst::FpuLoad32 {
                 rd: writable_reg,
                 mem,
                 flags,
@@ -195,6 +272,462 @@ impl Assembler {
         self.emit_alu_rrrr(ALUOp3::MAdd, scratch, rn, rd, regs::zero(), size);
     }
 
+    /// Floating point register to register move.
+    pub fn fmov_rr(&mut self, rn: Reg, rd: Reg) {
+        // Moving the lower 64 bits covers both single and double
+        // precision values.
+        self.emit(Inst::FpuMove64 {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Move a general purpose register into a floating point register.
+    pub fn mov_to_fpu(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::MovToFpu {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            size: size.into(),
+        });
+    }
+
+    /// Divide with three registers.
+    ///
+    /// Emits the checks required by WebAssembly's semantics: a trap
+    /// on division by zero and, for signed division, a trap on
+    /// overflow.
+    pub fn div_rrr(
+        &mut self,
+        divisor: Reg,
+        dividend: Reg,
+        dest: Reg,
+        kind: DivKind,
+        size: OperandSize,
+    ) {
+        self.trap_if_zero_divisor(divisor, size);
+
+        let (op, signed) = match kind {
+            DivKind::Signed => {
+                self.trap_if_div_overflow(divisor, dividend, size);
+                (ALUOp::SDiv, true)
+            }
+            DivKind::Unsigned => (ALUOp::UDiv, false),
+        };
+
+        let size = self.extend_div_operands(divisor, dividend, signed, size);
+        self.emit_alu_rrr(op, divisor, dividend, dest, size);
+    }
+
+    /// Remainder with three registers.
+    ///
+    /// Calculated as `dividend - (dividend / divisor) * divisor`.
+    /// Signed overflow is not checked, since `sdiv` produces a
+    /// quotient that results in a remainder of 0 in that case, as
+    /// required by WebAssembly.
+    pub fn rem_rrr(
+        &mut self,
+        divisor: Reg,
+        dividend: Reg,
+        dest: Reg,
+        kind: RemKind,
+        size: OperandSize,
+    ) {
+        self.trap_if_zero_divisor(divisor, size);
+
+        let (op, signed) = match kind {
+            RemKind::Signed => (ALUOp::SDiv, true),
+            RemKind::Unsigned => (ALUOp::UDiv, false),
+        };
+
+        let size = self.extend_div_operands(divisor, dividend, signed, size);
+        let scratch = regs::scratch();
+        self.emit_alu_rrr(op, divisor, dividend, scratch, size);
+        self.emit_alu_rrrr(ALUOp3::MSub, divisor, scratch, dest, dividend, size);
+    }
+
+    /// And with three registers.
+    pub fn and_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::And, rm, rn, rd, size);
+    }
+
+    /// And immediate and register.
+    pub fn and_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_logic_ir(ALUOp::And, imm, rn, rd, size);
+    }
+
+    /// Or with three registers.
+    pub fn or_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::Orr, rm, rn, rd, size);
+    }
+
+    /// Or immediate and register.
+    pub fn or_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_logic_ir(ALUOp::Orr, imm, rn, rd, size);
+    }
+
+    /// Xor with three registers.
+    pub fn xor_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::Eor, rm, rn, rd, size);
+    }
+
+    /// Xor immediate and register.
+    pub fn xor_ir(&mut self, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_alu_logic_ir(ALUOp::Eor, imm, rn, rd, size);
+    }
+
+    /// Shift with three registers.
+    pub fn shift_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, kind: ShiftKind, size: OperandSize) {
+        let shift_op = match kind {
+            ShiftKind::Rotl => {
+                // Rotating left by `n` is equivalent to rotating right by
+                // `-n`; the hardware only considers the lower bits of the
+                // shift amount.
+                let scratch = regs::scratch();
+                self.emit_alu_rrr(ALUOp::Sub, rm, regs::zero(), scratch, size);
+                self.emit_alu_rrr(ALUOp::RotR, scratch, rn, rd, size);
+                return;
+            }
+            kind => Self::shift_kind_to_alu_op(kind),
+        };
+
+        self.emit_alu_rrr(shift_op, rm, rn, rd, size);
+    }
+
+    /// Shift immediate and register.
+    pub fn shift_ir(&mut self, imm: u64, rn: Reg, rd: Reg, kind: ShiftKind, size: OperandSize) {
+        let bits = size.num_bits() as u64;
+        let amount = imm % bits;
+        let (shift_op, amount) = match kind {
+            ShiftKind::Rotl => (ALUOp::RotR, (bits - amount) % bits),
+            kind => (Self::shift_kind_to_alu_op(kind), amount),
+        };
+
+        self.emit(Inst::AluRRImmShift {
+            alu_op: shift_op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            immshift: ImmShift::maybe_from_u64(amount).unwrap(),
+        });
+    }
+
+    /// Count leading zeros.
+    pub fn clz(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_bit_rr(BitOp::Clz, rn, rd, size);
+    }
+
+    /// Reverse the bits of the source register.
+    pub fn rbit(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_bit_rr(BitOp::RBit, rn, rd, size);
+    }
+
+    /// Count the set bits of the source register.
+    ///
+    /// Uses the SIMD `cnt` instruction through the floating point
+    /// scratch register, given that there's no general purpose
+    /// register population count instruction.
+    pub fn popcnt(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        let tmp = regs::float_scratch();
+        self.mov_to_fpu(rn, tmp, size);
+        self.emit(Inst::VecMisc {
+            op: VecMisc2::Cnt,
+            rd: Writable::from_reg(tmp.into()),
+            rn: tmp.into(),
+            size: VectorSize::Size8x8,
+        });
+        self.emit(Inst::VecLanes {
+            op: VecLanesOp::Addv,
+            rd: Writable::from_reg(tmp.into()),
+            rn: tmp.into(),
+            size: VectorSize::Size8x8,
+        });
+        self.emit(Inst::MovFromVec {
+            rd: Writable::from_reg(rd.into()),
+            rn: tmp.into(),
+            idx: 0,
+            size: ScalarSize::Size8,
+        });
+    }
+
+    /// Sign or zero extend the source register.
+    pub fn extend(&mut self, rn: Reg, rd: Reg, kind: ExtendKind) {
+        let (signed, from_bits, to_bits) = match kind {
+            ExtendKind::I64ExtendI32S | ExtendKind::I64Extend32S => (true, 32, 64),
+            ExtendKind::I64ExtendI32U => (false, 32, 64),
+            ExtendKind::I32Extend8S => (true, 8, 32),
+            ExtendKind::I32Extend16S => (true, 16, 32),
+            ExtendKind::I64Extend8S => (true, 8, 64),
+            ExtendKind::I64Extend16S => (true, 16, 64),
+        };
+
+        self.emit(Inst::Extend {
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            signed,
+            from_bits,
+            to_bits,
+        });
+    }
+
+    /// Compare two registers.
+    pub fn cmp_rr(&mut self, rm: Reg, rn: Reg, size: OperandSize) {
+        self.emit_alu_rrr(ALUOp::SubS, rm, rn, regs::zero(), size);
+    }
+
+    /// Compare immediate and register.
+    pub fn cmp_ir(&mut self, imm: u64, rn: Reg, size: OperandSize) {
+        if let Some(imm) = Imm12::maybe_from_u64(imm) {
+            self.emit_alu_rri(ALUOp::SubS, imm, rn, regs::zero(), size);
+        } else {
+            let scratch = regs::scratch();
+            self.load_constant(imm, scratch);
+            self.cmp_rr(scratch, rn, size);
+        }
+    }
+
+    /// Set the destination register to 1 if the condition holds, 0
+    /// otherwise.
+    pub fn cset(&mut self, rd: Reg, cond: impl Into<Cond>) {
+        self.emit(Inst::CSet {
+            rd: Writable::from_reg(rd.into()),
+            cond: cond.into(),
+        });
+    }
+
+    /// Conditional select: `rd = cond ? rn : rm`.
+    pub fn csel(&mut self, rn: Reg, rm: Reg, rd: Reg, cond: impl Into<Cond>, size: OperandSize) {
+        let is_int = rd.is_int();
+        let rd = Writable::from_reg(rd.into());
+        let (rn, rm, cond) = (rn.into(), rm.into(), cond.into());
+        let inst = match (is_int, size) {
+            (true, _) => Inst::CSel { rd, cond, rn, rm },
+            (false, OperandSize::S32) => Inst::FpuCSel32 { rd, rn, rm, cond },
+            (false, _) => Inst::FpuCSel64 { rd, rn, rm, cond },
+        };
+        self.emit(inst);
+    }
+
+    /// Floating point add with three registers.
+    pub fn fadd_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Add, rm, rn, rd, size);
+    }
+
+    /// Floating point sub with three registers.
+    pub fn fsub_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Sub, rm, rn, rd, size);
+    }
+
+    /// Floating point mul with three registers.
+    pub fn fmul_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Mul, rm, rn, rd, size);
+    }
+
+    /// Floating point div with three registers.
+    pub fn fdiv_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Div, rm, rn, rd, size);
+    }
+
+    /// Floating point min with three registers.
+    pub fn fmin_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Min, rm, rn, rd, size);
+    }
+
+    /// Floating point max with three registers.
+    pub fn fmax_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rrr(FPUOp2::Max, rm, rn, rd, size);
+    }
+
+    /// Floating point copysign with three registers.
+    ///
+    /// Shifts the sign bit of `rm` into the least significant
+    /// position of the scratch register and inserts it back as the
+    /// most significant bit of `rd`, which holds the magnitude of
+    /// `rn`.
+    pub fn fcopysign_rrr(&mut self, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        let tmp = regs::float_scratch();
+        let bits = size.num_bits() as u8;
+        let max_shift = bits - 1;
+
+        let ushr = FPURightShiftImm::maybe_from_u8(max_shift, bits).unwrap();
+        let ushr = match size {
+            OperandSize::S32 => FPUOpRI::UShr32(ushr),
+            OperandSize::S64 => FPUOpRI::UShr64(ushr),
+            _ => unreachable!(),
+        };
+        self.emit(Inst::FpuRRI {
+            fpu_op: ushr,
+            rd: Writable::from_reg(tmp.into()),
+            rn: rm.into(),
+        });
+
+        if rn != rd {
+            self.fmov_rr(rn, rd);
+        }
+
+        let sli = FPULeftShiftImm::maybe_from_u8(max_shift, bits).unwrap();
+        let sli = match size {
+            OperandSize::S32 => FPUOpRIMod::Sli32(sli),
+            OperandSize::S64 => FPUOpRIMod::Sli64(sli),
+            _ => unreachable!(),
+        };
+        self.emit(Inst::FpuRRIMod {
+            fpu_op: sli,
+            rd: Writable::from_reg(rd.into()),
+            ri: rd.into(),
+            rn: tmp.into(),
+        });
+    }
+
+    /// Floating point neg.
+    pub fn fneg_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Neg, rn, rd, size);
+    }
+
+    /// Floating point abs.
+    pub fn fabs_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Abs, rn, rd, size);
+    }
+
+    /// Floating point square root.
+    pub fn fsqrt_rr(&mut self, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit_fpu_rr(FPUOp1::Sqrt, rn, rd, size);
+    }
+
+    /// Floating point round.
+    pub fn fround_rr(&mut self, rn: Reg, rd: Reg, mode: RoundingMode, size: OperandSize) {
+        use OperandSize::*;
+        use RoundingMode::*;
+
+        let op = match (mode, size) {
+            (Nearest, S32) => FpuRoundMode::Nearest32,
+            (Nearest, S64) => FpuRoundMode::Nearest64,
+            (Up, S32) => FpuRoundMode::Plus32,
+            (Up, S64) => FpuRoundMode::Plus64,
+            (Down, S32) => FpuRoundMode::Minus32,
+            (Down, S64) => FpuRoundMode::Minus64,
+            (Zero, S32) => FpuRoundMode::Zero32,
+            (Zero, S64) => FpuRoundMode::Zero64,
+            (_, S128) => unreachable!(),
+        };
+
+        self.emit(Inst::FpuRound {
+            op,
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    /// Floating point compare.
+    pub fn fcmp(&mut self, rn: Reg, rm: Reg, size: OperandSize) {
+        self.emit(Inst::FpuCmp {
+            size: size.into(),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    /// Load the effective address of the given addressing mode.
+    pub fn load_addr(&mut self, addr: Address, rd: Reg) {
+        let mem: AMode = addr.try_into().unwrap();
+        self.emit(Inst::LoadAddr {
+            rd: Writable::from_reg(rd.into()),
+            mem,
+        });
+    }
+
+    /// Emit a call to a function defined through a register.
+    pub fn call_with_reg(&mut self, callee: Reg) {
+        self.emit(Inst::CallInd {
+            info: Box::new(CallIndInfo {
+                rn: callee.into(),
+                uses: smallvec![],
+                defs: smallvec![],
+                clobbers: Default::default(),
+                opcode: Opcode::CallIndirect,
+                caller_callconv: CallConv::SystemV,
+                callee_callconv: CallConv::SystemV,
+                callee_pop_size: 0,
+            }),
+        });
+    }
+
+    /// Emit a call to a locally defined function through an index.
+    pub fn call_with_index(&mut self, index: u32) {
+        let dest = ExternalName::user(UserExternalNameRef::new(index as usize));
+        self.emit_call(dest);
+    }
+
+    /// Emit a call to a well-known libcall.
+    pub fn call_with_lib(&mut self, lib: LibCall) {
+        let dest = ExternalName::LibCall(lib);
+        self.emit_call(dest);
+    }
+
+    /// Performs an unconditional jump to the given label.
+    pub fn jmp(&mut self, target: MachLabel) {
+        self.emit(Inst::Jump {
+            dest: BranchTarget::Label(target),
+        });
+    }
+
+    /// Emits a conditional jump to the given label.
+    pub fn jmp_if(&mut self, cond: impl Into<Cond>, taken: MachLabel) {
+        // Conditional branches in Cranelift's aarch64 backend are
+        // two-way; the not-taken target is bound right after the
+        // branch, which allows the machine buffer to elide the
+        // unconditional part.
+        let not_taken = self.buffer.get_label();
+        self.emit(Inst::CondBr {
+            taken: BranchTarget::Label(taken),
+            not_taken: BranchTarget::Label(not_taken),
+            kind: CondBrKind::Cond(cond.into()),
+        });
+        self.buffer
+            .bind_label(not_taken, self.emit_state.ctrl_plane_mut());
+    }
+
+    /// Emits a jump table sequence. The default label is reached
+    /// whenever the flags of a prior comparison of the index against
+    /// the number of targets indicate that the index is out of
+    /// bounds.
+    pub fn jmp_table(
+        &mut self,
+        targets: &[MachLabel],
+        default: MachLabel,
+        index: Reg,
+        tmp1: Reg,
+        tmp2: Reg,
+    ) {
+        self.emit(Inst::JTSequence {
+            default,
+            targets: Box::new(targets.to_vec()),
+            ridx: index.into(),
+            rtmp1: Writable::from_reg(tmp1.into()),
+            rtmp2: Writable::from_reg(tmp2.into()),
+        });
+    }
+
+    /// Emit a trap instruction.
+    pub fn trap(&mut self, code: TrapCode) {
+        self.emit(Inst::Udf { trap_code: code });
+    }
+
+    /// Conditional trap.
+    pub fn trapif(&mut self, cond: impl Into<Cond>, trap_code: TrapCode) {
+        self.emit(Inst::TrapIf {
+            kind: CondBrKind::Cond(cond.into()),
+            trap_code,
+        });
+    }
+
+    /// Trap if the source register is zero.
+    pub fn trapz(&mut self, rn: Reg, trap_code: TrapCode) {
+        self.emit(Inst::TrapIf {
+            kind: CondBrKind::Zero(rn.into()),
+            trap_code,
+        });
+    }
+
     /// Return instruction.
     pub fn ret(&mut self) {
         self.emit(Inst::Ret {});
@@ -234,6 +767,142 @@ impl Assembler {
         });
     }
 
+    fn emit_alu_rrr(&mut self, op: ALUOp, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::AluRRR {
+            alu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    fn emit_alu_logic_ir(&mut self, op: ALUOp, imm: u64, rn: Reg, rd: Reg, size: OperandSize) {
+        let ty = match size {
+            OperandSize::S32 => types::I32,
+            _ => types::I64,
+        };
+
+        if let Some(imml) = ImmLogic::maybe_from_u64(imm, ty) {
+            self.emit(Inst::AluRRImmLogic {
+                alu_op: op,
+                size: size.into(),
+                rd: Writable::from_reg(rd.into()),
+                rn: rn.into(),
+                imml,
+            });
+        } else {
+            let scratch = regs::scratch();
+            self.load_constant(imm, scratch);
+            self.emit_alu_rrr(op, scratch, rn, rd, size);
+        }
+    }
+
+    fn emit_bit_rr(&mut self, op: BitOp, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::BitRR {
+            op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    fn emit_fpu_rr(&mut self, op: FPUOp1, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::FpuRR {
+            fpu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+        });
+    }
+
+    fn emit_fpu_rrr(&mut self, op: FPUOp2, rm: Reg, rn: Reg, rd: Reg, size: OperandSize) {
+        self.emit(Inst::FpuRRR {
+            fpu_op: op,
+            size: size.into(),
+            rd: Writable::from_reg(rd.into()),
+            rn: rn.into(),
+            rm: rm.into(),
+        });
+    }
+
+    fn emit_call(&mut self, dest: ExternalName) {
+        self.emit(Inst::Call {
+            info: Box::new(CallInfo {
+                dest,
+                uses: smallvec![],
+                defs: smallvec![],
+                clobbers: Default::default(),
+                opcode: Opcode::Call,
+                caller_callconv: CallConv::SystemV,
+                callee_callconv: CallConv::SystemV,
+                callee_pop_size: 0,
+            }),
+        });
+    }
+
+    fn shift_kind_to_alu_op(kind: ShiftKind) -> ALUOp {
+        match kind {
+            ShiftKind::Shl => ALUOp::Lsl,
+            ShiftKind::ShrS => ALUOp::Asr,
+            ShiftKind::ShrU => ALUOp::Lsr,
+            ShiftKind::Rotr => ALUOp::RotR,
+            ShiftKind::Rotl => unreachable!(),
+        }
+    }
+
+    // Helpers for division checks.
+
+    fn trap_if_zero_divisor(&mut self, divisor: Reg, size: OperandSize) {
+        self.cmp_ir(0, divisor, size);
+        self.trapif(Cond::Eq, TrapCode::IntegerDivisionByZero);
+    }
+
+    // `sdiv` and `udiv` are only emitted at 64 bits, like Cranelift does, so
+    // 32-bit operands are sign- or zero-extended in place. This leaves their
+    // low 32 bits, which hold the `i32` values, unchanged.
+    fn extend_div_operands(
+        &mut self,
+        divisor: Reg,
+        dividend: Reg,
+        signed: bool,
+        size: OperandSize,
+    ) -> OperandSize {
+        if size == OperandSize::S32 {
+            for reg in [divisor, dividend] {
+                let kind = if signed {
+                    ExtendKind::I64ExtendI32S
+                } else {
+                    ExtendKind::I64ExtendI32U
+                };
+                self.extend(reg, reg, kind);
+            }
+        }
+        OperandSize::S64
+    }
+
+    // Traps on `INT_MIN / -1`:
+    //   adds xzr, divisor, #1   ; divisor == -1 ?
+    //   ccmp dividend, #1, #0000, eq  ; dividend == INT_MIN ? (sets V)
+    //   b.vs trap
+    fn trap_if_div_overflow(&mut self, divisor: Reg, dividend: Reg, size: OperandSize) {
+        self.emit_alu_rri(
+            ALUOp::AddS,
+            Imm12::maybe_from_u64(1).unwrap(),
+            divisor,
+            regs::zero(),
+            size,
+        );
+        self.emit(Inst::CCmpImm {
+            size: size.into(),
+            rn: dividend.into(),
+            imm: UImm5::maybe_from_u8(1).unwrap(),
+            nzcv: NZCV::new(false, false, false, false),
+            cond: Cond::Eq,
+        });
+        self.trapif(Cond::Vs, TrapCode::IntegerOverflow);
+    }
+
     /// Get a label from the underlying machine code buffer.
     pub fn get_label(&mut self) -> MachLabel {
         self.buffer.get_label()
@@ -1,14 +1,16 @@
 use super::{abi::Aarch64ABI, address::Address, asm::Assembler, regs};
 use crate::{
-    abi::{self, local::LocalSlot},
+    abi::{self, align_to, calculate_frame_adjustment, local::LocalSlot, ABI},
     codegen::{ptr_type_from_ptr_size, CodeGenContext, TableData},
-    isa::reg::Reg,
+    isa::reg::{Reg, RegClass},
     masm::{
-        CalleeKind, DivKind, ExtendKind, FloatCmpKind, Imm as I, IntCmpKind,
+        CalleeKind, DivKind, ExtendKind, ExtractLaneKind, FloatCmpKind, Imm as I, IntCmpKind,
         MacroAssembler as Masm, OperandSize, RegImm, RemKind, RoundingMode, SPOffset, ShiftKind,
-        StackSlot, TrapCode,
+        StackSlot, TrapCode, VectorShape,
     },
+    stack::TypedReg,
 };
+use anyhow::{bail, Result};
 use cranelift_codegen::{settings, Final, MachBufferFinalized, MachLabel};
 use wasmtime_environ::PtrSize;
 
@@ -20,15 +22,18 @@ pub(crate) struct MacroAssembler {
     sp_offset: u32,
     /// The target pointer size.
     ptr_size: OperandSize,
+    /// Shared flags.
+    shared_flags: settings::Flags,
 }
 
 impl MacroAssembler {
     /// Create an Aarch64 MacroAssembler.
     pub fn new(ptr_size: impl PtrSize, shared_flags: settings::Flags) -> Self {
         Self {
-            asm: Assembler::new(shared_flags),
+            asm: Assembler::new(shared_flags.clone()),
             sp_offset: 0u32,
             ptr_size: ptr_type_from_ptr_size(ptr_size.size()).into(),
+            shared_flags,
         }
     }
 }
@@ -46,6 +51,12 @@ impl Masm for MacroAssembler {
 
         self.asm.stp(fp, lr, addr);
         self.asm.mov_rr(sp, fp, OperandSize::S64);
+
+        // The shadow stack pointer is callee-saved, so it must be
+        // preserved before it's clobbered.
+        let shadow_sp = regs::shadow_sp();
+        let addr = Address::pre_indexed_from_sp(-16);
+        self.asm.str(shadow_sp, addr, OperandSize::S64);
         self.move_sp_to_shadow_sp();
     }
 
@@ -59,6 +70,10 @@ impl Masm for MacroAssembler {
             self.move_sp_to_shadow_sp();
         }
 
+        let shadow_sp = regs::shadow_sp();
+        let addr = Address::post_indexed_from_sp(16);
+        self.asm.ldr(addr, shadow_sp, OperandSize::S64);
+
         let lr = regs::lr();
         let fp = regs::fp();
         let addr = Address::post_indexed_from_sp(16);
@@ -79,8 +94,16 @@ impl Masm for MacroAssembler {
         self.increment_sp(bytes);
     }
 
-    fn free_stack(&mut self, _bytes: u32) {
-        todo!()
+    fn free_stack(&mut self, bytes: u32) {
+        if bytes == 0 {
+            return;
+        }
+
+        let sp = regs::sp();
+        self.asm.add_ir(bytes as u64, sp, sp, OperandSize::S64);
+        self.move_sp_to_shadow_sp();
+
+        self.decrement_sp(bytes);
     }
 
     fn reset_stack_pointer(&mut self, offset: SPOffset) {
@@ -104,28 +127,89 @@ impl Masm for MacroAssembler {
 
     fn table_elem_address(
         &mut self,
-        _index: Reg,
-        _base: Reg,
-        _table_data: &TableData,
-        _context: &mut CodeGenContext,
+        index: Reg,
+        ptr_base: Reg,
+        table_data: &TableData,
+        context: &mut CodeGenContext,
     ) -> Self::Address {
-        todo!()
-    }
+        let vmctx = <Self::ABI as ABI>::vmctx_reg();
+        let scratch = regs::scratch();
+        let bound = context.any_gpr(self);
+        let tmp = context.any_gpr(self);
+
+        if let Some(offset) = table_data.base {
+            // If the table data declares a particular offset base,
+            // load the address into a register to further use it as
+            // the table address.
+            self.load_ptr(self.address_at_vmctx(offset), ptr_base);
+        } else {
+            // Else, simply move the vmctx register into the addr register as
+            // the base to calculate the table address.
+            self.asm.mov_rr(vmctx, ptr_base, self.ptr_size);
+        };
+
+        // OOB check.
+        let bound_addr = self.address_at_reg(ptr_base, table_data.current_elems_offset);
+        let bound_size = table_data.current_elements_size;
+        self.asm.ldr(bound_addr, bound, bound_size);
+        self.asm.cmp_rr(bound, index, bound_size);
+        self.asm.trapif(IntCmpKind::GeU, TrapCode::TableOutOfBounds);
+
+        // Calculate the offset of the table element into the scratch
+        // register; table elements are always a power of two in size,
+        // which allows using a shift instead of a multiplication.
+        let shift = table_data.element_size.bytes().trailing_zeros();
+        self.asm
+            .shift_ir(shift.into(), index, scratch, ShiftKind::Shl, self.ptr_size);
+        self.load_ptr(self.address_at_reg(ptr_base, table_data.offset), ptr_base);
+        // Copy the value of the table base into a temporary register
+        // so that we can use it later in case of a misspeculation.
+        self.asm.mov_rr(ptr_base, tmp, self.ptr_size);
+        // Calculate the address of the table element.
+        self.asm.add_rrr(scratch, ptr_base, ptr_base, self.ptr_size);
+        if self.shared_flags.enable_table_access_spectre_mitigation() {
+            // Perform a bounds check and override the value of the
+            // table element address in case the index is out of bounds.
+            self.asm.cmp_rr(bound, index, bound_size);
+            self.asm
+                .csel(tmp, ptr_base, ptr_base, IntCmpKind::GeU, self.ptr_size);
+        }
+        context.free_reg(bound);
+        context.free_reg(tmp);
+        self.address_at_reg(ptr_base, 0)
+    }
+
+    fn table_size(&mut self, table_data: &TableData, context: &mut CodeGenContext) {
+        let vmctx = <Self::ABI as ABI>::vmctx_reg();
+        let size = context.any_gpr(self);
+
+        // The register holding the size is also used as the base to
+        // load the size, given that the scratch register can't be used
+        // as a base register in aarch64 addressing modes with large
+        // offsets.
+        if let Some(offset) = table_data.base {
+            self.load_ptr(self.address_at_vmctx(offset), size);
+        } else {
+            self.asm.mov_rr(vmctx, size, self.ptr_size);
+        };
+
+        let size_addr = self.address_at_reg(size, table_data.current_elems_offset);
+        self.asm
+            .ldr(size_addr, size, table_data.current_elements_size);
 
-    fn table_size(&mut self, _table_data: &TableData, _context: &mut CodeGenContext) {
-        todo!()
+        context.stack.push(TypedReg::i32(size).into());
     }
 
-    fn address_from_sp(&self, _offset: SPOffset) -> Self::Address {
-        todo!()
+    fn address_from_sp(&self, offset: SPOffset) -> Self::Address {
+        Address::from_shadow_sp((self.sp_offset - offset.as_u32()) as i64)
     }
 
-    fn address_at_sp(&self, _offset: SPOffset) -> Self::Address {
-        todo!()
+    fn address_at_sp(&self, offset: SPOffset) -> Self::Address {
+        Address::from_shadow_sp(offset.as_u32() as i64)
     }
 
-    fn address_at_vmctx(&self, _offset: u32) -> Self::Address {
-        todo!()
+    fn address_at_vmctx(&self, offset: u32) -> Self::Address {
+        Address::offset(<Self::ABI as ABI>::vmctx_reg(), offset as i64)
     }
 
     fn store_ptr(&mut self, src: Reg, dst: Self::Address) {
@@ -135,15 +219,24 @@ impl Masm for MacroAssembler {
     fn store(&mut self, src: RegImm, dst: Address, size: OperandSize) {
         let src = match src {
             RegImm::Imm(v) => {
+                // Float constants are stored through their bit
+                // representation.
                 let imm = match v {
                     I::I32(v) => v as u64,
                     I::I64(v) => v,
-                    _ => unreachable!(),
+                    I::F32(v) => v as u64,
+                    I::F64(v) => v,
+                    I::V128(_) => unimplemented!("Support for v128 immediates"),
                 };
                 let scratch = regs::scratch();
                 self.asm.load_constant(imm, scratch);
                 scratch
             }
+            // The stack pointer can't be used as the source of a
+            // store, since its encoding is shared with the zero
+            // register. Its value is always mirrored by the shadow stack
+            // pointer, so use that instead.
+            RegImm::Reg(reg) if reg == regs::sp() => regs::shadow_sp(),
             RegImm::Reg(reg) => reg,
         };
 
@@ -152,26 +245,40 @@ impl Masm for MacroAssembler {
 
     fn call(
         &mut self,
-        _stack_args_size: u32,
-        _load_callee: impl FnMut(&mut Self) -> CalleeKind,
+        stack_args_size: u32,
+        mut load_callee: impl FnMut(&mut Self) -> CalleeKind,
     ) -> u32 {
-        todo!()
+        let alignment: u32 = <Self::ABI as abi::ABI>::call_stack_align().into();
+        let addend: u32 = <Self::ABI as abi::ABI>::arg_base_offset().into();
+        let delta = calculate_frame_adjustment(self.sp_offset().as_u32(), addend, alignment);
+        let aligned_args_size = align_to(stack_args_size, alignment);
+        let total_stack = delta + aligned_args_size;
+        self.reserve_stack(total_stack);
+        let callee = load_callee(self);
+        match callee {
+            CalleeKind::Indirect(reg) => self.asm.call_with_reg(reg),
+            CalleeKind::Direct(idx) => self.asm.call_with_index(idx),
+            CalleeKind::Known(lib) => self.asm.call_with_lib(lib),
+        };
+        total_stack
     }
 
     fn load(&mut self, src: Address, dst: Reg, size: OperandSize) {
         self.asm.ldr(src, dst, size);
     }
 
-    fn load_ptr(&mut self, _src: Self::Address, _dst: Reg) {
-        todo!()
+    fn load_ptr(&mut self, src: Self::Address, dst: Reg) {
+        self.load(src, dst, self.ptr_size);
     }
 
-    fn load_addr(&mut self, _src: Self::Address, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn load_addr(&mut self, src: Self::Address, dst: Reg, _size: OperandSize) {
+        self.asm.load_addr(src, dst);
     }
 
-    fn pop(&mut self, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn pop(&mut self, dst: Reg, size: OperandSize) {
+        let addr = self.address_from_sp(SPOffset::from_u32(self.sp_offset));
+        self.asm.ldr(addr, dst, size);
+        self.free_stack(size.bytes());
     }
 
     fn sp_offset(&self) -> SPOffset {
@@ -188,21 +295,29 @@ impl Masm for MacroAssembler {
                 let imm = match v {
                     I::I32(v) => v as u64,
                     I::I64(v) => v,
-                    _ => panic!(),
+                    I::F32(v) => v as u64,
+                    I::F64(v) => v,
+                    I::V128(_) => unimplemented!("Support for v128 immediates"),
                 };
 
                 let scratch = regs::scratch();
                 self.asm.load_constant(imm, scratch);
-                self.asm.mov_rr(scratch, rd, size);
-            }
-            (RegImm::Reg(rs), rd) => {
-                self.asm.mov_rr(rs, rd, size);
+                match rd.class() {
+                    RegClass::Int => self.asm.mov_rr(scratch, rd, size),
+                    RegClass::Float => self.asm.mov_to_fpu(scratch, rd, size),
+                    _ => unreachable!(),
+                }
             }
+            (RegImm::Reg(rs), rd) => match (rs.class(), rd.class()) {
+                (RegClass::Int, RegClass::Int) => self.asm.mov_rr(rs, rd, size),
+                (RegClass::Float, RegClass::Float) => self.asm.fmov_rr(rs, rd),
+                _ => panic!("Invalid operand combination; src={:?}, dst={:?}", rs, rd),
+            },
         }
     }
 
-    fn cmov(&mut self, _src: Reg, _dst: Reg, _cc: IntCmpKind, _size: OperandSize) {
-        todo!()
+    fn cmov(&mut self, src: Reg, dst: Reg, cc: IntCmpKind, size: OperandSize) {
+        self.asm.csel(src, dst, dst, cc, size);
     }
 
     fn add(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize) {
@@ -259,96 +374,186 @@ impl Masm for MacroAssembler {
         }
     }
 
-    fn float_add(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fadd_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_sub(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fsub_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_mul(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_mul(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmul_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_div(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fdiv_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_min(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_min(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmin_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_max(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_max(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fmax_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_copysign(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _size: OperandSize) {
-        todo!()
+    fn float_copysign(&mut self, dst: Reg, lhs: Reg, rhs: Reg, size: OperandSize) {
+        self.asm.fcopysign_rrr(rhs, lhs, dst, size);
     }
 
-    fn float_neg(&mut self, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn float_neg(&mut self, dst: Reg, size: OperandSize) {
+        self.asm.fneg_rr(dst, dst, size);
     }
 
-    fn float_abs(&mut self, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn float_abs(&mut self, dst: Reg, size: OperandSize) {
+        self.asm.fabs_rr(dst, dst, size);
     }
 
-    fn float_round(
-        &mut self,
-        _mode: RoundingMode,
-        _context: &mut CodeGenContext,
-        _size: OperandSize,
-    ) {
-        todo!();
+    fn float_round(&mut self, mode: RoundingMode, context: &mut CodeGenContext, size: OperandSize) {
+        let src = context.pop_to_reg(self, None);
+        self.asm.fround_rr(src.into(), src.into(), mode, size);
+        context.stack.push(src.into());
     }
 
-    fn float_sqrt(&mut self, _dst: Reg, _src: Reg, _size: OperandSize) {
-        todo!()
+    fn float_sqrt(&mut self, dst: Reg, src: Reg, size: OperandSize) {
+        self.asm.fsqrt_rr(src, dst, size);
     }
 
-    fn and(&mut self, _dst: Reg, _lhs: Reg, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn and(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), rn, rd) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.and_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), rn, rd) => {
+                self.asm.and_rrr(rm, rn, rd, size);
+            }
+        }
     }
 
-    fn or(&mut self, _dst: Reg, _lhs: Reg, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn or(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), rn, rd) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.or_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), rn, rd) => {
+                self.asm.or_rrr(rm, rn, rd, size);
+            }
+        }
     }
 
-    fn xor(&mut self, _dst: Reg, _lhs: Reg, _rhs: RegImm, _size: OperandSize) {
-        todo!()
+    fn xor(&mut self, dst: Reg, lhs: Reg, rhs: RegImm, size: OperandSize) {
+        match (rhs, lhs, dst) {
+            (RegImm::Imm(v), rn, rd) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.xor_ir(imm, rn, rd, size);
+            }
+
+            (RegImm::Reg(rm), rn, rd) => {
+                self.asm.xor_rrr(rm, rn, rd, size);
+            }
+        }
     }
 
-    fn shift(&mut self, _context: &mut CodeGenContext, _kind: ShiftKind, _size: OperandSize) {
-        todo!()
+    fn shift(&mut self, context: &mut CodeGenContext, kind: ShiftKind, size: OperandSize) {
+        let top = context.stack.peek().expect("value at stack top");
+
+        if size == OperandSize::S32 && top.is_i32_const() {
+            let val = context
+                .stack
+                .pop_i32_const()
+                .expect("i32 const value at stack top");
+            let typed_reg = context.pop_to_reg(self, None);
+
+            self.asm
+                .shift_ir(val as u64, typed_reg.into(), typed_reg.into(), kind, size);
+
+            context.stack.push(typed_reg.into());
+        } else if size == OperandSize::S64 && top.is_i64_const() {
+            let val = context
+                .stack
+                .pop_i64_const()
+                .expect("i64 const value at stack top");
+            let typed_reg = context.pop_to_reg(self, None);
+
+            self.asm
+                .shift_ir(val as u64, typed_reg.into(), typed_reg.into(), kind, size);
+
+            context.stack.push(typed_reg.into());
+        } else {
+            let src = context.pop_to_reg(self, None);
+            let dst = context.pop_to_reg(self, None);
+
+            self.asm
+                .shift_rrr(src.into(), dst.into(), dst.into(), kind, size);
+
+            context.free_reg(src);
+            context.stack.push(dst.into());
+        }
     }
 
-    fn div(&mut self, _context: &mut CodeGenContext, _kind: DivKind, _size: OperandSize) {
-        todo!()
+    fn div(&mut self, context: &mut CodeGenContext, kind: DivKind, size: OperandSize) {
+        let divisor = context.pop_to_reg(self, None);
+        let dividend = context.pop_to_reg(self, None);
+
+        self.asm
+            .div_rrr(divisor.into(), dividend.into(), dividend.into(), kind, size);
+
+        context.free_reg(divisor);
+        // Push the quotient.
+        context.stack.push(dividend.into());
     }
 
-    fn rem(&mut self, _context: &mut CodeGenContext, _kind: RemKind, _size: OperandSize) {
-        todo!()
+    fn rem(&mut self, context: &mut CodeGenContext, kind: RemKind, size: OperandSize) {
+        let divisor = context.pop_to_reg(self, None);
+        let dividend = context.pop_to_reg(self, None);
+
+        self.asm
+            .rem_rrr(divisor.into(), dividend.into(), dividend.into(), kind, size);
+
+        context.free_reg(divisor);
+        // Push the remainder.
+        context.stack.push(dividend.into());
     }
 
     fn zero(&mut self, reg: Reg) {
         self.asm.load_constant(0, reg);
     }
 
-    fn popcnt(&mut self, _context: &mut CodeGenContext, _size: OperandSize) {
-        todo!()
+    fn popcnt(&mut self, context: &mut CodeGenContext, size: OperandSize) {
+        let src = context.pop_to_reg(self, None);
+        self.asm.popcnt(src.into(), src.into(), size);
+        context.stack.push(src.into());
     }
 
-    fn push(&mut self, reg: Reg, _size: OperandSize) -> StackSlot {
-        let size = <Self::ABI as abi::ABI>::word_bytes();
-        self.reserve_stack(size);
-        let address = Address::from_shadow_sp(size as i64);
-        self.asm.str(reg, address, OperandSize::S64);
+    fn push(&mut self, reg: Reg, size: OperandSize) -> StackSlot {
+        let bytes = size.bytes();
+        self.reserve_stack(bytes);
+        let address = self.address_from_sp(SPOffset::from_u32(self.sp_offset));
+        self.asm.str(reg, address, size);
 
         StackSlot {
             offset: SPOffset::from_u32(self.sp_offset),
-            size,
+            size: bytes,
         }
     }
 
@@ -356,39 +561,143 @@ impl Masm for MacroAssembler {
         Address::offset(reg, offset as i64)
     }
 
-    fn cmp_with_set(&mut self, _src: RegImm, _dst: Reg, _kind: IntCmpKind, _size: OperandSize) {
-        todo!()
+    fn cmp_with_set(&mut self, src: RegImm, dst: Reg, kind: IntCmpKind, size: OperandSize) {
+        self.cmp(src, dst, size);
+        self.asm.cset(dst, kind);
     }
 
-    fn cmp(&mut self, _src: RegImm, _dest: Reg, _size: OperandSize) {
-        todo!()
+    fn cmp(&mut self, src: RegImm, dest: Reg, size: OperandSize) {
+        match src {
+            RegImm::Imm(v) => {
+                let imm = match v {
+                    I::I32(v) => v as u64,
+                    I::I64(v) => v,
+                    _ => unreachable!(),
+                };
+
+                self.asm.cmp_ir(imm, dest, size);
+            }
+            RegImm::Reg(src) => {
+                self.asm.cmp_rr(src, dest, size);
+            }
+        }
     }
 
     fn float_cmp_with_set(
         &mut self,
-        _src1: Reg,
-        _src2: Reg,
-        _dst: Reg,
-        _kind: FloatCmpKind,
-        _size: OperandSize,
+        src1: Reg,
+        src2: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        size: OperandSize,
     ) {
-        todo!()
+        // The condition codes for each comparison kind already account
+        // for unordered results; see `From<FloatCmpKind> for Cond`.
+        self.asm.fcmp(src2, src1, size);
+        self.asm.cset(dst, kind);
+    }
+
+    fn clz(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        self.asm.clz(src, dst, size);
+    }
+
+    fn ctz(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        // There's no count trailing zeros instruction; reversing the
+        // bits first allows counting the leading zeros instead.
+        self.asm.rbit(src, dst, size);
+        self.asm.clz(dst, dst, size);
+    }
+
+    fn wrap(&mut self, src: Reg, dst: Reg) {
+        self.asm.mov_rr(src, dst, OperandSize::S32);
     }
 
-    fn clz(&mut self, _src: Reg, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn extend(&mut self, src: Reg, dst: Reg, kind: ExtendKind) {
+        self.asm.extend(src, dst, kind);
     }
 
-    fn ctz(&mut self, _src: Reg, _dst: Reg, _size: OperandSize) {
-        todo!()
+    fn v128_and(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg) {
+        unimplemented!("Support for v128 operators")
     }
 
-    fn wrap(&mut self, _src: Reg, _dst: Reg) {
-        todo!()
+    fn v128_and_not(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg) {
+        unimplemented!("Support for v128 operators")
     }
 
-    fn extend(&mut self, _src: Reg, _dst: Reg, _kind: ExtendKind) {
-        todo!()
+    fn v128_or(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_xor(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_not(&mut self, _dst: Reg) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_add(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _shape: VectorShape) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_sub(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _shape: VectorShape) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_mul(&mut self, _context: &mut CodeGenContext, _shape: VectorShape) -> Result<()> {
+        bail!("Support for v128 operators")
+    }
+
+    fn v128_div(&mut self, _dst: Reg, _lhs: Reg, _rhs: Reg, _shape: VectorShape) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_splat(&mut self, _context: &mut CodeGenContext, _shape: VectorShape) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_extract_lane(
+        &mut self,
+        _context: &mut CodeGenContext,
+        _lane: u8,
+        _kind: ExtractLaneKind,
+    ) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_replace_lane(
+        &mut self,
+        _context: &mut CodeGenContext,
+        _lane: u8,
+        _shape: VectorShape,
+    ) -> Result<()> {
+        bail!("Support for v128 operators")
+    }
+
+    fn v128_int_cmp(
+        &mut self,
+        _context: &mut CodeGenContext,
+        _kind: IntCmpKind,
+        _shape: VectorShape,
+    ) -> Result<()> {
+        bail!("Support for v128 operators")
+    }
+
+    fn v128_float_cmp(
+        &mut self,
+        _context: &mut CodeGenContext,
+        _kind: FloatCmpKind,
+        _shape: VectorShape,
+    ) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_shift(&mut self, _context: &mut CodeGenContext, _kind: ShiftKind, _shape: VectorShape) {
+        unimplemented!("Support for v128 operators")
+    }
+
+    fn v128_shuffle(&mut self, _context: &mut CodeGenContext, _lanes: [u8; 16]) -> Result<()> {
+        bail!("Support for v128 operators")
     }
 
     fn get_label(&mut self) -> MachLabel {
@@ -402,33 +711,61 @@ impl Masm for MacroAssembler {
 
     fn branch(
         &mut self,
-        _kind: IntCmpKind,
-        _lhs: RegImm,
-        _rhs: Reg,
-        _taken: MachLabel,
-        _size: OperandSize,
+        kind: IntCmpKind,
+        lhs: RegImm,
+        rhs: Reg,
+        taken: MachLabel,
+        size: OperandSize,
     ) {
-        todo!()
+        use IntCmpKind::*;
+
+        match &(lhs, rhs) {
+            (RegImm::Reg(rlhs), rrhs) => {
+                // If the comparision kind is zero or not zero and both operands
+                // are the same register, compare against zero. Else we emit
+                // a normal comparison.
+                if (kind == Eq || kind == Ne) && (rlhs == rrhs) {
+                    self.asm.cmp_ir(0, *rrhs, size);
+                } else {
+                    self.cmp(lhs, rhs, size);
+                }
+            }
+            _ => self.cmp(lhs, rhs, size),
+        }
+        self.asm.jmp_if(kind, taken);
     }
 
-    fn jmp(&mut self, _target: MachLabel) {
-        todo!()
+    fn jmp(&mut self, target: MachLabel) {
+        self.asm.jmp(target);
     }
 
     fn unreachable(&mut self) {
-        todo!()
+        self.asm.trap(TrapCode::UnreachableCodeReached);
     }
 
-    fn jmp_table(&mut self, _targets: &[MachLabel], _index: Reg, _tmp: Reg) {
-        todo!()
+    fn jmp_table(&mut self, targets: &[MachLabel], index: Reg, tmp: Reg) {
+        // At least one default target.
+        assert!(targets.len() >= 1);
+        let default_index = targets.len() - 1;
+        // Emit bounds check, by comparing the index against the
+        // number of cases; the jump table sequence branches to the
+        // default target if the index is out of bounds.
+        let max = default_index;
+        let size = OperandSize::S32;
+        self.asm.cmp_ir(max as u64, index, size);
+
+        let default = targets[default_index];
+        let rest = &targets[0..default_index];
+        let tmp1 = regs::scratch();
+        self.asm.jmp_table(rest, default, index, tmp1, tmp);
     }
 
-    fn trapz(&mut self, _src: Reg, _code: TrapCode) {
-        todo!()
+    fn trapz(&mut self, src: Reg, code: TrapCode) {
+        self.asm.trapz(src, code);
     }
 
-    fn trapif(&mut self, _cc: IntCmpKind, _code: TrapCode) {
-        todo!()
+    fn trapif(&mut self, cc: IntCmpKind, code: TrapCode) {
+        self.asm.trapif(cc, code);
     }
 }
 
@@ -437,6 +774,16 @@ impl MacroAssembler {
         self.sp_offset += bytes;
     }
 
+    fn decrement_sp(&mut self, bytes: u32) {
+        assert!(
+            self.sp_offset >= bytes,
+            "sp offset = {}; bytes = {}",
+            self.sp_offset,
+            bytes
+        );
+        self.sp_offset -= bytes;
+    }
+
     // Copies the value of the stack pointer to the shadow stack
     // pointer: mov x28, sp
 
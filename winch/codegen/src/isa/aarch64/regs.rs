@@ -38,6 +38,23 @@ pub(crate) const fn ip1() -> Reg {
     xreg(17)
 }
 
+/// Floating point scratch register.
+pub(crate) const fn float_scratch() -> Reg {
+    vreg(31)
+}
+
+/// This register is used as a scratch register, in the context of trampolines only,
+/// where we assume that callee-saved registers are given the correct handling
+/// according to the system ABI. x19 is chosen given that it's a callee-saved,
+/// non-argument register.
+///
+/// In the context of all other internal functions, this register is not excluded
+/// from register allocation, so no extra assumptions should be made regarding
+/// its availability.
+pub(crate) const fn argv() -> Reg {
+    xreg(19)
+}
+
 /// Register used to carry platform state.
 const fn platform() -> Reg {
     xreg(18)
@@ -149,6 +166,13 @@ pub(crate) const NON_ALLOCATABLE_GPR: u32 = (1 << ip0().hw_enc())
 /// Bitmask to represent the available general purpose registers.
 pub(crate) const ALL_GPR: u32 = u32::MAX & !NON_ALLOCATABLE_GPR;
 
+/// Bitmask for non-allocatable FPR.
+// v31: Is used as the floating point scratch register.
+pub(crate) const NON_ALLOCATABLE_FPR: u32 = 1 << float_scratch().hw_enc();
+
+/// Bitmask to represent the available floating point registers.
+pub(crate) const ALL_FPR: u32 = u32::MAX & !NON_ALLOCATABLE_FPR;
+
 /// Returns the callee-saved registers.
 ///
 /// This function will return the set of registers that need to be saved
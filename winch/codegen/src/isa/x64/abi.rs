@@ -174,6 +174,11 @@ impl ABI for X64ABI {
         regs::vmctx()
     }
 
+    fn vmctx_reg_preserved() -> bool {
+        // r14 is callee-saved in both System V and fastcall.
+        true
+    }
+
     fn callee_saved_regs(call_conv: &CallingConvention) -> SmallVec<[(Reg, OperandSize); 18]> {
         regs::callee_saved(call_conv)
     }
@@ -190,6 +195,7 @@ impl ABI for X64ABI {
             },
             WasmType::F64 | WasmType::I64 => Self::word_bytes(),
             WasmType::F32 | WasmType::I32 => Self::word_bytes() / 2,
+            WasmType::V128 => Self::word_bytes() * 2,
             ty => unimplemented!("Support for WasmType: {ty}"),
         }
     }
@@ -217,7 +223,7 @@ impl X64ABI {
                 ty,
             ),
 
-            ty @ (WasmType::F32 | WasmType::F64) => (
+            ty @ (WasmType::F32 | WasmType::F64 | WasmType::V128) => (
                 Self::float_reg_for(index_env.next_fpr(), call_conv, params_or_returns),
                 ty,
             ),
@@ -228,10 +234,11 @@ impl X64ABI {
         let ty_size = <Self as ABI>::sizeof(wasm_arg);
         let default = || {
             let arg = ABIOperand::stack_offset(stack_offset, *ty, ty_size);
-            let slot_size = Self::stack_slot_size();
             // Stack slots for parameters are aligned to a fixed slot size,
-            // in the case of x64, 8 bytes.
+            // in the case of x64, 8 bytes; except for v128 parameters,
+            // which take two slots.
             // Stack slots for returns are type-size aligned.
+            let slot_size = Self::stack_slot_size().max(ty_size);
             let next_stack = if params_or_returns == ParamsOrReturns::Params {
                 align_to(stack_offset, slot_size) + slot_size
             } else {
@@ -2,7 +2,10 @@
 
 use crate::{
     isa::reg::Reg,
-    masm::{DivKind, ExtendKind, IntCmpKind, OperandSize, RemKind, RoundingMode, ShiftKind},
+    masm::{
+        DivKind, ExtendKind, FloatCmpKind, IntCmpKind, OperandSize, RemKind, RoundingMode,
+        ShiftKind, VectorShape,
+    },
 };
 use cranelift_codegen::{
     entity::EntityRef,
@@ -13,7 +16,7 @@ use cranelift_codegen::{
                 self, AluRmiROpcode, Amode, CmpOpcode, DivSignedness, ExtMode, FromWritableReg,
                 Gpr, GprMem, GprMemImm, Imm8Gpr, Imm8Reg, RegMem, RegMemImm,
                 ShiftKind as CraneliftShiftKind, SseOpcode, SyntheticAmode, WritableGpr,
-                WritableXmm, Xmm, XmmMem, XmmMemAligned, CC,
+                WritableXmm, Xmm, XmmMem, XmmMemAligned, XmmMemAlignedImm, CC,
             },
             settings as x64_settings, CallInfo, EmitInfo, EmitState, Inst,
         },
@@ -472,7 +475,7 @@ impl Assembler {
         let op = match size {
             OperandSize::S32 => SseOpcode::Andps,
             OperandSize::S64 => SseOpcode::Andpd,
-            OperandSize::S128 => unreachable!(),
+            OperandSize::S128 => SseOpcode::Pand,
         };
 
         self.emit(Inst::XmmRmR {
@@ -488,7 +491,7 @@ impl Assembler {
         let op = match size {
             OperandSize::S32 => SseOpcode::Andnps,
             OperandSize::S64 => SseOpcode::Andnpd,
-            OperandSize::S128 => unreachable!(),
+            OperandSize::S128 => SseOpcode::Pandn,
         };
 
         self.emit(Inst::XmmRmR {
@@ -540,7 +543,7 @@ impl Assembler {
         let op = match size {
             OperandSize::S32 => SseOpcode::Orps,
             OperandSize::S64 => SseOpcode::Orpd,
-            OperandSize::S128 => unreachable!(),
+            OperandSize::S128 => SseOpcode::Por,
         };
 
         self.emit(Inst::XmmRmR {
@@ -579,7 +582,7 @@ impl Assembler {
         let op = match size {
             OperandSize::S32 => SseOpcode::Xorps,
             OperandSize::S64 => SseOpcode::Xorpd,
-            OperandSize::S128 => unreachable!(),
+            OperandSize::S128 => SseOpcode::Pxor,
         };
 
         self.emit(Inst::XmmRmR {
@@ -985,6 +988,351 @@ impl Assembler {
         });
     }
 
+    /// Lane-wise addition of the 128-bit vectors in src and dst, with the
+    /// result put in dst.
+    pub fn xmm_vector_add_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Paddb,
+            VectorShape::I16x8 => SseOpcode::Paddw,
+            VectorShape::I32x4 => SseOpcode::Paddd,
+            VectorShape::I64x2 => SseOpcode::Paddq,
+            VectorShape::F32x4 => SseOpcode::Addps,
+            VectorShape::F64x2 => SseOpcode::Addpd,
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Lane-wise subtraction of the 128-bit vector in src from the one in dst,
+    /// with the result put in dst.
+    pub fn xmm_vector_sub_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Psubb,
+            VectorShape::I16x8 => SseOpcode::Psubw,
+            VectorShape::I32x4 => SseOpcode::Psubd,
+            VectorShape::I64x2 => SseOpcode::Psubq,
+            VectorShape::F32x4 => SseOpcode::Subps,
+            VectorShape::F64x2 => SseOpcode::Subpd,
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Lane-wise multiplication of the 128-bit vectors in src and dst, with
+    /// the result put in dst.
+    pub fn xmm_vector_mul_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I16x8 => SseOpcode::Pmullw,
+            VectorShape::I32x4 => {
+                assert!(self.isa_flags.has_sse41(), "Requires has_sse41 flag");
+                SseOpcode::Pmulld
+            }
+            VectorShape::F32x4 => SseOpcode::Mulps,
+            VectorShape::F64x2 => SseOpcode::Mulpd,
+            s => unreachable!("Unsupported vector multiplication shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Lane-wise division of the 128-bit vector in dst by the one in src, with
+    /// the result put in dst.
+    pub fn xmm_vector_div_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::F32x4 => SseOpcode::Divps,
+            VectorShape::F64x2 => SseOpcode::Divpd,
+            s => unreachable!("Unsupported vector division shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Set all the bits of the given XMM register.
+    pub fn xmm_all_ones(&mut self, dst: Reg) {
+        self.xmm_vector_rr(SseOpcode::Pcmpeqd, dst, dst);
+    }
+
+    /// Unsigned multiplication of the low 32 bits of each 64-bit lane of the
+    /// 128-bit vectors in src and dst, with the 64-bit products put in dst.
+    pub fn xmm_pmuludq_rr(&mut self, src: Reg, dst: Reg) {
+        self.xmm_vector_rr(SseOpcode::Pmuludq, src, dst);
+    }
+
+    /// Lane-wise equality comparison of the 128-bit integer vectors in src
+    /// and dst. Each lane of dst is set to all ones if the lanes are equal,
+    /// and to zero otherwise.
+    pub fn xmm_vector_cmp_eq_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Pcmpeqb,
+            VectorShape::I16x8 => SseOpcode::Pcmpeqw,
+            VectorShape::I32x4 => SseOpcode::Pcmpeqd,
+            VectorShape::I64x2 => {
+                assert!(self.isa_flags.has_sse41(), "Requires has_sse41 flag");
+                SseOpcode::Pcmpeqq
+            }
+            s => unreachable!("Unsupported vector integer comparison shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Lane-wise signed greater than comparison of the 128-bit integer
+    /// vector in dst against the one in src. Each lane of dst is set to all
+    /// ones if the comparison holds, and to zero otherwise.
+    pub fn xmm_vector_cmp_gt_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Pcmpgtb,
+            VectorShape::I16x8 => SseOpcode::Pcmpgtw,
+            VectorShape::I32x4 => SseOpcode::Pcmpgtd,
+            VectorShape::I64x2 => {
+                assert!(
+                    self.isa_flags.use_sse42(),
+                    "Requires has_sse41 and has_sse42 flags"
+                );
+                SseOpcode::Pcmpgtq
+            }
+            s => unreachable!("Unsupported vector integer comparison shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Lane-wise floating point comparison of the 128-bit vector in dst
+    /// against the one in src. Each lane of dst is set to all ones if the
+    /// comparison holds, and to zero otherwise. Only the equal, not equal,
+    /// less than and less than or equal comparisons can be encoded.
+    pub fn xmm_vector_float_cmp_rr(
+        &mut self,
+        src: Reg,
+        dst: Reg,
+        kind: FloatCmpKind,
+        shape: VectorShape,
+    ) {
+        let op = match shape {
+            VectorShape::F32x4 => SseOpcode::Cmpps,
+            VectorShape::F64x2 => SseOpcode::Cmppd,
+            s => unreachable!("Unsupported vector float comparison shape: {s:?}"),
+        };
+        let imm = match kind {
+            FloatCmpKind::Eq => 0,
+            FloatCmpKind::Lt => 1,
+            FloatCmpKind::Le => 2,
+            // Unordered or not equal, so that NaN lanes compare not equal.
+            FloatCmpKind::Ne => 4,
+            k => unreachable!("Unsupported vector float comparison: {k:?}"),
+        };
+
+        self.emit(Inst::XmmRmRImm {
+            op,
+            src1: dst.into(),
+            src2: src.into(),
+            dst: Writable::from_reg(dst.into()),
+            imm,
+            size: args::OperandSize::Size32,
+        });
+    }
+
+    /// Lane-wise shift of the 128-bit vector in dst by the amount held in the
+    /// low 64 bits of the src XMM register.
+    pub fn xmm_vector_shift_rr(&mut self, src: Reg, dst: Reg, kind: ShiftKind, shape: VectorShape) {
+        let src = XmmMemAlignedImm::new(RegMemImm::reg(src.into())).expect("valid xmm register");
+        self.xmm_vector_shift(src, dst, kind, shape);
+    }
+
+    /// Lane-wise shift of the 128-bit vector in dst by an immediate amount.
+    pub fn xmm_vector_shift_ir(&mut self, imm: u8, dst: Reg, kind: ShiftKind, shape: VectorShape) {
+        let src = XmmMemAlignedImm::new(RegMemImm::imm(imm.into())).expect("valid immediate");
+        self.xmm_vector_shift(src, dst, kind, shape);
+    }
+
+    fn xmm_vector_shift(
+        &mut self,
+        src: XmmMemAlignedImm,
+        dst: Reg,
+        kind: ShiftKind,
+        shape: VectorShape,
+    ) {
+        let opcode = match (kind, shape) {
+            (ShiftKind::Shl, VectorShape::I16x8) => SseOpcode::Psllw,
+            (ShiftKind::Shl, VectorShape::I32x4) => SseOpcode::Pslld,
+            (ShiftKind::Shl, VectorShape::I64x2) => SseOpcode::Psllq,
+            (ShiftKind::ShrU, VectorShape::I16x8) => SseOpcode::Psrlw,
+            (ShiftKind::ShrU, VectorShape::I32x4) => SseOpcode::Psrld,
+            (ShiftKind::ShrU, VectorShape::I64x2) => SseOpcode::Psrlq,
+            (ShiftKind::ShrS, VectorShape::I16x8) => SseOpcode::Psraw,
+            (ShiftKind::ShrS, VectorShape::I32x4) => SseOpcode::Psrad,
+            (_, s) => unreachable!("Unsupported vector shift shape: {s:?}"),
+        };
+
+        self.emit(Inst::XmmRmiReg {
+            opcode,
+            src1: dst.into(),
+            src2: src,
+            dst: dst.into(),
+        });
+    }
+
+    /// Shuffle the 32-bit lanes of the 128-bit vector in src as selected by
+    /// the given immediate, with the result put in dst.
+    pub fn xmm_pshufd_rri(&mut self, src: Reg, dst: Reg, imm: u8) {
+        self.xmm_shuffle_rri(SseOpcode::Pshufd, src, dst, imm);
+    }
+
+    /// Shuffle the four low 16-bit lanes of the 128-bit vector in src as
+    /// selected by the given immediate, with the result put in dst. The four
+    /// high lanes are copied unchanged.
+    pub fn xmm_pshuflw_rri(&mut self, src: Reg, dst: Reg, imm: u8) {
+        self.xmm_shuffle_rri(SseOpcode::Pshuflw, src, dst, imm);
+    }
+
+    fn xmm_shuffle_rri(&mut self, op: SseOpcode, src: Reg, dst: Reg, imm: u8) {
+        self.emit(Inst::XmmUnaryRmRImm {
+            op,
+            src: XmmMemAligned::from(Xmm::from(src)),
+            imm,
+            dst: dst.into(),
+        });
+    }
+
+    /// Shuffle the bytes of the 128-bit vector in dst using the byte indices
+    /// in src. Indices with their high bit set zero the destination byte.
+    pub fn xmm_pshufb_rr(&mut self, src: Reg, dst: Reg) {
+        assert!(self.isa_flags.has_ssse3(), "Requires has_ssse3 flag");
+        self.xmm_vector_rr(SseOpcode::Pshufb, src, dst);
+    }
+
+    /// Interleave the lanes of the low halves of the 128-bit vectors in dst
+    /// and src, with the result put in dst.
+    pub fn xmm_unpack_low_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Punpcklbw,
+            VectorShape::I16x8 => SseOpcode::Punpcklwd,
+            VectorShape::I32x4 => SseOpcode::Punpckldq,
+            VectorShape::I64x2 => SseOpcode::Punpcklqdq,
+            s => unreachable!("Unsupported vector unpack shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Interleave the lanes of the high halves of the 128-bit vectors in dst
+    /// and src, with the result put in dst.
+    pub fn xmm_unpack_high_rr(&mut self, src: Reg, dst: Reg, shape: VectorShape) {
+        let op = match shape {
+            VectorShape::I8x16 => SseOpcode::Punpckhbw,
+            VectorShape::I16x8 => SseOpcode::Punpckhwd,
+            VectorShape::I32x4 => SseOpcode::Punpckhdq,
+            VectorShape::I64x2 => SseOpcode::Punpckhqdq,
+            s => unreachable!("Unsupported vector unpack shape: {s:?}"),
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Narrow the 16-bit lanes of the 128-bit vectors in dst and src into
+    /// the 8-bit lanes of dst, with signed or unsigned saturation. The lanes
+    /// of dst come first.
+    pub fn xmm_pack_rr(&mut self, src: Reg, dst: Reg, signed: bool) {
+        let op = if signed {
+            SseOpcode::Packsswb
+        } else {
+            SseOpcode::Packuswb
+        };
+
+        self.xmm_vector_rr(op, src, dst);
+    }
+
+    /// Move the low 32 or 64 bits of the src XMM register to the dst GPR.
+    pub fn xmm_to_gpr(&mut self, src: Reg, dst: Reg, size: OperandSize) {
+        let op = match size {
+            OperandSize::S32 => SseOpcode::Movd,
+            OperandSize::S64 => SseOpcode::Movq,
+            OperandSize::S128 => unreachable!(),
+        };
+
+        self.emit(Inst::XmmToGpr {
+            op,
+            src: src.into(),
+            dst: dst.into(),
+            dst_size: size.into(),
+        });
+    }
+
+    /// Extract the given 16-bit lane of the src XMM register, zero extended,
+    /// into the dst GPR.
+    pub fn xmm_extract_i16_lane(&mut self, src: Reg, dst: Reg, lane: u8) {
+        self.emit(Inst::XmmToGprImm {
+            op: SseOpcode::Pextrw,
+            src: src.into(),
+            dst: dst.into(),
+            imm: lane,
+        });
+    }
+
+    /// Replace the given lane of the 128-bit integer vector in dst with the
+    /// value in the src GPR.
+    pub fn xmm_insert_lane_rr(&mut self, src: Reg, dst: Reg, lane: u8, shape: VectorShape) {
+        let (op, size) = match shape {
+            VectorShape::I8x16 => (SseOpcode::Pinsrb, OperandSize::S32),
+            VectorShape::I16x8 => (SseOpcode::Pinsrw, OperandSize::S32),
+            VectorShape::I32x4 => (SseOpcode::Pinsrd, OperandSize::S32),
+            VectorShape::I64x2 => (SseOpcode::Pinsrd, OperandSize::S64),
+            s => unreachable!("Unsupported vector lane insertion shape: {s:?}"),
+        };
+        if op != SseOpcode::Pinsrw {
+            assert!(self.isa_flags.has_sse41(), "Requires has_sse41 flag");
+        }
+
+        self.emit(Inst::XmmRmRImm {
+            op,
+            src1: dst.into(),
+            src2: src.into(),
+            dst: Writable::from_reg(dst.into()),
+            imm: lane,
+            size: size.into(),
+        });
+    }
+
+    /// Replace the given 32-bit lane of the 128-bit vector in dst with the
+    /// low lane of the src XMM register.
+    pub fn xmm_insertps_rr(&mut self, src: Reg, dst: Reg, lane: u8) {
+        assert!(self.isa_flags.has_sse41(), "Requires has_sse41 flag");
+        self.emit(Inst::XmmRmRImm {
+            op: SseOpcode::Insertps,
+            src1: dst.into(),
+            src2: src.into(),
+            dst: Writable::from_reg(dst.into()),
+            imm: lane << 4,
+            size: args::OperandSize::Size32,
+        });
+    }
+
+    /// Move the low 64-bit lane of the src XMM register to the low lane of
+    /// dst, leaving the high lane of dst unchanged.
+    pub fn xmm_movsd_rr(&mut self, src: Reg, dst: Reg) {
+        self.emit(Inst::XmmRmRUnaligned {
+            op: SseOpcode::Movsd,
+            src1: Xmm::from(dst).into(),
+            src2: Xmm::from(src).into(),
+            dst: dst.into(),
+        });
+    }
+
+    /// Move the low 64-bit lane of the src XMM register to the high lane of
+    /// dst, leaving the low lane of dst unchanged.
+    pub fn xmm_movlhps_rr(&mut self, src: Reg, dst: Reg) {
+        self.xmm_vector_rr(SseOpcode::Movlhps, src, dst);
+    }
+
+    fn xmm_vector_rr(&mut self, op: SseOpcode, src: Reg, dst: Reg) {
+        self.emit(Inst::XmmRmR {
+            op,
+            src1: dst.into(),
+            src2: XmmMemAligned::from(Xmm::from(src)),
+            dst: dst.into(),
+        });
+    }
+
     /// Mininum for src and dst XMM registers with results put in dst.
     pub fn xmm_min_seq(&mut self, src: Reg, dst: Reg, size: OperandSize) {
         self.emit(Inst::XmmMinMaxSeq {
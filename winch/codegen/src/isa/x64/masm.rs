@@ -6,8 +6,9 @@ use super::{
 };
 
 use crate::masm::{
-    DivKind, ExtendKind, FloatCmpKind, Imm as I, IntCmpKind, MacroAssembler as Masm, OperandSize,
-    RegImm, RemKind, RoundingMode, ShiftKind, TrapCode,
+    DivKind, ExtendKind, ExtractLaneKind, FloatCmpKind, Imm as I, IntCmpKind,
+    MacroAssembler as Masm, OperandSize, RegImm, RemKind, RoundingMode, ShiftKind, TrapCode,
+    VectorShape,
 };
 use crate::{
     abi::ABI,
@@ -23,6 +24,7 @@ use crate::{
     isa::reg::{Reg, RegClass},
     masm::CalleeKind,
 };
+use anyhow::{bail, Result};
 use cranelift_codegen::{
     isa::x64::settings as x64_settings, settings, Final, MachBufferFinalized, MachLabel,
 };
@@ -247,6 +249,11 @@ impl Masm for MacroAssembler {
                     self.asm.xmm_mov_mr(&addr, float_scratch, size);
                     self.asm.xmm_mov_rm(float_scratch, &dst, size);
                 }
+                I::V128(v) => {
+                    let addr = self.asm.add_constant(v.to_le_bytes().as_slice());
+                    self.asm.xmm_mov_mr(&addr, float_scratch, size);
+                    self.asm.xmm_mov_rm(float_scratch, &dst, size);
+                }
             },
             RegImm::Reg(reg) => {
                 if reg.is_int() {
@@ -341,6 +348,10 @@ impl Masm for MacroAssembler {
                     let addr = self.asm.add_constant(v.to_le_bytes().as_slice());
                     self.asm.xmm_mov_mr(&addr, dst, size);
                 }
+                I::V128(v) => {
+                    let addr = self.asm.add_constant(v.to_le_bytes().as_slice());
+                    self.asm.xmm_mov_mr(&addr, dst, size);
+                }
             },
         }
     }
@@ -916,6 +927,400 @@ impl Masm for MacroAssembler {
         }
     }
 
+    fn v128_and(&mut self, dst: Reg, lhs: Reg, rhs: Reg) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_and_rr(rhs, dst, OperandSize::S128);
+    }
+
+    fn v128_and_not(&mut self, dst: Reg, lhs: Reg, rhs: Reg) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        // `pandn` complements its destination operand, so compute the
+        // result in the scratch register, starting from `rhs`.
+        let scratch = regs::scratch_xmm();
+        self.asm.xmm_mov_rr(rhs, scratch, OperandSize::S128);
+        self.asm.xmm_andn_rr(lhs, scratch, OperandSize::S128);
+        self.asm.xmm_mov_rr(scratch, dst, OperandSize::S128);
+    }
+
+    fn v128_or(&mut self, dst: Reg, lhs: Reg, rhs: Reg) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_or_rr(rhs, dst, OperandSize::S128);
+    }
+
+    fn v128_xor(&mut self, dst: Reg, lhs: Reg, rhs: Reg) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_xor_rr(rhs, dst, OperandSize::S128);
+    }
+
+    fn v128_not(&mut self, dst: Reg) {
+        let scratch = regs::scratch_xmm();
+        self.asm.xmm_all_ones(scratch);
+        self.asm.xmm_xor_rr(scratch, dst, OperandSize::S128);
+    }
+
+    fn v128_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_vector_add_rr(rhs, dst, shape);
+    }
+
+    fn v128_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_vector_sub_rr(rhs, dst, shape);
+    }
+
+    fn v128_mul(&mut self, context: &mut CodeGenContext, shape: VectorShape) -> Result<()> {
+        if shape == VectorShape::I32x4 {
+            Self::ensure_isa_flag(self.flags.has_sse41(), "has_sse41")?;
+        }
+        if shape != VectorShape::I64x2 {
+            context.binop(self, OperandSize::S128, |masm, dst, src, _size| {
+                masm.asm.xmm_vector_mul_rr(src, dst, shape);
+                TypedReg::v128(dst)
+            });
+            return Ok(());
+        }
+
+        // There's no 64-bit lane multiplication before AVX-512, so build it
+        // from the 32-bit halves of each lane:
+        // lo(lhs) * lo(rhs) + ((hi(lhs) * lo(rhs) + lo(lhs) * hi(rhs)) << 32).
+        let rhs = context.pop_to_reg(self, None);
+        let lhs = context.pop_to_reg(self, None);
+        let tmp = context.reg_for_class(RegClass::Float, self);
+        let scratch = regs::scratch_xmm();
+        self.asm.xmm_mov_rr(lhs.into(), tmp, OperandSize::S128);
+        self.asm
+            .xmm_vector_shift_ir(32, tmp, ShiftKind::ShrU, VectorShape::I64x2);
+        self.asm.xmm_pmuludq_rr(rhs.into(), tmp);
+        self.asm.xmm_mov_rr(rhs.into(), scratch, OperandSize::S128);
+        self.asm
+            .xmm_vector_shift_ir(32, scratch, ShiftKind::ShrU, VectorShape::I64x2);
+        self.asm.xmm_pmuludq_rr(lhs.into(), scratch);
+        self.asm.xmm_vector_add_rr(scratch, tmp, VectorShape::I64x2);
+        self.asm
+            .xmm_vector_shift_ir(32, tmp, ShiftKind::Shl, VectorShape::I64x2);
+        self.asm.xmm_pmuludq_rr(rhs.into(), lhs.into());
+        self.asm
+            .xmm_vector_add_rr(tmp, lhs.into(), VectorShape::I64x2);
+        context.free_reg(tmp);
+        context.free_reg(rhs);
+        context.stack.push(lhs.into());
+        Ok(())
+    }
+
+    fn v128_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape) {
+        Self::ensure_two_argument_form(&dst, &lhs);
+        self.asm.xmm_vector_div_rr(rhs, dst, shape);
+    }
+
+    fn v128_splat(&mut self, context: &mut CodeGenContext, shape: VectorShape) {
+        let src = context.pop_to_reg(self, None);
+        let dst = match shape {
+            VectorShape::F32x4 | VectorShape::F64x2 => src.reg,
+            _ => {
+                let dst = context.reg_for_class(RegClass::Float, self);
+                let size = if shape == VectorShape::I64x2 {
+                    OperandSize::S64
+                } else {
+                    OperandSize::S32
+                };
+                self.asm.gpr_to_xmm(src.into(), dst, size);
+                context.free_reg(src);
+                dst
+            }
+        };
+
+        match shape {
+            VectorShape::I8x16 => {
+                // Widen the byte to the low 16-bit lane first.
+                self.asm.xmm_unpack_low_rr(dst, dst, VectorShape::I8x16);
+                self.asm.xmm_pshuflw_rri(dst, dst, 0);
+                self.asm.xmm_pshufd_rri(dst, dst, 0);
+            }
+            VectorShape::I16x8 => {
+                self.asm.xmm_pshuflw_rri(dst, dst, 0);
+                self.asm.xmm_pshufd_rri(dst, dst, 0);
+            }
+            VectorShape::I32x4 | VectorShape::F32x4 => self.asm.xmm_pshufd_rri(dst, dst, 0),
+            VectorShape::I64x2 | VectorShape::F64x2 => {
+                self.asm.xmm_pshufd_rri(dst, dst, 0b01_00_01_00)
+            }
+        }
+        context.stack.push(TypedReg::v128(dst).into());
+    }
+
+    fn v128_extract_lane(&mut self, context: &mut CodeGenContext, lane: u8, kind: ExtractLaneKind) {
+        let src = context.pop_to_reg(self, None);
+        // Moves the selected 32-bit or 64-bit lane to the low lane of `src`.
+        let shuffle_to_low_lane = |masm: &mut Self, lane_imm: u8| {
+            if lane != 0 {
+                masm.asm.xmm_pshufd_rri(src.into(), src.into(), lane_imm);
+            }
+        };
+
+        let dst = match kind {
+            ExtractLaneKind::F32x4 => {
+                shuffle_to_low_lane(self, lane);
+                TypedReg::f32(src.reg)
+            }
+            ExtractLaneKind::F64x2 => {
+                shuffle_to_low_lane(self, 0b11_10_11_10);
+                TypedReg::f64(src.reg)
+            }
+            _ => {
+                let dst = context.any_gpr(self);
+                let dst = match kind {
+                    ExtractLaneKind::I32x4 => {
+                        shuffle_to_low_lane(self, lane);
+                        self.asm.xmm_to_gpr(src.into(), dst, OperandSize::S32);
+                        TypedReg::i32(dst)
+                    }
+                    ExtractLaneKind::I64x2 => {
+                        shuffle_to_low_lane(self, 0b11_10_11_10);
+                        self.asm.xmm_to_gpr(src.into(), dst, OperandSize::S64);
+                        TypedReg::i64(dst)
+                    }
+                    ExtractLaneKind::I16x8S | ExtractLaneKind::I16x8U => {
+                        self.asm.xmm_extract_i16_lane(src.into(), dst, lane);
+                        if kind == ExtractLaneKind::I16x8S {
+                            self.asm.movsx_rr(dst, dst, ExtendKind::I32Extend16S);
+                        }
+                        TypedReg::i32(dst)
+                    }
+                    ExtractLaneKind::I8x16S | ExtractLaneKind::I8x16U => {
+                        // There's no byte extraction before SSE4.1, so extract
+                        // the 16-bit lane holding the byte.
+                        self.asm.xmm_extract_i16_lane(src.into(), dst, lane / 2);
+                        if lane % 2 == 1 {
+                            self.asm.shift_ir(8, dst, ShiftKind::ShrU, OperandSize::S32);
+                        }
+                        if kind == ExtractLaneKind::I8x16S {
+                            self.asm.movsx_rr(dst, dst, ExtendKind::I32Extend8S);
+                        } else if lane % 2 == 0 {
+                            self.asm.and_ir(0xff, dst, OperandSize::S32);
+                        }
+                        TypedReg::i32(dst)
+                    }
+                    ExtractLaneKind::F32x4 | ExtractLaneKind::F64x2 => unreachable!(),
+                };
+                context.free_reg(src);
+                dst
+            }
+        };
+        context.stack.push(dst.into());
+    }
+
+    fn v128_replace_lane(
+        &mut self,
+        context: &mut CodeGenContext,
+        lane: u8,
+        shape: VectorShape,
+    ) -> Result<()> {
+        match shape {
+            VectorShape::I16x8 | VectorShape::F64x2 => {}
+            _ => Self::ensure_isa_flag(self.flags.has_sse41(), "has_sse41")?,
+        }
+
+        let src = context.pop_to_reg(self, None);
+        let dst = context.pop_to_reg(self, None);
+        match shape {
+            VectorShape::F32x4 => self.asm.xmm_insertps_rr(src.into(), dst.into(), lane),
+            VectorShape::F64x2 if lane == 0 => self.asm.xmm_movsd_rr(src.into(), dst.into()),
+            VectorShape::F64x2 => self.asm.xmm_movlhps_rr(src.into(), dst.into()),
+            _ => self
+                .asm
+                .xmm_insert_lane_rr(src.into(), dst.into(), lane, shape),
+        }
+        context.free_reg(src);
+        context.stack.push(dst.into());
+        Ok(())
+    }
+
+    fn v128_int_cmp(
+        &mut self,
+        context: &mut CodeGenContext,
+        kind: IntCmpKind,
+        shape: VectorShape,
+    ) -> Result<()> {
+        if shape == VectorShape::I64x2 {
+            match kind {
+                IntCmpKind::Eq | IntCmpKind::Ne => {
+                    Self::ensure_isa_flag(self.flags.has_sse41(), "has_sse41")?
+                }
+                _ => {
+                    Self::ensure_isa_flag(self.flags.has_sse41(), "has_sse41")?;
+                    Self::ensure_isa_flag(self.flags.has_sse42(), "has_sse42")?
+                }
+            }
+        }
+
+        let rhs: Reg = context.pop_to_reg(self, None).into();
+        let lhs: Reg = context.pop_to_reg(self, None).into();
+        if let IntCmpKind::LtU | IntCmpKind::GtU | IntCmpKind::LeU | IntCmpKind::GeU = kind {
+            // There are no unsigned lane comparisons, so flip the sign bit of
+            // every lane and compare them as signed integers instead.
+            let lane_bits = match shape {
+                VectorShape::I8x16 => 8,
+                VectorShape::I16x8 => 16,
+                VectorShape::I32x4 => 32,
+                s => unreachable!("Unsupported unsigned vector comparison shape: {s:?}"),
+            };
+            let sign_bits = (u128::MAX / ((1u128 << lane_bits) - 1)) << (lane_bits - 1);
+            let scratch = regs::scratch_xmm();
+            self.mov(RegImm::v128(sign_bits as i128), scratch, OperandSize::S128);
+            self.asm.xmm_xor_rr(scratch, lhs, OperandSize::S128);
+            self.asm.xmm_xor_rr(scratch, rhs, OperandSize::S128);
+        }
+
+        match kind {
+            IntCmpKind::Eq => self.asm.xmm_vector_cmp_eq_rr(rhs, lhs, shape),
+            IntCmpKind::Ne => {
+                self.asm.xmm_vector_cmp_eq_rr(rhs, lhs, shape);
+                self.v128_not(lhs);
+            }
+            IntCmpKind::GtS | IntCmpKind::GtU => self.asm.xmm_vector_cmp_gt_rr(rhs, lhs, shape),
+            IntCmpKind::LeS | IntCmpKind::LeU => {
+                self.asm.xmm_vector_cmp_gt_rr(rhs, lhs, shape);
+                self.v128_not(lhs);
+            }
+            IntCmpKind::LtS | IntCmpKind::LtU => {
+                self.asm.xmm_vector_cmp_gt_rr(lhs, rhs, shape);
+                self.asm.xmm_mov_rr(rhs, lhs, OperandSize::S128);
+            }
+            IntCmpKind::GeS | IntCmpKind::GeU => {
+                self.asm.xmm_vector_cmp_gt_rr(lhs, rhs, shape);
+                self.v128_not(rhs);
+                self.asm.xmm_mov_rr(rhs, lhs, OperandSize::S128);
+            }
+        }
+        context.free_reg(rhs);
+        context.stack.push(TypedReg::v128(lhs).into());
+        Ok(())
+    }
+
+    fn v128_float_cmp(
+        &mut self,
+        context: &mut CodeGenContext,
+        kind: FloatCmpKind,
+        shape: VectorShape,
+    ) {
+        context.binop(self, OperandSize::S128, |masm, dst, src, _size| {
+            // Greater than comparisons can't be encoded, so swap the operands
+            // of the corresponding less than comparisons.
+            match kind {
+                FloatCmpKind::Gt | FloatCmpKind::Ge => {
+                    let kind = if let FloatCmpKind::Gt = kind {
+                        FloatCmpKind::Lt
+                    } else {
+                        FloatCmpKind::Le
+                    };
+                    masm.asm.xmm_vector_float_cmp_rr(dst, src, kind, shape);
+                    masm.asm.xmm_mov_rr(src, dst, OperandSize::S128);
+                }
+                kind => masm.asm.xmm_vector_float_cmp_rr(src, dst, kind, shape),
+            }
+            TypedReg::v128(dst)
+        });
+    }
+
+    fn v128_shift(&mut self, context: &mut CodeGenContext, kind: ShiftKind, shape: VectorShape) {
+        let amount = context.pop_to_reg(self, None);
+        let src = context.pop_to_reg(self, None);
+        let lane_bits = match shape {
+            VectorShape::I8x16 => 8,
+            VectorShape::I16x8 => 16,
+            VectorShape::I32x4 => 32,
+            VectorShape::I64x2 => 64,
+            s => unreachable!("Unsupported vector shift shape: {s:?}"),
+        };
+        // The shift amount is taken modulo the lane width.
+        self.asm
+            .and_ir(lane_bits - 1, amount.into(), OperandSize::S32);
+        let scratch = regs::scratch_xmm();
+
+        match (shape, kind) {
+            (VectorShape::I8x16, kind) => {
+                // There are no 8-bit lane shifts, so duplicate each byte into
+                // a 16-bit lane, shift it by 8 more bits so that the result
+                // lands in the low byte, and narrow the lanes back.
+                let tmp = context.reg_for_class(RegClass::Float, self);
+                self.asm.add_ir(8, amount.into(), OperandSize::S32);
+                self.asm
+                    .gpr_to_xmm(amount.into(), scratch, OperandSize::S32);
+                self.asm.xmm_mov_rr(src.into(), tmp, OperandSize::S128);
+                self.asm.xmm_unpack_low_rr(tmp, tmp, VectorShape::I8x16);
+                self.asm
+                    .xmm_unpack_high_rr(src.into(), src.into(), VectorShape::I8x16);
+                for reg in [tmp, src.reg] {
+                    self.asm
+                        .xmm_vector_shift_rr(scratch, reg, kind, VectorShape::I16x8);
+                    if let ShiftKind::Shl = kind {
+                        self.asm
+                            .xmm_vector_shift_ir(8, reg, ShiftKind::ShrU, VectorShape::I16x8);
+                    }
+                }
+                let signed = matches!(kind, ShiftKind::ShrS);
+                self.asm.xmm_pack_rr(src.into(), tmp, signed);
+                self.asm.xmm_mov_rr(tmp, src.into(), OperandSize::S128);
+                context.free_reg(tmp);
+            }
+            (VectorShape::I64x2, ShiftKind::ShrS) => {
+                // There's no 64-bit lane arithmetic shift before AVX-512, so
+                // shift logically and sign extend the result with
+                // `(x ^ m) - m`, where `m` is the sign bit shifted by the same
+                // amount.
+                let tmp = context.reg_for_class(RegClass::Float, self);
+                self.mov(RegImm::v128(i128::MIN | (1 << 63)), tmp, OperandSize::S128);
+                self.asm
+                    .gpr_to_xmm(amount.into(), scratch, OperandSize::S32);
+                self.asm
+                    .xmm_vector_shift_rr(scratch, tmp, ShiftKind::ShrU, shape);
+                self.asm
+                    .xmm_vector_shift_rr(scratch, src.into(), ShiftKind::ShrU, shape);
+                self.asm.xmm_xor_rr(tmp, src.into(), OperandSize::S128);
+                self.asm.xmm_vector_sub_rr(tmp, src.into(), shape);
+                context.free_reg(tmp);
+            }
+            (shape, kind) => {
+                self.asm
+                    .gpr_to_xmm(amount.into(), scratch, OperandSize::S32);
+                self.asm
+                    .xmm_vector_shift_rr(scratch, src.into(), kind, shape);
+            }
+        }
+        context.free_reg(amount);
+        context.stack.push(src.into());
+    }
+
+    fn v128_shuffle(&mut self, context: &mut CodeGenContext, lanes: [u8; 16]) -> Result<()> {
+        Self::ensure_isa_flag(self.flags.has_ssse3(), "has_ssse3")?;
+
+        // `pshufb` selects bytes from a single vector, zeroing the bytes whose
+        // index has the high bit set, so shuffle each vector separately and
+        // combine the results.
+        let mut lhs_mask = [0x80u8; 16];
+        let mut rhs_mask = [0x80u8; 16];
+        for (i, lane) in lanes.iter().enumerate() {
+            if *lane < 16 {
+                lhs_mask[i] = *lane;
+            } else {
+                rhs_mask[i] = *lane - 16;
+            }
+        }
+
+        context.binop(self, OperandSize::S128, |masm, dst, src, _size| {
+            let scratch = regs::scratch_xmm();
+            let lhs_mask = RegImm::v128(i128::from_le_bytes(lhs_mask));
+            masm.mov(lhs_mask, scratch, OperandSize::S128);
+            masm.asm.xmm_pshufb_rr(scratch, dst);
+            let rhs_mask = RegImm::v128(i128::from_le_bytes(rhs_mask));
+            masm.mov(rhs_mask, scratch, OperandSize::S128);
+            masm.asm.xmm_pshufb_rr(scratch, src);
+            masm.asm.xmm_or_rr(src, dst, OperandSize::S128);
+            TypedReg::v128(dst)
+        });
+        Ok(())
+    }
+
     fn unreachable(&mut self) {
         self.asm.trap(TrapCode::UnreachableCodeReached)
     }
@@ -995,6 +1400,15 @@ impl MacroAssembler {
         );
     }
 
+    /// Returns an error if an ISA extension required to lower an operator
+    /// isn't enabled.
+    fn ensure_isa_flag(enabled: bool, flag: &str) -> Result<()> {
+        if !enabled {
+            bail!("Winch requires the `{flag}` ISA flag to lower this operator");
+        }
+        Ok(())
+    }
+
     fn ensure_two_argument_form(dst: &Reg, lhs: &Reg) {
         assert!(
             dst == lhs,
@@ -1,6 +1,7 @@
 use crate::abi::{self, align_to, LocalSlot};
 use crate::codegen::{ptr_type_from_ptr_size, CodeGenContext, TableData};
 use crate::isa::reg::Reg;
+use anyhow::Result;
 use cranelift_codegen::{ir::LibCall, Final, MachBufferFinalized, MachLabel};
 use std::{fmt::Debug, ops::Range};
 use wasmtime_environ::PtrSize;
@@ -82,7 +83,7 @@ pub(crate) enum IntCmpKind {
 /// Kinds of float binary comparison in WebAssembly. The [`MacroAssembler`]
 /// implementation for each ISA is responsible for emitting the correct
 /// sequence of instructions when lowering code.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub(crate) enum FloatCmpKind {
     /// Equal.
     Eq,
@@ -98,9 +99,48 @@ pub(crate) enum FloatCmpKind {
     Ge,
 }
 
+/// The lane shape of a 128-bit vector, used by lane-wise vector operations.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum VectorShape {
+    /// Sixteen 8-bit integer lanes.
+    I8x16,
+    /// Eight 16-bit integer lanes.
+    I16x8,
+    /// Four 32-bit integer lanes.
+    I32x4,
+    /// Two 64-bit integer lanes.
+    I64x2,
+    /// Four single precision floating point lanes.
+    F32x4,
+    /// Two double precision floating point lanes.
+    F64x2,
+}
+
+/// Kinds of lane extraction from a 128-bit vector in WebAssembly.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum ExtractLaneKind {
+    /// Sign extends an 8-bit lane to i32.
+    I8x16S,
+    /// Zero extends an 8-bit lane to i32.
+    I8x16U,
+    /// Sign extends a 16-bit lane to i32.
+    I16x8S,
+    /// Zero extends a 16-bit lane to i32.
+    I16x8U,
+    /// Extracts a 32-bit integer lane.
+    I32x4,
+    /// Extracts a 64-bit integer lane.
+    I64x2,
+    /// Extracts a single precision floating point lane.
+    F32x4,
+    /// Extracts a double precision floating point lane.
+    F64x2,
+}
+
 /// Kinds of shifts in WebAssembly.The [`masm`] implementation for each ISA is
 /// responsible for emitting the correct sequence of instructions when
 /// lowering to machine code.
+#[derive(Clone, Copy)]
 pub(crate) enum ShiftKind {
     /// Left shift.
     Shl,
@@ -205,6 +245,8 @@ pub(crate) enum Imm {
     F32(u32),
     /// F64 immediate.
     F64(u64),
+    /// V128 immediate.
+    V128(i128),
 }
 
 impl Imm {
@@ -232,6 +274,11 @@ impl Imm {
         Self::F64(bits)
     }
 
+    /// Create a new V128 immediate.
+    pub fn v128(val: i128) -> Self {
+        Self::V128(val)
+    }
+
     /// Convert the immediate to i32, if possible.
     pub fn to_i32(&self) -> Option<i32> {
         match self {
@@ -298,6 +345,11 @@ impl RegImm {
     pub fn f64(bits: u64) -> Self {
         RegImm::Imm(Imm::f64(bits))
     }
+
+    /// V128 immediate.
+    pub fn v128(val: i128) -> Self {
+        RegImm::Imm(Imm::v128(val))
+    }
 }
 
 impl From<Reg> for RegImm {
@@ -606,6 +658,85 @@ pub(crate) trait MacroAssembler {
     /// Extends an integer of a given size to a larger size.
     fn extend(&mut self, src: Reg, dst: Reg, kind: ExtendKind);
 
+    /// Perform a bitwise and of two 128-bit vectors.
+    fn v128_and(&mut self, dst: Reg, lhs: Reg, rhs: Reg);
+
+    /// Perform a bitwise and of the first 128-bit vector with the
+    /// complement of the second one.
+    fn v128_and_not(&mut self, dst: Reg, lhs: Reg, rhs: Reg);
+
+    /// Perform a bitwise or of two 128-bit vectors.
+    fn v128_or(&mut self, dst: Reg, lhs: Reg, rhs: Reg);
+
+    /// Perform a bitwise exclusive or of two 128-bit vectors.
+    fn v128_xor(&mut self, dst: Reg, lhs: Reg, rhs: Reg);
+
+    /// Perform a bitwise not of a 128-bit vector.
+    fn v128_not(&mut self, dst: Reg);
+
+    /// Perform a lane-wise addition of two 128-bit vectors.
+    fn v128_add(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape);
+
+    /// Perform a lane-wise subtraction of two 128-bit vectors.
+    fn v128_sub(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape);
+
+    /// Perform a lane-wise multiplication of the two 128-bit vectors at the
+    /// top of the value stack. Returns an error if the multiplication can't
+    /// be lowered with the enabled ISA extensions.
+    fn v128_mul(&mut self, context: &mut CodeGenContext, shape: VectorShape) -> Result<()>;
+
+    /// Perform a lane-wise floating point division of two 128-bit vectors.
+    fn v128_div(&mut self, dst: Reg, lhs: Reg, rhs: Reg, shape: VectorShape);
+
+    /// Replicate the scalar at the top of the value stack to all the lanes of
+    /// a 128-bit vector.
+    fn v128_splat(&mut self, context: &mut CodeGenContext, shape: VectorShape);
+
+    /// Extract the given lane of the 128-bit vector at the top of the value
+    /// stack.
+    fn v128_extract_lane(&mut self, context: &mut CodeGenContext, lane: u8, kind: ExtractLaneKind);
+
+    /// Replace the given lane of a 128-bit vector with the scalar at the top
+    /// of the value stack. Returns an error if the lane can't be replaced with
+    /// the enabled ISA extensions.
+    fn v128_replace_lane(
+        &mut self,
+        context: &mut CodeGenContext,
+        lane: u8,
+        shape: VectorShape,
+    ) -> Result<()>;
+
+    /// Perform a lane-wise integer comparison of the two 128-bit vectors at
+    /// the top of the value stack, setting each lane of the result to all
+    /// ones if the comparison holds and to zero otherwise. Returns an error if
+    /// the comparison can't be lowered with the enabled ISA extensions.
+    fn v128_int_cmp(
+        &mut self,
+        context: &mut CodeGenContext,
+        kind: IntCmpKind,
+        shape: VectorShape,
+    ) -> Result<()>;
+
+    /// Perform a lane-wise floating point comparison of the two 128-bit
+    /// vectors at the top of the value stack, setting each lane of the result
+    /// to all ones if the comparison holds and to zero otherwise.
+    fn v128_float_cmp(
+        &mut self,
+        context: &mut CodeGenContext,
+        kind: FloatCmpKind,
+        shape: VectorShape,
+    );
+
+    /// Shift each lane of a 128-bit vector by the amount at the top of the
+    /// value stack, modulo the lane width.
+    fn v128_shift(&mut self, context: &mut CodeGenContext, kind: ShiftKind, shape: VectorShape);
+
+    /// Select the bytes of the result from the two 128-bit vectors at the
+    /// top of the value stack, indexing the bytes of the first vector with
+    /// 0-15 and the ones of the second with 16-31. Returns an error if the
+    /// shuffle can't be lowered with the enabled ISA extensions.
+    fn v128_shuffle(&mut self, context: &mut CodeGenContext, lanes: [u8; 16]) -> Result<()>;
+
     /// Zero a given memory range.
     ///
     /// The default implementation divides the given memory range
@@ -6,15 +6,17 @@
 
 use crate::abi::ABI;
 use crate::codegen::{control_index, Callee, CodeGen, ControlStackFrame, FnCall};
+use crate::isa::reg::Reg;
 use crate::masm::{
-    DivKind, ExtendKind, FloatCmpKind, IntCmpKind, MacroAssembler, OperandSize, RegImm, RemKind,
-    RoundingMode, ShiftKind,
+    DivKind, ExtendKind, ExtractLaneKind, FloatCmpKind, IntCmpKind, MacroAssembler, OperandSize,
+    RegImm, RemKind, RoundingMode, ShiftKind, VectorShape,
 };
 use crate::stack::{TypedReg, Val};
+use anyhow::anyhow;
 use cranelift_codegen::ir::TrapCode;
 use smallvec::SmallVec;
 use wasmparser::BrTable;
-use wasmparser::{BlockType, Ieee32, Ieee64, VisitOperator};
+use wasmparser::{BlockType, Ieee32, Ieee64, VisitOperator, V128};
 use wasmtime_environ::{
     FuncIndex, GlobalIndex, TableIndex, TableStyle, TypeIndex, WasmHeapType, WasmType,
     FUNCREF_INIT_BIT,
@@ -24,8 +26,8 @@ use wasmtime_environ::{
 ///
 /// This macro calls itself recursively;
 /// 1. It no-ops when matching a supported operator.
-/// 2. Defines the visitor function and panics when
-/// matching an unsupported operator.
+/// 2. Defines the visitor function and records a compilation
+/// error when matching an unsupported operator.
 macro_rules! def_unsupported {
     ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident)*) => {
         $(
@@ -35,7 +37,10 @@ macro_rules! def_unsupported {
 
                 fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
                     $($(let _ = $arg;)*)?
-                        todo!(stringify!($op))
+                    self.handle_lowering_result(Err(anyhow!(
+                        "Unsupported Wasm operator: {}",
+                        stringify!($op)
+                    )));
                 }
             );
         )*
@@ -179,6 +184,112 @@ macro_rules! def_unsupported {
     (emit TableSize $($rest:tt)*) => {};
     (emit TableFill $($rest:tt)*) => {};
     (emit ElemDrop $($rest:tt)*) => {};
+    (emit V128Const $($rest:tt)*) => {};
+    (emit V128Not $($rest:tt)*) => {};
+    (emit V128And $($rest:tt)*) => {};
+    (emit V128AndNot $($rest:tt)*) => {};
+    (emit V128Or $($rest:tt)*) => {};
+    (emit V128Xor $($rest:tt)*) => {};
+    (emit I8x16Add $($rest:tt)*) => {};
+    (emit I8x16Sub $($rest:tt)*) => {};
+    (emit I16x8Add $($rest:tt)*) => {};
+    (emit I16x8Sub $($rest:tt)*) => {};
+    (emit I16x8Mul $($rest:tt)*) => {};
+    (emit I32x4Add $($rest:tt)*) => {};
+    (emit I32x4Sub $($rest:tt)*) => {};
+    (emit I64x2Add $($rest:tt)*) => {};
+    (emit I64x2Sub $($rest:tt)*) => {};
+    (emit F32x4Add $($rest:tt)*) => {};
+    (emit F32x4Sub $($rest:tt)*) => {};
+    (emit F32x4Mul $($rest:tt)*) => {};
+    (emit F32x4Div $($rest:tt)*) => {};
+    (emit F64x2Add $($rest:tt)*) => {};
+    (emit F64x2Sub $($rest:tt)*) => {};
+    (emit F64x2Mul $($rest:tt)*) => {};
+    (emit F64x2Div $($rest:tt)*) => {};
+    (emit I8x16Shuffle $($rest:tt)*) => {};
+    (emit I8x16ExtractLaneS $($rest:tt)*) => {};
+    (emit I8x16ExtractLaneU $($rest:tt)*) => {};
+    (emit I8x16ReplaceLane $($rest:tt)*) => {};
+    (emit I16x8ExtractLaneS $($rest:tt)*) => {};
+    (emit I16x8ExtractLaneU $($rest:tt)*) => {};
+    (emit I16x8ReplaceLane $($rest:tt)*) => {};
+    (emit I32x4ExtractLane $($rest:tt)*) => {};
+    (emit I32x4ReplaceLane $($rest:tt)*) => {};
+    (emit I64x2ExtractLane $($rest:tt)*) => {};
+    (emit I64x2ReplaceLane $($rest:tt)*) => {};
+    (emit F32x4ExtractLane $($rest:tt)*) => {};
+    (emit F32x4ReplaceLane $($rest:tt)*) => {};
+    (emit F64x2ExtractLane $($rest:tt)*) => {};
+    (emit F64x2ReplaceLane $($rest:tt)*) => {};
+    (emit I8x16Splat $($rest:tt)*) => {};
+    (emit I16x8Splat $($rest:tt)*) => {};
+    (emit I32x4Splat $($rest:tt)*) => {};
+    (emit I64x2Splat $($rest:tt)*) => {};
+    (emit F32x4Splat $($rest:tt)*) => {};
+    (emit F64x2Splat $($rest:tt)*) => {};
+    (emit I8x16Eq $($rest:tt)*) => {};
+    (emit I8x16Ne $($rest:tt)*) => {};
+    (emit I8x16LtS $($rest:tt)*) => {};
+    (emit I8x16LtU $($rest:tt)*) => {};
+    (emit I8x16GtS $($rest:tt)*) => {};
+    (emit I8x16GtU $($rest:tt)*) => {};
+    (emit I8x16LeS $($rest:tt)*) => {};
+    (emit I8x16LeU $($rest:tt)*) => {};
+    (emit I8x16GeS $($rest:tt)*) => {};
+    (emit I8x16GeU $($rest:tt)*) => {};
+    (emit I16x8Eq $($rest:tt)*) => {};
+    (emit I16x8Ne $($rest:tt)*) => {};
+    (emit I16x8LtS $($rest:tt)*) => {};
+    (emit I16x8LtU $($rest:tt)*) => {};
+    (emit I16x8GtS $($rest:tt)*) => {};
+    (emit I16x8GtU $($rest:tt)*) => {};
+    (emit I16x8LeS $($rest:tt)*) => {};
+    (emit I16x8LeU $($rest:tt)*) => {};
+    (emit I16x8GeS $($rest:tt)*) => {};
+    (emit I16x8GeU $($rest:tt)*) => {};
+    (emit I32x4Eq $($rest:tt)*) => {};
+    (emit I32x4Ne $($rest:tt)*) => {};
+    (emit I32x4LtS $($rest:tt)*) => {};
+    (emit I32x4LtU $($rest:tt)*) => {};
+    (emit I32x4GtS $($rest:tt)*) => {};
+    (emit I32x4GtU $($rest:tt)*) => {};
+    (emit I32x4LeS $($rest:tt)*) => {};
+    (emit I32x4LeU $($rest:tt)*) => {};
+    (emit I32x4GeS $($rest:tt)*) => {};
+    (emit I32x4GeU $($rest:tt)*) => {};
+    (emit I64x2Eq $($rest:tt)*) => {};
+    (emit I64x2Ne $($rest:tt)*) => {};
+    (emit I64x2LtS $($rest:tt)*) => {};
+    (emit I64x2GtS $($rest:tt)*) => {};
+    (emit I64x2LeS $($rest:tt)*) => {};
+    (emit I64x2GeS $($rest:tt)*) => {};
+    (emit F32x4Eq $($rest:tt)*) => {};
+    (emit F32x4Ne $($rest:tt)*) => {};
+    (emit F32x4Lt $($rest:tt)*) => {};
+    (emit F32x4Gt $($rest:tt)*) => {};
+    (emit F32x4Le $($rest:tt)*) => {};
+    (emit F32x4Ge $($rest:tt)*) => {};
+    (emit F64x2Eq $($rest:tt)*) => {};
+    (emit F64x2Ne $($rest:tt)*) => {};
+    (emit F64x2Lt $($rest:tt)*) => {};
+    (emit F64x2Gt $($rest:tt)*) => {};
+    (emit F64x2Le $($rest:tt)*) => {};
+    (emit F64x2Ge $($rest:tt)*) => {};
+    (emit I8x16Shl $($rest:tt)*) => {};
+    (emit I8x16ShrS $($rest:tt)*) => {};
+    (emit I8x16ShrU $($rest:tt)*) => {};
+    (emit I16x8Shl $($rest:tt)*) => {};
+    (emit I16x8ShrS $($rest:tt)*) => {};
+    (emit I16x8ShrU $($rest:tt)*) => {};
+    (emit I32x4Shl $($rest:tt)*) => {};
+    (emit I32x4ShrS $($rest:tt)*) => {};
+    (emit I32x4ShrU $($rest:tt)*) => {};
+    (emit I64x2Shl $($rest:tt)*) => {};
+    (emit I64x2ShrS $($rest:tt)*) => {};
+    (emit I64x2ShrU $($rest:tt)*) => {};
+    (emit I32x4Mul $($rest:tt)*) => {};
+    (emit I64x2Mul $($rest:tt)*) => {};
 
     (emit $unsupported:tt $($rest:tt)*) => {$($rest)*};
 }
@@ -1023,7 +1134,7 @@ where
             .get_local(index)
             .unwrap_or_else(|| panic!("valid local at slot = {}", index));
         match slot.ty {
-            I32 | I64 | F32 | F64 => context.stack.push(Val::local(index, slot.ty)),
+            I32 | I64 | F32 | F64 | V128 => context.stack.push(Val::local(index, slot.ty)),
             Ref(rt) => match rt.heap_type {
                 WasmHeapType::Func => context.stack.push(Val::local(index, slot.ty)),
                 ht => unimplemented!("Support for WasmHeapType: {ht}"),
@@ -1277,6 +1388,10 @@ where
             self.masm,
             &mut self.context,
         ));
+
+        // The value stack is fully spilled on loop entry, so it's safe to
+        // emit the conditional builtin calls for the checks.
+        self.emit_fuel_and_epoch_checks();
     }
 
     fn visit_br(&mut self, depth: u32) {
@@ -1476,6 +1591,485 @@ where
         self.context.free_reg(cond);
     }
 
+    fn visit_v128_const(&mut self, val: V128) {
+        self.context.stack.push(Val::v128(val.i128()));
+    }
+
+    fn visit_v128_not(&mut self) {
+        self.context
+            .unop(self.masm, OperandSize::S128, &mut |masm, reg, _size| {
+                masm.v128_not(reg);
+                TypedReg::v128(reg)
+            });
+    }
+
+    fn visit_v128_and(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_and(dst, dst, src));
+    }
+
+    fn visit_v128_andnot(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_and_not(dst, dst, src));
+    }
+
+    fn visit_v128_or(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_or(dst, dst, src));
+    }
+
+    fn visit_v128_xor(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_xor(dst, dst, src));
+    }
+
+    fn visit_i8x16_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::I8x16));
+    }
+
+    fn visit_i8x16_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::I8x16));
+    }
+
+    fn visit_i16x8_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::I16x8));
+    }
+
+    fn visit_i16x8_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::I16x8));
+    }
+
+    fn visit_i16x8_mul(&mut self) {
+        self.v128_mul(VectorShape::I16x8);
+    }
+
+    fn visit_i32x4_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::I32x4));
+    }
+
+    fn visit_i32x4_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::I32x4));
+    }
+
+    fn visit_i64x2_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::I64x2));
+    }
+
+    fn visit_i64x2_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::I64x2));
+    }
+
+    fn visit_f32x4_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::F32x4));
+    }
+
+    fn visit_f32x4_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::F32x4));
+    }
+
+    fn visit_f32x4_mul(&mut self) {
+        self.v128_mul(VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_div(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_div(dst, dst, src, VectorShape::F32x4));
+    }
+
+    fn visit_f64x2_add(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_add(dst, dst, src, VectorShape::F64x2));
+    }
+
+    fn visit_f64x2_sub(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_sub(dst, dst, src, VectorShape::F64x2));
+    }
+
+    fn visit_f64x2_mul(&mut self) {
+        self.v128_mul(VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_div(&mut self) {
+        self.v128_binop(|masm, dst, src| masm.v128_div(dst, dst, src, VectorShape::F64x2));
+    }
+
+    fn visit_i32x4_mul(&mut self) {
+        self.v128_mul(VectorShape::I32x4);
+    }
+
+    fn visit_i64x2_mul(&mut self) {
+        self.v128_mul(VectorShape::I64x2);
+    }
+
+    fn visit_i8x16_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::I8x16);
+    }
+
+    fn visit_i16x8_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::I16x8);
+    }
+
+    fn visit_i32x4_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::I32x4);
+    }
+
+    fn visit_i64x2_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::I64x2);
+    }
+
+    fn visit_f32x4_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::F32x4);
+    }
+
+    fn visit_f64x2_splat(&mut self) {
+        self.masm.v128_splat(&mut self.context, VectorShape::F64x2);
+    }
+
+    fn visit_i8x16_extract_lane_s(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I8x16S);
+    }
+
+    fn visit_i8x16_extract_lane_u(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I8x16U);
+    }
+
+    fn visit_i16x8_extract_lane_s(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I16x8S);
+    }
+
+    fn visit_i16x8_extract_lane_u(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I16x8U);
+    }
+
+    fn visit_i32x4_extract_lane(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I32x4);
+    }
+
+    fn visit_i64x2_extract_lane(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::I64x2);
+    }
+
+    fn visit_f32x4_extract_lane(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::F32x4);
+    }
+
+    fn visit_f64x2_extract_lane(&mut self, lane: u8) {
+        self.masm
+            .v128_extract_lane(&mut self.context, lane, ExtractLaneKind::F64x2);
+    }
+
+    fn visit_i8x16_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::I8x16);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_i16x8_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::I16x8);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_i32x4_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::I32x4);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_i64x2_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::I64x2);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_f32x4_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::F32x4);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_f64x2_replace_lane(&mut self, lane: u8) {
+        let result = self
+            .masm
+            .v128_replace_lane(&mut self.context, lane, VectorShape::F64x2);
+        self.handle_lowering_result(result);
+    }
+
+    fn visit_i8x16_eq(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Eq, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_ne(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Ne, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_lt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtS, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_lt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtU, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_gt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtS, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_gt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtU, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_le_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeS, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_le_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeU, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_ge_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeS, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_ge_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeU, VectorShape::I8x16);
+    }
+
+    fn visit_i16x8_eq(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Eq, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_ne(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Ne, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_lt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtS, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_lt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtU, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_gt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtS, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_gt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtU, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_le_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeS, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_le_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeU, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_ge_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeS, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_ge_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeU, VectorShape::I16x8);
+    }
+
+    fn visit_i32x4_eq(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Eq, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_ne(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Ne, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_lt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtS, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_lt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtU, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_gt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtS, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_gt_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtU, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_le_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeS, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_le_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeU, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_ge_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeS, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_ge_u(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeU, VectorShape::I32x4);
+    }
+
+    fn visit_i64x2_eq(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Eq, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_ne(&mut self) {
+        self.v128_int_cmp(IntCmpKind::Ne, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_lt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LtS, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_gt_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GtS, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_le_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::LeS, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_ge_s(&mut self) {
+        self.v128_int_cmp(IntCmpKind::GeS, VectorShape::I64x2);
+    }
+
+    fn visit_f32x4_eq(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Eq, VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_ne(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Ne, VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_lt(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Lt, VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_gt(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Gt, VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_le(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Le, VectorShape::F32x4);
+    }
+
+    fn visit_f32x4_ge(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Ge, VectorShape::F32x4);
+    }
+
+    fn visit_f64x2_eq(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Eq, VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_ne(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Ne, VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_lt(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Lt, VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_gt(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Gt, VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_le(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Le, VectorShape::F64x2);
+    }
+
+    fn visit_f64x2_ge(&mut self) {
+        self.masm
+            .v128_float_cmp(&mut self.context, FloatCmpKind::Ge, VectorShape::F64x2);
+    }
+
+    fn visit_i8x16_shl(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::Shl, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_shr_s(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrS, VectorShape::I8x16);
+    }
+
+    fn visit_i8x16_shr_u(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrU, VectorShape::I8x16);
+    }
+
+    fn visit_i16x8_shl(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::Shl, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_shr_s(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrS, VectorShape::I16x8);
+    }
+
+    fn visit_i16x8_shr_u(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrU, VectorShape::I16x8);
+    }
+
+    fn visit_i32x4_shl(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::Shl, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_shr_s(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrS, VectorShape::I32x4);
+    }
+
+    fn visit_i32x4_shr_u(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrU, VectorShape::I32x4);
+    }
+
+    fn visit_i64x2_shl(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::Shl, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_shr_s(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrS, VectorShape::I64x2);
+    }
+
+    fn visit_i64x2_shr_u(&mut self) {
+        self.masm
+            .v128_shift(&mut self.context, ShiftKind::ShrU, VectorShape::I64x2);
+    }
+
+    fn visit_i8x16_shuffle(&mut self, lanes: [u8; 16]) {
+        let result = self.masm.v128_shuffle(&mut self.context, lanes);
+        self.handle_lowering_result(result);
+    }
+
     wasmparser::for_each_operator!(def_unsupported);
 }
 
@@ -1497,6 +2091,30 @@ where
                 TypedReg::i32(dst) // Return value for comparisons is an `i32`.
             });
     }
+
+    fn v128_mul(&mut self, shape: VectorShape) {
+        let result = self.masm.v128_mul(&mut self.context, shape);
+        self.handle_lowering_result(result);
+    }
+
+    fn v128_int_cmp(&mut self, kind: IntCmpKind, shape: VectorShape) {
+        let result = self.masm.v128_int_cmp(&mut self.context, kind, shape);
+        self.handle_lowering_result(result);
+    }
+
+    fn v128_binop<F>(&mut self, mut emit: F)
+    where
+        F: FnMut(&mut M, Reg, Reg),
+    {
+        self.context.binop(
+            self.masm,
+            OperandSize::S128,
+            &mut |masm: &mut M, dst, src, _size| {
+                emit(masm, dst, src);
+                TypedReg::v128(dst)
+            },
+        );
+    }
 }
 
 impl From<WasmType> for OperandSize {
@@ -1504,6 +2122,7 @@ impl From<WasmType> for OperandSize {
         match ty {
             WasmType::I32 | WasmType::F32 => OperandSize::S32,
             WasmType::I64 | WasmType::F64 => OperandSize::S64,
+            WasmType::V128 => OperandSize::S128,
             WasmType::Ref(rt) => {
                 match rt.heap_type {
                     // TODO: Harcoded size, assuming 64-bit support only. Once
@@ -0,0 +1,29 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "") (param f32) (result f32)
+    local.get 0
+    block
+    end
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300bc             	stur	s0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 9fc340bc             	ldur	s31, [x28, #0xc]
+;;   24:	 ff1300d1             	sub	sp, sp, #4
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 9f0300bc             	stur	s31, [x28]
+;;   30:	 800340bc             	ldur	s0, [x28]
+;;   34:	 ff130091             	add	sp, sp, #4
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 ff430091             	add	sp, sp, #0x10
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   48:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   4c:	 c0035fd6             	ret	
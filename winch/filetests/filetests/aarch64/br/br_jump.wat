@@ -0,0 +1,39 @@
+;;! target = "aarch64"
+(module
+  (func (;0;) (result i32)
+    (local i32)
+    local.get 0
+    loop ;; label = @1
+      local.get 0
+      block ;; label = @2
+      end
+      br 0 (;@1;)
+    end
+  )
+  (export "" (func 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   28:	 ff1300d1             	sub	sp, sp, #4
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 900300b8             	stur	w16, [x28]
+;;   34:	 900341b8             	ldur	w16, [x28, #0x10]
+;;   38:	 ff1300d1             	sub	sp, sp, #4
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 900300b8             	stur	w16, [x28]
+;;   44:	 ff130091             	add	sp, sp, #4
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 faffff17             	b	#0x34
+;;   50:	 ff430091             	add	sp, sp, #0x10
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
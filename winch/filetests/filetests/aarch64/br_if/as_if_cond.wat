@@ -0,0 +1,37 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-if-cond") (param i32) (result i32)
+    (block (result i32)
+      (if (result i32)
+        (br_if 0 (i32.const 1) (local.get 0))
+        (then (i32.const 2))
+        (else (i32.const 3))
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   24:	 300080d2             	mov	x16, #1
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 3f000071             	cmp	w1, #0
+;;   30:	 01010054             	b.ne	#0x50
+;;   34:	 1f000071             	cmp	w0, #0
+;;   38:	 80000054             	b.eq	#0x48
+;;   3c:	 500080d2             	mov	x16, #2
+;;   40:	 e003102a             	mov	w0, w16
+;;   44:	 03000014             	b	#0x50
+;;   48:	 700080d2             	mov	x16, #3
+;;   4c:	 e003102a             	mov	w0, w16
+;;   50:	 ff430091             	add	sp, sp, #0x10
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   5c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   60:	 c0035fd6             	ret	
@@ -0,0 +1,34 @@
+;;! target = "aarch64"
+(module
+  (func (export "as-local-set-value") (param i32) (result i32)
+    (local i32)
+    (block (result i32)
+      (local.set 0 (br_if 0 (i32.const 17) (local.get 0)))
+      (i32.const -1)
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 100080d2             	mov	x16, #0
+;;   20:	 908300b8             	stur	w16, [x28, #8]
+;;   24:	 100080d2             	mov	x16, #0
+;;   28:	 890300f8             	stur	x9, [x28]
+;;   2c:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   30:	 300280d2             	mov	x16, #0x11
+;;   34:	 e003102a             	mov	w0, w16
+;;   38:	 3f000071             	cmp	w1, #0
+;;   3c:	 81000054             	b.ne	#0x4c
+;;   40:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   44:	 f07f40b2             	orr	x16, xzr, #0xffffffff
+;;   48:	 e003102a             	mov	w0, w16
+;;   4c:	 ff430091             	add	sp, sp, #0x10
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   58:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   5c:	 c0035fd6             	ret	
@@ -0,0 +1,62 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "") 
+    call 1
+    call 1
+    br_if 0
+    drop
+  )
+  (func (;1;) (result i32)
+    i32.const 1
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 00000094             	bl	#0x24
+;;   28:	 898340f8             	ldur	x9, [x28, #8]
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 ff1300d1             	sub	sp, sp, #4
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 800300b8             	stur	w0, [x28]
+;;   40:	 ff1300d1             	sub	sp, sp, #4
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 00000094             	bl	#0x48
+;;   4c:	 898340f8             	ldur	x9, [x28, #8]
+;;   50:	 ff130091             	add	sp, sp, #4
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 1f000071             	cmp	w0, #0
+;;   5c:	 80000054             	b.eq	#0x6c
+;;   60:	 ff130091             	add	sp, sp, #4
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 03000014             	b	#0x74
+;;   6c:	 ff130091             	add	sp, sp, #4
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 ff230091             	add	sp, sp, #8
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   80:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   84:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
@@ -0,0 +1,60 @@
+;;! target = "aarch64"
+(module
+  (func (export "nested-br_table-loop-block") (param i32) (result i32)
+    (local.set 0
+      (loop (result i32)
+        (block
+          (br_table 1 0 0 (local.get 0))
+        )
+        (i32.const 0)
+      )
+    )
+    (loop (result i32)
+      (block
+        (br_table 0 1 1 (local.get 0))
+      )
+      (i32.const 3)
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f080071             	cmp	w0, #2
+;;   28:	 42010054             	b.hs	#0x50
+;;   2c:	 e123809a             	csel	x1, xzr, x0, hs
+;;   30:	 9f2203d5             	csdb	
+;;   34:	 90000010             	adr	x16, #0x44
+;;   38:	 015aa1b8             	ldrsw	x1, [x16, w1, uxtw #2]
+;;   3c:	 1002018b             	add	x16, x16, x1
+;;   40:	 00021fd6             	br	x16
+;;   44:	 dcffffff             	.byte	0xdc, 0xff, 0xff, 0xff
+;;   48:	 0c000000             	.byte	0x0c, 0x00, 0x00, 0x00
+;;   4c:	 f5ffff17             	b	#0x20
+;;   50:	 100080d2             	mov	x16, #0
+;;   54:	 e003102a             	mov	w0, w16
+;;   58:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   5c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   60:	 1f080071             	cmp	w0, #2
+;;   64:	 c2ffff54             	b.hs	#0x5c
+;;   68:	 e123809a             	csel	x1, xzr, x0, hs
+;;   6c:	 9f2203d5             	csdb	
+;;   70:	 90000010             	adr	x16, #0x80
+;;   74:	 015aa1b8             	ldrsw	x1, [x16, w1, uxtw #2]
+;;   78:	 1002018b             	add	x16, x16, x1
+;;   7c:	 00021fd6             	br	x16
+;;   80:	 08000000             	.byte	0x08, 0x00, 0x00, 0x00
+;;   84:	 dcffffff             	.byte	0xdc, 0xff, 0xff, 0xff
+;;   88:	 700080d2             	mov	x16, #3
+;;   8c:	 e003102a             	mov	w0, w16
+;;   90:	 ff430091             	add	sp, sp, #0x10
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   9c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   a0:	 c0035fd6             	ret	
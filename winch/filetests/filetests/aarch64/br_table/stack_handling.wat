@@ -0,0 +1,48 @@
+;;! target = "aarch64"
+(module
+  (func (;0;) (param i32)
+    local.get 0
+    block ;; label = @1
+      i32.const 808727609
+      br_table 0 (;@1;) 1 (;@0;) 0 (;@1;)
+    end
+    drop
+  )
+  (export "main" (func 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   24:	 ff1300d1             	sub	sp, sp, #4
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 900300b8             	stur	w16, [x28]
+;;   30:	 30878652             	mov	w16, #0x3439
+;;   34:	 9006a672             	movk	w16, #0x3034, lsl #16
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 1f080071             	cmp	w0, #2
+;;   40:	 a2010054             	b.hs	#0x74
+;;   44:	 e123809a             	csel	x1, xzr, x0, hs
+;;   48:	 9f2203d5             	csdb	
+;;   4c:	 90000010             	adr	x16, #0x5c
+;;   50:	 015aa1b8             	ldrsw	x1, [x16, w1, uxtw #2]
+;;   54:	 1002018b             	add	x16, x16, x1
+;;   58:	 00021fd6             	br	x16
+;;   5c:	 18000000             	.byte	0x18, 0x00, 0x00, 0x00
+;;   60:	 0c000000             	.byte	0x0c, 0x00, 0x00, 0x00
+;;   64:	 04000014             	b	#0x74
+;;   68:	 ff130091             	add	sp, sp, #4
+;;   6c:	 fc030091             	mov	x28, sp
+;;   70:	 03000014             	b	#0x7c
+;;   74:	 ff130091             	add	sp, sp, #4
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 ff430091             	add	sp, sp, #0x10
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   88:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   8c:	 c0035fd6             	ret	
@@ -0,0 +1,166 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "main") (param i32) (param i32) (result i32)
+    (local.get 1)
+    (local.get 0)
+    (i32.div_u)
+
+    (call $add (i32.const 1) (i32.const 2) (i32.const 3) (i32.const 4) (i32.const 5) (i32.const 6) (i32.const 7) (i32.const 8))
+
+    (local.get 1)
+    (local.get 0)
+    (i32.div_u)
+
+    (call $add (i32.const 2) (i32.const 3) (i32.const 4) (i32.const 5) (i32.const 6) (i32.const 7) (i32.const 8))
+  )
+
+  (func $add (param i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+    (local.get 0)
+    (local.get 1)
+    (i32.add)
+    (local.get 2)
+    (i32.add)
+    (local.get 3)
+    (i32.add)
+    (local.get 4)
+    (i32.add)
+    (local.get 5)
+    (i32.add)
+    (local.get 6)
+    (i32.add)
+    (local.get 7)
+    (i32.add)
+    (local.get 8)
+    (i32.add)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 818340b8             	ldur	w1, [x28, #8]
+;;   2c:	 1f000071             	cmp	w0, #0
+;;   30:	 80090054             	b.eq	#0x160
+;;   34:	 e003002a             	mov	w0, w0
+;;   38:	 e103012a             	mov	w1, w1
+;;   3c:	 2108c09a             	udiv	x1, x1, x0
+;;   40:	 ff1300d1             	sub	sp, sp, #4
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 810300b8             	stur	w1, [x28]
+;;   4c:	 ff7300d1             	sub	sp, sp, #0x1c
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 80c341b8             	ldur	w0, [x28, #0x1c]
+;;   58:	 300080d2             	mov	x16, #1
+;;   5c:	 e103102a             	mov	w1, w16
+;;   60:	 500080d2             	mov	x16, #2
+;;   64:	 e203102a             	mov	w2, w16
+;;   68:	 700080d2             	mov	x16, #3
+;;   6c:	 e303102a             	mov	w3, w16
+;;   70:	 900080d2             	mov	x16, #4
+;;   74:	 e403102a             	mov	w4, w16
+;;   78:	 b00080d2             	mov	x16, #5
+;;   7c:	 e503102a             	mov	w5, w16
+;;   80:	 d00080d2             	mov	x16, #6
+;;   84:	 e603102a             	mov	w6, w16
+;;   88:	 f00080d2             	mov	x16, #7
+;;   8c:	 e703102a             	mov	w7, w16
+;;   90:	 100180d2             	mov	x16, #8
+;;   94:	 f003102a             	mov	w16, w16
+;;   98:	 900300b8             	stur	w16, [x28]
+;;   9c:	 00000094             	bl	#0x9c
+;;   a0:	 890342f8             	ldur	x9, [x28, #0x20]
+;;   a4:	 ff730091             	add	sp, sp, #0x1c
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 ff130091             	add	sp, sp, #4
+;;   b0:	 fc030091             	mov	x28, sp
+;;   b4:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   b8:	 828340b8             	ldur	w2, [x28, #8]
+;;   bc:	 3f000071             	cmp	w1, #0
+;;   c0:	 20050054             	b.eq	#0x164
+;;   c4:	 e103012a             	mov	w1, w1
+;;   c8:	 e203022a             	mov	w2, w2
+;;   cc:	 4208c19a             	udiv	x2, x2, x1
+;;   d0:	 ff1300d1             	sub	sp, sp, #4
+;;   d4:	 fc030091             	mov	x28, sp
+;;   d8:	 800300b8             	stur	w0, [x28]
+;;   dc:	 ff1300d1             	sub	sp, sp, #4
+;;   e0:	 fc030091             	mov	x28, sp
+;;   e4:	 820300b8             	stur	w2, [x28]
+;;   e8:	 ff6300d1             	sub	sp, sp, #0x18
+;;   ec:	 fc030091             	mov	x28, sp
+;;   f0:	 80c341b8             	ldur	w0, [x28, #0x1c]
+;;   f4:	 818341b8             	ldur	w1, [x28, #0x18]
+;;   f8:	 500080d2             	mov	x16, #2
+;;   fc:	 e203102a             	mov	w2, w16
+;;  100:	 700080d2             	mov	x16, #3
+;;  104:	 e303102a             	mov	w3, w16
+;;  108:	 900080d2             	mov	x16, #4
+;;  10c:	 e403102a             	mov	w4, w16
+;;  110:	 b00080d2             	mov	x16, #5
+;;  114:	 e503102a             	mov	w5, w16
+;;  118:	 d00080d2             	mov	x16, #6
+;;  11c:	 e603102a             	mov	w6, w16
+;;  120:	 f00080d2             	mov	x16, #7
+;;  124:	 e703102a             	mov	w7, w16
+;;  128:	 100180d2             	mov	x16, #8
+;;  12c:	 f003102a             	mov	w16, w16
+;;  130:	 900300b8             	stur	w16, [x28]
+;;  134:	 00000094             	bl	#0x134
+;;  138:	 890342f8             	ldur	x9, [x28, #0x20]
+;;  13c:	 ff630091             	add	sp, sp, #0x18
+;;  140:	 fc030091             	mov	x28, sp
+;;  144:	 ff230091             	add	sp, sp, #8
+;;  148:	 fc030091             	mov	x28, sp
+;;  14c:	 ff430091             	add	sp, sp, #0x10
+;;  150:	 fc030091             	mov	x28, sp
+;;  154:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  158:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  15c:	 c0035fd6             	ret	
+;;  160:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  164:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ffa300d1             	sub	sp, sp, #0x28
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804302b8             	stur	w0, [x28, #0x24]
+;;   1c:	 810302b8             	stur	w1, [x28, #0x20]
+;;   20:	 82c301b8             	stur	w2, [x28, #0x1c]
+;;   24:	 838301b8             	stur	w3, [x28, #0x18]
+;;   28:	 844301b8             	stur	w4, [x28, #0x14]
+;;   2c:	 850301b8             	stur	w5, [x28, #0x10]
+;;   30:	 86c300b8             	stur	w6, [x28, #0xc]
+;;   34:	 878300b8             	stur	w7, [x28, #8]
+;;   38:	 890300f8             	stur	x9, [x28]
+;;   3c:	 800342b8             	ldur	w0, [x28, #0x20]
+;;   40:	 814342b8             	ldur	w1, [x28, #0x24]
+;;   44:	 2160200b             	add	w1, w1, w0, uxtx
+;;   48:	 80c341b8             	ldur	w0, [x28, #0x1c]
+;;   4c:	 2160200b             	add	w1, w1, w0, uxtx
+;;   50:	 808341b8             	ldur	w0, [x28, #0x18]
+;;   54:	 2160200b             	add	w1, w1, w0, uxtx
+;;   58:	 804341b8             	ldur	w0, [x28, #0x14]
+;;   5c:	 2160200b             	add	w1, w1, w0, uxtx
+;;   60:	 800341b8             	ldur	w0, [x28, #0x10]
+;;   64:	 2160200b             	add	w1, w1, w0, uxtx
+;;   68:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   6c:	 2160200b             	add	w1, w1, w0, uxtx
+;;   70:	 808340b8             	ldur	w0, [x28, #8]
+;;   74:	 2160200b             	add	w1, w1, w0, uxtx
+;;   78:	 a00341b8             	ldur	w0, [x29, #0x10]
+;;   7c:	 2160200b             	add	w1, w1, w0, uxtx
+;;   80:	 e003012a             	mov	w0, w1
+;;   84:	 ffa30091             	add	sp, sp, #0x28
+;;   88:	 fc030091             	mov	x28, sp
+;;   8c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   90:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   94:	 c0035fd6             	ret	
@@ -0,0 +1,80 @@
+;;! target = "aarch64"
+
+(module
+  (func $fibonacci8 (param $n i32) (result i32)
+    (if (result i32) (i32.le_s (local.get $n) (i32.const 1))
+      (then
+        ;; If n <= 1, return n (base case)
+        (local.get $n)
+      )
+      (else
+        ;; Else, return fibonacci(n - 1) + fibonacci(n - 2)
+        (i32.add
+          (call $fibonacci8
+            (i32.sub (local.get $n) (i32.const 1)) ;; Calculate n - 1
+          )
+          (call $fibonacci8
+            (i32.sub (local.get $n) (i32.const 2)) ;; Calculate n - 2
+          )
+        )
+      )
+    )
+  )
+  (export "fib" (func $fibonacci8))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f040071             	cmp	w0, #1
+;;   28:	 e0c79f9a             	cset	x0, le
+;;   2c:	 1f000071             	cmp	w0, #0
+;;   30:	 60000054             	b.eq	#0x3c
+;;   34:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   38:	 25000014             	b	#0xcc
+;;   3c:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   40:	 00040051             	sub	w0, w0, #1
+;;   44:	 ff1300d1             	sub	sp, sp, #4
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 800300b8             	stur	w0, [x28]
+;;   50:	 ff3300d1             	sub	sp, sp, #0xc
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   5c:	 00000094             	bl	#0x5c
+;;   60:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   64:	 ff330091             	add	sp, sp, #0xc
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 ff130091             	add	sp, sp, #4
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   78:	 21080051             	sub	w1, w1, #2
+;;   7c:	 ff1300d1             	sub	sp, sp, #4
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 800300b8             	stur	w0, [x28]
+;;   88:	 ff1300d1             	sub	sp, sp, #4
+;;   8c:	 fc030091             	mov	x28, sp
+;;   90:	 810300b8             	stur	w1, [x28]
+;;   94:	 ff2300d1             	sub	sp, sp, #8
+;;   98:	 fc030091             	mov	x28, sp
+;;   9c:	 808340b8             	ldur	w0, [x28, #8]
+;;   a0:	 00000094             	bl	#0xa0
+;;   a4:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   a8:	 ff230091             	add	sp, sp, #8
+;;   ac:	 fc030091             	mov	x28, sp
+;;   b0:	 ff130091             	add	sp, sp, #4
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 810340b8             	ldur	w1, [x28]
+;;   bc:	 ff130091             	add	sp, sp, #4
+;;   c0:	 fc030091             	mov	x28, sp
+;;   c4:	 2160200b             	add	w1, w1, w0, uxtx
+;;   c8:	 e003012a             	mov	w0, w1
+;;   cc:	 ff430091             	add	sp, sp, #0x10
+;;   d0:	 fc030091             	mov	x28, sp
+;;   d4:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   d8:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   dc:	 c0035fd6             	ret	
@@ -0,0 +1,64 @@
+;;! target = "aarch64"
+
+(module
+  (func $main (result i32)
+    (local $var i32)
+    (call $product (i32.const 20) (i32.const 80))
+    (local.set $var (i32.const 2))
+    (local.get $var)
+    (i32.div_u))
+
+  (func $product (param i32 i32) (result i32)
+    (local.get 0)
+    (local.get 1)
+    (i32.mul))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 908300f8             	stur	x16, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 900280d2             	mov	x16, #0x14
+;;   28:	 e003102a             	mov	w0, w16
+;;   2c:	 100a80d2             	mov	x16, #0x50
+;;   30:	 e103102a             	mov	w1, w16
+;;   34:	 00000094             	bl	#0x34
+;;   38:	 890340f8             	ldur	x9, [x28]
+;;   3c:	 500080d2             	mov	x16, #2
+;;   40:	 e103102a             	mov	w1, w16
+;;   44:	 81c300b8             	stur	w1, [x28, #0xc]
+;;   48:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   4c:	 3f000071             	cmp	w1, #0
+;;   50:	 20010054             	b.eq	#0x74
+;;   54:	 e103012a             	mov	w1, w1
+;;   58:	 e003002a             	mov	w0, w0
+;;   5c:	 0008c19a             	udiv	x0, x0, x1
+;;   60:	 ff430091             	add	sp, sp, #0x10
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   6c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   70:	 c0035fd6             	ret	
+;;   74:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340b8             	ldur	w0, [x28, #8]
+;;   28:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   2c:	 217c001b             	mul	w1, w1, w0
+;;   30:	 e003012a             	mov	w0, w1
+;;   34:	 ff430091             	add	sp, sp, #0x10
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   40:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   44:	 c0035fd6             	ret	
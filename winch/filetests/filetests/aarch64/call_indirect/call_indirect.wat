@@ -0,0 +1,188 @@
+;;! target = "aarch64"
+
+(module
+  (type $over-i32 (func (param i32) (result i32)))
+
+  (table funcref
+    (elem
+      $fib-i32
+    )
+  )
+  
+  (func $fib-i32 (export "fib-i32") (type $over-i32)
+    (if (result i32) (i32.le_u (local.get 0) (i32.const 1))
+      (then (i32.const 1))
+      (else
+        (i32.add
+          (call_indirect (type $over-i32)
+            (i32.sub (local.get 0) (i32.const 2))
+            (i32.const 0)
+          )
+          (call_indirect (type $over-i32)
+            (i32.sub (local.get 0) (i32.const 1))
+            (i32.const 0)
+          )
+        )
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f040071             	cmp	w0, #1
+;;   28:	 e0879f9a             	cset	x0, ls
+;;   2c:	 1f000071             	cmp	w0, #0
+;;   30:	 80000054             	b.eq	#0x40
+;;   34:	 300080d2             	mov	x16, #1
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 85000014             	b	#0x250
+;;   40:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   44:	 00080051             	sub	w0, w0, #2
+;;   48:	 ff1300d1             	sub	sp, sp, #4
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 800300b8             	stur	w0, [x28]
+;;   54:	 100080d2             	mov	x16, #0
+;;   58:	 e103102a             	mov	w1, w16
+;;   5c:	 e20309aa             	mov	x2, x9
+;;   60:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   64:	 3f00036b             	cmp	w1, w3
+;;   68:	 e20f0054             	b.hs	#0x264
+;;   6c:	 30f07dd3             	lsl	x16, x1, #3
+;;   70:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   74:	 e40302aa             	mov	x4, x2
+;;   78:	 4260308b             	add	x2, x2, x16, uxtx
+;;   7c:	 3f00036b             	cmp	w1, w3
+;;   80:	 8220829a             	csel	x2, x4, x2, hs
+;;   84:	 400040f8             	ldur	x0, [x2]
+;;   88:	 1f0000f1             	cmp	x0, #0
+;;   8c:	 41020054             	b.ne	#0xd4
+;;   90:	 ff2300d1             	sub	sp, sp, #8
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 890300f8             	stur	x9, [x28]
+;;   9c:	 ff1300d1             	sub	sp, sp, #4
+;;   a0:	 fc030091             	mov	x28, sp
+;;   a4:	 810300b8             	stur	w1, [x28]
+;;   a8:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   ac:	 038244f8             	ldur	x3, [x16, #0x48]
+;;   b0:	 804340f8             	ldur	x0, [x28, #4]
+;;   b4:	 100080d2             	mov	x16, #0
+;;   b8:	 e103102a             	mov	w1, w16
+;;   bc:	 820340b8             	ldur	w2, [x28]
+;;   c0:	 60003fd6             	blr	x3
+;;   c4:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   c8:	 ff330091             	add	sp, sp, #0xc
+;;   cc:	 fc030091             	mov	x28, sp
+;;   d0:	 02000014             	b	#0xd8
+;;   d4:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   d8:	 800c00b4             	cbz	x0, #0x268
+;;   dc:	 300144f8             	ldur	x16, [x9, #0x40]
+;;   e0:	 010240b8             	ldur	w1, [x16]
+;;   e4:	 028041b8             	ldur	w2, [x0, #0x18]
+;;   e8:	 3f00026b             	cmp	w1, w2
+;;   ec:	 010c0054             	b.ne	#0x26c
+;;   f0:	 ff2300d1             	sub	sp, sp, #8
+;;   f4:	 fc030091             	mov	x28, sp
+;;   f8:	 800300f8             	stur	x0, [x28]
+;;   fc:	 810340f8             	ldur	x1, [x28]
+;;  100:	 ff230091             	add	sp, sp, #8
+;;  104:	 fc030091             	mov	x28, sp
+;;  108:	 220041f8             	ldur	x2, [x1, #0x10]
+;;  10c:	 ff3300d1             	sub	sp, sp, #0xc
+;;  110:	 fc030091             	mov	x28, sp
+;;  114:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;  118:	 40003fd6             	blr	x2
+;;  11c:	 890341f8             	ldur	x9, [x28, #0x10]
+;;  120:	 ff330091             	add	sp, sp, #0xc
+;;  124:	 fc030091             	mov	x28, sp
+;;  128:	 ff130091             	add	sp, sp, #4
+;;  12c:	 fc030091             	mov	x28, sp
+;;  130:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;  134:	 21040051             	sub	w1, w1, #1
+;;  138:	 ff1300d1             	sub	sp, sp, #4
+;;  13c:	 fc030091             	mov	x28, sp
+;;  140:	 800300b8             	stur	w0, [x28]
+;;  144:	 ff1300d1             	sub	sp, sp, #4
+;;  148:	 fc030091             	mov	x28, sp
+;;  14c:	 810300b8             	stur	w1, [x28]
+;;  150:	 100080d2             	mov	x16, #0
+;;  154:	 e103102a             	mov	w1, w16
+;;  158:	 e20309aa             	mov	x2, x9
+;;  15c:	 438045b8             	ldur	w3, [x2, #0x58]
+;;  160:	 3f00036b             	cmp	w1, w3
+;;  164:	 62080054             	b.hs	#0x270
+;;  168:	 30f07dd3             	lsl	x16, x1, #3
+;;  16c:	 420045f8             	ldur	x2, [x2, #0x50]
+;;  170:	 e40302aa             	mov	x4, x2
+;;  174:	 4260308b             	add	x2, x2, x16, uxtx
+;;  178:	 3f00036b             	cmp	w1, w3
+;;  17c:	 8220829a             	csel	x2, x4, x2, hs
+;;  180:	 400040f8             	ldur	x0, [x2]
+;;  184:	 1f0000f1             	cmp	x0, #0
+;;  188:	 c1020054             	b.ne	#0x1e0
+;;  18c:	 ff2300d1             	sub	sp, sp, #8
+;;  190:	 fc030091             	mov	x28, sp
+;;  194:	 890300f8             	stur	x9, [x28]
+;;  198:	 ff1300d1             	sub	sp, sp, #4
+;;  19c:	 fc030091             	mov	x28, sp
+;;  1a0:	 810300b8             	stur	w1, [x28]
+;;  1a4:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  1a8:	 038244f8             	ldur	x3, [x16, #0x48]
+;;  1ac:	 ff3300d1             	sub	sp, sp, #0xc
+;;  1b0:	 fc030091             	mov	x28, sp
+;;  1b4:	 800341f8             	ldur	x0, [x28, #0x10]
+;;  1b8:	 100080d2             	mov	x16, #0
+;;  1bc:	 e103102a             	mov	w1, w16
+;;  1c0:	 82c340b8             	ldur	w2, [x28, #0xc]
+;;  1c4:	 60003fd6             	blr	x3
+;;  1c8:	 890342f8             	ldur	x9, [x28, #0x20]
+;;  1cc:	 ff330091             	add	sp, sp, #0xc
+;;  1d0:	 fc030091             	mov	x28, sp
+;;  1d4:	 ff330091             	add	sp, sp, #0xc
+;;  1d8:	 fc030091             	mov	x28, sp
+;;  1dc:	 02000014             	b	#0x1e4
+;;  1e0:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;  1e4:	 800400b4             	cbz	x0, #0x274
+;;  1e8:	 300144f8             	ldur	x16, [x9, #0x40]
+;;  1ec:	 010240b8             	ldur	w1, [x16]
+;;  1f0:	 028041b8             	ldur	w2, [x0, #0x18]
+;;  1f4:	 3f00026b             	cmp	w1, w2
+;;  1f8:	 01040054             	b.ne	#0x278
+;;  1fc:	 ff2300d1             	sub	sp, sp, #8
+;;  200:	 fc030091             	mov	x28, sp
+;;  204:	 800300f8             	stur	x0, [x28]
+;;  208:	 810340f8             	ldur	x1, [x28]
+;;  20c:	 ff230091             	add	sp, sp, #8
+;;  210:	 fc030091             	mov	x28, sp
+;;  214:	 220041f8             	ldur	x2, [x1, #0x10]
+;;  218:	 ff2300d1             	sub	sp, sp, #8
+;;  21c:	 fc030091             	mov	x28, sp
+;;  220:	 808340b8             	ldur	w0, [x28, #8]
+;;  224:	 40003fd6             	blr	x2
+;;  228:	 890341f8             	ldur	x9, [x28, #0x10]
+;;  22c:	 ff230091             	add	sp, sp, #8
+;;  230:	 fc030091             	mov	x28, sp
+;;  234:	 ff130091             	add	sp, sp, #4
+;;  238:	 fc030091             	mov	x28, sp
+;;  23c:	 810340b8             	ldur	w1, [x28]
+;;  240:	 ff130091             	add	sp, sp, #4
+;;  244:	 fc030091             	mov	x28, sp
+;;  248:	 2160200b             	add	w1, w1, w0, uxtx
+;;  24c:	 e003012a             	mov	w0, w1
+;;  250:	 ff430091             	add	sp, sp, #0x10
+;;  254:	 fc030091             	mov	x28, sp
+;;  258:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  25c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  260:	 c0035fd6             	ret	
+;;  264:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  268:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  26c:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  270:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  274:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  278:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
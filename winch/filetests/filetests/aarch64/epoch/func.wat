@@ -0,0 +1,38 @@
+;;! target = "aarch64"
+;;! epoch_interruption = true
+
+(module
+    (func (result i32)
+        (i32.const 1)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 208141f8             	ldur	x0, [x9, #0x18]
+;;   20:	 000040f8             	ldur	x0, [x0]
+;;   24:	 218140f8             	ldur	x1, [x9, #8]
+;;   28:	 210041f8             	ldur	x1, [x1, #0x10]
+;;   2c:	 1f0001eb             	cmp	x0, x1
+;;   30:	 63010054             	b.lo	#0x5c
+;;   34:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   38:	 01024bf8             	ldur	x1, [x16, #0xb0]
+;;   3c:	 ff2300d1             	sub	sp, sp, #8
+;;   40:	 fc030091             	mov	x28, sp
+;;   44:	 890300f8             	stur	x9, [x28]
+;;   48:	 800340f8             	ldur	x0, [x28]
+;;   4c:	 20003fd6             	blr	x1
+;;   50:	 898340f8             	ldur	x9, [x28, #8]
+;;   54:	 ff230091             	add	sp, sp, #8
+;;   58:	 fc030091             	mov	x28, sp
+;;   5c:	 300080d2             	mov	x16, #1
+;;   60:	 e003102a             	mov	w0, w16
+;;   64:	 ff230091             	add	sp, sp, #8
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   70:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   74:	 c0035fd6             	ret	
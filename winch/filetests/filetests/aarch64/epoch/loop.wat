@@ -0,0 +1,74 @@
+;;! target = "aarch64"
+;;! epoch_interruption = true
+
+(module
+    (func (param i32) (result i32)
+        (loop
+            (local.get 0)
+            (i32.const 1)
+            (i32.sub)
+            (local.tee 0)
+            (br_if 0)
+        )
+        (local.get 0)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 208141f8             	ldur	x0, [x9, #0x18]
+;;   24:	 000040f8             	ldur	x0, [x0]
+;;   28:	 218140f8             	ldur	x1, [x9, #8]
+;;   2c:	 210041f8             	ldur	x1, [x1, #0x10]
+;;   30:	 1f0001eb             	cmp	x0, x1
+;;   34:	 e3010054             	b.lo	#0x70
+;;   38:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   3c:	 01024bf8             	ldur	x1, [x16, #0xb0]
+;;   40:	 ff2300d1             	sub	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 890300f8             	stur	x9, [x28]
+;;   4c:	 ff2300d1             	sub	sp, sp, #8
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 808340f8             	ldur	x0, [x28, #8]
+;;   58:	 20003fd6             	blr	x1
+;;   5c:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   60:	 ff230091             	add	sp, sp, #8
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 ff230091             	add	sp, sp, #8
+;;   6c:	 fc030091             	mov	x28, sp
+;;   70:	 208141f8             	ldur	x0, [x9, #0x18]
+;;   74:	 000040f8             	ldur	x0, [x0]
+;;   78:	 218140f8             	ldur	x1, [x9, #8]
+;;   7c:	 210041f8             	ldur	x1, [x1, #0x10]
+;;   80:	 1f0001eb             	cmp	x0, x1
+;;   84:	 e3010054             	b.lo	#0xc0
+;;   88:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   8c:	 01024bf8             	ldur	x1, [x16, #0xb0]
+;;   90:	 ff2300d1             	sub	sp, sp, #8
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 890300f8             	stur	x9, [x28]
+;;   9c:	 ff2300d1             	sub	sp, sp, #8
+;;   a0:	 fc030091             	mov	x28, sp
+;;   a4:	 808340f8             	ldur	x0, [x28, #8]
+;;   a8:	 20003fd6             	blr	x1
+;;   ac:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   b0:	 ff230091             	add	sp, sp, #8
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 ff230091             	add	sp, sp, #8
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   c4:	 00040051             	sub	w0, w0, #1
+;;   c8:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   cc:	 1f000071             	cmp	w0, #0
+;;   d0:	 01fdff54             	b.ne	#0x70
+;;   d4:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   d8:	 ff430091             	add	sp, sp, #0x10
+;;   dc:	 fc030091             	mov	x28, sp
+;;   e0:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   e4:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   e8:	 c0035fd6             	ret	
@@ -0,0 +1,49 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        (local $foo f64)  
+        (local $bar f64)
+
+        (f64.const 1.1)
+        (local.set $foo)
+
+        (f64.const 2.2)
+        (local.set $bar)
+
+        (local.get $foo)
+        (local.get $bar)
+        f64.le
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 100080d2             	mov	x16, #0
+;;   1c:	 900301f8             	stur	x16, [x28, #0x10]
+;;   20:	 908300f8             	stur	x16, [x28, #8]
+;;   24:	 890300f8             	stur	x9, [x28]
+;;   28:	 503393d2             	mov	x16, #0x999a
+;;   2c:	 3033b3f2             	movk	x16, #0x9999, lsl #16
+;;   30:	 3033d3f2             	movk	x16, #0x9999, lsl #32
+;;   34:	 30fee7f2             	movk	x16, #0x3ff1, lsl #48
+;;   38:	 0002679e             	fmov	d0, x16
+;;   3c:	 800301fc             	stur	d0, [x28, #0x10]
+;;   40:	 503393d2             	mov	x16, #0x999a
+;;   44:	 3033b3f2             	movk	x16, #0x9999, lsl #16
+;;   48:	 3033d3f2             	movk	x16, #0x9999, lsl #32
+;;   4c:	 3000e8f2             	movk	x16, #0x4001, lsl #48
+;;   50:	 0002679e             	fmov	d0, x16
+;;   54:	 808300fc             	stur	d0, [x28, #8]
+;;   58:	 808340fc             	ldur	d0, [x28, #8]
+;;   5c:	 810341fc             	ldur	d1, [x28, #0x10]
+;;   60:	 2020601e             	fcmp	d1, d0
+;;   64:	 e0879f9a             	cset	x0, ls
+;;   68:	 ff630091             	add	sp, sp, #0x18
+;;   6c:	 fc030091             	mov	x28, sp
+;;   70:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   74:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   78:	 c0035fd6             	ret	
@@ -0,0 +1,80 @@
+;;! target = "aarch64"
+;;! consume_fuel = true
+
+(module
+    (func $callee (result i32)
+        (i32.const 1)
+    )
+    (func (result i32)
+        (call $callee)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 208140f8             	ldur	x0, [x9, #8]
+;;   20:	 008040f8             	ldur	x0, [x0, #8]
+;;   24:	 1f0000f1             	cmp	x0, #0
+;;   28:	 6b010054             	b.lt	#0x54
+;;   2c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   30:	 01824af8             	ldur	x1, [x16, #0xa8]
+;;   34:	 ff2300d1             	sub	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 890300f8             	stur	x9, [x28]
+;;   40:	 800340f8             	ldur	x0, [x28]
+;;   44:	 20003fd6             	blr	x1
+;;   48:	 898340f8             	ldur	x9, [x28, #8]
+;;   4c:	 ff230091             	add	sp, sp, #8
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 208140f8             	ldur	x0, [x9, #8]
+;;   58:	 018040f8             	ldur	x1, [x0, #8]
+;;   5c:	 21080091             	add	x1, x1, #2
+;;   60:	 018000f8             	stur	x1, [x0, #8]
+;;   64:	 300080d2             	mov	x16, #1
+;;   68:	 e003102a             	mov	w0, w16
+;;   6c:	 ff230091             	add	sp, sp, #8
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 208140f8             	ldur	x0, [x9, #8]
+;;   20:	 008040f8             	ldur	x0, [x0, #8]
+;;   24:	 1f0000f1             	cmp	x0, #0
+;;   28:	 6b010054             	b.lt	#0x54
+;;   2c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   30:	 01824af8             	ldur	x1, [x16, #0xa8]
+;;   34:	 ff2300d1             	sub	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 890300f8             	stur	x9, [x28]
+;;   40:	 800340f8             	ldur	x0, [x28]
+;;   44:	 20003fd6             	blr	x1
+;;   48:	 898340f8             	ldur	x9, [x28, #8]
+;;   4c:	 ff230091             	add	sp, sp, #8
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 208140f8             	ldur	x0, [x9, #8]
+;;   58:	 018040f8             	ldur	x1, [x0, #8]
+;;   5c:	 21080091             	add	x1, x1, #2
+;;   60:	 018000f8             	stur	x1, [x0, #8]
+;;   64:	 ff2300d1             	sub	sp, sp, #8
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 00000094             	bl	#0x6c
+;;   70:	 898340f8             	ldur	x9, [x28, #8]
+;;   74:	 ff230091             	add	sp, sp, #8
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 ff230091             	add	sp, sp, #8
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   88:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   8c:	 c0035fd6             	ret	
@@ -0,0 +1,47 @@
+;;! target = "aarch64"
+;;! consume_fuel = true
+
+(module
+    (func (param i32) (result i32)
+        (local.get 0)
+        (i32.const 1)
+        (i32.add)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 208140f8             	ldur	x0, [x9, #8]
+;;   24:	 008040f8             	ldur	x0, [x0, #8]
+;;   28:	 1f0000f1             	cmp	x0, #0
+;;   2c:	 eb010054             	b.lt	#0x68
+;;   30:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   34:	 01824af8             	ldur	x1, [x16, #0xa8]
+;;   38:	 ff2300d1             	sub	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 890300f8             	stur	x9, [x28]
+;;   44:	 ff2300d1             	sub	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 808340f8             	ldur	x0, [x28, #8]
+;;   50:	 20003fd6             	blr	x1
+;;   54:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   58:	 ff230091             	add	sp, sp, #8
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 ff230091             	add	sp, sp, #8
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   6c:	 00040011             	add	w0, w0, #1
+;;   70:	 218140f8             	ldur	x1, [x9, #8]
+;;   74:	 228040f8             	ldur	x2, [x1, #8]
+;;   78:	 42100091             	add	x2, x2, #4
+;;   7c:	 228000f8             	stur	x2, [x1, #8]
+;;   80:	 ff430091             	add	sp, sp, #0x10
+;;   84:	 fc030091             	mov	x28, sp
+;;   88:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   8c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   90:	 c0035fd6             	ret	
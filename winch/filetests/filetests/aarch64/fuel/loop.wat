@@ -0,0 +1,82 @@
+;;! target = "aarch64"
+;;! consume_fuel = true
+
+(module
+    (func (param i32) (result i32)
+        (loop
+            (local.get 0)
+            (i32.const 1)
+            (i32.sub)
+            (local.tee 0)
+            (br_if 0)
+        )
+        (local.get 0)
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 208140f8             	ldur	x0, [x9, #8]
+;;   24:	 008040f8             	ldur	x0, [x0, #8]
+;;   28:	 1f0000f1             	cmp	x0, #0
+;;   2c:	 eb010054             	b.lt	#0x68
+;;   30:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   34:	 01824af8             	ldur	x1, [x16, #0xa8]
+;;   38:	 ff2300d1             	sub	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 890300f8             	stur	x9, [x28]
+;;   44:	 ff2300d1             	sub	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 808340f8             	ldur	x0, [x28, #8]
+;;   50:	 20003fd6             	blr	x1
+;;   54:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   58:	 ff230091             	add	sp, sp, #8
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 ff230091             	add	sp, sp, #8
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 208140f8             	ldur	x0, [x9, #8]
+;;   6c:	 018040f8             	ldur	x1, [x0, #8]
+;;   70:	 21040091             	add	x1, x1, #1
+;;   74:	 018000f8             	stur	x1, [x0, #8]
+;;   78:	 208140f8             	ldur	x0, [x9, #8]
+;;   7c:	 008040f8             	ldur	x0, [x0, #8]
+;;   80:	 1f0000f1             	cmp	x0, #0
+;;   84:	 eb010054             	b.lt	#0xc0
+;;   88:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   8c:	 01824af8             	ldur	x1, [x16, #0xa8]
+;;   90:	 ff2300d1             	sub	sp, sp, #8
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 890300f8             	stur	x9, [x28]
+;;   9c:	 ff2300d1             	sub	sp, sp, #8
+;;   a0:	 fc030091             	mov	x28, sp
+;;   a4:	 808340f8             	ldur	x0, [x28, #8]
+;;   a8:	 20003fd6             	blr	x1
+;;   ac:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   b0:	 ff230091             	add	sp, sp, #8
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 ff230091             	add	sp, sp, #8
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   c4:	 00040051             	sub	w0, w0, #1
+;;   c8:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   cc:	 218140f8             	ldur	x1, [x9, #8]
+;;   d0:	 228040f8             	ldur	x2, [x1, #8]
+;;   d4:	 42140091             	add	x2, x2, #5
+;;   d8:	 228000f8             	stur	x2, [x1, #8]
+;;   dc:	 1f000071             	cmp	w0, #0
+;;   e0:	 c1fcff54             	b.ne	#0x78
+;;   e4:	 208140f8             	ldur	x0, [x9, #8]
+;;   e8:	 018040f8             	ldur	x1, [x0, #8]
+;;   ec:	 21040091             	add	x1, x1, #1
+;;   f0:	 018000f8             	stur	x1, [x0, #8]
+;;   f4:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   f8:	 ff430091             	add	sp, sp, #0x10
+;;   fc:	 fc030091             	mov	x28, sp
+;;  100:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  104:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  108:	 c0035fd6             	ret	
@@ -0,0 +1,31 @@
+;;! target = "aarch64"
+
+(module
+    (func (result i32)
+        i64.const 1
+        i32.wrap_i64
+        block
+        end
+    )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300080d2             	mov	x16, #1
+;;   20:	 e00310aa             	mov	x0, x16
+;;   24:	 e003002a             	mov	w0, w0
+;;   28:	 ff1300d1             	sub	sp, sp, #4
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 800300b8             	stur	w0, [x28]
+;;   34:	 800340b8             	ldur	w0, [x28]
+;;   38:	 ff130091             	add	sp, sp, #4
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 ff230091             	add	sp, sp, #8
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
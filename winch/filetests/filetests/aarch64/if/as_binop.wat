@@ -0,0 +1,84 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "as-binary-operand") (param i32 i32) (result i32)
+    (i32.mul
+      (if (result i32) (local.get 0)
+        (then (call $dummy) (i32.const 3))
+        (else (call $dummy) (i32.const -3))
+      )
+      (if (result i32) (local.get 1)
+        (then (call $dummy) (i32.const 4))
+        (else (call $dummy) (i32.const -5))
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   28:	 1f000071             	cmp	w0, #0
+;;   2c:	 c0000054             	b.eq	#0x44
+;;   30:	 00000094             	bl	#0x30
+;;   34:	 890340f8             	ldur	x9, [x28]
+;;   38:	 700080d2             	mov	x16, #3
+;;   3c:	 e003102a             	mov	w0, w16
+;;   40:	 05000014             	b	#0x54
+;;   44:	 00000094             	bl	#0x44
+;;   48:	 890340f8             	ldur	x9, [x28]
+;;   4c:	 50008012             	mov	w16, #-3
+;;   50:	 e003102a             	mov	w0, w16
+;;   54:	 818340b8             	ldur	w1, [x28, #8]
+;;   58:	 ff1300d1             	sub	sp, sp, #4
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 800300b8             	stur	w0, [x28]
+;;   64:	 3f000071             	cmp	w1, #0
+;;   68:	 40010054             	b.eq	#0x90
+;;   6c:	 ff3300d1             	sub	sp, sp, #0xc
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 00000094             	bl	#0x74
+;;   78:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   7c:	 ff330091             	add	sp, sp, #0xc
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 900080d2             	mov	x16, #4
+;;   88:	 e003102a             	mov	w0, w16
+;;   8c:	 09000014             	b	#0xb0
+;;   90:	 ff3300d1             	sub	sp, sp, #0xc
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 00000094             	bl	#0x98
+;;   9c:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   a0:	 ff330091             	add	sp, sp, #0xc
+;;   a4:	 fc030091             	mov	x28, sp
+;;   a8:	 90008012             	mov	w16, #-5
+;;   ac:	 e003102a             	mov	w0, w16
+;;   b0:	 810340b8             	ldur	w1, [x28]
+;;   b4:	 ff130091             	add	sp, sp, #4
+;;   b8:	 fc030091             	mov	x28, sp
+;;   bc:	 217c001b             	mul	w1, w1, w0
+;;   c0:	 e003012a             	mov	w0, w1
+;;   c4:	 ff430091             	add	sp, sp, #0x10
+;;   c8:	 fc030091             	mov	x28, sp
+;;   cc:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   d0:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   d4:	 c0035fd6             	ret	
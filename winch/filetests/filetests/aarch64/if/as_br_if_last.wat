@@ -0,0 +1,71 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "as-br_if-last") (param i32) (result i32)
+    (block (result i32)
+      (br_if 0
+        (i32.const 2)
+        (if (result i32) (local.get 0)
+          (then (call $dummy) (i32.const 1))
+          (else (call $dummy) (i32.const 0))
+        )
+      )
+      (return (i32.const 3))
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f000071             	cmp	w0, #0
+;;   28:	 c0000054             	b.eq	#0x40
+;;   2c:	 00000094             	bl	#0x2c
+;;   30:	 890340f8             	ldur	x9, [x28]
+;;   34:	 300080d2             	mov	x16, #1
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 05000014             	b	#0x50
+;;   40:	 00000094             	bl	#0x40
+;;   44:	 890340f8             	ldur	x9, [x28]
+;;   48:	 100080d2             	mov	x16, #0
+;;   4c:	 e003102a             	mov	w0, w16
+;;   50:	 ff1300d1             	sub	sp, sp, #4
+;;   54:	 fc030091             	mov	x28, sp
+;;   58:	 800300b8             	stur	w0, [x28]
+;;   5c:	 810340b8             	ldur	w1, [x28]
+;;   60:	 ff130091             	add	sp, sp, #4
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 500080d2             	mov	x16, #2
+;;   6c:	 e003102a             	mov	w0, w16
+;;   70:	 3f000071             	cmp	w1, #0
+;;   74:	 01010054             	b.ne	#0x94
+;;   78:	 ff1300d1             	sub	sp, sp, #4
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 800300b8             	stur	w0, [x28]
+;;   84:	 700080d2             	mov	x16, #3
+;;   88:	 e003102a             	mov	w0, w16
+;;   8c:	 ff130091             	add	sp, sp, #4
+;;   90:	 fc030091             	mov	x28, sp
+;;   94:	 ff430091             	add	sp, sp, #0x10
+;;   98:	 fc030091             	mov	x28, sp
+;;   9c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   a0:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   a4:	 c0035fd6             	ret	
@@ -0,0 +1,52 @@
+;;! target = "aarch64"
+(module
+  (func $dummy)
+  (func (export "as-test-operand") (param i32) (result i32)
+    (i32.eqz
+      (if (result i32) (local.get 0)
+        (then (call $dummy) (i32.const 13))
+        (else (call $dummy) (i32.const 0))
+      )
+    )
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 1f000071             	cmp	w0, #0
+;;   28:	 c0000054             	b.eq	#0x40
+;;   2c:	 00000094             	bl	#0x2c
+;;   30:	 890340f8             	ldur	x9, [x28]
+;;   34:	 b00180d2             	mov	x16, #0xd
+;;   38:	 e003102a             	mov	w0, w16
+;;   3c:	 05000014             	b	#0x50
+;;   40:	 00000094             	bl	#0x40
+;;   44:	 890340f8             	ldur	x9, [x28]
+;;   48:	 100080d2             	mov	x16, #0
+;;   4c:	 e003102a             	mov	w0, w16
+;;   50:	 1f000071             	cmp	w0, #0
+;;   54:	 e0179f9a             	cset	x0, eq
+;;   58:	 ff430091             	add	sp, sp, #0x10
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   64:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   68:	 c0035fd6             	ret	
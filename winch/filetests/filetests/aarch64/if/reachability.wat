@@ -0,0 +1,47 @@
+;;! target = "aarch64"
+(module
+  (func (;0;) (param i32) (result i32)
+    local.get 0
+    local.get 0
+    if (result i32)
+      i32.const 1
+        return
+      else
+        i32.const 2
+      end
+      i32.sub
+  )
+  (export "main" (func 0))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   24:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   28:	 ff1300d1             	sub	sp, sp, #4
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 900300b8             	stur	w16, [x28]
+;;   34:	 1f000071             	cmp	w0, #0
+;;   38:	 c0000054             	b.eq	#0x50
+;;   3c:	 300080d2             	mov	x16, #1
+;;   40:	 e003102a             	mov	w0, w16
+;;   44:	 ff130091             	add	sp, sp, #4
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 08000014             	b	#0x6c
+;;   50:	 500080d2             	mov	x16, #2
+;;   54:	 e003102a             	mov	w0, w16
+;;   58:	 810340b8             	ldur	w1, [x28]
+;;   5c:	 ff130091             	add	sp, sp, #4
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 2160204b             	sub	w1, w1, w0, uxtx
+;;   68:	 e003012a             	mov	w0, w1
+;;   6c:	 ff430091             	add	sp, sp, #0x10
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,32 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "") (param i32) (result i32)
+    local.get 0
+    i32.const 1
+    local.set 0
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   24:	 ff1300d1             	sub	sp, sp, #4
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 900300b8             	stur	w16, [x28]
+;;   30:	 300080d2             	mov	x16, #1
+;;   34:	 e003102a             	mov	w0, w16
+;;   38:	 800301b8             	stur	w0, [x28, #0x10]
+;;   3c:	 800340b8             	ldur	w0, [x28]
+;;   40:	 ff130091             	add	sp, sp, #4
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 ff430091             	add	sp, sp, #0x10
+;;   4c:	 fc030091             	mov	x28, sp
+;;   50:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   54:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   58:	 c0035fd6             	ret	
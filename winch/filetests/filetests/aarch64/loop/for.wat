@@ -0,0 +1,54 @@
+;;! target = "aarch64"
+(module
+  (func (export "for-") (param i64) (result i64)
+    (local i64 i64)
+    (local.set 1 (i64.const 1))
+    (local.set 2 (i64.const 2))
+    (block
+      (loop
+        (br_if 1 (i64.gt_u (local.get 2) (local.get 0)))
+        (local.set 1 (i64.mul (local.get 1) (local.get 2)))
+        (local.set 2 (i64.add (local.get 2) (i64.const 1)))
+        (br 0)
+      )
+    )
+    (local.get 1)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff8300d1             	sub	sp, sp, #0x20
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808301f8             	stur	x0, [x28, #0x18]
+;;   1c:	 100080d2             	mov	x16, #0
+;;   20:	 900301f8             	stur	x16, [x28, #0x10]
+;;   24:	 908300f8             	stur	x16, [x28, #8]
+;;   28:	 890300f8             	stur	x9, [x28]
+;;   2c:	 300080d2             	mov	x16, #1
+;;   30:	 e00310aa             	mov	x0, x16
+;;   34:	 800301f8             	stur	x0, [x28, #0x10]
+;;   38:	 500080d2             	mov	x16, #2
+;;   3c:	 e00310aa             	mov	x0, x16
+;;   40:	 808300f8             	stur	x0, [x28, #8]
+;;   44:	 808341f8             	ldur	x0, [x28, #0x18]
+;;   48:	 818340f8             	ldur	x1, [x28, #8]
+;;   4c:	 3f0000eb             	cmp	x1, x0
+;;   50:	 e1979f9a             	cset	x1, hi
+;;   54:	 3f000071             	cmp	w1, #0
+;;   58:	 21010054             	b.ne	#0x7c
+;;   5c:	 808340f8             	ldur	x0, [x28, #8]
+;;   60:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   64:	 217c009b             	mul	x1, x1, x0
+;;   68:	 810301f8             	stur	x1, [x28, #0x10]
+;;   6c:	 808340f8             	ldur	x0, [x28, #8]
+;;   70:	 00040091             	add	x0, x0, #1
+;;   74:	 808300f8             	stur	x0, [x28, #8]
+;;   78:	 f3ffff17             	b	#0x44
+;;   7c:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   80:	 ff830091             	add	sp, sp, #0x20
+;;   84:	 fc030091             	mov	x28, sp
+;;   88:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   8c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   90:	 c0035fd6             	ret	
@@ -0,0 +1,78 @@
+;;! target = "aarch64"
+
+(module
+  (func $dummy)
+  (func (export "multi") (result i32)
+    (loop (call $dummy) (call $dummy) (call $dummy) (call $dummy))
+    (loop (result i32) (call $dummy) (call $dummy) (i32.const 8) (call $dummy))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff2300d1             	sub	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 00000094             	bl	#0x24
+;;   28:	 898340f8             	ldur	x9, [x28, #8]
+;;   2c:	 ff230091             	add	sp, sp, #8
+;;   30:	 fc030091             	mov	x28, sp
+;;   34:	 ff2300d1             	sub	sp, sp, #8
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 00000094             	bl	#0x3c
+;;   40:	 898340f8             	ldur	x9, [x28, #8]
+;;   44:	 ff230091             	add	sp, sp, #8
+;;   48:	 fc030091             	mov	x28, sp
+;;   4c:	 ff2300d1             	sub	sp, sp, #8
+;;   50:	 fc030091             	mov	x28, sp
+;;   54:	 00000094             	bl	#0x54
+;;   58:	 898340f8             	ldur	x9, [x28, #8]
+;;   5c:	 ff230091             	add	sp, sp, #8
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 ff2300d1             	sub	sp, sp, #8
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 00000094             	bl	#0x6c
+;;   70:	 898340f8             	ldur	x9, [x28, #8]
+;;   74:	 ff230091             	add	sp, sp, #8
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 ff2300d1             	sub	sp, sp, #8
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 00000094             	bl	#0x84
+;;   88:	 898340f8             	ldur	x9, [x28, #8]
+;;   8c:	 ff230091             	add	sp, sp, #8
+;;   90:	 fc030091             	mov	x28, sp
+;;   94:	 ff2300d1             	sub	sp, sp, #8
+;;   98:	 fc030091             	mov	x28, sp
+;;   9c:	 00000094             	bl	#0x9c
+;;   a0:	 898340f8             	ldur	x9, [x28, #8]
+;;   a4:	 ff230091             	add	sp, sp, #8
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 ff2300d1             	sub	sp, sp, #8
+;;   b0:	 fc030091             	mov	x28, sp
+;;   b4:	 00000094             	bl	#0xb4
+;;   b8:	 898340f8             	ldur	x9, [x28, #8]
+;;   bc:	 ff230091             	add	sp, sp, #8
+;;   c0:	 fc030091             	mov	x28, sp
+;;   c4:	 100180d2             	mov	x16, #8
+;;   c8:	 e003102a             	mov	w0, w16
+;;   cc:	 ff230091             	add	sp, sp, #8
+;;   d0:	 fc030091             	mov	x28, sp
+;;   d4:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   d8:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   dc:	 c0035fd6             	ret	
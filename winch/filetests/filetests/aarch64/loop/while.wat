@@ -0,0 +1,48 @@
+;;! target = "aarch64"
+(module
+  (func (export "while-") (param i64) (result i64)
+    (local i64)
+    (local.set 1 (i64.const 1))
+    (block
+      (loop
+        (br_if 1 (i64.eqz (local.get 0)))
+        (local.set 1 (i64.mul (local.get 0) (local.get 1)))
+        (local.set 0 (i64.sub (local.get 0) (i64.const 1)))
+        (br 0)
+      )
+    )
+    (local.get 1)
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 800301f8             	stur	x0, [x28, #0x10]
+;;   1c:	 100080d2             	mov	x16, #0
+;;   20:	 908300f8             	stur	x16, [x28, #8]
+;;   24:	 890300f8             	stur	x9, [x28]
+;;   28:	 300080d2             	mov	x16, #1
+;;   2c:	 e00310aa             	mov	x0, x16
+;;   30:	 808300f8             	stur	x0, [x28, #8]
+;;   34:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   38:	 1f0000f1             	cmp	x0, #0
+;;   3c:	 e0179f9a             	cset	x0, eq
+;;   40:	 1f000071             	cmp	w0, #0
+;;   44:	 21010054             	b.ne	#0x68
+;;   48:	 808340f8             	ldur	x0, [x28, #8]
+;;   4c:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   50:	 217c009b             	mul	x1, x1, x0
+;;   54:	 818300f8             	stur	x1, [x28, #8]
+;;   58:	 800341f8             	ldur	x0, [x28, #0x10]
+;;   5c:	 000400d1             	sub	x0, x0, #1
+;;   60:	 800301f8             	stur	x0, [x28, #0x10]
+;;   64:	 f4ffff17             	b	#0x34
+;;   68:	 808340f8             	ldur	x0, [x28, #8]
+;;   6c:	 ff630091             	add	sp, sp, #0x18
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,29 @@
+;;! target = "aarch64"
+
+(module
+  (func (export "select-i64") (param i64 i64 i32) (result i64)
+    (select (local.get 0) (local.get 1) (local.get 2))
+  )
+)
+ 
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff8300d1             	sub	sp, sp, #0x20
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808301f8             	stur	x0, [x28, #0x18]
+;;   1c:	 810301f8             	stur	x1, [x28, #0x10]
+;;   20:	 82c300b8             	stur	w2, [x28, #0xc]
+;;   24:	 890300f8             	stur	x9, [x28]
+;;   28:	 80c340b8             	ldur	w0, [x28, #0xc]
+;;   2c:	 810341f8             	ldur	x1, [x28, #0x10]
+;;   30:	 828341f8             	ldur	x2, [x28, #0x18]
+;;   34:	 1f000071             	cmp	w0, #0
+;;   38:	 4110819a             	csel	x1, x2, x1, ne
+;;   3c:	 e00301aa             	mov	x0, x1
+;;   40:	 ff830091             	add	sp, sp, #0x20
+;;   44:	 fc030091             	mov	x28, sp
+;;   48:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   4c:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   50:	 c0035fd6             	ret	
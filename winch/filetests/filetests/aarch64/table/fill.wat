@@ -0,0 +1,148 @@
+;;! target = "aarch64"
+(module
+  (type $t0 (func))
+  (func $f1 (type $t0))
+  (func $f2 (type $t0))
+  (func $f3 (type $t0))
+
+  ;; Define two tables of funcref
+  (table $t1 3 funcref)
+  (table $t2 10 funcref)
+
+  ;; Initialize table $t1 with functions $f1, $f2, $f3
+  (elem (i32.const 0) $f1 $f2 $f3)
+
+  ;; Function to fill table $t1 using a function reference from table $t2
+  (func (export "fill") (param $i i32) (param $r i32) (param $n i32)
+    (local $ref funcref)
+    (local.set $ref (table.get $t1 (local.get $r)))
+    (table.fill $t2 (local.get $i) (local.get $ref) (local.get $n))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff8300d1             	sub	sp, sp, #0x20
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c301b8             	stur	w0, [x28, #0x1c]
+;;   1c:	 818301b8             	stur	w1, [x28, #0x18]
+;;   20:	 824301b8             	stur	w2, [x28, #0x14]
+;;   24:	 100080d2             	mov	x16, #0
+;;   28:	 900301b8             	stur	w16, [x28, #0x10]
+;;   2c:	 100080d2             	mov	x16, #0
+;;   30:	 908300f8             	stur	x16, [x28, #8]
+;;   34:	 890300f8             	stur	x9, [x28]
+;;   38:	 818341b8             	ldur	w1, [x28, #0x18]
+;;   3c:	 e20309aa             	mov	x2, x9
+;;   40:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   44:	 3f00036b             	cmp	w1, w3
+;;   48:	 a2080054             	b.hs	#0x15c
+;;   4c:	 30f07dd3             	lsl	x16, x1, #3
+;;   50:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   54:	 e40302aa             	mov	x4, x2
+;;   58:	 4260308b             	add	x2, x2, x16, uxtx
+;;   5c:	 3f00036b             	cmp	w1, w3
+;;   60:	 8220829a             	csel	x2, x4, x2, hs
+;;   64:	 400040f8             	ldur	x0, [x2]
+;;   68:	 1f0000f1             	cmp	x0, #0
+;;   6c:	 c1020054             	b.ne	#0xc4
+;;   70:	 ff2300d1             	sub	sp, sp, #8
+;;   74:	 fc030091             	mov	x28, sp
+;;   78:	 890300f8             	stur	x9, [x28]
+;;   7c:	 ff1300d1             	sub	sp, sp, #4
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 810300b8             	stur	w1, [x28]
+;;   88:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   8c:	 038244f8             	ldur	x3, [x16, #0x48]
+;;   90:	 ff1300d1             	sub	sp, sp, #4
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 808340f8             	ldur	x0, [x28, #8]
+;;   9c:	 100080d2             	mov	x16, #0
+;;   a0:	 e103102a             	mov	w1, w16
+;;   a4:	 824340b8             	ldur	w2, [x28, #4]
+;;   a8:	 60003fd6             	blr	x3
+;;   ac:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   b0:	 ff130091             	add	sp, sp, #4
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 ff330091             	add	sp, sp, #0xc
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 02000014             	b	#0xc8
+;;   c4:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   c8:	 80c300f8             	stur	x0, [x28, #0xc]
+;;   cc:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   d0:	 058246f8             	ldur	x5, [x16, #0x68]
+;;   d4:	 ff2300d1             	sub	sp, sp, #8
+;;   d8:	 fc030091             	mov	x28, sp
+;;   dc:	 890300f8             	stur	x9, [x28]
+;;   e0:	 904342b8             	ldur	w16, [x28, #0x24]
+;;   e4:	 ff1300d1             	sub	sp, sp, #4
+;;   e8:	 fc030091             	mov	x28, sp
+;;   ec:	 900300b8             	stur	w16, [x28]
+;;   f0:	 908341f8             	ldur	x16, [x28, #0x18]
+;;   f4:	 ff2300d1             	sub	sp, sp, #8
+;;   f8:	 fc030091             	mov	x28, sp
+;;   fc:	 900300f8             	stur	x16, [x28]
+;;  100:	 908342b8             	ldur	w16, [x28, #0x28]
+;;  104:	 ff1300d1             	sub	sp, sp, #4
+;;  108:	 fc030091             	mov	x28, sp
+;;  10c:	 900300b8             	stur	w16, [x28]
+;;  110:	 ff2300d1             	sub	sp, sp, #8
+;;  114:	 fc030091             	mov	x28, sp
+;;  118:	 808341f8             	ldur	x0, [x28, #0x18]
+;;  11c:	 300080d2             	mov	x16, #1
+;;  120:	 e103102a             	mov	w1, w16
+;;  124:	 824341b8             	ldur	w2, [x28, #0x14]
+;;  128:	 83c340f8             	ldur	x3, [x28, #0xc]
+;;  12c:	 848340b8             	ldur	w4, [x28, #8]
+;;  130:	 a0003fd6             	blr	x5
+;;  134:	 890342f8             	ldur	x9, [x28, #0x20]
+;;  138:	 ff230091             	add	sp, sp, #8
+;;  13c:	 fc030091             	mov	x28, sp
+;;  140:	 ff630091             	add	sp, sp, #0x18
+;;  144:	 fc030091             	mov	x28, sp
+;;  148:	 ff830091             	add	sp, sp, #0x20
+;;  14c:	 fc030091             	mov	x28, sp
+;;  150:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  154:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  158:	 c0035fd6             	ret	
+;;  15c:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
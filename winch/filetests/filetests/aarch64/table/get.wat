@@ -0,0 +1,72 @@
+;;! target = "aarch64"
+(module
+  (table $t3 3 funcref)
+  (elem (table $t3) (i32.const 1) func $dummy)
+  (func $dummy)
+  (func $f3 (export "get-funcref") (param $i i32) (result funcref)
+    (table.get $t3 (local.get $i))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 81c340b8             	ldur	w1, [x28, #0xc]
+;;   24:	 e20309aa             	mov	x2, x9
+;;   28:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   2c:	 3f00036b             	cmp	w1, w3
+;;   30:	 a2040054             	b.hs	#0xc4
+;;   34:	 30f07dd3             	lsl	x16, x1, #3
+;;   38:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   3c:	 e40302aa             	mov	x4, x2
+;;   40:	 4260308b             	add	x2, x2, x16, uxtx
+;;   44:	 3f00036b             	cmp	w1, w3
+;;   48:	 8220829a             	csel	x2, x4, x2, hs
+;;   4c:	 400040f8             	ldur	x0, [x2]
+;;   50:	 1f0000f1             	cmp	x0, #0
+;;   54:	 c1020054             	b.ne	#0xac
+;;   58:	 ff2300d1             	sub	sp, sp, #8
+;;   5c:	 fc030091             	mov	x28, sp
+;;   60:	 890300f8             	stur	x9, [x28]
+;;   64:	 ff1300d1             	sub	sp, sp, #4
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 810300b8             	stur	w1, [x28]
+;;   70:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   74:	 038244f8             	ldur	x3, [x16, #0x48]
+;;   78:	 ff1300d1             	sub	sp, sp, #4
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 808340f8             	ldur	x0, [x28, #8]
+;;   84:	 100080d2             	mov	x16, #0
+;;   88:	 e103102a             	mov	w1, w16
+;;   8c:	 824340b8             	ldur	w2, [x28, #4]
+;;   90:	 60003fd6             	blr	x3
+;;   94:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   98:	 ff130091             	add	sp, sp, #4
+;;   9c:	 fc030091             	mov	x28, sp
+;;   a0:	 ff330091             	add	sp, sp, #0xc
+;;   a4:	 fc030091             	mov	x28, sp
+;;   a8:	 02000014             	b	#0xb0
+;;   ac:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   b0:	 ff430091             	add	sp, sp, #0x10
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   bc:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   c0:	 c0035fd6             	ret	
+;;   c4:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
@@ -0,0 +1,41 @@
+;;! target = "aarch64"
+
+(module
+  (table $t1 0 funcref)
+
+  (func (export "grow-by-10") (param $r funcref) (result i32)
+    (table.grow $t1 (local.get $r) (i32.const 10))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 808300f8             	stur	x0, [x28, #8]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   24:	 040245f8             	ldur	x4, [x16, #0x50]
+;;   28:	 ff2300d1             	sub	sp, sp, #8
+;;   2c:	 fc030091             	mov	x28, sp
+;;   30:	 890300f8             	stur	x9, [x28]
+;;   34:	 900341f8             	ldur	x16, [x28, #0x10]
+;;   38:	 ff2300d1             	sub	sp, sp, #8
+;;   3c:	 fc030091             	mov	x28, sp
+;;   40:	 900300f8             	stur	x16, [x28]
+;;   44:	 808340f8             	ldur	x0, [x28, #8]
+;;   48:	 100080d2             	mov	x16, #0
+;;   4c:	 e103102a             	mov	w1, w16
+;;   50:	 500180d2             	mov	x16, #0xa
+;;   54:	 e203102a             	mov	w2, w16
+;;   58:	 830340f8             	ldur	x3, [x28]
+;;   5c:	 80003fd6             	blr	x4
+;;   60:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   64:	 ff430091             	add	sp, sp, #0x10
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 ff430091             	add	sp, sp, #0x10
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   78:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   7c:	 c0035fd6             	ret	
@@ -0,0 +1,360 @@
+;;! target = "aarch64"
+
+(module
+  (type (func (result i32)))  ;; type #0
+  (import "a" "ef0" (func (result i32)))    ;; index 0
+  (import "a" "ef1" (func (result i32)))
+  (import "a" "ef2" (func (result i32)))
+  (import "a" "ef3" (func (result i32)))
+  (import "a" "ef4" (func (result i32)))    ;; index 4
+  (table $t0 30 30 funcref)
+  (table $t1 30 30 funcref)
+  (elem (table $t0) (i32.const 2) func 3 1 4 1)
+  (elem funcref
+    (ref.func 2) (ref.func 7) (ref.func 1) (ref.func 8))
+  (elem (table $t0) (i32.const 12) func 7 5 2 3 6)
+  (elem funcref
+    (ref.func 5) (ref.func 9) (ref.func 2) (ref.func 7) (ref.func 6))
+  (func (result i32) (i32.const 5))  ;; index 5
+  (func (result i32) (i32.const 6))
+  (func (result i32) (i32.const 7))
+  (func (result i32) (i32.const 8))
+  (func (result i32) (i32.const 9))  ;; index 9
+  (func (export "test")
+    (table.init $t0 1 (i32.const 7) (i32.const 0) (i32.const 4))
+         (elem.drop 1)
+         (table.init $t0 3 (i32.const 15) (i32.const 1) (i32.const 3))
+         (elem.drop 3)
+         (table.copy $t0 0 (i32.const 20) (i32.const 15) (i32.const 5))
+         (table.copy $t0 0 (i32.const 21) (i32.const 29) (i32.const 1))
+         (table.copy $t0 0 (i32.const 24) (i32.const 10) (i32.const 1))
+         (table.copy $t0 0 (i32.const 13) (i32.const 11) (i32.const 4))
+         (table.copy $t0 0 (i32.const 19) (i32.const 20) (i32.const 5)))
+  (func (export "check") (param i32) (result i32)
+    (call_indirect $t0 (type 0) (local.get 0)))
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 b00080d2             	mov	x16, #5
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 d00080d2             	mov	x16, #6
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 f00080d2             	mov	x16, #7
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 100180d2             	mov	x16, #8
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 300180d2             	mov	x16, #9
+;;   20:	 e003102a             	mov	w0, w16
+;;   24:	 ff230091             	add	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   30:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   34:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   20:	 060241f8             	ldur	x6, [x16, #0x10]
+;;   24:	 ff2300d1             	sub	sp, sp, #8
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 890300f8             	stur	x9, [x28]
+;;   30:	 800340f8             	ldur	x0, [x28]
+;;   34:	 100080d2             	mov	x16, #0
+;;   38:	 e103102a             	mov	w1, w16
+;;   3c:	 300080d2             	mov	x16, #1
+;;   40:	 e203102a             	mov	w2, w16
+;;   44:	 f00080d2             	mov	x16, #7
+;;   48:	 e303102a             	mov	w3, w16
+;;   4c:	 100080d2             	mov	x16, #0
+;;   50:	 e403102a             	mov	w4, w16
+;;   54:	 900080d2             	mov	x16, #4
+;;   58:	 e503102a             	mov	w5, w16
+;;   5c:	 c0003fd6             	blr	x6
+;;   60:	 898340f8             	ldur	x9, [x28, #8]
+;;   64:	 ff230091             	add	sp, sp, #8
+;;   68:	 fc030091             	mov	x28, sp
+;;   6c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   70:	 028241f8             	ldur	x2, [x16, #0x18]
+;;   74:	 ff2300d1             	sub	sp, sp, #8
+;;   78:	 fc030091             	mov	x28, sp
+;;   7c:	 890300f8             	stur	x9, [x28]
+;;   80:	 800340f8             	ldur	x0, [x28]
+;;   84:	 300080d2             	mov	x16, #1
+;;   88:	 e103102a             	mov	w1, w16
+;;   8c:	 40003fd6             	blr	x2
+;;   90:	 898340f8             	ldur	x9, [x28, #8]
+;;   94:	 ff230091             	add	sp, sp, #8
+;;   98:	 fc030091             	mov	x28, sp
+;;   9c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   a0:	 060241f8             	ldur	x6, [x16, #0x10]
+;;   a4:	 ff2300d1             	sub	sp, sp, #8
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 890300f8             	stur	x9, [x28]
+;;   b0:	 800340f8             	ldur	x0, [x28]
+;;   b4:	 100080d2             	mov	x16, #0
+;;   b8:	 e103102a             	mov	w1, w16
+;;   bc:	 700080d2             	mov	x16, #3
+;;   c0:	 e203102a             	mov	w2, w16
+;;   c4:	 f00180d2             	mov	x16, #0xf
+;;   c8:	 e303102a             	mov	w3, w16
+;;   cc:	 300080d2             	mov	x16, #1
+;;   d0:	 e403102a             	mov	w4, w16
+;;   d4:	 700080d2             	mov	x16, #3
+;;   d8:	 e503102a             	mov	w5, w16
+;;   dc:	 c0003fd6             	blr	x6
+;;   e0:	 898340f8             	ldur	x9, [x28, #8]
+;;   e4:	 ff230091             	add	sp, sp, #8
+;;   e8:	 fc030091             	mov	x28, sp
+;;   ec:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   f0:	 028241f8             	ldur	x2, [x16, #0x18]
+;;   f4:	 ff2300d1             	sub	sp, sp, #8
+;;   f8:	 fc030091             	mov	x28, sp
+;;   fc:	 890300f8             	stur	x9, [x28]
+;;  100:	 800340f8             	ldur	x0, [x28]
+;;  104:	 700080d2             	mov	x16, #3
+;;  108:	 e103102a             	mov	w1, w16
+;;  10c:	 40003fd6             	blr	x2
+;;  110:	 898340f8             	ldur	x9, [x28, #8]
+;;  114:	 ff230091             	add	sp, sp, #8
+;;  118:	 fc030091             	mov	x28, sp
+;;  11c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  120:	 068240f8             	ldur	x6, [x16, #8]
+;;  124:	 ff2300d1             	sub	sp, sp, #8
+;;  128:	 fc030091             	mov	x28, sp
+;;  12c:	 890300f8             	stur	x9, [x28]
+;;  130:	 800340f8             	ldur	x0, [x28]
+;;  134:	 100080d2             	mov	x16, #0
+;;  138:	 e103102a             	mov	w1, w16
+;;  13c:	 100080d2             	mov	x16, #0
+;;  140:	 e203102a             	mov	w2, w16
+;;  144:	 900280d2             	mov	x16, #0x14
+;;  148:	 e303102a             	mov	w3, w16
+;;  14c:	 f00180d2             	mov	x16, #0xf
+;;  150:	 e403102a             	mov	w4, w16
+;;  154:	 b00080d2             	mov	x16, #5
+;;  158:	 e503102a             	mov	w5, w16
+;;  15c:	 c0003fd6             	blr	x6
+;;  160:	 898340f8             	ldur	x9, [x28, #8]
+;;  164:	 ff230091             	add	sp, sp, #8
+;;  168:	 fc030091             	mov	x28, sp
+;;  16c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  170:	 068240f8             	ldur	x6, [x16, #8]
+;;  174:	 ff2300d1             	sub	sp, sp, #8
+;;  178:	 fc030091             	mov	x28, sp
+;;  17c:	 890300f8             	stur	x9, [x28]
+;;  180:	 800340f8             	ldur	x0, [x28]
+;;  184:	 100080d2             	mov	x16, #0
+;;  188:	 e103102a             	mov	w1, w16
+;;  18c:	 100080d2             	mov	x16, #0
+;;  190:	 e203102a             	mov	w2, w16
+;;  194:	 b00280d2             	mov	x16, #0x15
+;;  198:	 e303102a             	mov	w3, w16
+;;  19c:	 b00380d2             	mov	x16, #0x1d
+;;  1a0:	 e403102a             	mov	w4, w16
+;;  1a4:	 300080d2             	mov	x16, #1
+;;  1a8:	 e503102a             	mov	w5, w16
+;;  1ac:	 c0003fd6             	blr	x6
+;;  1b0:	 898340f8             	ldur	x9, [x28, #8]
+;;  1b4:	 ff230091             	add	sp, sp, #8
+;;  1b8:	 fc030091             	mov	x28, sp
+;;  1bc:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  1c0:	 068240f8             	ldur	x6, [x16, #8]
+;;  1c4:	 ff2300d1             	sub	sp, sp, #8
+;;  1c8:	 fc030091             	mov	x28, sp
+;;  1cc:	 890300f8             	stur	x9, [x28]
+;;  1d0:	 800340f8             	ldur	x0, [x28]
+;;  1d4:	 100080d2             	mov	x16, #0
+;;  1d8:	 e103102a             	mov	w1, w16
+;;  1dc:	 100080d2             	mov	x16, #0
+;;  1e0:	 e203102a             	mov	w2, w16
+;;  1e4:	 100380d2             	mov	x16, #0x18
+;;  1e8:	 e303102a             	mov	w3, w16
+;;  1ec:	 500180d2             	mov	x16, #0xa
+;;  1f0:	 e403102a             	mov	w4, w16
+;;  1f4:	 300080d2             	mov	x16, #1
+;;  1f8:	 e503102a             	mov	w5, w16
+;;  1fc:	 c0003fd6             	blr	x6
+;;  200:	 898340f8             	ldur	x9, [x28, #8]
+;;  204:	 ff230091             	add	sp, sp, #8
+;;  208:	 fc030091             	mov	x28, sp
+;;  20c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  210:	 068240f8             	ldur	x6, [x16, #8]
+;;  214:	 ff2300d1             	sub	sp, sp, #8
+;;  218:	 fc030091             	mov	x28, sp
+;;  21c:	 890300f8             	stur	x9, [x28]
+;;  220:	 800340f8             	ldur	x0, [x28]
+;;  224:	 100080d2             	mov	x16, #0
+;;  228:	 e103102a             	mov	w1, w16
+;;  22c:	 100080d2             	mov	x16, #0
+;;  230:	 e203102a             	mov	w2, w16
+;;  234:	 b00180d2             	mov	x16, #0xd
+;;  238:	 e303102a             	mov	w3, w16
+;;  23c:	 700180d2             	mov	x16, #0xb
+;;  240:	 e403102a             	mov	w4, w16
+;;  244:	 900080d2             	mov	x16, #4
+;;  248:	 e503102a             	mov	w5, w16
+;;  24c:	 c0003fd6             	blr	x6
+;;  250:	 898340f8             	ldur	x9, [x28, #8]
+;;  254:	 ff230091             	add	sp, sp, #8
+;;  258:	 fc030091             	mov	x28, sp
+;;  25c:	 308143f8             	ldur	x16, [x9, #0x38]
+;;  260:	 068240f8             	ldur	x6, [x16, #8]
+;;  264:	 ff2300d1             	sub	sp, sp, #8
+;;  268:	 fc030091             	mov	x28, sp
+;;  26c:	 890300f8             	stur	x9, [x28]
+;;  270:	 800340f8             	ldur	x0, [x28]
+;;  274:	 100080d2             	mov	x16, #0
+;;  278:	 e103102a             	mov	w1, w16
+;;  27c:	 100080d2             	mov	x16, #0
+;;  280:	 e203102a             	mov	w2, w16
+;;  284:	 700280d2             	mov	x16, #0x13
+;;  288:	 e303102a             	mov	w3, w16
+;;  28c:	 900280d2             	mov	x16, #0x14
+;;  290:	 e403102a             	mov	w4, w16
+;;  294:	 b00080d2             	mov	x16, #5
+;;  298:	 e503102a             	mov	w5, w16
+;;  29c:	 c0003fd6             	blr	x6
+;;  2a0:	 898340f8             	ldur	x9, [x28, #8]
+;;  2a4:	 ff230091             	add	sp, sp, #8
+;;  2a8:	 fc030091             	mov	x28, sp
+;;  2ac:	 ff230091             	add	sp, sp, #8
+;;  2b0:	 fc030091             	mov	x28, sp
+;;  2b4:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  2b8:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  2bc:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 890300f8             	stur	x9, [x28]
+;;   20:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   24:	 ff1300d1             	sub	sp, sp, #4
+;;   28:	 fc030091             	mov	x28, sp
+;;   2c:	 900300b8             	stur	w16, [x28]
+;;   30:	 810340b8             	ldur	w1, [x28]
+;;   34:	 ff130091             	add	sp, sp, #4
+;;   38:	 fc030091             	mov	x28, sp
+;;   3c:	 e20309aa             	mov	x2, x9
+;;   40:	 43804fb8             	ldur	w3, [x2, #0xf8]
+;;   44:	 3f00036b             	cmp	w1, w3
+;;   48:	 82060054             	b.hs	#0x118
+;;   4c:	 30f07dd3             	lsl	x16, x1, #3
+;;   50:	 42004ff8             	ldur	x2, [x2, #0xf0]
+;;   54:	 e40302aa             	mov	x4, x2
+;;   58:	 4260308b             	add	x2, x2, x16, uxtx
+;;   5c:	 3f00036b             	cmp	w1, w3
+;;   60:	 8220829a             	csel	x2, x4, x2, hs
+;;   64:	 400040f8             	ldur	x0, [x2]
+;;   68:	 1f0000f1             	cmp	x0, #0
+;;   6c:	 c1020054             	b.ne	#0xc4
+;;   70:	 ff2300d1             	sub	sp, sp, #8
+;;   74:	 fc030091             	mov	x28, sp
+;;   78:	 890300f8             	stur	x9, [x28]
+;;   7c:	 ff1300d1             	sub	sp, sp, #4
+;;   80:	 fc030091             	mov	x28, sp
+;;   84:	 810300b8             	stur	w1, [x28]
+;;   88:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   8c:	 038244f8             	ldur	x3, [x16, #0x48]
+;;   90:	 ff1300d1             	sub	sp, sp, #4
+;;   94:	 fc030091             	mov	x28, sp
+;;   98:	 808340f8             	ldur	x0, [x28, #8]
+;;   9c:	 100080d2             	mov	x16, #0
+;;   a0:	 e103102a             	mov	w1, w16
+;;   a4:	 824340b8             	ldur	w2, [x28, #4]
+;;   a8:	 60003fd6             	blr	x3
+;;   ac:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   b0:	 ff130091             	add	sp, sp, #4
+;;   b4:	 fc030091             	mov	x28, sp
+;;   b8:	 ff330091             	add	sp, sp, #0xc
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 02000014             	b	#0xc8
+;;   c4:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   c8:	 a00200b4             	cbz	x0, #0x11c
+;;   cc:	 300144f8             	ldur	x16, [x9, #0x40]
+;;   d0:	 010240b8             	ldur	w1, [x16]
+;;   d4:	 028041b8             	ldur	w2, [x0, #0x18]
+;;   d8:	 3f00026b             	cmp	w1, w2
+;;   dc:	 21020054             	b.ne	#0x120
+;;   e0:	 ff2300d1             	sub	sp, sp, #8
+;;   e4:	 fc030091             	mov	x28, sp
+;;   e8:	 800300f8             	stur	x0, [x28]
+;;   ec:	 810340f8             	ldur	x1, [x28]
+;;   f0:	 ff230091             	add	sp, sp, #8
+;;   f4:	 fc030091             	mov	x28, sp
+;;   f8:	 220041f8             	ldur	x2, [x1, #0x10]
+;;   fc:	 40003fd6             	blr	x2
+;;  100:	 890340f8             	ldur	x9, [x28]
+;;  104:	 ff430091             	add	sp, sp, #0x10
+;;  108:	 fc030091             	mov	x28, sp
+;;  10c:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;  110:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  114:	 c0035fd6             	ret	
+;;  118:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  11c:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  120:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
@@ -0,0 +1,125 @@
+;;! target = "aarch64"
+
+
+(module
+  (table $t3 2 funcref)
+  (elem (table $t3) (i32.const 1) func $dummy)
+  (func $dummy)
+
+  (func (export "set-funcref") (param $i i32) (param $r funcref)
+    (table.set $t3 (local.get $i) (local.get $r))
+  )
+  (func (export "set-funcref-from") (param $i i32) (param $j i32)
+    (table.set $t3 (local.get $i) (table.get $t3 (local.get $j)))
+  )
+)
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff2300d1             	sub	sp, sp, #8
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 890300f8             	stur	x9, [x28]
+;;   1c:	 ff230091             	add	sp, sp, #8
+;;   20:	 fc030091             	mov	x28, sp
+;;   24:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   28:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   2c:	 c0035fd6             	ret	
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff6300d1             	sub	sp, sp, #0x18
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 804301b8             	stur	w0, [x28, #0x14]
+;;   1c:	 818300f8             	stur	x1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 808340f8             	ldur	x0, [x28, #8]
+;;   28:	 814341b8             	ldur	w1, [x28, #0x14]
+;;   2c:	 e20309aa             	mov	x2, x9
+;;   30:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   34:	 3f00036b             	cmp	w1, w3
+;;   38:	 c2010054             	b.hs	#0x70
+;;   3c:	 30f07dd3             	lsl	x16, x1, #3
+;;   40:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   44:	 e40302aa             	mov	x4, x2
+;;   48:	 4260308b             	add	x2, x2, x16, uxtx
+;;   4c:	 3f00036b             	cmp	w1, w3
+;;   50:	 8220829a             	csel	x2, x4, x2, hs
+;;   54:	 000040b2             	orr	x0, x0, #1
+;;   58:	 400000f8             	stur	x0, [x2]
+;;   5c:	 ff630091             	add	sp, sp, #0x18
+;;   60:	 fc030091             	mov	x28, sp
+;;   64:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   68:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;   6c:	 c0035fd6             	ret	
+;;   70:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;
+;;    0:	 fd7bbfa9             	stp	x29, x30, [sp, #-0x10]!
+;;    4:	 fd030091             	mov	x29, sp
+;;    8:	 fc0f1ff8             	str	x28, [sp, #-0x10]!
+;;    c:	 fc030091             	mov	x28, sp
+;;   10:	 ff4300d1             	sub	sp, sp, #0x10
+;;   14:	 fc030091             	mov	x28, sp
+;;   18:	 80c300b8             	stur	w0, [x28, #0xc]
+;;   1c:	 818300b8             	stur	w1, [x28, #8]
+;;   20:	 890300f8             	stur	x9, [x28]
+;;   24:	 818340b8             	ldur	w1, [x28, #8]
+;;   28:	 e20309aa             	mov	x2, x9
+;;   2c:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   30:	 3f00036b             	cmp	w1, w3
+;;   34:	 82060054             	b.hs	#0x104
+;;   38:	 30f07dd3             	lsl	x16, x1, #3
+;;   3c:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   40:	 e40302aa             	mov	x4, x2
+;;   44:	 4260308b             	add	x2, x2, x16, uxtx
+;;   48:	 3f00036b             	cmp	w1, w3
+;;   4c:	 8220829a             	csel	x2, x4, x2, hs
+;;   50:	 400040f8             	ldur	x0, [x2]
+;;   54:	 1f0000f1             	cmp	x0, #0
+;;   58:	 c1020054             	b.ne	#0xb0
+;;   5c:	 90c340b8             	ldur	w16, [x28, #0xc]
+;;   60:	 ff1300d1             	sub	sp, sp, #4
+;;   64:	 fc030091             	mov	x28, sp
+;;   68:	 900300b8             	stur	w16, [x28]
+;;   6c:	 ff2300d1             	sub	sp, sp, #8
+;;   70:	 fc030091             	mov	x28, sp
+;;   74:	 890300f8             	stur	x9, [x28]
+;;   78:	 ff1300d1             	sub	sp, sp, #4
+;;   7c:	 fc030091             	mov	x28, sp
+;;   80:	 810300b8             	stur	w1, [x28]
+;;   84:	 308143f8             	ldur	x16, [x9, #0x38]
+;;   88:	 038244f8             	ldur	x3, [x16, #0x48]
+;;   8c:	 804340f8             	ldur	x0, [x28, #4]
+;;   90:	 100080d2             	mov	x16, #0
+;;   94:	 e103102a             	mov	w1, w16
+;;   98:	 820340b8             	ldur	w2, [x28]
+;;   9c:	 60003fd6             	blr	x3
+;;   a0:	 890341f8             	ldur	x9, [x28, #0x10]
+;;   a4:	 ff330091             	add	sp, sp, #0xc
+;;   a8:	 fc030091             	mov	x28, sp
+;;   ac:	 02000014             	b	#0xb4
+;;   b0:	 00f87f92             	and	x0, x0, #0xfffffffffffffffe
+;;   b4:	 810340b8             	ldur	w1, [x28]
+;;   b8:	 ff130091             	add	sp, sp, #4
+;;   bc:	 fc030091             	mov	x28, sp
+;;   c0:	 e20309aa             	mov	x2, x9
+;;   c4:	 438045b8             	ldur	w3, [x2, #0x58]
+;;   c8:	 3f00036b             	cmp	w1, w3
+;;   cc:	 e2010054             	b.hs	#0x108
+;;   d0:	 30f07dd3             	lsl	x16, x1, #3
+;;   d4:	 420045f8             	ldur	x2, [x2, #0x50]
+;;   d8:	 e40302aa             	mov	x4, x2
+;;   dc:	 4260308b             	add	x2, x2, x16, uxtx
+;;   e0:	 3f00036b             	cmp	w1, w3
+;;   e4:	 8220829a             	csel	x2, x4, x2, hs
+;;   e8:	 000040b2             	orr	x0, x0, #1
+;;   ec:	 400000f8             	stur	x0, [x2]
+;;   f0:	 ff430091             	add	sp, sp, #0x10
+;;   f4:	 fc030091             	mov	x28, sp
+;;   f8:	 fc0741f8             	ldr	x28, [sp], #0x10
+;;   fc:	 fd7bc1a8             	ldp	x29, x30, [sp], #0x10
+;;  100:	 c0035fd6             	ret	
+;;  104:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
+;;  108:	 1fc10000             	.byte	0x1f, 0xc1, 0x00, 0x00
@@ -48,12 +48,12 @@
 ;;   3d:	 890424               	mov	dword ptr [rsp], eax
 ;;   40:	 b900000000           	mov	ecx, 0
 ;;   45:	 4c89f2               	mov	rdx, r14
-;;   48:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   48:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   4b:	 39d9                 	cmp	ecx, ebx
 ;;   4d:	 0f8348010000         	jae	0x19b
 ;;   53:	 4189cb               	mov	r11d, ecx
 ;;   56:	 4d6bdb08             	imul	r11, r11, 8
-;;   5a:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   5a:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   5e:	 4889d6               	mov	rsi, rdx
 ;;   61:	 4c01da               	add	rdx, r11
 ;;   64:	 39d9                 	cmp	ecx, ebx
@@ -96,12 +96,12 @@
 ;;   e8:	 890c24               	mov	dword ptr [rsp], ecx
 ;;   eb:	 b900000000           	mov	ecx, 0
 ;;   f0:	 4c89f2               	mov	rdx, r14
-;;   f3:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   f3:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   f6:	 39d9                 	cmp	ecx, ebx
 ;;   f8:	 0f83a3000000         	jae	0x1a1
 ;;   fe:	 4189cb               	mov	r11d, ecx
 ;;  101:	 4d6bdb08             	imul	r11, r11, 8
-;;  105:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;  105:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;  109:	 4889d6               	mov	rsi, rdx
 ;;  10c:	 4c01da               	add	rdx, r11
 ;;  10f:	 39d9                 	cmp	ecx, ebx
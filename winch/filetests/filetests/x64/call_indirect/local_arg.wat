@@ -94,12 +94,12 @@
 ;;   1e:	 44891c24             	mov	dword ptr [rsp], r11d
 ;;   22:	 b900000000           	mov	ecx, 0
 ;;   27:	 4c89f2               	mov	rdx, r14
-;;   2a:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   2a:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   2d:	 39d9                 	cmp	ecx, ebx
 ;;   2f:	 0f8387000000         	jae	0xbc
 ;;   35:	 4189cb               	mov	r11d, ecx
 ;;   38:	 4d6bdb08             	imul	r11, r11, 8
-;;   3c:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   3c:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   40:	 4889d6               	mov	rsi, rdx
 ;;   43:	 4c01da               	add	rdx, r11
 ;;   46:	 39d9                 	cmp	ecx, ebx
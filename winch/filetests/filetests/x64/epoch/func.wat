@@ -0,0 +1,28 @@
+;;! target = "x86_64"
+;;! epoch_interruption = true
+
+(module
+    (func (result i32)
+        (i32.const 1)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 498b4618             	mov	rax, qword ptr [r14 + 0x18]
+;;   10:	 488b00               	mov	rax, qword ptr [rax]
+;;   13:	 498b4e08             	mov	rcx, qword ptr [r14 + 8]
+;;   17:	 488b4910             	mov	rcx, qword ptr [rcx + 0x10]
+;;   1b:	 4839c8               	cmp	rax, rcx
+;;   1e:	 0f8217000000         	jb	0x3b
+;;   24:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   28:	 498b8bb0000000       	mov	rcx, qword ptr [r11 + 0xb0]
+;;   2f:	 4156                 	push	r14
+;;   31:	 488b3c24             	mov	rdi, qword ptr [rsp]
+;;   35:	 ffd1                 	call	rcx
+;;   37:	 4883c408             	add	rsp, 8
+;;   3b:	 b801000000           	mov	eax, 1
+;;   40:	 4883c408             	add	rsp, 8
+;;   44:	 5d                   	pop	rbp
+;;   45:	 c3                   	ret	
@@ -0,0 +1,57 @@
+;;! target = "x86_64"
+;;! epoch_interruption = true
+
+(module
+    (func (param i32) (result i32)
+        (loop
+            (local.get 0)
+            (i32.const 1)
+            (i32.sub)
+            (local.tee 0)
+            (br_if 0)
+        )
+        (local.get 0)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c893424             	mov	qword ptr [rsp], r14
+;;   10:	 498b4618             	mov	rax, qword ptr [r14 + 0x18]
+;;   14:	 488b00               	mov	rax, qword ptr [rax]
+;;   17:	 498b4e08             	mov	rcx, qword ptr [r14 + 8]
+;;   1b:	 488b4910             	mov	rcx, qword ptr [rcx + 0x10]
+;;   1f:	 4839c8               	cmp	rax, rcx
+;;   22:	 0f8220000000         	jb	0x48
+;;   28:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   2c:	 498b8bb0000000       	mov	rcx, qword ptr [r11 + 0xb0]
+;;   33:	 4156                 	push	r14
+;;   35:	 4883ec08             	sub	rsp, 8
+;;   39:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   3e:	 ffd1                 	call	rcx
+;;   40:	 4883c408             	add	rsp, 8
+;;   44:	 4883c408             	add	rsp, 8
+;;   48:	 498b4618             	mov	rax, qword ptr [r14 + 0x18]
+;;   4c:	 488b00               	mov	rax, qword ptr [rax]
+;;   4f:	 498b4e08             	mov	rcx, qword ptr [r14 + 8]
+;;   53:	 488b4910             	mov	rcx, qword ptr [rcx + 0x10]
+;;   57:	 4839c8               	cmp	rax, rcx
+;;   5a:	 0f8220000000         	jb	0x80
+;;   60:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   64:	 498b8bb0000000       	mov	rcx, qword ptr [r11 + 0xb0]
+;;   6b:	 4156                 	push	r14
+;;   6d:	 4883ec08             	sub	rsp, 8
+;;   71:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   76:	 ffd1                 	call	rcx
+;;   78:	 4883c408             	add	rsp, 8
+;;   7c:	 4883c408             	add	rsp, 8
+;;   80:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   84:	 83e801               	sub	eax, 1
+;;   87:	 8944240c             	mov	dword ptr [rsp + 0xc], eax
+;;   8b:	 85c0                 	test	eax, eax
+;;   8d:	 0f85b5ffffff         	jne	0x48
+;;   93:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   97:	 4883c410             	add	rsp, 0x10
+;;   9b:	 5d                   	pop	rbp
+;;   9c:	 c3                   	ret	
@@ -0,0 +1,18 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (result f32)
+        (local.get 0)
+        (f32x4.extract_lane 3)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 f30f7f442408         	movdqu	xmmword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   18:	 660f70c003           	pshufd	xmm0, xmm0, 3
+;;   1d:	 4883c418             	add	rsp, 0x18
+;;   21:	 5d                   	pop	rbp
+;;   22:	 c3                   	ret	
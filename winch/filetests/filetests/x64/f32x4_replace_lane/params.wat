@@ -0,0 +1,23 @@
+;;! target = "x86_64"
+;;! flags = ["has_sse41"]
+
+(module
+    (func (param v128) (param f32) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (f32x4.replace_lane 2)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 f30f114c240c         	movss	dword ptr [rsp + 0xc], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f1044240c         	movss	xmm0, dword ptr [rsp + 0xc]
+;;   1e:	 f30f6f4c2410         	movdqu	xmm1, xmmword ptr [rsp + 0x10]
+;;   24:	 660f3a21c820         	insertps	xmm1, xmm0, 0x20
+;;   2a:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   2e:	 4883c420             	add	rsp, 0x20
+;;   32:	 5d                   	pop	rbp
+;;   33:	 c3                   	ret	
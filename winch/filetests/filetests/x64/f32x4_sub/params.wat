@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param v128) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (f32x4.sub)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec28             	sub	rsp, 0x28
+;;    8:	 f30f7f442418         	movdqu	xmmword ptr [rsp + 0x18], xmm0
+;;    e:	 f30f7f4c2408         	movdqu	xmmword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   1e:	 f30f6f4c2418         	movdqu	xmm1, xmmword ptr [rsp + 0x18]
+;;   24:	 0f5cc8               	subps	xmm1, xmm0
+;;   27:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   2b:	 4883c428             	add	rsp, 0x28
+;;   2f:	 5d                   	pop	rbp
+;;   30:	 c3                   	ret	
@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param f64) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (f64x2.replace_lane 1)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 f20f114c2408         	movsd	qword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f20f10442408         	movsd	xmm0, qword ptr [rsp + 8]
+;;   1e:	 f30f6f4c2410         	movdqu	xmm1, xmmword ptr [rsp + 0x10]
+;;   24:	 0f16c8               	movlhps	xmm1, xmm0
+;;   27:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   2b:	 4883c420             	add	rsp, 0x20
+;;   2f:	 5d                   	pop	rbp
+;;   30:	 c3                   	ret	
@@ -0,0 +1,58 @@
+;;! target = "x86_64"
+;;! consume_fuel = true
+
+(module
+    (func $callee (result i32)
+        (i32.const 1)
+    )
+    (func (result i32)
+        (call $callee)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   10:	 488b4008             	mov	rax, qword ptr [rax + 8]
+;;   14:	 4883f800             	cmp	rax, 0
+;;   18:	 0f8c17000000         	jl	0x35
+;;   1e:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   22:	 498b83a8000000       	mov	rax, qword ptr [r11 + 0xa8]
+;;   29:	 4156                 	push	r14
+;;   2b:	 488b3c24             	mov	rdi, qword ptr [rsp]
+;;   2f:	 ffd0                 	call	rax
+;;   31:	 4883c408             	add	rsp, 8
+;;   35:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   39:	 488b4808             	mov	rcx, qword ptr [rax + 8]
+;;   3d:	 4883c102             	add	rcx, 2
+;;   41:	 48894808             	mov	qword ptr [rax + 8], rcx
+;;   45:	 b801000000           	mov	eax, 1
+;;   4a:	 4883c408             	add	rsp, 8
+;;   4e:	 5d                   	pop	rbp
+;;   4f:	 c3                   	ret	
+;;
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   10:	 488b4008             	mov	rax, qword ptr [rax + 8]
+;;   14:	 4883f800             	cmp	rax, 0
+;;   18:	 0f8c17000000         	jl	0x35
+;;   1e:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   22:	 498b83a8000000       	mov	rax, qword ptr [r11 + 0xa8]
+;;   29:	 4156                 	push	r14
+;;   2b:	 488b3c24             	mov	rdi, qword ptr [rsp]
+;;   2f:	 ffd0                 	call	rax
+;;   31:	 4883c408             	add	rsp, 8
+;;   35:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   39:	 488b4808             	mov	rcx, qword ptr [rax + 8]
+;;   3d:	 4883c102             	add	rcx, 2
+;;   41:	 48894808             	mov	qword ptr [rax + 8], rcx
+;;   45:	 4883ec08             	sub	rsp, 8
+;;   49:	 e800000000           	call	0x4e
+;;   4e:	 4883c408             	add	rsp, 8
+;;   52:	 4883c408             	add	rsp, 8
+;;   56:	 5d                   	pop	rbp
+;;   57:	 c3                   	ret	
@@ -0,0 +1,36 @@
+;;! target = "x86_64"
+;;! consume_fuel = true
+
+(module
+    (func (param i32) (result i32)
+        (local.get 0)
+        (i32.const 1)
+        (i32.add)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c893424             	mov	qword ptr [rsp], r14
+;;   10:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   14:	 488b4008             	mov	rax, qword ptr [rax + 8]
+;;   18:	 4883f800             	cmp	rax, 0
+;;   1c:	 0f8c20000000         	jl	0x42
+;;   22:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   26:	 498b83a8000000       	mov	rax, qword ptr [r11 + 0xa8]
+;;   2d:	 4156                 	push	r14
+;;   2f:	 4883ec08             	sub	rsp, 8
+;;   33:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   38:	 ffd0                 	call	rax
+;;   3a:	 4883c408             	add	rsp, 8
+;;   3e:	 4883c408             	add	rsp, 8
+;;   42:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   46:	 83c001               	add	eax, 1
+;;   49:	 498b4e08             	mov	rcx, qword ptr [r14 + 8]
+;;   4d:	 488b5108             	mov	rdx, qword ptr [rcx + 8]
+;;   51:	 4883c204             	add	rdx, 4
+;;   55:	 48895108             	mov	qword ptr [rcx + 8], rdx
+;;   59:	 4883c410             	add	rsp, 0x10
+;;   5d:	 5d                   	pop	rbp
+;;   5e:	 c3                   	ret	
@@ -0,0 +1,65 @@
+;;! target = "x86_64"
+;;! consume_fuel = true
+
+(module
+    (func (param i32) (result i32)
+        (loop
+            (local.get 0)
+            (i32.const 1)
+            (i32.sub)
+            (local.tee 0)
+            (br_if 0)
+        )
+        (local.get 0)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c893424             	mov	qword ptr [rsp], r14
+;;   10:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   14:	 488b4008             	mov	rax, qword ptr [rax + 8]
+;;   18:	 4883f800             	cmp	rax, 0
+;;   1c:	 0f8c20000000         	jl	0x42
+;;   22:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   26:	 498b83a8000000       	mov	rax, qword ptr [r11 + 0xa8]
+;;   2d:	 4156                 	push	r14
+;;   2f:	 4883ec08             	sub	rsp, 8
+;;   33:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   38:	 ffd0                 	call	rax
+;;   3a:	 4883c408             	add	rsp, 8
+;;   3e:	 4883c408             	add	rsp, 8
+;;   42:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   46:	 488b4808             	mov	rcx, qword ptr [rax + 8]
+;;   4a:	 4883c101             	add	rcx, 1
+;;   4e:	 48894808             	mov	qword ptr [rax + 8], rcx
+;;   52:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   56:	 488b4008             	mov	rax, qword ptr [rax + 8]
+;;   5a:	 4883f800             	cmp	rax, 0
+;;   5e:	 0f8c20000000         	jl	0x84
+;;   64:	 4d8b5e38             	mov	r11, qword ptr [r14 + 0x38]
+;;   68:	 498b83a8000000       	mov	rax, qword ptr [r11 + 0xa8]
+;;   6f:	 4156                 	push	r14
+;;   71:	 4883ec08             	sub	rsp, 8
+;;   75:	 488b7c2408           	mov	rdi, qword ptr [rsp + 8]
+;;   7a:	 ffd0                 	call	rax
+;;   7c:	 4883c408             	add	rsp, 8
+;;   80:	 4883c408             	add	rsp, 8
+;;   84:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   88:	 83e801               	sub	eax, 1
+;;   8b:	 8944240c             	mov	dword ptr [rsp + 0xc], eax
+;;   8f:	 498b4e08             	mov	rcx, qword ptr [r14 + 8]
+;;   93:	 488b5108             	mov	rdx, qword ptr [rcx + 8]
+;;   97:	 4883c205             	add	rdx, 5
+;;   9b:	 48895108             	mov	qword ptr [rcx + 8], rdx
+;;   9f:	 85c0                 	test	eax, eax
+;;   a1:	 0f85abffffff         	jne	0x52
+;;   a7:	 498b4608             	mov	rax, qword ptr [r14 + 8]
+;;   ab:	 488b4808             	mov	rcx, qword ptr [rax + 8]
+;;   af:	 4883c101             	add	rcx, 1
+;;   b3:	 48894808             	mov	qword ptr [rax + 8], rcx
+;;   b7:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   bb:	 4883c410             	add	rsp, 0x10
+;;   bf:	 5d                   	pop	rbp
+;;   c0:	 c3                   	ret	
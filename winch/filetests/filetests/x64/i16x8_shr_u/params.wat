@@ -0,0 +1,23 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param i32) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i16x8.shr_u)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;   12:	 4c893424             	mov	qword ptr [rsp], r14
+;;   16:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   1a:	 f30f6f442410         	movdqu	xmm0, xmmword ptr [rsp + 0x10]
+;;   20:	 83e00f               	and	eax, 0xf
+;;   23:	 66440f6ef8           	movd	xmm15, eax
+;;   28:	 66410fd1c7           	psrlw	xmm0, xmm15
+;;   2d:	 4883c420             	add	rsp, 0x20
+;;   31:	 5d                   	pop	rbp
+;;   32:	 c3                   	ret	
@@ -0,0 +1,35 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param v128) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i32x4.lt_u)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec28             	sub	rsp, 0x28
+;;    8:	 f30f7f442418         	movdqu	xmmword ptr [rsp + 0x18], xmm0
+;;    e:	 f30f7f4c2408         	movdqu	xmmword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   1e:	 f30f6f4c2418         	movdqu	xmm1, xmmword ptr [rsp + 0x18]
+;;   24:	 f3440f6f3d23000000   	
+;; 				movdqu	xmm15, xmmword ptr [rip + 0x23]
+;;   2d:	 66410fefcf           	pxor	xmm1, xmm15
+;;   32:	 66410fefc7           	pxor	xmm0, xmm15
+;;   37:	 660f66c1             	pcmpgtd	xmm0, xmm1
+;;   3b:	 660f6fc8             	movdqa	xmm1, xmm0
+;;   3f:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   43:	 4883c428             	add	rsp, 0x28
+;;   47:	 5d                   	pop	rbp
+;;   48:	 c3                   	ret	
+;;   49:	 0000                 	add	byte ptr [rax], al
+;;   4b:	 0000                 	add	byte ptr [rax], al
+;;   4d:	 0000                 	add	byte ptr [rax], al
+;;   4f:	 0000                 	add	byte ptr [rax], al
+;;   51:	 0000                 	add	byte ptr [rax], al
+;;   53:	 800000               	add	byte ptr [rax], 0
+;;   56:	 008000000080         	add	byte ptr [rax - 0x80000000], al
+;;   5c:	 0000                 	add	byte ptr [rax], al
@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (result v128)
+        (local.get 0)
+        (i32.const 3)
+        (i32x4.shl)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 f30f7f442408         	movdqu	xmmword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 b803000000           	mov	eax, 3
+;;   17:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   1d:	 83e01f               	and	eax, 0x1f
+;;   20:	 66440f6ef8           	movd	xmm15, eax
+;;   25:	 66410ff2c7           	pslld	xmm0, xmm15
+;;   2a:	 4883c418             	add	rsp, 0x18
+;;   2e:	 5d                   	pop	rbp
+;;   2f:	 c3                   	ret	
@@ -0,0 +1,19 @@
+;;! target = "x86_64"
+
+(module
+    (func (param i32) (result v128)
+        (local.get 0)
+        (i32x4.splat)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c893424             	mov	qword ptr [rsp], r14
+;;   10:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   14:	 660f6ec0             	movd	xmm0, eax
+;;   18:	 660f70c000           	pshufd	xmm0, xmm0, 0
+;;   1d:	 4883c410             	add	rsp, 0x10
+;;   21:	 5d                   	pop	rbp
+;;   22:	 c3                   	ret	
@@ -0,0 +1,31 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param v128) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i64x2.mul)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec28             	sub	rsp, 0x28
+;;    8:	 f30f7f442418         	movdqu	xmmword ptr [rsp + 0x18], xmm0
+;;    e:	 f30f7f4c2408         	movdqu	xmmword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   1e:	 f30f6f4c2418         	movdqu	xmm1, xmmword ptr [rsp + 0x18]
+;;   24:	 660f6fd1             	movdqa	xmm2, xmm1
+;;   28:	 660f73d220           	psrlq	xmm2, 0x20
+;;   2d:	 660ff4d0             	pmuludq	xmm2, xmm0
+;;   31:	 66440f6ff8           	movdqa	xmm15, xmm0
+;;   36:	 66410f73d720         	psrlq	xmm15, 0x20
+;;   3c:	 66440ff4f9           	pmuludq	xmm15, xmm1
+;;   41:	 66410fd4d7           	paddq	xmm2, xmm15
+;;   46:	 660f73f220           	psllq	xmm2, 0x20
+;;   4b:	 660ff4c8             	pmuludq	xmm1, xmm0
+;;   4f:	 660fd4ca             	paddq	xmm1, xmm2
+;;   53:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   57:	 4883c428             	add	rsp, 0x28
+;;   5b:	 5d                   	pop	rbp
+;;   5c:	 c3                   	ret	
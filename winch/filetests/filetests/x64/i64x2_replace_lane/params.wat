@@ -0,0 +1,22 @@
+;;! target = "x86_64"
+;;! flags = ["has_sse41"]
+
+(module
+    (func (param v128) (param i64) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i64x2.replace_lane 1)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 48897c2408           	mov	qword ptr [rsp + 8], rdi
+;;   13:	 4c893424             	mov	qword ptr [rsp], r14
+;;   17:	 488b442408           	mov	rax, qword ptr [rsp + 8]
+;;   1c:	 f30f6f442410         	movdqu	xmm0, xmmword ptr [rsp + 0x10]
+;;   22:	 66480f3a22c001       	pinsrq	xmm0, rax, 1
+;;   29:	 4883c420             	add	rsp, 0x20
+;;   2d:	 5d                   	pop	rbp
+;;   2e:	 c3                   	ret	
@@ -0,0 +1,36 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param i32) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i64x2.shr_s)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;   12:	 4c893424             	mov	qword ptr [rsp], r14
+;;   16:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   1a:	 f30f6f442410         	movdqu	xmm0, xmmword ptr [rsp + 0x10]
+;;   20:	 83e03f               	and	eax, 0x3f
+;;   23:	 f30f6f0d25000000     	movdqu	xmm1, xmmword ptr [rip + 0x25]
+;;   2b:	 66440f6ef8           	movd	xmm15, eax
+;;   30:	 66410fd3cf           	psrlq	xmm1, xmm15
+;;   35:	 66410fd3c7           	psrlq	xmm0, xmm15
+;;   3a:	 660fefc1             	pxor	xmm0, xmm1
+;;   3e:	 660ffbc1             	psubq	xmm0, xmm1
+;;   42:	 4883c420             	add	rsp, 0x20
+;;   46:	 5d                   	pop	rbp
+;;   47:	 c3                   	ret	
+;;   48:	 0000                 	add	byte ptr [rax], al
+;;   4a:	 0000                 	add	byte ptr [rax], al
+;;   4c:	 0000                 	add	byte ptr [rax], al
+;;   4e:	 0000                 	add	byte ptr [rax], al
+;;   50:	 0000                 	add	byte ptr [rax], al
+;;   52:	 0000                 	add	byte ptr [rax], al
+;;   54:	 0000                 	add	byte ptr [rax], al
+;;   56:	 008000000000         	add	byte ptr [rax], al
+;;   5c:	 0000                 	add	byte ptr [rax], al
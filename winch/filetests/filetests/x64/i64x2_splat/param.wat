@@ -0,0 +1,19 @@
+;;! target = "x86_64"
+
+(module
+    (func (param i64) (result v128)
+        (local.get 0)
+        (i64x2.splat)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 48897c2408           	mov	qword ptr [rsp + 8], rdi
+;;    d:	 4c893424             	mov	qword ptr [rsp], r14
+;;   11:	 488b442408           	mov	rax, qword ptr [rsp + 8]
+;;   16:	 66480f6ec0           	movq	xmm0, rax
+;;   1b:	 660f70c044           	pshufd	xmm0, xmm0, 0x44
+;;   20:	 4883c410             	add	rsp, 0x10
+;;   24:	 5d                   	pop	rbp
+;;   25:	 c3                   	ret	
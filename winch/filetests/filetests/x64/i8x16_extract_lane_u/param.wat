@@ -0,0 +1,19 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (result i32)
+        (local.get 0)
+        (i8x16.extract_lane_u 4)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 f30f7f442408         	movdqu	xmmword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   18:	 660fc5c002           	pextrw	eax, xmm0, 2
+;;   1d:	 81e0ff000000         	and	eax, 0xff
+;;   23:	 4883c418             	add	rsp, 0x18
+;;   27:	 5d                   	pop	rbp
+;;   28:	 c3                   	ret	
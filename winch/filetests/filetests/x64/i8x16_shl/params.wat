@@ -0,0 +1,32 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (param i32) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i8x16.shl)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec20             	sub	rsp, 0x20
+;;    8:	 f30f7f442410         	movdqu	xmmword ptr [rsp + 0x10], xmm0
+;;    e:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;   12:	 4c893424             	mov	qword ptr [rsp], r14
+;;   16:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   1a:	 f30f6f442410         	movdqu	xmm0, xmmword ptr [rsp + 0x10]
+;;   20:	 83e007               	and	eax, 7
+;;   23:	 83c008               	add	eax, 8
+;;   26:	 66440f6ef8           	movd	xmm15, eax
+;;   2b:	 660f6fc8             	movdqa	xmm1, xmm0
+;;   2f:	 660f60c9             	punpcklbw	xmm1, xmm1
+;;   33:	 660f68c0             	punpckhbw	xmm0, xmm0
+;;   37:	 66410ff1cf           	psllw	xmm1, xmm15
+;;   3c:	 660f71d108           	psrlw	xmm1, 8
+;;   41:	 66410ff1c7           	psllw	xmm0, xmm15
+;;   46:	 660f71d008           	psrlw	xmm0, 8
+;;   4b:	 660f67c8             	packuswb	xmm1, xmm0
+;;   4f:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   53:	 4883c420             	add	rsp, 0x20
+;;   57:	 5d                   	pop	rbp
+;;   58:	 c3                   	ret	
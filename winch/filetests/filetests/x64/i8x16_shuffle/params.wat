@@ -0,0 +1,30 @@
+;;! target = "x86_64"
+;;! flags = ["has_ssse3"]
+
+(module
+    (func (param v128) (param v128) (result v128)
+        (local.get 0)
+        (local.get 1)
+        (i8x16.shuffle 0 17 2 19 4 21 6 23 8 25 10 27 12 29 14 31)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec28             	sub	rsp, 0x28
+;;    8:	 f30f7f442418         	movdqu	xmmword ptr [rsp + 0x18], xmm0
+;;    e:	 f30f7f4c2408         	movdqu	xmmword ptr [rsp + 8], xmm1
+;;   14:	 4c893424             	mov	qword ptr [rsp], r14
+;;   18:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   1e:	 f30f6f4c2418         	movdqu	xmm1, xmmword ptr [rsp + 0x18]
+;;   24:	 f3440f6f3d23000000   	
+;; 				movdqu	xmm15, xmmword ptr [rip + 0x23]
+;;   2d:	 66410f3800cf         	pshufb	xmm1, xmm15
+;;   33:	 f3440f6f3d24000000   	
+;; 				movdqu	xmm15, xmmword ptr [rip + 0x24]
+;;   3c:	 66410f3800c7         	pshufb	xmm0, xmm15
+;;   42:	 660febc8             	por	xmm1, xmm0
+;;   46:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   4a:	 4883c428             	add	rsp, 0x28
+;;   4e:	 5d                   	pop	rbp
+;;   4f:	 c3                   	ret	
+;;   50:	 008002800480         	add	byte ptr [rax - 0x7ffb7ffe], al
@@ -0,0 +1,21 @@
+;;! target = "x86_64"
+
+(module
+    (func (param i32) (result v128)
+        (local.get 0)
+        (i8x16.splat)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec10             	sub	rsp, 0x10
+;;    8:	 897c240c             	mov	dword ptr [rsp + 0xc], edi
+;;    c:	 4c893424             	mov	qword ptr [rsp], r14
+;;   10:	 8b44240c             	mov	eax, dword ptr [rsp + 0xc]
+;;   14:	 660f6ec0             	movd	xmm0, eax
+;;   18:	 660f60c0             	punpcklbw	xmm0, xmm0
+;;   1c:	 f20f70c000           	pshuflw	xmm0, xmm0, 0
+;;   21:	 660f70c000           	pshufd	xmm0, xmm0, 0
+;;   26:	 4883c410             	add	rsp, 0x10
+;;   2a:	 5d                   	pop	rbp
+;;   2b:	 c3                   	ret	
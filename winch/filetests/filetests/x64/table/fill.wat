@@ -55,12 +55,12 @@
 ;;   25:	 4c893424             	mov	qword ptr [rsp], r14
 ;;   29:	 8b4c2418             	mov	ecx, dword ptr [rsp + 0x18]
 ;;   2d:	 4c89f2               	mov	rdx, r14
-;;   30:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   30:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   33:	 39d9                 	cmp	ecx, ebx
 ;;   35:	 0f83b5000000         	jae	0xf0
 ;;   3b:	 4189cb               	mov	r11d, ecx
 ;;   3e:	 4d6bdb08             	imul	r11, r11, 8
-;;   42:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   42:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   46:	 4889d6               	mov	rsi, rdx
 ;;   49:	 4c01da               	add	rdx, r11
 ;;   4c:	 39d9                 	cmp	ecx, ebx
@@ -24,12 +24,12 @@
 ;;    c:	 4c893424             	mov	qword ptr [rsp], r14
 ;;   10:	 8b4c240c             	mov	ecx, dword ptr [rsp + 0xc]
 ;;   14:	 4c89f2               	mov	rdx, r14
-;;   17:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   17:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   1a:	 39d9                 	cmp	ecx, ebx
 ;;   1c:	 0f835f000000         	jae	0x81
 ;;   22:	 4189cb               	mov	r11d, ecx
 ;;   25:	 4d6bdb08             	imul	r11, r11, 8
-;;   29:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   29:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   2d:	 4889d6               	mov	rsi, rdx
 ;;   30:	 4c01da               	add	rdx, r11
 ;;   33:	 39d9                 	cmp	ecx, ebx
@@ -188,12 +188,12 @@
 ;;   1d:	 8b0c24               	mov	ecx, dword ptr [rsp]
 ;;   20:	 4883c404             	add	rsp, 4
 ;;   24:	 4c89f2               	mov	rdx, r14
-;;   27:	 8b9af0000000         	mov	ebx, dword ptr [rdx + 0xf0]
+;;   27:	 8b9af8000000         	mov	ebx, dword ptr [rdx + 0xf8]
 ;;   2d:	 39d9                 	cmp	ecx, ebx
 ;;   2f:	 0f8385000000         	jae	0xba
 ;;   35:	 4189cb               	mov	r11d, ecx
 ;;   38:	 4d6bdb08             	imul	r11, r11, 8
-;;   3c:	 488b92e8000000       	mov	rdx, qword ptr [rdx + 0xe8]
+;;   3c:	 488b92f0000000       	mov	rdx, qword ptr [rdx + 0xf0]
 ;;   43:	 4889d6               	mov	rsi, rdx
 ;;   46:	 4c01da               	add	rdx, r11
 ;;   49:	 39d9                 	cmp	ecx, ebx
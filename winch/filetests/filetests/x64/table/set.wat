@@ -31,12 +31,12 @@
 ;;   15:	 488b442408           	mov	rax, qword ptr [rsp + 8]
 ;;   1a:	 8b4c2414             	mov	ecx, dword ptr [rsp + 0x14]
 ;;   1e:	 4c89f2               	mov	rdx, r14
-;;   21:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   21:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   24:	 39d9                 	cmp	ecx, ebx
 ;;   26:	 0f8324000000         	jae	0x50
 ;;   2c:	 4189cb               	mov	r11d, ecx
 ;;   2f:	 4d6bdb08             	imul	r11, r11, 8
-;;   33:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   33:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   37:	 4889d6               	mov	rsi, rdx
 ;;   3a:	 4c01da               	add	rdx, r11
 ;;   3d:	 39d9                 	cmp	ecx, ebx
@@ -56,12 +56,12 @@
 ;;   10:	 4c893424             	mov	qword ptr [rsp], r14
 ;;   14:	 8b4c2408             	mov	ecx, dword ptr [rsp + 8]
 ;;   18:	 4c89f2               	mov	rdx, r14
-;;   1b:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   1b:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   1e:	 39d9                 	cmp	ecx, ebx
 ;;   20:	 0f8396000000         	jae	0xbc
 ;;   26:	 4189cb               	mov	r11d, ecx
 ;;   29:	 4d6bdb08             	imul	r11, r11, 8
-;;   2d:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   2d:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   31:	 4889d6               	mov	rsi, rdx
 ;;   34:	 4c01da               	add	rdx, r11
 ;;   37:	 39d9                 	cmp	ecx, ebx
@@ -87,12 +87,12 @@
 ;;   83:	 8b0c24               	mov	ecx, dword ptr [rsp]
 ;;   86:	 4883c404             	add	rsp, 4
 ;;   8a:	 4c89f2               	mov	rdx, r14
-;;   8d:	 8b5a50               	mov	ebx, dword ptr [rdx + 0x50]
+;;   8d:	 8b5a58               	mov	ebx, dword ptr [rdx + 0x58]
 ;;   90:	 39d9                 	cmp	ecx, ebx
 ;;   92:	 0f8326000000         	jae	0xbe
 ;;   98:	 4189cb               	mov	r11d, ecx
 ;;   9b:	 4d6bdb08             	imul	r11, r11, 8
-;;   9f:	 488b5248             	mov	rdx, qword ptr [rdx + 0x48]
+;;   9f:	 488b5250             	mov	rdx, qword ptr [rdx + 0x50]
 ;;   a3:	 4889d6               	mov	rsi, rdx
 ;;   a6:	 4c01da               	add	rdx, r11
 ;;   a9:	 39d9                 	cmp	ecx, ebx
@@ -9,7 +9,7 @@
 ;;    4:	 4883ec08             	sub	rsp, 8
 ;;    8:	 4c893424             	mov	qword ptr [rsp], r14
 ;;    c:	 4d89f3               	mov	r11, r14
-;;    f:	 418b4350             	mov	eax, dword ptr [r11 + 0x50]
+;;    f:	 418b4358             	mov	eax, dword ptr [r11 + 0x58]
 ;;   13:	 4883c408             	add	rsp, 8
 ;;   17:	 5d                   	pop	rbp
 ;;   18:	 c3                   	ret	
@@ -0,0 +1,26 @@
+;;! target = "x86_64"
+
+(module
+    (func (result v128)
+        (v128.const i32x4 1 2 3 4)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec08             	sub	rsp, 8
+;;    8:	 4c893424             	mov	qword ptr [rsp], r14
+;;    c:	 f30f6f050c000000     	movdqu	xmm0, xmmword ptr [rip + 0xc]
+;;   14:	 4883c408             	add	rsp, 8
+;;   18:	 5d                   	pop	rbp
+;;   19:	 c3                   	ret	
+;;   1a:	 0000                 	add	byte ptr [rax], al
+;;   1c:	 0000                 	add	byte ptr [rax], al
+;;   1e:	 0000                 	add	byte ptr [rax], al
+;;   20:	 0100                 	add	dword ptr [rax], eax
+;;   22:	 0000                 	add	byte ptr [rax], al
+;;   24:	 0200                 	add	al, byte ptr [rax]
+;;   26:	 0000                 	add	byte ptr [rax], al
+;;   28:	 0300                 	add	eax, dword ptr [rax]
+;;   2a:	 0000                 	add	byte ptr [rax], al
+;;   2c:	 0400                 	add	al, 0
+;;   2e:	 0000                 	add	byte ptr [rax], al
@@ -0,0 +1,35 @@
+;;! target = "x86_64"
+
+(module
+    (func (result v128)
+        (local v128)
+        (v128.const i64x2 -1 0)
+        (local.set 0)
+        (v128.const i8x16 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16)
+        (local.get 0)
+        (i8x16.add)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 4531db               	xor	r11d, r11d
+;;    b:	 4c895c2410           	mov	qword ptr [rsp + 0x10], r11
+;;   10:	 4c895c2408           	mov	qword ptr [rsp + 8], r11
+;;   15:	 4c893424             	mov	qword ptr [rsp], r14
+;;   19:	 f30f6f052f000000     	movdqu	xmm0, xmmword ptr [rip + 0x2f]
+;;   21:	 f30f7f442408         	movdqu	xmmword ptr [rsp + 8], xmm0
+;;   27:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   2d:	 f30f6f0d2b000000     	movdqu	xmm1, xmmword ptr [rip + 0x2b]
+;;   35:	 660ffcc8             	paddb	xmm1, xmm0
+;;   39:	 660f6fc1             	movdqa	xmm0, xmm1
+;;   3d:	 4883c418             	add	rsp, 0x18
+;;   41:	 5d                   	pop	rbp
+;;   42:	 c3                   	ret	
+;;   43:	 0000                 	add	byte ptr [rax], al
+;;   45:	 0000                 	add	byte ptr [rax], al
+;;   47:	 0000                 	add	byte ptr [rax], al
+;;   49:	 0000                 	add	byte ptr [rax], al
+;;   4b:	 0000                 	add	byte ptr [rax], al
+;;   4d:	 0000                 	add	byte ptr [rax], al
+;;   4f:	 00ff                 	add	bh, bh
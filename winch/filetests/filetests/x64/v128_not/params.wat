@@ -0,0 +1,19 @@
+;;! target = "x86_64"
+
+(module
+    (func (param v128) (result v128)
+        (local.get 0)
+        (v128.not)
+    )
+)
+;;    0:	 55                   	push	rbp
+;;    1:	 4889e5               	mov	rbp, rsp
+;;    4:	 4883ec18             	sub	rsp, 0x18
+;;    8:	 f30f7f442408         	movdqu	xmmword ptr [rsp + 8], xmm0
+;;    e:	 4c893424             	mov	qword ptr [rsp], r14
+;;   12:	 f30f6f442408         	movdqu	xmm0, xmmword ptr [rsp + 8]
+;;   18:	 66450f76ff           	pcmpeqd	xmm15, xmm15
+;;   1d:	 66410fefc7           	pxor	xmm0, xmm15
+;;   22:	 4883c418             	add	rsp, 0x18
+;;   26:	 5d                   	pop	rbp
+;;   27:	 c3                   	ret	
@@ -22,6 +22,8 @@ mod test {
     struct TestConfig {
         target: String,
         flags: Option<Vec<String>>,
+        consume_fuel: Option<bool>,
+        epoch_interruption: Option<bool>,
     }
 
     /// A helper function to parse the test configuration from the top of the file.
@@ -104,7 +106,9 @@ mod test {
         let mut validator = Validator::new();
         let parser = WasmParser::new(0);
         let mut types = Default::default();
-        let tunables = Tunables::default();
+        let mut tunables = Tunables::default();
+        tunables.consume_fuel = config.consume_fuel.unwrap_or(false);
+        tunables.epoch_interruption = config.epoch_interruption.unwrap_or(false);
         let mut translation = ModuleEnvironment::new(&tunables, &mut validator, &mut types)
             .translate(parser, &wasm)
             .context("Failed to translate WebAssembly module")
@@ -114,7 +118,7 @@ mod test {
 
         let binding = body_inputs
             .into_iter()
-            .map(|func| compile(&isa, &types, &translation, func).join("\n"))
+            .map(|func| compile(&isa, &types, &translation, &tunables, func).join("\n"))
             .collect::<Vec<String>>()
             .join("\n\n");
         let actual = binding.as_str();
@@ -149,6 +153,7 @@ mod test {
         isa: &Box<dyn TargetIsa>,
         module_types: &ModuleTypesBuilder,
         translation: &ModuleTranslation,
+        tunables: &Tunables,
         f: (DefinedFuncIndex, FunctionBodyData<'_>),
     ) -> Vec<String> {
         let module = &translation.module;
@@ -168,6 +173,7 @@ mod test {
                 &body,
                 translation,
                 module_types,
+                tunables,
                 &mut builtins,
                 &mut validator,
             )